@@ -642,6 +642,101 @@ pub struct LineDiffHunk {
     pub new_count: u32,
 }
 
+/// How one region of a three-way merge (`mergeThreeWay`) was settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, rename_all = "lowercase")]
+pub enum MergeRegionKind {
+    /// Neither side touched these base lines.
+    Unchanged,
+    /// Only ours changed them; the merge takes ours.
+    Ours,
+    /// Only theirs changed them; the merge takes theirs.
+    Theirs,
+    /// Both sides made the same change; the merge takes it once.
+    Both,
+    /// The sides changed them differently; the merge writes conflict
+    /// markers around both versions.
+    Conflict,
+}
+
+/// One region of a three-way merge. Regions tile all four texts in
+/// order: each starts where the previous one ended in base, ours,
+/// theirs and the merged text alike. Lines follow the
+/// `computeLineDiff` convention (0-based, `\n`-terminated segments).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct MergeRegion {
+    pub kind: MergeRegionKind,
+    /// First line of the region in base.
+    pub base_start: u32,
+    pub base_count: u32,
+    /// First line of the region in ours.
+    pub ours_start: u32,
+    pub ours_count: u32,
+    /// First line of the region in theirs.
+    pub theirs_start: u32,
+    pub theirs_count: u32,
+    /// First line of the region in the merged text. For a conflict this
+    /// spans the whole marker block.
+    pub merged_start: u32,
+    pub merged_count: u32,
+}
+
+/// Result of `mergeThreeWay`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct MergeResult {
+    /// The merged text, with every conflict written diff3-style:
+    /// `<<<<<<< ours`, `||||||| base`, `=======`, `>>>>>>> theirs`.
+    pub merged: String,
+    pub regions: Vec<MergeRegion>,
+    /// Number of `Conflict` regions; 0 means the merge is clean.
+    pub conflicts: u32,
+}
+
+/// How a file differs between the two trees of `compareDirectories`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, rename_all = "lowercase")]
+pub enum DirDiffStatus {
+    /// Only in the right tree.
+    Added,
+    /// Only in the left tree.
+    Removed,
+    /// In both trees, with different contents.
+    Modified,
+}
+
+/// One file that differs between two directory trees.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct DirDiffEntry {
+    /// Path relative to both roots, `/`-separated.
+    pub path: String,
+    pub status: DirDiffStatus,
+    /// Lines only in the right file (every line of an added file).
+    pub added: u32,
+    /// Lines only in the left file (every line of a removed file).
+    pub removed: u32,
+    /// Either side is not text; `added` and `removed` are 0.
+    pub binary: bool,
+}
+
+/// Result of `compareDirectories`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct DirDiff {
+    /// Differing files, sorted by path.
+    pub entries: Vec<DirDiffEntry>,
+    /// Files present in both trees with identical contents.
+    pub identical: u32,
+}
+
 /// Information about the viewport
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
@@ -1356,6 +1451,27 @@ pub struct CompositeHunk {
     pub ops: Option<String>,
 }
 
+/// A run of aligned rows for a composite buffer with any number of panes.
+///
+/// Hunks only describe two panes (old/new); sections describe each pane,
+/// which is what a three-pane merge view needs. Sections are listed in
+/// order and each covers the next `count` lines of every pane. Line `i`
+/// of each pane shares row `i` of the section; a pane with fewer lines
+/// is padded with blank rows.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export, rename = "TsCompositeSection")]
+pub struct CompositeSection {
+    /// `[start, count]` lines (0-indexed) of each pane, in `sources` order
+    // Not `[usize; 2]`: rquickjs_serde can't decode fixed-size arrays.
+    #[ts(type = "Array<[number, number]>")]
+    pub lines: Vec<Vec<usize>>,
+    /// Row style: "context", "addition", "deletion" or "modification".
+    /// Every section but a context one starts with a hunk header row, so
+    /// hunk navigation stops at it.
+    pub kind: String,
+}
+
 /// Options for creating a composite buffer (used by plugin API)
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(deny_unknown_fields)]
@@ -1374,6 +1490,11 @@ pub struct CreateCompositeBufferOptions {
     /// Diff hunks for alignment (optional)
     #[serde(default)]
     pub hunks: Option<Vec<CompositeHunk>>,
+    /// Aligned sections for any number of panes (optional). Takes the
+    /// place of `hunks` when both are given.
+    #[serde(default)]
+    #[ts(optional)]
+    pub sections: Option<Vec<CompositeSection>>,
     /// When set, the first render will scroll to center the Nth hunk (0-indexed).
     /// This avoids timing issues with imperative scroll commands that depend on
     /// render-created state (viewport dimensions, view state).
@@ -4366,6 +4487,8 @@ pub enum PluginCommand {
         sources: Vec<CompositeSourceConfig>,
         /// Diff hunks for line alignment (optional)
         hunks: Option<Vec<CompositeHunk>>,
+        /// Aligned sections for any number of panes (optional)
+        sections: Option<Vec<CompositeSection>>,
         /// When set, first render scrolls to center this hunk (0-indexed)
         initial_focus_hunk: Option<usize>,
         /// Request ID for async response
@@ -4378,6 +4501,12 @@ pub enum PluginCommand {
         hunks: Vec<CompositeHunk>,
     },
 
+    /// Replace a composite buffer's alignment with aligned sections
+    UpdateCompositeSections {
+        buffer_id: BufferId,
+        sections: Vec<CompositeSection>,
+    },
+
     /// Close a composite buffer
     CloseCompositeBuffer { buffer_id: BufferId },
 
@@ -4517,6 +4646,71 @@ pub enum PluginCommand {
         request_id: u64,
     },
 
+    /// Get a buffer's undo tree; resolves with an `UndoHistory`
+    GetUndoHistory {
+        buffer_id: BufferId,
+        request_id: u64,
+    },
+
+    /// Get a buffer's text as of an undo state (see `GetUndoHistory`);
+    /// resolves with the text
+    GetUndoStateText {
+        buffer_id: BufferId,
+        state: u64,
+        request_id: u64,
+    },
+
+    /// Rewrite whole lines across files; resolves with a `LineEditResult`.
+    /// Open buffers are edited in memory as one undoable step each; other
+    /// files are patched on disk through the active filesystem.
+    ApplyLineEdits {
+        edits: Vec<LineEdit>,
+        request_id: u64,
+    },
+
+    /// Move a buffer to an undo state, on whatever branch of the undo tree
+    /// it is. The buffer must be the active one.
+    UndoToState { buffer_id: BufferId, state: u64 },
+
+    /// Find a tree-sitter text object (e.g. `"function.inner"`) around a
+    /// byte offset, or the next one after it; resolves with a
+    /// `TextObjectSpan` or `null`
+    GetTextObjectRange {
+        buffer_id: BufferId,
+        position: usize,
+        object: String,
+        request_id: u64,
+    },
+
+    /// Prepare an LSP call or type hierarchy (`kind` is `"call"` or
+    /// `"type"`) for the symbol at a byte offset; resolves with
+    /// `HierarchyItem`s
+    PrepareHierarchy {
+        kind: String,
+        buffer_id: BufferId,
+        position: usize,
+        request_id: u64,
+    },
+
+    /// Fetch one level of an LSP hierarchy around an item returned by
+    /// `PrepareHierarchy` (`direction` is `"incoming"`, `"outgoing"`,
+    /// `"supertypes"` or `"subtypes"`); resolves with `HierarchyItem`s
+    ExpandHierarchy {
+        direction: String,
+        #[ts(type = "HierarchyItem")]
+        item: JsonValue,
+        request_id: u64,
+    },
+
+    /// Send a request to the active debug session and return the raw
+    /// response body (DAP)
+    DebugRequest {
+        command: String,
+        #[ts(type = "any")]
+        arguments: Option<JsonValue>,
+        request_id: u64,
+    },
+
     /// Set the internal clipboard content
     SetClipboard { text: String },
 
@@ -4942,7 +5136,8 @@ pub enum PluginCommand {
     RegisterGrammar {
         /// Language identifier (e.g., "elixir", "zig")
         language: String,
-        /// Path to the grammar file (.sublime-syntax or .tmLanguage)
+        /// Path to the grammar file (.sublime-syntax or .tmLanguage), or to a
+        /// compiled tree-sitter parser (.so, .dylib, .dll) for a built-in language
         grammar_path: String,
         /// File extensions to associate with this grammar (e.g., ["ex", "exs"])
         extensions: Vec<String>,
@@ -5154,6 +5349,13 @@ pub enum PluginCommand {
         /// such a buffer, so the host searches its piece-tree content directly
         /// and emits matches carrying this buffer id. 0 means "no such buffer".
         source_buffer_id: usize,
+        /// Match `pattern` as a tree-sitter code pattern with metavariables
+        /// instead of text. `fixed_string`, `case_sensitive` and
+        /// `whole_words` are ignored.
+        structural: bool,
+        /// Structural rewrite template; each match carries its rendered
+        /// replacement. Empty means no rewrite.
+        rewrite: String,
         /// Handle ID — key into the shared `SearchHandleRegistry`
         handle_id: u64,
     },
@@ -5172,6 +5374,9 @@ pub enum PluginCommand {
         matches: Vec<(usize, usize)>,
         /// Replacement text
         replacement: String,
+        /// Per-match replacement texts, parallel to `matches`. Overrides
+        /// `replacement` when non-empty.
+        replacements: Vec<String>,
         /// Callback ID for async response
         callback_id: JsCallbackId,
     },
@@ -5684,6 +5889,110 @@ pub struct TsHighlightSpan {
     pub italic: bool,
 }
 
+/// A buffer's undo tree, as returned by `getUndoHistory`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UndoHistory {
+    /// State the buffer is in (0 = before the first recorded edit)
+    #[ts(type = "number")]
+    pub current: u64,
+    /// State the buffer was last saved in, if still known
+    #[ts(type = "number | null")]
+    pub saved: Option<u64>,
+    /// Every state, oldest first
+    pub states: Vec<UndoHistoryState>,
+}
+
+/// One state of a buffer's undo tree
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UndoHistoryState {
+    /// State number, in order of creation (1-based)
+    #[ts(type = "number")]
+    pub seq: u64,
+    /// State this one was reached from (0 = the start of the history)
+    #[ts(type = "number")]
+    pub parent: u64,
+    /// When the change was made (milliseconds since epoch)
+    #[ts(type = "number")]
+    pub timestamp: u64,
+    /// Short description of the change, e.g. `Insert "foo"`
+    pub label: String,
+    /// Number of edits the state groups together
+    pub edits: u32,
+}
+
+/// One line to rewrite with `applyLineEdits`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct LineEdit {
+    /// File the line is in
+    pub file: String,
+    /// Line number (1-based, as reported by grep)
+    pub line: u32,
+    /// What the line said when the edit was made, without its line ending;
+    /// a line that no longer says this is reported as a conflict
+    pub expected: String,
+    /// New contents of the line, without its line ending
+    pub text: String,
+}
+
+/// A line `applyLineEdits` left alone because it changed since the search
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct LineEditConflict {
+    pub file: String,
+    pub line: u32,
+    /// What the line says now (`null` if the file is shorter or unreadable)
+    pub actual: Option<String>,
+}
+
+/// Outcome of `applyLineEdits`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct LineEditResult {
+    /// Lines rewritten
+    pub applied: u32,
+    /// Files touched, open buffers included
+    pub files: u32,
+    /// Lines skipped because they no longer match `expected`
+    pub conflicts: Vec<LineEditConflict>,
+}
+
+/// Byte range of a text object, as returned by `getTextObjectRange`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TextObjectSpan {
+    /// Start byte offset
+    pub start: usize,
+    /// End byte offset (exclusive)
+    pub end: usize,
+}
+
+/// One node of an LSP call or type hierarchy
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HierarchyItem {
+    /// Symbol name
+    pub name: String,
+    /// LSP `SymbolKind` (5 = Class, 6 = Method, 12 = Function, …)
+    pub kind: u32,
+    /// Extra detail such as the signature or containing module
+    pub detail: Option<String>,
+    /// File path, or the raw URI for non-file resources
+    pub file: String,
+    /// Line of the symbol name (1-based)
+    pub line: u32,
+    /// Column of the symbol name (1-based)
+    pub column: u32,
+    /// Language whose server produced the item; expansion goes back to it
+    pub language: String,
+    /// The server's own item, sent back verbatim when expanding
+    #[ts(type = "unknown")]
+    pub item: JsonValue,
+}
+
 /// Result from spawning a process with spawnProcess
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -5732,6 +6041,10 @@ pub struct GrepMatch {
     pub column: usize,
     /// The matched line content (for display)
     pub context: String,
+    /// Rewritten text for the match (structural search with a rewrite)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub replacement: Option<String>,
 }
 
 /// Per-call result from `SearchHandle.take()` — the matches accumulated since
@@ -6467,6 +6780,8 @@ mod fromjs_impls {
         ViewTokenStyle,
         LayoutHints,
         CompositeHunk,
+        CompositeSection,
+        LineEdit,
         LanguagePackConfig,
         LspServerPackConfig,
         ProcessLimitsPackConfig,
@@ -6695,6 +7010,24 @@ mod fromjs_impls {
             assert_eq!(got.new_count, 4);
         }
 
+        #[test]
+        fn composite_section_decodes_pane_ranges_and_kind() {
+            let got: CompositeSection =
+                eval_as("({lines: [[0, 2], [0, 3], [1, 0]], kind: 'modification'})");
+            assert_eq!(got.lines, vec![vec![0, 2], vec![0, 3], vec![1, 0]]);
+            assert_eq!(got.kind, "modification");
+        }
+
+        #[test]
+        fn line_edit_decodes_all_fields() {
+            let got: LineEdit =
+                eval_as("({file: 'src/a.rs', line: 3, expected: 'old', text: 'new'})");
+            assert_eq!(got.file, "src/a.rs");
+            assert_eq!(got.line, 3);
+            assert_eq!(got.expected, "old");
+            assert_eq!(got.text, "new");
+        }
+
         #[test]
         fn language_pack_config_decodes_comment_prefix_and_tab_size() {
             let got: LanguagePackConfig =
@@ -8273,6 +8606,7 @@ mod tests {
             line,
             column: 1,
             context: "match".to_string(),
+            replacement: None,
         }
     }
 
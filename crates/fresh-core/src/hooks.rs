@@ -262,6 +262,16 @@ pub enum HookArgs {
         locations: Vec<LspLocation>,
     },
 
+    /// LSP call / type hierarchy prepared for the symbol at the cursor
+    LspHierarchy {
+        /// `"call"` or `"type"`
+        kind: String,
+        /// The symbol name being queried
+        symbol: String,
+        /// Hierarchy roots (usually one)
+        items: Vec<crate::api::HierarchyItem>,
+    },
+
     /// An LSP navigation (go-to-definition, …) resolved to a non-`file://`
     /// URI whose scheme a plugin claimed via `registerLspUriScheme`. The
     /// plugin is expected to fetch the synthetic document and open it (e.g.
@@ -284,6 +294,35 @@ pub enum HookArgs {
         server_name: String,
     },
 
+    /// A debug session changed state: `"starting"`, `"running"`,
+    /// `"stopped"` or `"ended"`.
+    DebugSessionChanged {
+        /// New session state
+        state: String,
+        /// Language whose debug adapter runs the session
+        language: String,
+    },
+
+    /// The debuggee stopped (breakpoint, step, pause, exception) and its
+    /// call stack was fetched. Frame ids can be passed to `scopes` /
+    /// `evaluate` through `editor.debugRequest`.
+    DebugStopped {
+        /// Thread that stopped, if the adapter reported one
+        thread_id: Option<i64>,
+        /// Why it stopped (`"breakpoint"`, `"step"`, `"exception"`, …)
+        reason: String,
+        /// Call stack, innermost frame first
+        frames: Vec<DebugStackFrame>,
+    },
+
+    /// Output from the debuggee or the adapter (`output` event)
+    DebugOutput {
+        /// `"console"`, `"stdout"`, `"stderr"`, …
+        category: String,
+        /// Output text, usually newline-terminated
+        output: String,
+    },
+
     /// Mouse click event
     MouseClick {
         /// Column (x coordinate) in screen cells
@@ -668,6 +707,21 @@ pub struct LspLocation {
     pub column: u32,
 }
 
+/// One frame of a debuggee call stack
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DebugStackFrame {
+    /// Adapter frame id (for `scopes`, `evaluate`, …)
+    pub id: i64,
+    /// Function name
+    pub name: String,
+    /// Source file path, if the frame has source
+    pub file: Option<String>,
+    /// Line number (1-based)
+    pub line: u32,
+    /// Column number (1-based)
+    pub column: u32,
+}
+
 /// Type for hook callbacks
 pub type HookCallback = Box<dyn Fn(&HookArgs) -> bool + Send + Sync>;
 
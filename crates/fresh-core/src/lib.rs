@@ -128,6 +128,7 @@ pub mod display_width;
 pub mod file_explorer;
 pub mod file_uri;
 pub mod menu;
+pub mod merge;
 pub mod overlay;
 pub mod plugin_schemas;
 pub mod services;
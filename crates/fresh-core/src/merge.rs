@@ -0,0 +1,358 @@
+//! Native three-way line merge (diff3).
+//!
+//! Shared by the `mergeThreeWay` plugin API; lives next to [`crate::diff`]
+//! because it is built on it. Ours and theirs are each diffed against base
+//! with the patience diff, then the two hunk lists are walked together in
+//! base order. Hunks whose base ranges overlap or touch are grouped — the
+//! same rule git uses, so adjacent edits from both sides conflict rather
+//! than being silently interleaved — and each group is settled:
+//!
+//! - only one side changed it: take that side;
+//! - both sides made the identical change: take it once;
+//! - otherwise: a conflict, written with diff3-style markers.
+//!
+//! Lines use the [`crate::diff::compute_line_diff`] tokenization, so the
+//! region line numbers agree with `computeLineDiff` hunks and with
+//! `text.split("\n")` line arrays in plugins.
+
+use crate::api::{LineDiffHunk, MergeRegion, MergeRegionKind, MergeResult};
+use crate::diff::{diff_interned_lines, LineInterner};
+
+const OURS_MARKER: &str = "<<<<<<< ours\n";
+const BASE_MARKER: &str = "||||||| base\n";
+const SEPARATOR_MARKER: &str = "=======\n";
+const THEIRS_MARKER: &str = ">>>>>>> theirs\n";
+
+/// Merge the changes `ours` and `theirs` each made to `base`.
+///
+/// Never fails: regions the sides disagree on come back as
+/// [`MergeRegionKind::Conflict`] with markers in the merged text, and
+/// `conflicts` counts them.
+pub fn merge_three_way(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let ours_lines: Vec<&str> = ours.split_inclusive('\n').collect();
+    let theirs_lines: Vec<&str> = theirs.split_inclusive('\n').collect();
+
+    let mut interner = LineInterner::default();
+    let base_ids: Vec<u32> = base_lines.iter().map(|l| interner.intern(l)).collect();
+    let ours_ids: Vec<u32> = ours_lines.iter().map(|l| interner.intern(l)).collect();
+    let theirs_ids: Vec<u32> = theirs_lines.iter().map(|l| interner.intern(l)).collect();
+
+    let ours_hunks = diff_interned_lines(&base_ids, &ours_ids);
+    let theirs_hunks = diff_interned_lines(&base_ids, &theirs_ids);
+
+    let mut out = MergeBuilder {
+        base: &base_lines,
+        ours: &ours_lines,
+        theirs: &theirs_lines,
+        merged: String::with_capacity(base.len().max(ours.len()).max(theirs.len())),
+        merged_lines: 0,
+        regions: Vec::new(),
+        conflicts: 0,
+    };
+
+    let (mut i, mut j) = (0, 0);
+    let mut base_pos = 0u32;
+    let (mut ours_delta, mut theirs_delta) = (0i64, 0i64);
+    loop {
+        let start = match (ours_hunks.get(i), theirs_hunks.get(j)) {
+            (Some(o), Some(t)) => o.old_start.min(t.old_start),
+            (Some(o), None) => o.old_start,
+            (None, Some(t)) => t.old_start,
+            (None, None) => break,
+        };
+
+        // Grow the group while the next hunk of either side overlaps or
+        // touches it.
+        let (first_ours, first_theirs) = (i, j);
+        let mut end = start;
+        loop {
+            if let Some(h) = ours_hunks.get(i).filter(|h| h.old_start <= end) {
+                end = end.max(h.old_start + h.old_count);
+                i += 1;
+            } else if let Some(h) = theirs_hunks.get(j).filter(|h| h.old_start <= end) {
+                end = end.max(h.old_start + h.old_count);
+                j += 1;
+            } else {
+                break;
+            }
+        }
+
+        out.unchanged(base_pos, start, ours_delta, theirs_delta);
+
+        let ours_start = offset(start, ours_delta);
+        let theirs_start = offset(start, theirs_delta);
+        ours_delta += growth(&ours_hunks[first_ours..i]);
+        theirs_delta += growth(&theirs_hunks[first_theirs..j]);
+        let ours_range = ours_start..offset(end, ours_delta);
+        let theirs_range = theirs_start..offset(end, theirs_delta);
+
+        let kind = match (i > first_ours, j > first_theirs) {
+            (true, false) => MergeRegionKind::Ours,
+            (false, true) => MergeRegionKind::Theirs,
+            _ if ours_ids[ours_range.clone()] == theirs_ids[theirs_range.clone()] => {
+                MergeRegionKind::Both
+            }
+            _ => MergeRegionKind::Conflict,
+        };
+        out.changed(kind, start as usize..end as usize, ours_range, theirs_range);
+        base_pos = end;
+    }
+    out.unchanged(base_pos, base_lines.len() as u32, ours_delta, theirs_delta);
+
+    MergeResult {
+        merged: out.merged,
+        regions: out.regions,
+        conflicts: out.conflicts,
+    }
+}
+
+/// `base_line` shifted by a side's accumulated line delta.
+fn offset(base_line: u32, delta: i64) -> usize {
+    (base_line as i64 + delta) as usize
+}
+
+/// How many lines a run of hunks adds to (or removes from) its side.
+fn growth(hunks: &[LineDiffHunk]) -> i64 {
+    hunks
+        .iter()
+        .map(|h| h.new_count as i64 - h.old_count as i64)
+        .sum()
+}
+
+struct MergeBuilder<'a> {
+    base: &'a [&'a str],
+    ours: &'a [&'a str],
+    theirs: &'a [&'a str],
+    merged: String,
+    merged_lines: u32,
+    regions: Vec<MergeRegion>,
+    conflicts: u32,
+}
+
+impl MergeBuilder<'_> {
+    /// Emit base lines `start..end`, untouched by either side.
+    fn unchanged(&mut self, start: u32, end: u32, ours_delta: i64, theirs_delta: i64) {
+        if start >= end {
+            return;
+        }
+        let merged_start = self.merged_lines;
+        for line in &self.base[start as usize..end as usize] {
+            self.push(line);
+        }
+        self.regions.push(MergeRegion {
+            kind: MergeRegionKind::Unchanged,
+            base_start: start,
+            base_count: end - start,
+            ours_start: offset(start, ours_delta) as u32,
+            ours_count: end - start,
+            theirs_start: offset(start, theirs_delta) as u32,
+            theirs_count: end - start,
+            merged_start,
+            merged_count: self.merged_lines - merged_start,
+        });
+    }
+
+    /// Emit a group one or both sides changed.
+    fn changed(
+        &mut self,
+        kind: MergeRegionKind,
+        base: std::ops::Range<usize>,
+        ours: std::ops::Range<usize>,
+        theirs: std::ops::Range<usize>,
+    ) {
+        let merged_start = self.merged_lines;
+        match kind {
+            MergeRegionKind::Ours | MergeRegionKind::Both => self.push_ours(&ours),
+            MergeRegionKind::Theirs => self.push_theirs(&theirs),
+            MergeRegionKind::Unchanged => self.push_base(&base),
+            MergeRegionKind::Conflict => {
+                self.conflicts += 1;
+                self.push(OURS_MARKER);
+                self.push_ours(&ours);
+                self.push(BASE_MARKER);
+                self.push_base(&base);
+                self.push(SEPARATOR_MARKER);
+                self.push_theirs(&theirs);
+                self.push(THEIRS_MARKER);
+            }
+        }
+        self.regions.push(MergeRegion {
+            kind,
+            base_start: base.start as u32,
+            base_count: base.len() as u32,
+            ours_start: ours.start as u32,
+            ours_count: ours.len() as u32,
+            theirs_start: theirs.start as u32,
+            theirs_count: theirs.len() as u32,
+            merged_start,
+            merged_count: self.merged_lines - merged_start,
+        });
+    }
+
+    fn push_ours(&mut self, range: &std::ops::Range<usize>) {
+        for line in &self.ours[range.clone()] {
+            self.push(line);
+        }
+    }
+
+    fn push_theirs(&mut self, range: &std::ops::Range<usize>) {
+        for line in &self.theirs[range.clone()] {
+            self.push(line);
+        }
+    }
+
+    fn push_base(&mut self, range: &std::ops::Range<usize>) {
+        for line in &self.base[range.clone()] {
+            self.push(line);
+        }
+    }
+
+    /// Append one line. A marker may follow a side's final unterminated
+    /// line, so that line is terminated first to keep the marker on a
+    /// line of its own.
+    fn push(&mut self, line: &str) {
+        if !self.merged.is_empty() && !self.merged.ends_with('\n') {
+            self.merged.push('\n');
+        }
+        self.merged.push_str(line);
+        self.merged_lines += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(result: &MergeResult) -> Vec<MergeRegionKind> {
+        result.regions.iter().map(|r| r.kind).collect()
+    }
+
+    /// Regions must tile base, ours, theirs and the merged text with no
+    /// gaps or overlaps.
+    fn assert_regions_tile(base: &str, ours: &str, theirs: &str, result: &MergeResult) {
+        let count = |text: &str| text.split_inclusive('\n').count() as u32;
+        let (mut b, mut o, mut t, mut m) = (0, 0, 0, 0);
+        for r in &result.regions {
+            assert_eq!(
+                (r.base_start, r.ours_start, r.theirs_start, r.merged_start),
+                (b, o, t, m)
+            );
+            b += r.base_count;
+            o += r.ours_count;
+            t += r.theirs_count;
+            m += r.merged_count;
+        }
+        assert_eq!(
+            (b, o, t, m),
+            (
+                count(base),
+                count(ours),
+                count(theirs),
+                count(&result.merged)
+            )
+        );
+    }
+
+    #[test]
+    fn non_overlapping_changes_merge_cleanly() {
+        let base = "a\nb\nc\nd\ne\n";
+        let ours = "a\nB\nc\nd\ne\n";
+        let theirs = "a\nb\nc\nD\ne\n";
+        let result = merge_three_way(base, ours, theirs);
+        assert_eq!(result.merged, "a\nB\nc\nD\ne\n");
+        assert_eq!(result.conflicts, 0);
+        assert_eq!(
+            kinds(&result),
+            vec![
+                MergeRegionKind::Unchanged,
+                MergeRegionKind::Ours,
+                MergeRegionKind::Unchanged,
+                MergeRegionKind::Theirs,
+                MergeRegionKind::Unchanged,
+            ]
+        );
+        assert_regions_tile(base, ours, theirs, &result);
+    }
+
+    #[test]
+    fn identical_changes_are_taken_once() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nX\nc\n";
+        let result = merge_three_way(base, ours, ours);
+        assert_eq!(result.merged, ours);
+        assert_eq!(result.conflicts, 0);
+        assert_eq!(kinds(&result)[1], MergeRegionKind::Both);
+    }
+
+    #[test]
+    fn overlapping_changes_conflict_with_markers() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nours\nc\n";
+        let theirs = "a\ntheirs\nc\n";
+        let result = merge_three_way(base, ours, theirs);
+        assert_eq!(
+            result.merged,
+            "a\n<<<<<<< ours\nours\n||||||| base\nb\n=======\ntheirs\n>>>>>>> theirs\nc\n"
+        );
+        assert_eq!(result.conflicts, 1);
+        let conflict = &result.regions[1];
+        assert_eq!(conflict.kind, MergeRegionKind::Conflict);
+        assert_eq!((conflict.base_start, conflict.base_count), (1, 1));
+        assert_eq!((conflict.merged_start, conflict.merged_count), (1, 7));
+        assert_regions_tile(base, ours, theirs, &result);
+    }
+
+    #[test]
+    fn adjacent_changes_conflict() {
+        let base = "a\nb\nc\nd\n";
+        let ours = "a\nB\nc\nd\n";
+        let theirs = "a\nb\nC\nd\n";
+        let result = merge_three_way(base, ours, theirs);
+        assert_eq!(result.conflicts, 1);
+        assert_regions_tile(base, ours, theirs, &result);
+    }
+
+    #[test]
+    fn insertions_at_the_same_place_conflict_unless_equal() {
+        let base = "a\nb\n";
+        let result = merge_three_way(base, "a\nx\nb\n", "a\ny\nb\n");
+        assert_eq!(result.conflicts, 1);
+        let conflict = &result.regions[1];
+        assert_eq!((conflict.base_start, conflict.base_count), (1, 0));
+
+        let result = merge_three_way(base, "a\nx\nb\n", "a\nx\nb\n");
+        assert_eq!(result.conflicts, 0);
+        assert_eq!(result.merged, "a\nx\nb\n");
+    }
+
+    #[test]
+    fn deletion_and_edit_elsewhere_merge() {
+        let base = "a\nb\nc\nd\ne\nf\n";
+        let ours = "a\nc\nd\ne\nf\n";
+        let theirs = "a\nb\nc\nd\nE\nf\n";
+        let result = merge_three_way(base, ours, theirs);
+        assert_eq!(result.merged, "a\nc\nd\nE\nf\n");
+        assert_regions_tile(base, ours, theirs, &result);
+    }
+
+    #[test]
+    fn unterminated_last_line_keeps_markers_on_their_own_lines() {
+        let result = merge_three_way("a\nb", "a\nours", "a\ntheirs");
+        assert_eq!(
+            result.merged,
+            "a\n<<<<<<< ours\nours\n||||||| base\nb\n=======\ntheirs\n>>>>>>> theirs\n"
+        );
+    }
+
+    #[test]
+    fn empty_base_is_an_add_add_conflict() {
+        let result = merge_three_way("", "same\nours\n", "same\ntheirs\n");
+        assert_eq!(result.conflicts, 1);
+        assert_regions_tile("", "same\nours\n", "same\ntheirs\n", &result);
+
+        let result = merge_three_way("", "", "new\n");
+        assert_eq!(result.merged, "new\n");
+        assert_eq!(kinds(&result), vec![MergeRegionKind::Theirs]);
+    }
+}
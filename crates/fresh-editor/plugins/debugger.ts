@@ -0,0 +1,485 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+const editor = getEditor();
+
+/**
+ * Debugger Panels Plugin
+ *
+ * The editor core owns the Debug Adapter Protocol session: it spawns the
+ * adapter through the active authority, keeps breakpoints in the gutter,
+ * highlights the stopped line and handles stepping. This plugin renders the
+ * inspection views on top of it as one buffer group:
+ *
+ *   * Call Stack  — frames from the `debug_stopped` hook; Enter selects a
+ *                   frame and opens its source.
+ *   * Variables   — `scopes` + `variables` for the selected frame; Enter
+ *                   expands / collapses structured values.
+ *   * Watch       — user expressions re-evaluated on every stop.
+ *   * Debug Console — program / adapter output (`debug_output` hook) plus
+ *                   expressions evaluated in the selected frame.
+ *
+ * Everything beyond what the hooks carry goes through `editor.debugRequest`,
+ * which forwards a raw DAP request to the running session.
+ */
+
+// =============================================================================
+// State
+// =============================================================================
+
+interface Frame {
+  id: number;
+  name: string;
+  file: string | null;
+  line: number;
+  column: number;
+}
+
+interface Variable {
+  name: string;
+  value: string;
+  type?: string;
+  variablesReference: number;
+}
+
+interface Scope {
+  name: string;
+  variablesReference: number;
+  expensive?: boolean;
+}
+
+interface DebuggerState {
+  groupId: number | null;
+  panelBuffers: Record<string, number>;
+  sessionState: string;
+  frames: Frame[];
+  selectedFrame: number;
+  /** `variablesReference` → children, for every expanded node. */
+  children: Map<number, Variable[]>;
+  scopes: Scope[];
+  watches: string[];
+  watchResults: Map<string, string>;
+  console: string[];
+}
+
+const state: DebuggerState = {
+  groupId: null,
+  panelBuffers: {},
+  sessionState: "ended",
+  frames: [],
+  selectedFrame: 0,
+  children: new Map(),
+  scopes: [],
+  watches: [],
+  watchResults: new Map(),
+  console: [],
+};
+
+/** Oldest console lines are dropped beyond this. */
+const MAX_CONSOLE_LINES = 1000;
+
+/** Nested variables deeper than this are not rendered. */
+const MAX_VARIABLE_DEPTH = 8;
+
+const GROUP_LAYOUT = JSON.stringify({
+  type: "split",
+  direction: "v",
+  ratio: 0.5,
+  first: {
+    type: "split",
+    direction: "h",
+    ratio: 0.4,
+    first: { type: "scrollable", id: "stack" },
+    second: { type: "scrollable", id: "variables" },
+  },
+  second: {
+    type: "split",
+    direction: "h",
+    ratio: 0.4,
+    first: { type: "scrollable", id: "watch" },
+    second: { type: "scrollable", id: "console" },
+  },
+});
+
+const PANELS = ["stack", "variables", "watch", "console"];
+
+editor.defineMode(
+  "debugger",
+  [
+    ["Return", "debugger_enter"],
+    ["Tab", "debugger_next_panel"],
+    ["a", "debugger_add_watch"],
+    ["d", "debugger_remove_watch"],
+    ["e", "debugger_evaluate"],
+    ["q", "debugger_close"],
+  ],
+  true, // read-only
+  false, // allow_text_input
+  true, // inherit Normal-context bindings (F-key stepping keeps working)
+);
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+function header(title: string): TextPropertyEntry {
+  return {
+    text: `${title}\n`,
+    style: { fg: "syntax.keyword", bold: true },
+  };
+}
+
+function placeholder(text: string): TextPropertyEntry {
+  return { text: `  ${text}\n`, style: { fg: "ui.menu_disabled_fg" } };
+}
+
+function renderStack(): void {
+  if (state.groupId === null) return;
+  const entries: TextPropertyEntry[] = [header(editor.t("panel.call_stack"))];
+  if (state.frames.length === 0) {
+    entries.push(placeholder(editor.t("panel.not_stopped")));
+  }
+  state.frames.forEach((frame, index) => {
+    const marker = index === state.selectedFrame ? "▶ " : "  ";
+    const location = frame.file
+      ? `${editor.pathBasename(frame.file)}:${frame.line}`
+      : "?";
+    entries.push({
+      text: `${marker}${frame.name}  ${location}\n`,
+      properties: { frameIndex: index },
+    });
+  });
+  editor.setPanelContent(state.groupId, "stack", entries);
+}
+
+function variableEntries(
+  variables: Variable[],
+  depth: number,
+  entries: TextPropertyEntry[],
+): void {
+  for (const v of variables) {
+    const indent = "  ".repeat(depth + 1);
+    const expandable = v.variablesReference > 0;
+    const expanded = expandable && state.children.has(v.variablesReference);
+    const twisty = expandable ? (expanded ? "▾ " : "▸ ") : "  ";
+    entries.push({
+      text: `${indent}${twisty}${v.name} = ${v.value}\n`,
+      properties: { variablesReference: v.variablesReference },
+    });
+    if (expanded && depth < MAX_VARIABLE_DEPTH) {
+      variableEntries(state.children.get(v.variablesReference)!, depth + 1, entries);
+    }
+  }
+}
+
+function renderVariables(): void {
+  if (state.groupId === null) return;
+  const entries: TextPropertyEntry[] = [header(editor.t("panel.variables"))];
+  if (state.scopes.length === 0) {
+    entries.push(placeholder(editor.t("panel.not_stopped")));
+  }
+  for (const scope of state.scopes) {
+    const expanded = state.children.has(scope.variablesReference);
+    entries.push({
+      text: `${expanded ? "▾" : "▸"} ${scope.name}\n`,
+      properties: { variablesReference: scope.variablesReference },
+      style: { bold: true },
+    });
+    if (expanded) {
+      variableEntries(state.children.get(scope.variablesReference)!, 0, entries);
+    }
+  }
+  editor.setPanelContent(state.groupId, "variables", entries);
+}
+
+function renderWatch(): void {
+  if (state.groupId === null) return;
+  const entries: TextPropertyEntry[] = [header(editor.t("panel.watch"))];
+  if (state.watches.length === 0) {
+    entries.push(placeholder(editor.t("panel.no_watches")));
+  }
+  state.watches.forEach((expression, index) => {
+    const result = state.watchResults.get(expression) ?? "…";
+    entries.push({
+      text: `  ${expression} = ${result}\n`,
+      properties: { watchIndex: index },
+    });
+  });
+  editor.setPanelContent(state.groupId, "watch", entries);
+}
+
+function renderConsole(): void {
+  if (state.groupId === null) return;
+  const entries: TextPropertyEntry[] = [header(editor.t("panel.console"))];
+  for (const line of state.console) {
+    entries.push({ text: `${line}\n` });
+  }
+  editor.setPanelContent(state.groupId, "console", entries);
+}
+
+function renderAll(): void {
+  renderStack();
+  renderVariables();
+  renderWatch();
+  renderConsole();
+}
+
+// =============================================================================
+// Adapter queries
+// =============================================================================
+
+function currentFrameId(): number | null {
+  const frame = state.frames[state.selectedFrame];
+  return frame ? frame.id : null;
+}
+
+async function fetchVariables(reference: number): Promise<Variable[]> {
+  const body = (await editor.debugRequest("variables", {
+    variablesReference: reference,
+  })) as { variables?: Variable[] };
+  return body.variables ?? [];
+}
+
+async function refreshVariables(): Promise<void> {
+  state.scopes = [];
+  state.children.clear();
+  const frameId = currentFrameId();
+  if (frameId !== null) {
+    try {
+      const body = (await editor.debugRequest("scopes", { frameId })) as {
+        scopes?: Scope[];
+      };
+      state.scopes = body.scopes ?? [];
+      // Cheap scopes (locals, arguments) open expanded; the rest
+      // (globals, registers) load on demand.
+      for (const scope of state.scopes) {
+        if (!scope.expensive && scope.variablesReference > 0) {
+          state.children.set(
+            scope.variablesReference,
+            await fetchVariables(scope.variablesReference),
+          );
+        }
+      }
+    } catch (e) {
+      editor.debug(`debugger: scopes failed: ${e}`);
+    }
+  }
+  renderVariables();
+}
+
+async function evaluate(expression: string, context: string): Promise<string> {
+  try {
+    const args: Record<string, unknown> = { expression, context };
+    const frameId = currentFrameId();
+    if (frameId !== null) args.frameId = frameId;
+    const body = (await editor.debugRequest("evaluate", args)) as {
+      result?: string;
+    };
+    return body.result ?? "";
+  } catch (e) {
+    return `<${e}>`;
+  }
+}
+
+async function refreshWatches(): Promise<void> {
+  state.watchResults.clear();
+  if (state.frames.length > 0) {
+    for (const expression of state.watches) {
+      state.watchResults.set(expression, await evaluate(expression, "watch"));
+    }
+  }
+  renderWatch();
+}
+
+function appendConsole(text: string): void {
+  const lines = text.replace(/\n$/, "").split("\n");
+  state.console.push(...lines);
+  if (state.console.length > MAX_CONSOLE_LINES) {
+    state.console.splice(0, state.console.length - MAX_CONSOLE_LINES);
+  }
+  renderConsole();
+}
+
+// =============================================================================
+// Hooks
+// =============================================================================
+
+editor.on("debug_session_changed", (data) => {
+  state.sessionState = data.state;
+  if (data.state === "starting") {
+    state.console = [];
+  }
+  if (data.state === "running" || data.state === "ended") {
+    state.frames = [];
+    state.scopes = [];
+    state.children.clear();
+    state.watchResults.clear();
+    renderAll();
+  }
+});
+
+editor.on("debug_stopped", async (data) => {
+  state.frames = data.frames.map((f) => ({
+    id: f.id,
+    name: f.name,
+    file: f.file ?? null,
+    line: f.line,
+    column: f.column,
+  }));
+  state.selectedFrame = 0;
+  renderStack();
+  await refreshVariables();
+  await refreshWatches();
+});
+
+editor.on("debug_output", (data) => {
+  // Telemetry events are adapter bookkeeping, not user output.
+  if (data.category === "telemetry") return;
+  appendConsole(data.output);
+});
+
+editor.on("buffer_closed", (data) => {
+  if (state.groupId === null) return;
+  if (Object.values(state.panelBuffers).includes(data.buffer_id)) {
+    state.groupId = null;
+    state.panelBuffers = {};
+  }
+});
+
+// =============================================================================
+// Commands
+// =============================================================================
+
+async function debugger_show_panels(): Promise<void> {
+  if (state.groupId !== null) {
+    editor.focusBufferGroupPanel(state.groupId, "stack");
+    return;
+  }
+  // `createBufferGroup` is a runtime-only binding (see git_log).
+  const group = await (editor as any).createBufferGroup(
+    "*Debug*",
+    "debugger",
+    GROUP_LAYOUT,
+  );
+  state.groupId = group.groupId as number;
+  state.panelBuffers = group.panels as Record<string, number>;
+  for (const panel of PANELS) {
+    const bufferId = state.panelBuffers[panel];
+    if (bufferId !== undefined) {
+      editor.setBufferShowCursors(bufferId, true);
+    }
+  }
+  renderAll();
+}
+registerHandler("debugger_show_panels", debugger_show_panels);
+
+function debugger_close(): void {
+  if (state.groupId === null) return;
+  const groupId = state.groupId;
+  state.groupId = null;
+  state.panelBuffers = {};
+  editor.closeBufferGroup(groupId);
+}
+registerHandler("debugger_close", debugger_close);
+
+function focusedPanel(): string | null {
+  const active = editor.getActiveBufferId();
+  for (const panel of PANELS) {
+    if (state.panelBuffers[panel] === active) return panel;
+  }
+  return null;
+}
+
+function debugger_next_panel(): void {
+  if (state.groupId === null) return;
+  const current = focusedPanel();
+  const next = PANELS[(PANELS.indexOf(current ?? "console") + 1) % PANELS.length];
+  editor.focusBufferGroupPanel(state.groupId, next);
+}
+registerHandler("debugger_next_panel", debugger_next_panel);
+
+function propertyAtCursor(key: string): unknown {
+  const bufferId = editor.getActiveBufferId();
+  for (const record of editor.getTextPropertiesAtCursor(bufferId)) {
+    if (record[key] !== undefined) return record[key];
+  }
+  return undefined;
+}
+
+async function debugger_enter(): Promise<void> {
+  const panel = focusedPanel();
+  if (panel === "stack") {
+    const index = propertyAtCursor("frameIndex");
+    if (typeof index !== "number") return;
+    state.selectedFrame = index;
+    renderStack();
+    const frame = state.frames[index];
+    if (frame.file) {
+      editor.openFile(frame.file, frame.line, frame.column);
+    }
+    await refreshVariables();
+    await refreshWatches();
+  } else if (panel === "variables") {
+    const reference = propertyAtCursor("variablesReference");
+    if (typeof reference !== "number" || reference <= 0) return;
+    if (state.children.has(reference)) {
+      state.children.delete(reference);
+    } else {
+      try {
+        state.children.set(reference, await fetchVariables(reference));
+      } catch (e) {
+        editor.setStatus(editor.t("status.request_failed", { error: String(e) }));
+      }
+    }
+    renderVariables();
+  }
+}
+registerHandler("debugger_enter", debugger_enter);
+
+async function debugger_add_watch(): Promise<void> {
+  const expression = await editor.prompt(editor.t("prompt.watch"), "");
+  if (!expression || expression.trim() === "") return;
+  state.watches.push(expression.trim());
+  await refreshWatches();
+}
+registerHandler("debugger_add_watch", debugger_add_watch);
+
+function debugger_remove_watch(): void {
+  const index = propertyAtCursor("watchIndex");
+  if (typeof index !== "number") return;
+  const [removed] = state.watches.splice(index, 1);
+  state.watchResults.delete(removed);
+  renderWatch();
+}
+registerHandler("debugger_remove_watch", debugger_remove_watch);
+
+async function debugger_evaluate(): Promise<void> {
+  if (state.sessionState === "ended") {
+    editor.setStatus(editor.t("status.no_session"));
+    return;
+  }
+  const expression = await editor.prompt(editor.t("prompt.evaluate"), "");
+  if (!expression || expression.trim() === "") return;
+  appendConsole(`> ${expression}`);
+  appendConsole(await evaluate(expression, "repl"));
+}
+registerHandler("debugger_evaluate", debugger_evaluate);
+
+editor.registerCommand(
+  "%cmd.debugger_show_panels",
+  "%cmd.debugger_show_panels_desc",
+  "debugger_show_panels",
+  null,
+);
+editor.registerCommand(
+  "%cmd.debugger_add_watch",
+  "%cmd.debugger_add_watch_desc",
+  "debugger_add_watch",
+  null,
+);
+editor.registerCommand(
+  "%cmd.debugger_evaluate",
+  "%cmd.debugger_evaluate_desc",
+  "debugger_evaluate",
+  null,
+);
+editor.debug("Debugger panels plugin initialized");
@@ -0,0 +1,612 @@
+/// <reference path="./lib/fresh.d.ts" />
+import {
+  col,
+  hintBar,
+  list,
+  raw,
+  WidgetPanel,
+} from "./lib/widgets.ts";
+
+const editor = getEditor();
+
+/**
+ * Compare Folders Plugin
+ *
+ * "Compare Folders" asks for two directories, walks both through the active
+ * filesystem (`editor.compareDirectories`, which honours ignore files) and
+ * lists every added, removed and changed file as a tree in the Utility Dock,
+ * with per-file line stats. Moving the selection previews the file as a
+ * side-by-side diff in the source split. `>` and `<` copy the selected file
+ * (or every file under a selected folder) to the right or left tree; inside
+ * the diff they copy just the hunk under the cursor.
+ */
+
+interface Row {
+  /** Relative path of the file or folder, `/`-separated. */
+  path: string;
+  depth: number;
+  /** Set for file rows; folder rows have none. */
+  entry: DirDiffEntry | null;
+}
+
+interface Preview {
+  path: string;
+  compositeId: number;
+  leftBufferId: number;
+  rightBufferId: number;
+  leftText: string;
+  rightText: string;
+  hunks: LineDiffHunk[];
+}
+
+interface DirDiffPanel {
+  bufferId: number;
+  splitId: number;
+  sourceSplitId: number;
+  sourceBufferId: number;
+  widgetPanel: WidgetPanel;
+  left: string;
+  right: string;
+  diff: DirDiff;
+  /** Folders the user folded away. */
+  collapsed: Set<string>;
+  rows: Row[];
+  selected: number;
+  preview: Preview | null;
+  /** Bumped per selection so a slow preview can't land after a newer one. */
+  previewToken: number;
+}
+
+type Side = "left" | "right";
+
+const MODE = "dir-diff";
+const PREVIEW_MODE = "dir-diff-preview";
+const LIST_KEY = "dirDiffEntries";
+
+const STATUS_SYMBOL: Record<DirDiffStatus, string> = {
+  added: "+",
+  removed: "-",
+  modified: "~",
+};
+const STATUS_COLOR: Record<DirDiffStatus, string> = {
+  added: "ui.file_status_added_fg",
+  removed: "ui.file_status_deleted_fg",
+  modified: "ui.file_status_modified_fg",
+};
+
+let panel: DirDiffPanel | null = null;
+
+// =============================================================================
+// Tree
+// =============================================================================
+
+/**
+ * Rows for the differing files, grouped under their folders. Entries arrive
+ * sorted by path, so every folder's files are contiguous and a folder row is
+ * due whenever a path leaves the previous path's folders.
+ */
+function buildRows(entries: DirDiffEntry[], collapsed: Set<string>): Row[] {
+  const rows: Row[] = [];
+  let open: string[] = [];
+  for (const entry of entries) {
+    const parts = entry.path.split("/");
+    const dirs = parts.slice(0, -1);
+    let shared = 0;
+    while (shared < open.length && shared < dirs.length && open[shared] === dirs[shared]) {
+      shared++;
+    }
+    for (let depth = shared; depth < dirs.length; depth++) {
+      const path = dirs.slice(0, depth + 1).join("/");
+      if (!isHidden(path, collapsed)) rows.push({ path, depth, entry: null });
+    }
+    open = dirs;
+    if (!isHidden(entry.path, collapsed)) {
+      rows.push({ path: entry.path, depth: dirs.length, entry });
+    }
+  }
+  return rows;
+}
+
+/** Whether `path` sits inside a collapsed folder. */
+function isHidden(path: string, collapsed: Set<string>): boolean {
+  const parts = path.split("/");
+  for (let i = 1; i < parts.length; i++) {
+    if (collapsed.has(parts.slice(0, i).join("/"))) return true;
+  }
+  return false;
+}
+
+/** Entries at or below a row: the file itself, or a folder's files. */
+function entriesUnder(row: Row): DirDiffEntry[] {
+  if (!panel) return [];
+  if (row.entry) return [row.entry];
+  const prefix = `${row.path}/`;
+  return panel.diff.entries.filter((entry) => entry.path.startsWith(prefix));
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+function statsText(entries: DirDiffEntry[]): string {
+  const added = entries.reduce((sum, e) => sum + e.added, 0);
+  const removed = entries.reduce((sum, e) => sum + e.removed, 0);
+  return `+${added} -${removed}`;
+}
+
+function rowEntry(row: Row): TextPropertyEntry {
+  const indent = "  ".repeat(row.depth);
+  const name = row.path.split("/").pop() ?? row.path;
+  if (!row.entry) {
+    const marker = panel?.collapsed.has(row.path) ? "▸" : "▾";
+    const head = `${indent}${marker} ${name}/`;
+    const stats = statsText(entriesUnder(row));
+    return {
+      text: `${head}  ${stats}`,
+      inlineOverlays: [
+        { start: 0, end: head.length, style: { fg: "syntax.keyword" } },
+        { start: head.length + 2, end: head.length + 2 + stats.length, style: { fg: "syntax.comment" } },
+      ],
+    };
+  }
+  const symbol = STATUS_SYMBOL[row.entry.status];
+  const head = `${indent}${symbol} ${name}`;
+  const stats = row.entry.binary ? editor.t("label.binary") : statsText([row.entry]);
+  return {
+    text: `${head}  ${stats}`,
+    inlineOverlays: [
+      {
+        start: indent.length,
+        end: head.length,
+        style: { fg: STATUS_COLOR[row.entry.status] },
+      },
+      { start: head.length + 2, end: head.length + 2 + stats.length, style: { fg: "syntax.comment" } },
+    ],
+  };
+}
+
+function render(): void {
+  if (!panel) return;
+  const summary = panel.diff.entries.length === 0
+    ? editor.t("label.identical_all")
+    : editor.t("label.summary", {
+      differ: String(panel.diff.entries.length),
+      identical: String(panel.diff.identical),
+    });
+  panel.widgetPanel.set(
+    col(
+      raw([
+        {
+          text: editor.t("title", { left: panel.left, right: panel.right }),
+          style: { fg: "syntax.keyword", bold: true },
+        },
+        { text: summary, style: { fg: "syntax.comment" } },
+      ]),
+      list({
+        items: panel.rows.map(rowEntry),
+        selectedIndex: panel.selected,
+        key: LIST_KEY,
+      }),
+      hintBar([
+        { keys: "↑↓", label: editor.t("hint.preview") },
+        { keys: "Enter", label: editor.t("hint.open") },
+        { keys: "> <", label: editor.t("hint.copy") },
+        { keys: "r", label: editor.t("hint.refresh") },
+        { keys: "Esc", label: editor.t("hint.close") },
+      ]),
+    ),
+  );
+}
+
+// =============================================================================
+// Diff preview
+// =============================================================================
+
+function disposePreview(preview: Preview | null): void {
+  if (!preview) return;
+  try {
+    editor.closeCompositeBuffer(preview.compositeId);
+    editor.closeBuffer(preview.leftBufferId);
+    editor.closeBuffer(preview.rightBufferId);
+  } catch { /* already gone */ }
+}
+
+/** Show the source buffer again in its split, dropping any preview. */
+function restoreSource(): void {
+  if (!panel) return;
+  const preview = panel.preview;
+  panel.preview = null;
+  editor.setSplitBuffer(panel.sourceSplitId, panel.sourceBufferId);
+  disposePreview(preview);
+}
+
+function sidePath(side: Side, path: string): string {
+  return editor.pathJoin(side === "left" ? panel!.left : panel!.right, path);
+}
+
+async function showPreview(row: Row | undefined): Promise<void> {
+  if (!panel) return;
+  const token = ++panel.previewToken;
+  const entry = row?.entry;
+  if (!entry || entry.binary) {
+    restoreSource();
+    if (entry) editor.setStatus(editor.t("status.binary", { path: entry.path }));
+    return;
+  }
+  const { sourceSplitId, splitId } = panel;
+  const leftText = editor.readFile(sidePath("left", entry.path)) ?? "";
+  const rightText = editor.readFile(sidePath("right", entry.path)) ?? "";
+  const name = editor.pathBasename(entry.path);
+
+  const leftRes = await editor.createVirtualBuffer({
+    name: `*Compare:left:${entry.path}*`, mode: "normal", readOnly: true,
+    entries: [{ text: leftText }], showLineNumbers: true, editingDisabled: true,
+    hiddenFromTabs: true,
+  });
+  const rightRes = await editor.createVirtualBuffer({
+    name: `*Compare:right:${entry.path}*`, mode: "normal", readOnly: true,
+    entries: [{ text: rightText }], showLineNumbers: true, editingDisabled: true,
+    hiddenFromTabs: true,
+  });
+  if (!panel || token !== panel.previewToken) {
+    editor.closeBuffer(leftRes.bufferId);
+    editor.closeBuffer(rightRes.bufferId);
+    return;
+  }
+
+  const hunks = editor.computeLineDiff(leftText, rightText);
+  const paneLabel = (side: Side, present: boolean) =>
+    present
+      ? editor.pathBasename(side === "left" ? panel!.left : panel!.right)
+      : editor.t("pane.missing");
+  const compositeId = await editor.createCompositeBuffer({
+    name: editor.t("preview_title", { name }),
+    mode: PREVIEW_MODE,
+    layout: { type: "side-by-side", ratios: [0.5, 0.5], showSeparator: true },
+    sources: [
+      {
+        bufferId: leftRes.bufferId,
+        label: paneLabel("left", entry.status !== "added"),
+        editable: false,
+        style: { gutterStyle: "diff-markers" },
+      },
+      {
+        bufferId: rightRes.bufferId,
+        label: paneLabel("right", entry.status !== "removed"),
+        editable: false,
+        style: { gutterStyle: "diff-markers" },
+      },
+    ],
+    hunks,
+  });
+
+  const preview: Preview = {
+    path: entry.path,
+    compositeId,
+    leftBufferId: leftRes.bufferId,
+    rightBufferId: rightRes.bufferId,
+    leftText,
+    rightText,
+    hunks,
+  };
+  if (!panel || token !== panel.previewToken) {
+    disposePreview(preview);
+    return;
+  }
+  const previous = panel.preview;
+  panel.preview = preview;
+  // showBuffer targets the active split: show the diff in the source split
+  // and hand focus back to the list.
+  editor.focusSplit(sourceSplitId);
+  editor.showBuffer(compositeId);
+  editor.focusSplit(splitId);
+  disposePreview(previous);
+}
+
+function previewSelected(): void {
+  if (!panel) return;
+  showPreview(panel.rows[panel.selected]).catch((e) => editor.error(`dir-diff: ${e}`));
+}
+
+// =============================================================================
+// Copying
+// =============================================================================
+
+/** Lines of `text`, each keeping its newline, as `computeLineDiff` counts them. */
+function splitLines(text: string): string[] {
+  return text.match(/[^\n]*\n|[^\n]+$/g) ?? [];
+}
+
+/** Copy whole files from the `from` tree to the other one. */
+function copyFiles(entries: DirDiffEntry[], from: Side): number {
+  const to: Side = from === "left" ? "right" : "left";
+  let copied = 0;
+  for (const entry of entries) {
+    // Copying never deletes: a file missing on the source side stays put.
+    const missing = from === "left" ? entry.status === "added" : entry.status === "removed";
+    if (missing) continue;
+    if (editor.copyPath(sidePath(from, entry.path), sidePath(to, entry.path))) copied++;
+  }
+  return copied;
+}
+
+/** Copy the `from` side of `hunk` over the other side of the previewed file. */
+function copyHunk(preview: Preview, hunk: LineDiffHunk, from: Side): boolean {
+  const left = splitLines(preview.leftText);
+  const right = splitLines(preview.rightText);
+  let target: string[];
+  if (from === "left") {
+    const lines = left.slice(hunk.oldStart, hunk.oldStart + hunk.oldCount);
+    target = [...right.slice(0, hunk.newStart), ...lines, ...right.slice(hunk.newStart + hunk.newCount)];
+  } else {
+    const lines = right.slice(hunk.newStart, hunk.newStart + hunk.newCount);
+    target = [...left.slice(0, hunk.oldStart), ...lines, ...left.slice(hunk.oldStart + hunk.oldCount)];
+  }
+  const to: Side = from === "left" ? "right" : "left";
+  return editor.writeFile(sidePath(to, preview.path), target.join(""));
+}
+
+/** The hunk on the diff cursor's row, if any. */
+async function hunkAtCursor(preview: Preview): Promise<LineDiffHunk | null> {
+  const info = await editor.getCompositeCursorInfo();
+  if (!info) return null;
+  const [left, right] = info.lines;
+  return preview.hunks.find((hunk) =>
+    (left != null && left >= hunk.oldStart && left < hunk.oldStart + hunk.oldCount) ||
+    (right != null && right >= hunk.newStart && right < hunk.newStart + hunk.newCount)
+  ) ?? null;
+}
+
+async function copySelected(from: Side): Promise<void> {
+  if (!panel) return;
+  const row = panel.rows[panel.selected];
+  if (!row) return;
+  const entries = entriesUnder(row);
+  const copied = copyFiles(entries, from);
+  if (copied === 0) {
+    editor.setStatus(editor.t("status.nothing_to_copy", { side: editor.t(`side.${from}`) }));
+    return;
+  }
+  editor.setStatus(editor.t(from === "left" ? "status.copied_right" : "status.copied_left", {
+    count: String(copied),
+  }));
+  await refresh(row.path);
+}
+
+async function copyHunkAtCursor(from: Side): Promise<void> {
+  const preview = panel?.preview;
+  if (!preview) return;
+  const hunk = await hunkAtCursor(preview);
+  if (!hunk) {
+    editor.setStatus(editor.t("status.no_hunk"));
+    return;
+  }
+  if (!copyHunk(preview, hunk, from)) {
+    editor.setStatus(editor.t("status.write_failed", { path: preview.path }));
+    return;
+  }
+  editor.setStatus(editor.t(from === "left" ? "status.hunk_right" : "status.hunk_left"));
+  await refresh(preview.path);
+  // Stay in the diff so several hunks can be copied in a row.
+  if (panel?.preview) editor.focusSplit(panel.sourceSplitId);
+}
+
+registerHandler("dir_diff_copy_to_right", () => copySelected("left"));
+registerHandler("dir_diff_copy_to_left", () => copySelected("right"));
+registerHandler("dir_diff_hunk_to_right", () => copyHunkAtCursor("left"));
+registerHandler("dir_diff_hunk_to_left", () => copyHunkAtCursor("right"));
+
+// =============================================================================
+// Panel lifecycle
+// =============================================================================
+
+/** Re-run the comparison, keeping the selection on `path` if it still differs. */
+async function refresh(path?: string): Promise<void> {
+  if (!panel) return;
+  panel.diff = editor.compareDirectories(panel.left, panel.right);
+  panel.rows = buildRows(panel.diff.entries, panel.collapsed);
+  const index = path === undefined ? -1 : panel.rows.findIndex((row) => row.path === path);
+  panel.selected = index >= 0
+    ? index
+    : Math.max(0, Math.min(panel.selected, panel.rows.length - 1));
+  render();
+  await showPreview(panel.rows[panel.selected]);
+}
+
+/** An absolute folder path from user input, or null if there is none there. */
+function resolveFolder(input: string | null): string | null {
+  const trimmed = input?.trim().replace(/(.)[\\/]+$/, "$1");
+  if (!trimmed) return null;
+  const path = trimmed.startsWith("/") || /^[A-Za-z]:[\\/]/.test(trimmed)
+    ? trimmed
+    : editor.pathJoin(editor.getCwd(), trimmed);
+  return editor.fileExists(path) ? path : null;
+}
+
+function withSlash(dir: string): string {
+  return dir.endsWith("/") ? dir : `${dir}/`;
+}
+
+async function askFolder(label: string, initial: string): Promise<string | null> {
+  const input = await editor.prompt(label, initial);
+  if (input === null) return null;
+  const folder = resolveFolder(input);
+  if (!folder) editor.setStatus(editor.t("status.no_folder", { path: input.trim() }));
+  return folder;
+}
+
+async function dir_diff_compare(): Promise<void> {
+  // Prefill a folder to type a name into: the working directory, then the
+  // left folder's parent, where a sibling to compare with usually lives.
+  const left = await askFolder(editor.t("prompt.left"), withSlash(editor.getCwd()));
+  if (!left) return;
+  const right = await askFolder(editor.t("prompt.right"), withSlash(editor.pathDirname(left)));
+  if (!right) return;
+
+  if (panel) {
+    restoreSource();
+    panel.left = left;
+    panel.right = right;
+    panel.collapsed.clear();
+    panel.selected = 0;
+    await refresh();
+    editor.focusSplit(panel.splitId);
+    return;
+  }
+
+  const sourceSplitId = editor.getActiveSplitId();
+  const sourceBufferId = editor.getActiveBufferId();
+  const result = await editor.createVirtualBufferInSplit({
+    name: "*Compare Folders*",
+    mode: MODE,
+    readOnly: true,
+    entries: [],
+    ratio: 0.65,
+    panelId: "dir-diff-panel",
+    role: "utility_dock",
+    showLineNumbers: false,
+    showCursors: false,
+    editingDisabled: true,
+    scrollable: false,
+  });
+  panel = {
+    bufferId: result.bufferId,
+    splitId: result.splitId ?? editor.getActiveSplitId(),
+    sourceSplitId,
+    sourceBufferId,
+    widgetPanel: new WidgetPanel(result.bufferId),
+    left,
+    right,
+    diff: { entries: [], identical: 0 },
+    collapsed: new Set(),
+    rows: [],
+    selected: 0,
+    preview: null,
+    previewToken: 0,
+  };
+  await refresh();
+}
+registerHandler("dir_diff_compare", dir_diff_compare);
+
+function dir_diff_close(): void {
+  if (!panel) return;
+  panel.previewToken++;
+  restoreSource();
+  const { bufferId, splitId, sourceSplitId, widgetPanel } = panel;
+  panel = null;
+  widgetPanel.unmount();
+  editor.closeBuffer(bufferId);
+  if (splitId !== sourceSplitId) {
+    editor.closeSplit(splitId);
+  }
+  editor.focusSplit(sourceSplitId);
+}
+registerHandler("dir_diff_close", dir_diff_close);
+
+/** Enter: fold or unfold a folder, or move into a file's diff. */
+function dir_diff_open(): void {
+  if (!panel) return;
+  const row = panel.rows[panel.selected];
+  if (!row) return;
+  if (row.entry) {
+    if (panel.preview?.path === row.path) editor.focusSplit(panel.sourceSplitId);
+    return;
+  }
+  if (panel.collapsed.has(row.path)) panel.collapsed.delete(row.path);
+  else panel.collapsed.add(row.path);
+  panel.rows = buildRows(panel.diff.entries, panel.collapsed);
+  render();
+}
+registerHandler("dir_diff_open", dir_diff_open);
+
+registerHandler("dir_diff_refresh", () => {
+  if (!panel) return;
+  const row = panel.rows[panel.selected];
+  refresh(row?.path).catch((e) => editor.error(`dir-diff: ${e}`));
+});
+
+registerHandler("dir_diff_back", () => {
+  if (panel) editor.focusSplit(panel.splitId);
+});
+
+function select(index: number): void {
+  if (!panel || panel.rows.length === 0) return;
+  const clamped = Math.max(0, Math.min(index, panel.rows.length - 1));
+  if (clamped === panel.selected) return;
+  panel.selected = clamped;
+  render();
+  previewSelected();
+}
+
+// Clicking a row selects it like the arrow keys do.
+editor.on("widget_event", (args) => {
+  if (!panel || args.panel_id !== panel.widgetPanel.id()) return;
+  if (args.widget_key !== LIST_KEY || args.event_type !== "select") return;
+  const payload = args.payload as { index?: number } | undefined;
+  if (typeof payload?.index === "number") select(payload.index);
+});
+
+editor.on("buffer_closed", (args) => {
+  if (!panel) return;
+  if (args.buffer_id === panel.bufferId) {
+    panel.previewToken++;
+    disposePreview(panel.preview);
+    panel.widgetPanel.unmount();
+    panel = null;
+  }
+});
+
+// Every selection change previews, so the plugin owns the selection.
+const PAGE = 10;
+const NAV_KEYS: [string, (selected: number) => number][] = [
+  ["Up", (i) => i - 1],
+  ["Down", (i) => i + 1],
+  ["PageUp", (i) => i - PAGE],
+  ["PageDown", (i) => i + PAGE],
+  ["Home", () => 0],
+  ["End", () => Number.MAX_SAFE_INTEGER],
+];
+for (const [name, step] of NAV_KEYS) {
+  registerHandler(`dir_diff_key_${name}`, () => {
+    if (panel) select(step(panel.selected));
+  });
+}
+
+editor.defineMode(
+  MODE,
+  [
+    ...NAV_KEYS.map(([name]): [string, string] => [name, `dir_diff_key_${name}`]),
+    ["Return", "dir_diff_open"],
+    [">", "dir_diff_copy_to_right"],
+    ["<", "dir_diff_copy_to_left"],
+    ["r", "dir_diff_refresh"],
+    ["q", "dir_diff_close"],
+    ["Escape", "dir_diff_close"],
+  ],
+  true,
+  false,
+);
+
+// The diff keeps normal scrolling; the copy keys act on the hunk under the
+// cursor and Escape goes back to the list.
+editor.defineMode(
+  PREVIEW_MODE,
+  [
+    [">", "dir_diff_hunk_to_right"],
+    ["<", "dir_diff_hunk_to_left"],
+    ["q", "dir_diff_close"],
+    ["Escape", "dir_diff_back"],
+  ],
+  true,
+  false,
+  true,
+);
+
+editor.registerCommand(
+  "%cmd.compare_folders",
+  "%cmd.compare_folders_desc",
+  "dir_diff_compare",
+  null,
+);
+
+editor.debug("Compare folders plugin initialized");
@@ -223,6 +223,18 @@ type TsCompositeHunk = {
 	*/
 	ops?: string;
 };
+type TsCompositeSection = {
+	/**
+	* `[start, count]` lines (0-indexed) of each pane, in `sources` order
+	*/
+	lines: Array<[number, number]>;
+	/**
+	* Row style: "context", "addition", "deletion" or "modification".
+	* Every section but a context one starts with a hunk header row, so
+	* hunk navigation stops at it.
+	*/
+	kind: string;
+};
 type TsCreateCompositeBufferOptions = {
 	/**
 	* Buffer name (displayed in tabs/title)
@@ -245,6 +257,11 @@ type TsCreateCompositeBufferOptions = {
 	*/
 	hunks: Array<TsCompositeHunk> | null;
 	/**
+	* Aligned sections for any number of panes (optional). Takes the
+	* place of `hunks` when both are given.
+	*/
+	sections?: Array<TsCompositeSection>;
+	/**
 	* When set, the first render will scroll to center the Nth hunk (0-indexed).
 	* This avoids timing issues with imperative scroll commands that depend on
 	* render-created state (viewport dimensions, view state).
@@ -1378,6 +1395,107 @@ type GrammarInfoSnapshot = {
 	*/
 	short_name: string | null;
 };
+type HierarchyItem = {
+	/**
+	* Symbol name
+	*/
+	name: string;
+	/**
+	* LSP `SymbolKind` (5 = Class, 6 = Method, 12 = Function, …)
+	*/
+	kind: number;
+	/**
+	* Extra detail such as the signature or containing module
+	*/
+	detail: string | null;
+	/**
+	* File path, or the raw URI for non-file resources
+	*/
+	file: string;
+	/**
+	* Line of the symbol name (1-based)
+	*/
+	line: number;
+	/**
+	* Column of the symbol name (1-based)
+	*/
+	column: number;
+	/**
+	* Language whose server produced the item; expansion goes back to it
+	*/
+	language: string;
+	/**
+	* The server's own item, sent back verbatim when expanding
+	*/
+	item: unknown;
+};
+type UndoHistoryState = {
+	/**
+	* State number, in order of creation (1-based)
+	*/
+	seq: number;
+	/**
+	* State this one was reached from (0 = the start of the history)
+	*/
+	parent: number;
+	/**
+	* When the change was made (milliseconds since epoch)
+	*/
+	timestamp: number;
+	/**
+	* Short description of the change, e.g. `Insert "foo"`
+	*/
+	label: string;
+	/**
+	* Number of edits the state groups together
+	*/
+	edits: number;
+};
+type MergeRegionKind = "unchanged" | "ours" | "theirs" | "both" | "conflict";
+type MergeRegion = {
+	kind: MergeRegionKind;
+	/**
+	* First line of the region in base.
+	*/
+	baseStart: number;
+	baseCount: number;
+	/**
+	* First line of the region in ours.
+	*/
+	oursStart: number;
+	oursCount: number;
+	/**
+	* First line of the region in theirs.
+	*/
+	theirsStart: number;
+	theirsCount: number;
+	/**
+	* First line of the region in the merged text. For a conflict this
+	* spans the whole marker block.
+	*/
+	mergedStart: number;
+	mergedCount: number;
+};
+type DirDiffStatus = "added" | "removed" | "modified";
+type DirDiffEntry = {
+	/**
+	* Path relative to both roots, `/`-separated.
+	*/
+	path: string;
+	status: DirDiffStatus;
+	/**
+	* Lines only in the right file (every line of an added file).
+	*/
+	added: number;
+	/**
+	* Lines only in the left file (every line of a removed file).
+	*/
+	removed: number;
+	/**
+	* Either side is not text; `added` and `removed` are 0.
+	*/
+	binary: boolean;
+};
 type AnimationRect = {
 	x: number;
 	y: number;
@@ -2170,6 +2288,47 @@ type ReplaceResult = {
 	*/
 	bufferId: number;
 };
+type LineEdit = {
+	/**
+	* File the line is in
+	*/
+	file: string;
+	/**
+	* Line number (1-based, as reported by grep)
+	*/
+	line: number;
+	/**
+	* What the line said when the edit was made, without its line ending;
+	* a line that no longer says this is reported as a conflict
+	*/
+	expected: string;
+	/**
+	* New contents of the line, without its line ending
+	*/
+	text: string;
+};
+type LineEditConflict = {
+	file: string;
+	line: number;
+	/**
+	* What the line says now (`null` if the file is shorter or unreadable)
+	*/
+	actual: string | null;
+};
+type LineEditResult = {
+	/**
+	* Lines rewritten
+	*/
+	applied: number;
+	/**
+	* Files touched, open buffers included
+	*/
+	files: number;
+	/**
+	* Lines skipped because they no longer match `expected`
+	*/
+	conflicts: Array<LineEditConflict>;
+};
 type AuthorityPath = {
 	kind: "authority";
 	value: string;
@@ -2451,6 +2610,16 @@ type DiffBaselineResult = {
 	*/
 	hunks: Array<LineDiffHunk>;
 };
+type DirDiff = {
+	/**
+	* Differing files, sorted by path.
+	*/
+	entries: Array<DirDiffEntry>;
+	/**
+	* Files present in both trees with identical contents.
+	*/
+	identical: number;
+};
 type GrepMatch = {
 	/**
 	* Absolute file path
@@ -2480,6 +2649,10 @@ type GrepMatch = {
 	* The matched line content (for display)
 	*/
 	context: string;
+	/**
+	* Rewritten text for the match (structural search with a rewrite)
+	*/
+	replacement?: string;
 };
 type LanguagePackConfig = {
 	/**
@@ -2560,6 +2733,18 @@ type LspServerPackConfig = {
 	*/
 	processLimits: ProcessLimitsPackConfig | null;
 };
+type MergeResult = {
+	/**
+	* The merged text, with every conflict written diff3-style:
+	* `<<<<<<< ours`, `||||||| base`, `=======`, `>>>>>>> theirs`.
+	*/
+	merged: string;
+	regions: Array<MergeRegion>;
+	/**
+	* Number of `Conflict` regions; 0 means the merge is clean.
+	*/
+	conflicts: number;
+};
 type RemoteAgentTransport = {
 	kind: "kubectl-exec";
 	/** kubeconfig context to select (`--context`); omit for the current one. */
@@ -2672,6 +2857,16 @@ type StyledText = {
 	text: string;
 	style?: Partial<OverlayOptions>;
 };
+type TextObjectSpan = {
+	/**
+	* Start byte offset
+	*/
+	start: number;
+	/**
+	* End byte offset (exclusive)
+	*/
+	end: number;
+};
 type TextPropertiesAtCursor = Array<Record<string, unknown>>;
 type TsHighlightSpan = {
 	start: number;
@@ -2680,6 +2875,20 @@ type TsHighlightSpan = {
 	bold: boolean;
 	italic: boolean;
 };
+type UndoHistory = {
+	/**
+	* State the buffer is in (0 = before the first recorded edit)
+	*/
+	current: number;
+	/**
+	* State the buffer was last saved in, if still known
+	*/
+	saved: number | null;
+	/**
+	* Every state, oldest first
+	*/
+	states: Array<UndoHistoryState>;
+};
 type VirtualBufferResult = {
 	/**
 	* The created buffer ID
@@ -3193,6 +3402,17 @@ interface EditorAPI {
 	*/
 	computeLineDiff(oldText: string, newText: string): LineDiffHunk[];
 	/**
+	* Three-way line merge (native diff3; see `fresh_core::merge`):
+	* applies the changes `ours` and `theirs` each made to `base`.
+	* Changes to different lines, and identical changes on both sides,
+	* merge cleanly; the rest become conflict regions, written into
+	* `merged` with `<<<<<<<`/`|||||||`/`=======`/`>>>>>>>` markers.
+	* `regions` tiles all four texts in order, so a caller can map any
+	* line of the merge back to base, ours and theirs. Line numbering
+	* follows `computeLineDiff`.
+	*/
+	mergeThreeWay(base: string, ours: string, theirs: string): MergeResult;
+	/**
 	* Check if a file exists on the path's filesystem (a window's authority,
 	* or the local host for a `LocalPath`).
 	*/
@@ -3211,6 +3431,14 @@ interface EditorAPI {
 	*/
 	readDir(path: string | LocalPath | WindowPath | AuthorityPath): DirEntry[];
 	/**
+	* Compare two directory trees file by file. Each side is walked on its
+	* own path's filesystem, honouring `.gitignore`/`.ignore` files and
+	* skipping `.git`. Returns the differing files sorted by path, with
+	* per-file line stats, plus a count of identical files. Synchronous:
+	* every file on both sides is read, so keep the roots reasonably small.
+	*/
+	compareDirectories(left: string | LocalPath | WindowPath | AuthorityPath, right: string | LocalPath | WindowPath | AuthorityPath): DirDiff;
+	/**
 	* Create a directory (and all parent directories) recursively on the
 	* path's filesystem. Returns true if the directory was created or already
 	* exists.
@@ -3365,7 +3593,8 @@ interface EditorAPI {
 	*/
 	reloadAndApplyTheme(themeName: string): void;
 	/**
-	* Register a TextMate grammar file for a language
+	* Register a TextMate grammar file for a language, or a compiled
+	* tree-sitter parser (.so, .dylib, .dll) for one of Fresh's built-in languages
 	* The grammar will be pending until reload_grammars() is called
 	*/
 	registerGrammar(language: string, grammarPath: string, extensions: string[]): boolean;
@@ -3591,6 +3820,11 @@ interface EditorAPI {
 	*/
 	updateCompositeAlignment(bufferId: number, hunks: TsCompositeHunk[]): boolean;
 	/**
+	* Replace a composite buffer's alignment with aligned sections, for
+	* views with more than two panes (see `TsCompositeSection`)
+	*/
+	updateCompositeSections(bufferId: number, sections: TsCompositeSection[]): boolean;
+	/**
 	* Close a composite buffer
 	*/
 	closeCompositeBuffer(bufferId: number): boolean;
@@ -4876,18 +5110,71 @@ interface EditorAPI {
 		wholeWords?: boolean;
 		sourceBufferId?: number;
 		fileGlob?: string;
+		structural?: boolean;
+		rewrite?: string;
 	}): SearchHandle;
 	/**
 	* Replace matches in a file's buffer (async)
 	* Opens the file if not already in a buffer, applies edits via the buffer model,
-	* and saves. All edits are grouped as a single undo action.
+	* and saves. All edits are grouped as a single undo action. `replacement`
+	* is either one text for every match or an array with one per match; an
+	* array of any other length is rejected and the file is left unchanged.
+	*/
+	replaceInFile(filePath: string, matches: number[][], replacement: string | string[], bufferId?: number): Promise<ReplaceResult>;
+	/**
+	* Rewrite whole lines across files (async). Each edit names a 1-based
+	* line and what it said when the edit was made (`expected`); a line
+	* that says something else now is skipped and reported as a conflict.
+	* Open buffers are edited in memory, one undo step per buffer, and
+	* left unsaved; other files are patched on disk in place.
 	*/
-	replaceInFile(filePath: string, matches: number[][], replacement: string, bufferId?: number): Promise<ReplaceResult>;
+	applyLineEdits(edits: LineEdit[]): Promise<LineEditResult>;
 	/**
 	* Send LSP request (async, returns request_id)
 	*/
 	sendLspRequest(language: string, method: string, params: Record<string, unknown> | null): Promise<unknown>;
 	/**
+	* Get a buffer's undo tree (async). Resolves with the state the
+	* buffer is in, the saved state and every state across all branches.
+	*/
+	getUndoHistory(bufferId: number): Promise<UndoHistory>;
+	/**
+	* Get a buffer's text as of an undo state (async), without moving
+	* the buffer there. Rejects for an unknown state or one past an
+	* edit that cannot be replayed.
+	*/
+	getUndoStateText(bufferId: number, state: number): Promise<string>;
+	/**
+	* Move a buffer to an undo state from `getUndoHistory`, switching
+	* undo branches as needed. The buffer must be the active one.
+	*/
+	undoToState(bufferId: number, state: number): boolean;
+	/**
+	* Find a tree-sitter text object around a byte offset (async).
+	* `object` is a kind with an optional part, e.g. `"function.inner"` or
+	* `"parameter"`; resolves with the innermost enclosing occurrence, or
+	* the next one after the offset, or `null` when there is none or the
+	* buffer's language has no text objects.
+	*/
+	getTextObjectRange(bufferId: number, position: number, object: string): Promise<TextObjectSpan | null>;
+	/**
+	* Prepare an LSP call or type hierarchy at a byte offset (async).
+	* `kind` is `"call"` or `"type"`; resolves with the hierarchy roots,
+	* rejects when no server for the buffer supports it.
+	*/
+	prepareHierarchy(kind: string, bufferId: number, position: number): Promise<HierarchyItem[]>;
+	/**
+	* Expand an LSP hierarchy item one level (async). `direction` is
+	* `"incoming"` / `"outgoing"` for call hierarchies and `"supertypes"` /
+	* `"subtypes"` for type hierarchies.
+	*/
+	expandHierarchy(direction: string, item: HierarchyItem): Promise<HierarchyItem[]>;
+	/**
+	* Send DAP request to the active debug session (async, returns request_id).
+	* Resolves with the response body; rejects when no session is running.
+	*/
+	debugRequest(command: string, args: Record<string, unknown> | null): Promise<unknown>;
+	/**
 	* Spawn a background process (async, returns request_id which is also process_id)
 	*/
 	spawnBackgroundProcess(command: string, args: string[], cwd?: string): ProcessHandle<BackgroundProcessResult>;
@@ -5335,6 +5622,11 @@ interface HookEventMap {
 			column: number;
 		}[];
 	};
+	lsp_hierarchy: {
+		kind: "call" | "type";
+		symbol: string;
+		items: HierarchyItem[];
+	};
 	lsp_server_request: {
 		language: string;
 		method: string;
@@ -5353,6 +5645,26 @@ interface HookEventMap {
 		missing_servers: string[];
 		user_dismissed: boolean;
 	};
+	// ── Debugger (DAP) ───────────────────────────────────────────────────────
+	debug_session_changed: {
+		state: "starting" | "running" | "stopped" | "ended";
+		language: string;
+	};
+	debug_stopped: {
+		thread_id: number | null;
+		reason: string;
+		frames: {
+			id: number;
+			name: string;
+			file: string | null;
+			line: number;
+			column: number;
+		}[];
+	};
+	debug_output: {
+		category: string;
+		output: string;
+	};
 	// ── UI events ────────────────────────────────────────────────────────────
 	action_popup_result: {
 		popup_id: string;
@@ -744,6 +744,158 @@ editor.registerCommand(
   "live-grep-internal"
 );
 
+// ── Editable results ────────────────────────────────────────────
+//
+// In the spirit of Emacs wgrep: the last result set becomes an ordinary
+// editable buffer, one `file:line: content` row per matched line. Edit the
+// content in place (multiple cursors, regex replace, macros — anything the
+// editor can do to text), then Apply writes each changed row back over its
+// source line. The host re-checks every line against what the search saw,
+// so a line that changed underneath us is reported instead of clobbered.
+
+const EDIT_MODE = "live-grep-edit";
+
+interface EditRow {
+  file: string;
+  line: number;
+  /** The line as the search saw it (or as we last wrote it). */
+  original: string;
+}
+
+interface EditSession {
+  bufferId: number;
+  /** Rows keyed by their `file:line` prefix. */
+  rows: Map<string, EditRow>;
+}
+
+let editSession: EditSession | null = null;
+
+const rowKey = (file: string, line: number) => `${file}:${line}`;
+
+// Only file-backed scopes map to a line we can rewrite; terminal
+// scrollback and diagnostic messages aren't file content.
+function editableRows(matches: GrepMatch[]): EditRow[] {
+  const seen = new Set<string>();
+  const rows: EditRow[] = [];
+  for (const m of matches) {
+    if (m.source === "terminals" || m.source === "diagnostics") continue;
+    const key = rowKey(m.file, m.line);
+    if (seen.has(key)) continue;
+    seen.add(key);
+    rows.push({ file: m.file, line: m.line, original: m.content.replace(/\r$/, "") });
+  }
+  return rows;
+}
+
+async function editResults(): Promise<void> {
+  const rows = editableRows(lastResults);
+  if (rows.length === 0) {
+    editor.setStatus(editor.t("status.no_results_to_edit"));
+    return;
+  }
+  const applyKey = editor.getKeybindingLabel("live_grep_apply_edits", EDIT_MODE) ?? "Ctrl+S";
+  const hint = editor.t("edit.hint", { key: applyKey });
+  const entries: TextPropertyEntry[] = [
+    { text: `${hint}\n`, style: { fg: "syntax.comment", italic: true } },
+  ];
+  for (const row of rows) {
+    const prefix = `${rowKey(row.file, row.line)}: `;
+    entries.push({
+      text: `${prefix}${row.original}\n`,
+      inlineOverlays: [
+        { start: 0, end: prefix.length, unit: "char", style: { fg: "syntax.comment" } },
+      ],
+    });
+  }
+  if (editSession) editor.closeBuffer(editSession.bufferId);
+  const res = await editor.createVirtualBuffer({
+    name: editor.t("edit.title", { query: lastQuery }),
+    mode: EDIT_MODE,
+    readOnly: false,
+    editingDisabled: false,
+    showLineNumbers: false,
+    entries,
+  });
+  editSession = {
+    bufferId: res.bufferId,
+    rows: new Map(rows.map((row) => [rowKey(row.file, row.line), row])),
+  };
+}
+registerHandler("live_grep_edit_results", editResults);
+
+async function applyEdits(): Promise<void> {
+  const session = editSession;
+  if (!session || editor.getActiveBufferId() !== session.bufferId) return;
+  const text = await editor.getBufferText(session.bufferId);
+  const edits: LineEdit[] = [];
+  const changed: { row: EditRow; text: string }[] = [];
+  for (const line of text.split("\n")) {
+    // Rows the user mangled past recognition (or the hint line) are
+    // simply not edits.
+    const m = line.match(/^(.+?):(\d+): /);
+    const row = m ? session.rows.get(`${m[1]}:${m[2]}`) : undefined;
+    if (!m || !row) continue;
+    const content = line.slice(m[0].length);
+    if (content === row.original) continue;
+    edits.push({ file: row.file, line: row.line, expected: row.original, text: content });
+    changed.push({ row, text: content });
+  }
+  if (edits.length === 0) {
+    editor.setStatus(editor.t("status.no_edits"));
+    return;
+  }
+
+  const result = await editor.applyLineEdits(edits);
+  const conflicted = new Set(result.conflicts.map((c) => rowKey(c.file, c.line)));
+  for (const { row, text } of changed) {
+    if (!conflicted.has(rowKey(row.file, row.line))) row.original = text;
+  }
+  if (result.conflicts.length === 0) {
+    editor.setStatus(
+      editor.t("status.edits_applied", {
+        lines: String(result.applied),
+        files: String(result.files),
+      })
+    );
+    return;
+  }
+  for (const c of result.conflicts) {
+    editor.warn(
+      `[live_grep] ${c.file}:${c.line} changed since the search` +
+        (c.actual === null ? " (line is gone)" : `: ${c.actual}`)
+    );
+  }
+  const first = result.conflicts[0];
+  editor.setStatus(
+    editor.t("status.edits_conflicts", {
+      lines: String(result.applied),
+      conflicts: String(result.conflicts.length),
+      location: rowKey(first.file, first.line),
+    })
+  );
+}
+registerHandler("live_grep_apply_edits", applyEdits);
+
+editor.on("buffer_closed", (args) => {
+  if (editSession && args.buffer_id === editSession.bufferId) editSession = null;
+});
+
+// Typing goes to the buffer as usual; only the apply key is claimed.
+editor.defineMode(EDIT_MODE, [["C-s", "live_grep_apply_edits"]], false, false);
+
+editor.registerCommand(
+  "%cmd.live_grep_edit_results",
+  "%cmd.live_grep_edit_results_desc",
+  "live_grep_edit_results",
+  null
+);
+editor.registerCommand(
+  "%cmd.live_grep_apply_edits",
+  "%cmd.live_grep_apply_edits_desc",
+  "live_grep_apply_edits",
+  EDIT_MODE
+);
+
 /**
  * Switch to the next *available* registered provider, in priority
  * order, wrapping at the end. Unavailable providers (those whose
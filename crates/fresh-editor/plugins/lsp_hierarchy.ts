@@ -0,0 +1,317 @@
+/// <reference path="./lib/fresh.d.ts" />
+import {
+  col,
+  hintBar,
+  key as widgetKey,
+  raw,
+  tree,
+  treeNode,
+  type TreeNode,
+  WidgetPanel,
+} from "./lib/widgets.ts";
+
+const editor = getEditor();
+
+/**
+ * LSP Hierarchy Plugin
+ *
+ * Renders the `lsp_hierarchy` hook ("Show Call Hierarchy" / "Show Type
+ * Hierarchy") as an expandable tree in the Utility Dock. Children are
+ * fetched one level at a time through `editor.expandHierarchy` the first
+ * time a node is expanded; Enter jumps to the symbol and `t` flips the
+ * direction (callers ↔ callees, subtypes ↔ supertypes).
+ */
+
+type Direction = "incoming" | "outgoing" | "supertypes" | "subtypes";
+
+interface HierarchyNode {
+  item: HierarchyItem;
+  /** `null` until the node has been expanded once. */
+  children: HierarchyNode[] | null;
+}
+
+interface HierarchyPanel {
+  bufferId: number;
+  splitId: number;
+  sourceSplitId: number;
+  widgetPanel: WidgetPanel;
+  kind: "call" | "type";
+  symbol: string;
+  direction: Direction;
+  roots: HierarchyNode[];
+  /** Tree keys (`"0/2/1"`, index path from the roots) currently expanded. */
+  expanded: Set<string>;
+}
+
+const MODE = "lsp-hierarchy";
+const TREE_KEY = "hierarchyTree";
+
+let panel: HierarchyPanel | null = null;
+
+/** Short labels for the LSP `SymbolKind`s hierarchies usually contain. */
+const SYMBOL_KINDS: Record<number, string> = {
+  2: "mod",
+  5: "class",
+  6: "method",
+  9: "ctor",
+  10: "enum",
+  11: "interface",
+  12: "fn",
+  23: "struct",
+  26: "type",
+};
+
+function defaultDirection(kind: "call" | "type"): Direction {
+  return kind === "call" ? "incoming" : "subtypes";
+}
+
+function flipDirection(direction: Direction): Direction {
+  switch (direction) {
+    case "incoming":
+      return "outgoing";
+    case "outgoing":
+      return "incoming";
+    case "supertypes":
+      return "subtypes";
+    case "subtypes":
+      return "supertypes";
+  }
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+function nodeLabel(item: HierarchyItem): TextPropertyEntry {
+  const kind = SYMBOL_KINDS[item.kind] ?? "";
+  const location = `${editor.pathBasename(item.file)}:${item.line}`;
+  const detail = item.detail ? `  ${item.detail}` : "";
+  return {
+    text: `${kind ? kind + " " : ""}${item.name}  ${location}${detail}`,
+    inlineOverlays: kind
+      ? [{ start: 0, end: kind.length, style: { fg: "syntax.keyword" } }]
+      : undefined,
+  };
+}
+
+function flatten(
+  nodes: HierarchyNode[],
+  prefix: string,
+  depth: number,
+  out: { nodes: TreeNode[]; keys: string[] },
+): void {
+  nodes.forEach((node, index) => {
+    const key = prefix ? `${prefix}/${index}` : `${index}`;
+    // Unexpanded nodes may have children; expanded ones know.
+    const hasChildren = node.children === null || node.children.length > 0;
+    out.nodes.push(treeNode(nodeLabel(node.item), { depth, hasChildren }));
+    out.keys.push(key);
+    if (node.children) {
+      flatten(node.children, key, depth + 1, out);
+    }
+  });
+}
+
+function render(): void {
+  if (!panel) return;
+  const flat = { nodes: [] as TreeNode[], keys: [] as string[] };
+  flatten(panel.roots, "", 0, flat);
+  panel.widgetPanel.set(
+    col(
+      raw([
+        {
+          text: editor.t(`title.${panel.direction}`, { symbol: panel.symbol }),
+          style: { fg: "syntax.keyword", bold: true },
+        },
+      ]),
+      tree({
+        nodes: flat.nodes,
+        itemKeys: flat.keys,
+        expandedKeys: [...panel.expanded],
+        key: TREE_KEY,
+      }),
+      hintBar([
+        { keys: "Enter", label: editor.t("hint.open") },
+        { keys: "t", label: editor.t(`hint.show_${flipDirection(panel.direction)}`) },
+        { keys: "Esc", label: editor.t("hint.close") },
+      ]),
+    ),
+  );
+}
+
+function nodeAt(key: string): HierarchyNode | null {
+  if (!panel) return null;
+  let nodes: HierarchyNode[] | null = panel.roots;
+  let node: HierarchyNode | null = null;
+  for (const part of key.split("/")) {
+    node = nodes?.[Number(part)] ?? null;
+    if (!node) return null;
+    nodes = node.children;
+  }
+  return node;
+}
+
+// =============================================================================
+// Expansion and navigation
+// =============================================================================
+
+async function loadChildren(node: HierarchyNode): Promise<void> {
+  if (!panel || node.children !== null) return;
+  const direction = panel.direction;
+  try {
+    const items = await editor.expandHierarchy(direction, node.item);
+    // The direction may have flipped while the request was in flight.
+    if (!panel || panel.direction !== direction) return;
+    node.children = items.map((item) => ({ item, children: null }));
+  } catch (e) {
+    node.children = [];
+    editor.setStatus(editor.t("status.expand_failed", { error: String(e) }));
+  }
+}
+
+async function expandKey(key: string): Promise<void> {
+  const node = nodeAt(key);
+  if (!panel || !node) return;
+  panel.expanded.add(key);
+  await loadChildren(node);
+  render();
+}
+
+function openNode(key: string): void {
+  const node = nodeAt(key);
+  if (!panel || !node) return;
+  const { file, line, column } = node.item;
+  editor.openFileInSplit(panel.sourceSplitId, file, line, column);
+}
+
+editor.on("widget_event", (args) => {
+  if (!panel || args.panel_id !== panel.widgetPanel.id()) return;
+  if (args.widget_key !== TREE_KEY) return;
+  const payload = args.payload as
+    | { key?: string; expanded?: boolean }
+    | undefined;
+  if (typeof payload?.key !== "string") return;
+
+  if (args.event_type === "expand") {
+    if (payload.expanded) {
+      expandKey(payload.key).catch((e) => editor.error(`lsp-hierarchy: ${e}`));
+    } else {
+      panel.expanded.delete(payload.key);
+    }
+  } else if (args.event_type === "activate") {
+    openNode(payload.key);
+  }
+});
+
+// =============================================================================
+// Panel lifecycle
+// =============================================================================
+
+async function showHierarchy(
+  kind: "call" | "type",
+  symbol: string,
+  items: HierarchyItem[],
+): Promise<void> {
+  const roots = items.map((item) => ({ item, children: null }));
+  if (panel) {
+    panel.kind = kind;
+    panel.symbol = symbol;
+    panel.direction = defaultDirection(kind);
+    panel.roots = roots;
+    panel.expanded = new Set();
+  } else {
+    const sourceSplitId = editor.getActiveSplitId();
+    const result = await editor.createVirtualBufferInSplit({
+      name: "*Hierarchy*",
+      mode: MODE,
+      readOnly: true,
+      entries: [],
+      ratio: 0.65,
+      panelId: "lsp-hierarchy-panel",
+      role: "utility_dock",
+      showLineNumbers: false,
+      showCursors: false,
+      editingDisabled: true,
+      // The tree owns its scroll window.
+      scrollable: false,
+    });
+    panel = {
+      bufferId: result.bufferId,
+      splitId: result.splitId ?? editor.getActiveSplitId(),
+      sourceSplitId,
+      widgetPanel: new WidgetPanel(result.bufferId),
+      kind,
+      symbol,
+      direction: defaultDirection(kind),
+      roots,
+      expanded: new Set(),
+    };
+  }
+  // Open the roots right away: a single root with its callers (or
+  // subtypes) visible is what the command was asked for.
+  for (let i = 0; i < roots.length; i++) {
+    panel.expanded.add(`${i}`);
+    await loadChildren(roots[i]);
+  }
+  render();
+}
+
+editor.on("lsp_hierarchy", (data) => {
+  showHierarchy(data.kind, data.symbol, data.items).catch((e) =>
+    editor.error(`lsp-hierarchy: ${e}`)
+  );
+});
+
+editor.on("buffer_closed", (args) => {
+  if (panel && args.buffer_id === panel.bufferId) {
+    panel.widgetPanel.unmount();
+    panel = null;
+  }
+});
+
+async function lsp_hierarchy_flip_direction(): Promise<void> {
+  if (!panel) return;
+  panel.direction = flipDirection(panel.direction);
+  for (const root of panel.roots) root.children = null;
+  panel.expanded = new Set(panel.roots.map((_, i) => `${i}`));
+  for (const root of panel.roots) {
+    await loadChildren(root);
+  }
+  render();
+}
+registerHandler("lsp_hierarchy_flip_direction", lsp_hierarchy_flip_direction);
+
+function lsp_hierarchy_close(): void {
+  if (!panel) return;
+  const { bufferId, splitId, sourceSplitId, widgetPanel } = panel;
+  panel = null;
+  widgetPanel.unmount();
+  editor.closeBuffer(bufferId);
+  if (splitId !== sourceSplitId) {
+    editor.closeSplit(splitId);
+  }
+  editor.focusSplit(sourceSplitId);
+}
+registerHandler("lsp_hierarchy_close", lsp_hierarchy_close);
+
+// Tree navigation is host-owned; route the keys through smart-key dispatch.
+const NAV_KEYS = ["Up", "Down", "Left", "Right", "PageUp", "PageDown", "Home", "End", "Return"];
+for (const name of NAV_KEYS) {
+  registerHandler(`lsp_hierarchy_key_${name}`, () => {
+    panel?.widgetPanel.command(widgetKey(name === "Return" ? "Enter" : name));
+  });
+}
+
+editor.defineMode(
+  MODE,
+  [
+    ...NAV_KEYS.map((name): [string, string] => [name, `lsp_hierarchy_key_${name}`]),
+    ["t", "lsp_hierarchy_flip_direction"],
+    ["q", "lsp_hierarchy_close"],
+    ["Escape", "lsp_hierarchy_close"],
+  ],
+  true,
+  false,
+);
+
+editor.debug("LSP hierarchy plugin initialized");
@@ -12,42 +12,47 @@ const editor = getEditor();
  * 3-Way Merge Conflict Resolution Plugin
  *
  * Provides an interactive merge conflict resolution interface with:
- * - Automatic detection of git conflict markers when files are opened
- * - Multi-panel UI showing OURS, THEIRS, and editable RESULT
- * - Keyboard navigation between conflicts
- * - One-key resolution (accept ours, theirs, or both)
- * - git-mediate style auto-resolution for trivial conflicts
- * - Visual highlighting with intra-line diffing
+ * - Automatic detection of unmerged files when they are opened
+ * - A three-pane OURS | RESULT | THEIRS composite view, aligned row by row
+ * - A native diff3 merge (`editor.mergeThreeWay`): changes to different
+ *   lines, and identical changes on both sides, resolve automatically
+ * - Conflicts re-derived from git's index stages when the markers in the
+ *   file are missing or mangled
+ * - One-key resolution per conflict (ours, theirs, or both in either order)
+ *   with undo/redo of every pick
  *
- * Architecture: Plugin-based implementation following the spec in docs/MERGE.md
- *
- * TODO: Consider rewriting to use the built-in side-by-side diff in the core
- * editor, or use composite buffers for a simpler implementation.
+ * Architecture: the file is split back into its base, ours and theirs
+ * versions, merged natively, and each merge region becomes one aligned
+ * section of the composite buffer. Every pick rebuilds the RESULT pane and
+ * the section list; nothing is written to the file until "Save & Exit".
  */
 
 // =============================================================================
 // Types and Interfaces
 // =============================================================================
 
+type Resolution = "ours" | "theirs" | "both" | "both_theirs_first";
+
+/** The three versions a merge is computed from. */
+interface MergeVersions {
+  base: string;
+  ours: string;
+  theirs: string;
+}
+
 interface ConflictBlock {
   /** Index of this conflict (0-based) */
   index: number;
-  /** Byte offset where the conflict starts (<<<<<<< marker) */
-  startOffset: number;
-  /** Byte offset where the conflict ends (after >>>>>>> marker) */
-  endOffset: number;
+  /** Index of the conflict's region in `MergeState.regions` */
+  region: number;
   /** Content from "ours" side (our branch) */
   ours: string;
-  /** Content from "base" (common ancestor) - may be empty if no diff3 */
+  /** Content from "base" (common ancestor) */
   base: string;
   /** Content from "theirs" side (incoming changes) */
   theirs: string;
-  /** Whether this conflict has been resolved */
-  resolved: boolean;
-  /** Resolution type if resolved */
-  resolution?: "ours" | "theirs" | "both" | "manual";
-  /** The resolved content (if resolved) */
-  resolvedContent?: string;
+  /** How the conflict was resolved, or null while unresolved */
+  resolution: Resolution | null;
 }
 
 interface MergeState {
@@ -57,30 +62,24 @@ interface MergeState {
   sourceBufferId: number | null;
   /** The original file path */
   sourcePath: string | null;
-  /** Original file content (for abort) */
-  originalContent: string;
-  /** List of detected conflicts */
+  /** The versions being merged, with their original line endings */
+  versions: MergeVersions;
+  /** The merge regions, tiling base, ours and theirs in order */
+  regions: MergeRegion[];
+  /** The conflict regions, in file order */
   conflicts: ConflictBlock[];
+  /** Number of changes merged without a conflict */
+  autoResolved: number;
   /** Index of currently selected conflict */
   selectedIndex: number;
-  /** The OURS panel buffer ID */
+  /** Resolution snapshots to return to on undo / redo */
+  undoStack: Array<Array<Resolution | null>>;
+  redoStack: Array<Array<Resolution | null>>;
+  /** The composite view and the three buffers it shows */
+  compositeId: number | null;
   oursPanelId: number | null;
-  /** The THEIRS panel buffer ID */
-  theirsPanelId: number | null;
-  /** The RESULT panel buffer ID (editable) */
   resultPanelId: number | null;
-  /** Split IDs for each panel */
-  oursSplitId: number | null;
-  theirsSplitId: number | null;
-  resultSplitId: number | null;
-  /** Content for OURS side */
-  oursContent: string;
-  /** Content for THEIRS side */
-  theirsContent: string;
-  /** Content for BASE side (common ancestor) */
-  baseContent: string;
-  /** Current result content */
-  resultContent: string;
+  theirsPanelId: number | null;
 }
 
 // =============================================================================
@@ -91,21 +90,24 @@ const mergeState: MergeState = {
   isActive: false,
   sourceBufferId: null,
   sourcePath: null,
-  originalContent: "",
+  versions: { base: "", ours: "", theirs: "" },
+  regions: [],
   conflicts: [],
+  autoResolved: 0,
   selectedIndex: 0,
+  undoStack: [],
+  redoStack: [],
+  compositeId: null,
   oursPanelId: null,
-  theirsPanelId: null,
   resultPanelId: null,
-  oursSplitId: null,
-  theirsSplitId: null,
-  resultSplitId: null,
-  oursContent: "",
-  theirsContent: "",
-  baseContent: "",
-  resultContent: "",
+  theirsPanelId: null,
 };
 
+/** Pane order in the composite view */
+const PANE_OURS = 0;
+const PANE_RESULT = 1;
+const PANE_THEIRS = 2;
+
 // Caches for the buffer_activated / after_file_open detection path.
 // Without these, every tab switch re-spawns `git rev-parse` + `git ls-files`
 // even though their answers rarely change. The detection path is purely a
@@ -152,6 +154,8 @@ async function detectMergeConflictFor(
         ["ls-files", "-u", path],
         fileDir,
       );
+      // A merge started while git ran owns the status bar now.
+      if (mergeState.isActive) return;
       if (lsFiles.exit_code === 0 && lsFiles.stdout.trim().length > 0) {
         editor.setStatus(statusOnDetect());
       }
@@ -166,83 +170,87 @@ async function detectMergeConflictFor(
   return promise;
 }
 
-// =============================================================================
-// Color Definitions
-// =============================================================================
-
-// Theme keys preferred over hard-coded RGB so the look tracks the
-// active theme. `addOverlay` accepts theme key strings directly.
-const OURS_FG_KEY = "editor.diff_add_bg";        // green-ish in most themes
-const THEIRS_FG_KEY = "editor.diff_remove_bg";   // red-ish
-const UNRESOLVED_FG_KEY = "ui.file_status_conflicted_fg";
-
-const colors = {
-  // RGB tuples retained for any future contributor who reaches for them
-  // — the active call sites use the theme keys above. None of these
-  // appear in the currently rendered UI.
-  oursHeader: [100, 200, 255] as [number, number, number],
-  theirsHeader: [255, 180, 100] as [number, number, number],
-  resultHeader: [150, 255, 150] as [number, number, number],
-  conflictBase: [70, 70, 70] as [number, number, number],
-  diffAdd: [50, 100, 50] as [number, number, number],
-  diffDel: [100, 50, 50] as [number, number, number],
-  diffMod: [50, 50, 100] as [number, number, number],
-  selected: [80, 80, 120] as [number, number, number],
-  button: [100, 149, 237] as [number, number, number],
-  resolved: [100, 200, 100] as [number, number, number],
-};
-
 // =============================================================================
 // Mode Definition
 // =============================================================================
 
-// Define merge-conflict mode with keybindings
-// Inherits from "normal" so cursor movement (hjkl) works
-// Uses ] and [ for conflict navigation to avoid overriding j/k
+// Define merge-conflict mode with keybindings for the composite view.
+// Uses ] and [ for conflict navigation to avoid overriding j/k; Tab still
+// moves focus between the panes.
 editor.defineMode(
   "merge-conflict",
   [
-    // Conflict navigation (use ] and [ to avoid overriding j/k cursor movement)
+    // Conflict navigation
     ["]", "merge_next_conflict"],
     ["[", "merge_prev_conflict"],
-    // Also support n/p for navigation
     ["n", "merge_next_conflict"],
     ["p", "merge_prev_conflict"],
 
     // Resolution actions
-    ["u", "merge_use_ours"],      // Use ours
-    ["t", "merge_take_theirs"],   // Take theirs
-    ["b", "merge_use_both"],      // Use both
+    ["u", "merge_use_ours"],                 // Use ours
+    ["t", "merge_take_theirs"],              // Take theirs
+    ["b", "merge_use_both"],                 // Ours, then theirs
+    ["B", "merge_use_both_theirs_first"],    // Theirs, then ours
+    ["x", "merge_reset_conflict"],           // Back to unresolved
+
+    // History of picks
+    ["z", "merge_undo"],
+    ["Z", "merge_redo"],
+    ["C-z", "merge_undo"],
+    ["C-y", "merge_redo"],
 
     // Completion
-    ["s", "merge_save_and_exit"], // Save & exit
-    ["q", "merge_abort"],         // Abort
+    ["s", "merge_save_and_exit"],
+    ["q", "merge_abort"],
 
     // Help
     ["?", "merge_show_help"],
   ],
-  true // read-only for navigation panels
+  true
 );
 
-// Define merge-result mode for the editable RESULT panel
-editor.defineMode(
-  "merge-result",
-  [
-    // Navigation - use C-j/C-k to avoid conflicting with C-p (command palette)
-    ["C-j", "merge_next_conflict"],
-    ["C-k", "merge_prev_conflict"],
+// =============================================================================
+// Line Helpers
+// =============================================================================
 
-    // Resolution shortcuts
-    ["C-u", "merge_use_ours"],
-    ["C-t", "merge_take_theirs"],
-    ["C-b", "merge_use_both"],
+/** Split text into lines, each keeping its terminator. */
+function splitLines(text: string): string[] {
+  const lines: string[] = [];
+  let start = 0;
+  while (start < text.length) {
+    const nl = text.indexOf("\n", start);
+    if (nl < 0) {
+      lines.push(text.substring(start));
+      break;
+    }
+    lines.push(text.substring(start, nl + 1));
+    start = nl + 1;
+  }
+  return lines;
+}
 
-    // Completion
-    ["C-s", "merge_save_and_exit"],
-    ["C-q", "merge_abort"],
-  ],
-  false // editable
-);
+/** Lines `[start, start + count)` of `lines`, joined back into text. */
+function sliceLines(lines: string[], start: number, count: number): string {
+  return lines.slice(start, start + count).join("");
+}
+
+/** The line terminator the file uses, so combined hunks keep it. */
+function lineEnding(text: string): string {
+  return text.includes("\r\n") ? "\r\n" : "\n";
+}
+
+/** Concatenate two hunks, terminating the first one's last line if needed. */
+function joinHunks(first: string, second: string, eol: string): string {
+  if (first.length > 0 && second.length > 0 && !first.endsWith("\n")) {
+    return first + eol + second;
+  }
+  return first + second;
+}
+
+/** Panes show text without carriage returns; line numbering is unchanged. */
+function displayText(text: string): string {
+  return text.replace(/\r\n/g, "\n");
+}
 
 // =============================================================================
 // Conflict Detection and Parsing
@@ -257,63 +265,97 @@ function hasConflictMarkers(content: string): boolean {
          content.includes(">>>>>>>");
 }
 
-/**
- * Parse conflict markers from file content
- * Supports both 2-way (no base) and 3-way (with base via diff3) conflicts
- */
-function parseConflicts(content: string): ConflictBlock[] {
-  const conflicts: ConflictBlock[] = [];
-
-  // Regex to match conflict blocks
-  // Supports optional base section (||||||| marker)
-  // Key: use ^ anchors to ensure markers are at start of lines (multiline mode)
-  // Note: use \r?\n to handle both LF and CRLF line endings
-  const conflictRegex = /^<<<<<<<[^\r\n]*\r?\n([\s\S]*?)(?:^\|\|\|\|\|\|\|[^\r\n]*\r?\n([\s\S]*?))?^=======\r?\n([\s\S]*?)^>>>>>>>[^\r\n]*$/gm;
-
-  let match;
-  let index = 0;
-
-  while ((match = conflictRegex.exec(content)) !== null) {
-    const startOffset = match.index;
-    const endOffset = match.index + match[0].length;
-
-    conflicts.push({
-      index: index++,
-      startOffset,
-      endOffset,
-      ours: match[1] || "",
-      base: match[2] || "",
-      theirs: match[3] || "",
-      resolved: false,
-    });
-  }
+type MarkerKind = "start" | "base" | "separator" | "end" | null;
 
-  return conflicts;
+function markerKind(line: string): MarkerKind {
+  const bare = line.replace(/\r?\n$/, "");
+  if (/^<{7}(\s|$)/.test(bare)) return "start";
+  if (/^\|{7}(\s|$)/.test(bare)) return "base";
+  if (bare === "=======") return "separator";
+  if (/^>{7}(\s|$)/.test(bare)) return "end";
+  return null;
 }
 
 /**
- * Extract non-conflict sections and build initial result content
+ * Reconstruct the whole-file base, ours and theirs versions from the
+ * conflict markers in `content`.
+ *
+ * Returns null when the markers are mangled (out of order, nested or
+ * unterminated) and so cannot be trusted. `hasBase` is false when any
+ * block lacks a diff3 `|||||||` section, in which case the base of that
+ * block is unknown and recorded as empty.
  */
-function buildInitialResult(content: string, conflicts: ConflictBlock[]): string {
-  if (conflicts.length === 0) return content;
-
-  let result = "";
-  let lastEnd = 0;
-
-  for (const conflict of conflicts) {
-    // Add non-conflict text before this conflict
-    result += content.substring(lastEnd, conflict.startOffset);
-
-    // Add a placeholder for the conflict
-    result += `<<<CONFLICT_${conflict.index}>>>`;
-
-    lastEnd = conflict.endOffset;
+function versionsFromMarkers(content: string): (MergeVersions & {
+  blocks: number;
+  hasBase: boolean;
+}) | null {
+  const base: string[] = [];
+  const ours: string[] = [];
+  const theirs: string[] = [];
+  let state: "out" | "ours" | "base" | "theirs" = "out";
+  let blocks = 0;
+  let hasBase = true;
+  let blockHasBase = false;
+
+  for (const line of splitLines(content)) {
+    const marker = markerKind(line);
+    switch (state) {
+      case "out":
+        if (marker === "start") {
+          state = "ours";
+          blockHasBase = false;
+        } else if (marker === "base" || marker === "end") {
+          return null;
+        } else {
+          // A lone "=======" outside a conflict is ordinary content.
+          base.push(line);
+          ours.push(line);
+          theirs.push(line);
+        }
+        break;
+      case "ours":
+        if (marker === "base") {
+          state = "base";
+          blockHasBase = true;
+        } else if (marker === "separator") {
+          state = "theirs";
+        } else if (marker !== null) {
+          return null;
+        } else {
+          ours.push(line);
+        }
+        break;
+      case "base":
+        if (marker === "separator") {
+          state = "theirs";
+        } else if (marker !== null) {
+          return null;
+        } else {
+          base.push(line);
+        }
+        break;
+      case "theirs":
+        if (marker === "end") {
+          state = "out";
+          blocks++;
+          hasBase = hasBase && blockHasBase;
+        } else if (marker !== null) {
+          return null;
+        } else {
+          theirs.push(line);
+        }
+        break;
+    }
   }
 
-  // Add remaining text after last conflict
-  result += content.substring(lastEnd);
-
-  return result;
+  if (state !== "out") return null;
+  return {
+    base: base.join(""),
+    ours: ours.join(""),
+    theirs: theirs.join(""),
+    blocks,
+    hasBase,
+  };
 }
 
 // =============================================================================
@@ -323,11 +365,7 @@ function buildInitialResult(content: string, conflicts: ConflictBlock[]): string
 /**
  * Fetch the base (common ancestor), ours, and theirs versions from git
  */
-async function fetchGitVersions(filePath: string): Promise<{
-  base: string;
-  ours: string;
-  theirs: string;
-} | null> {
+async function fetchGitVersions(filePath: string): Promise<MergeVersions | null> {
   try {
     // Resolve the file's repo (its own sub-project in a monorepo); all three
     // `git show :N:<path>` reads run there, against the repo-relative path.
@@ -349,14 +387,18 @@ async function fetchGitVersions(filePath: string): Promise<{
     const theirsResult = await git(editor, repo, ["show", `:3:${relativePath}`]);
     editor.debug(`fetchGitVersions: theirs exit_code=${theirsResult.exit_code}, stdout length=${theirsResult.stdout.length}`);
 
-    // Get BASE version (common ancestor, :1:)
+    // Get BASE version (common ancestor, :1:) - absent for add/add conflicts
     const baseResult = await git(editor, repo, ["show", `:1:${relativePath}`]);
     editor.debug(`fetchGitVersions: base exit_code=${baseResult.exit_code}, stdout length=${baseResult.stdout.length}`);
 
+    if (oursResult.exit_code !== 0 || theirsResult.exit_code !== 0) {
+      return null;
+    }
+
     return {
       base: baseResult.exit_code === 0 ? baseResult.stdout : "",
-      ours: oursResult.exit_code === 0 ? oursResult.stdout : "",
-      theirs: theirsResult.exit_code === 0 ? theirsResult.stdout : "",
+      ours: oursResult.stdout,
+      theirs: theirsResult.stdout,
     };
   } catch (e) {
     editor.debug(`Failed to fetch git versions: ${e}`);
@@ -364,698 +406,229 @@ async function fetchGitVersions(filePath: string): Promise<{
   }
 }
 
-// =============================================================================
-// Auto-Resolution (git-mediate style)
-// =============================================================================
-
 /**
- * Attempt to auto-resolve trivial conflicts using git-mediate logic
- * A conflict is trivially resolvable if only one side changed from base
+ * Pick the versions to merge.
+ *
+ * Intact diff3 markers win: they also carry whatever the user already
+ * edited outside the conflicts. Mangled markers, and 2-way markers (which
+ * drop the base), fall back to git's index stages.
  */
-function autoResolveConflicts(conflicts: ConflictBlock[]): void {
-  for (const conflict of conflicts) {
-    if (conflict.resolved) continue;
-
-    // If we have base content, check for trivial resolution
-    if (conflict.base) {
-      const oursChanged = conflict.ours.trim() !== conflict.base.trim();
-      const theirsChanged = conflict.theirs.trim() !== conflict.base.trim();
-
-      if (oursChanged && !theirsChanged) {
-        // Only ours changed - use ours
-        conflict.resolved = true;
-        conflict.resolution = "ours";
-        conflict.resolvedContent = conflict.ours;
-        editor.debug(`Auto-resolved conflict ${conflict.index}: using OURS (theirs unchanged)`);
-      } else if (!oursChanged && theirsChanged) {
-        // Only theirs changed - use theirs
-        conflict.resolved = true;
-        conflict.resolution = "theirs";
-        conflict.resolvedContent = conflict.theirs;
-        editor.debug(`Auto-resolved conflict ${conflict.index}: using THEIRS (ours unchanged)`);
-      } else if (!oursChanged && !theirsChanged) {
-        // Neither changed (identical) - use either
-        conflict.resolved = true;
-        conflict.resolution = "ours";
-        conflict.resolvedContent = conflict.ours;
-        editor.debug(`Auto-resolved conflict ${conflict.index}: both identical to base`);
-      }
-      // If both changed differently, leave unresolved
-    }
-
-    // Check if ours and theirs are identical
-    if (!conflict.resolved && conflict.ours.trim() === conflict.theirs.trim()) {
-      conflict.resolved = true;
-      conflict.resolution = "ours";
-      conflict.resolvedContent = conflict.ours;
-      editor.debug(`Auto-resolved conflict ${conflict.index}: ours and theirs identical`);
-    }
+async function loadVersions(
+  filePath: string,
+  content: string,
+): Promise<{ versions: MergeVersions; rederived: boolean } | null> {
+  const parsed = versionsFromMarkers(content);
+  if (parsed && parsed.blocks > 0 && parsed.hasBase) {
+    return { versions: parsed, rederived: false };
   }
-}
-
-// =============================================================================
-// Word-Level Diff
-// =============================================================================
 
-/**
- * Simple word-level diff for intra-line highlighting
- */
-function computeWordDiff(a: string, b: string): Array<{
-  type: "same" | "add" | "del" | "mod";
-  aStart: number;
-  aEnd: number;
-  bStart: number;
-  bEnd: number;
-}> {
-  // Split into words (preserving whitespace positions)
-  const aWords = a.split(/(\s+)/);
-  const bWords = b.split(/(\s+)/);
-
-  const diffs: Array<{
-    type: "same" | "add" | "del" | "mod";
-    aStart: number;
-    aEnd: number;
-    bStart: number;
-    bEnd: number;
-  }> = [];
-
-  let aPos = 0;
-  let bPos = 0;
-  let aIdx = 0;
-  let bIdx = 0;
-
-  // Simple LCS-based diff (for short texts)
-  while (aIdx < aWords.length || bIdx < bWords.length) {
-    if (aIdx >= aWords.length) {
-      // Rest of b is additions
-      const bWord = bWords[bIdx];
-      diffs.push({
-        type: "add",
-        aStart: aPos,
-        aEnd: aPos,
-        bStart: bPos,
-        bEnd: bPos + bWord.length,
-      });
-      bPos += bWord.length;
-      bIdx++;
-    } else if (bIdx >= bWords.length) {
-      // Rest of a is deletions
-      const aWord = aWords[aIdx];
-      diffs.push({
-        type: "del",
-        aStart: aPos,
-        aEnd: aPos + aWord.length,
-        bStart: bPos,
-        bEnd: bPos,
-      });
-      aPos += aWord.length;
-      aIdx++;
-    } else if (aWords[aIdx] === bWords[bIdx]) {
-      // Same
-      const word = aWords[aIdx];
-      diffs.push({
-        type: "same",
-        aStart: aPos,
-        aEnd: aPos + word.length,
-        bStart: bPos,
-        bEnd: bPos + word.length,
-      });
-      aPos += word.length;
-      bPos += word.length;
-      aIdx++;
-      bIdx++;
-    } else {
-      // Different - mark as modification
-      const aWord = aWords[aIdx];
-      const bWord = bWords[bIdx];
-      diffs.push({
-        type: "mod",
-        aStart: aPos,
-        aEnd: aPos + aWord.length,
-        bStart: bPos,
-        bEnd: bPos + bWord.length,
-      });
-      aPos += aWord.length;
-      bPos += bWord.length;
-      aIdx++;
-      bIdx++;
-    }
+  const staged = await fetchGitVersions(filePath);
+  if (staged) {
+    // Only worth telling the user about when the markers were unusable.
+    const rederived = !parsed || parsed.blocks === 0;
+    return { versions: staged, rederived };
   }
 
-  return diffs;
+  if (parsed && parsed.blocks > 0) {
+    return { versions: parsed, rederived: false };
+  }
+  return null;
 }
 
 // =============================================================================
-// View Rendering - Full File Content (JetBrains-style)
+// Merge Model
 // =============================================================================
 
 /**
- * Build entries showing the full file content for OURS or THEIRS
- * This displays the complete file from git, highlighting conflict regions
+ * Run the native three-way merge and collect its conflict regions.
  */
-function buildFullFileEntries(side: "ours" | "theirs"): TextPropertyEntry[] {
-  const entries: TextPropertyEntry[] = [];
-  const content = side === "ours" ? mergeState.oursContent : mergeState.theirsContent;
-
-  // If we don't have the git version, fall back to showing conflict regions only
-  if (!content) {
-    entries.push({
-      text: editor.t("panel.git_unavailable") + "\n\n",
-      properties: { type: "warning" },
-    });
-
-    // Show conflict regions from parsed conflicts
-    for (const conflict of mergeState.conflicts) {
-      const conflictContent = side === "ours" ? conflict.ours : conflict.theirs;
-      const isSelected = conflict.index === mergeState.selectedIndex;
-
-      entries.push({
-        text: `--- ${editor.t("panel.conflict", { index: String(conflict.index + 1) })} ---\n`,
-        properties: {
-          type: "conflict-header",
-          conflictIndex: conflict.index,
-          selected: isSelected,
-        },
-      });
+function computeMerge(versions: MergeVersions): void {
+  const result = editor.mergeThreeWay(versions.base, versions.ours, versions.theirs);
+  const baseLines = splitLines(versions.base);
+  const oursLines = splitLines(versions.ours);
+  const theirsLines = splitLines(versions.theirs);
 
-      entries.push({
-        text: (conflictContent || editor.t("panel.empty")) + "\n",
-        properties: {
-          type: "conflict-content",
-          conflictIndex: conflict.index,
-          side: side,
-        },
+  mergeState.regions = result.regions;
+  mergeState.conflicts = [];
+  mergeState.autoResolved = 0;
+
+  result.regions.forEach((region, regionIndex) => {
+    if (region.kind === "conflict") {
+      mergeState.conflicts.push({
+        index: mergeState.conflicts.length,
+        region: regionIndex,
+        ours: sliceLines(oursLines, region.oursStart, region.oursCount),
+        base: sliceLines(baseLines, region.baseStart, region.baseCount),
+        theirs: sliceLines(theirsLines, region.theirsStart, region.theirsCount),
+        resolution: null,
       });
+    } else if (region.kind !== "unchanged") {
+      mergeState.autoResolved++;
     }
-    return entries;
-  }
-
-  // Show full file content with conflict regions highlighted
-  // The content from git is the clean version without markers
-  const lines = content.split("\n");
-  for (let i = 0; i < lines.length; i++) {
-    const line = lines[i];
-    // Check if this line is in a conflict region
-    const inConflict = isLineInConflict(i, side);
-
-    entries.push({
-      text: line + (i < lines.length - 1 ? "\n" : ""),
-      properties: {
-        type: inConflict ? "conflict-line" : "normal-line",
-        lineNumber: i + 1,
-        side: side,
-        ...(inConflict ? { conflictIndex: getConflictIndexForLine(i, side) } : {}),
-      },
-    });
-  }
-
-  return entries;
+  });
 }
 
 /**
- * Check if a line number falls within a conflict region
+ * Text a conflict contributes to the RESULT. Unresolved conflicts show
+ * the base, so the RESULT pane reads as "what nobody has decided yet".
  */
-function isLineInConflict(_lineNumber: number, _side: "ours" | "theirs"): boolean {
-  // For now, we don't have line mapping from git versions to original file
-  // This would require proper diff/alignment between versions
-  // TODO: Implement proper line-to-conflict mapping
-  return false;
+function conflictText(conflict: ConflictBlock, eol: string): string {
+  switch (conflict.resolution) {
+    case "ours":
+      return conflict.ours;
+    case "theirs":
+      return conflict.theirs;
+    case "both":
+      return joinHunks(conflict.ours, conflict.theirs, eol);
+    case "both_theirs_first":
+      return joinHunks(conflict.theirs, conflict.ours, eol);
+    default:
+      return conflict.base;
+  }
 }
 
-/**
- * Get the conflict index for a line number
- */
-function getConflictIndexForLine(_lineNumber: number, _side: "ours" | "theirs"): number {
-  return 0;
+interface ResultLayout {
+  /** The RESULT text, with the file's original line endings */
+  text: string;
+  /** First RESULT line and line count of each merge region */
+  starts: number[];
+  counts: number[];
 }
 
 /**
- * Build entries showing the merged result content
- * This shows the file with resolved/unresolved conflict regions
+ * Assemble the RESULT text from the merge regions and current picks.
  */
-function buildResultFileEntries(): TextPropertyEntry[] {
-  const entries: TextPropertyEntry[] = [];
-
-  // Build the result by combining non-conflict regions with resolved conflicts
-  const originalContent = mergeState.originalContent;
-  if (!originalContent) {
-    entries.push({
-      text: "(No content available)\n",
-      properties: { type: "error" },
-    });
-    return entries;
-  }
-
-  // Parse the original content and replace conflict regions with resolutions
-  let result = originalContent;
-
-  // Process conflicts in reverse order to maintain correct positions
-  const sortedConflicts = [...mergeState.conflicts].sort((a, b) => b.startOffset - a.startOffset);
-
-  for (const conflict of sortedConflicts) {
-    let replacement: string;
-
-    if (conflict.resolved && conflict.resolvedContent !== undefined) {
-      replacement = conflict.resolvedContent;
+function buildResult(): ResultLayout {
+  const { ours, theirs } = mergeState.versions;
+  const oursLines = splitLines(ours);
+  const theirsLines = splitLines(theirs);
+  const eol = lineEnding(ours);
+  const conflictByRegion = new Map<number, ConflictBlock>();
+  for (const conflict of mergeState.conflicts) {
+    conflictByRegion.set(conflict.region, conflict);
+  }
+
+  let text = "";
+  let line = 0;
+  const starts: number[] = [];
+  const counts: number[] = [];
+  mergeState.regions.forEach((region, regionIndex) => {
+    let chunk: string;
+    const conflict = conflictByRegion.get(regionIndex);
+    if (conflict) {
+      chunk = conflictText(conflict, eol);
+    } else if (region.kind === "theirs") {
+      chunk = sliceLines(theirsLines, region.theirsStart, region.theirsCount);
     } else {
-      // Show unresolved conflict with markers
-      replacement = `<<<<<<< OURS\n${conflict.ours || ""}\n=======\n${conflict.theirs || ""}\n>>>>>>> THEIRS`;
+      // unchanged, ours and both all read from ours.
+      chunk = sliceLines(oursLines, region.oursStart, region.oursCount);
     }
-
-    // Replace the conflict region in the result
-    const before = result.substring(0, conflict.startOffset);
-    const after = result.substring(conflict.endOffset);
-    result = before + replacement + after;
-  }
-
-  // Now display the result content
-  const lines = result.split("\n");
-  for (let i = 0; i < lines.length; i++) {
-    const line = lines[i];
-    const isConflictMarker = line.startsWith("<<<<<<<") || line.startsWith("=======") || line.startsWith(">>>>>>>");
-
-    entries.push({
-      text: line + (i < lines.length - 1 ? "\n" : ""),
-      properties: {
-        type: isConflictMarker ? "conflict-marker" : "result-line",
-        lineNumber: i + 1,
-      },
-    });
-  }
-
-  return entries;
-}
-
-// =============================================================================
-// View Rendering - Summary Style (Legacy)
-// =============================================================================
-
-/**
- * Build entries for OURS panel (summary style)
- */
-function buildOursEntries(): TextPropertyEntry[] {
-  const entries: TextPropertyEntry[] = [];
-
-  // Header
-  entries.push({
-    text: "═══════════════════════════════════════════════════════════════════════════════\n",
-    properties: { type: "separator" },
-  });
-  entries.push({
-    text: "  " + editor.t("panel.ours_header") + "\n",
-    properties: { type: "header", panel: "ours" },
+    const count = splitLines(chunk).length;
+    starts.push(line);
+    counts.push(count);
+    text = joinHunks(text, chunk, eol);
+    line += count;
   });
-  entries.push({
-    text: "═══════════════════════════════════════════════════════════════════════════════\n",
-    properties: { type: "separator" },
-  });
-
-  // Show each conflict's OURS side
-  for (const conflict of mergeState.conflicts) {
-    const isSelected = conflict.index === mergeState.selectedIndex;
-    const marker = isSelected ? "> " : "  ";
-    const status = conflict.resolved ? editor.t("panel.resolved") : editor.t("panel.pending");
-
-    entries.push({
-      text: `\n${marker}${editor.t("panel.conflict", { index: String(conflict.index + 1) })} ${status}\n`,
-      properties: {
-        type: "conflict-header",
-        conflictIndex: conflict.index,
-        selected: isSelected,
-        resolved: conflict.resolved,
-      },
-    });
-
-    entries.push({
-      text: "─────────────────────────────────────────────────────────────────────────────\n",
-      properties: { type: "separator" },
-    });
 
-    // Content
-    const content = conflict.ours || editor.t("panel.empty");
-    for (const line of content.split("\n")) {
-      entries.push({
-        text: `  ${line}\n`,
-        properties: {
-          type: "conflict-content",
-          conflictIndex: conflict.index,
-          side: "ours",
-        },
-      });
-    }
-  }
-
-  return entries;
+  return { text, starts, counts };
 }
 
 /**
- * Build entries for THEIRS panel
+ * One aligned composite section per merge region: unchanged text is
+ * context, merged and resolved changes are additions, and conflicts
+ * still waiting for a pick are modifications.
  */
-function buildTheirsEntries(): TextPropertyEntry[] {
-  const entries: TextPropertyEntry[] = [];
-
-  // Header
-  entries.push({
-    text: "═══════════════════════════════════════════════════════════════════════════════\n",
-    properties: { type: "separator" },
-  });
-  entries.push({
-    text: "  " + editor.t("panel.theirs_header") + "\n",
-    properties: { type: "header", panel: "theirs" },
-  });
-  entries.push({
-    text: "═══════════════════════════════════════════════════════════════════════════════\n",
-    properties: { type: "separator" },
-  });
-
-  // Show each conflict's THEIRS side
+function buildSections(layout: ResultLayout): TsCompositeSection[] {
+  const resolvedRegions = new Set<number>();
+  const unresolvedRegions = new Set<number>();
   for (const conflict of mergeState.conflicts) {
-    const isSelected = conflict.index === mergeState.selectedIndex;
-    const marker = isSelected ? "> " : "  ";
-    const status = conflict.resolved ? editor.t("panel.resolved") : editor.t("panel.pending");
-
-    entries.push({
-      text: `\n${marker}${editor.t("panel.conflict", { index: String(conflict.index + 1) })} ${status}\n`,
-      properties: {
-        type: "conflict-header",
-        conflictIndex: conflict.index,
-        selected: isSelected,
-        resolved: conflict.resolved,
-      },
-    });
-
-    entries.push({
-      text: "─────────────────────────────────────────────────────────────────────────────\n",
-      properties: { type: "separator" },
-    });
-
-    // Content
-    const content = conflict.theirs || editor.t("panel.empty");
-    for (const line of content.split("\n")) {
-      entries.push({
-        text: `  ${line}\n`,
-        properties: {
-          type: "conflict-content",
-          conflictIndex: conflict.index,
-          side: "theirs",
-        },
-      });
-    }
+    (conflict.resolution ? resolvedRegions : unresolvedRegions).add(conflict.region);
   }
 
-  return entries;
-}
-
-/**
- * Build entries for RESULT panel
- */
-function buildResultEntries(): TextPropertyEntry[] {
-  const entries: TextPropertyEntry[] = [];
-
-  // Header
-  entries.push({
-    text: "═══════════════════════════════════════════════════════════════════════════════\n",
-    properties: { type: "separator" },
-  });
-  entries.push({
-    text: "  " + editor.t("panel.result_header") + "\n",
-    properties: { type: "header", panel: "result" },
-  });
-  entries.push({
-    text: "═══════════════════════════════════════════════════════════════════════════════\n",
-    properties: { type: "separator" },
-  });
-
-  // Build result content
-  const unresolvedCount = mergeState.conflicts.filter(c => !c.resolved).length;
-
-  if (unresolvedCount > 0) {
-    entries.push({
-      text: `\n  ⚠ ${editor.t("panel.remaining", { count: String(unresolvedCount) })}\n\n`,
-      properties: { type: "warning" },
-    });
-  } else {
-    entries.push({
-      text: "\n  ✓ " + editor.t("panel.all_resolved") + "\n\n",
-      properties: { type: "success" },
-    });
-  }
-
-  // Show resolved content or action buttons for each conflict
-  for (const conflict of mergeState.conflicts) {
-    const isSelected = conflict.index === mergeState.selectedIndex;
-    const marker = isSelected ? "> " : "  ";
-
-    entries.push({
-      text: `${marker}${editor.t("panel.conflict", { index: String(conflict.index + 1) })}:\n`,
-      properties: {
-        type: "conflict-header",
-        conflictIndex: conflict.index,
-        selected: isSelected,
-      },
-    });
-
-    if (conflict.resolved && conflict.resolvedContent !== undefined) {
-      // Show resolved content
-      entries.push({
-        text: `  ${editor.t("panel.resolved_with", { resolution: conflict.resolution || "" })}\n`,
-        properties: { type: "resolution-info", resolution: conflict.resolution },
-      });
-
-      for (const line of conflict.resolvedContent.split("\n")) {
-        entries.push({
-          text: `  ${line}\n`,
-          properties: {
-            type: "resolved-content",
-            conflictIndex: conflict.index,
-          },
-        });
-      }
-    } else {
-      // Show clickable action buttons
-      // Each button is a separate entry with onClick for mouse support
-      entries.push({
-        text: "  << ",
-        properties: { type: "action-prefix" },
-      });
-      entries.push({
-        text: editor.t("btn.accept_ours"),
-        properties: {
-          type: "action-button",
-          conflictIndex: conflict.index,
-          onClick: "merge_use_ours",
-        },
-      });
-      entries.push({
-        text: " | ",
-        properties: { type: "action-separator" },
-      });
-      entries.push({
-        text: editor.t("btn.accept_theirs"),
-        properties: {
-          type: "action-button",
-          conflictIndex: conflict.index,
-          onClick: "merge_take_theirs",
-        },
-      });
-      entries.push({
-        text: " | ",
-        properties: { type: "action-separator" },
-      });
-      entries.push({
-        text: editor.t("btn.both"),
-        properties: {
-          type: "action-button",
-          conflictIndex: conflict.index,
-          onClick: "merge_use_both",
-        },
-      });
-      entries.push({
-        text: " >>\n",
-        properties: { type: "action-suffix" },
-      });
+  return mergeState.regions.map((region, regionIndex) => {
+    let kind = "addition";
+    if (region.kind === "unchanged") {
+      kind = "context";
+    } else if (unresolvedRegions.has(regionIndex)) {
+      kind = "modification";
     }
-
-    entries.push({
-      text: "─────────────────────────────────────────────────────────────────────────────\n",
-      properties: { type: "separator" },
-    });
-  }
-
-  // Help bar with clickable buttons
-  entries.push({
-    text: "\n",
-    properties: { type: "blank" },
-  });
-  // Navigation
-  entries.push({
-    text: editor.t("btn.next"),
-    properties: { type: "help-button", onClick: "merge_next_conflict" },
-  });
-  entries.push({
-    text: " ",
-    properties: { type: "help-separator" },
-  });
-  entries.push({
-    text: editor.t("btn.prev"),
-    properties: { type: "help-button", onClick: "merge_prev_conflict" },
-  });
-  entries.push({
-    text: " | ",
-    properties: { type: "help-separator" },
-  });
-  // Resolution
-  entries.push({
-    text: editor.t("btn.use_ours"),
-    properties: { type: "help-button", onClick: "merge_use_ours" },
-  });
-  entries.push({
-    text: " ",
-    properties: { type: "help-separator" },
-  });
-  entries.push({
-    text: editor.t("btn.take_theirs"),
-    properties: { type: "help-button", onClick: "merge_take_theirs" },
-  });
-  entries.push({
-    text: " ",
-    properties: { type: "help-separator" },
-  });
-  entries.push({
-    text: editor.t("btn.both"),
-    properties: { type: "help-button", onClick: "merge_use_both" },
-  });
-  entries.push({
-    text: " | ",
-    properties: { type: "help-separator" },
-  });
-  // Completion
-  entries.push({
-    text: editor.t("btn.save_exit"),
-    properties: { type: "help-button", onClick: "merge_save_and_exit" },
-  });
-  entries.push({
-    text: " ",
-    properties: { type: "help-separator" },
-  });
-  entries.push({
-    text: editor.t("btn.abort"),
-    properties: { type: "help-button", onClick: "merge_abort" },
-  });
-  entries.push({
-    text: "\n",
-    properties: { type: "help-newline" },
+    return {
+      lines: [
+        [region.oursStart, region.oursCount],
+        [layout.starts[regionIndex], layout.counts[regionIndex]],
+        [region.theirsStart, region.theirsCount],
+      ],
+      kind,
+    };
   });
-
-  return entries;
 }
 
-/**
- * Apply visual highlighting to panels
- */
-function applyHighlighting(): void {
-  // Highlight OURS panel
-  if (mergeState.oursPanelId !== null) {
-    editor.clearAllOverlays(mergeState.oursPanelId);
-    highlightPanel(mergeState.oursPanelId, "ours");
-  }
-
-  // Highlight THEIRS panel
-  if (mergeState.theirsPanelId !== null) {
-    editor.clearAllOverlays(mergeState.theirsPanelId);
-    highlightPanel(mergeState.theirsPanelId, "theirs");
-  }
-
-  // Highlight RESULT panel
-  if (mergeState.resultPanelId !== null) {
-    editor.clearAllOverlays(mergeState.resultPanelId);
-    highlightResultPanel(mergeState.resultPanelId);
-  }
-}
-
-/**
- * Highlight a side panel (OURS or THEIRS)
- * Note: We compute content from our entries since getBufferText was removed
- *
- * TODO: Implement proper conflict region highlighting:
- * - Find actual conflict regions in git content by searching for conflict.ours/conflict.theirs text
- * - Highlight each conflict region with appropriate color (conflictOurs/conflictTheirs)
- * - Use different highlight for selected conflict vs unselected
- * - Consider using line-based highlighting for better visual effect
- */
-function highlightPanel(bufferId: number, side: "ours" | "theirs"): void {
-  // Build content from entries (same as what we set on the buffer)
-  const entries = buildFullFileEntries(side);
-  const content = entries.map(e => e.text).join("");
-  const lines = content.split("\n");
-
-  let byteOffset = 0;
-  const conflictColor = side === "ours" ? OURS_FG_KEY : THEIRS_FG_KEY;
-
-  for (let lineIdx = 0; lineIdx < lines.length; lineIdx++) {
-    const line = lines[lineIdx];
-    const lineStart = byteOffset;
-    const lineEnd = byteOffset + line.length;
-
-    // Highlight conflict header lines
-    if (line.includes("--- Conflict")) {
-      editor.addOverlay(bufferId, `merge-conflict-header-${lineIdx}`, lineStart, lineEnd, {
-        fg: conflictColor,
-        underline: true,
-      });
-    }
-
-    byteOffset = lineEnd + 1;
-  }
-}
+// =============================================================================
+// View Management
+// =============================================================================
 
 /**
- * Highlight the RESULT panel
- * Note: We compute content from our entries since getBufferText was removed
+ * Create the composite merge view (JetBrains-style: OURS | RESULT | THEIRS)
  */
-function highlightResultPanel(bufferId: number): void {
-  // Build content from entries (same as what we set on the buffer)
-  const entries = buildResultFileEntries();
-  const content = entries.map(e => e.text).join("");
-  const lines = content.split("\n");
-
-  let byteOffset = 0;
-
-  for (let lineIdx = 0; lineIdx < lines.length; lineIdx++) {
-    const line = lines[lineIdx];
-    const lineStart = byteOffset;
-    const lineEnd = byteOffset + line.length;
-
-    // Highlight conflict markers
-    if (line.startsWith("<<<<<<<") || line.startsWith("=======") || line.startsWith(">>>>>>>")) {
-      editor.addOverlay(bufferId, `merge-marker-${lineIdx}`, lineStart, lineEnd, {
-        fg: UNRESOLVED_FG_KEY,
-        underline: true,
-      });
-    }
+async function createMergeView(layout: ResultLayout): Promise<boolean> {
+  // Include the source file's extension in the pane buffer names so
+  // tree-sitter picks the right language for highlighting
+  const path = mergeState.sourcePath ?? "";
+  const base = editor.pathBasename(path);
+  const dot = base.lastIndexOf(".");
+  const sourceExt = dot > 0 ? base.substring(dot) : "";
+
+  const pane = async (side: string, text: string): Promise<number> => {
+    const res = await editor.createVirtualBuffer({
+      name: `*${side}*${sourceExt}`,
+      mode: "merge-conflict",
+      readOnly: true,
+      entries: [{ text: displayText(text) }],
+      showLineNumbers: true,
+      editingDisabled: true,
+      hiddenFromTabs: true,
+    });
+    return res.bufferId;
+  };
+
+  mergeState.oursPanelId = await pane("OURS", mergeState.versions.ours);
+  mergeState.resultPanelId = await pane("RESULT", layout.text);
+  mergeState.theirsPanelId = await pane("THEIRS", mergeState.versions.theirs);
+
+  const source = (bufferId: number, label: string) => ({
+    bufferId,
+    label,
+    editable: false,
+    style: { gutterStyle: "diff-markers" },
+  });
+  mergeState.compositeId = await editor.createCompositeBuffer({
+    name: editor.t("view.title", { name: base }),
+    mode: "merge-conflict",
+    layout: { type: "side-by-side", ratios: [1 / 3, 1 / 3, 1 / 3], showSeparator: true },
+    sources: [
+      source(mergeState.oursPanelId, editor.t("pane.ours")),
+      source(mergeState.resultPanelId, editor.t("pane.result")),
+      source(mergeState.theirsPanelId, editor.t("pane.theirs")),
+    ],
+    hunks: null,
+    sections: buildSections(layout),
+  });
 
-    byteOffset = lineEnd + 1;
-  }
+  return editor.showBuffer(mergeState.compositeId);
 }
 
 /**
- * Update all panel views
+ * Rebuild the RESULT pane and the alignment after a pick
  */
 function updateViews(): void {
-  if (mergeState.oursPanelId !== null) {
-    editor.setVirtualBufferContent(mergeState.oursPanelId, buildFullFileEntries("ours"));
-  }
-
-  if (mergeState.theirsPanelId !== null) {
-    editor.setVirtualBufferContent(mergeState.theirsPanelId, buildFullFileEntries("theirs"));
-  }
-
+  const layout = buildResult();
   if (mergeState.resultPanelId !== null) {
-    editor.setVirtualBufferContent(mergeState.resultPanelId, buildResultFileEntries());
+    editor.setVirtualBufferContent(mergeState.resultPanelId, [{ text: displayText(layout.text) }]);
   }
-
-  applyHighlighting();
-  updateStatusBar();
+  if (mergeState.compositeId !== null) {
+    editor.updateCompositeSections(mergeState.compositeId, buildSections(layout));
+  }
+  scrollToSelectedConflict(layout);
 }
 
 /**
@@ -1063,8 +636,7 @@ function updateViews(): void {
  */
 function updateStatusBar(): void {
   const total = mergeState.conflicts.length;
-  const resolved = mergeState.conflicts.filter(c => c.resolved).length;
-  const remaining = total - resolved;
+  const remaining = unresolvedCount();
 
   if (remaining > 0) {
     editor.setStatus(editor.t("status.progress", { remaining: String(remaining), total: String(total), current: String(mergeState.selectedIndex + 1) }));
@@ -1074,125 +646,27 @@ function updateStatusBar(): void {
 }
 
 /**
- * Scroll all three panels to show the selected conflict
- * This computes the byte offset where the conflict appears in each panel's content
- * and uses setBufferCursor to scroll the viewport.
+ * Put the composite cursor on the selected conflict. The RESULT pane is
+ * preferred; a conflict that contributes no RESULT lines (e.g. both sides
+ * deleted different base lines and nothing is picked) is shown via
+ * whichever side still has lines.
  */
-function scrollToSelectedConflict(): void {
+function scrollToSelectedConflict(layout: ResultLayout = buildResult()): void {
   const conflict = mergeState.conflicts[mergeState.selectedIndex];
-  if (!conflict) return;
-
-  // Scroll OURS panel
-  if (mergeState.oursPanelId !== null) {
-    const oursOffset = computeConflictOffset("ours", conflict.index);
-    if (oursOffset >= 0) {
-      editor.setBufferCursor(mergeState.oursPanelId, oursOffset);
-    }
-  }
+  if (!conflict || mergeState.compositeId === null) return;
+  const region = mergeState.regions[conflict.region];
 
-  // Scroll THEIRS panel
-  if (mergeState.theirsPanelId !== null) {
-    const theirsOffset = computeConflictOffset("theirs", conflict.index);
-    if (theirsOffset >= 0) {
-      editor.setBufferCursor(mergeState.theirsPanelId, theirsOffset);
-    }
-  }
-
-  // Scroll RESULT panel
-  if (mergeState.resultPanelId !== null) {
-    const resultOffset = computeResultConflictOffset(conflict.index);
-    if (resultOffset >= 0) {
-      editor.setBufferCursor(mergeState.resultPanelId, resultOffset);
-    }
+  if (layout.counts[conflict.region] > 0) {
+    editor.setCompositeCursorLine(mergeState.compositeId, PANE_RESULT, layout.starts[conflict.region]);
+  } else if (region.oursCount > 0) {
+    editor.setCompositeCursorLine(mergeState.compositeId, PANE_OURS, region.oursStart);
+  } else if (region.theirsCount > 0) {
+    editor.setCompositeCursorLine(mergeState.compositeId, PANE_THEIRS, region.theirsStart);
   }
 }
 
-/**
- * Compute the byte offset where a conflict appears in the OURS or THEIRS panel content.
- * We search for the actual conflict text (conflict.ours or conflict.theirs) in the
- * git content to find the exact position.
- */
-function computeConflictOffset(side: "ours" | "theirs", conflictIndex: number): number {
-  const gitContent = side === "ours" ? mergeState.oursContent : mergeState.theirsContent;
-  const conflict = mergeState.conflicts[conflictIndex];
-
-  if (!conflict) return 0;
-
-  // Get the conflict text for this side
-  const conflictText = side === "ours" ? conflict.ours : conflict.theirs;
-
-  if (gitContent && conflictText) {
-    // Strategy 1: Search for the exact conflict text (trimmed)
-    const trimmedText = conflictText.trim();
-    if (trimmedText.length > 0) {
-      const pos = gitContent.indexOf(trimmedText);
-      if (pos >= 0) {
-        return pos;
-      }
-    }
-
-    // Strategy 2: Search for the first line of the conflict
-    const firstLine = conflictText.split("\n")[0]?.trim();
-    if (firstLine && firstLine.length > 5) {
-      const pos = gitContent.indexOf(firstLine);
-      if (pos >= 0) {
-        return pos;
-      }
-    }
-
-    // Strategy 3: Ratio-based fallback
-    const originalLength = mergeState.originalContent.length;
-    if (originalLength > 0) {
-      const ratio = conflict.startOffset / originalLength;
-      return Math.floor(ratio * gitContent.length);
-    }
-  }
-
-  // If no git content, we built entries manually - find "--- Conflict N ---"
-  const entries = buildFullFileEntries(side);
-  let offset = 0;
-  for (const entry of entries) {
-    if (entry.text.includes(`--- Conflict ${conflictIndex + 1} ---`)) {
-      return offset;
-    }
-    offset += entry.text.length;
-  }
-
-  return 0;
-}
-
-/**
- * Compute the byte offset where a conflict appears in the RESULT panel content.
- * The RESULT panel shows the original file with conflict markers (<<<<<<< OURS, etc.)
- * We need to find the Nth <<<<<<< marker.
- */
-function computeResultConflictOffset(conflictIndex: number): number {
-  const entries = buildResultFileEntries();
-  const content = entries.map(e => e.text).join("");
-
-  // Find the Nth occurrence of <<<<<<< marker
-  let searchPos = 0;
-  let conflictCount = 0;
-
-  while (searchPos < content.length) {
-    const markerPos = content.indexOf("<<<<<<<", searchPos);
-    if (markerPos === -1) break;
-
-    if (conflictCount === conflictIndex) {
-      return markerPos;
-    }
-
-    conflictCount++;
-    searchPos = markerPos + 7; // Skip past "<<<<<<<" to continue searching
-  }
-
-  // Fallback: use ratio-based estimation like we do for OURS/THEIRS
-  const conflict = mergeState.conflicts[conflictIndex];
-  if (conflict && mergeState.originalContent.length > 0) {
-    const ratio = conflict.startOffset / mergeState.originalContent.length;
-    return Math.floor(ratio * content.length);
-  }
-  return 0;
+function unresolvedCount(): number {
+  return mergeState.conflicts.filter(c => c.resolution === null).length;
 }
 
 // =============================================================================
@@ -1238,277 +712,112 @@ async function start_merge_conflict() : Promise<void> {
     return;
   }
 
-  // Get file content from git's working tree (has conflict markers)
-  const catFileResult = await git(editor, repo, ["show", `:0:${info.path}`]);
-
-  // If :0: doesn't exist, read the working tree file directly
-  let content: string;
-  if (catFileResult.exit_code !== 0) {
-    editor.debug(`Merge: git show :0: failed, reading working tree file`);
-    const fileContent = await editor.readFile(editor.authorityPath(info.path));
-    if (!fileContent) {
-      editor.setStatus(editor.t("status.failed_read"));
-      return;
-    }
-    content = fileContent;
-  } else {
-    // The staged version shouldn't have conflict markers, use working tree
-    const fileContent = await editor.readFile(editor.authorityPath(info.path));
-    if (!fileContent) {
-      editor.setStatus(editor.t("status.failed_read"));
-      return;
-    }
-    content = fileContent;
+  // The working tree file holds the conflict markers
+  const content = await editor.readFile(editor.authorityPath(info.path));
+  if (content === null || content === undefined) {
+    editor.setStatus(editor.t("status.failed_read"));
+    return;
   }
+  editor.debug(`Merge: file has conflict markers: ${hasConflictMarkers(content)}, content length: ${content.length}`);
 
-  // Check for conflict markers in content
-  const hasMarkers = hasConflictMarkers(content);
-  editor.debug(`Merge: file has conflict markers: ${hasMarkers}, content length: ${content.length}`);
+  editor.setStatus(editor.t("status.starting"));
 
-  if (!hasMarkers) {
-    editor.setStatus(editor.t("status.no_markers"));
+  const loaded = await loadVersions(info.path, content);
+  if (!loaded) {
+    editor.setStatus(editor.t(hasConflictMarkers(content) ? "status.failed_parse" : "status.no_markers"));
     return;
   }
 
-  editor.setStatus(editor.t("status.starting"));
-
   // Store original state
   mergeState.sourceBufferId = bufferId;
   mergeState.sourcePath = info.path;
-  mergeState.originalContent = content;
+  mergeState.versions = loaded.versions;
+  mergeState.undoStack = [];
+  mergeState.redoStack = [];
 
-  // Parse conflicts
-  mergeState.conflicts = parseConflicts(content);
+  computeMerge(loaded.versions);
+  editor.debug(`Merge: ${mergeState.conflicts.length} conflicts, ${mergeState.autoResolved} auto-resolved`);
 
-  // Debug: log parse results
-  editor.debug(`Merge: parseConflicts found ${mergeState.conflicts.length} conflicts`);
+  mergeState.selectedIndex = 0;
 
-  if (mergeState.conflicts.length === 0) {
+  if (!(await createMergeView(buildResult()))) {
+    closeMergeView();
     editor.setStatus(editor.t("status.failed_parse"));
-    // Log more detail for debugging
-    editor.debug(`Merge: regex failed, content has <<<<<<< at index ${content.indexOf("<<<<<<<")}`);
-    editor.debug(`Merge: content around <<<<<<< : ${content.substring(content.indexOf("<<<<<<<") - 20, content.indexOf("<<<<<<<") + 100)}`);
     return;
   }
 
-  editor.debug(`Found ${mergeState.conflicts.length} conflicts`);
-
-  // Fetch git versions for auto-resolution
-  const versions = await fetchGitVersions(info.path);
-  if (versions) {
-    mergeState.baseContent = versions.base;
-    mergeState.oursContent = versions.ours;
-    mergeState.theirsContent = versions.theirs;
-    editor.debug("Fetched git versions for auto-resolution");
-  }
-
-  // Attempt auto-resolution
-  autoResolveConflicts(mergeState.conflicts);
-
-  const autoResolved = mergeState.conflicts.filter(c => c.resolved).length;
-  if (autoResolved > 0) {
-    editor.debug(`Auto-resolved ${autoResolved} trivial conflicts`);
-  }
-
-  // Find first unresolved conflict
-  mergeState.selectedIndex = 0;
-  for (let i = 0; i < mergeState.conflicts.length; i++) {
-    if (!mergeState.conflicts[i].resolved) {
-      mergeState.selectedIndex = i;
-      break;
-    }
-  }
-
-  // Create the merge UI panels
-  await createMergePanels();
-
   mergeState.isActive = true;
 
   // Register merge-mode commands now that we're active
   registerMergeModeCommands();
 
-  updateViews();
-
-  // Scroll all panels to show the first conflict
+  // Land on the first conflict
   scrollToSelectedConflict();
 
-  const remaining = mergeState.conflicts.length - autoResolved;
+  const remaining = mergeState.conflicts.length;
+  let status: string;
   if (remaining > 0) {
-    editor.setStatus(editor.t("status.conflicts_to_resolve", { remaining: String(remaining), auto_resolved: String(autoResolved) }));
+    status = editor.t("status.conflicts_to_resolve", { remaining: String(remaining), auto_resolved: String(mergeState.autoResolved) });
   } else {
-    editor.setStatus(editor.t("status.all_auto_resolved", { total: String(mergeState.conflicts.length) }));
+    status = editor.t("status.all_auto_resolved", { total: String(mergeState.autoResolved) });
   }
-}
-registerHandler("start_merge_conflict", start_merge_conflict);
-
-/**
- * Create the multi-panel merge UI (JetBrains-style: OURS | RESULT | THEIRS)
- *
- * Creates three vertical splits and then calls distributeSplitsEvenly()
- * to ensure all panels get equal width.
- */
-async function createMergePanels(): Promise<void> {
-  // Get the source file's extension for syntax highlighting
-  // Tree-sitter uses filename extension to determine language
-  const sourceExt = mergeState.sourcePath
-    ? mergeState.sourcePath.substring(mergeState.sourcePath.lastIndexOf("."))
-    : "";
-
-  editor.debug(`Merge: source extension '${sourceExt}' for syntax highlighting`);
-
-  // Create OURS panel first (takes over current view)
-  // Include extension in name so tree-sitter can apply highlighting
-  const oursResult = await editor.createVirtualBuffer({
-    name: `*OURS*${sourceExt}`,
-    mode: "merge-conflict",
-    readOnly: true,
-    entries: buildFullFileEntries("ours"),
-    showLineNumbers: true,
-    showCursors: true,
-    editingDisabled: true,
-  });
-
-  if (oursResult !== null) {
-    mergeState.oursPanelId = oursResult.bufferId;
-    mergeState.oursSplitId = editor.getActiveSplitId();
-  }
-
-  // Create THEIRS panel to the right (vertical split)
-  const theirsResult = await editor.createVirtualBufferInSplit({
-    name: `*THEIRS*${sourceExt}`,
-    mode: "merge-conflict",
-    readOnly: true,
-    entries: buildFullFileEntries("theirs"),
-    ratio: 0.5,  // Will be equalized by distributeSplitsEvenly
-    direction: "vertical",
-    panelId: "merge-theirs",
-    showLineNumbers: true,
-    showCursors: true,
-    editingDisabled: true,
-  });
-
-  if (theirsResult !== null) {
-    mergeState.theirsPanelId = theirsResult.bufferId;
-    mergeState.theirsSplitId = theirsResult.splitId ?? editor.getActiveSplitId();
-  }
-
-  // Focus back on OURS and create RESULT in the middle
-  if (mergeState.oursSplitId !== null) {
-    editor.focusSplit(mergeState.oursSplitId);
-  }
-
-  const resultResult = await editor.createVirtualBufferInSplit({
-    name: `*RESULT*${sourceExt}`,
-    mode: "merge-result",
-    readOnly: false,
-    entries: buildResultFileEntries(),
-    ratio: 0.5,  // Will be equalized by distributeSplitsEvenly
-    direction: "vertical",
-    panelId: "merge-result",
-    showLineNumbers: true,
-    showCursors: true,
-    editingDisabled: false,
-  });
-
-  if (resultResult !== null) {
-    mergeState.resultPanelId = resultResult.bufferId;
-    mergeState.resultSplitId = resultResult.splitId ?? editor.getActiveSplitId();
-  }
-
-  // Distribute splits evenly so all three panels get equal width
-  editor.distributeSplitsEvenly();
-
-  // Focus the RESULT panel since that's where the user will resolve conflicts
-  if (mergeState.resultSplitId !== null) {
-    editor.focusSplit(mergeState.resultSplitId);
+  if (loaded.rederived) {
+    status = `${editor.t("status.rederived")} | ${status}`;
   }
+  editor.setStatus(status);
 }
+registerHandler("start_merge_conflict", start_merge_conflict);
 
 // =============================================================================
 // Public Commands - Navigation
 // =============================================================================
 
-function merge_next_conflict() : void {
-  editor.debug(`merge_next_conflict called, isActive=${mergeState.isActive}, conflicts=${mergeState.conflicts.length}`);
-
+/**
+ * Select the next (`step` = 1) or previous (`step` = -1) conflict,
+ * preferring unresolved ones and wrapping around.
+ */
+function selectConflict(step: number): void {
   if (!mergeState.isActive) {
     editor.setStatus(editor.t("status.no_active_merge"));
     return;
   }
-  if (mergeState.conflicts.length === 0) {
+  const total = mergeState.conflicts.length;
+  if (total === 0) {
     editor.setStatus(editor.t("status.no_conflicts"));
     return;
   }
-  if (mergeState.conflicts.length === 1) {
+  if (total === 1) {
     // Single conflict: just re-scroll to it (useful for re-focusing)
     editor.setStatus(editor.t("status.single_refocused"));
     scrollToSelectedConflict();
     return;
   }
 
-  // Find next unresolved conflict (or wrap around)
-  let startIndex = mergeState.selectedIndex;
-  let index = (startIndex + 1) % mergeState.conflicts.length;
-
-  // First try to find next unresolved
+  const startIndex = mergeState.selectedIndex;
+  let index = (startIndex + step + total) % total;
   while (index !== startIndex) {
-    if (!mergeState.conflicts[index].resolved) {
+    if (mergeState.conflicts[index].resolution === null) {
       mergeState.selectedIndex = index;
-      editor.setStatus(editor.t("status.conflict_of", { current: String(index + 1), total: String(mergeState.conflicts.length) }));
-      updateViews();
+      editor.setStatus(editor.t("status.conflict_of", { current: String(index + 1), total: String(total) }));
       scrollToSelectedConflict();
       return;
     }
-    index = (index + 1) % mergeState.conflicts.length;
+    index = (index + step + total) % total;
   }
 
-  // If all resolved, just move to next
-  mergeState.selectedIndex = (mergeState.selectedIndex + 1) % mergeState.conflicts.length;
-  editor.setStatus(editor.t("status.conflict_all_resolved", { current: String(mergeState.selectedIndex + 1), total: String(mergeState.conflicts.length) }));
-  updateViews();
+  // If all resolved, just move along
+  mergeState.selectedIndex = (startIndex + step + total) % total;
+  editor.setStatus(editor.t("status.conflict_all_resolved", { current: String(mergeState.selectedIndex + 1), total: String(total) }));
   scrollToSelectedConflict();
 }
+
+function merge_next_conflict() : void {
+  selectConflict(1);
+}
 registerHandler("merge_next_conflict", merge_next_conflict);
 
 function merge_prev_conflict() : void {
-  editor.debug(`merge_prev_conflict called, isActive=${mergeState.isActive}, conflicts=${mergeState.conflicts.length}`);
-
-  if (!mergeState.isActive) {
-    editor.setStatus(editor.t("status.no_active_merge"));
-    return;
-  }
-  if (mergeState.conflicts.length === 0) {
-    editor.setStatus(editor.t("status.no_conflicts"));
-    return;
-  }
-  if (mergeState.conflicts.length === 1) {
-    // Single conflict: just re-scroll to it (useful for re-focusing)
-    editor.setStatus(editor.t("status.single_refocused"));
-    scrollToSelectedConflict();
-    return;
-  }
-
-  // Find previous unresolved conflict (or wrap around)
-  let startIndex = mergeState.selectedIndex;
-  let index = (startIndex - 1 + mergeState.conflicts.length) % mergeState.conflicts.length;
-
-  // First try to find previous unresolved
-  while (index !== startIndex) {
-    if (!mergeState.conflicts[index].resolved) {
-      mergeState.selectedIndex = index;
-      editor.setStatus(editor.t("status.conflict_of", { current: String(index + 1), total: String(mergeState.conflicts.length) }));
-      updateViews();
-      scrollToSelectedConflict();
-      return;
-    }
-    index = (index - 1 + mergeState.conflicts.length) % mergeState.conflicts.length;
-  }
-
-  // If all resolved, just move to previous
-  mergeState.selectedIndex = (mergeState.selectedIndex - 1 + mergeState.conflicts.length) % mergeState.conflicts.length;
-  editor.setStatus(editor.t("status.conflict_all_resolved", { current: String(mergeState.selectedIndex + 1), total: String(mergeState.conflicts.length) }));
-  updateViews();
-  scrollToSelectedConflict();
+  selectConflict(-1);
 }
 registerHandler("merge_prev_conflict", merge_prev_conflict);
 
@@ -1516,82 +825,126 @@ registerHandler("merge_prev_conflict", merge_prev_conflict);
 // Public Commands - Resolution
 // =============================================================================
 
-function merge_use_ours() : void {
+function snapshot(): Array<Resolution | null> {
+  return mergeState.conflicts.map(c => c.resolution);
+}
+
+function restore(resolutions: Array<Resolution | null>): void {
+  mergeState.conflicts.forEach((c, i) => {
+    c.resolution = resolutions[i] ?? null;
+  });
+}
+
+/**
+ * Resolve (or, with null, un-resolve) the selected conflict as one
+ * undoable step, then move on to the next unresolved conflict.
+ */
+function resolveSelected(resolution: Resolution | null): void {
   if (!mergeState.isActive) {
     editor.setStatus(editor.t("status.no_active_merge"));
     return;
   }
 
   const conflict = mergeState.conflicts[mergeState.selectedIndex];
-  if (!conflict) return;
+  if (!conflict) {
+    editor.setStatus(editor.t("status.no_conflicts"));
+    return;
+  }
+  if (conflict.resolution === resolution) return;
 
-  conflict.resolved = true;
-  conflict.resolution = "ours";
-  conflict.resolvedContent = conflict.ours;
+  mergeState.undoStack.push(snapshot());
+  mergeState.redoStack = [];
+  conflict.resolution = resolution;
 
-  editor.debug(`Resolved conflict ${conflict.index} with OURS`);
+  editor.debug(`Resolved conflict ${conflict.index} with ${resolution ?? "nothing"}`);
 
-  // Move to next unresolved conflict
-  moveToNextUnresolved();
+  if (resolution === null) {
+    editor.setStatus(editor.t("status.reset", { index: String(conflict.index + 1) }));
+  } else {
+    moveToNextUnresolved();
+    updateStatusBar();
+  }
   updateViews();
 }
+
+function merge_use_ours() : void {
+  resolveSelected("ours");
+}
 registerHandler("merge_use_ours", merge_use_ours);
 
 function merge_take_theirs() : void {
-  if (!mergeState.isActive) {
-    editor.setStatus(editor.t("status.no_active_merge"));
-    return;
-  }
-
-  const conflict = mergeState.conflicts[mergeState.selectedIndex];
-  if (!conflict) return;
+  resolveSelected("theirs");
+}
+registerHandler("merge_take_theirs", merge_take_theirs);
 
-  conflict.resolved = true;
-  conflict.resolution = "theirs";
-  conflict.resolvedContent = conflict.theirs;
+function merge_use_both() : void {
+  resolveSelected("both");
+}
+registerHandler("merge_use_both", merge_use_both);
 
-  editor.debug(`Resolved conflict ${conflict.index} with THEIRS`);
+function merge_use_both_theirs_first() : void {
+  resolveSelected("both_theirs_first");
+}
+registerHandler("merge_use_both_theirs_first", merge_use_both_theirs_first);
 
-  // Move to next unresolved conflict
-  moveToNextUnresolved();
-  updateViews();
+function merge_reset_conflict() : void {
+  resolveSelected(null);
 }
-registerHandler("merge_take_theirs", merge_take_theirs);
+registerHandler("merge_reset_conflict", merge_reset_conflict);
 
-function merge_use_both() : void {
+/**
+ * Step back through (or forward again through) the picks made so far.
+ * The selection follows the conflict the step changed.
+ */
+function stepHistory(
+  from: Array<Array<Resolution | null>>,
+  to: Array<Array<Resolution | null>>,
+  emptyKey: string,
+  doneKey: string,
+): void {
   if (!mergeState.isActive) {
     editor.setStatus(editor.t("status.no_active_merge"));
     return;
   }
+  const target = from.pop();
+  if (!target) {
+    editor.setStatus(editor.t(emptyKey));
+    return;
+  }
+  const current = snapshot();
+  to.push(current);
+  restore(target);
 
-  const conflict = mergeState.conflicts[mergeState.selectedIndex];
-  if (!conflict) return;
-
-  conflict.resolved = true;
-  conflict.resolution = "both";
-  conflict.resolvedContent = conflict.ours + conflict.theirs;
+  const changed = current.findIndex((r, i) => r !== target[i]);
+  if (changed >= 0) mergeState.selectedIndex = changed;
+  editor.setStatus(editor.t(doneKey, { index: String(mergeState.selectedIndex + 1) }));
+  updateViews();
+}
 
-  editor.debug(`Resolved conflict ${conflict.index} with BOTH`);
+function merge_undo() : void {
+  stepHistory(mergeState.undoStack, mergeState.redoStack, "status.undo_empty", "status.undone");
+}
+registerHandler("merge_undo", merge_undo);
 
-  // Move to next unresolved conflict
-  moveToNextUnresolved();
-  updateViews();
+function merge_redo() : void {
+  stepHistory(mergeState.redoStack, mergeState.undoStack, "status.redo_empty", "status.redone");
 }
-registerHandler("merge_use_both", merge_use_both);
+registerHandler("merge_redo", merge_redo);
 
 /**
  * Move selection to the next unresolved conflict
  */
 function moveToNextUnresolved(): void {
+  const total = mergeState.conflicts.length;
   const startIndex = mergeState.selectedIndex;
-  let index = (startIndex + 1) % mergeState.conflicts.length;
+  let index = (startIndex + 1) % total;
 
   while (index !== startIndex) {
-    if (!mergeState.conflicts[index].resolved) {
+    if (mergeState.conflicts[index].resolution === null) {
       mergeState.selectedIndex = index;
       return;
     }
-    index = (index + 1) % mergeState.conflicts.length;
+    index = (index + 1) % total;
   }
 
   // All resolved, stay where we are
@@ -1607,28 +960,13 @@ async function merge_save_and_exit() : Promise<void> {
     return;
   }
 
-  const unresolvedCount = mergeState.conflicts.filter(c => !c.resolved).length;
-
-  if (unresolvedCount > 0) {
-    // TODO: Add confirmation prompt
-    editor.setStatus(editor.t("status.cannot_save", { count: String(unresolvedCount) }));
+  const remaining = unresolvedCount();
+  if (remaining > 0) {
+    editor.setStatus(editor.t("status.cannot_save", { count: String(remaining) }));
     return;
   }
 
-  // Build final content by replacing conflict markers with resolved content
-  let finalContent = mergeState.originalContent;
-
-  // Process conflicts in reverse order to preserve offsets
-  const sortedConflicts = [...mergeState.conflicts].sort((a, b) => b.startOffset - a.startOffset);
-
-  for (const conflict of sortedConflicts) {
-    if (conflict.resolvedContent !== undefined) {
-      finalContent =
-        finalContent.substring(0, conflict.startOffset) +
-        conflict.resolvedContent +
-        finalContent.substring(conflict.endOffset);
-    }
-  }
+  const finalContent = buildResult().text;
 
   // Update the original buffer with resolved content
   if (mergeState.sourceBufferId !== null) {
@@ -1645,8 +983,7 @@ async function merge_save_and_exit() : Promise<void> {
     editor.debug("Applied resolved content to source buffer");
   }
 
-  // Close merge panels
-  closeMergePanels();
+  closeMergeView();
 
   editor.setStatus(editor.t("status.complete"));
 }
@@ -1658,66 +995,50 @@ function merge_abort() : void {
     return;
   }
 
-  // TODO: Add confirmation prompt if there are resolutions
-
-  // Close merge panels without saving
-  closeMergePanels();
+  // Close the merge view without touching the file
+  closeMergeView();
 
   editor.setStatus(editor.t("status.aborted"));
 }
 registerHandler("merge_abort", merge_abort);
 
 /**
- * Close all merge panels and reset state
+ * Close the merge view and its pane buffers, and reset state
  */
-function closeMergePanels(): void {
-  // Close buffers
-  if (mergeState.oursPanelId !== null) {
-    editor.closeBuffer(mergeState.oursPanelId);
-  }
-  if (mergeState.theirsPanelId !== null) {
-    editor.closeBuffer(mergeState.theirsPanelId);
-  }
-  if (mergeState.resultPanelId !== null) {
-    editor.closeBuffer(mergeState.resultPanelId);
+function closeMergeView(): void {
+  // Show the source first so closing the composite doesn't leave the
+  // split on an unrelated buffer
+  if (mergeState.sourceBufferId !== null) {
+    editor.showBuffer(mergeState.sourceBufferId);
   }
 
-  // Close splits
-  if (mergeState.oursSplitId !== null) {
-    editor.closeSplit(mergeState.oursSplitId);
-  }
-  if (mergeState.theirsSplitId !== null) {
-    editor.closeSplit(mergeState.theirsSplitId);
+  if (mergeState.compositeId !== null) {
+    editor.closeCompositeBuffer(mergeState.compositeId);
   }
-  if (mergeState.resultSplitId !== null) {
-    editor.closeSplit(mergeState.resultSplitId);
-  }
-
-  // Focus back on source buffer if it exists
-  if (mergeState.sourceBufferId !== null) {
-    editor.showBuffer(mergeState.sourceBufferId);
+  for (const id of [mergeState.oursPanelId, mergeState.resultPanelId, mergeState.theirsPanelId]) {
+    if (id !== null) editor.closeBuffer(id);
   }
 
   // Unregister merge-mode commands
-  unregisterMergeModeCommands();
+  if (mergeState.isActive) {
+    unregisterMergeModeCommands();
+  }
 
   // Reset state
   mergeState.isActive = false;
   mergeState.sourceBufferId = null;
   mergeState.sourcePath = null;
-  mergeState.originalContent = "";
+  mergeState.versions = { base: "", ours: "", theirs: "" };
+  mergeState.regions = [];
   mergeState.conflicts = [];
+  mergeState.autoResolved = 0;
   mergeState.selectedIndex = 0;
+  mergeState.undoStack = [];
+  mergeState.redoStack = [];
+  mergeState.compositeId = null;
   mergeState.oursPanelId = null;
-  mergeState.theirsPanelId = null;
   mergeState.resultPanelId = null;
-  mergeState.oursSplitId = null;
-  mergeState.theirsSplitId = null;
-  mergeState.resultSplitId = null;
-  mergeState.oursContent = "";
-  mergeState.theirsContent = "";
-  mergeState.baseContent = "";
-  mergeState.resultContent = "";
+  mergeState.theirsPanelId = null;
 }
 
 // =============================================================================
@@ -1729,20 +1050,6 @@ function merge_show_help() : void {
 }
 registerHandler("merge_show_help", merge_show_help);
 
-// =============================================================================
-// Hook Handlers - Auto-Detection
-// =============================================================================
-
-/**
- * Handle buffer activation - check for conflict markers
- */
-
-
-/**
- * Handle file open - check for conflict markers
- */
-
-
 // =============================================================================
 // Hook Registration
 // =============================================================================
@@ -1782,6 +1089,10 @@ const MERGE_MODE_COMMANDS = [
   { name: "%cmd.use_ours", desc: "%cmd.use_ours_desc", action: "merge_use_ours" },
   { name: "%cmd.take_theirs", desc: "%cmd.take_theirs_desc", action: "merge_take_theirs" },
   { name: "%cmd.use_both", desc: "%cmd.use_both_desc", action: "merge_use_both" },
+  { name: "%cmd.use_both_theirs_first", desc: "%cmd.use_both_theirs_first_desc", action: "merge_use_both_theirs_first" },
+  { name: "%cmd.reset", desc: "%cmd.reset_desc", action: "merge_reset_conflict" },
+  { name: "%cmd.undo", desc: "%cmd.undo_desc", action: "merge_undo" },
+  { name: "%cmd.redo", desc: "%cmd.redo_desc", action: "merge_redo" },
   { name: "%cmd.save_exit", desc: "%cmd.save_exit_desc", action: "merge_save_and_exit" },
   { name: "%cmd.abort", desc: "%cmd.abort_desc", action: "merge_abort" },
 ];
@@ -82,6 +82,11 @@ interface PanelState {
   caseSensitive: boolean;
   useRegex: boolean;
   wholeWords: boolean;
+  // Structural mode: the search field is a code pattern with metavariables
+  // (`foo($A, $B)`) matched against each file's syntax tree, and the
+  // replace field is its rewrite (`bar($B, $A)`). The host renders each
+  // match's rewrite, so rows preview it and Replace applies it per match.
+  structural: boolean;
   // Scope (§1): when false, results are restricted to the source buffer.
   // `sourceBufferPath` is the absolute path of the buffer that was
   // active when the panel opened; `sourceBufferRelPath` is the
@@ -313,6 +318,7 @@ const modeBindings: [string, string][] = [
   ["M-c", "search_replace_toggle_case"],
   ["M-r", "search_replace_toggle_regex"],
   ["M-w", "search_replace_toggle_whole_word"],
+  ["M-s", "search_replace_toggle_structural"],
   ["M-Return", "search_replace_replace_all"],
   ["S-Return", "search_replace_replace_scoped"],
   // Match navigation (issue #2434). Also bound in keymaps/default.json
@@ -488,13 +494,14 @@ function getViewportWidth(): number {
 // theme keys, and focus affordance match every other plugin.
 function buildOptionsRowSpec(): WidgetSpec {
   if (!panel) return col();
-  const { focusPanel, optionIndex, caseSensitive, useRegex, wholeWords, allFiles } = panel;
+  const { focusPanel, optionIndex, caseSensitive, useRegex, wholeWords, structural, allFiles } = panel;
   const W = Math.max(MIN_WIDTH, panel.viewportWidth - 2);
   const oFocus = focusPanel === "options";
 
   const caseLabel = editor.t("panel.case_toggle");
   const regexLabel = editor.t("panel.regex_toggle");
   const wholeLabel = editor.t("panel.whole_toggle");
+  const structuralLabel = editor.t("panel.structural_toggle");
   const allFilesLabel = editor.t("panel.all_files_toggle");
   // Replace All button label tracks scope (§1):
   //   * allFiles=true  → "Replace All (Alt+Ret)"
@@ -518,6 +525,8 @@ function buildOptionsRowSpec(): WidgetSpec {
     toggle(useRegex, regexLabel, { key: "regex" }),
     spacer(2),
     toggle(wholeWords, wholeLabel, { key: "whole" }),
+    spacer(2),
+    toggle(structural, structuralLabel, { key: "structural" }),
     flexSpacer(),
     button(replLabel, { intent: "primary", key: "replaceAll" }),
   );
@@ -727,7 +736,15 @@ function renderFlatItemEntry(item: FlatItem, W: number): TextPropertyEntry {
   // in segment-local char units; the host shifts them by the
   // context segment's char start during entry concatenation.
   const ctxOverlays: InlineOverlay[] = [];
-  if (panel.searchPattern) {
+  if (result.match.replacement !== undefined) {
+    // Structural match: the pattern isn't text, so highlight the span
+    // the host matched — from its column to the end of its first line.
+    const start = result.match.column - 1 - (rawCtx.length - rawCtx.trimStart().length);
+    const end = Math.min(displayCtx.length, start + result.match.length);
+    if (start >= 0 && start < end) {
+      ctxOverlays.push({ start, end, style: { bg: C.matchBg, fg: C.matchFg }, unit: "char" });
+    }
+  } else if (panel.searchPattern) {
     highlightMatches(displayCtx, panel.searchPattern, panel.useRegex, panel.caseSensitive, ctxOverlays);
   }
 
@@ -736,6 +753,14 @@ function renderFlatItemEntry(item: FlatItem, W: number): TextPropertyEntry {
     { text: " - " },
     { text: displayCtx, overlays: ctxOverlays },
   ];
+  if (result.match.replacement !== undefined && panel.replaceText) {
+    // Preview the rewrite on one line; multi-line rewrites collapse.
+    const preview = result.match.replacement.replace(/\s+/g, " ").trim();
+    segments.push(
+      { text: " → ", style: { fg: C.dim } },
+      { text: truncate(preview, Math.max(10, innerWidth - location.length - 6 - displayCtx.length)), style: { fg: C.statusOk } },
+    );
+  }
 
   return styledRow(segments, {
     padToChars: innerWidth,
@@ -1163,6 +1188,8 @@ async function performSearch(pattern: string, silent?: boolean): Promise<SearchR
       maxResults: MAX_RESULTS,
       wholeWords: panel.wholeWords,
       fileGlob: panel.fileGlob,
+      structural: panel.structural,
+      rewrite: panel.structural ? panel.replaceText : "",
       // Lets the host search an unnamed/unsaved source buffer in-memory;
       // it has no on-disk file the project walk could otherwise reach.
       sourceBufferId: panel.sourceBufferId,
@@ -1510,6 +1537,7 @@ async function openPanelInner(opts?: { allFiles?: boolean }): Promise<void> {
       caseSensitive: false,
       useRegex: false,
       wholeWords: false,
+      structural: false,
       allFiles,
       sourceBufferPath,
       sourceBufferRelPath,
@@ -1552,17 +1580,25 @@ async function executeReplacements(results?: SearchResult[]): Promise<string> {
   // "[No Name]" label) still resolves to the right buffer rather than
   // colliding with another unnamed buffer's matches. On-disk files
   // (bufferId 0) key by path and are opened/saved by the host as before.
-  type Group = { filePath: string; bufferId: number; matches: Array<[number, number]> };
+  // Structural matches each carry their own rewrite, so those groups pass
+  // one replacement per match instead of the replace field's text.
+  type Group = {
+    filePath: string;
+    bufferId: number;
+    matches: Array<[number, number]>;
+    replacements: string[];
+  };
   const groups: Map<string, Group> = new Map();
   for (const result of toReplace) {
     const bufferId = result.match.bufferId || 0;
     const key = bufferId > 0 ? `buf:${bufferId}` : result.match.file;
     let group = groups.get(key);
     if (!group) {
-      group = { filePath: result.match.file, bufferId, matches: [] };
+      group = { filePath: result.match.file, bufferId, matches: [], replacements: [] };
       groups.set(key, group);
     }
     group.matches.push([result.match.byteOffset, result.match.length]);
+    group.replacements.push(result.match.replacement ?? panel.replaceText);
   }
 
   let filesModified = 0;
@@ -1576,7 +1612,7 @@ async function executeReplacements(results?: SearchResult[]): Promise<string> {
       const result = await editor.replaceInFile(
         group.filePath,
         group.matches,
-        panel.replaceText,
+        panel.structural ? group.replacements : panel.replaceText,
         group.bufferId
       );
       replacementsCount += result.replacements;
@@ -1776,7 +1812,7 @@ registerHandler("search_replace_shift_tab", () => dispatch(widgetKey("Shift+Tab"
 // focused). Plugin no longer needs separate file-row expand
 // handling.
 
-// Global option toggles (Alt+C, Alt+R, Alt+W)
+// Global option toggles (Alt+C, Alt+R, Alt+W, Alt+S)
 function search_replace_toggle_case(): void {
   if (!panel) return;
   panel.caseSensitive = !panel.caseSensitive;
@@ -1801,6 +1837,14 @@ function search_replace_toggle_whole_word(): void {
 }
 registerHandler("search_replace_toggle_whole_word", search_replace_toggle_whole_word);
 
+function search_replace_toggle_structural(): void {
+  if (!panel) return;
+  panel.structural = !panel.structural;
+  updatePanelContent();
+  rerunSearchDebounced();
+}
+registerHandler("search_replace_toggle_structural", search_replace_toggle_structural);
+
 function search_replace_replace_all(): void {
   doReplaceAll();
 }
@@ -2369,8 +2413,13 @@ editor.on("widget_event", (args) => {
       // the right place.
       panel.cursorPos = byteToCharOffset(payload.value, cursorByte);
     } else if (args.widget_key === "replaceField") {
+      const rewriteChanged = panel.structural && panel.replaceText !== payload.value;
       panel.replaceText = payload.value;
       panel.cursorPos = byteToCharOffset(payload.value, cursorByte);
+      if (rewriteChanged) {
+        // The host renders structural rewrites, so refresh the previews.
+        rerunSearchDebounced();
+      }
     } else if (args.widget_key === "fileGlobField") {
       if (panel.fileGlob !== payload.value) {
         panel.fileGlob = payload.value;
@@ -2473,6 +2522,11 @@ editor.on("widget_event", (args) => {
         panel.widgetPanel?.setChecked("whole", newChecked);
         rerunSearchDebounced();
         break;
+      case "structural":
+        panel.structural = newChecked;
+        panel.widgetPanel?.setChecked("structural", newChecked);
+        rerunSearchDebounced();
+        break;
       case "matchTree": {
         // The `[v]`/`[ ]` glyph on a tree row was clicked. Plugin
         // owns the source-of-truth (`result.selected`) — flip it
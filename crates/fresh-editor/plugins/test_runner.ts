@@ -0,0 +1,813 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+const editor = getEditor();
+
+/**
+ * Test Runner Plugin
+ *
+ * Runs the test under the cursor, every test in the file, or the last run
+ * again, and shows the outcome where the tests are:
+ *
+ *   * a ✓ / ✗ / ● marker in the gutter next to each test;
+ *   * the failure message as inline virtual text on the failing line;
+ *   * a results tree ("*Tests*" buffer group) with the run's output beside
+ *     it. Enter on a test jumps to it.
+ *
+ * Tests are discovered through the language server where it knows them —
+ * rust-analyzer's `experimental/runnables`, or code lenses carrying a
+ * runnable — and otherwise by per-language patterns (Rust `#[test]`
+ * functions, pytest `test_*` functions and `Test*` classes, Go `TestXxx`).
+ * Commands go through `spawnProcess`, so they run wherever the active
+ * authority runs processes (a container, a remote host).
+ */
+
+// =============================================================================
+// Configuration
+// =============================================================================
+
+editor.defineConfigString("pythonCommand", {
+  default: "python3",
+  description: "Python interpreter used to run pytest (`<pythonCommand> -m pytest`).",
+});
+
+function pythonCommand(): string {
+  const cfg = (editor.getPluginConfig() ?? {}) as { pythonCommand?: string };
+  return cfg.pythonCommand && cfg.pythonCommand.length > 0 ? cfg.pythonCommand : "python3";
+}
+
+// =============================================================================
+// Types
+// =============================================================================
+
+interface TestItem {
+  /** Name the runner reports the test under (`tests::adds`, `t.py::test_x`). */
+  id: string;
+  /** Short name shown in the results tree. */
+  label: string;
+  file: string;
+  /** 0-indexed first and last line of the test's definition. */
+  line: number;
+  endLine: number;
+  /** From an LSP runnable: the exact cargo invocation and where to run it. */
+  cargoArgs?: string[];
+  cwd?: string;
+}
+
+type Status = "running" | "passed" | "failed" | "skipped";
+
+interface TestResult {
+  status: Status;
+  message?: string;
+  /** Where the failure was reported, when the runner says. */
+  file?: string;
+  line?: number;
+}
+
+interface Command {
+  command: string;
+  args: string[];
+  cwd: string;
+}
+
+interface Runner {
+  /** Tests defined in `text`, the contents of `file`, by pattern. */
+  discover(file: string, text: string): TestItem[];
+  /** The commands running `tests`, all from one file, one after another. */
+  commands(tests: TestItem[]): Command[];
+  /** Results by reported test id. */
+  parse(output: string): Map<string, TestResult>;
+}
+
+interface Run {
+  title: string;
+  runner: string;
+  tests: TestItem[];
+  commands: Command[];
+  results: Map<string, TestResult>;
+  /** Output of each command run so far. */
+  outputs: string[];
+  /** First non-zero exit code, or 0 once every command succeeded. */
+  exitCode: number | null;
+}
+
+// =============================================================================
+// State
+// =============================================================================
+
+const NS_PASSED = "test-runner-passed";
+const NS_FAILED = "test-runner-failed";
+const NS_RUNNING = "test-runner-running";
+const VTEXT_PREFIX = "test-runner:";
+const PRIORITY = 12;
+
+const COLORS = {
+  passed: [80, 200, 120] as [number, number, number],
+  failed: [220, 90, 90] as [number, number, number],
+  running: [220, 180, 80] as [number, number, number],
+};
+
+const SYMBOLS: Record<Status, string> = {
+  passed: "✓",
+  failed: "✗",
+  running: "●",
+  skipped: "○",
+};
+
+/** Inline failure messages are cut to this many characters. */
+const MAX_MESSAGE_CHARS = 120;
+
+const GROUP_LAYOUT = JSON.stringify({
+  type: "split",
+  direction: "h",
+  ratio: 0.45,
+  first: { type: "scrollable", id: "tree" },
+  second: { type: "scrollable", id: "output" },
+});
+
+const state = {
+  groupId: null as number | null,
+  panelBuffers: {} as Record<string, number>,
+  running: false,
+  lastRun: null as Run | null,
+  /** Latest result of every test run so far, by file then test id. */
+  known: new Map<string, Map<string, { test: TestItem; result: TestResult }>>(),
+};
+
+editor.defineMode(
+  "test-results",
+  [
+    ["Return", "test_results_open"],
+    ["r", "test_run_last"],
+    ["Tab", "test_results_next_panel"],
+    ["q", "test_results_close"],
+  ],
+  true, // read-only
+);
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+function relativeTo(root: string, path: string): string {
+  const prefix = root.endsWith("/") ? root : `${root}/`;
+  return path.startsWith(prefix) ? path.slice(prefix.length) : path;
+}
+
+/** Whether two paths name the same file, when either may be relative. */
+function samePath(a: string, b: string): boolean {
+  return a === b || a.endsWith(`/${b}`) || b.endsWith(`/${a}`);
+}
+
+function lines(text: string): string[] {
+  return text.split("\n");
+}
+
+/** Line where the block opened on `start` ends, by brace depth. */
+function braceBlockEnd(source: string[], start: number): number {
+  let depth = 0;
+  let opened = false;
+  for (let i = start; i < source.length; i++) {
+    for (const ch of source[i]) {
+      if (ch === "{") {
+        depth++;
+        opened = true;
+      } else if (ch === "}") {
+        depth--;
+        if (opened && depth === 0) return i;
+      }
+    }
+  }
+  return source.length - 1;
+}
+
+/** Last line indented deeper than `indent` after `start` (Python blocks). */
+function indentBlockEnd(source: string[], start: number, indent: number): number {
+  let end = start;
+  for (let i = start + 1; i < source.length; i++) {
+    const text = source[i];
+    if (text.trim() === "") continue;
+    if (text.length - text.trimStart().length <= indent) break;
+    end = i;
+  }
+  return end;
+}
+
+function escapeRegex(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
+
+/** Module path of a Rust file within its crate: `src/a/b.rs` is `a::b`,
+ * `src/a/mod.rs` is `a`, and a crate root (`lib.rs`, `main.rs`, a binary or an
+ * integration test file) is the root. A directory under `tests/` is taken to
+ * be a module of the test file that declares it. */
+function rustFileModule(file: string): string[] {
+  const parts = file.split(/[\\/]/);
+  const root = Math.max(parts.lastIndexOf("src"), parts.lastIndexOf("tests"));
+  if (root < 0) return [];
+  const dirs = parts.slice(root + 1, -1);
+  const name = parts[parts.length - 1].replace(/\.rs$/, "");
+  if (parts[root] === "tests" && dirs.length === 0) return [];
+  if (parts[root] === "src") {
+    if (dirs.length === 0 && (name === "lib" || name === "main")) return [];
+    if (dirs[0] === "bin") return [];
+  }
+  return name === "mod" ? dirs : [...dirs, name];
+}
+
+// =============================================================================
+// Runners
+// =============================================================================
+
+const rust: Runner = {
+  discover(file, text) {
+    const source = lines(text);
+    const tests: TestItem[] = [];
+    // Inline `mod name {` blocks open at `depth`, innermost last.
+    const modules: { name: string; depth: number }[] = [];
+    const fileModule = rustFileModule(file);
+    let depth = 0;
+    for (let i = 0; i < source.length; i++) {
+      const opened = source[i].match(/^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*\{/);
+      if (opened) modules.push({ name: opened[1], depth });
+      for (const ch of source[i]) {
+        if (ch === "{") depth++;
+        else if (ch === "}") depth--;
+      }
+      while (modules.length > 0 && depth <= modules[modules.length - 1].depth) modules.pop();
+      if (!/^\s*#\[(\w+::)*test\b/.test(source[i])) continue;
+      // The attribute may be followed by others (`#[should_panic]`).
+      for (let j = i + 1; j < Math.min(source.length, i + 8); j++) {
+        const m = source[j].match(/^\s*(?:pub\s+)?(?:async\s+)?fn\s+(\w+)/);
+        if (m) {
+          const id = [...fileModule, ...modules.map((mod) => mod.name), m[1]].join("::");
+          tests.push({ id, label: m[1], file, line: j, endLine: braceBlockEnd(source, j) });
+          break;
+        }
+      }
+    }
+    return tests;
+  },
+  commands(tests) {
+    // Tests from the language server carry their own cargo invocation (the
+    // package and target they are in); those sharing one run together.
+    // Discovered tests have full module paths, so `--exact` holds for both.
+    const groups = new Map<string, TestItem[]>();
+    for (const test of tests) {
+      const key = JSON.stringify([test.cargoArgs ?? null, test.cwd ?? null]);
+      groups.set(key, [...(groups.get(key) ?? []), test]);
+    }
+    return [...groups.values()].map((group) => ({
+      command: "cargo",
+      args: [...(group[0].cargoArgs ?? ["test"]), "--", ...group.map((t) => t.id), "--exact"],
+      cwd: group[0].cwd ?? editor.getCwd(),
+    }));
+  },
+  parse(output) {
+    const results = new Map<string, TestResult>();
+    const source = lines(output);
+    for (let i = 0; i < source.length; i++) {
+      const line = source[i];
+      const outcome = line.match(/^test (\S+) \.\.\. (ok|FAILED|ignored)/);
+      if (outcome) {
+        const status: Status =
+          outcome[2] === "ok" ? "passed" : outcome[2] === "FAILED" ? "failed" : "skipped";
+        results.set(outcome[1], { ...results.get(outcome[1]), status });
+        continue;
+      }
+      // thread 'tests::adds' (123) panicked at src/lib.rs:10:5:
+      // assertion `left == right` failed
+      const panic = line.match(/^thread '([^']+)'(?: \(\d+\))? panicked at (.+?):(\d+):\d+:?$/);
+      if (panic) {
+        const previous = results.get(panic[1]);
+        results.set(panic[1], {
+          status: previous?.status ?? "failed",
+          message: (source[i + 1] ?? "").trim(),
+          file: panic[2],
+          line: parseInt(panic[3], 10) - 1,
+        });
+      }
+    }
+    return results;
+  },
+};
+
+const python: Runner = {
+  discover(file, text) {
+    const source = lines(text);
+    const relative = relativeTo(editor.getCwd(), file);
+    const tests: TestItem[] = [];
+    const classes: { name: string; indent: number }[] = [];
+    for (let i = 0; i < source.length; i++) {
+      const text = source[i];
+      if (text.trim() === "") continue;
+      const indent = text.length - text.trimStart().length;
+      while (classes.length > 0 && classes[classes.length - 1].indent >= indent) {
+        classes.pop();
+      }
+      const klass = text.match(/^\s*class\s+(\w+)/);
+      if (klass) {
+        classes.push({ name: klass[1], indent });
+        continue;
+      }
+      const def = text.match(/^\s*(?:async\s+)?def\s+(test\w*)\s*\(/);
+      if (!def) continue;
+      const outer = classes.map((c) => c.name);
+      if (outer.some((name) => !name.startsWith("Test"))) continue;
+      tests.push({
+        id: [relative, ...outer, def[1]].join("::"),
+        label: [...outer, def[1]].join("."),
+        file,
+        line: i,
+        endLine: indentBlockEnd(source, i, indent),
+      });
+    }
+    return tests;
+  },
+  commands(tests) {
+    return [
+      {
+        command: pythonCommand(),
+        args: ["-m", "pytest", "-v", "-rf", "--tb=line", ...tests.map((t) => t.id)],
+        cwd: editor.getCwd(),
+      },
+    ];
+  },
+  parse(output) {
+    const results = new Map<string, TestResult>();
+    const locations: { file: string; line: number; message: string }[] = [];
+    for (const line of lines(output)) {
+      const outcome = line.match(/^(\S+::\S+) (PASSED|FAILED|SKIPPED|ERROR|XFAIL|XPASS)\b/);
+      if (outcome) {
+        const status: Status =
+          outcome[2] === "PASSED" || outcome[2] === "XFAIL"
+            ? "passed"
+            : outcome[2] === "SKIPPED"
+              ? "skipped"
+              : "failed";
+        results.set(outcome[1], { ...results.get(outcome[1]), status });
+        continue;
+      }
+      const summary = line.match(/^(?:FAILED|ERROR) (\S+::\S+) - (.*)$/);
+      if (summary) {
+        results.set(summary[1], { ...results.get(summary[1]), status: "failed", message: summary[2] });
+        continue;
+      }
+      // --tb=line: "/abs/path/test_x.py:12: AssertionError: ..."
+      const location = line.match(/^(\S+\.py):(\d+): (.*)$/);
+      if (location) {
+        locations.push({ file: location[1], line: parseInt(location[2], 10) - 1, message: location[3] });
+      }
+    }
+    // Attach each traceback line to the failed test whose file it is in.
+    for (const [id, result] of results) {
+      if (result.status !== "failed") continue;
+      const file = id.split("::")[0];
+      const location = locations.find((l) => samePath(l.file, file));
+      if (location) {
+        result.file = location.file;
+        result.line = location.line;
+        result.message = result.message ?? location.message;
+        locations.splice(locations.indexOf(location), 1);
+      }
+    }
+    return results;
+  },
+};
+
+const go: Runner = {
+  discover(file, text) {
+    if (!file.endsWith("_test.go")) return [];
+    const source = lines(text);
+    const tests: TestItem[] = [];
+    for (let i = 0; i < source.length; i++) {
+      const m = source[i].match(/^func\s+(Test\w*)\s*\(\s*\w+\s+\*testing\.T\s*\)/);
+      if (m) {
+        tests.push({ id: m[1], label: m[1], file, line: i, endLine: braceBlockEnd(source, i) });
+      }
+    }
+    return tests;
+  },
+  commands(tests) {
+    const names = tests.map((t) => escapeRegex(t.id)).join("|");
+    return [
+      {
+        command: "go",
+        args: ["test", "-v", "-run", `^(${names})$`, "."],
+        cwd: editor.pathDirname(tests[0].file),
+      },
+    ];
+  },
+  parse(output) {
+    const results = new Map<string, TestResult>();
+    let current: string | null = null;
+    for (const line of lines(output)) {
+      const run = line.match(/^=== (?:RUN|CONT)\s+(\S+)/);
+      if (run) {
+        current = run[1];
+        continue;
+      }
+      const outcome = line.match(/^\s*--- (PASS|FAIL|SKIP): (\S+)/);
+      if (outcome) {
+        const status: Status =
+          outcome[1] === "PASS" ? "passed" : outcome[1] === "FAIL" ? "failed" : "skipped";
+        results.set(outcome[2], { ...results.get(outcome[2]), status });
+        continue;
+      }
+      const message = line.match(/^\s+(\S+\.go):(\d+): (.*)$/);
+      if (message && current && !results.get(current)?.message) {
+        results.set(current, {
+          status: results.get(current)?.status ?? "failed",
+          message: message[3],
+          file: message[1],
+          line: parseInt(message[2], 10) - 1,
+        });
+      }
+    }
+    return results;
+  },
+};
+
+const RUNNERS: Record<string, Runner> = { rust, python, go };
+
+// =============================================================================
+// Discovery
+// =============================================================================
+
+interface LspRunnable {
+  label?: string;
+  location?: { targetRange?: { start: { line: number }; end: { line: number } } };
+  kind?: string;
+  args?: { cargoArgs?: string[]; executableArgs?: string[]; cwd?: string; workspaceRoot?: string };
+}
+
+function testFromRunnable(file: string, runnable: LspRunnable): TestItem | null {
+  const range = runnable.location?.targetRange;
+  const args = runnable.args;
+  if (runnable.kind !== "cargo" || !range || !args?.cargoArgs) return null;
+  if (!runnable.label?.startsWith("test ")) return null;
+  const id = args.executableArgs?.[0];
+  if (!id) return null;
+  return {
+    id,
+    label: id,
+    file,
+    line: range.start.line,
+    endLine: range.end.line,
+    cargoArgs: args.cargoArgs,
+    cwd: args.cwd ?? args.workspaceRoot,
+  };
+}
+
+/** Tests the language server reports for `file`, or `null` when it has none. */
+async function lspTests(language: string, file: string): Promise<TestItem[] | null> {
+  const textDocument = { uri: editor.pathToFileUri(file) };
+  try {
+    const runnables = (await editor.sendLspRequest(language, "experimental/runnables", {
+      textDocument,
+    })) as LspRunnable[] | null;
+    const tests = (runnables ?? [])
+      .map((r) => testFromRunnable(file, r))
+      .filter((t): t is TestItem => t !== null);
+    if (tests.length > 0) return tests;
+  } catch (_e) {
+    // Not rust-analyzer, or no server: try code lenses.
+  }
+  try {
+    const lenses = (await editor.sendLspRequest(language, "textDocument/codeLens", {
+      textDocument,
+    })) as { command?: { arguments?: unknown[] } }[] | null;
+    const tests = (lenses ?? [])
+      .map((lens) => lens.command?.arguments?.[0] as LspRunnable | undefined)
+      .filter((r): r is LspRunnable => !!r && typeof r === "object")
+      .map((r) => testFromRunnable(file, r))
+      .filter((t): t is TestItem => t !== null);
+    if (tests.length > 0) return tests;
+  } catch (_e) {
+    // Fall back to patterns.
+  }
+  return null;
+}
+
+interface FileTests {
+  bufferId: number;
+  file: string;
+  runner: string;
+  tests: TestItem[];
+}
+
+/** The active buffer's tests, or `null` (with a status message) when its
+ * language has no test runner. */
+async function activeFileTests(): Promise<FileTests | null> {
+  const bufferId = editor.getActiveBufferId();
+  const info = editor.getBufferInfo(bufferId);
+  const runner = info ? RUNNERS[info.language] : undefined;
+  if (!info || !runner || info.path === "") {
+    editor.setStatus(editor.t("status.unsupported"));
+    return null;
+  }
+  const tests =
+    (await lspTests(info.language, info.path)) ??
+    runner.discover(info.path, await editor.getBufferText(bufferId));
+  return { bufferId, file: info.path, runner: info.language, tests };
+}
+
+// =============================================================================
+// Decorations
+// =============================================================================
+
+function remember(test: TestItem, result: TestResult): void {
+  let byId = state.known.get(test.file);
+  if (!byId) {
+    byId = new Map();
+    state.known.set(test.file, byId);
+  }
+  byId.set(test.id, { test, result });
+}
+
+/** Redraw the gutter markers and failure messages of `file`, if it is open. */
+async function decorate(file: string): Promise<void> {
+  const bufferId = editor.findBufferByPath(file);
+  if (!bufferId) return;
+  editor.clearLineIndicators(bufferId, NS_PASSED);
+  editor.clearLineIndicators(bufferId, NS_FAILED);
+  editor.clearLineIndicators(bufferId, NS_RUNNING);
+  editor.removeVirtualTextsByPrefix(bufferId, VTEXT_PREFIX);
+  const entries = [...(state.known.get(file)?.values() ?? [])];
+  if (entries.length === 0) return;
+
+  const byStatus = (status: Status) =>
+    entries.filter((e) => e.result.status === status).map((e) => e.test.line);
+  const indicators: [string, Status, [number, number, number]][] = [
+    [NS_PASSED, "passed", COLORS.passed],
+    [NS_FAILED, "failed", COLORS.failed],
+    [NS_RUNNING, "running", COLORS.running],
+  ];
+  for (const [namespace, status, [r, g, b]] of indicators) {
+    const testLines = byStatus(status);
+    if (testLines.length > 0) {
+      editor.setLineIndicators(bufferId, testLines, namespace, SYMBOLS[status], r, g, b, PRIORITY);
+    }
+  }
+
+  const failures = entries.filter((e) => e.result.status === "failed" && e.result.message);
+  if (failures.length === 0) return;
+  const source = lines(await editor.getBufferText(bufferId));
+  const lineEnd = (line: number): number => {
+    let offset = 0;
+    for (let i = 0; i < line && i < source.length; i++) {
+      offset += editor.utf8ByteLength(source[i]) + 1;
+    }
+    return offset + editor.utf8ByteLength(source[line] ?? "");
+  };
+  for (const { test, result } of failures) {
+    // On the reported line when it is inside this test, else on the test.
+    const inTest =
+      result.file !== undefined &&
+      result.line !== undefined &&
+      samePath(result.file, file) &&
+      result.line >= test.line &&
+      result.line <= test.endLine;
+    const line = inTest ? result.line! : test.line;
+    let message = result.message!.replace(/\s+/g, " ");
+    if (message.length > MAX_MESSAGE_CHARS) {
+      message = `${message.slice(0, MAX_MESSAGE_CHARS - 1)}…`;
+    }
+    editor.addVirtualTextStyled(
+      bufferId,
+      `${VTEXT_PREFIX}${test.id}`,
+      lineEnd(line),
+      `  ✗ ${message}`,
+      { fg: "diagnostic.error_fg", italic: true },
+      true, // before the newline: at the end of the line
+    );
+  }
+}
+
+// =============================================================================
+// Running
+// =============================================================================
+
+/** Result of `test` in `results`, which may report it under a longer path. */
+function resultFor(test: TestItem, results: Map<string, TestResult>): TestResult | undefined {
+  const exact = results.get(test.id);
+  if (exact) return exact;
+  for (const [id, result] of results) {
+    if (id.endsWith(`::${test.id}`)) return result;
+  }
+  return undefined;
+}
+
+async function runTests(runnerName: string, title: string, tests: TestItem[]): Promise<void> {
+  if (state.running) {
+    editor.setStatus(editor.t("status.busy"));
+    return;
+  }
+  if (tests.length === 0) {
+    editor.setStatus(editor.t("status.no_tests"));
+    return;
+  }
+  const runner = RUNNERS[runnerName];
+  const commands = runner.commands(tests);
+  const run: Run = {
+    title,
+    runner: runnerName,
+    tests,
+    commands,
+    results: new Map(),
+    outputs: [],
+    exitCode: null,
+  };
+  state.lastRun = run;
+  state.running = true;
+  const files = [...new Set(tests.map((t) => t.file))];
+  for (const test of tests) remember(test, { status: "running" });
+  for (const file of files) await decorate(file);
+  renderPanels();
+  editor.setStatus(editor.t("status.running", { count: String(tests.length) }));
+
+  for (const command of commands) {
+    try {
+      const result = await editor.spawnProcess(command.command, command.args, command.cwd);
+      run.outputs.push([result.stdout, result.stderr].filter((s) => s.length > 0).join("\n"));
+      if (run.exitCode === null || run.exitCode === 0) run.exitCode = result.exit_code;
+    } catch (e) {
+      run.outputs.push(String(e));
+    }
+  }
+  state.running = false;
+
+  run.results = runner.parse(run.outputs.join("\n"));
+  let passed = 0;
+  let failed = 0;
+  for (const test of tests) {
+    // A test the output never mentions did not run: it failed to build,
+    // or the command itself failed.
+    const result = resultFor(test, run.results) ?? {
+      status: "failed" as Status,
+      message: editor.t("result.not_run"),
+    };
+    remember(test, result);
+    if (result.status === "passed") passed++;
+    if (result.status === "failed") failed++;
+  }
+  for (const file of files) await decorate(file);
+  renderPanels();
+  editor.setStatus(
+    editor.t(failed > 0 ? "status.failed" : "status.passed", {
+      passed: String(passed),
+      failed: String(failed),
+    }),
+  );
+}
+
+// =============================================================================
+// Results panel
+// =============================================================================
+
+function renderPanels(): void {
+  if (state.groupId === null) return;
+  const run = state.lastRun;
+  const tree: TextPropertyEntry[] = [];
+  if (!run) {
+    tree.push({ text: `${editor.t("panel.no_runs")}\n`, style: { fg: "ui.menu_disabled_fg" } });
+  } else {
+    tree.push({ text: `${run.title}\n`, style: { fg: "syntax.keyword", bold: true } });
+    const byFile = new Map<string, TestItem[]>();
+    for (const test of run.tests) {
+      byFile.set(test.file, [...(byFile.get(test.file) ?? []), test]);
+    }
+    for (const [file, tests] of byFile) {
+      tree.push({ text: `▾ ${relativeTo(editor.getCwd(), file)}\n`, style: { bold: true } });
+      for (const test of tests) {
+        const result = state.known.get(file)?.get(test.id)?.result ?? { status: "running" };
+        const color =
+          result.status === "passed"
+            ? "diagnostic.info_fg"
+            : result.status === "failed"
+              ? "diagnostic.error_fg"
+              : "ui.menu_disabled_fg";
+        tree.push({
+          text: `  ${SYMBOLS[result.status]} ${test.label}\n`,
+          style: { fg: color },
+          properties: { file: test.file, line: test.line },
+        });
+        if (result.status === "failed" && result.message) {
+          tree.push({
+            text: `      ${result.message}\n`,
+            style: { fg: "ui.menu_disabled_fg" },
+            properties: {
+              file: result.file && samePath(result.file, file) ? file : test.file,
+              line: result.file && samePath(result.file, file) ? result.line : test.line,
+            },
+          });
+        }
+      }
+    }
+  }
+  editor.setPanelContent(state.groupId, "tree", tree);
+
+  const output: TextPropertyEntry[] = [];
+  if (run) {
+    run.commands.forEach((command, i) => {
+      const commandLine = [command.command, ...command.args].join(" ");
+      output.push({ text: `$ ${commandLine}\n`, style: { fg: "syntax.comment" } });
+      for (const line of lines((run.outputs[i] ?? "").replace(/\n$/, ""))) {
+        output.push({ text: `${line}\n` });
+      }
+    });
+  }
+  editor.setPanelContent(state.groupId, "output", output);
+}
+
+async function test_show_results(): Promise<void> {
+  if (state.groupId !== null) {
+    editor.focusBufferGroupPanel(state.groupId, "tree");
+    return;
+  }
+  // `createBufferGroup` is a runtime-only binding (see git_log).
+  const group = await (editor as any).createBufferGroup("*Tests*", "test-results", GROUP_LAYOUT);
+  state.groupId = group.groupId as number;
+  state.panelBuffers = group.panels as Record<string, number>;
+  for (const bufferId of Object.values(state.panelBuffers)) {
+    editor.setBufferShowCursors(bufferId, true);
+  }
+  renderPanels();
+}
+registerHandler("test_show_results", test_show_results);
+
+function test_results_close(): void {
+  if (state.groupId === null) return;
+  const groupId = state.groupId;
+  state.groupId = null;
+  state.panelBuffers = {};
+  editor.closeBufferGroup(groupId);
+}
+registerHandler("test_results_close", test_results_close);
+
+function test_results_next_panel(): void {
+  if (state.groupId === null) return;
+  const onTree = state.panelBuffers.tree === editor.getActiveBufferId();
+  editor.focusBufferGroupPanel(state.groupId, onTree ? "output" : "tree");
+}
+registerHandler("test_results_next_panel", test_results_next_panel);
+
+function test_results_open(): void {
+  const bufferId = editor.getActiveBufferId();
+  for (const record of editor.getTextPropertiesAtCursor(bufferId)) {
+    if (typeof record.file === "string" && typeof record.line === "number") {
+      editor.openFile(record.file, record.line + 1, 1);
+      return;
+    }
+  }
+}
+registerHandler("test_results_open", test_results_open);
+
+editor.on("buffer_closed", (data) => {
+  if (state.groupId === null) return;
+  if (Object.values(state.panelBuffers).includes(data.buffer_id)) {
+    state.groupId = null;
+    state.panelBuffers = {};
+  }
+});
+
+// =============================================================================
+// Commands
+// =============================================================================
+
+async function test_run_at_cursor(): Promise<void> {
+  const found = await activeFileTests();
+  if (!found) return;
+  const line = editor.getCursorLine();
+  const test = found.tests
+    .filter((t) => t.line <= line && line <= t.endLine)
+    .sort((a, b) => a.endLine - a.line - (b.endLine - b.line))[0];
+  if (!test) {
+    editor.setStatus(editor.t("status.no_test_at_cursor"));
+    return;
+  }
+  await runTests(found.runner, test.label, [test]);
+}
+registerHandler("test_run_at_cursor", test_run_at_cursor);
+
+async function test_run_file(): Promise<void> {
+  const found = await activeFileTests();
+  if (!found) return;
+  await runTests(found.runner, editor.pathBasename(found.file), found.tests);
+}
+registerHandler("test_run_file", test_run_file);
+
+async function test_run_last(): Promise<void> {
+  const run = state.lastRun;
+  if (!run) {
+    editor.setStatus(editor.t("status.no_last_run"));
+    return;
+  }
+  await runTests(run.runner, run.title, run.tests);
+}
+registerHandler("test_run_last", test_run_last);
+
+editor.registerCommand("%cmd.test_run_at_cursor", "%cmd.test_run_at_cursor_desc", "test_run_at_cursor", null);
+editor.registerCommand("%cmd.test_run_file", "%cmd.test_run_file_desc", "test_run_file", null);
+editor.registerCommand("%cmd.test_run_last", "%cmd.test_run_last_desc", "test_run_last", null);
+editor.registerCommand("%cmd.test_show_results", "%cmd.test_show_results_desc", "test_show_results", null);
+editor.debug("Test runner plugin initialized");
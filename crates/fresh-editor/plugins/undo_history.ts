@@ -0,0 +1,412 @@
+/// <reference path="./lib/fresh.d.ts" />
+import {
+  col,
+  hintBar,
+  list,
+  raw,
+  WidgetPanel,
+} from "./lib/widgets.ts";
+
+const editor = getEditor();
+
+/**
+ * Undo History Plugin
+ *
+ * "Undo History" lists every state of the active buffer's undo tree in the
+ * Utility Dock — including branches that were undone and then edited over —
+ * with the age of each change. Moving the selection previews that state as
+ * a side-by-side diff against the current text in the source split; Enter
+ * jumps the buffer to it (`editor.undoToState`).
+ */
+
+interface HistoryRow {
+  /** Undo state the row stands for (0 = the unedited buffer). */
+  seq: number;
+  /** Branch depth: later siblings of a state are indented one level. */
+  depth: number;
+  label: string;
+  timestamp: number | null;
+}
+
+interface Preview {
+  seq: number;
+  compositeId: number;
+  oldBufferId: number;
+  newBufferId: number;
+}
+
+interface UndoPanel {
+  bufferId: number;
+  splitId: number;
+  sourceSplitId: number;
+  sourceBufferId: number;
+  widgetPanel: WidgetPanel;
+  history: UndoHistory;
+  rows: HistoryRow[];
+  selected: number;
+  preview: Preview | null;
+  /** Bumped per selection so a slow preview can't land after a newer one. */
+  previewToken: number;
+}
+
+const MODE = "undo-history";
+const PREVIEW_MODE = "undo-history-preview";
+const LIST_KEY = "undoStates";
+
+let panel: UndoPanel | null = null;
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+/**
+ * Depth-first walk of the undo tree, oldest state first. The first child of
+ * a state continues at its depth; each further child starts a branch one
+ * level deeper, so a straight history stays flush left.
+ */
+function buildRows(history: UndoHistory): HistoryRow[] {
+  const children = new Map<number, UndoHistoryState[]>();
+  for (const state of history.states) {
+    const siblings = children.get(state.parent) ?? [];
+    siblings.push(state);
+    children.set(state.parent, siblings);
+  }
+  const rows: HistoryRow[] = [
+    { seq: 0, depth: 0, label: editor.t("label.original"), timestamp: null },
+  ];
+  const visit = (parent: number, depth: number): void => {
+    const kids = (children.get(parent) ?? []).sort((a, b) => a.seq - b.seq);
+    kids.forEach((state, index) => {
+      const childDepth = index === 0 ? depth : depth + 1;
+      const label = state.edits > 1
+        ? editor.t("label.edits", { label: state.label, count: String(state.edits) })
+        : state.label;
+      rows.push({ seq: state.seq, depth: childDepth, label, timestamp: state.timestamp });
+      visit(state.seq, childDepth);
+    });
+  };
+  visit(0, 0);
+  return rows;
+}
+
+function formatAge(timestamp: number | null): string {
+  if (timestamp === null) return "";
+  const seconds = Math.max(0, Math.floor((Date.now() - timestamp) / 1000));
+  let age: string;
+  if (seconds < 60) age = `${seconds}s`;
+  else if (seconds < 3600) age = `${Math.floor(seconds / 60)}m`;
+  else if (seconds < 86400) age = `${Math.floor(seconds / 3600)}h`;
+  else age = `${Math.floor(seconds / 86400)}d`;
+  return editor.t("label.ago", { age });
+}
+
+function rowEntry(row: HistoryRow, history: UndoHistory): TextPropertyEntry {
+  const marker = row.seq === history.current ? "● " : "  ";
+  const indent = "│ ".repeat(row.depth);
+  const seq = String(row.seq).padStart(3);
+  const age = formatAge(row.timestamp);
+  const saved = row.seq === history.saved ? `  ${editor.t("label.saved")}` : "";
+  const head = `${marker}${seq}  ${indent}`;
+  const text = `${head}${row.label}${age ? "  " + age : ""}${saved}`;
+  const ageStart = head.length + row.label.length + 2;
+  return {
+    text,
+    inlineOverlays: [
+      { start: 0, end: head.length, style: { fg: "syntax.keyword" } },
+      ...(age
+        ? [{ start: ageStart, end: ageStart + age.length, style: { fg: "syntax.comment" } }]
+        : []),
+    ],
+  };
+}
+
+function render(): void {
+  if (!panel) return;
+  const name = editor.pathBasename(editor.getBufferPath(panel.sourceBufferId)) ||
+    editor.t("label.untitled");
+  panel.widgetPanel.set(
+    col(
+      raw([
+        {
+          text: editor.t("title", { name }),
+          style: { fg: "syntax.keyword", bold: true },
+        },
+      ]),
+      list({
+        items: panel.rows.map((row) => rowEntry(row, panel!.history)),
+        selectedIndex: panel.selected,
+        key: LIST_KEY,
+      }),
+      hintBar([
+        { keys: "↑↓", label: editor.t("hint.preview") },
+        { keys: "Enter", label: editor.t("hint.jump") },
+        { keys: "Esc", label: editor.t("hint.close") },
+      ]),
+    ),
+  );
+}
+
+// =============================================================================
+// Diff preview
+// =============================================================================
+
+function disposePreview(preview: Preview | null): void {
+  if (!preview) return;
+  try {
+    editor.closeCompositeBuffer(preview.compositeId);
+    editor.closeBuffer(preview.oldBufferId);
+    editor.closeBuffer(preview.newBufferId);
+  } catch { /* already gone */ }
+}
+
+function textEntries(text: string): TextPropertyEntry[] {
+  return [{ text }];
+}
+
+/** Show the source buffer again in its split, dropping any preview. */
+function restoreSource(): void {
+  if (!panel) return;
+  const preview = panel.preview;
+  panel.preview = null;
+  editor.setSplitBuffer(panel.sourceSplitId, panel.sourceBufferId);
+  disposePreview(preview);
+}
+
+async function showPreview(seq: number): Promise<void> {
+  if (!panel) return;
+  const token = ++panel.previewToken;
+  if (seq === panel.history.current) {
+    restoreSource();
+    return;
+  }
+  const { sourceBufferId, sourceSplitId, splitId } = panel;
+  let current: string;
+  let target: string;
+  try {
+    [current, target] = await Promise.all([
+      editor.getUndoStateText(sourceBufferId, panel.history.current),
+      editor.getUndoStateText(sourceBufferId, seq),
+    ]);
+  } catch {
+    // A state past an edit that cannot be replayed has no preview.
+    if (panel && token === panel.previewToken) restoreSource();
+    return;
+  }
+  if (!panel || token !== panel.previewToken) return;
+
+  const name = editor.pathBasename(editor.getBufferPath(sourceBufferId)) ||
+    editor.t("label.untitled");
+  const oldRes = await editor.createVirtualBuffer({
+    name: `*Undo:current:${name}*`, mode: "normal", readOnly: true,
+    entries: textEntries(current), showLineNumbers: true, editingDisabled: true,
+    hiddenFromTabs: true,
+  });
+  const newRes = await editor.createVirtualBuffer({
+    name: `*Undo:${seq}:${name}*`, mode: "normal", readOnly: true,
+    entries: textEntries(target), showLineNumbers: true, editingDisabled: true,
+    hiddenFromTabs: true,
+  });
+  if (!panel || token !== panel.previewToken) {
+    editor.closeBuffer(oldRes.bufferId);
+    editor.closeBuffer(newRes.bufferId);
+    return;
+  }
+
+  const compositeId = await editor.createCompositeBuffer({
+    name: editor.t("preview_title", { name, seq: String(seq) }),
+    mode: PREVIEW_MODE,
+    layout: { type: "side-by-side", ratios: [0.5, 0.5], showSeparator: true },
+    sources: [
+      {
+        bufferId: oldRes.bufferId,
+        label: editor.t("pane.current"),
+        editable: false,
+        style: { gutterStyle: "diff-markers" },
+      },
+      {
+        bufferId: newRes.bufferId,
+        label: editor.t("pane.state", { seq: String(seq) }),
+        editable: false,
+        style: { gutterStyle: "diff-markers" },
+      },
+    ],
+    hunks: editor.computeLineDiff(current, target),
+  });
+
+  const preview = { seq, compositeId, oldBufferId: oldRes.bufferId, newBufferId: newRes.bufferId };
+  if (!panel || token !== panel.previewToken) {
+    disposePreview(preview);
+    return;
+  }
+  const previous = panel.preview;
+  panel.preview = preview;
+  // showBuffer targets the active split: show the diff in the source split
+  // and hand focus back to the list.
+  editor.focusSplit(sourceSplitId);
+  editor.showBuffer(compositeId);
+  editor.focusSplit(splitId);
+  disposePreview(previous);
+}
+
+// =============================================================================
+// Panel lifecycle
+// =============================================================================
+
+async function refresh(): Promise<void> {
+  if (!panel) return;
+  panel.history = await editor.getUndoHistory(panel.sourceBufferId);
+  panel.rows = buildRows(panel.history);
+  const current = panel.rows.findIndex((row) => row.seq === panel!.history.current);
+  panel.selected = Math.max(0, current);
+  render();
+}
+
+async function undo_history_show(): Promise<void> {
+  const sourceBufferId = editor.getActiveBufferId();
+  if (panel && panel.bufferId === sourceBufferId) return;
+  if (panel) {
+    panel.sourceBufferId = sourceBufferId;
+    panel.sourceSplitId = editor.getActiveSplitId();
+    restoreSource();
+    await refresh();
+    editor.focusSplit(panel.splitId);
+    return;
+  }
+
+  const sourceSplitId = editor.getActiveSplitId();
+  const history = await editor.getUndoHistory(sourceBufferId);
+  const result = await editor.createVirtualBufferInSplit({
+    name: "*Undo History*",
+    mode: MODE,
+    readOnly: true,
+    entries: [],
+    ratio: 0.65,
+    panelId: "undo-history-panel",
+    role: "utility_dock",
+    showLineNumbers: false,
+    showCursors: false,
+    editingDisabled: true,
+    scrollable: false,
+  });
+  panel = {
+    bufferId: result.bufferId,
+    splitId: result.splitId ?? editor.getActiveSplitId(),
+    sourceSplitId,
+    sourceBufferId,
+    widgetPanel: new WidgetPanel(result.bufferId),
+    history,
+    rows: [],
+    selected: 0,
+    preview: null,
+    previewToken: 0,
+  };
+  await refresh();
+}
+registerHandler("undo_history_show", undo_history_show);
+
+async function undo_history_jump(): Promise<void> {
+  if (!panel) return;
+  const row = panel.rows[panel.selected];
+  if (!row) return;
+  panel.previewToken++;
+  restoreSource();
+  // undoToState acts on the active buffer.
+  editor.focusSplit(panel.sourceSplitId);
+  editor.undoToState(panel.sourceBufferId, row.seq);
+  await refresh();
+}
+registerHandler("undo_history_jump", undo_history_jump);
+
+function undo_history_close(): void {
+  if (!panel) return;
+  panel.previewToken++;
+  restoreSource();
+  const { bufferId, splitId, sourceSplitId, widgetPanel } = panel;
+  panel = null;
+  widgetPanel.unmount();
+  editor.closeBuffer(bufferId);
+  if (splitId !== sourceSplitId) {
+    editor.closeSplit(splitId);
+  }
+  editor.focusSplit(sourceSplitId);
+}
+registerHandler("undo_history_close", undo_history_close);
+
+function select(index: number): void {
+  if (!panel || panel.rows.length === 0) return;
+  const clamped = Math.max(0, Math.min(index, panel.rows.length - 1));
+  if (clamped === panel.selected) return;
+  panel.selected = clamped;
+  render();
+  showPreview(panel.rows[clamped].seq).catch((e) => editor.error(`undo-history: ${e}`));
+}
+
+// Clicking a row selects it like the arrow keys do.
+editor.on("widget_event", (args) => {
+  if (!panel || args.panel_id !== panel.widgetPanel.id()) return;
+  if (args.widget_key !== LIST_KEY || args.event_type !== "select") return;
+  const payload = args.payload as { index?: number } | undefined;
+  if (typeof payload?.index === "number") select(payload.index);
+});
+
+editor.on("buffer_closed", (args) => {
+  if (!panel) return;
+  if (args.buffer_id === panel.bufferId) {
+    panel.previewToken++;
+    disposePreview(panel.preview);
+    panel.widgetPanel.unmount();
+    panel = null;
+  } else if (args.buffer_id === panel.sourceBufferId) {
+    undo_history_close();
+  }
+});
+
+// Every selection change previews, so the plugin owns the selection.
+const PAGE = 10;
+const NAV_KEYS: [string, (selected: number) => number][] = [
+  ["Up", (i) => i - 1],
+  ["Down", (i) => i + 1],
+  ["PageUp", (i) => i - PAGE],
+  ["PageDown", (i) => i + PAGE],
+  ["Home", () => 0],
+  ["End", () => Number.MAX_SAFE_INTEGER],
+];
+for (const [name, step] of NAV_KEYS) {
+  registerHandler(`undo_history_key_${name}`, () => {
+    if (panel) select(step(panel.selected));
+  });
+}
+
+editor.defineMode(
+  MODE,
+  [
+    ...NAV_KEYS.map(([name]): [string, string] => [name, `undo_history_key_${name}`]),
+    ["Return", "undo_history_jump"],
+    ["q", "undo_history_close"],
+    ["Escape", "undo_history_close"],
+  ],
+  true,
+  false,
+);
+
+// The preview keeps normal scrolling; only the panel verbs are rebound.
+editor.defineMode(
+  PREVIEW_MODE,
+  [
+    ["Return", "undo_history_jump"],
+    ["q", "undo_history_close"],
+    ["Escape", "undo_history_close"],
+  ],
+  true,
+  false,
+  true,
+);
+
+editor.registerCommand(
+  "%cmd.undo_history",
+  "%cmd.undo_history_desc",
+  "undo_history_show",
+  null,
+);
+
+editor.debug("Undo history plugin initialized");
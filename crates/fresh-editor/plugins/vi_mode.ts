@@ -2015,6 +2015,17 @@ function vi_redo() : void {
 }
 registerHandler("vi_redo", vi_redo);
 
+// g- / g+: walk undo states in time order, across undo branches
+function vi_undo_in_time() : void {
+  editor.executeAction("undo_in_time");
+}
+registerHandler("vi_undo_in_time", vi_undo_in_time);
+
+function vi_redo_in_time() : void {
+  editor.executeAction("redo_in_time");
+}
+registerHandler("vi_redo_in_time", vi_redo_in_time);
+
 // Repeat last change (. command)
 async function vi_repeat() : Promise<void> {
   if (!state.lastChange) {
@@ -2626,6 +2637,42 @@ async function takeVisualRangeText(): Promise<{ bufferId: number; start: number;
   const text = await editor.getBufferText(bufferId, range.start, range.end);
   return { bufferId, start: range.start, end: range.end, text };
 }
+// Replace the visual selection with a syntax text object around its head.
+async function selectVisualTextObject(objectType: string, inner: boolean): Promise<void> {
+  consumeCount();
+  const origin = visualWORDMotionOrigin();
+  if (origin === null) {
+    return;
+  }
+  const bufferId = editor.getActiveBufferId();
+  const span = await editor.getTextObjectRange(
+    bufferId, origin, syntaxTextObjectName(objectType, inner));
+  if (!span || span.end <= span.start) {
+    return;
+  }
+  const text = await editor.getBufferText(bufferId, span.start, span.end);
+  state.visualAnchor = span.start;
+  state.visualHead = span.end - 1;
+  state.visualRange = { start: span.start, end: span.end };
+  editor.setBufferCursor(bufferId, span.start);
+  for (let i = [...text].length; i > 0; i--) {
+    editor.executeAction("select_right");
+  }
+}
+
+async function vi_vis_inner_function(): Promise<void> { await selectVisualTextObject("f", true); }
+registerHandler("vi_vis_inner_function", vi_vis_inner_function);
+async function vi_vis_around_function(): Promise<void> { await selectVisualTextObject("f", false); }
+registerHandler("vi_vis_around_function", vi_vis_around_function);
+async function vi_vis_inner_class(): Promise<void> { await selectVisualTextObject("c", true); }
+registerHandler("vi_vis_inner_class", vi_vis_inner_class);
+async function vi_vis_around_class(): Promise<void> { await selectVisualTextObject("c", false); }
+registerHandler("vi_vis_around_class", vi_vis_around_class);
+async function vi_vis_inner_parameter(): Promise<void> { await selectVisualTextObject("a", true); }
+registerHandler("vi_vis_inner_parameter", vi_vis_inner_parameter);
+async function vi_vis_around_parameter(): Promise<void> { await selectVisualTextObject("a", false); }
+registerHandler("vi_vis_around_parameter", vi_vis_around_parameter);
+
 // Visual mode operators - act on selection
 async function vi_vis_delete() : Promise<void> {
   const directRange = await takeVisualRangeText();
@@ -2785,6 +2832,19 @@ async function applyTextObject(objectType: string): Promise<void> {
   let selectEnd = -1;
 
   switch (objectType) {
+    case "f":
+    case "c":
+    case "a": {
+      // Syntax-aware objects come from the language's textobjects.scm.
+      const span = await editor.getTextObjectRange(
+        bufferId, cursorPos, syntaxTextObjectName(objectType, isInner));
+      if (span) {
+        selectStart = span.start;
+        selectEnd = span.end;
+      }
+      break;
+    }
+
     case "word": {
       // Find word boundaries
       const wordChars = /[a-zA-Z0-9_]/;
@@ -3002,6 +3062,13 @@ async function applyTextObject(objectType: string): Promise<void> {
   switchMode("normal");
 }
 
+// Name of the tree-sitter text object behind a vi object key: `f` is a
+// function, `c` a class, `a` a parameter (argument).
+function syntaxTextObjectName(objectType: string, inner: boolean): string {
+  const kind = objectType === "f" ? "function" : objectType === "c" ? "class" : "parameter";
+  return `${kind}.${inner ? "inner" : "outer"}`;
+}
+
 // Helper to find matching bracket pair containing the cursor
 function findMatchingPair(text: string, pos: number, openChar: string, closeChar: string): { start: number; end: number } | null {
   let depth = 0;
@@ -3054,6 +3121,12 @@ async function vi_to_bracket(): Promise<void> { await applyTextObject("["); }
 registerHandler("vi_to_bracket", vi_to_bracket);
 async function vi_to_angle(): Promise<void> { await applyTextObject("<"); }
 registerHandler("vi_to_angle", vi_to_angle);
+async function vi_to_function(): Promise<void> { await applyTextObject("f"); }
+registerHandler("vi_to_function", vi_to_function);
+async function vi_to_class(): Promise<void> { await applyTextObject("c"); }
+registerHandler("vi_to_class", vi_to_class);
+async function vi_to_parameter(): Promise<void> { await applyTextObject("a"); }
+registerHandler("vi_to_parameter", vi_to_parameter);
 
 // Cancel text object mode
 function vi_to_cancel(): void {
@@ -3582,6 +3655,8 @@ function defineViModes(): void {
     // Undo/Redo
     ["u", "vi_undo"],
     ["C-r", "vi_redo"],
+    ["g -", "vi_undo_in_time"],
+    ["g +", "vi_redo_in_time"],
 
     // Repeat last change
     [".", "vi_repeat"],
@@ -3704,6 +3779,11 @@ function defineViModes(): void {
     ["<", "vi_to_angle"],
     [">", "vi_to_angle"],
 
+    // Syntax objects (tree-sitter)
+    ["f", "vi_to_function"],
+    ["c", "vi_to_class"],
+    ["a", "vi_to_parameter"],
+
     // Cancel
     ["Escape", "vi_to_cancel"],
   ], true);
@@ -3746,6 +3826,14 @@ function defineViModes(): void {
     ["{", "vi_vis_paragraph_up"],
     ["}", "vi_vis_paragraph_down"],
 
+    // Syntax objects (tree-sitter): select a function, class or parameter
+    ["i f", "vi_vis_inner_function"],
+    ["a f", "vi_vis_around_function"],
+    ["i c", "vi_vis_inner_class"],
+    ["a c", "vi_vis_around_class"],
+    ["i a", "vi_vis_inner_parameter"],
+    ["a a", "vi_vis_around_parameter"],
+
     // Switch visual sub-modes
     ["V", "vi_visual_toggle_line"],
     ["C-v", "vi_visual_block"],  // Switch to block mode
@@ -3936,6 +4024,8 @@ const opCommands = [
   ["vi_paste_before", "paste_before"],
   ["vi_undo", "undo"],
   ["vi_redo", "redo"],
+  ["vi_undo_in_time", "undo_in_time"],
+  ["vi_redo_in_time", "redo_in_time"],
   ["vi_join", "join_lines"],
 ];
 
@@ -0,0 +1,18 @@
+; Functions: the whole definition, and its body
+(function_declaration body: (_) @function.inner) @function.outer
+(method_declaration body: (_) @function.inner) @function.outer
+(func_literal body: (_) @function.inner) @function.outer
+
+; Struct and interface types stand in for classes
+(type_declaration
+  (type_spec type: (struct_type (field_declaration_list) @class.inner))) @class.outer
+(type_declaration
+  (type_spec type: (interface_type) @class.inner)) @class.outer
+
+; Parameters and call arguments
+(parameter_list (_) @parameter.inner)
+(argument_list (_) @parameter.inner)
+
+(comment) @comment.outer
+
+(block) @block.outer
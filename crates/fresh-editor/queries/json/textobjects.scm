@@ -0,0 +1,8 @@
+; Object members and array elements swap like parameters
+(object (pair) @parameter.inner)
+(array (_) @parameter.inner)
+
+(comment) @comment.outer
+
+(object) @block.outer
+(array) @block.outer
@@ -0,0 +1,2 @@
+; Appended to Go's textobjects.scm: templ components are functions too
+(component_declaration (component_block) @function.inner) @function.outer
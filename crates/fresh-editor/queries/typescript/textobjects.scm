@@ -0,0 +1,21 @@
+; Functions: the whole definition, and its body
+(function_declaration body: (_) @function.inner) @function.outer
+(generator_function_declaration body: (_) @function.inner) @function.outer
+(function_expression body: (_) @function.inner) @function.outer
+(generator_function body: (_) @function.inner) @function.outer
+(arrow_function body: (_) @function.inner) @function.outer
+(method_definition body: (_) @function.inner) @function.outer
+
+; Classes and interfaces
+(class_declaration body: (_) @class.inner) @class.outer
+(abstract_class_declaration body: (_) @class.inner) @class.outer
+(class body: (_) @class.inner) @class.outer
+(interface_declaration body: (_) @class.inner) @class.outer
+
+; Parameters and call arguments
+(formal_parameters (_) @parameter.inner)
+(arguments (_) @parameter.inner)
+
+(comment) @comment.outer
+
+(statement_block) @block.outer
@@ -9,6 +9,7 @@
 //! separate from *doing what the action says*.
 
 use super::*;
+use crate::model::kill_ring::KillDirection;
 use anyhow::Result as AnyhowResult;
 use crossterm::event::KeyModifiers as KM;
 use rust_i18n::t;
@@ -88,6 +89,17 @@ impl Editor {
             self.reset_dabbrev_state();
         }
 
+        // Only yank-pop continues a yank, and only kills continue a kill.
+        if !matches!(action, Action::YankPop) {
+            self.active_window_mut().yank_state = None;
+        }
+        if !matches!(
+            action,
+            Action::Cut | Action::KillLine | Action::KillWordForward | Action::KillWordBackward
+        ) {
+            self.active_window_mut().kill_chain = false;
+        }
+
         // Enter on a line that points somewhere (`editor.setLineTargets`)
         // follows it, the same as clicking it. Intercepted here rather than
         // inside the newline handler so the behaviour is identical whether
@@ -461,6 +473,15 @@ impl Editor {
                 }
                 self.paste()
             }
+            Action::KillLine => self.kill_with(Action::DeleteToLineEnd, KillDirection::Forward),
+            Action::KillWordForward => {
+                self.kill_with(Action::DeleteWordForward, KillDirection::Forward)
+            }
+            Action::KillWordBackward => {
+                self.kill_with(Action::DeleteWordBackward, KillDirection::Backward)
+            }
+            Action::YankPop => self.yank_pop(),
+            Action::PasteFromKillRing => self.start_kill_ring_prompt(),
             Action::SelectAll => {
                 // Focused widget Text wins over the buffer's
                 // select-all. SelectAll on the buffer is then
@@ -484,6 +505,8 @@ impl Editor {
             Action::Redo => {
                 self.handle_redo();
             }
+            Action::UndoInTime => self.handle_undo_in_time(),
+            Action::RedoInTime => self.handle_redo_in_time(),
             Action::ShowHelp => {
                 self.ensure_help_panel_mode_registered();
                 self.active_window_mut().open_help_manual();
@@ -536,6 +559,12 @@ impl Editor {
                 }
                 self.start_quick_open_with_prefix("");
             }
+            Action::QuickOpenSymbols => {
+                if self.close_quick_open_if_open() {
+                    return Ok(());
+                }
+                self.start_quick_open_with_prefix("@");
+            }
             Action::OpenLiveGrep => {
                 self.handle_action(Action::PluginAction("start_live_grep".to_string()))?;
             }
@@ -881,12 +910,30 @@ impl Editor {
             Action::LspImplementation => {
                 self.request_implementation()?;
             }
+            Action::LspCallHierarchy => {
+                self.request_hierarchy(crate::services::lsp::hierarchy::HierarchyKind::Call);
+            }
+            Action::LspTypeHierarchy => {
+                self.request_hierarchy(crate::services::lsp::hierarchy::HierarchyKind::Type);
+            }
             Action::LspSignatureHelp => {
                 self.request_signature_help();
             }
             Action::LspCodeActions => {
                 self.request_code_actions()?;
             }
+            Action::LspCodeLens => {
+                self.run_code_lens_at_cursor();
+            }
+            Action::LspFollowLink => self.follow_document_link_at_cursor(),
+            Action::LspPickColor => self.pick_color_at_cursor(),
+            Action::SmartExpandSelection => self.smart_expand_selection(),
+            Action::SmartShrinkSelection => self.smart_shrink_selection(),
+            Action::SelectTextObject(object) => self.select_text_object(&object),
+            Action::GotoNextTextObject(object) => self.goto_text_object(&object, true),
+            Action::GotoPreviousTextObject(object) => self.goto_text_object(&object, false),
+            Action::SwapParameterNext => self.swap_parameter(true),
+            Action::SwapParameterPrevious => self.swap_parameter(false),
             Action::LspRestart => {
                 self.handle_lsp_restart();
             }
@@ -896,6 +943,20 @@ impl Editor {
             Action::LspToggleForBuffer => {
                 self.handle_lsp_toggle_for_buffer();
             }
+            Action::DebugStart => self.debug_start(),
+            Action::DebugStop => self.debug_stop(),
+            Action::DebugContinue => self.debug_thread_request("continue"),
+            Action::DebugPause => self.debug_thread_request("pause"),
+            Action::DebugStepOver => self.debug_thread_request("next"),
+            Action::DebugStepInto => self.debug_thread_request("stepIn"),
+            Action::DebugStepOut => self.debug_thread_request("stepOut"),
+            Action::DebugToggleBreakpoint => self.debug_toggle_breakpoint(),
+            Action::DebugClearBreakpoints => self.debug_clear_breakpoints(),
+            Action::RunTask => self.start_run_task_prompt(),
+            Action::RerunLastTask => self.rerun_last_task(),
+            Action::StopTasks => self.stop_tasks(),
+            Action::NextProblem => self.goto_task_problem(true),
+            Action::PreviousProblem => self.goto_task_problem(false),
             Action::ToggleInlayHints => {
                 self.toggle_inlay_hints();
             }
@@ -1060,6 +1121,8 @@ impl Editor {
             Action::TogglePromptLine => self.toggle_prompt_line(),
             Action::ToggleVerticalScrollbar => self.toggle_vertical_scrollbar(),
             Action::ToggleHorizontalScrollbar => self.toggle_horizontal_scrollbar(),
+            Action::ToggleStickyScroll => self.toggle_sticky_scroll(),
+            Action::ToggleBreadcrumbs => self.toggle_breadcrumbs(),
             Action::ToggleLineNumbers => self.toggle_line_numbers(),
             Action::ToggleLineNumbersCurrentBuffer => self.toggle_line_numbers_current_buffer(),
             Action::ToggleLineWrapCurrentBuffer => self.toggle_line_wrap_current_buffer(),
@@ -1070,6 +1133,7 @@ impl Editor {
             Action::ToggleFoldIndicatorsCurrentBuffer => {
                 self.toggle_fold_indicators_current_buffer()
             }
+            Action::ToggleMinimapCurrentBuffer => self.toggle_minimap_current_buffer(),
             Action::ToggleCurrentLineHighlightCurrentBuffer => {
                 self.toggle_current_line_highlight_current_buffer()
             }
@@ -1080,6 +1144,7 @@ impl Editor {
             Action::ToggleScrollSync => self.active_window_mut().toggle_scroll_sync(),
             Action::ToggleMouseCapture => self.toggle_mouse_capture(),
             Action::ToggleMouseHover => self.toggle_mouse_hover(),
+            Action::ToggleSpellCheck => self.toggle_spell_check(),
             Action::ToggleDebugHighlights => self.active_window_mut().toggle_debug_highlights(),
             // Rulers
             Action::AddRuler => {
@@ -1722,6 +1787,24 @@ impl Editor {
             Action::SendSelectionToTerminal => {
                 self.send_selection_to_terminal();
             }
+            Action::TerminalPreviousPrompt => {
+                self.jump_to_terminal_prompt(false);
+            }
+            Action::TerminalNextPrompt => {
+                self.jump_to_terminal_prompt(true);
+            }
+            Action::TerminalSelectCommandOutput => {
+                self.select_terminal_command_output(false);
+            }
+            Action::TerminalCopyCommandOutput => {
+                self.select_terminal_command_output(true);
+            }
+            Action::TerminalRerunCommand => {
+                self.rerun_terminal_command();
+            }
+            Action::TerminalFoldCommandOutputs => {
+                self.toggle_terminal_output_folds();
+            }
             Action::ShellCommand => {
                 // Run shell command on buffer/selection, output to new buffer
                 self.start_shell_command_prompt(false);
@@ -1986,8 +2069,8 @@ impl Editor {
     }
 
     /// If the Quick Open prompt is currently open, cancel it and return `true`.
-    /// All four Quick Open variants (CommandPalette, QuickOpen, QuickOpenBuffers,
-    /// QuickOpenFiles) toggle off when invoked while the picker is already visible.
+    /// All Quick Open variants (CommandPalette, QuickOpen, QuickOpenBuffers,
+    /// QuickOpenFiles, QuickOpenSymbols) toggle off when invoked while the picker is already visible.
     fn close_quick_open_if_open(&mut self) -> bool {
         if let Some(prompt) = &self.active_window_mut().prompt {
             if prompt.prompt_type == PromptType::QuickOpen {
@@ -137,6 +137,13 @@ impl Editor {
         // reading them on the editor loop.
         self.drive_pending_content_loads();
 
+        // A language server's question whose popup went away without an
+        // answer (dismissed along with other menus) is answered as declined,
+        // so the server isn't left waiting; an explorer operation whose
+        // review went away is cancelled.
+        self.drop_orphaned_lsp_window_requests();
+        self.drop_orphaned_file_operation();
+
         let Some(bridge) = &self.async_bridge else {
             return false;
         };
@@ -227,6 +234,13 @@ impl Editor {
                         tracing::error!("Error handling implementation response: {}", e);
                     }
                 }
+                AsyncMessage::LspHierarchy {
+                    language,
+                    request_id,
+                    result,
+                } => {
+                    self.handle_hierarchy_response(request_id, &language, result);
+                }
                 AsyncMessage::LspRename { request_id, result } => {
                     if let Err(e) = self.handle_rename_response(request_id, result) {
                         tracing::error!("Error handling rename response: {}", e);
@@ -263,6 +277,22 @@ impl Editor {
                 AsyncMessage::LspApplyEdit { edit, label } => {
                     self.handle_lsp_apply_edit(edit, label);
                 }
+                AsyncMessage::LspShowMessageRequest {
+                    language: _,
+                    server_name,
+                    params,
+                    reply,
+                } => {
+                    self.handle_lsp_show_message_request(server_name, params, reply);
+                }
+                AsyncMessage::LspShowDocument {
+                    language: _,
+                    server_name,
+                    params,
+                    reply,
+                } => {
+                    self.handle_lsp_show_document(server_name, params, reply);
+                }
                 AsyncMessage::LspCodeActionResolved {
                     request_id: _,
                     action,
@@ -321,6 +351,81 @@ impl Editor {
                 } => {
                     self.handle_lsp_folding_ranges(request_id, uri, ranges);
                 }
+                AsyncMessage::LspCodeLens {
+                    request_id,
+                    uri,
+                    lenses,
+                } => {
+                    self.active_window_mut()
+                        .handle_lsp_code_lens(request_id, uri, lenses);
+                }
+                AsyncMessage::LspSelectionRanges {
+                    request_id,
+                    uri: _,
+                    ranges,
+                } => {
+                    self.handle_lsp_selection_ranges(request_id, ranges);
+                }
+                AsyncMessage::LspLinkedEditingRanges {
+                    request_id,
+                    uri: _,
+                    ranges,
+                } => {
+                    self.handle_lsp_linked_editing_ranges(request_id, ranges);
+                }
+                AsyncMessage::LspOnTypeFormatting {
+                    request_id,
+                    uri: _,
+                    edits,
+                } => {
+                    self.handle_lsp_on_type_formatting(request_id, edits);
+                }
+                AsyncMessage::LspDocumentLinks {
+                    request_id,
+                    uri,
+                    links,
+                } => {
+                    self.active_window_mut()
+                        .handle_lsp_document_links(request_id, uri, links);
+                }
+                AsyncMessage::LspDocumentLinkResolved { request_id, link } => {
+                    self.handle_lsp_document_link_resolved(request_id, link);
+                }
+                AsyncMessage::LspDocumentColors {
+                    request_id,
+                    uri,
+                    colors,
+                } => {
+                    self.active_window_mut()
+                        .handle_lsp_document_colors(request_id, uri, colors);
+                }
+                AsyncMessage::LspColorPresentations {
+                    request_id,
+                    uri: _,
+                    presentations,
+                } => {
+                    self.handle_lsp_color_presentations(request_id, presentations);
+                }
+                AsyncMessage::LspDocumentSymbols {
+                    request_id,
+                    uri: _,
+                    symbols,
+                } => {
+                    self.handle_lsp_document_symbols(request_id, symbols);
+                }
+                AsyncMessage::LspWillFileOperation { request_id, edit } => {
+                    self.handle_lsp_will_file_operation(request_id, edit);
+                }
+                AsyncMessage::LspWorkspaceSymbols {
+                    request_id,
+                    symbols,
+                } => {
+                    self.handle_lsp_workspace_symbols(request_id, symbols);
+                }
+                AsyncMessage::LspCodeLensResolved { request_id, lens } => {
+                    self.active_window_mut()
+                        .handle_lsp_code_lens_resolved(request_id, lens);
+                }
                 AsyncMessage::LspSemanticTokens {
                     request_id,
                     uri,
@@ -340,6 +445,9 @@ impl Editor {
                 AsyncMessage::LspSemanticTokensRefresh { language } => {
                     self.handle_lsp_semantic_tokens_refresh(language);
                 }
+                AsyncMessage::LspCodeLensRefresh { language } => {
+                    self.handle_lsp_code_lens_refresh(language);
+                }
                 AsyncMessage::LspDynamicCapabilities {
                     language,
                     server_name,
@@ -453,6 +561,30 @@ impl Editor {
                 } => {
                     self.handle_plugin_lsp_response(request_id, result);
                 }
+                AsyncMessage::DapEvent {
+                    session_id,
+                    event,
+                    body,
+                } => {
+                    self.handle_dap_event(session_id, event, body);
+                }
+                AsyncMessage::DapResponse {
+                    session_id,
+                    origin,
+                    command,
+                    result,
+                } => {
+                    self.handle_dap_response(session_id, origin, command, result);
+                }
+                AsyncMessage::DapSessionEnded { session_id, error } => {
+                    self.handle_dap_session_ended(session_id, error);
+                }
+                AsyncMessage::TaskOutput { run_id, lines } => {
+                    self.handle_task_output(run_id, lines);
+                }
+                AsyncMessage::TaskFinished { run_id, result } => {
+                    self.handle_task_finished(run_id, result);
+                }
                 AsyncMessage::RemoteAttachReady(ready) => {
                     self.handle_remote_attach_ready(ready);
                 }
@@ -652,6 +784,9 @@ impl Editor {
         self.resend_did_open_for_language(&language);
         self.request_semantic_tokens_for_language(&language);
         self.request_folding_ranges_for_language(&language);
+        self.request_code_lens_for_language(&language);
+        self.request_document_links_for_language(&language);
+        self.request_document_colors_for_language(&language);
         // Now that capabilities are known, kick off inlay hints
         // and pull-diagnostics for buffers that opened before the
         // `initialize` handshake completed. Both paths route
@@ -843,6 +978,7 @@ impl Editor {
         let owner = terminal.window;
         // Terminal output received - check if we should auto-jump back to terminal mode
         tracing::trace!("Terminal output received for {}", terminal);
+        self.handle_task_terminal_output(terminal);
 
         // If the focused split is viewing this terminal in scrollback and
         // jump_to_end_on_output is enabled, snap it back to the live grid.
@@ -1250,6 +1386,7 @@ impl Editor {
         let terminal_id = terminal.terminal;
         let exited_window_id = terminal.window;
         tracing::info!("Terminal {} exited", terminal);
+        self.handle_task_terminal_exited(terminal, exit_code);
         // A remote window whose carrier just dropped: its embedded PTY (a
         // separate `ssh -t` / `kubectl exec` from the agent channel) died with
         // the link, not because the user exited the shell. Keep the
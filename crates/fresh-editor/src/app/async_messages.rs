@@ -118,7 +118,7 @@ impl Editor {
     /// Anchor freshly received diagnostics to the open buffer for `uri` (if
     /// any), stamping each with the buffer's current version so `CoordMap` can
     /// carry it forward across later edits (#2602).
-    fn anchor_diagnostics(
+    pub(super) fn anchor_diagnostics(
         &self,
         uri: &str,
         diagnostics: Vec<Diagnostic>,
@@ -136,7 +136,7 @@ impl Editor {
 
     /// Materialise the merged push + pull view (positions mapped to the buffer's
     /// current version) and rebuild the overlays from it.
-    fn merge_and_apply_diagnostics(&mut self, uri: &str) {
+    pub(super) fn merge_and_apply_diagnostics(&mut self, uri: &str) {
         let merged = self.active_window_mut().recompute_merged_diagnostics(uri);
 
         if let Some((buffer_id, updated)) = self.apply_diagnostics_to_buffer(uri, &merged) {
@@ -427,6 +427,10 @@ impl crate::app::window::Window {
                 request.buffer_id
             );
         }
+        // Applying hints cleared every virtual text, lens rows and colour
+        // swatches included.
+        self.render_code_lenses(request.buffer_id);
+        self.render_document_colors(request.buffer_id);
     }
 }
 
@@ -999,6 +1003,9 @@ impl Editor {
         if changed && register {
             self.request_semantic_tokens_for_language(&language);
             self.request_folding_ranges_for_language(&language);
+            self.request_code_lens_for_language(&language);
+            self.request_document_links_for_language(&language);
+            self.request_document_colors_for_language(&language);
             self.request_inlay_hints_for_language(&language);
             self.pull_diagnostics_for_language(&language);
         }
@@ -105,6 +105,13 @@ impl Editor {
             });
         }
 
+        // Keep the undo history for the next time the file is opened.
+        self.persist_undo_history(id);
+
+        // Remember where this buffer's breakpoints ended up so they can be
+        // restored when the file is reopened.
+        self.active_window_mut().sync_breakpoints_for_buffer(id);
+
         // Delete recovery data for explicitly closed buffers (including unnamed)
         if let Err(e) = self.delete_buffer_recovery(id) {
             tracing::debug!("Failed to delete buffer recovery on close: {}", e);
@@ -235,6 +242,7 @@ impl Editor {
             .terminal_explicit_titles
             .remove(&id);
         self.active_window_mut().terminal_fg_cache.remove(&id);
+        self.active_window_mut().terminal_command_blocks.remove(&id);
 
         // Retain the rendered backing file so its scrollback stays
         // searchable after close (Universal Search "Terminals" scope).
@@ -259,6 +267,11 @@ impl Editor {
             self.spawn_off_loop_effect("terminal_backing_cleanup", move || {
                 if let Some(ref path) = backing {
                     Self::retain_closed_terminal_backing(path);
+                    // Command blocks only serve the live scroll-back view.
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = crate::app::terminal::terminal_backing_fs().remove_file(
+                        &crate::services::terminal::command_blocks::blocks_path(path),
+                    );
                 }
                 if let Some(log_file) = log_file {
                     if backing.as_ref() != Some(&log_file) {
@@ -492,6 +505,9 @@ impl Editor {
         self.active_window_mut()
             .semantic_tokens_full_debounce
             .remove(&id);
+        self.active_window_mut().clear_code_lenses(id);
+        self.active_window_mut().clear_document_links(id);
+        self.active_window_mut().clear_document_colors(id);
 
         // Remove buffer from the active window's panel_ids mapping
         // if it was a panel buffer. Prevents stale entries when the
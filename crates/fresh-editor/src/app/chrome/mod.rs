@@ -246,6 +246,8 @@ pub(crate) enum PointerGrab {
     VScrollbar,
     /// A split's horizontal scrollbar.
     HScrollbar,
+    /// A split's minimap (centres the viewport on the row under the pointer).
+    Minimap,
     /// Text selection inside an info popup.
     PopupSelect,
     /// The prompt's suggestion-list scrollbar (overlay and dropdown).
@@ -296,6 +298,9 @@ pub(crate) fn pointer_grab(ed: &Editor) -> Option<PointerGrab> {
     if ms.dragging_horizontal_scrollbar.is_some() {
         return Some(PointerGrab::HScrollbar);
     }
+    if ms.dragging_minimap.is_some() {
+        return Some(PointerGrab::Minimap);
+    }
     if ms.selecting_in_popup.is_some() {
         return Some(PointerGrab::PopupSelect);
     }
@@ -68,6 +68,17 @@ impl ChromeComponent for Splits {
         for (_, _, r, _, _, _) in &ed.active_layout().horizontal_scrollbar_areas {
             t.rect("chrome:h_scrollbar", 50, *r);
         }
+        for minimap in &ed.active_layout().minimap_areas {
+            t.rect("chrome:minimap", 50, minimap.rect);
+        }
+        for area in &ed.active_layout().scope_header_areas {
+            if let Some(rect) = area.sticky_rect {
+                t.rect("chrome:sticky_scroll", 50, rect);
+            }
+            if let Some(rect) = area.breadcrumb_rect {
+                t.rect("chrome:breadcrumbs", 50, rect);
+            }
+        }
         for (_, _, content_rect, ..) in &ed.active_layout().split_areas {
             t.rect("chrome:editor", 10, *content_rect);
         }
@@ -237,6 +248,9 @@ impl ChromeComponent for Splits {
         let consumed = match bx.kind {
             "chrome:scrollbars" => ed.handle_click_scrollbar(ev.col, ev.row),
             "chrome:h_scrollbar" => ed.handle_click_horizontal_scrollbar(ev.col, ev.row),
+            "chrome:minimap" => ed.handle_click_minimap(ev.col, ev.row),
+            "chrome:sticky_scroll" => ed.handle_click_sticky_scroll(ev.col, ev.row),
+            "chrome:breadcrumbs" => ed.handle_click_breadcrumbs(ev.col, ev.row),
             "chrome:split_separators" => ed.handle_click_split_separator(ev.col, ev.row),
             "chrome:split_buttons" => ed.handle_click_split_controls(ev.col, ev.row),
             "chrome:tabs" => ed.handle_click_tab_bar(ev.col, ev.row),
@@ -301,10 +315,15 @@ impl ChromeComponent for Splits {
                 ed.active_window_mut().scroll_tab_strip(split_id, delta);
                 Ok(Disposition::Consumed)
             }
-            // A split pane, hit in its content rect or scrollbar
-            // gutter (moved from the old central `wheel_surface_at`
+            // A split pane, hit in its content rect, sticky lines, breadcrumb
+            // row, minimap or scrollbar gutter (moved from the old central `wheel_surface_at`
             // fork — the surface's wheel lives with the surface).
-            "chrome:editor" | "chrome:scrollbars" | "chrome:h_scrollbar" => {
+            "chrome:editor"
+            | "chrome:sticky_scroll"
+            | "chrome:breadcrumbs"
+            | "chrome:minimap"
+            | "chrome:scrollbars"
+            | "chrome:h_scrollbar" => {
                 let Some((split_id, buffer_id)) = ed.active_window().split_at_position(col, row)
                 else {
                     return Ok(Disposition::Pass);
@@ -347,7 +366,12 @@ impl ChromeComponent for Splits {
                 ed.active_window_mut().scroll_tab_strip(split_id, delta);
                 Ok(Disposition::Consumed)
             }
-            "chrome:editor" | "chrome:scrollbars" | "chrome:h_scrollbar" => {
+            "chrome:editor"
+            | "chrome:sticky_scroll"
+            | "chrome:breadcrumbs"
+            | "chrome:minimap"
+            | "chrome:scrollbars"
+            | "chrome:h_scrollbar" => {
                 let Some((split_id, buffer_id)) = ed.active_window().split_at_position(col, row)
                 else {
                     return Ok(Disposition::Pass);
@@ -720,6 +744,33 @@ impl Editor {
         Some(Ok(()))
     }
 
+    /// Click in a split's minimap: focus the split, centre its viewport on
+    /// the clicked row and grab the pointer so a drag keeps scrolling.
+    pub(super) fn handle_click_minimap(&mut self, col: u16, row: u16) -> Option<AnyhowResult<()>> {
+        let (split_id, buffer_id, target_byte) = self
+            .active_layout()
+            .minimap_areas
+            .iter()
+            .find_map(|minimap| {
+                if in_rect(col, row, minimap.rect) {
+                    Some((
+                        minimap.split_id,
+                        minimap.buffer_id,
+                        minimap.byte_at_row(row)?,
+                    ))
+                } else {
+                    None
+                }
+            })?;
+
+        self.focus_split(split_id, buffer_id);
+        self.active_window_mut().mouse_state.dragging_minimap = Some(split_id);
+        Some(
+            self.active_window_mut()
+                .handle_minimap_jump(split_id, buffer_id, target_byte),
+        )
+    }
+
     pub(super) fn handle_click_horizontal_scrollbar(
         &mut self,
         col: u16,
@@ -1166,6 +1217,31 @@ impl Editor {
         Ok(())
     }
 
+    /// Minimap drag (`PointerGrab::Minimap`): the minimap scrolls along with
+    /// the viewport, so a drag maps the pointer onto the whole buffer the way
+    /// a scrollbar track jump does rather than onto the rows drawn last frame.
+    pub(crate) fn handle_minimap_drag(&mut self, col: u16, row: u16) -> AnyhowResult<()> {
+        let Some(dragging_split_id) = self.active_window().mouse_state.dragging_minimap else {
+            return Ok(());
+        };
+        let target = self
+            .active_layout()
+            .minimap_areas
+            .iter()
+            .find(|minimap| minimap.split_id == dragging_split_id)
+            .map(|minimap| (minimap.buffer_id, minimap.rect));
+        if let Some((buffer_id, rect)) = target {
+            self.active_window_mut().handle_scrollbar_jump(
+                col,
+                row.clamp(rect.y, rect.y + rect.height.saturating_sub(1)),
+                dragging_split_id,
+                buffer_id,
+                rect,
+            )?;
+        }
+        Ok(())
+    }
+
     /// Horizontal scrollbar drag (`PointerGrab::HScrollbar`): relative
     /// thumb drag or track jump on the grabbed split.
     pub(crate) fn handle_hscrollbar_drag(&mut self, col: u16, _row: u16) -> AnyhowResult<()> {
@@ -260,6 +260,20 @@ impl Editor {
             }
         }
 
+        // Ctrl+click on a document link follows it.
+        if modifiers.contains(KeyModifiers::CONTROL)
+            && self.click_document_link(col, row, split_id, buffer_id, content_rect)
+        {
+            self.focus_split(split_id, buffer_id);
+            return Ok(());
+        }
+
+        // A click on a code lens title runs the lens.
+        if self.click_code_lens(col, row, split_id, buffer_id, content_rect) {
+            self.focus_split(split_id, buffer_id);
+            return Ok(());
+        }
+
         // Dispatch MouseClick hook to plugins
         // Plugins can handle clicks on their virtual buffers
         if self
@@ -17,6 +17,7 @@ use crate::input::multi_cursor::{
 use crate::model::buffer_position::byte_to_2d;
 use crate::model::cursor::Cursor;
 use crate::model::event::{BufferId, CursorId, Event};
+use crate::model::kill_ring::{normalize_line_endings, KillDirection};
 use crate::primitives::ansi::strip_ansi_codes;
 use crate::primitives::word_navigation::{
     find_vi_word_end, find_word_start_left, find_word_start_right,
@@ -24,7 +25,7 @@ use crate::primitives::word_navigation::{
 use crate::services::async_bridge::AsyncMessage;
 use crate::view::virtual_text::{VirtualTextId, VirtualTextPosition};
 
-use super::Editor;
+use super::{Editor, YankCycleState};
 
 /// Per-paste timeout. The async-paste path renders a placeholder
 /// marker and lets the user keep editing; if the background arboard
@@ -127,6 +128,13 @@ impl Editor {
     /// If no selection exists, copies the entire current line (like VSCode/Rider/Zed).
     /// For block selections, copies only the rectangular region.
     pub fn copy_selection(&mut self) {
+        self.copy_selection_to_kill_ring(false);
+    }
+
+    /// Copy the selection (or current lines) into the kill ring and the
+    /// clipboard, one kill ring part per cursor. `kill` marks a cut, which
+    /// appends to the previous entry when it directly follows another kill.
+    fn copy_selection_to_kill_ring(&mut self, kill: bool) {
         // Check if any cursor has a block selection (takes priority)
         let has_block_selection = self
             .active_cursors()
@@ -140,7 +148,7 @@ impl Editor {
             // for the formatting-preserving variant).
             let text = strip_ansi_codes(&self.copy_block_selection_text());
             if !text.is_empty() {
-                self.clipboard.copy(text);
+                self.record_kill(vec![text], KillDirection::Forward, kill);
                 self.active_window_mut().status_message = Some(t!("clipboard.copied").to_string());
             }
             return;
@@ -153,57 +161,52 @@ impl Editor {
             .any(|(_, cursor)| cursor.selection_range().is_some());
 
         if has_selection {
-            // Original behavior: copy selected text
-            let ranges: Vec<_> = self
+            // Original behavior: copy selected text, top to bottom so each
+            // cursor's part lines up with the cursor that yanks it back
+            let mut ranges: Vec<_> = self
                 .active_cursors()
                 .iter()
                 .filter_map(|(_, cursor)| cursor.selection_range())
                 .collect();
-
-            let mut text = String::new();
-            let state = self.active_state_mut();
-            for range in ranges {
-                if !text.is_empty() {
-                    text.push('\n');
-                }
-                let range_text = state.get_text_range(range.start, range.end);
-                text.push_str(&range_text);
-            }
+            ranges.sort_by_key(|r| r.start);
 
             // Strip ANSI escape codes: ANSI-aware buffers render escapes as
             // zero-width styling, so the user sees colored text — the plain
             // copy should carry that visible text, not the control codes.
-            let text = strip_ansi_codes(&text);
-            if !text.is_empty() {
-                self.clipboard.copy(text);
+            let state = self.active_state_mut();
+            let parts: Vec<String> = ranges
+                .into_iter()
+                .map(|range| strip_ansi_codes(&state.get_text_range(range.start, range.end)))
+                .collect();
+
+            if parts.iter().any(|p| !p.is_empty()) {
+                self.record_kill(parts, KillDirection::Forward, kill);
                 self.active_window_mut().status_message = Some(t!("clipboard.copied").to_string());
             }
         } else {
             // No selection: copy entire line(s) for each cursor
             let estimated_line_length = 80;
-            let mut text = String::new();
 
             // Collect cursor positions first
-            let positions: Vec<_> = self
+            let mut positions: Vec<_> = self
                 .active_cursors()
                 .iter()
                 .map(|(_, c)| c.position)
                 .collect();
+            positions.sort_unstable();
             let state = self.active_state_mut();
 
-            for pos in positions {
-                let mut iter = state.buffer.line_iterator(pos, estimated_line_length);
-                if let Some((_start, content)) = iter.next_line() {
-                    if !text.is_empty() {
-                        text.push('\n');
-                    }
-                    text.push_str(&content);
-                }
-            }
+            let parts: Vec<String> = positions
+                .into_iter()
+                .filter_map(|pos| {
+                    let mut iter = state.buffer.line_iterator(pos, estimated_line_length);
+                    iter.next_line()
+                        .map(|(_start, content)| strip_ansi_codes(&content))
+                })
+                .collect();
 
-            let text = strip_ansi_codes(&text);
-            if !text.is_empty() {
-                self.clipboard.copy(text);
+            if parts.iter().any(|p| !p.is_empty()) {
+                self.record_kill(parts, KillDirection::Forward, kill);
                 self.active_window_mut().status_message =
                     Some(t!("clipboard.copied_line").to_string());
             }
@@ -523,7 +526,9 @@ impl Editor {
             .any(|(_, cursor)| cursor.selection_range().is_some());
 
         // Copy first (this handles both selection and whole-line cases)
-        self.copy_selection();
+        self.copy_selection_to_kill_ring(true);
+        // The deletion below would break the chain the copy just extended.
+        let kill_chain = self.active_window().kill_chain;
 
         if has_selection {
             // Delete selected text from all cursors
@@ -624,6 +629,7 @@ impl Editor {
                     Some(t!("clipboard.cut_line").to_string());
             }
         }
+        self.active_window_mut().kill_chain = kill_chain;
     }
 
     /// Paste the clipboard content at all cursor positions
@@ -1308,11 +1314,28 @@ impl Editor {
 
         // Convert to buffer's line ending format (only used in non-column mode;
         // a single column-paste line never contains an embedded newline).
-        let paste_text_full = match self.active_state().buffer.line_ending() {
-            crate::model::buffer::LineEnding::LF => normalized.clone(),
-            crate::model::buffer::LineEnding::CRLF => normalized.replace('\n', "\r\n"),
-            crate::model::buffer::LineEnding::CR => normalized.replace('\n', "\r"),
+        let line_ending = self.active_state().buffer.line_ending();
+        let to_buffer_endings = |text: &str| match line_ending {
+            crate::model::buffer::LineEnding::LF => text.to_string(),
+            crate::model::buffer::LineEnding::CRLF => text.replace('\n', "\r\n"),
+            crate::model::buffer::LineEnding::CR => text.replace('\n', "\r"),
         };
+        let paste_text_full = to_buffer_endings(&normalized);
+
+        // Yanking a multi-cursor kill into the same number of cursors gives
+        // each cursor its own part back, even when the parts span lines.
+        let yank_index = self.kill_ring.index_for_yank(&normalized);
+        let kill_parts: Option<Vec<String>> = self
+            .kill_ring
+            .get(yank_index)
+            .map(|entry| entry.parts())
+            .filter(|parts| cursor_data.len() > 1 && parts.len() == cursor_data.len())
+            .map(|parts| {
+                parts
+                    .iter()
+                    .map(|p| to_buffer_endings(&normalize_line_endings(p)))
+                    .collect()
+            });
 
         // Get deleted text for each selection
         let cursor_data_with_text: Vec<_> = {
@@ -1343,6 +1366,7 @@ impl Editor {
         // the back when iterating.
         let total = cursor_data_with_text.len();
         let mut events = Vec::new();
+        let mut inserted = Vec::with_capacity(total);
         for (i, (cursor_id, selection, insert_position, deleted_text, virtual_gap)) in
             cursor_data_with_text.into_iter().enumerate()
         {
@@ -1353,11 +1377,14 @@ impl Editor {
                     cursor_id,
                 });
             }
-            let mut text = if use_column_paste {
+            let mut text = if let Some(parts) = &kill_parts {
+                parts[total - 1 - i].clone()
+            } else if use_column_paste {
                 lines_for_distribution[total - 1 - i].to_string()
             } else {
                 paste_text_full.clone()
             };
+            inserted.push((cursor_id, text.clone()));
             if !virtual_gap.is_empty() {
                 text = format!("{}{}", virtual_gap, text);
             }
@@ -1378,6 +1405,14 @@ impl Editor {
             self.log_and_apply_event(&event);
         }
 
+        // Remember what was yanked so yank-pop can swap it for an older kill.
+        let buffer_id = self.active_buffer();
+        self.active_window_mut().yank_state = Some(YankCycleState {
+            buffer_id,
+            index: yank_index,
+            inserted,
+        });
+
         self.active_window_mut().status_message = Some(t!("clipboard.pasted").to_string());
     }
 
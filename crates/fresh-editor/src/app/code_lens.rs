@@ -0,0 +1,662 @@
+//! LSP code lens.
+//!
+//! Lenses are pulled per buffer (`textDocument/codeLens`) on open, after
+//! edits, and whenever the server asks for a `workspace/codeLens/refresh`.
+//! Each source line that carries lenses gets one virtual line above it in
+//! the `lsp-code-lens` namespace, with the titles joined by `" | "`.
+//! Lenses that arrive without a command are resolved lazily
+//! (`codeLens/resolve`) once their line scrolls into view.
+//!
+//! A lens runs its command through `workspace/executeCommand`, either by
+//! clicking its title or with the "Run Code Lens" action on the line below
+//! it (a chooser pops up when the line has several lenses).
+
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Range;
+use std::time::Instant;
+
+use lsp_types::{CodeLens, Command};
+use ratatui::style::{Color, Style};
+use rust_i18n::t;
+use unicode_width::UnicodeWidthStr;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::types::LspFeature;
+use crate::view::popup::{Popup, PopupListItem, PopupPosition};
+use crate::view::virtual_text::{VirtualTextId, VirtualTextNamespace, VirtualTextPosition};
+
+/// Virtual-text namespace owning the lens rows.
+const CODE_LENS_NAMESPACE: &str = "lsp-code-lens";
+
+/// Separator between the titles of lenses sharing a line.
+const TITLE_SEPARATOR: &str = " | ";
+
+/// Lens rows sort after every other `LineAbove` entry at the same anchor, so
+/// they sit directly above the code they describe — which is what lets a
+/// click on "the row above line N" be attributed to line N's lenses.
+const CODE_LENS_PRIORITY: i32 = i32::MAX;
+
+#[derive(Clone, Debug)]
+pub(crate) struct CodeLensRequest {
+    pub(crate) buffer_id: BufferId,
+    pub(crate) version: u64,
+}
+
+/// An in-flight `codeLens/resolve`, tied to the lens list it came from.
+#[derive(Clone, Debug)]
+pub(crate) struct CodeLensResolveRequest {
+    pub(crate) buffer_id: BufferId,
+    pub(crate) generation: u64,
+    pub(crate) index: usize,
+}
+
+/// The lenses of one buffer and the virtual lines currently showing them.
+#[derive(Debug, Default)]
+pub(crate) struct BufferCodeLenses {
+    /// Buffer version the lens ranges refer to.
+    version: u64,
+    /// Bumped on every fresh response so late resolves for a replaced list
+    /// are dropped.
+    generation: u64,
+    lenses: Vec<CodeLens>,
+    /// Lenses with a resolve sent (or failed) for this generation.
+    resolving: HashSet<usize>,
+    rows: Vec<CodeLensRow>,
+}
+
+/// One rendered lens line.
+#[derive(Debug)]
+struct CodeLensRow {
+    id: VirtualTextId,
+    /// Display-column span of each title, paired with its lens index.
+    spans: Vec<(Range<usize>, usize)>,
+}
+
+/// Build the text of a lens row and the column span each title occupies.
+///
+/// `indent` is the (tab-expanded) indentation of the source line, so the
+/// titles line up with the code underneath.
+fn layout_lens_row(indent: &str, titles: &[(usize, &str)]) -> (String, Vec<(Range<usize>, usize)>) {
+    let mut text = indent.to_string();
+    let mut col = indent.width();
+    let mut spans = Vec::with_capacity(titles.len());
+    for (n, (index, title)) in titles.iter().enumerate() {
+        if n > 0 {
+            text.push_str(TITLE_SEPARATOR);
+            col += TITLE_SEPARATOR.width();
+        }
+        let width = title.width();
+        spans.push((col..col + width, *index));
+        text.push_str(title);
+        col += width;
+    }
+    (text, spans)
+}
+
+/// Leading whitespace of `line`, with tabs expanded to spaces.
+fn leading_indent(line: &[u8], tab_size: usize) -> String {
+    let mut indent = String::new();
+    for &b in line {
+        match b {
+            b' ' => indent.push(' '),
+            b'\t' => {
+                let pad = tab_size.max(1) - indent.len() % tab_size.max(1);
+                indent.extend(std::iter::repeat_n(' ', pad));
+            }
+            _ => break,
+        }
+    }
+    indent
+}
+
+fn namespace() -> VirtualTextNamespace {
+    VirtualTextNamespace::from_string(CODE_LENS_NAMESPACE.to_string())
+}
+
+impl crate::app::window::Window {
+    /// Schedule a code-lens refresh for a buffer (debounced).
+    pub fn schedule_code_lens_refresh(&mut self, buffer_id: BufferId) {
+        const CODE_LENS_DEBOUNCE_MS: u64 = 400;
+        let next_time = Instant::now() + std::time::Duration::from_millis(CODE_LENS_DEBOUNCE_MS);
+        self.code_lens_debounce.insert(buffer_id, next_time);
+    }
+
+    /// Handle a `textDocument/codeLens` response.
+    pub(crate) fn handle_lsp_code_lens(
+        &mut self,
+        request_id: u64,
+        uri: String,
+        lenses: Vec<CodeLens>,
+    ) {
+        let Some(request) = self.pending_code_lens_requests.remove(&request_id) else {
+            tracing::debug!(
+                "Ignoring code lens response without pending request (request_id={})",
+                request_id
+            );
+            return;
+        };
+        let Some(state) = self.buffers.get(&request.buffer_id) else {
+            return;
+        };
+        if state.buffer.version() != request.version {
+            tracing::debug!(
+                "Ignoring stale code lenses for {} (request_id={}, version={}, current={})",
+                uri,
+                request_id,
+                request.version,
+                state.buffer.version()
+            );
+            self.schedule_code_lens_refresh(request.buffer_id);
+            return;
+        }
+
+        tracing::info!(
+            "Received {} code lenses for {} (request_id={})",
+            lenses.len(),
+            uri,
+            request_id
+        );
+        let entry = self.code_lenses.entry(request.buffer_id).or_default();
+        entry.version = request.version;
+        entry.generation += 1;
+        entry.lenses = lenses;
+        entry.resolving.clear();
+        self.render_code_lenses(request.buffer_id);
+    }
+
+    /// Handle a `codeLens/resolve` response.
+    pub(crate) fn handle_lsp_code_lens_resolved(
+        &mut self,
+        request_id: u64,
+        lens: Result<CodeLens, String>,
+    ) {
+        let Some(request) = self.pending_code_lens_resolves.remove(&request_id) else {
+            return;
+        };
+        let lens = match lens {
+            Ok(lens) => lens,
+            Err(e) => {
+                // Leave the index in `resolving` so the lens is not retried
+                // on every frame; the next refresh starts over.
+                tracing::debug!("codeLens/resolve failed: {}", e);
+                return;
+            }
+        };
+        let Some(entry) = self.code_lenses.get_mut(&request.buffer_id) else {
+            return;
+        };
+        if entry.generation != request.generation {
+            return;
+        }
+        let Some(slot) = entry.lenses.get_mut(request.index) else {
+            return;
+        };
+        *slot = lens;
+        self.render_code_lenses(request.buffer_id);
+    }
+
+    /// Rebuild the lens rows of a buffer from its cached lenses.
+    ///
+    /// Other features clear every virtual text of a buffer (inlay hints,
+    /// undo), so this is also called to put the rows back afterwards. Lenses
+    /// computed for an older buffer version are not drawn: their line
+    /// numbers no longer match, and a refresh is already scheduled.
+    pub(crate) fn render_code_lenses(&mut self, buffer_id: BufferId) {
+        let Some(entry) = self.code_lenses.get_mut(&buffer_id) else {
+            return;
+        };
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let ns = namespace();
+        state
+            .virtual_texts
+            .clear_namespace(&mut state.marker_list, &ns);
+        entry.rows.clear();
+        if state.buffer.version() != entry.version {
+            return;
+        }
+
+        let mut by_line: BTreeMap<usize, Vec<(usize, &str)>> = BTreeMap::new();
+        for (index, lens) in entry.lenses.iter().enumerate() {
+            if let Some(command) = &lens.command {
+                by_line
+                    .entry(lens.range.start.line as usize)
+                    .or_default()
+                    .push((index, command.title.as_str()));
+            }
+        }
+
+        let tab_size = state.buffer_settings.tab_size;
+        // Fallback when the theme lacks the key; the on-screen colour is
+        // resolved from `editor.line_number_fg`, like inlay hints.
+        let style = Style::default().fg(Color::Rgb(128, 128, 128));
+        for (line, titles) in by_line {
+            let Some(anchor) = state.buffer.line_start_offset(line) else {
+                continue;
+            };
+            let indent = state
+                .buffer
+                .get_line(line)
+                .map(|bytes| leading_indent(&bytes, tab_size))
+                .unwrap_or_default();
+            let (text, spans) = layout_lens_row(&indent, &titles);
+            let id = state.virtual_texts.add_line_with_theme_keys(
+                &mut state.marker_list,
+                anchor,
+                text,
+                style,
+                Some("editor.line_number_fg".to_string()),
+                None,
+                VirtualTextPosition::LineAbove,
+                ns.clone(),
+                CODE_LENS_PRIORITY,
+                None,
+                None,
+                Vec::new(),
+            );
+            entry.rows.push(CodeLensRow { id, spans });
+        }
+    }
+
+    /// Lenses shown above `line`, as `(title span, command)` pairs.
+    fn code_lenses_on_line(
+        &self,
+        buffer_id: BufferId,
+        line: usize,
+    ) -> Vec<(Range<usize>, Command)> {
+        let (Some(entry), Some(state)) = (
+            self.code_lenses.get(&buffer_id),
+            self.buffers.get(&buffer_id),
+        ) else {
+            return Vec::new();
+        };
+        // Rows are anchored by markers, so their line is read live rather
+        // than from the (possibly outdated) lens ranges.
+        let row = entry.rows.iter().find(|row| {
+            state
+                .virtual_texts
+                .marker_id_of(row.id)
+                .and_then(|marker| state.marker_list.get_position(marker))
+                .is_some_and(|pos| state.buffer.get_line_number(pos) == line)
+        });
+        row.map(|row| {
+            row.spans
+                .iter()
+                .filter_map(|(span, index)| {
+                    let command = entry.lenses.get(*index)?.command.clone()?;
+                    Some((span.clone(), command))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+    }
+
+    /// Drop every code lens of a buffer, including its rows.
+    pub(crate) fn clear_code_lenses(&mut self, buffer_id: BufferId) {
+        self.code_lens_debounce.remove(&buffer_id);
+        self.pending_code_lens_requests
+            .retain(|_, r| r.buffer_id != buffer_id);
+        self.pending_code_lens_resolves
+            .retain(|_, r| r.buffer_id != buffer_id);
+        if self.code_lenses.remove(&buffer_id).is_some() {
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state
+                    .virtual_texts
+                    .clear_namespace(&mut state.marker_list, &namespace());
+            }
+        }
+    }
+}
+
+impl Editor {
+    /// Re-pull code lenses for all open buffers of a language.
+    pub(super) fn request_code_lens_for_language(&mut self, language: &str) {
+        let buffer_ids: Vec<_> = self
+            .buffers_for_language(language)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        for buffer_id in buffer_ids {
+            self.active_window_mut()
+                .schedule_code_lens_refresh(buffer_id);
+        }
+    }
+
+    pub(super) fn handle_lsp_code_lens_refresh(&mut self, language: String) {
+        tracing::info!(
+            "LSP ({}) code-lens refresh requested, re-pulling code lenses",
+            language
+        );
+        self.request_code_lens_for_language(&language);
+    }
+
+    /// Issue a debounced code-lens request if the timer has elapsed, then
+    /// resolve the lenses visible in `start_line..=end_line`.
+    pub(crate) fn maybe_request_code_lens(
+        &mut self,
+        buffer_id: BufferId,
+        start_line: usize,
+        end_line: usize,
+    ) {
+        let ready = self
+            .active_window()
+            .code_lens_debounce
+            .get(&buffer_id)
+            .is_some_and(|ready_at| Instant::now() >= *ready_at);
+        if ready {
+            self.active_window_mut()
+                .code_lens_debounce
+                .remove(&buffer_id);
+            self.request_code_lens_for_buffer(buffer_id);
+        }
+        self.resolve_visible_code_lenses(buffer_id, start_line, end_line);
+    }
+
+    fn request_code_lens_for_buffer(&mut self, buffer_id: BufferId) {
+        if self
+            .active_window()
+            .pending_code_lens_requests
+            .values()
+            .any(|r| r.buffer_id == buffer_id)
+        {
+            // The in-flight answer will be stale; ask again once it lands.
+            self.active_window_mut()
+                .schedule_code_lens_refresh(buffer_id);
+            return;
+        }
+        let window = self.active_window();
+        let Some(version) = window.buffers.get(&buffer_id).map(|s| s.buffer.version()) else {
+            return;
+        };
+        let request_id = window.next_lsp_request_id;
+
+        let sent = self.with_lsp_for_buffer(buffer_id, LspFeature::CodeLens, |handle, uri, _| {
+            handle.code_lens(request_id, uri.as_uri().clone())
+        });
+        match sent {
+            Some(Ok(())) => {
+                let window = self.active_window_mut();
+                window.next_lsp_request_id += 1;
+                window
+                    .pending_code_lens_requests
+                    .insert(request_id, CodeLensRequest { buffer_id, version });
+            }
+            Some(Err(e)) => tracing::debug!("Failed to request code lenses: {}", e),
+            None => {}
+        }
+    }
+
+    /// Send `codeLens/resolve` for unresolved lenses on the given lines.
+    fn resolve_visible_code_lenses(
+        &mut self,
+        buffer_id: BufferId,
+        start_line: usize,
+        end_line: usize,
+    ) {
+        let window = self.active_window();
+        let Some(entry) = window.code_lenses.get(&buffer_id) else {
+            return;
+        };
+        let current = window.buffers.get(&buffer_id).map(|s| s.buffer.version());
+        if current != Some(entry.version) {
+            return;
+        }
+        let wanted: Vec<(usize, CodeLens)> = entry
+            .lenses
+            .iter()
+            .enumerate()
+            .filter(|(index, lens)| {
+                lens.command.is_none()
+                    && !entry.resolving.contains(index)
+                    && (start_line..=end_line).contains(&(lens.range.start.line as usize))
+            })
+            .map(|(index, lens)| (index, lens.clone()))
+            .collect();
+        if wanted.is_empty() {
+            return;
+        }
+        let generation = entry.generation;
+        let Some(language) = window.buffers.get(&buffer_id).map(|s| s.language.clone()) else {
+            return;
+        };
+
+        let window = self.active_window_mut();
+        let Some(sh) = window
+            .lsp
+            .handle_for_feature_mut(&language, LspFeature::CodeLens)
+        else {
+            return;
+        };
+        if !sh.capabilities.code_lens_resolve {
+            return;
+        }
+        for (index, lens) in wanted {
+            let request_id = window.next_lsp_request_id;
+            window.next_lsp_request_id += 1;
+            if let Err(e) = sh.handle.code_lens_resolve(request_id, lens) {
+                tracing::debug!("Failed to send codeLens/resolve: {}", e);
+                return;
+            }
+            window.pending_code_lens_resolves.insert(
+                request_id,
+                CodeLensResolveRequest {
+                    buffer_id,
+                    generation,
+                    index,
+                },
+            );
+            if let Some(entry) = window.code_lenses.get_mut(&buffer_id) {
+                entry.resolving.insert(index);
+            }
+        }
+    }
+
+    /// "Run Code Lens": run the lens above the cursor line, or offer a
+    /// chooser when there are several.
+    pub(crate) fn run_code_lens_at_cursor(&mut self) {
+        let buffer_id = self.active_buffer();
+        let position = self.active_cursors().primary().position;
+        let Some(line) = self
+            .buffers()
+            .get(&buffer_id)
+            .map(|s| s.buffer.get_line_number(position))
+        else {
+            return;
+        };
+        let mut commands: Vec<Command> = self
+            .active_window()
+            .code_lenses_on_line(buffer_id, line)
+            .into_iter()
+            .map(|(_, command)| command)
+            .collect();
+
+        match commands.len() {
+            0 => self.set_status_message(t!("lsp.no_code_lens").to_string()),
+            1 => {
+                let command = commands.remove(0);
+                self.execute_code_lens_command(buffer_id, command);
+            }
+            _ => self.show_code_lens_popup(commands),
+        }
+    }
+
+    fn show_code_lens_popup(&mut self, commands: Vec<Command>) {
+        let items: Vec<PopupListItem> = commands
+            .iter()
+            .enumerate()
+            .map(|(i, command)| PopupListItem {
+                text: format!("{}. {}", i + 1, command.title),
+                detail: None,
+                icon: None,
+                data: Some(i.to_string()),
+                disabled: false,
+            })
+            .collect();
+        self.active_window_mut().pending_code_lens_commands = Some(commands);
+
+        let theme = self.theme.read().unwrap();
+        let mut popup = Popup::list(items, &theme);
+        popup.kind = crate::view::popup::PopupKind::Action;
+        popup.title = Some(t!("lsp.popup_code_lens").to_string());
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = 60;
+        popup.max_height = 15;
+        popup.border_style = Style::default().fg(theme.popup_border_fg);
+        popup.background_style = Style::default().bg(theme.popup_bg);
+        drop(theme);
+        popup.resolver = crate::view::popup::PopupResolver::CodeLens;
+        popup.focused = true;
+
+        let buffer_id = self.active_buffer();
+        if let Some(state) = self.active_window_mut().buffers.get_mut(&buffer_id) {
+            state.popups.show_or_replace(popup);
+        }
+    }
+
+    /// Run the `index`-th command of the code-lens chooser.
+    pub(crate) fn execute_code_lens_choice(&mut self, index: usize) {
+        let command = self
+            .active_window_mut()
+            .pending_code_lens_commands
+            .take()
+            .and_then(|mut commands| (index < commands.len()).then(|| commands.remove(index)));
+        if let Some(command) = command {
+            let buffer_id = self.active_buffer();
+            self.execute_code_lens_command(buffer_id, command);
+        }
+    }
+
+    /// Run a lens command on the server that produced the lens.
+    fn execute_code_lens_command(&mut self, buffer_id: BufferId, command: Command) {
+        tracing::info!(
+            "Executing code lens: {} ({})",
+            command.title,
+            command.command
+        );
+        let sent = self.with_lsp_for_buffer(buffer_id, LspFeature::CodeLens, |handle, _, _| {
+            handle.execute_command(command.command.clone(), command.arguments.clone())
+        });
+        match sent {
+            Some(Ok(())) => self.set_status_message(
+                t!("lsp.code_lens_running", title = &command.title).to_string(),
+            ),
+            Some(Err(e)) => {
+                tracing::warn!("Failed to send executeCommand: {}", e);
+                self.set_status_message(t!("lsp.no_server_active").to_string());
+            }
+            None => self.set_status_message(t!("lsp.no_server_active").to_string()),
+        }
+    }
+
+    /// Run the lens under a click, if the click landed on a lens title.
+    ///
+    /// Lens rows sit directly above their source line (see
+    /// [`CODE_LENS_PRIORITY`]), so the clicked row belongs to the line of
+    /// the next row down.
+    pub(super) fn click_code_lens(
+        &mut self,
+        col: u16,
+        row: u16,
+        split_id: crate::model::event::LeafId,
+        buffer_id: BufferId,
+        content_rect: ratatui::layout::Rect,
+    ) -> bool {
+        if !self.active_window().code_lenses.contains_key(&buffer_id) {
+            return false;
+        }
+        let Some(view_state) = self
+            .active_window()
+            .buffers
+            .splits()
+            .and_then(|(_, vs)| vs.get(&split_id))
+        else {
+            return false;
+        };
+        let left_column = view_state.viewport.left_column;
+        let compose_width = view_state.compose_width;
+        let gutter_width = self
+            .buffers()
+            .get(&buffer_id)
+            .map(|s| s.margins.left_total_width() as u16)
+            .unwrap_or(0);
+        let mappings = self
+            .active_layout()
+            .view_line_mappings
+            .get(&split_id)
+            .cloned();
+        let Some(target) = super::click_geometry::screen_to_buffer_position_with_overshoot(
+            col,
+            row,
+            content_rect,
+            gutter_width,
+            &mappings,
+            0,
+            false,
+            compose_width,
+        ) else {
+            return false;
+        };
+        if target.row_overshoot > 0 || target.row_undershoot > 0 {
+            return false;
+        }
+
+        let visual_row = row.saturating_sub(content_rect.y) as usize;
+        let next_row_byte = mappings.as_ref().and_then(|mappings| {
+            let clicked = mappings.get(visual_row)?;
+            let next = mappings.get(visual_row + 1)?;
+            if !clicked.is_plugin_virtual || next.is_plugin_virtual {
+                return None;
+            }
+            next.first_source_byte()
+        });
+        let Some(line) = next_row_byte.and_then(|byte| {
+            self.buffers()
+                .get(&buffer_id)
+                .map(|s| s.buffer.get_line_number(byte))
+        }) else {
+            return false;
+        };
+
+        let column = target.text_col + left_column;
+        let command = self
+            .active_window()
+            .code_lenses_on_line(buffer_id, line)
+            .into_iter()
+            .find(|(span, _)| span.contains(&column))
+            .map(|(_, command)| command);
+        match command {
+            Some(command) => {
+                self.execute_code_lens_command(buffer_id, command);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lens_row_joins_titles_after_indent() {
+        let (text, spans) = layout_lens_row("    ", &[(0, "3 references"), (2, "Run")]);
+        assert_eq!(text, "    3 references | Run");
+        assert_eq!(spans, vec![(4..16, 0), (19..22, 2)]);
+    }
+
+    #[test]
+    fn lens_row_spans_use_display_width() {
+        let (_, spans) = layout_lens_row("", &[(0, "▶ Run"), (1, "Debug")]);
+        assert_eq!(spans, vec![(0..5, 0), (8..13, 1)]);
+    }
+
+    #[test]
+    fn indent_expands_tabs_to_the_next_stop() {
+        assert_eq!(leading_indent(b"\tfn main()", 4), "    ");
+        assert_eq!(leading_indent(b"  \tx", 4), "    ");
+        assert_eq!(leading_indent(b"x", 4), "");
+    }
+}
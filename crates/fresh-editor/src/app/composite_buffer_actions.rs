@@ -452,6 +452,7 @@ pub(crate) struct CreateCompositeBufferArgs {
     pub layout_config: fresh_core::api::CompositeLayoutConfig,
     pub source_configs: Vec<fresh_core::api::CompositeSourceConfig>,
     pub hunks: Option<Vec<fresh_core::api::CompositeHunk>>,
+    pub sections: Option<Vec<fresh_core::api::CompositeSection>>,
     pub initial_focus_hunk: Option<usize>,
     pub request_id: Option<u64>,
 }
@@ -1160,6 +1161,7 @@ impl Editor {
             layout_config,
             source_configs,
             hunks,
+            sections,
             initial_focus_hunk,
             request_id: _request_id,
         } = args;
@@ -1207,8 +1209,12 @@ impl Editor {
         // Create the composite buffer
         let buffer_id = self.create_composite_buffer(name.clone(), mode.clone(), layout, sources);
 
-        // Set alignment from hunks if provided
-        if let Some(hunk_configs) = hunks {
+        // Set alignment from sections or hunks if provided
+        if let Some(section_configs) = sections {
+            let alignment = LineAlignment::from_sections(&aligned_sections(section_configs));
+            self.active_window_mut()
+                .set_composite_alignment(buffer_id, alignment);
+        } else if let Some(hunk_configs) = hunks {
             let diff_hunks: Vec<DiffHunk> = hunk_configs
                 .into_iter()
                 .map(|h| {
@@ -1319,6 +1325,26 @@ impl Editor {
         }
     }
 
+    /// Handle the UpdateCompositeSections plugin command
+    #[cfg(feature = "plugins")]
+    pub(crate) fn handle_update_composite_sections(
+        &mut self,
+        buffer_id: BufferId,
+        section_configs: Vec<fresh_core::api::CompositeSection>,
+    ) {
+        use crate::model::composite_buffer::LineAlignment;
+
+        if self
+            .active_window()
+            .composite_buffers
+            .contains_key(&buffer_id)
+        {
+            let alignment = LineAlignment::from_sections(&aligned_sections(section_configs));
+            self.active_window_mut()
+                .set_composite_alignment(buffer_id, alignment);
+        }
+    }
+
     /// Handle a mouse click in a composite buffer view
     pub(crate) fn handle_composite_click(
         &mut self,
@@ -1458,6 +1484,35 @@ impl Editor {
     }
 }
 
+/// Convert plugin section configs to alignment sections. Unknown kinds
+/// render as context.
+#[cfg(feature = "plugins")]
+fn aligned_sections(
+    configs: Vec<fresh_core::api::CompositeSection>,
+) -> Vec<crate::model::composite_buffer::AlignedSection> {
+    use crate::model::composite_buffer::{AlignedSection, RowType};
+
+    configs
+        .into_iter()
+        .map(|config| AlignedSection {
+            lines: config
+                .lines
+                .iter()
+                .map(|range| {
+                    let at = |i: usize| range.get(i).copied().unwrap_or(0);
+                    (at(0), at(1))
+                })
+                .collect(),
+            row_type: match config.kind.as_str() {
+                "addition" => RowType::Addition,
+                "deletion" => RowType::Deletion,
+                "modification" => RowType::Modification,
+                _ => RowType::Context,
+            },
+        })
+        .collect()
+}
+
 /// Scan upward from the row just above the cursor to the nearest row whose
 /// focused pane has content, move the cursor there, and scroll up if needed.
 /// Returns whether the cursor actually wrapped. Callers must ensure
@@ -0,0 +1,665 @@
+//! Debugger orchestrators.
+//!
+//! Breakpoint bookkeeping is pure window state and lives on `impl Window`.
+//! Starting sessions, stepping and reacting to adapter traffic stay on
+//! `impl Editor` because they fire plugin hooks, resolve plugin callbacks
+//! and navigate the active window to the stopped location.
+//!
+//! Async messages from an adapter carry only a session id, so handlers
+//! look up the owning window with [`Editor::debug_session_window_id`].
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use rust_i18n::t;
+use serde_json::Value;
+
+use crate::app::debugger::{
+    expand_launch_variables, DebugSession, DebugStatus, BREAKPOINT_NAMESPACE, STOPPED_NAMESPACE,
+};
+use crate::model::event::BufferId;
+use crate::services::dap::protocol::{
+    set_breakpoints_arguments, OutputEventBody, StackTraceBody, StoppedEventBody,
+};
+use crate::services::dap::{DapHandle, DapLaunch, DapRequestOrigin};
+use crate::services::plugins::hooks::{DebugStackFrame, HookArgs};
+use crate::state::EditorState;
+use crate::view::margin::LineIndicator;
+use crate::view::overlay::OverlayNamespace;
+
+use super::Editor;
+
+/// Number of frames requested when the debuggee stops.
+const STACK_TRACE_LEVELS: i64 = 50;
+
+fn breakpoint_indicator() -> LineIndicator {
+    LineIndicator::new("●", ratatui::style::Color::Red, 20)
+}
+
+/// 0-based lines currently carrying a breakpoint indicator in `state`.
+fn breakpoint_lines(state: &EditorState) -> BTreeSet<usize> {
+    state
+        .margins
+        .indicator_positions_for_namespace(BREAKPOINT_NAMESPACE)
+        .into_iter()
+        .map(|(_, pos)| state.buffer.get_line_number(pos))
+        .collect()
+}
+
+impl crate::app::window::Window {
+    /// Path as the adapter sees it: translated into the remote workspace
+    /// when the authority maps paths, unchanged otherwise.
+    fn debug_adapter_path(&self, host: &Path) -> String {
+        self.authority
+            .path_translation
+            .as_ref()
+            .and_then(|t| t.host_to_remote(host))
+            .unwrap_or_else(|| host.to_path_buf())
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Inverse of [`Self::debug_adapter_path`] for paths reported by the
+    /// adapter (stack frames).
+    fn debug_host_path(&self, adapter_path: &str) -> PathBuf {
+        let path = PathBuf::from(adapter_path);
+        self.authority
+            .path_translation
+            .as_ref()
+            .and_then(|t| t.remote_to_host(&path))
+            .unwrap_or(path)
+    }
+
+    /// Refresh the stored breakpoint lines of `buffer_id` from its gutter
+    /// indicators, which have followed any edits since they were placed.
+    /// Returns the file path and its lines.
+    pub(crate) fn sync_breakpoints_for_buffer(
+        &mut self,
+        buffer_id: BufferId,
+    ) -> Option<(PathBuf, Vec<usize>)> {
+        let state = self.buffers.get(&buffer_id)?;
+        let path = state.buffer.file_path()?.to_path_buf();
+        let lines = breakpoint_lines(state);
+        if lines.is_empty() && self.debug.breakpoints_for(&path).is_empty() {
+            return None;
+        }
+        let sorted = lines.iter().copied().collect();
+        self.debug.set_breakpoints(path.clone(), lines);
+        Some((path, sorted))
+    }
+
+    /// Re-create gutter indicators for breakpoints stored while `state`'s
+    /// file was closed. Called while finalizing a freshly opened buffer.
+    pub(crate) fn restore_breakpoint_indicators(&self, state: &mut EditorState) {
+        let Some(path) = state.buffer.file_path() else {
+            return;
+        };
+        for line in self.debug.breakpoints_for(path) {
+            if let Some(offset) = state.buffer.line_start_offset(line) {
+                state.margins.set_line_indicator(
+                    offset,
+                    BREAKPOINT_NAMESPACE.to_string(),
+                    breakpoint_indicator(),
+                );
+            }
+        }
+    }
+
+    /// Send `setBreakpoints` for `path` if a session is running.
+    fn send_breakpoints(&self, path: &Path, lines: &[usize]) {
+        if let Some(session) = &self.debug.session {
+            let arguments = set_breakpoints_arguments(&self.debug_adapter_path(path), lines);
+            if let Err(e) =
+                session
+                    .handle
+                    .request(DapRequestOrigin::Editor, "setBreakpoints", Some(arguments))
+            {
+                tracing::warn!("Failed to send breakpoints: {}", e);
+            }
+        }
+    }
+
+    /// Toggle a breakpoint on the primary cursor's line.
+    pub(crate) fn debug_toggle_breakpoint(&mut self) {
+        let buffer_id = self.active_buffer();
+        let position = self.active_cursors().primary().position;
+        let Some(state) = self.buffer_state_mut(buffer_id) else {
+            return;
+        };
+        if state.buffer.file_path().is_none() {
+            self.set_status_message(t!("debug.breakpoint_needs_file").to_string());
+            return;
+        }
+        let line = state.buffer.get_line_number(position);
+        let existing: Vec<_> = state
+            .margins
+            .indicator_positions_for_namespace(BREAKPOINT_NAMESPACE)
+            .into_iter()
+            .filter(|(_, pos)| state.buffer.get_line_number(*pos) == line)
+            .map(|(marker_id, _)| marker_id)
+            .collect();
+        let added = existing.is_empty();
+        if added {
+            let offset = state.buffer.line_start_offset(line).unwrap_or(0);
+            state.margins.set_line_indicator(
+                offset,
+                BREAKPOINT_NAMESPACE.to_string(),
+                breakpoint_indicator(),
+            );
+        } else {
+            for marker_id in existing {
+                state
+                    .margins
+                    .remove_line_indicator(marker_id, BREAKPOINT_NAMESPACE);
+            }
+        }
+
+        if let Some((path, lines)) = self.sync_breakpoints_for_buffer(buffer_id) {
+            self.send_breakpoints(&path, &lines);
+        }
+        let message = if added {
+            t!("debug.breakpoint_added", line = line + 1)
+        } else {
+            t!("debug.breakpoint_removed", line = line + 1)
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Remove every breakpoint in every file.
+    pub(crate) fn debug_clear_breakpoints(&mut self) {
+        for state in self.buffers.as_map_mut().values_mut() {
+            state
+                .margins
+                .clear_line_indicators_for_namespace(BREAKPOINT_NAMESPACE);
+        }
+        for path in self.debug.clear_breakpoints() {
+            self.send_breakpoints(&path, &[]);
+        }
+        self.set_status_message(t!("debug.breakpoints_cleared").to_string());
+    }
+
+    /// Remove the stopped-line highlight, wherever it is.
+    fn clear_debug_stopped_location(&mut self) {
+        let Some(buffer_id) = self.debug.stopped_buffer.take() else {
+            return;
+        };
+        if let Some(state) = self.buffer_state_mut(buffer_id) {
+            state
+                .margins
+                .clear_line_indicators_for_namespace(STOPPED_NAMESPACE);
+            state.overlays.clear_namespace(
+                &OverlayNamespace::from_string(STOPPED_NAMESPACE.to_string()),
+                &mut state.marker_list,
+            );
+        }
+    }
+
+    /// Mark `line` (0-based) of `buffer_id` as the current execution point.
+    fn show_debug_stopped_location(&mut self, buffer_id: BufferId, line: usize) {
+        self.clear_debug_stopped_location();
+        let Some(state) = self.buffer_state_mut(buffer_id) else {
+            return;
+        };
+        let Some(start) = state.buffer.line_start_offset(line) else {
+            return;
+        };
+        let end = state
+            .buffer
+            .line_start_offset(line + 1)
+            .unwrap_or_else(|| state.buffer.len());
+        state.margins.set_line_indicator(
+            start,
+            STOPPED_NAMESPACE.to_string(),
+            LineIndicator::new("▶", ratatui::style::Color::Yellow, 30),
+        );
+        state.add_overlay(
+            Some(OverlayNamespace::from_string(STOPPED_NAMESPACE.to_string())),
+            start..end,
+            crate::model::event::OverlayFace::Style {
+                options: fresh_core::api::OverlayOptions {
+                    bg: Some(fresh_core::api::OverlayColorSpec::ThemeKey(
+                        "diagnostic.warning_bg".to_string(),
+                    )),
+                    extend_to_line_end: true,
+                    ..Default::default()
+                },
+            },
+            95,
+            None,
+            true,
+            None,
+        );
+        self.debug.stopped_buffer = Some(buffer_id);
+    }
+}
+
+impl Editor {
+    /// Window owning the session `session_id`, if it is still alive.
+    fn debug_session_window_id(&self, session_id: u64) -> Option<fresh_core::WindowId> {
+        self.windows.iter().find_map(|(id, w)| {
+            w.debug
+                .session
+                .as_ref()
+                .filter(|s| s.handle.session_id() == session_id)
+                .map(|_| *id)
+        })
+    }
+
+    fn fire_debug_session_changed(&self, state: &str, language: String) {
+        self.plugin_manager.read().unwrap().run_hook(
+            "debug_session_changed",
+            HookArgs::DebugSessionChanged {
+                state: state.to_string(),
+                language,
+            },
+        );
+    }
+
+    /// Start a session for the active buffer's language, or continue the
+    /// current one if it is stopped.
+    pub(crate) fn debug_start(&mut self) {
+        if let Some(session) = &self.active_window().debug.session {
+            if session.status == DebugStatus::Stopped {
+                self.debug_thread_request("continue");
+            } else {
+                self.set_status_message(t!("debug.already_running").to_string());
+            }
+            return;
+        }
+
+        let language = self.active_state().language.clone();
+        let Some(adapter) = self.config().debug_adapters.get(&language).cloned() else {
+            self.set_status_message(t!("debug.no_adapter", language = language).to_string());
+            return;
+        };
+        let Some(runtime) = self.tokio_runtime.clone() else {
+            self.set_status_message(t!("debug.unavailable").to_string());
+            return;
+        };
+
+        // Breakpoint lines in open buffers may have moved with edits since
+        // they were set; the adapter gets the current ones.
+        let window = self.active_window_mut();
+        let buffer_ids = window.buffers.ids();
+        for buffer_id in buffer_ids {
+            window.sync_breakpoints_for_buffer(buffer_id);
+        }
+
+        let root = window.root.clone();
+        let file = window
+            .active_state()
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf);
+        let mut vars: HashMap<&str, String> = HashMap::new();
+        vars.insert("workspaceFolder", window.debug_adapter_path(&root));
+        vars.insert(
+            "workspaceFolderBasename",
+            root.file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        );
+        if let Some(file) = &file {
+            vars.insert("file", window.debug_adapter_path(file));
+            if let Some(dir) = file.parent() {
+                vars.insert("fileDirname", window.debug_adapter_path(dir));
+            }
+            if let Some(stem) = file.file_stem() {
+                vars.insert(
+                    "fileBasenameNoExtension",
+                    stem.to_string_lossy().into_owned(),
+                );
+            }
+        }
+
+        let launch = DapLaunch {
+            command: adapter.command.clone(),
+            args: adapter.args.clone(),
+            env: adapter.env.into_iter().collect(),
+            adapter_id: language.clone(),
+            request: adapter.request.as_str().to_string(),
+            arguments: expand_launch_variables(&adapter.configuration, &vars),
+        };
+        let handle = DapHandle::spawn(
+            runtime.handle(),
+            launch,
+            window.bridge.sender(),
+            window.authority.long_running_spawner.clone(),
+        );
+        window.debug.session = Some(DebugSession {
+            handle,
+            language: language.clone(),
+            status: DebugStatus::Starting,
+            thread_id: None,
+            stop_reason: String::new(),
+            stop_description: None,
+        });
+        window.set_status_message(
+            t!("debug.starting", command = adapter.command.as_str()).to_string(),
+        );
+        self.fire_debug_session_changed(DebugStatus::Starting.as_str(), language);
+    }
+
+    /// End the active session, terminating the debuggee.
+    pub(crate) fn debug_stop(&mut self) {
+        match &self.active_window().debug.session {
+            Some(session) => session.handle.disconnect(),
+            None => self.set_status_message(t!("debug.not_running").to_string()),
+        }
+    }
+
+    /// Send a thread-scoped execution request (`continue`, `next`, `stepIn`,
+    /// `stepOut`, `pause`) for the thread that last stopped.
+    pub(crate) fn debug_thread_request(&mut self, command: &str) {
+        let Some(session) = &self.active_window().debug.session else {
+            self.set_status_message(t!("debug.not_running").to_string());
+            return;
+        };
+        // Only `pause` makes sense while running; everything else needs a
+        // stopped thread.
+        let wants_stopped = command != "pause";
+        if wants_stopped != (session.status == DebugStatus::Stopped) {
+            let message = if wants_stopped {
+                t!("debug.not_stopped")
+            } else {
+                t!("debug.already_stopped")
+            };
+            self.set_status_message(message.to_string());
+            return;
+        }
+        let thread_id = session.thread_id.unwrap_or(1);
+        if let Err(e) = session.handle.request(
+            DapRequestOrigin::Editor,
+            command,
+            Some(serde_json::json!({ "threadId": thread_id })),
+        ) {
+            self.set_status_message(t!("debug.failed", error = e).to_string());
+        }
+    }
+
+    /// Toggle a breakpoint on the cursor line of the active buffer.
+    pub(crate) fn debug_toggle_breakpoint(&mut self) {
+        self.active_window_mut().debug_toggle_breakpoint();
+    }
+
+    /// Remove every breakpoint.
+    pub(crate) fn debug_clear_breakpoints(&mut self) {
+        self.active_window_mut().debug_clear_breakpoints();
+    }
+
+    /// Plugin `editor.debugRequest`: forward an arbitrary request to the
+    /// active window's session. The response resolves the plugin callback.
+    #[cfg(feature = "plugins")]
+    pub(super) fn handle_debug_request(
+        &mut self,
+        command: String,
+        arguments: Option<Value>,
+        request_id: u64,
+    ) {
+        let result = match &self.active_window().debug.session {
+            Some(session) => {
+                session
+                    .handle
+                    .request(DapRequestOrigin::Plugin(request_id), &command, arguments)
+            }
+            None => Err(t!("debug.not_running").to_string()),
+        };
+        if let Err(e) = result {
+            self.plugin_manager
+                .read()
+                .unwrap()
+                .reject_callback(fresh_core::api::JsCallbackId::from(request_id), e);
+        }
+    }
+
+    /// Handle an event pushed by a debug adapter.
+    pub(super) fn handle_dap_event(&mut self, session_id: u64, event: String, body: Option<Value>) {
+        let Some(window_id) = self.debug_session_window_id(session_id) else {
+            return;
+        };
+        let body = body.unwrap_or(Value::Null);
+        match event.as_str() {
+            "initialized" => {
+                // Configuration phase: breakpoints first, then let the
+                // debuggee run.
+                let window = self.windows.get_mut(&window_id).expect("window present");
+                for path in window.debug.breakpoint_files() {
+                    let lines = window.debug.breakpoints_for(&path);
+                    window.send_breakpoints(&path, &lines);
+                }
+                if let Some(session) = window.debug.session_mut(session_id) {
+                    if let Err(e) =
+                        session
+                            .handle
+                            .request(DapRequestOrigin::Editor, "configurationDone", None)
+                    {
+                        tracing::warn!("Failed to finish debug configuration: {}", e);
+                    }
+                }
+            }
+            "stopped" => {
+                let stopped: StoppedEventBody = match serde_json::from_value(body) {
+                    Ok(stopped) => stopped,
+                    Err(e) => {
+                        tracing::warn!("Malformed DAP stopped event: {}", e);
+                        return;
+                    }
+                };
+                let window = self.windows.get_mut(&window_id).expect("window present");
+                let Some(session) = window.debug.session_mut(session_id) else {
+                    return;
+                };
+                session.status = DebugStatus::Stopped;
+                if stopped.thread_id.is_some() {
+                    session.thread_id = stopped.thread_id;
+                }
+                session.stop_reason = stopped.reason;
+                session.stop_description = stopped.description;
+                let thread_id = session.thread_id.unwrap_or(1);
+                if let Err(e) = session.handle.request(
+                    DapRequestOrigin::Editor,
+                    "stackTrace",
+                    Some(serde_json::json!({
+                        "threadId": thread_id,
+                        "startFrame": 0,
+                        "levels": STACK_TRACE_LEVELS,
+                    })),
+                ) {
+                    tracing::warn!("Failed to request stack trace: {}", e);
+                }
+            }
+            "continued" => {
+                let window = self.windows.get_mut(&window_id).expect("window present");
+                let Some(session) = window.debug.session_mut(session_id) else {
+                    return;
+                };
+                session.status = DebugStatus::Running;
+                let language = session.language.clone();
+                window.clear_debug_stopped_location();
+                self.fire_debug_session_changed(DebugStatus::Running.as_str(), language);
+            }
+            "output" => {
+                if let Ok(output) = serde_json::from_value::<OutputEventBody>(body) {
+                    self.plugin_manager.read().unwrap().run_hook(
+                        "debug_output",
+                        HookArgs::DebugOutput {
+                            category: output.category.unwrap_or_else(|| "console".to_string()),
+                            output: output.output,
+                        },
+                    );
+                }
+            }
+            "exited" => {
+                let code = body.get("exitCode").and_then(Value::as_i64).unwrap_or(0);
+                if let Some(window) = self.windows.get_mut(&window_id) {
+                    window.set_status_message(t!("debug.exited", code = code).to_string());
+                }
+            }
+            "terminated" => {
+                if let Some(session) = self
+                    .windows
+                    .get_mut(&window_id)
+                    .and_then(|w| w.debug.session_mut(session_id))
+                {
+                    session.handle.disconnect();
+                }
+            }
+            _ => {
+                tracing::debug!("Ignoring DAP event '{}'", event);
+            }
+        }
+    }
+
+    /// Handle a response from a debug adapter.
+    pub(super) fn handle_dap_response(
+        &mut self,
+        session_id: u64,
+        origin: DapRequestOrigin,
+        command: String,
+        result: Result<Value, String>,
+    ) {
+        if let DapRequestOrigin::Plugin(request_id) = origin {
+            let callback_id = fresh_core::api::JsCallbackId::from(request_id);
+            let plugin_manager = self.plugin_manager.read().unwrap();
+            match result {
+                Ok(value) => plugin_manager.resolve_callback(callback_id, value.to_string()),
+                Err(err) => plugin_manager.reject_callback(callback_id, err),
+            }
+            return;
+        }
+        let Some(window_id) = self.debug_session_window_id(session_id) else {
+            return;
+        };
+
+        match (command.as_str(), result) {
+            ("stackTrace", Ok(body)) => self.handle_dap_stack_trace(window_id, session_id, body),
+            ("initialize" | "launch" | "attach", Err(error)) => {
+                if let Some(window) = self.windows.get_mut(&window_id) {
+                    window.set_status_message(t!("debug.failed", error = error).to_string());
+                    if let Some(session) = window.debug.session_mut(session_id) {
+                        session.handle.disconnect();
+                    }
+                }
+            }
+            ("launch" | "attach", Ok(_)) => {
+                let Some(session) = self
+                    .windows
+                    .get_mut(&window_id)
+                    .and_then(|w| w.debug.session_mut(session_id))
+                else {
+                    return;
+                };
+                // A `stopped` event (stop-on-entry) may already have arrived.
+                if session.status == DebugStatus::Starting {
+                    session.status = DebugStatus::Running;
+                    let language = session.language.clone();
+                    self.fire_debug_session_changed(DebugStatus::Running.as_str(), language);
+                }
+            }
+            (_, Err(error)) => {
+                tracing::warn!("DAP request '{}' failed: {}", command, error);
+            }
+            (_, Ok(_)) => {}
+        }
+    }
+
+    /// Reveal the innermost frame of a fresh stack trace and report the
+    /// whole stack to plugins.
+    fn handle_dap_stack_trace(
+        &mut self,
+        window_id: fresh_core::WindowId,
+        session_id: u64,
+        body: Value,
+    ) {
+        let trace: StackTraceBody = match serde_json::from_value(body) {
+            Ok(trace) => trace,
+            Err(e) => {
+                tracing::warn!("Malformed DAP stackTrace response: {}", e);
+                return;
+            }
+        };
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        let Some(session) = window.debug.session.as_ref() else {
+            return;
+        };
+        let thread_id = session.thread_id;
+        let reason = session.stop_reason.clone();
+        let status = t!(
+            "debug.stopped",
+            reason = session.stop_description.as_deref().unwrap_or(&reason)
+        )
+        .to_string();
+        let language = session.language.clone();
+
+        let frames: Vec<DebugStackFrame> = trace
+            .stack_frames
+            .iter()
+            .map(|frame| DebugStackFrame {
+                id: frame.id,
+                name: frame.name.clone(),
+                file: frame
+                    .source
+                    .as_ref()
+                    .and_then(|s| s.path.as_deref())
+                    .map(|p| window.debug_host_path(p).to_string_lossy().into_owned()),
+                line: frame.line.max(0) as u32,
+                column: frame.column.max(0) as u32,
+            })
+            .collect();
+
+        // Navigation goes through the active window; a session owned by a
+        // background window still reports its stack, it just doesn't steal
+        // focus.
+        let top = frames
+            .iter()
+            .find_map(|f| f.file.as_ref().map(|file| (PathBuf::from(file), f.line)));
+        if let (Some((path, line)), true) = (top, window_id == self.active_window) {
+            match self.open_file(&path) {
+                Ok(buffer_id) => {
+                    self.jump_to_line_column(Some(line as usize), None);
+                    self.active_window_mut()
+                        .show_debug_stopped_location(buffer_id, (line as usize).saturating_sub(1));
+                }
+                Err(e) => tracing::warn!("Failed to open stopped location {:?}: {}", path, e),
+            }
+        }
+        // Set after navigation so it isn't replaced by the "Opened" message.
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.set_status_message(status);
+        }
+
+        if self.debug_session_window_id(session_id).is_none() {
+            return;
+        }
+        self.fire_debug_session_changed(DebugStatus::Stopped.as_str(), language);
+        self.plugin_manager.read().unwrap().run_hook(
+            "debug_stopped",
+            HookArgs::DebugStopped {
+                thread_id,
+                reason,
+                frames,
+            },
+        );
+    }
+
+    /// The adapter process is gone (normal disconnect, crash or spawn
+    /// failure): drop the session and its decorations.
+    pub(super) fn handle_dap_session_ended(&mut self, session_id: u64, error: Option<String>) {
+        let Some(window_id) = self.debug_session_window_id(session_id) else {
+            return;
+        };
+        let window = self.windows.get_mut(&window_id).expect("window present");
+        let Some(session) = window.debug.session.take() else {
+            return;
+        };
+        window.clear_debug_stopped_location();
+        let message = match error {
+            Some(error) => t!("debug.failed", error = error),
+            None => t!("debug.ended"),
+        };
+        window.set_status_message(message.to_string());
+        self.fire_debug_session_changed("ended", session.language);
+    }
+}
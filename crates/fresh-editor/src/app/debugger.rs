@@ -0,0 +1,184 @@
+//! Debugger state owned by each window.
+//!
+//! Breakpoints are stored per file (0-based lines) so they survive closing
+//! and reopening a buffer and can be sent to an adapter before the file is
+//! ever opened. While a file is open the gutter indicators in
+//! [`BREAKPOINT_NAMESPACE`] are the source of truth — they move with edits —
+//! and the stored lines are refreshed from them whenever breakpoints change.
+//! Everything that talks to the adapter lives in `debug_actions`.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::model::event::BufferId;
+use crate::services::dap::DapHandle;
+
+/// Line-indicator namespace for breakpoint markers.
+pub(crate) const BREAKPOINT_NAMESPACE: &str = "debug-breakpoint";
+
+/// Line-indicator and overlay namespace for the "stopped here" highlight.
+pub(crate) const STOPPED_NAMESPACE: &str = "debug-stopped";
+
+/// Lifecycle of a debug session as the editor sees it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DebugStatus {
+    /// Adapter spawned, handshake / launch in flight.
+    Starting,
+    /// Debuggee running.
+    Running,
+    /// Debuggee paused (breakpoint, step, pause, exception).
+    Stopped,
+}
+
+impl DebugStatus {
+    /// Name reported to plugins through the `debug_session_changed` hook.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Starting => "starting",
+            Self::Running => "running",
+            Self::Stopped => "stopped",
+        }
+    }
+}
+
+/// A live debug session.
+#[derive(Debug)]
+pub(crate) struct DebugSession {
+    pub handle: DapHandle,
+    pub language: String,
+    pub status: DebugStatus,
+    /// Thread that reported the last `stopped` event; the target of
+    /// continue / step requests.
+    pub thread_id: Option<i64>,
+    /// Reason of the last `stopped` event, held until its stack trace
+    /// arrives and is reported to plugins.
+    pub stop_reason: String,
+    /// Human-readable text of the last `stopped` event, if the adapter
+    /// sent one; shown in the status bar instead of the bare reason.
+    pub stop_description: Option<String>,
+}
+
+/// Per-window debugger state: breakpoints plus the (single) active session.
+#[derive(Debug, Default)]
+pub(crate) struct DebugState {
+    breakpoints: BTreeMap<PathBuf, BTreeSet<usize>>,
+    pub session: Option<DebugSession>,
+    /// Buffer currently carrying the stopped-line decoration.
+    pub stopped_buffer: Option<BufferId>,
+}
+
+impl DebugState {
+    /// Replace the breakpoint lines of `path`. An empty set forgets the file.
+    pub(crate) fn set_breakpoints(&mut self, path: PathBuf, lines: BTreeSet<usize>) {
+        if lines.is_empty() {
+            self.breakpoints.remove(&path);
+        } else {
+            self.breakpoints.insert(path, lines);
+        }
+    }
+
+    /// Breakpoint lines of `path` (0-based, ascending).
+    pub(crate) fn breakpoints_for(&self, path: &Path) -> Vec<usize> {
+        self.breakpoints
+            .get(path)
+            .map(|lines| lines.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Every file with at least one breakpoint.
+    pub(crate) fn breakpoint_files(&self) -> Vec<PathBuf> {
+        self.breakpoints.keys().cloned().collect()
+    }
+
+    /// Forget every breakpoint. Returns the files that had some, so the
+    /// caller can clear them on the adapter side too.
+    pub(crate) fn clear_breakpoints(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.breakpoints).into_keys().collect()
+    }
+
+    /// The active session if it is the one identified by `session_id`.
+    pub(crate) fn session_mut(&mut self, session_id: u64) -> Option<&mut DebugSession> {
+        self.session
+            .as_mut()
+            .filter(|s| s.handle.session_id() == session_id)
+    }
+}
+
+/// Substitute `${name}` placeholders in every string of a launch
+/// configuration. Unknown placeholders are left untouched so adapter-specific
+/// syntax passes through.
+pub(crate) fn expand_launch_variables(value: &Value, vars: &HashMap<&str, String>) -> Value {
+    match value {
+        Value::String(s) => {
+            let mut out = s.clone();
+            for (name, replacement) in vars {
+                out = out.replace(&format!("${{{}}}", name), replacement);
+            }
+            Value::String(out)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|v| expand_launch_variables(v, vars))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), expand_launch_variables(v, vars)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_breakpoint_set_forgets_file() {
+        let mut state = DebugState::default();
+        let path = PathBuf::from("/w/main.py");
+        state.set_breakpoints(path.clone(), BTreeSet::from([4, 1]));
+        assert_eq!(state.breakpoints_for(&path), vec![1, 4]);
+        assert_eq!(state.breakpoint_files(), vec![path.clone()]);
+
+        state.set_breakpoints(path.clone(), BTreeSet::new());
+        assert!(state.breakpoints_for(&path).is_empty());
+        assert!(state.breakpoint_files().is_empty());
+    }
+
+    #[test]
+    fn clear_breakpoints_returns_affected_files() {
+        let mut state = DebugState::default();
+        state.set_breakpoints(PathBuf::from("/a"), BTreeSet::from([0]));
+        state.set_breakpoints(PathBuf::from("/b"), BTreeSet::from([2]));
+        assert_eq!(
+            state.clear_breakpoints(),
+            vec![PathBuf::from("/a"), PathBuf::from("/b")]
+        );
+        assert!(state.breakpoint_files().is_empty());
+    }
+
+    #[test]
+    fn expands_nested_placeholders_and_keeps_unknown_ones() {
+        let vars = HashMap::from([
+            ("file", "/w/main.py".to_string()),
+            ("workspaceFolder", "/w".to_string()),
+        ]);
+        let config = serde_json::json!({
+            "program": "${file}",
+            "cwd": "${workspaceFolder}",
+            "args": ["--root", "${workspaceFolder}/out", "${env:HOME}"],
+            "stopOnEntry": false,
+        });
+        let expanded = expand_launch_variables(&config, &vars);
+        assert_eq!(expanded["program"], "/w/main.py");
+        assert_eq!(expanded["cwd"], "/w");
+        assert_eq!(expanded["args"][1], "/w/out");
+        assert_eq!(expanded["args"][2], "${env:HOME}");
+        assert_eq!(expanded["stopOnEntry"], false);
+    }
+}
@@ -0,0 +1,543 @@
+//! LSP document colours.
+//!
+//! Colour literals are pulled per buffer (`textDocument/documentColor`) on
+//! open and after edits, and each gets a swatch drawn as inline virtual
+//! text just before it, in the colour it denotes.
+//!
+//! "Pick Color" on a literal asks the server how that colour can be
+//! written (`textDocument/colorPresentation`) and offers the answers in a
+//! chooser; the last row prompts for another colour as a hex value and
+//! asks again. Picking a presentation rewrites the literal in one undo
+//! step.
+
+use std::time::Instant;
+
+use lsp_types::{Color as LspColor, ColorInformation, ColorPresentation, Range, TextEdit};
+use ratatui::style::{Color, Style};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::types::LspFeature;
+use crate::view::popup::{Popup, PopupListItem, PopupPosition};
+use crate::view::prompt::PromptType;
+use crate::view::virtual_text::{VirtualTextId, VirtualTextPosition};
+
+/// String-id prefix of the swatch virtual texts.
+const COLOR_SWATCH_PREFIX: &str = "lsp-color:";
+
+/// Text of one swatch; the renderer pads inline virtual text with a space.
+const SWATCH: &str = "■";
+
+/// `data` of the chooser row that prompts for another colour.
+const CUSTOM_COLOR_DATA: &str = "custom";
+
+#[derive(Clone, Debug)]
+pub(crate) struct DocumentColorRequest {
+    pub(crate) buffer_id: BufferId,
+    pub(crate) version: u64,
+}
+
+/// The colour literals of one buffer and the swatches in front of them.
+#[derive(Debug, Default)]
+pub(crate) struct BufferDocumentColors {
+    /// Buffer version the colour ranges refer to.
+    version: u64,
+    colors: Vec<ColorInformation>,
+    /// Swatch of each drawn colour with the literal's index and byte length.
+    swatches: Vec<(VirtualTextId, usize, usize)>,
+}
+
+/// The literal "Pick Color" is rewriting, and the presentations offered
+/// for it once they arrive.
+#[derive(Clone, Debug)]
+pub(crate) struct ColorPick {
+    buffer_id: BufferId,
+    version: u64,
+    range: Range,
+    color: LspColor,
+    presentations: Vec<ColorPresentation>,
+}
+
+fn channel_to_u8(channel: f32) -> u8 {
+    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// `#rrggbb`, or `#rrggbbaa` for a translucent colour.
+fn color_to_hex(color: &LspColor) -> String {
+    let mut hex = format!(
+        "#{:02x}{:02x}{:02x}",
+        channel_to_u8(color.red),
+        channel_to_u8(color.green),
+        channel_to_u8(color.blue)
+    );
+    if color.alpha < 1.0 {
+        hex.push_str(&format!("{:02x}", channel_to_u8(color.alpha)));
+    }
+    hex
+}
+
+/// Parse `#rgb`, `#rrggbb` or `#rrggbbaa` (the `#` is optional).
+fn parse_hex_color(input: &str) -> Option<LspColor> {
+    let hex = input.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if !hex.is_ascii() {
+        return None;
+    }
+    let expanded: String = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => hex.to_string(),
+        _ => return None,
+    };
+    let channel = |i: usize| -> Option<f32> {
+        let byte = u8::from_str_radix(expanded.get(i..i + 2)?, 16).ok()?;
+        Some(f32::from(byte) / 255.0)
+    };
+    Some(LspColor {
+        red: channel(0)?,
+        green: channel(2)?,
+        blue: channel(4)?,
+        alpha: if expanded.len() == 8 {
+            channel(6)?
+        } else {
+            1.0
+        },
+    })
+}
+
+impl crate::app::window::Window {
+    /// Schedule a document-colour refresh for a buffer (debounced).
+    pub fn schedule_document_color_refresh(&mut self, buffer_id: BufferId) {
+        const DOCUMENT_COLOR_DEBOUNCE_MS: u64 = 400;
+        let next_time =
+            Instant::now() + std::time::Duration::from_millis(DOCUMENT_COLOR_DEBOUNCE_MS);
+        self.document_color_debounce.insert(buffer_id, next_time);
+    }
+
+    /// Handle a `textDocument/documentColor` response.
+    pub(crate) fn handle_lsp_document_colors(
+        &mut self,
+        request_id: u64,
+        uri: String,
+        colors: Vec<ColorInformation>,
+    ) {
+        let Some(request) = self.pending_document_color_requests.remove(&request_id) else {
+            tracing::debug!(
+                "Ignoring document color response without pending request (request_id={})",
+                request_id
+            );
+            return;
+        };
+        let Some(state) = self.buffers.get(&request.buffer_id) else {
+            return;
+        };
+        if state.buffer.version() != request.version {
+            tracing::debug!(
+                "Ignoring stale document colors for {} (request_id={}, version={}, current={})",
+                uri,
+                request_id,
+                request.version,
+                state.buffer.version()
+            );
+            self.schedule_document_color_refresh(request.buffer_id);
+            return;
+        }
+
+        tracing::info!(
+            "Received {} document colors for {} (request_id={})",
+            colors.len(),
+            uri,
+            request_id
+        );
+        let entry = self.document_colors.entry(request.buffer_id).or_default();
+        entry.version = request.version;
+        entry.colors = colors;
+        self.render_document_colors(request.buffer_id);
+    }
+
+    /// Rebuild the swatches of a buffer from its cached colours.
+    ///
+    /// Inlay hints clear every virtual text of a buffer, so this is also
+    /// called to put the swatches back afterwards. Colours computed for an
+    /// older buffer version are not drawn; a refresh is already scheduled.
+    pub(crate) fn render_document_colors(&mut self, buffer_id: BufferId) {
+        let Some(entry) = self.document_colors.get_mut(&buffer_id) else {
+            return;
+        };
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        state
+            .virtual_texts
+            .remove_by_prefix(&mut state.marker_list, COLOR_SWATCH_PREFIX);
+        entry.swatches.clear();
+        if state.buffer.version() != entry.version {
+            return;
+        }
+
+        for (index, info) in entry.colors.iter().enumerate() {
+            let start = state.buffer.lsp_position_to_byte(
+                info.range.start.line as usize,
+                info.range.start.character as usize,
+            );
+            let end = state.buffer.lsp_position_to_byte(
+                info.range.end.line as usize,
+                info.range.end.character as usize,
+            );
+            if start >= end {
+                continue;
+            }
+            let color = Color::Rgb(
+                channel_to_u8(info.color.red),
+                channel_to_u8(info.color.green),
+                channel_to_u8(info.color.blue),
+            );
+            let id = state.virtual_texts.add_with_id(
+                &mut state.marker_list,
+                start,
+                SWATCH.to_string(),
+                Style::default().fg(color),
+                VirtualTextPosition::BeforeChar,
+                0,
+                format!("{}{}", COLOR_SWATCH_PREFIX, index),
+            );
+            entry.swatches.push((id, index, end - start));
+        }
+    }
+
+    /// The colour literal touching `position`: its colour and byte range.
+    fn document_color_at(
+        &self,
+        buffer_id: BufferId,
+        position: usize,
+    ) -> Option<(LspColor, std::ops::Range<usize>)> {
+        let entry = self.document_colors.get(&buffer_id)?;
+        let state = self.buffers.get(&buffer_id)?;
+        // Swatches are anchored by markers, so the literal's start is read
+        // live rather than from the (possibly outdated) LSP range.
+        entry.swatches.iter().find_map(|(id, index, len)| {
+            let marker = state.virtual_texts.marker_id_of(*id)?;
+            let start = state.marker_list.get_position(marker)?;
+            let end = start + len;
+            if !(start..=end).contains(&position) {
+                return None;
+            }
+            Some((entry.colors.get(*index)?.color, start..end))
+        })
+    }
+
+    /// Drop every document colour of a buffer, including its swatches.
+    pub(crate) fn clear_document_colors(&mut self, buffer_id: BufferId) {
+        self.document_color_debounce.remove(&buffer_id);
+        self.pending_document_color_requests
+            .retain(|_, r| r.buffer_id != buffer_id);
+        if self
+            .color_pick
+            .as_ref()
+            .is_some_and(|pick| pick.buffer_id == buffer_id)
+        {
+            self.color_pick = None;
+            self.pending_color_presentation_request = None;
+        }
+        if self.document_colors.remove(&buffer_id).is_some() {
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state
+                    .virtual_texts
+                    .remove_by_prefix(&mut state.marker_list, COLOR_SWATCH_PREFIX);
+            }
+        }
+    }
+}
+
+impl Editor {
+    /// Re-pull document colors for all open buffers of a language.
+    pub(super) fn request_document_colors_for_language(&mut self, language: &str) {
+        let buffer_ids: Vec<_> = self
+            .buffers_for_language(language)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        for buffer_id in buffer_ids {
+            self.active_window_mut()
+                .schedule_document_color_refresh(buffer_id);
+        }
+    }
+
+    /// Issue a debounced document-colour request if the timer has elapsed.
+    pub(crate) fn maybe_request_document_colors(&mut self, buffer_id: BufferId) {
+        let ready = self
+            .active_window()
+            .document_color_debounce
+            .get(&buffer_id)
+            .is_some_and(|ready_at| Instant::now() >= *ready_at);
+        if !ready {
+            return;
+        }
+        self.active_window_mut()
+            .document_color_debounce
+            .remove(&buffer_id);
+
+        if self
+            .active_window()
+            .pending_document_color_requests
+            .values()
+            .any(|r| r.buffer_id == buffer_id)
+        {
+            // The in-flight answer will be stale; ask again once it lands.
+            self.active_window_mut()
+                .schedule_document_color_refresh(buffer_id);
+            return;
+        }
+        let window = self.active_window();
+        let Some(version) = window.buffers.get(&buffer_id).map(|s| s.buffer.version()) else {
+            return;
+        };
+        let request_id = window.next_lsp_request_id;
+
+        let sent =
+            self.with_lsp_for_buffer(buffer_id, LspFeature::DocumentColor, |handle, uri, _| {
+                handle.document_colors(request_id, uri.as_uri().clone())
+            });
+        match sent {
+            Some(Ok(())) => {
+                let window = self.active_window_mut();
+                window.next_lsp_request_id += 1;
+                window
+                    .pending_document_color_requests
+                    .insert(request_id, DocumentColorRequest { buffer_id, version });
+            }
+            Some(Err(e)) => tracing::debug!("Failed to request document colors: {}", e),
+            None => {}
+        }
+    }
+
+    /// "Pick Color": offer the presentations of the colour under the cursor.
+    pub(crate) fn pick_color_at_cursor(&mut self) {
+        let buffer_id = self.active_buffer();
+        let position = self.active_cursors().primary().position;
+        let Some((color, range)) = self.active_window().document_color_at(buffer_id, position)
+        else {
+            self.set_status_message(t!("lsp.no_document_color").to_string());
+            return;
+        };
+        let Some(state) = self.buffers().get(&buffer_id) else {
+            return;
+        };
+        let (start_line, start_char) = state.buffer.position_to_lsp_position(range.start);
+        let (end_line, end_char) = state.buffer.position_to_lsp_position(range.end);
+        let version = state.buffer.version();
+        self.active_window_mut().color_pick = Some(ColorPick {
+            buffer_id,
+            version,
+            range: Range::new(
+                lsp_types::Position::new(start_line as u32, start_char as u32),
+                lsp_types::Position::new(end_line as u32, end_char as u32),
+            ),
+            color,
+            presentations: Vec::new(),
+        });
+        self.request_color_presentations(color);
+    }
+
+    /// Ask for the presentations of `color` at the literal being picked.
+    fn request_color_presentations(&mut self, color: LspColor) {
+        let Some(pick) = self.active_window().color_pick.clone() else {
+            return;
+        };
+        let request_id = self.active_window().next_lsp_request_id;
+        let sent = self.with_lsp_for_buffer(
+            pick.buffer_id,
+            LspFeature::DocumentColor,
+            |handle, uri, _| {
+                handle.color_presentations(request_id, uri.as_uri().clone(), color, pick.range)
+            },
+        );
+        match sent {
+            Some(Ok(())) => {
+                let window = self.active_window_mut();
+                window.next_lsp_request_id += 1;
+                window.pending_color_presentation_request = Some(request_id);
+                if let Some(pick) = window.color_pick.as_mut() {
+                    pick.color = color;
+                }
+            }
+            Some(Err(e)) => {
+                tracing::debug!("Failed to request color presentations: {}", e);
+                self.set_status_message(t!("lsp.no_server_active").to_string());
+            }
+            None => self.set_status_message(t!("lsp.no_server_active").to_string()),
+        }
+    }
+
+    /// Handle a `textDocument/colorPresentation` response by showing the
+    /// chooser.
+    pub(crate) fn handle_lsp_color_presentations(
+        &mut self,
+        request_id: u64,
+        presentations: Result<Vec<ColorPresentation>, String>,
+    ) {
+        let active_buffer = self.active_buffer();
+        let window = self.active_window_mut();
+        if window.pending_color_presentation_request != Some(request_id) {
+            return;
+        }
+        window.pending_color_presentation_request = None;
+        let presentations = match presentations {
+            Ok(presentations) => presentations,
+            Err(e) => {
+                tracing::debug!("colorPresentation failed: {}", e);
+                Vec::new()
+            }
+        };
+        let Some(pick) = window.color_pick.as_mut() else {
+            return;
+        };
+        let current = window
+            .buffers
+            .get(&pick.buffer_id)
+            .map(|s| s.buffer.version());
+        if current != Some(pick.version) || pick.buffer_id != active_buffer {
+            window.color_pick = None;
+            return;
+        }
+        if presentations.is_empty() {
+            window.color_pick = None;
+            self.set_status_message(t!("lsp.no_color_presentations").to_string());
+            return;
+        }
+        pick.presentations = presentations;
+        self.show_color_presentation_popup();
+    }
+
+    fn show_color_presentation_popup(&mut self) {
+        let Some(pick) = self.active_window().color_pick.as_ref() else {
+            return;
+        };
+        let mut items: Vec<PopupListItem> = pick
+            .presentations
+            .iter()
+            .enumerate()
+            .map(|(i, presentation)| PopupListItem {
+                text: presentation.label.clone(),
+                detail: None,
+                icon: None,
+                data: Some(i.to_string()),
+                disabled: false,
+            })
+            .collect();
+        items.push(PopupListItem {
+            text: t!("lsp.color_custom").to_string(),
+            detail: None,
+            icon: None,
+            data: Some(CUSTOM_COLOR_DATA.to_string()),
+            disabled: false,
+        });
+        let title = t!("lsp.popup_color", color = color_to_hex(&pick.color)).to_string();
+
+        let theme = self.theme.read().unwrap();
+        let mut popup = Popup::list(items, &theme);
+        popup.kind = crate::view::popup::PopupKind::Action;
+        popup.title = Some(title);
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = 40;
+        popup.max_height = 15;
+        popup.border_style = Style::default().fg(theme.popup_border_fg);
+        popup.background_style = Style::default().bg(theme.popup_bg);
+        drop(theme);
+        popup.resolver = crate::view::popup::PopupResolver::ColorPresentation;
+        popup.focused = true;
+
+        let buffer_id = self.active_buffer();
+        if let Some(state) = self.active_window_mut().buffers.get_mut(&buffer_id) {
+            state.popups.show_or_replace(popup);
+        }
+    }
+
+    /// Act on a row of the colour chooser: rewrite the literal with the
+    /// chosen presentation, or prompt for another colour.
+    pub(crate) fn execute_color_presentation_choice(&mut self, data: &str) {
+        if data == CUSTOM_COLOR_DATA {
+            let current = self
+                .active_window()
+                .color_pick
+                .as_ref()
+                .map(|pick| color_to_hex(&pick.color))
+                .unwrap_or_default();
+            self.start_prompt_with_initial_text(
+                t!("lsp.color_prompt").to_string(),
+                PromptType::LspPickColor,
+                current,
+            );
+            return;
+        }
+        let Some(pick) = self.active_window_mut().color_pick.take() else {
+            return;
+        };
+        let Some(presentation) = data
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| pick.presentations.get(index))
+        else {
+            return;
+        };
+        let current = self
+            .buffers()
+            .get(&pick.buffer_id)
+            .map(|s| s.buffer.version());
+        if current != Some(pick.version) {
+            return;
+        }
+
+        let mut edits = vec![presentation.text_edit.clone().unwrap_or_else(|| TextEdit {
+            range: pick.range,
+            new_text: presentation.label.clone(),
+        })];
+        edits.extend(
+            presentation
+                .additional_text_edits
+                .clone()
+                .unwrap_or_default(),
+        );
+        if let Err(e) = self.apply_lsp_text_edits(pick.buffer_id, edits) {
+            tracing::warn!("Failed to apply color presentation: {}", e);
+        }
+    }
+
+    /// Handle the hex value typed after "Other color…".
+    pub(crate) fn handle_pick_color_input(&mut self, input: &str) {
+        match parse_hex_color(input) {
+            Some(color) => self.request_color_presentations(color),
+            None => {
+                self.active_window_mut().color_pick = None;
+                self.set_status_message(t!("lsp.color_invalid", input = input).to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_round_trips_opaque_and_translucent_colours() {
+        let red = parse_hex_color("#ff0000").unwrap();
+        assert_eq!(color_to_hex(&red), "#ff0000");
+        let translucent = parse_hex_color("00ff0080").unwrap();
+        assert_eq!(color_to_hex(&translucent), "#00ff0080");
+    }
+
+    #[test]
+    fn short_hex_expands_each_digit() {
+        let color = parse_hex_color("#0af").unwrap();
+        assert_eq!(color_to_hex(&color), "#00aaff");
+        assert_eq!(color.alpha, 1.0);
+    }
+
+    #[test]
+    fn malformed_hex_is_rejected() {
+        assert!(parse_hex_color("#12345").is_none());
+        assert!(parse_hex_color("#gggggg").is_none());
+        assert!(parse_hex_color("").is_none());
+    }
+}
@@ -0,0 +1,477 @@
+//! LSP document links.
+//!
+//! Links are pulled per buffer (`textDocument/documentLink`) on open and
+//! after edits, and drawn as underlines in the `lsp-document-link` overlay
+//! namespace. Links with a web target also carry it as an OSC 8 hyperlink,
+//! so terminals that support those can open them natively.
+//!
+//! A link is followed with Ctrl+click or the "Follow Link" action at the
+//! cursor. `file://` targets open in a buffer (honouring a `#L<line>,<col>`
+//! fragment); anything else is handed to the system opener. Links that
+//! arrive without a target are resolved (`documentLink/resolve`) when
+//! followed.
+
+use std::time::Instant;
+
+use lsp_types::{DocumentLink, Uri};
+use ratatui::style::{Modifier, Style};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::types::LspFeature;
+use crate::view::overlay::{Overlay, OverlayFace, OverlayHandle, OverlayNamespace};
+
+/// Overlay namespace owning the link underlines.
+const DOCUMENT_LINK_NAMESPACE: &str = "lsp-document-link";
+
+#[derive(Clone, Debug)]
+pub(crate) struct DocumentLinkRequest {
+    pub(crate) buffer_id: BufferId,
+    pub(crate) version: u64,
+}
+
+/// An in-flight `documentLink/resolve` for a link being followed.
+#[derive(Clone, Debug)]
+pub(crate) struct DocumentLinkResolveRequest {
+    pub(crate) request_id: u64,
+    pub(crate) buffer_id: BufferId,
+    pub(crate) generation: u64,
+    pub(crate) index: usize,
+}
+
+/// The links of one buffer and the overlays underlining them.
+#[derive(Debug, Default)]
+pub(crate) struct BufferDocumentLinks {
+    /// Bumped on every fresh response so a late resolve for a replaced list
+    /// is not written back.
+    generation: u64,
+    links: Vec<DocumentLink>,
+    /// Underline of each drawn link, paired with its link index. Overlays
+    /// are marker-anchored, so they keep pointing at the right text while
+    /// edits wait for the next refresh.
+    overlays: Vec<(OverlayHandle, usize)>,
+}
+
+fn namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string(DOCUMENT_LINK_NAMESPACE.to_string())
+}
+
+/// Parse the line (and optional column) of a `file://` link fragment.
+///
+/// Accepts `L12`, `12`, `L12,5` and `12:5`; both numbers are 1-based.
+fn parse_line_fragment(fragment: &str) -> Option<(usize, Option<usize>)> {
+    let fragment = fragment.strip_prefix('L').unwrap_or(fragment);
+    let (line, column) = match fragment.split_once([',', ':']) {
+        Some((line, column)) => (line, Some(column)),
+        None => (fragment, None),
+    };
+    let line = line.parse().ok().filter(|&l| l > 0)?;
+    let column = column.and_then(|c| c.parse().ok());
+    Some((line, column))
+}
+
+impl crate::app::window::Window {
+    /// Schedule a document-link refresh for a buffer (debounced).
+    pub fn schedule_document_link_refresh(&mut self, buffer_id: BufferId) {
+        const DOCUMENT_LINK_DEBOUNCE_MS: u64 = 400;
+        let next_time =
+            Instant::now() + std::time::Duration::from_millis(DOCUMENT_LINK_DEBOUNCE_MS);
+        self.document_link_debounce.insert(buffer_id, next_time);
+    }
+
+    /// Handle a `textDocument/documentLink` response.
+    pub(crate) fn handle_lsp_document_links(
+        &mut self,
+        request_id: u64,
+        uri: String,
+        links: Vec<DocumentLink>,
+    ) {
+        let Some(request) = self.pending_document_link_requests.remove(&request_id) else {
+            tracing::debug!(
+                "Ignoring document link response without pending request (request_id={})",
+                request_id
+            );
+            return;
+        };
+        let Some(state) = self.buffers.get(&request.buffer_id) else {
+            return;
+        };
+        if state.buffer.version() != request.version {
+            tracing::debug!(
+                "Ignoring stale document links for {} (request_id={}, version={}, current={})",
+                uri,
+                request_id,
+                request.version,
+                state.buffer.version()
+            );
+            self.schedule_document_link_refresh(request.buffer_id);
+            return;
+        }
+
+        tracing::info!(
+            "Received {} document links for {} (request_id={})",
+            links.len(),
+            uri,
+            request_id
+        );
+        let entry = self.document_links.entry(request.buffer_id).or_default();
+        entry.generation += 1;
+        entry.links = links;
+        self.render_document_links(request.buffer_id);
+    }
+
+    /// Replace the underlines of a buffer with its cached links.
+    fn render_document_links(&mut self, buffer_id: BufferId) {
+        let Some(entry) = self.document_links.get_mut(&buffer_id) else {
+            return;
+        };
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let ns = namespace();
+        state.overlays.clear_namespace(&ns, &mut state.marker_list);
+        entry.overlays.clear();
+
+        for (index, link) in entry.links.iter().enumerate() {
+            let start = state.buffer.lsp_position_to_byte(
+                link.range.start.line as usize,
+                link.range.start.character as usize,
+            );
+            let end = state.buffer.lsp_position_to_byte(
+                link.range.end.line as usize,
+                link.range.end.character as usize,
+            );
+            if start >= end {
+                continue;
+            }
+            let mut overlay = Overlay::with_namespace(
+                &mut state.marker_list,
+                start..end,
+                OverlayFace::Style {
+                    style: Style::default().add_modifier(Modifier::UNDERLINED),
+                },
+                ns.clone(),
+            );
+            overlay.url = link
+                .target
+                .as_ref()
+                .filter(|target| target.scheme().is_some_and(|s| s.as_str() != "file"))
+                .map(|target| target.as_str().to_string());
+            overlay.message = link.tooltip.clone();
+            let handle = state.overlays.add(overlay);
+            entry.overlays.push((handle, index));
+        }
+    }
+
+    /// Index of the link whose underline covers `position`.
+    fn document_link_at(&self, buffer_id: BufferId, position: usize) -> Option<usize> {
+        let entry = self.document_links.get(&buffer_id)?;
+        let state = self.buffers.get(&buffer_id)?;
+        entry.overlays.iter().find_map(|(handle, index)| {
+            let overlay = state.overlays.get_by_handle(handle)?;
+            overlay
+                .range(&state.marker_list)
+                .contains(&position)
+                .then_some(*index)
+        })
+    }
+
+    /// Drop every document link of a buffer, including its underlines.
+    pub(crate) fn clear_document_links(&mut self, buffer_id: BufferId) {
+        self.document_link_debounce.remove(&buffer_id);
+        self.pending_document_link_requests
+            .retain(|_, r| r.buffer_id != buffer_id);
+        if self
+            .pending_document_link_resolve
+            .as_ref()
+            .is_some_and(|r| r.buffer_id == buffer_id)
+        {
+            self.pending_document_link_resolve = None;
+        }
+        if self.document_links.remove(&buffer_id).is_some() {
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state
+                    .overlays
+                    .clear_namespace(&namespace(), &mut state.marker_list);
+            }
+        }
+    }
+}
+
+impl Editor {
+    /// Re-pull document links for all open buffers of a language.
+    pub(super) fn request_document_links_for_language(&mut self, language: &str) {
+        let buffer_ids: Vec<_> = self
+            .buffers_for_language(language)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        for buffer_id in buffer_ids {
+            self.active_window_mut()
+                .schedule_document_link_refresh(buffer_id);
+        }
+    }
+
+    /// Issue a debounced document-link request if the timer has elapsed.
+    pub(crate) fn maybe_request_document_links(&mut self, buffer_id: BufferId) {
+        let ready = self
+            .active_window()
+            .document_link_debounce
+            .get(&buffer_id)
+            .is_some_and(|ready_at| Instant::now() >= *ready_at);
+        if !ready {
+            return;
+        }
+        self.active_window_mut()
+            .document_link_debounce
+            .remove(&buffer_id);
+
+        if self
+            .active_window()
+            .pending_document_link_requests
+            .values()
+            .any(|r| r.buffer_id == buffer_id)
+        {
+            // The in-flight answer will be stale; ask again once it lands.
+            self.active_window_mut()
+                .schedule_document_link_refresh(buffer_id);
+            return;
+        }
+        let window = self.active_window();
+        let Some(version) = window.buffers.get(&buffer_id).map(|s| s.buffer.version()) else {
+            return;
+        };
+        let request_id = window.next_lsp_request_id;
+
+        let sent =
+            self.with_lsp_for_buffer(buffer_id, LspFeature::DocumentLink, |handle, uri, _| {
+                handle.document_links(request_id, uri.as_uri().clone())
+            });
+        match sent {
+            Some(Ok(())) => {
+                let window = self.active_window_mut();
+                window.next_lsp_request_id += 1;
+                window
+                    .pending_document_link_requests
+                    .insert(request_id, DocumentLinkRequest { buffer_id, version });
+            }
+            Some(Err(e)) => tracing::debug!("Failed to request document links: {}", e),
+            None => {}
+        }
+    }
+
+    /// "Follow Link": open the target of the link under the cursor.
+    pub(crate) fn follow_document_link_at_cursor(&mut self) {
+        let buffer_id = self.active_buffer();
+        let position = self.active_cursors().primary().position;
+        match self.active_window().document_link_at(buffer_id, position) {
+            Some(index) => self.follow_document_link(buffer_id, index),
+            None => self.set_status_message(t!("lsp.no_document_link").to_string()),
+        }
+    }
+
+    /// Follow the link under a Ctrl+click, if the click landed on one.
+    pub(super) fn click_document_link(
+        &mut self,
+        col: u16,
+        row: u16,
+        split_id: crate::model::event::LeafId,
+        buffer_id: BufferId,
+        content_rect: ratatui::layout::Rect,
+    ) -> bool {
+        if !self.active_window().document_links.contains_key(&buffer_id) {
+            return false;
+        }
+        let Some(compose_width) = self
+            .active_window()
+            .buffers
+            .splits()
+            .and_then(|(_, vs)| vs.get(&split_id))
+            .map(|vs| vs.compose_width)
+        else {
+            return false;
+        };
+        let gutter_width = self
+            .buffers()
+            .get(&buffer_id)
+            .map(|s| s.margins.left_total_width() as u16)
+            .unwrap_or(0);
+        let mappings = self
+            .active_layout()
+            .view_line_mappings
+            .get(&split_id)
+            .cloned();
+        let Some(target) = super::click_geometry::screen_to_buffer_position_with_overshoot(
+            col,
+            row,
+            content_rect,
+            gutter_width,
+            &mappings,
+            0,
+            false,
+            compose_width,
+        ) else {
+            return false;
+        };
+        if target.row_overshoot > 0 || target.row_undershoot > 0 || target.col_overshoot > 0 {
+            return false;
+        }
+        match self
+            .active_window()
+            .document_link_at(buffer_id, target.position)
+        {
+            Some(index) => {
+                self.follow_document_link(buffer_id, index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Open the target of a link, resolving it first when the server left
+    /// the target out.
+    fn follow_document_link(&mut self, buffer_id: BufferId, index: usize) {
+        let window = self.active_window();
+        let Some(entry) = window.document_links.get(&buffer_id) else {
+            return;
+        };
+        let Some(link) = entry.links.get(index).cloned() else {
+            return;
+        };
+        if let Some(target) = &link.target {
+            self.open_document_link_target(target);
+            return;
+        }
+
+        let generation = entry.generation;
+        let Some(language) = window.buffers.get(&buffer_id).map(|s| s.language.clone()) else {
+            return;
+        };
+        let window = self.active_window_mut();
+        let request_id = window.next_lsp_request_id;
+        let sent = window
+            .lsp
+            .handle_for_feature_mut(&language, LspFeature::DocumentLink)
+            .filter(|sh| sh.capabilities.document_link_resolve)
+            .map(|sh| sh.handle.document_link_resolve(request_id, link));
+        match sent {
+            Some(Ok(())) => {
+                window.next_lsp_request_id += 1;
+                window.pending_document_link_resolve = Some(DocumentLinkResolveRequest {
+                    request_id,
+                    buffer_id,
+                    generation,
+                    index,
+                });
+            }
+            Some(Err(e)) => {
+                tracing::debug!("Failed to send documentLink/resolve: {}", e);
+                self.set_status_message(t!("lsp.document_link_no_target").to_string());
+            }
+            None => self.set_status_message(t!("lsp.document_link_no_target").to_string()),
+        }
+    }
+
+    /// Handle a `documentLink/resolve` response by following the link.
+    pub(crate) fn handle_lsp_document_link_resolved(
+        &mut self,
+        request_id: u64,
+        link: Result<DocumentLink, String>,
+    ) {
+        let window = self.active_window_mut();
+        if window
+            .pending_document_link_resolve
+            .as_ref()
+            .is_none_or(|r| r.request_id != request_id)
+        {
+            return;
+        }
+        let Some(request) = window.pending_document_link_resolve.take() else {
+            return;
+        };
+        let link = match link {
+            Ok(link) => link,
+            Err(e) => {
+                tracing::debug!("documentLink/resolve failed: {}", e);
+                self.set_status_message(t!("lsp.document_link_no_target").to_string());
+                return;
+            }
+        };
+        let target = link.target.clone();
+        if let Some(entry) = window.document_links.get_mut(&request.buffer_id) {
+            if entry.generation == request.generation {
+                if let Some(slot) = entry.links.get_mut(request.index) {
+                    *slot = link;
+                }
+            }
+        }
+        match target {
+            Some(target) => self.open_document_link_target(&target),
+            None => self.set_status_message(t!("lsp.document_link_no_target").to_string()),
+        }
+    }
+
+    /// Open a link target: files in a buffer, everything else externally.
+    fn open_document_link_target(&mut self, target: &Uri) {
+        let is_file = target.scheme().is_some_and(|s| s.as_str() == "file");
+        if !is_file {
+            let url = target.as_str().to_string();
+            #[cfg(feature = "runtime")]
+            if let Err(e) = open::that(&url) {
+                self.set_status_message(
+                    t!("lsp.document_link_open_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+            self.set_status_message(t!("lsp.document_link_opening", url = &url).to_string());
+            return;
+        }
+
+        // The fragment is a line/column hint, not part of the path.
+        let (without_fragment, fragment) = match target.as_str().split_once('#') {
+            Some((uri, fragment)) => (uri, Some(fragment)),
+            None => (target.as_str(), None),
+        };
+        let Ok(uri) = without_fragment.parse::<Uri>() else {
+            return;
+        };
+        let wire = crate::app::types::LspUri::from_wire(uri);
+        if let Err(e) = self.open_lsp_uri_target(&wire) {
+            if let Some(confirmation) =
+                e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
+            {
+                self.start_large_file_encoding_confirmation(confirmation);
+            } else {
+                self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+            }
+            return;
+        }
+        if let Some((line, column)) = fragment.and_then(parse_line_fragment) {
+            self.goto_line_col(line, column);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fragment_accepts_line_and_optional_column() {
+        assert_eq!(parse_line_fragment("L12"), Some((12, None)));
+        assert_eq!(parse_line_fragment("12"), Some((12, None)));
+        assert_eq!(parse_line_fragment("L12,5"), Some((12, Some(5))));
+        assert_eq!(parse_line_fragment("12:5"), Some((12, Some(5))));
+        assert_eq!(parse_line_fragment("L3C7"), None);
+    }
+
+    #[test]
+    fn fragment_rejects_anchors_and_line_zero() {
+        assert_eq!(parse_line_fragment("section-2"), None);
+        assert_eq!(parse_line_fragment("L0"), None);
+        assert_eq!(parse_line_fragment(""), None);
+    }
+}
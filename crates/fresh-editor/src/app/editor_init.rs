@@ -624,6 +624,8 @@ impl Editor {
             ansi_background_path: None,
             background_fade: crate::primitives::ansi_background::DEFAULT_BACKGROUND_FADE,
             clipboard: crate::services::clipboard::Clipboard::new(),
+            kill_ring: crate::model::kill_ring::KillRing::default(),
+            spell_checker: None,
             should_quit: false,
             workspace_trust_prompt_cancellable: false,
             workspace_trust_markers: Vec::new(),
@@ -686,6 +688,8 @@ impl Editor {
             keybinding_editor: None,
             stdin_stream: stdin_stream::StdinStream::default(),
             global_popups: crate::view::popup::PopupManager::new(),
+            pending_window_requests: Default::default(),
+            pending_file_operation: None,
             previous_cursor_screen_pos: None,
             cursor_jump_animation: None,
             pending_vb_animations: Vec::new(),
@@ -1152,6 +1156,7 @@ impl Editor {
         )));
         quick_open_registry.register(Box::new(BufferProvider::new()));
         quick_open_registry.register(Box::new(GotoLineProvider::new()));
+        quick_open_registry.register(Box::new(DocumentSymbolProvider::new()));
 
         // Build shared theme cache for plugin access
         let theme_cache = Arc::new(RwLock::new(theme_registry.to_json_map()));
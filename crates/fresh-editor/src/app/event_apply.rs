@@ -23,6 +23,16 @@ use crate::model::event::Event;
 use super::types::EventLineInfo;
 use super::Editor;
 
+/// Whether `event` moves a cursor or edits the buffer, ending a run of
+/// kills.
+fn breaks_kill_chain(event: &Event) -> bool {
+    match event {
+        Event::MoveCursor { .. } => true,
+        Event::Batch { events, .. } => events.iter().any(breaks_kill_chain),
+        _ => event.modifies_buffer(),
+    }
+}
+
 impl Editor {
     /// All event applications MUST go through this method to ensure consistency.
     /// Log an event and apply it to the active buffer.
@@ -81,6 +91,13 @@ impl Editor {
                 .promote_active_buffer_from_preview();
         }
 
+        // Moving the cursor or editing ends a run of kills however it came
+        // about (a click, a plugin, an LSP edit). The kill commands put the
+        // chain back after applying their own edits.
+        if breaks_kill_chain(event) {
+            self.active_window_mut().kill_chain = false;
+        }
+
         // IMPORTANT: Calculate LSP changes and line info BEFORE applying to buffer!
         // The byte positions in the events are relative to the ORIGINAL buffer,
         // so we must convert them to LSP positions before modifying the buffer.
@@ -135,6 +152,9 @@ impl Editor {
                 win.prune_orphaned_folds(buf);
                 win.schedule_semantic_tokens_full_refresh(buf);
                 win.schedule_folding_ranges_refresh(buf);
+                win.schedule_code_lens_refresh(buf);
+                win.schedule_document_link_refresh(buf);
+                win.schedule_document_color_refresh(buf);
             }
             Event::Batch { events, .. } => {
                 let has_edits = events
@@ -147,6 +167,9 @@ impl Editor {
                     win.prune_orphaned_folds(buf);
                     win.schedule_semantic_tokens_full_refresh(buf);
                     win.schedule_folding_ranges_refresh(buf);
+                    win.schedule_code_lens_refresh(buf);
+                    win.schedule_document_link_refresh(buf);
+                    win.schedule_document_color_refresh(buf);
                 }
             }
             _ => {}
@@ -274,6 +297,7 @@ impl Editor {
         // holds regardless of which edit path runs.
         self.active_window_mut()
             .promote_active_buffer_from_preview();
+        self.active_window_mut().kill_chain = false;
 
         let active_buf = self.active_buffer();
         // Use `effective_active_split` rather than `split_manager.active_split()`
@@ -643,7 +643,19 @@ impl Editor {
     /// Perform the actual file explorer delete operation (called after prompt confirmation)
     /// For local files: moves to system trash/recycle bin
     /// For remote files: moves to ~/.local/share/fresh/trash/ on remote
-    pub fn perform_file_explorer_delete(&mut self, path: std::path::PathBuf, _is_dir: bool) {
+    pub fn perform_file_explorer_delete(&mut self, path: std::path::PathBuf, is_dir: bool) {
+        self.run_explorer_file_action(super::lsp_file_operations::ExplorerFileAction::Delete {
+            paths: vec![(path, is_dir)],
+        });
+    }
+
+    /// Trash `path` once language servers have had their say. Returns
+    /// whether it went.
+    pub(super) fn finish_file_explorer_delete(
+        &mut self,
+        path: std::path::PathBuf,
+        _is_dir: bool,
+    ) -> bool {
         let name = path
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
@@ -737,11 +749,13 @@ impl Editor {
 
                 // Ensure focus remains on file explorer
                 self.active_window_mut().key_context = KeyContext::FileExplorer;
+                true
             }
             Err(e) => {
                 self.set_status_message(
                     t!("explorer.error_trash", error = e.to_string()).to_string(),
                 );
+                false
             }
         }
     }
@@ -831,6 +845,23 @@ impl Editor {
             return;
         }
 
+        self.run_explorer_file_action(super::lsp_file_operations::ExplorerFileAction::Rename {
+            original_path,
+            original_name,
+            new_name,
+            is_new_file,
+        });
+    }
+
+    /// Rename on disk once language servers have had their say. Returns
+    /// whether the rename went through.
+    pub(super) fn finish_file_explorer_rename(
+        &mut self,
+        original_path: std::path::PathBuf,
+        original_name: String,
+        new_name: String,
+        is_new_file: bool,
+    ) -> bool {
         let new_path = original_path
             .parent()
             .map(|p| p.join(&new_name))
@@ -889,13 +920,17 @@ impl Editor {
                         t!("explorer.renamed", old = &original_name, new = &new_name).to_string(),
                     );
                     self.notify_file_explorer_change(&new_path);
+                    true
                 }
                 Err(e) => {
                     self.set_status_message(
                         t!("explorer.error_renaming", error = e.to_string()).to_string(),
                     );
+                    false
                 }
             }
+        } else {
+            false
         }
     }
 
@@ -1099,21 +1134,41 @@ impl Editor {
     }
 
     /// Paste all resolved items (safe + confirmed-overwrite) from a multi-conflict flow.
+    pub(super) fn execute_resolved_multi_paste(
+        &mut self,
+        safe: Vec<(PathBuf, PathBuf)>,
+        to_overwrite: Vec<(PathBuf, PathBuf)>,
+        is_cut: bool,
+    ) {
+        if safe.is_empty() && to_overwrite.is_empty() {
+            return;
+        }
+        self.run_explorer_file_action(super::lsp_file_operations::ExplorerFileAction::MultiPaste {
+            safe,
+            to_overwrite,
+            is_cut,
+        });
+    }
+
+    /// Run the filesystem side of a resolved multi-paste.
     ///
     /// Runs every filesystem op first, then does a single tree refresh and
     /// a single navigate to the first successfully pasted item. Each paste
     /// inside `perform_file_explorer_paste` would otherwise re-reload the
     /// same parent directories N times and flash N different status
     /// messages, with only the last one ever being visible.
-    pub(super) fn execute_resolved_multi_paste(
+    ///
+    /// Returns the pairs that fully went through: clean moves for a cut,
+    /// every landed copy otherwise.
+    pub(super) fn finish_resolved_multi_paste(
         &mut self,
         safe: Vec<(PathBuf, PathBuf)>,
         to_overwrite: Vec<(PathBuf, PathBuf)>,
         is_cut: bool,
-    ) {
+    ) -> Vec<(PathBuf, PathBuf)> {
         let total = safe.len() + to_overwrite.len();
         if total == 0 {
-            return;
+            return Vec::new();
         }
 
         let mut succeeded: Vec<(PathBuf, PathBuf)> = Vec::with_capacity(total);
@@ -1224,6 +1279,11 @@ impl Editor {
             self.active_window_mut().file_explorer_clipboard = None;
         }
         self.active_window_mut().key_context = KeyContext::FileExplorer;
+        if is_cut {
+            clean_moves
+        } else {
+            succeeded
+        }
     }
 
     /// Move or copy a single item at the filesystem level. No tree or UI
@@ -1396,6 +1456,21 @@ impl Editor {
     }
 
     pub fn perform_file_explorer_paste(&mut self, src: PathBuf, dst: PathBuf, is_cut: bool) {
+        self.run_explorer_file_action(super::lsp_file_operations::ExplorerFileAction::Paste {
+            src,
+            dst,
+            is_cut,
+        });
+    }
+
+    /// Move or copy on disk once language servers have had their say.
+    /// Returns whether the item fully arrived (a partial move doesn't count).
+    pub(super) fn finish_file_explorer_paste(
+        &mut self,
+        src: PathBuf,
+        dst: PathBuf,
+        is_cut: bool,
+    ) -> bool {
         let name = dst
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
@@ -1420,6 +1495,7 @@ impl Editor {
                     self.set_status_message(t!("explorer.pasted", name = &name).to_string());
                 }
                 self.active_window_mut().key_context = KeyContext::FileExplorer;
+                true
             }
             PasteOpOutcome::SourceRemovalFailed {
                 dst: landed_dst,
@@ -1441,6 +1517,7 @@ impl Editor {
                 // NB: don't clear the clipboard — source is still at its
                 // original location and the user may want to retry.
                 self.active_window_mut().key_context = KeyContext::FileExplorer;
+                false
             }
             PasteOpOutcome::Failed(e) => {
                 let msg = if is_cut {
@@ -1449,6 +1526,7 @@ impl Editor {
                     t!("explorer.error_copying", error = e.to_string()).to_string()
                 };
                 self.set_status_message(msg);
+                false
             }
         }
     }
@@ -175,6 +175,9 @@ impl Editor {
         }
 
         self.set_active_buffer(buffer_id);
+        if is_new_buffer {
+            self.apply_task_diagnostics_to_opened_file(path);
+        }
 
         // Opening a file focuses a buffer in the active split. If a
         // *different* split is maximized (most commonly the docked
@@ -993,6 +996,9 @@ impl crate::app::window::Window {
             .configure_for_line_numbers(self.resources.config.editor.line_numbers);
         state.apply_occurrence_highlight(self.resources.config.editor.highlight_occurrences);
 
+        // Breakpoints set before the file was last closed reappear.
+        self.restore_breakpoint_indicators(&mut state);
+
         self.buffers.insert(buffer_id, state);
         self.event_logs
             .insert(buffer_id, crate::model::event::EventLog::new());
@@ -1252,6 +1258,7 @@ impl crate::app::window::Window {
         // Restore global file state (scroll/cursor position) if available
         // This persists file positions across projects and editor instances
         self.restore_global_file_state(buffer_id, path, target_split);
+        self.restore_undo_history(buffer_id, path);
 
         // Emit control event
         self.resources.event_broadcaster.emit_named(
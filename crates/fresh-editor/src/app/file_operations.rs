@@ -89,6 +89,22 @@ impl Editor {
         self.finalize_save_buffer(buffer_id, path, false)
     }
 
+    /// Write a buffer's undo history to disk (`editor.persistent_undo`),
+    /// off the editor thread. No-op unless the buffer matches its file.
+    pub(crate) fn persist_undo_history(&self, buffer_id: BufferId) {
+        let Some((path, content, entries)) = self.active_window().undo_history_snapshot(buffer_id)
+        else {
+            return;
+        };
+        let max_bytes = self.config().editor.persistent_undo_max_bytes;
+        let ephemeral = self.config().editor.ephemeral_file_patterns.clone();
+        self.spawn_off_loop_effect("persist_undo_history", move || {
+            crate::workspace::PersistedUndoStore::save(
+                &path, &content, entries, max_bytes, &ephemeral,
+            );
+        });
+    }
+
     /// Internal helper to finalize save state for a specific buffer
     pub(crate) fn finalize_save_buffer(
         &mut self,
@@ -128,6 +144,7 @@ impl Editor {
         if let Some(event_log) = self.active_window_mut().event_logs.get_mut(&buffer_id) {
             event_log.mark_saved();
         }
+        self.persist_undo_history(buffer_id);
 
         // Update file modification time after save
         if let Some(ref p) = path {
@@ -1605,8 +1622,11 @@ impl crate::app::window::Window {
                     }
                 }
 
-                // Schedule folding range refresh
+                // Schedule folding range, code lens, link and colour refresh
                 self.schedule_folding_ranges_refresh(buffer_id);
+                self.schedule_code_lens_refresh(buffer_id);
+                self.schedule_document_link_refresh(buffer_id);
+                self.schedule_document_color_refresh(buffer_id);
             }
             LspSpawnResult::NotAutoStart => {
                 tracing::debug!(
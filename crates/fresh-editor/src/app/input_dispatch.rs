@@ -6,7 +6,7 @@
 use super::terminal_input::{should_enter_terminal_mode, TerminalModeInputHandler};
 use super::Editor;
 use crate::input::handler::{DeferredAction, InputContext, InputHandler, InputResult};
-use crate::input::keybindings::{Action, KeyContext};
+use crate::input::keybindings::{Action, KeyContext, KeybindingResolver};
 use anyhow::Result as AnyhowResult;
 use crossterm::event::KeyEvent;
 use rust_i18n::t;
@@ -78,6 +78,27 @@ impl Editor {
             return Some(result);
         }
 
+        // Command-block navigation is bound in the Terminal context, which
+        // scroll-back (Normal context) doesn't consult; honour those keys
+        // here so prompt jumps and output selection work in both modes.
+        if self.active_window().editor_pane_owns_keyboard()
+            && self
+                .active_window()
+                .is_terminal_buffer(self.active_buffer())
+        {
+            let action = self
+                .keybindings
+                .read()
+                .unwrap()
+                .resolve(event, KeyContext::Terminal);
+            if KeybindingResolver::is_terminal_command_block_action(&action) {
+                if let Err(e) = self.handle_action(action) {
+                    tracing::warn!("terminal command-block action failed: {e}");
+                }
+                return Some(InputResult::Consumed);
+            }
+        }
+
         // Check for keys that should re-enter terminal mode from scrollback view.
         // Any plain character key exits scrollback and is forwarded to the terminal.
         // The focus gate matters because the active buffer is still the
@@ -239,6 +260,22 @@ impl Editor {
                 }
             }
             DeferredAction::PromptSelectionChanged { selected_index } => {
+                if self
+                    .active_window()
+                    .prompt
+                    .as_ref()
+                    .is_some_and(|p| p.prompt_type == crate::view::prompt::PromptType::KillRing)
+                {
+                    self.preview_kill_ring_selection();
+                }
+                if self
+                    .active_window()
+                    .prompt
+                    .as_ref()
+                    .is_some_and(|p| p.prompt_type == crate::view::prompt::PromptType::QuickOpen)
+                {
+                    self.preview_quick_open_symbol();
+                }
                 // Fire hook for plugin prompts so they can update live preview
                 let plugin_custom_type =
                     self.active_window()
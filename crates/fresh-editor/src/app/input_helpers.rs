@@ -255,6 +255,10 @@ impl Editor {
         // Cancel any pending LSP requests since the text is changing
         self.active_window_mut().cancel_pending_lsp_requests();
 
+        // A character that can't be part of a linked tag name goes in at
+        // the primary cursor alone.
+        self.break_linked_editing_for(c);
+
         if let Some(events) = self
             .active_window_mut()
             .action_to_events(Action::InsertChar(c))
@@ -283,6 +287,9 @@ impl Editor {
         // Auto-trigger completion on trigger characters
         self.maybe_trigger_completion(c);
 
+        // Ask the server to format after its on-type trigger characters
+        self.maybe_format_on_type(c);
+
         Ok(())
     }
 
@@ -304,51 +311,65 @@ impl Editor {
         let action_description = format!("{:?}", action);
 
         if let Some(events) = self.active_window_mut().action_to_events(action) {
-            // Refuse the action if it would mutate a read-only buffer.
-            // Checking at the event level (rather than maintaining a hand-
-            // written allowlist of "editing" actions) catches every action
-            // that produces Insert/Delete, including SortLines, OpenLine,
-            // case transforms, and anything added in the future.
-            let has_buffer_mods = events
-                .iter()
-                .any(|e| matches!(e, Event::Insert { .. } | Event::Delete { .. }));
-            if has_buffer_mods && self.active_window().is_editing_disabled() {
-                self.set_status_message(t!("buffer.editing_disabled").to_string());
-                return Ok(());
-            }
+            self.apply_action_events(events, action_description);
+        }
 
-            if events.len() > 1 {
-                if has_buffer_mods {
-                    // Multi-cursor buffer edit: use optimized bulk edit (O(n) instead of O(n²))
-                    if let Some(bulk_edit) =
-                        self.apply_events_as_bulk_edit(events.clone(), action_description)
-                    {
-                        self.active_event_log_mut().append(bulk_edit);
-                    }
-                } else {
-                    // Multi-cursor non-buffer operation: use Batch for atomic undo
-                    let batch = Event::Batch {
-                        events: events.clone(),
-                        description: action_description,
-                    };
-                    self.active_event_log_mut().append(batch.clone());
-                    self.apply_event_to_active_buffer(&batch);
-                }
+        Ok(())
+    }
+
+    /// Apply the events an action produced: bulk edit (or batch) for
+    /// multi-cursor, plain log-and-apply for a single event, with position
+    /// history tracking. Returns false without applying anything when the
+    /// events would mutate a read-only buffer.
+    pub(super) fn apply_action_events(
+        &mut self,
+        events: Vec<Event>,
+        action_description: String,
+    ) -> bool {
+        // Refuse the action if it would mutate a read-only buffer.
+        // Checking at the event level (rather than maintaining a hand-
+        // written allowlist of "editing" actions) catches every action
+        // that produces Insert/Delete, including SortLines, OpenLine,
+        // case transforms, and anything added in the future.
+        let has_buffer_mods = events
+            .iter()
+            .any(|e| matches!(e, Event::Insert { .. } | Event::Delete { .. }));
+        if has_buffer_mods && self.active_window().is_editing_disabled() {
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            return false;
+        }
 
-                // Track position history for all events
-                for event in &events {
-                    self.track_cursor_movement(event);
+        if events.len() > 1 {
+            if has_buffer_mods {
+                // Multi-cursor buffer edit: use optimized bulk edit (O(n) instead of O(n²))
+                if let Some(bulk_edit) =
+                    self.apply_events_as_bulk_edit(events.clone(), action_description)
+                {
+                    self.active_event_log_mut().append(bulk_edit);
                 }
             } else {
-                // Single cursor - apply normally
-                for event in events {
-                    self.log_and_apply_event(&event);
-                    self.track_cursor_movement(&event);
-                }
+                // Multi-cursor non-buffer operation: use Batch for atomic undo
+                let batch = Event::Batch {
+                    events: events.clone(),
+                    description: action_description,
+                };
+                self.active_event_log_mut().append(batch.clone());
+                self.apply_event_to_active_buffer(&batch);
+            }
+
+            // Track position history for all events
+            for event in &events {
+                self.track_cursor_movement(event);
+            }
+        } else {
+            // Single cursor - apply normally
+            for event in events {
+                self.log_and_apply_event(&event);
+                self.track_cursor_movement(&event);
             }
         }
 
-        Ok(())
+        true
     }
 
     /// Track cursor movement in position history if applicable.
@@ -0,0 +1,271 @@
+//! Kill ring actions: kills, yank-pop and the kill ring picker.
+//!
+//! Copies, cuts and the Emacs kill commands all record into the editor's
+//! shared [`KillRing`](crate::model::kill_ring::KillRing). A kill directly
+//! following another kill appends to the same entry; the chain is broken by
+//! any other action, and by any cursor move or edit however it came about.
+//! Every buffer paste remembers what it inserted so yank-pop (`M-y`) can
+//! swap it in place for the previous ring entry.
+
+use ratatui::style::{Modifier, Style};
+use rust_i18n::t;
+
+use super::{Editor, YankCycleState};
+use crate::input::keybindings::Action;
+use crate::model::event::Event;
+use crate::model::kill_ring::{normalize_line_endings, KillDirection};
+use crate::view::virtual_text::{VirtualTextNamespace, VirtualTextPosition};
+
+/// Width of the one-line excerpt shown for each entry in the picker.
+const PICKER_EXCERPT_CHARS: usize = 80;
+
+/// Lines of the selected entry previewed below the cursor.
+const PREVIEW_MAX_LINES: usize = 10;
+
+/// Virtual text namespace holding the picker's preview lines.
+const PREVIEW_NAMESPACE: &str = "kill-ring-preview";
+
+impl Editor {
+    /// Record copied or killed text in the kill ring and mirror the newest
+    /// entry to the clipboard. With `kill` set, a kill that directly follows
+    /// another kill is appended to the same entry in `direction`.
+    pub(crate) fn record_kill(&mut self, parts: Vec<String>, direction: KillDirection, kill: bool) {
+        if kill && self.active_window().kill_chain {
+            self.kill_ring.append(parts, direction);
+        } else {
+            self.kill_ring.push(parts);
+        }
+        if kill {
+            self.active_window_mut().kill_chain = true;
+        }
+        if let Some(entry) = self.kill_ring.head() {
+            self.clipboard.copy(entry.text());
+        }
+    }
+
+    /// Kill the text `delete` would remove at every cursor: delete it as
+    /// usual and record it in the kill ring, one part per cursor.
+    pub(crate) fn kill_with(&mut self, delete: Action, direction: KillDirection) {
+        if self.active_window().is_editing_disabled() {
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            return;
+        }
+        let description = format!("{:?}", delete);
+        let Some(events) = self.active_window_mut().action_to_events(delete) else {
+            return;
+        };
+
+        let mut killed: Vec<(usize, String)> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Delete {
+                    range,
+                    deleted_text,
+                    ..
+                } => Some((range.start, deleted_text.clone())),
+                _ => None,
+            })
+            .collect();
+        if killed.is_empty() {
+            return;
+        }
+        killed.sort_by_key(|(start, _)| *start);
+        let parts = killed.into_iter().map(|(_, text)| text).collect();
+
+        // Applying the deletion breaks the chain; whether this kill
+        // continues it was decided before.
+        let chain = self.active_window().kill_chain;
+        if self.apply_action_events(events, description) {
+            self.active_window_mut().kill_chain = chain;
+            self.record_kill(parts, direction, true);
+        }
+    }
+
+    /// Replace the text the previous yank inserted with the next older
+    /// kill ring entry, wrapping around at the oldest.
+    pub(crate) fn yank_pop(&mut self) {
+        let buffer_id = self.active_buffer();
+        let Some(state) = self
+            .active_window_mut()
+            .yank_state
+            .take()
+            .filter(|s| s.buffer_id == buffer_id && !self.kill_ring.is_empty())
+        else {
+            self.set_status_message(t!("kill_ring.not_after_yank").to_string());
+            return;
+        };
+        if self.active_window().is_editing_disabled() {
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            return;
+        }
+
+        // Find each cursor's yanked text right before it. Anything else
+        // means the text was edited since, so there is nothing to replace.
+        let mut spans = Vec::with_capacity(state.inserted.len());
+        for (cursor_id, text) in &state.inserted {
+            let Some(end) = self.active_cursors().get(*cursor_id).map(|c| c.position) else {
+                self.set_status_message(t!("kill_ring.not_after_yank").to_string());
+                return;
+            };
+            let start = end.saturating_sub(text.len());
+            if self.active_state_mut().get_text_range(start, end) != *text {
+                self.set_status_message(t!("kill_ring.not_after_yank").to_string());
+                return;
+            }
+            spans.push((*cursor_id, start..end, text.clone()));
+        }
+        spans.sort_by_key(|(_, range, _)| range.start);
+
+        let index = (state.index + 1) % self.kill_ring.len();
+        let Some(entry) = self.kill_ring.get(index).cloned() else {
+            return;
+        };
+        let line_ending = self.active_state().buffer.line_ending().insertion_str();
+        let to_buffer_endings =
+            |text: &str| normalize_line_endings(text).replace('\n', line_ending);
+        let count = spans.len();
+        let replacements: Vec<String> = if count > 1 && entry.parts().len() == count {
+            entry.parts().iter().map(|p| to_buffer_endings(p)).collect()
+        } else {
+            vec![to_buffer_endings(&entry.text()); count]
+        };
+
+        let mut events = Vec::with_capacity(count * 2);
+        for ((cursor_id, range, old), new) in spans.iter().zip(&replacements).rev() {
+            events.push(Event::Delete {
+                range: range.clone(),
+                deleted_text: old.clone(),
+                cursor_id: *cursor_id,
+            });
+            events.push(Event::Insert {
+                position: range.start,
+                text: new.clone(),
+                cursor_id: *cursor_id,
+            });
+        }
+        if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, "Yank pop".to_string()) {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+
+        let inserted = spans
+            .into_iter()
+            .zip(replacements)
+            .map(|((cursor_id, _, _), text)| (cursor_id, text))
+            .collect();
+        self.active_window_mut().yank_state = Some(YankCycleState {
+            buffer_id,
+            index,
+            inserted,
+        });
+        self.set_status_message(
+            t!(
+                "kill_ring.yank_pop",
+                index = index + 1,
+                total = self.kill_ring.len()
+            )
+            .to_string(),
+        );
+    }
+
+    /// Open the kill ring picker: recent kills, newest first, filtered as
+    /// you type, with the selected entry previewed below the cursor.
+    pub(crate) fn start_kill_ring_prompt(&mut self) {
+        use crate::input::commands::Suggestion;
+        use crate::view::prompt::{Prompt, PromptType};
+
+        if self.kill_ring.is_empty() {
+            self.set_status_message(t!("kill_ring.empty").to_string());
+            return;
+        }
+
+        let suggestions: Vec<Suggestion> = self
+            .kill_ring
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let lines = normalize_line_endings(&entry.text()).lines().count().max(1);
+                Suggestion {
+                    description_spans: None,
+                    text: entry.excerpt(PICKER_EXCERPT_CHARS),
+                    description: Some(t!("kill_ring.lines", count = lines).to_string()),
+                    value: Some(index.to_string()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+
+        self.active_window_mut().prompt = Some(Prompt::with_suggestions(
+            t!("kill_ring.prompt").to_string(),
+            PromptType::KillRing,
+            suggestions,
+        ));
+        self.preview_kill_ring_selection();
+    }
+
+    /// Show the picker's selected entry as virtual lines below the primary
+    /// cursor, replacing the previous preview.
+    pub(crate) fn preview_kill_ring_selection(&mut self) {
+        self.clear_kill_ring_preview();
+
+        let index = self
+            .active_window()
+            .prompt
+            .as_ref()
+            .and_then(|p| p.suggestions.get(p.selected_suggestion?))
+            .and_then(|s| s.value.as_deref()?.parse::<usize>().ok());
+        let Some(entry) = index.and_then(|i| self.kill_ring.get(i)) else {
+            return;
+        };
+        let text = normalize_line_endings(&entry.text());
+        let lines: Vec<&str> = text.lines().collect();
+        let mut preview: Vec<String> = lines
+            .iter()
+            .take(PREVIEW_MAX_LINES)
+            .map(|line| format!("▎ {}", line))
+            .collect();
+        if lines.len() > PREVIEW_MAX_LINES {
+            preview.push(
+                t!(
+                    "kill_ring.more_lines",
+                    count = lines.len() - PREVIEW_MAX_LINES
+                )
+                .to_string(),
+            );
+        }
+
+        let position = self.active_cursors().primary().position;
+        let style = Style::default().add_modifier(Modifier::DIM | Modifier::ITALIC);
+        let namespace = VirtualTextNamespace::from_string(PREVIEW_NAMESPACE.to_string());
+        let state = self.active_state_mut();
+        for (priority, line) in preview.into_iter().enumerate() {
+            state.virtual_texts.add_line(
+                &mut state.marker_list,
+                position,
+                line,
+                style,
+                VirtualTextPosition::LineBelow,
+                namespace.clone(),
+                priority as i32,
+            );
+        }
+    }
+
+    /// Remove the kill ring picker's preview lines.
+    pub(crate) fn clear_kill_ring_preview(&mut self) {
+        let namespace = VirtualTextNamespace::from_string(PREVIEW_NAMESPACE.to_string());
+        let state = self.active_state_mut();
+        state
+            .virtual_texts
+            .clear_namespace(&mut state.marker_list, &namespace);
+    }
+
+    /// Paste kill ring entry `index`, as picked in the kill ring prompt.
+    /// The yank can be followed by yank-pop like any other.
+    pub(crate) fn yank_kill_ring_entry(&mut self, index: usize) {
+        if let Some(text) = self.kill_ring.get(index).map(|e| e.text()) {
+            self.paste_text(text);
+        }
+    }
+}
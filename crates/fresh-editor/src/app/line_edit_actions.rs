@@ -0,0 +1,355 @@
+//! Whole-line rewrites across files, behind the `applyLineEdits` plugin API.
+//!
+//! This is the write-back half of editable search results (the `live_grep`
+//! plugin's "Edit Results" buffer): each edit names a line and what it said
+//! when the results were captured. A line that says something else now is
+//! left alone and reported as a conflict. Files open in a buffer are edited
+//! in memory as one undoable step and left for the user to save; the rest
+//! are patched on disk through the active authority's `FileSystem`, copying
+//! the untouched spans with `write_patched` so a remote host only receives
+//! the changed lines.
+
+use super::Editor;
+use crate::model::event::{BufferId, CursorId, Event};
+use crate::model::filesystem::WriteOp;
+use fresh_core::api::{JsCallbackId, LineEdit, LineEditConflict, LineEditResult};
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::Path;
+
+/// A checked edit: replace `old`, the content bytes at `range`, with `text`.
+#[derive(Debug, PartialEq, Eq)]
+struct LineReplacement {
+    range: Range<usize>,
+    old: String,
+    text: String,
+}
+
+/// The bytes of `line` without its `\n` / `\r\n` terminator.
+fn line_content(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// Check every edit against the line it targets and plan the rewrites.
+///
+/// `line_at(n)` returns the start offset and bytes (terminator included) of
+/// 0-based line `n`. Edits that no longer match land in `conflicts`; edits
+/// that change nothing are dropped. Replacements come back in line order.
+fn plan_line_edits(
+    file: &str,
+    edits: &[LineEdit],
+    mut line_at: impl FnMut(usize) -> Option<(usize, Vec<u8>)>,
+    conflicts: &mut Vec<LineEditConflict>,
+) -> Vec<LineReplacement> {
+    // A line edited twice keeps the last edit.
+    let by_line: BTreeMap<u32, &LineEdit> = edits.iter().map(|e| (e.line, e)).collect();
+    let mut plan = Vec::new();
+    for (line, edit) in by_line {
+        let found = (line > 0)
+            .then(|| line_at(line as usize - 1))
+            .flatten()
+            .map(|(start, bytes)| {
+                let content = line_content(&bytes);
+                let actual = String::from_utf8_lossy(content).into_owned();
+                (start..start + content.len(), actual)
+            });
+        match found {
+            Some((range, actual)) if actual == edit.expected.trim_end_matches('\r') => {
+                if edit.text != actual {
+                    plan.push(LineReplacement {
+                        range,
+                        old: actual,
+                        text: edit.text.clone(),
+                    });
+                }
+            }
+            found => conflicts.push(LineEditConflict {
+                file: file.to_string(),
+                line,
+                actual: found.map(|(_, actual)| actual),
+            }),
+        }
+    }
+    plan
+}
+
+/// Byte offset of the start of every line in `bytes`.
+fn line_starts(bytes: &[u8]) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(
+            bytes
+                .iter()
+                .enumerate()
+                .filter(|&(i, &b)| b == b'\n' && i + 1 < bytes.len())
+                .map(|(i, _)| i + 1),
+        )
+        .filter(|_| !bytes.is_empty())
+        .collect()
+}
+
+/// The patch recipe that applies `plan` (in line order) to a file.
+fn patch_ops<'a>(plan: &'a [LineReplacement], file_len: usize) -> Vec<WriteOp<'a>> {
+    let mut ops = Vec::with_capacity(plan.len() * 2 + 1);
+    let mut copied = 0;
+    for replacement in plan {
+        if replacement.range.start > copied {
+            ops.push(WriteOp::Copy {
+                offset: copied as u64,
+                len: (replacement.range.start - copied) as u64,
+            });
+        }
+        ops.push(WriteOp::Insert {
+            data: replacement.text.as_bytes(),
+        });
+        copied = replacement.range.end;
+    }
+    if file_len > copied {
+        ops.push(WriteOp::Copy {
+            offset: copied as u64,
+            len: (file_len - copied) as u64,
+        });
+    }
+    ops
+}
+
+impl Editor {
+    /// Handle `ApplyLineEdits` from a plugin.
+    pub(super) fn handle_apply_line_edits(&mut self, edits: Vec<LineEdit>, request_id: u64) {
+        let mut by_file: BTreeMap<String, Vec<LineEdit>> = BTreeMap::new();
+        for edit in edits {
+            by_file.entry(edit.file.clone()).or_default().push(edit);
+        }
+
+        let mut result = LineEditResult::default();
+        for (file, edits) in by_file {
+            let path = Path::new(&file);
+            let path = if path.is_absolute() {
+                path.to_path_buf()
+            } else {
+                self.working_dir().join(path)
+            };
+            let open = self
+                .buffers()
+                .iter()
+                .find(|(_, state)| state.buffer.file_path() == Some(path.as_path()))
+                .map(|(&id, _)| id);
+            let applied = match open {
+                Some(buffer_id) => {
+                    self.apply_line_edits_to_buffer(buffer_id, &file, &edits, &mut result)
+                }
+                None => self.apply_line_edits_to_file(&path, &file, &edits, &mut result),
+            };
+            if applied > 0 {
+                result.applied += applied;
+                result.files += 1;
+            }
+        }
+
+        self.plugin_manager.read().unwrap().resolve_callback(
+            JsCallbackId::from(request_id),
+            serde_json::to_string(&result).unwrap_or_else(|_| "null".to_string()),
+        );
+    }
+
+    /// Rewrite lines of an open buffer as one undoable edit.
+    fn apply_line_edits_to_buffer(
+        &mut self,
+        buffer_id: BufferId,
+        file: &str,
+        edits: &[LineEdit],
+        result: &mut LineEditResult,
+    ) -> u32 {
+        let Some(state) = self.buffers().get(&buffer_id) else {
+            return 0;
+        };
+        let plan = plan_line_edits(
+            file,
+            edits,
+            |line| {
+                let start = state.buffer.line_start_offset(line)?;
+                Some((start, state.buffer.get_line(line)?))
+            },
+            &mut result.conflicts,
+        );
+        if plan.is_empty() {
+            return 0;
+        }
+
+        let mut events = Vec::with_capacity(plan.len() * 2);
+        for replacement in plan.iter().rev() {
+            if !replacement.range.is_empty() {
+                events.push(Event::Delete {
+                    range: replacement.range.clone(),
+                    deleted_text: replacement.old.clone(),
+                    cursor_id: CursorId(0),
+                });
+            }
+            if !replacement.text.is_empty() {
+                events.push(Event::Insert {
+                    position: replacement.range.start,
+                    text: replacement.text.clone(),
+                    cursor_id: CursorId(0),
+                });
+            }
+        }
+        let description = format!(
+            "Edit search results ({} line{})",
+            plan.len(),
+            if plan.len() == 1 { "" } else { "s" }
+        );
+        match self.apply_events_to_buffer_as_bulk_edit(buffer_id, events, description) {
+            Ok(()) => plan.len() as u32,
+            Err(e) => {
+                tracing::warn!("applyLineEdits: editing {} failed: {}", file, e);
+                0
+            }
+        }
+    }
+
+    /// Rewrite lines of a file that isn't open, patching it on disk.
+    fn apply_line_edits_to_file(
+        &mut self,
+        path: &Path,
+        file: &str,
+        edits: &[LineEdit],
+        result: &mut LineEditResult,
+    ) -> u32 {
+        let fs = self.authority().filesystem.clone();
+        let bytes = match fs.read_file(path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!("applyLineEdits: reading {} failed: {}", file, e);
+                result
+                    .conflicts
+                    .extend(edits.iter().map(|edit| LineEditConflict {
+                        file: file.to_string(),
+                        line: edit.line,
+                        actual: None,
+                    }));
+                return 0;
+            }
+        };
+        let starts = line_starts(&bytes);
+        let plan = plan_line_edits(
+            file,
+            edits,
+            |line| {
+                let start = *starts.get(line)?;
+                let end = starts.get(line + 1).copied().unwrap_or(bytes.len());
+                Some((start, bytes[start..end].to_vec()))
+            },
+            &mut result.conflicts,
+        );
+        if plan.is_empty() {
+            return 0;
+        }
+        match fs.write_patched(path, path, &patch_ops(&plan, bytes.len())) {
+            Ok(()) => plan.len() as u32,
+            Err(e) => {
+                tracing::warn!("applyLineEdits: writing {} failed: {}", file, e);
+                result
+                    .conflicts
+                    .extend(plan.iter().filter_map(|replacement| {
+                        let line = starts.partition_point(|&s| s <= replacement.range.start);
+                        Some(LineEditConflict {
+                            file: file.to_string(),
+                            line: u32::try_from(line).ok()?,
+                            actual: None,
+                        })
+                    }));
+                0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(line: u32, expected: &str, text: &str) -> LineEdit {
+        LineEdit {
+            file: "f".to_string(),
+            line,
+            expected: expected.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    fn plan(text: &str, edits: &[LineEdit]) -> (Vec<LineReplacement>, Vec<LineEditConflict>) {
+        let bytes = text.as_bytes();
+        let starts = line_starts(bytes);
+        let mut conflicts = Vec::new();
+        let plan = plan_line_edits(
+            "f",
+            edits,
+            |line| {
+                let start = *starts.get(line)?;
+                let end = starts.get(line + 1).copied().unwrap_or(bytes.len());
+                Some((start, bytes[start..end].to_vec()))
+            },
+            &mut conflicts,
+        );
+        (plan, conflicts)
+    }
+
+    fn patched(text: &str, plan: &[LineReplacement]) -> String {
+        let mut out = Vec::new();
+        for op in patch_ops(plan, text.len()) {
+            match op {
+                WriteOp::Copy { offset, len } => out
+                    .extend_from_slice(&text.as_bytes()[offset as usize..(offset + len) as usize]),
+                WriteOp::Insert { data } => out.extend_from_slice(data),
+            }
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn rewrites_matching_lines_and_keeps_line_endings() {
+        let text = "fn old() {}\r\nlet x = old();\nold\n";
+        let (plan, conflicts) = plan(
+            text,
+            &[
+                edit(1, "fn old() {}", "fn new() {}"),
+                edit(3, "old", "new"),
+                edit(2, "let x = old();", "let x = old();"),
+            ],
+        );
+        assert!(conflicts.is_empty());
+        assert_eq!(plan.len(), 2, "an unchanged line is not rewritten");
+        assert_eq!(patched(text, &plan), "fn new() {}\r\nlet x = old();\nnew\n");
+    }
+
+    #[test]
+    fn reports_lines_that_changed_since_the_search() {
+        let (plan, conflicts) = plan(
+            "a\nb\n",
+            &[edit(1, "x", "y"), edit(2, "b", "B"), edit(9, "c", "C")],
+        );
+        assert_eq!(plan.len(), 1);
+        assert_eq!(
+            conflicts,
+            vec![
+                LineEditConflict {
+                    file: "f".to_string(),
+                    line: 1,
+                    actual: Some("a".to_string()),
+                },
+                LineEditConflict {
+                    file: "f".to_string(),
+                    line: 9,
+                    actual: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn last_line_without_newline_can_be_rewritten() {
+        let text = "a\nb";
+        let (plan, _) = plan(text, &[edit(2, "b", "bee")]);
+        assert_eq!(patched(text, &plan), "a\nbee");
+    }
+}
@@ -0,0 +1,328 @@
+//! Linked editing.
+//!
+//! When the cursor rests in a range the language server links to others
+//! (`textDocument/linkedEditingRange` — an HTML/JSX opening tag name and its
+//! closing tag), a cursor is added at the same offset in each of the other
+//! ranges. Typing then goes through the usual multi-cursor path, so every
+//! keystroke edits all the ranges in one undo step. The extra cursors go
+//! away when the cursor leaves the range or a typed character can't be part
+//! of it.
+
+use std::ops::Range;
+
+use lsp_types::{LinkedEditingRanges, Position};
+use regex::Regex;
+
+use super::Editor;
+use crate::model::event::{BufferId, CursorId, Event};
+use crate::types::LspFeature;
+
+/// An in-flight `textDocument/linkedEditingRange`, with the cursor position
+/// it was asked for; the answer is dropped if the cursor has moved since.
+#[derive(Clone, Debug)]
+pub(crate) struct LinkedEditingRequest {
+    request_id: u64,
+    buffer_id: BufferId,
+    version: u64,
+    position: usize,
+}
+
+/// The cursors linked editing added to mirror the primary one.
+#[derive(Debug)]
+pub(crate) struct LinkedEditing {
+    buffer_id: BufferId,
+    cursor_ids: Vec<CursorId>,
+    /// What the ranges may contain, from the server; `None` means word
+    /// characters.
+    word_pattern: Option<Regex>,
+}
+
+/// Where the cursors mirroring `position` go: the same offset into every
+/// range but the one holding `position`. Empty when no range holds it.
+fn linked_positions(ranges: &[Range<usize>], position: usize) -> Vec<usize> {
+    let Some(current) = ranges
+        .iter()
+        .position(|r| r.start <= position && position <= r.end)
+    else {
+        return Vec::new();
+    };
+    let offset = position - ranges[current].start;
+    ranges
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != current)
+        .map(|(_, r)| (r.start + offset).min(r.end))
+        .collect()
+}
+
+/// Whether `c` may be typed into a linked range without breaking the link.
+fn fits_linked_range(word_pattern: Option<&Regex>, c: char) -> bool {
+    match word_pattern {
+        Some(pattern) => pattern.is_match(c.encode_utf8(&mut [0; 4])),
+        None => c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.' | '$'),
+    }
+}
+
+impl Editor {
+    /// Ask the language server which ranges are linked to the primary
+    /// cursor, when linked editing is on for the buffer's language and the
+    /// cursor moved or the buffer changed since the last ask.
+    pub(crate) fn maybe_request_linked_editing_ranges(&mut self) {
+        let buffer_id = self.active_buffer();
+        if self
+            .active_window()
+            .linked_editing
+            .as_ref()
+            .is_some_and(|session| session.buffer_id != buffer_id)
+        {
+            self.active_window_mut().linked_editing = None;
+        }
+        let Some(state) = self.active_window().buffers.get(&buffer_id) else {
+            return;
+        };
+        if !self
+            .config
+            .languages
+            .get(&state.language)
+            .is_some_and(|lang| lang.linked_editing)
+        {
+            self.end_linked_editing();
+            return;
+        }
+        let state = self.active_state();
+
+        // Only with a single cursor — or the primary plus the ones linked
+        // editing added itself.
+        let cursors = self.active_cursors();
+        let primary_id = cursors.primary_id();
+        let primary = cursors.primary();
+        if !primary.collapsed() {
+            return;
+        }
+        let linked = self
+            .active_window()
+            .linked_editing
+            .as_ref()
+            .map(|session| session.cursor_ids.as_slice())
+            .unwrap_or_default();
+        if cursors
+            .iter()
+            .any(|(id, _)| id != primary_id && !linked.contains(&id))
+        {
+            return;
+        }
+
+        let position = primary.position;
+        let version = state.buffer.version();
+        let key = (buffer_id, version, position);
+        let window = self.active_window();
+        if window.linked_editing_asked == Some(key) {
+            return;
+        }
+        let (line, character) = state.buffer.position_to_lsp_position(position);
+        let request_id = window.next_lsp_request_id;
+
+        let sent = self.with_lsp_for_buffer(
+            buffer_id,
+            LspFeature::LinkedEditingRange,
+            |handle, uri, _| {
+                handle.linked_editing_range(
+                    request_id,
+                    uri.as_uri().clone(),
+                    Position::new(line as u32, character as u32),
+                )
+            },
+        );
+        let window = self.active_window_mut();
+        window.linked_editing_asked = Some(key);
+        match sent {
+            Some(Ok(())) => {
+                window.next_lsp_request_id += 1;
+                window.pending_linked_editing_request = Some(LinkedEditingRequest {
+                    request_id,
+                    buffer_id,
+                    version,
+                    position,
+                });
+            }
+            Some(Err(e)) => tracing::debug!("Failed to request linked editing ranges: {}", e),
+            None => {}
+        }
+    }
+
+    pub(super) fn handle_lsp_linked_editing_ranges(
+        &mut self,
+        request_id: u64,
+        ranges: Result<Option<LinkedEditingRanges>, String>,
+    ) {
+        let window = self.active_window_mut();
+        let request = match window.pending_linked_editing_request.take() {
+            Some(request) if request.request_id == request_id => request,
+            other => {
+                window.pending_linked_editing_request = other;
+                return;
+            }
+        };
+        if self.active_buffer() != request.buffer_id
+            || self.active_state().buffer.version() != request.version
+            || self.active_cursors().primary().position != request.position
+        {
+            tracing::debug!(
+                "Dropping stale linked editing ranges (request {})",
+                request_id
+            );
+            return;
+        }
+        let ranges = ranges.unwrap_or_else(|e| {
+            tracing::debug!("Linked editing range request failed: {}", e);
+            None
+        });
+
+        let (targets, word_pattern) = match ranges {
+            Some(ranges) => {
+                let buffer = &self.active_state().buffer;
+                let byte = |p: &Position| {
+                    buffer.lsp_position_to_byte(p.line as usize, p.character as usize)
+                };
+                let byte_ranges: Vec<_> = ranges
+                    .ranges
+                    .iter()
+                    .map(|r| byte(&r.start)..byte(&r.end))
+                    .collect();
+                let word_pattern = ranges.word_pattern.as_deref().and_then(|pattern| {
+                    Regex::new(pattern)
+                        .map_err(|e| tracing::debug!("Bad linked editing word pattern: {}", e))
+                        .ok()
+                });
+                (
+                    linked_positions(&byte_ranges, request.position),
+                    word_pattern,
+                )
+            }
+            None => (Vec::new(), None),
+        };
+        self.set_linked_cursors(request.buffer_id, targets, word_pattern);
+    }
+
+    /// Drop the linked cursors before `c` is typed if it can't be part of
+    /// the linked ranges, so it only goes in at the primary cursor.
+    pub(super) fn break_linked_editing_for(&mut self, c: char) {
+        let Some(session) = self.active_window().linked_editing.as_ref() else {
+            return;
+        };
+        if !fits_linked_range(session.word_pattern.as_ref(), c) {
+            self.end_linked_editing();
+        }
+    }
+
+    /// Remove the cursors linked editing added.
+    pub(crate) fn end_linked_editing(&mut self) {
+        if let Some(session) = self.active_window().linked_editing.as_ref() {
+            self.set_linked_cursors(session.buffer_id, Vec::new(), None);
+        }
+    }
+
+    /// Replace the linked cursors with ones at `targets`, leaving them be
+    /// when they are already there.
+    fn set_linked_cursors(
+        &mut self,
+        buffer_id: BufferId,
+        mut targets: Vec<usize>,
+        word_pattern: Option<Regex>,
+    ) {
+        let cursors = self.active_cursors();
+        let existing: Vec<(CursorId, usize, Option<usize>)> = self
+            .active_window()
+            .linked_editing
+            .iter()
+            .flat_map(|session| &session.cursor_ids)
+            .filter_map(|&id| cursors.get(id).map(|c| (id, c.position, c.anchor)))
+            .collect();
+        let mut current: Vec<usize> = existing.iter().map(|&(_, position, _)| position).collect();
+        current.sort_unstable();
+        targets.sort_unstable();
+
+        let cursor_ids = if current == targets {
+            existing.iter().map(|&(id, _, _)| id).collect()
+        } else {
+            let next_free_id = cursors.iter().map(|(id, _)| id.0 + 1).max().unwrap_or(0);
+            let mut events: Vec<Event> = existing
+                .iter()
+                .map(|&(cursor_id, position, anchor)| Event::RemoveCursor {
+                    cursor_id,
+                    position,
+                    anchor,
+                })
+                .collect();
+            let cursor_ids: Vec<CursorId> = (0..targets.len())
+                .map(|i| CursorId(next_free_id + i))
+                .collect();
+            events.extend(
+                cursor_ids
+                    .iter()
+                    .zip(&targets)
+                    .map(|(&cursor_id, &position)| Event::AddCursor {
+                        cursor_id,
+                        position,
+                        anchor: None,
+                    }),
+            );
+            // An added cursor becomes the primary one; re-adding the cursor
+            // being typed at makes it primary again.
+            if !cursor_ids.is_empty() {
+                let primary_id = cursors.primary_id();
+                let primary = cursors.primary();
+                events.push(Event::RemoveCursor {
+                    cursor_id: primary_id,
+                    position: primary.position,
+                    anchor: primary.anchor,
+                });
+                events.push(Event::AddCursor {
+                    cursor_id: primary_id,
+                    position: primary.position,
+                    anchor: primary.anchor,
+                });
+            }
+            let batch = Event::Batch {
+                events,
+                description: "Linked editing".to_string(),
+            };
+            self.active_event_log_mut().append(batch.clone());
+            self.apply_event_to_active_buffer(&batch);
+            cursor_ids
+        };
+
+        self.active_window_mut().linked_editing = (!targets.is_empty()).then(|| LinkedEditing {
+            buffer_id,
+            cursor_ids,
+            word_pattern,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linked_positions_mirror_the_offset() {
+        // `<div></div>`: `div` at 1..4 and 7..10.
+        let ranges = [1..4, 7..10];
+        assert_eq!(linked_positions(&ranges, 2), vec![8]);
+        assert_eq!(linked_positions(&ranges, 4), vec![10]);
+        assert_eq!(linked_positions(&ranges, 7), vec![1]);
+        assert!(linked_positions(&ranges, 5).is_empty());
+    }
+
+    #[test]
+    fn typed_characters_must_fit_the_word_pattern() {
+        assert!(fits_linked_range(None, 'a'));
+        assert!(fits_linked_range(None, '-'));
+        assert!(!fits_linked_range(None, ' '));
+        assert!(!fits_linked_range(None, '>'));
+
+        let digits = Regex::new("[0-9]+").unwrap();
+        assert!(fits_linked_range(Some(&digits), '7'));
+        assert!(!fits_linked_range(Some(&digits), 'a'));
+    }
+}
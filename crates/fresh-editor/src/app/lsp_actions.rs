@@ -1101,6 +1101,9 @@ impl Editor {
         self.active_window_mut()
             .pending_inlay_hints_requests
             .retain(|_, req| req.buffer_id != buffer_id);
+        self.active_window_mut().clear_code_lenses(buffer_id);
+        self.active_window_mut().clear_document_links(buffer_id);
+        self.active_window_mut().clear_document_colors(buffer_id);
 
         // Clear all LSP-related overlays for this buffer (diagnostics + inlay hints)
         let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
@@ -1237,12 +1240,15 @@ impl Editor {
             }
         }
 
-        // Schedule folding range refresh
+        // Schedule folding range, code lens, link and colour refresh
         let _ = __next_id;
         let _ = lsp;
         let _ = handle;
-        self.active_window_mut()
-            .schedule_folding_ranges_refresh(buffer_id);
+        let window = self.active_window_mut();
+        window.schedule_folding_ranges_refresh(buffer_id);
+        window.schedule_code_lens_refresh(buffer_id);
+        window.schedule_document_link_refresh(buffer_id);
+        window.schedule_document_color_refresh(buffer_id);
     }
 
     /// Set up a plugin development workspace for LSP support on a buffer.
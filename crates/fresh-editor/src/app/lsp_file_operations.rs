@@ -0,0 +1,767 @@
+//! Language-server file operations for the file explorer.
+//!
+//! Before the explorer renames, moves, creates or deletes files it asks the
+//! servers that registered for the operation (`workspace/will*Files`) for a
+//! `WorkspaceEdit` — typically the import paths across the project that
+//! point at the files being moved. The operation waits for every answer
+//! (each request has its own short timeout), the edits are shown in a review
+//! popup, and only then does anything touch the disk: the edits first, as
+//! one undo step per buffer, then the filesystem operation, then the
+//! `workspace/did*Files` notification.
+//!
+//! With no interested server the operation runs straight away, exactly as
+//! before.
+//!
+//! The review popup is global, so there is one pending operation for the
+//! editor. It remembers the window it came from: the answers and the review
+//! are seen through even when another window is active by then.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result as AnyhowResult;
+use lsp_types::{
+    CreateFilesParams, DeleteFilesParams, DocumentChangeOperation, DocumentChanges, FileCreate,
+    FileDelete, FileRename, RenameFilesParams, TextEdit, WorkspaceEdit,
+};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::app::types::LspUri;
+use crate::services::lsp::file_operations::{FileOperation, FileOperationPhase};
+use crate::view::popup::PopupResolver;
+use fresh_core::WindowId;
+
+/// Preview rows shown in the review popup before the rest is summarized.
+const MAX_PREVIEW_ROWS: usize = 40;
+
+/// An explorer operation, as confirmed by the user.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ExplorerFileAction {
+    /// Rename in place; for `is_new_file` this names a file or folder the
+    /// explorer just created, so servers hear about a create.
+    Rename {
+        original_path: PathBuf,
+        original_name: String,
+        new_name: String,
+        is_new_file: bool,
+    },
+    /// Move to the trash.
+    Delete { paths: Vec<(PathBuf, bool)> },
+    /// Paste a single cut (move) or copied item.
+    Paste {
+        src: PathBuf,
+        dst: PathBuf,
+        is_cut: bool,
+    },
+    /// Paste several items once every conflict has been resolved.
+    MultiPaste {
+        safe: Vec<(PathBuf, PathBuf)>,
+        to_overwrite: Vec<(PathBuf, PathBuf)>,
+        is_cut: bool,
+    },
+}
+
+impl ExplorerFileAction {
+    fn new_path(original_path: &Path, new_name: &str) -> PathBuf {
+        original_path
+            .parent()
+            .map(|p| p.join(new_name))
+            .unwrap_or_else(|| original_path.to_path_buf())
+    }
+
+    /// `(from, to)` for every item a rename, move or copy touches; `to`
+    /// alone for deletes.
+    fn moves(&self) -> Vec<(Option<PathBuf>, PathBuf)> {
+        match self {
+            ExplorerFileAction::Rename {
+                original_path,
+                new_name,
+                ..
+            } => vec![(
+                Some(original_path.clone()),
+                Self::new_path(original_path, new_name),
+            )],
+            ExplorerFileAction::Delete { paths } => {
+                paths.iter().map(|(p, _)| (None, p.clone())).collect()
+            }
+            ExplorerFileAction::Paste { src, dst, .. } => vec![(Some(src.clone()), dst.clone())],
+            ExplorerFileAction::MultiPaste {
+                safe, to_overwrite, ..
+            } => safe
+                .iter()
+                .chain(to_overwrite)
+                .map(|(src, dst)| (Some(src.clone()), dst.clone()))
+                .collect(),
+        }
+    }
+
+    /// Whether servers see this action as files being created: naming a
+    /// freshly created entry, or pasting a copy.
+    fn creates(&self) -> bool {
+        match self {
+            ExplorerFileAction::Rename { is_new_file, .. } => *is_new_file,
+            ExplorerFileAction::Delete { .. } => false,
+            ExplorerFileAction::Paste { is_cut, .. }
+            | ExplorerFileAction::MultiPaste { is_cut, .. } => !is_cut,
+        }
+    }
+}
+
+/// An explorer operation waiting on `workspace/will*Files` answers, or —
+/// once `awaiting` is empty — on the user to review the edits.
+#[derive(Debug)]
+pub(crate) struct PendingFileOperation {
+    /// Window whose explorer started the operation.
+    window: WindowId,
+    action: ExplorerFileAction,
+    awaiting: HashSet<u64>,
+    edits: Vec<WorkspaceEdit>,
+}
+
+/// The text edits of several `WorkspaceEdit`s grouped by file, in file
+/// order, plus any resource operations they carry.
+#[derive(Debug, Default)]
+struct MergedEdits {
+    files: BTreeMap<LspUriKey, (LspUri, Vec<TextEdit>)>,
+    resource_ops: Vec<lsp_types::ResourceOp>,
+}
+
+/// Orders and dedups files by their URI string.
+type LspUriKey = String;
+
+impl MergedEdits {
+    fn new(edits: Vec<WorkspaceEdit>) -> Self {
+        let mut merged = Self::default();
+        for edit in edits {
+            for (uri, edits) in edit.changes.into_iter().flatten() {
+                merged.add(uri, edits);
+            }
+            match edit.document_changes {
+                Some(DocumentChanges::Edits(edits)) => {
+                    for doc in edits {
+                        merged.add(doc.text_document.uri, plain_edits(doc.edits));
+                    }
+                }
+                Some(DocumentChanges::Operations(ops)) => {
+                    for op in ops {
+                        match op {
+                            DocumentChangeOperation::Edit(doc) => {
+                                merged.add(doc.text_document.uri, plain_edits(doc.edits));
+                            }
+                            DocumentChangeOperation::Op(op) => merged.resource_ops.push(op),
+                        }
+                    }
+                }
+                None => {}
+            }
+        }
+        merged.files.retain(|_, (_, edits)| !edits.is_empty());
+        merged
+    }
+
+    fn add(&mut self, uri: lsp_types::Uri, edits: Vec<TextEdit>) {
+        self.files
+            .entry(uri.as_str().to_string())
+            .or_insert_with(|| (LspUri::from_wire(uri), Vec::new()))
+            .1
+            .extend(edits);
+    }
+
+    fn change_count(&self) -> usize {
+        self.files
+            .values()
+            .map(|(_, edits)| edits.len())
+            .sum::<usize>()
+            + self.resource_ops.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.change_count() == 0
+    }
+}
+
+fn plain_edits(
+    edits: Vec<lsp_types::OneOf<TextEdit, lsp_types::AnnotatedTextEdit>>,
+) -> Vec<TextEdit> {
+    edits
+        .into_iter()
+        .map(|edit| match edit {
+            lsp_types::OneOf::Left(edit) => edit,
+            lsp_types::OneOf::Right(annotated) => annotated.text_edit,
+        })
+        .collect()
+}
+
+/// `line` (one line of a file, without its line break) after the edits that
+/// start on line `line_number`. Edits spanning lines keep only what they
+/// insert up to the first line break.
+fn preview_line(line: &str, line_number: u32, edits: &[&TextEdit]) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut edits: Vec<&TextEdit> = edits
+        .iter()
+        .copied()
+        .filter(|e| e.range.start.line == line_number)
+        .collect();
+    edits.sort_by_key(|e| std::cmp::Reverse(e.range.start.character));
+    let mut out = chars.clone();
+    for edit in edits {
+        let start = (edit.range.start.character as usize).min(chars.len());
+        let end = if edit.range.end.line == line_number {
+            (edit.range.end.character as usize).clamp(start, chars.len())
+        } else {
+            chars.len()
+        };
+        let new_text = edit.new_text.split('\n').next().unwrap_or_default();
+        out.splice(start..end.min(out.len()), new_text.chars());
+    }
+    out.into_iter().collect()
+}
+
+impl Editor {
+    /// Run a confirmed explorer operation, first asking the language servers
+    /// that registered for it for the edits that go with it.
+    pub(crate) fn run_explorer_file_action(&mut self, action: ExplorerFileAction) {
+        if self.pending_file_operation.is_some() {
+            self.set_status_message(t!("explorer.file_operation_busy").to_string());
+            return;
+        }
+        let operation = self.file_operation_for(&action);
+        let mut request_id = self.active_window().next_lsp_request_id;
+        let mut awaiting = HashSet::new();
+        if let Some(operation) = operation.filter(|op| !op.is_empty()) {
+            let paths = self.file_operation_paths(&action);
+            let paths: Vec<(&Path, bool)> = paths.iter().map(|(p, d)| (p.as_path(), *d)).collect();
+            if let Some(lsp) = self.lsp() {
+                for server in
+                    lsp.handles_for_file_operation(&operation, FileOperationPhase::Will, &paths)
+                {
+                    match server
+                        .handle
+                        .will_file_operation(request_id, operation.clone())
+                    {
+                        Ok(()) => {
+                            awaiting.insert(request_id);
+                            request_id += 1;
+                        }
+                        Err(e) => tracing::debug!(
+                            "Failed to send {} to {}: {}",
+                            operation.method(FileOperationPhase::Will),
+                            server.name,
+                            e
+                        ),
+                    }
+                }
+            }
+        }
+
+        if awaiting.is_empty() {
+            self.perform_explorer_file_action(action);
+            return;
+        }
+        self.active_window_mut().next_lsp_request_id = request_id;
+        self.pending_file_operation = Some(PendingFileOperation {
+            window: self.active_window,
+            action,
+            awaiting,
+            edits: Vec::new(),
+        });
+        self.set_status_message(t!("explorer.file_operation_waiting").to_string());
+    }
+
+    pub(super) fn handle_lsp_will_file_operation(
+        &mut self,
+        request_id: u64,
+        edit: Result<Option<WorkspaceEdit>, String>,
+    ) {
+        let Some(pending) = self
+            .pending_file_operation
+            .as_mut()
+            .filter(|p| p.awaiting.contains(&request_id))
+        else {
+            return;
+        };
+        pending.awaiting.remove(&request_id);
+        match edit {
+            Ok(Some(edit)) => pending.edits.push(edit),
+            Ok(None) => {}
+            // A server that times out or fails just doesn't get a say.
+            Err(e) => tracing::warn!("File operation request {} failed: {}", request_id, e),
+        }
+        if !pending.awaiting.is_empty() {
+            return;
+        }
+
+        let Some(pending) = self.pending_file_operation.take() else {
+            return;
+        };
+        let merged = MergedEdits::new(pending.edits);
+        if merged.is_empty() {
+            let action = pending.action;
+            self.in_window(pending.window, |editor| {
+                editor.perform_explorer_file_action(action)
+            });
+            return;
+        }
+        self.show_file_operation_review(&merged);
+        self.pending_file_operation = Some(PendingFileOperation {
+            window: pending.window,
+            action: pending.action,
+            awaiting: HashSet::new(),
+            edits: vec![WorkspaceEdit {
+                changes: Some(
+                    merged
+                        .files
+                        .into_values()
+                        .map(|(uri, edits)| (uri.into_inner(), edits))
+                        .collect(),
+                ),
+                document_changes: (!merged.resource_ops.is_empty()).then(|| {
+                    DocumentChanges::Operations(
+                        merged
+                            .resource_ops
+                            .into_iter()
+                            .map(DocumentChangeOperation::Op)
+                            .collect(),
+                    )
+                }),
+                change_annotations: None,
+            }],
+        });
+    }
+
+    /// Dispatch the choice made in the review popup: `"apply"` the edits and
+    /// go ahead, `"skip"` them and go ahead, or `"cancel"` the operation.
+    pub(crate) fn handle_file_operation_review_action(&mut self, action_key: &str) {
+        let Some(pending) = self.pending_file_operation.take() else {
+            return;
+        };
+        let action = pending.action;
+        let ran = match action_key {
+            "apply" => self.in_window(pending.window, |editor| {
+                let key_context = editor.active_window().key_context.clone();
+                let merged = MergedEdits::new(pending.edits);
+                let applied = editor.apply_file_operation_edits(merged);
+                editor.active_window_mut().key_context = key_context;
+                match applied {
+                    Ok(()) => editor.perform_explorer_file_action(action),
+                    Err(e) => editor.set_status_message(
+                        t!("explorer.file_operation_edit_failed", error = e.to_string())
+                            .to_string(),
+                    ),
+                }
+            }),
+            "skip" => self.in_window(pending.window, |editor| {
+                editor.perform_explorer_file_action(action)
+            }),
+            _ => false,
+        };
+        if !ran {
+            self.set_status_message(t!("explorer.file_operation_cancelled").to_string());
+        }
+    }
+
+    /// Cancel the operation under review once its popup is gone without an
+    /// answer (dismissed along with other menus), so the explorer is not
+    /// left busy for good.
+    pub(crate) fn drop_orphaned_file_operation(&mut self) {
+        let in_review = self
+            .pending_file_operation
+            .as_ref()
+            .is_some_and(|pending| pending.awaiting.is_empty());
+        if !in_review {
+            return;
+        }
+        let shown = self
+            .global_popups
+            .all()
+            .iter()
+            .any(|popup| matches!(popup.resolver, PopupResolver::FileOperationReview));
+        if !shown {
+            self.pending_file_operation = None;
+            self.set_status_message(t!("explorer.file_operation_cancelled").to_string());
+        }
+    }
+
+    /// Run `f` with `window` as the active window: the explorer operation
+    /// belongs to it, whichever window is active when it finishes. Returns
+    /// `false` without running `f` when the window has been closed.
+    fn in_window(&mut self, window: WindowId, f: impl FnOnce(&mut Self)) -> bool {
+        if !self.windows.contains_key(&window) {
+            return false;
+        }
+        let previous = std::mem::replace(&mut self.active_window, window);
+        f(self);
+        if self.windows.contains_key(&previous) {
+            self.active_window = previous;
+        }
+        true
+    }
+
+    /// Do `action` on disk, then tell the servers about what went through.
+    fn perform_explorer_file_action(&mut self, action: ExplorerFileAction) {
+        let done = match action {
+            ExplorerFileAction::Rename {
+                original_path,
+                original_name,
+                new_name,
+                is_new_file,
+            } => self
+                .finish_file_explorer_rename(
+                    original_path.clone(),
+                    original_name.clone(),
+                    new_name.clone(),
+                    is_new_file,
+                )
+                .then_some(ExplorerFileAction::Rename {
+                    original_path,
+                    original_name,
+                    new_name,
+                    is_new_file,
+                }),
+            ExplorerFileAction::Delete { paths } => {
+                let deleted: Vec<(PathBuf, bool)> = paths
+                    .into_iter()
+                    .filter(|(path, is_dir)| {
+                        self.finish_file_explorer_delete(path.clone(), *is_dir)
+                    })
+                    .collect();
+                (!deleted.is_empty()).then_some(ExplorerFileAction::Delete { paths: deleted })
+            }
+            ExplorerFileAction::Paste { src, dst, is_cut } => self
+                .finish_file_explorer_paste(src.clone(), dst.clone(), is_cut)
+                .then_some(ExplorerFileAction::Paste { src, dst, is_cut }),
+            ExplorerFileAction::MultiPaste {
+                safe,
+                to_overwrite,
+                is_cut,
+            } => {
+                let pasted = self.finish_resolved_multi_paste(safe, to_overwrite, is_cut);
+                (!pasted.is_empty()).then_some(ExplorerFileAction::MultiPaste {
+                    safe: pasted,
+                    to_overwrite: Vec::new(),
+                    is_cut,
+                })
+            }
+        };
+        let Some(done) = done else {
+            return;
+        };
+        let Some(operation) = self.file_operation_for(&done).filter(|op| !op.is_empty()) else {
+            return;
+        };
+        let paths = self.file_operation_paths(&done);
+        let paths: Vec<(&Path, bool)> = paths.iter().map(|(p, d)| (p.as_path(), *d)).collect();
+        if let Some(lsp) = self.lsp() {
+            for server in
+                lsp.handles_for_file_operation(&operation, FileOperationPhase::Did, &paths)
+            {
+                if let Err(e) = server.handle.did_file_operation(operation.clone()) {
+                    tracing::debug!(
+                        "Failed to send {} to {}: {}",
+                        operation.method(FileOperationPhase::Did),
+                        server.name,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    /// The protocol batch for `action`. `None` if a path can't be sent
+    /// (not absolute).
+    fn file_operation_for(&self, action: &ExplorerFileAction) -> Option<FileOperation> {
+        let translation = self.authority().path_translation.as_ref();
+        let uri = |path: &Path| {
+            LspUri::from_host_path(path, translation).map(|uri| uri.as_str().to_string())
+        };
+        let moves = action.moves();
+        let operation = match action {
+            ExplorerFileAction::Delete { .. } => FileOperation::Delete(DeleteFilesParams {
+                files: moves
+                    .iter()
+                    .map(|(_, path)| uri(path).map(|uri| FileDelete { uri }))
+                    .collect::<Option<_>>()?,
+            }),
+            _ if action.creates() => FileOperation::Create(CreateFilesParams {
+                files: moves
+                    .iter()
+                    .map(|(_, path)| uri(path).map(|uri| FileCreate { uri }))
+                    .collect::<Option<_>>()?,
+            }),
+            _ => FileOperation::Rename(RenameFilesParams {
+                files: moves
+                    .iter()
+                    .map(|(from, to)| {
+                        Some(FileRename {
+                            old_uri: uri(from.as_deref()?)?,
+                            new_uri: uri(to)?,
+                        })
+                    })
+                    .collect::<Option<_>>()?,
+            }),
+        };
+        Some(operation)
+    }
+
+    /// The host paths servers' filters are matched against — the source of
+    /// a rename or move, the target of a create or delete — and whether
+    /// each is a directory.
+    fn file_operation_paths(&self, action: &ExplorerFileAction) -> Vec<(PathBuf, bool)> {
+        if let ExplorerFileAction::Delete { paths } = action {
+            return paths.clone();
+        }
+        let fs = &self.authority().filesystem;
+        let creates = action.creates();
+        action
+            .moves()
+            .into_iter()
+            .map(|(from, to)| {
+                let is_dir = [from.as_deref(), Some(to.as_path())]
+                    .into_iter()
+                    .flatten()
+                    .any(|p| fs.is_dir(p).unwrap_or(false));
+                let path = match from {
+                    Some(from) if !creates => from,
+                    _ => to,
+                };
+                (path, is_dir)
+            })
+            .collect()
+    }
+
+    /// Show the edits servers want to make alongside an explorer operation.
+    fn show_file_operation_review(&mut self, merged: &MergedEdits) {
+        use crate::view::popup::{Popup, PopupContent, PopupKind, PopupListItem, PopupPosition};
+        use ratatui::style::Style;
+
+        let mut items = vec![
+            PopupListItem::new(t!("explorer.file_operation_review.apply").to_string())
+                .with_data("apply".to_string()),
+            PopupListItem::new(t!("explorer.file_operation_review.skip").to_string())
+                .with_data("skip".to_string()),
+            PopupListItem::new(t!("explorer.file_operation_review.cancel").to_string())
+                .with_data("cancel".to_string()),
+            PopupListItem::new(String::new()).disabled(),
+        ];
+        let rows = self.file_operation_preview_rows(merged);
+        let hidden = rows.len().saturating_sub(MAX_PREVIEW_ROWS);
+        items.extend(
+            rows.into_iter()
+                .take(MAX_PREVIEW_ROWS)
+                .map(|row| PopupListItem::new(row).disabled()),
+        );
+        if hidden > 0 {
+            items.push(
+                PopupListItem::new(
+                    t!("explorer.file_operation_review.more", count = hidden).to_string(),
+                )
+                .disabled(),
+            );
+        }
+
+        let popup_width = (items
+            .iter()
+            .map(|i| unicode_width::UnicodeWidthStr::width(i.text.as_str()))
+            .max()
+            .unwrap_or(40)
+            + 4) as u16;
+        let (popup_bg, popup_border_fg) = {
+            let theme = self.theme();
+            (theme.popup_bg, theme.popup_border_fg)
+        };
+        let popup = Popup {
+            kind: PopupKind::List,
+            title: Some(t!("explorer.file_operation_review.title").to_string()),
+            description: Some(
+                t!(
+                    "explorer.file_operation_review.description",
+                    count = merged.change_count(),
+                    files = merged.files.len()
+                )
+                .to_string(),
+            ),
+            transient: false,
+            content: PopupContent::List { items, selected: 0 },
+            position: PopupPosition::Centered,
+            width: popup_width.clamp(50, 100),
+            max_height: 20,
+            bordered: true,
+            border_style: Style::default().fg(popup_border_fg),
+            background_style: Style::default().bg(popup_bg),
+            scroll_offset: 0,
+            text_selection: None,
+            accept_key_hint: None,
+            resolver: PopupResolver::FileOperationReview,
+            focused: true,
+            focus_key_hint: None,
+        };
+        self.global_popups.show(popup);
+    }
+
+    /// One header row per file, then one row per edited line showing the
+    /// line as it will read afterwards.
+    fn file_operation_preview_rows(&self, merged: &MergedEdits) -> Vec<String> {
+        let translation = self.authority().path_translation.as_ref();
+        let working_dir = self.working_dir().to_path_buf();
+        let mut rows = Vec::new();
+        for (uri, edits) in merged.files.values() {
+            let path = uri.to_host_path(translation);
+            let name = path
+                .as_deref()
+                .map(|p| p.strip_prefix(&working_dir).unwrap_or(p))
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_else(|| uri.as_str().to_string());
+            rows.push(
+                t!(
+                    "explorer.file_operation_review.file",
+                    name = &name,
+                    count = edits.len()
+                )
+                .to_string(),
+            );
+            let lines = path
+                .as_deref()
+                .map(|p| self.file_lines(p))
+                .unwrap_or_default();
+            let edits: Vec<&TextEdit> = edits.iter().collect();
+            let mut line_numbers: Vec<u32> = edits.iter().map(|e| e.range.start.line).collect();
+            line_numbers.sort_unstable();
+            line_numbers.dedup();
+            for line_number in line_numbers {
+                let line = lines
+                    .get(line_number as usize)
+                    .map(String::as_str)
+                    .unwrap_or_default();
+                rows.push(format!(
+                    "  {:>4}: {}",
+                    line_number + 1,
+                    preview_line(line, line_number, &edits).trim_end()
+                ));
+            }
+        }
+        rows
+    }
+
+    /// The lines of `path`, from its open buffer if there is one (it may
+    /// have unsaved edits), from disk otherwise.
+    fn file_lines(&self, path: &Path) -> Vec<String> {
+        let open = self
+            .buffer_ids_under_path(path)
+            .into_iter()
+            .filter_map(|id| self.buffers().get(&id))
+            .find(|state| state.buffer.file_path() == Some(path))
+            .and_then(|state| state.buffer.to_string());
+        let text = open.or_else(|| {
+            self.authority()
+                .filesystem
+                .read_file(path)
+                .ok()
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        });
+        text.map(|text| {
+            text.lines()
+                .map(|line| line.trim_end_matches('\r').to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+    }
+
+    /// Apply reviewed edits: every file is opened before anything changes,
+    /// so a file that can't be opened leaves all of them untouched, and
+    /// each buffer gets its edits as a single undo step.
+    fn apply_file_operation_edits(&mut self, merged: MergedEdits) -> AnyhowResult<()> {
+        let original_active = self.active_buffer();
+        let translation = self.authority().path_translation.clone();
+        let mut targets = Vec::with_capacity(merged.files.len());
+        for (uri, edits) in merged.files.into_values() {
+            let path = super::lsp_uri_to_host_path(&uri, translation.as_ref())
+                .map_err(anyhow::Error::msg)?;
+            let buffer_id = self.open_file(&path)?;
+            targets.push((buffer_id, edits));
+        }
+        for op in merged.resource_ops {
+            self.apply_resource_operation(op)?;
+        }
+        for (buffer_id, edits) in targets {
+            self.apply_lsp_text_edits(buffer_id, edits)?;
+        }
+        // Opening the files moved focus; go back to where the user was.
+        if original_active != self.active_buffer() && self.buffers().get(&original_active).is_some()
+        {
+            self.set_active_buffer(original_active);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{Position, Range};
+
+    fn edit(line: u32, start: u32, end: u32, new_text: &str) -> TextEdit {
+        TextEdit {
+            range: Range::new(Position::new(line, start), Position::new(line, end)),
+            new_text: new_text.to_string(),
+        }
+    }
+
+    #[test]
+    fn preview_applies_the_edits_on_the_line() {
+        let line = "import { a } from './old';";
+        let rename = edit(3, 19, 24, "./new/path");
+        assert_eq!(
+            preview_line(line, 3, &[&rename]),
+            "import { a } from './new/path';"
+        );
+        // Edits on other lines leave it alone.
+        assert_eq!(preview_line(line, 4, &[&rename]), line);
+
+        let both = [&edit(0, 0, 1, "B"), &edit(0, 2, 3, "D")];
+        assert_eq!(preview_line("abcd", 0, &both), "BbDd");
+    }
+
+    #[test]
+    fn merged_edits_group_by_file() {
+        let uri: lsp_types::Uri = "file:///p/a.ts".parse().unwrap();
+        let first = WorkspaceEdit {
+            changes: Some([(uri.clone(), vec![edit(0, 0, 1, "x")])].into()),
+            ..Default::default()
+        };
+        let second = WorkspaceEdit {
+            document_changes: Some(DocumentChanges::Edits(vec![lsp_types::TextDocumentEdit {
+                text_document: lsp_types::OptionalVersionedTextDocumentIdentifier {
+                    uri,
+                    version: None,
+                },
+                edits: vec![lsp_types::OneOf::Left(edit(1, 0, 1, "y"))],
+            }])),
+            ..Default::default()
+        };
+        let merged = MergedEdits::new(vec![first, second]);
+        assert_eq!(merged.files.len(), 1);
+        assert_eq!(merged.change_count(), 2);
+        assert!(MergedEdits::new(vec![WorkspaceEdit::default()]).is_empty());
+    }
+
+    #[test]
+    fn pasting_a_copy_is_a_create_and_a_cut_is_a_rename() {
+        let copy = ExplorerFileAction::Paste {
+            src: PathBuf::from("/p/a.ts"),
+            dst: PathBuf::from("/p/lib/a.ts"),
+            is_cut: false,
+        };
+        assert!(copy.creates());
+        let cut = ExplorerFileAction::Paste {
+            src: PathBuf::from("/p/a.ts"),
+            dst: PathBuf::from("/p/lib/a.ts"),
+            is_cut: true,
+        };
+        assert!(!cut.creates());
+        assert_eq!(
+            cut.moves(),
+            vec![(Some(PathBuf::from("/p/a.ts")), PathBuf::from("/p/lib/a.ts"))]
+        );
+    }
+}
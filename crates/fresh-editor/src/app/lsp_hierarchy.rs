@@ -0,0 +1,264 @@
+//! LSP call and type hierarchies.
+//!
+//! "Show Call Hierarchy" / "Show Type Hierarchy" prepare the hierarchy for
+//! the symbol under the cursor and hand the roots to plugins through the
+//! `lsp_hierarchy` hook; the bundled `lsp_hierarchy` plugin renders them as
+//! a lazily expanded tree. Plugins drive both protocol steps themselves via
+//! `editor.prepareHierarchy` / `editor.expandHierarchy`.
+
+use fresh_core::api::{HierarchyItem, JsCallbackId};
+use rust_i18n::t;
+use serde_json::Value;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::services::authority::PathTranslation;
+use crate::services::lsp::hierarchy::{
+    HierarchyDirection, HierarchyItemHeader, HierarchyKind, HierarchyRequest,
+};
+use crate::services::plugins::hooks::HookArgs;
+use crate::types::LspFeature;
+
+/// Who is waiting for a hierarchy response.
+#[derive(Debug)]
+pub(crate) enum HierarchyOrigin {
+    /// A palette command: report the roots through the `lsp_hierarchy` hook.
+    Command(HierarchyKind),
+    /// `prepareHierarchy` / `expandHierarchy`: settle the plugin's promise.
+    Plugin(JsCallbackId),
+}
+
+fn feature_for(kind: HierarchyKind) -> LspFeature {
+    match kind {
+        HierarchyKind::Call => LspFeature::CallHierarchy,
+        HierarchyKind::Type => LspFeature::TypeHierarchy,
+    }
+}
+
+/// Convert a raw server item into the plugin-facing shape, translating the
+/// URI to a host path. Items the editor cannot read are dropped.
+fn to_plugin_item(
+    raw: Value,
+    language: &str,
+    translation: Option<&PathTranslation>,
+) -> Option<HierarchyItem> {
+    let header = HierarchyItemHeader::from_item(&raw)?;
+    let file = if header.uri.scheme().map(|s| s.as_str()) == Some("file") {
+        crate::app::types::LspUri::from_wire(header.uri.clone())
+            .to_host_path(translation)
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_else(|| header.uri.path().as_str().to_string())
+    } else {
+        header.uri.as_str().to_string()
+    };
+    Some(HierarchyItem {
+        name: header.name,
+        kind: header.kind,
+        detail: header.detail,
+        file,
+        line: header.selection_range.start.line + 1,
+        column: header.selection_range.start.character + 1,
+        language: language.to_string(),
+        item: raw,
+    })
+}
+
+impl Editor {
+    /// Prepare a call or type hierarchy for the symbol at the cursor.
+    pub(crate) fn request_hierarchy(&mut self, kind: HierarchyKind) {
+        let buffer_id = self.active_buffer();
+        let position = self.active_cursors().primary().position;
+        if let Err(e) =
+            self.send_prepare_hierarchy(buffer_id, position, kind, HierarchyOrigin::Command(kind))
+        {
+            tracing::debug!("{} hierarchy not requested: {}", kind.as_str(), e);
+            self.set_status_message(t!("lsp.no_server_active").to_string());
+        }
+    }
+
+    fn send_prepare_hierarchy(
+        &mut self,
+        buffer_id: BufferId,
+        position: usize,
+        kind: HierarchyKind,
+        origin: HierarchyOrigin,
+    ) -> Result<(), String> {
+        let (line, character) = self
+            .buffers()
+            .get(&buffer_id)
+            .ok_or_else(|| t!("lsp.buffer_not_found").to_string())?
+            .buffer
+            .position_to_lsp_position(position);
+        let request_id = self.active_window().next_lsp_request_id;
+
+        let sent = self.with_lsp_for_buffer(buffer_id, feature_for(kind), |handle, uri, _| {
+            handle.hierarchy(
+                request_id,
+                HierarchyRequest::Prepare {
+                    kind,
+                    uri: uri.as_uri().clone(),
+                    line: line as u32,
+                    character: character as u32,
+                },
+            )
+        });
+        match sent {
+            Some(Ok(())) => {
+                let window = self.active_window_mut();
+                window.next_lsp_request_id += 1;
+                window.pending_hierarchy_requests.insert(request_id, origin);
+                Ok(())
+            }
+            Some(Err(e)) => Err(e),
+            None => Err(format!(
+                "No language server provides a {} hierarchy for this buffer",
+                kind.as_str()
+            )),
+        }
+    }
+
+    /// Handle `PrepareHierarchy` from a plugin.
+    #[cfg(feature = "plugins")]
+    pub(super) fn handle_prepare_hierarchy(
+        &mut self,
+        kind: String,
+        buffer_id: BufferId,
+        position: usize,
+        request_id: u64,
+    ) {
+        let callback_id = JsCallbackId::from(request_id);
+        let result = match HierarchyKind::parse(&kind) {
+            Some(kind) => self.send_prepare_hierarchy(
+                buffer_id,
+                position,
+                kind,
+                HierarchyOrigin::Plugin(callback_id),
+            ),
+            None => Err(format!("Unknown hierarchy kind '{}'", kind)),
+        };
+        if let Err(e) = result {
+            self.plugin_manager
+                .read()
+                .unwrap()
+                .reject_callback(callback_id, e);
+        }
+    }
+
+    /// Handle `ExpandHierarchy` from a plugin. The item goes back to the
+    /// server of the language that produced it.
+    #[cfg(feature = "plugins")]
+    pub(super) fn handle_expand_hierarchy(
+        &mut self,
+        direction: String,
+        item: Value,
+        request_id: u64,
+    ) {
+        let callback_id = JsCallbackId::from(request_id);
+        let result = self.send_expand_hierarchy(&direction, item, callback_id);
+        if let Err(e) = result {
+            self.plugin_manager
+                .read()
+                .unwrap()
+                .reject_callback(callback_id, e);
+        }
+    }
+
+    #[cfg(feature = "plugins")]
+    fn send_expand_hierarchy(
+        &mut self,
+        direction: &str,
+        item: Value,
+        callback_id: JsCallbackId,
+    ) -> Result<(), String> {
+        let direction = HierarchyDirection::parse(direction)
+            .ok_or_else(|| format!("Unknown hierarchy direction '{}'", direction))?;
+        let item: HierarchyItem =
+            serde_json::from_value(item).map_err(|e| format!("Invalid hierarchy item: {}", e))?;
+        let request_id = self.active_window().next_lsp_request_id;
+
+        let window = self.active_window_mut();
+        let handle = window
+            .lsp
+            .handle_for_feature_mut(&item.language, feature_for(direction.kind()))
+            .ok_or_else(|| format!("LSP server for '{}' is unavailable", item.language))?;
+        handle.handle.hierarchy(
+            request_id,
+            HierarchyRequest::Expand {
+                direction,
+                item: item.item,
+            },
+        )?;
+        window.next_lsp_request_id += 1;
+        window
+            .pending_hierarchy_requests
+            .insert(request_id, HierarchyOrigin::Plugin(callback_id));
+        Ok(())
+    }
+
+    /// Route a hierarchy response to whoever asked for it.
+    pub(super) fn handle_hierarchy_response(
+        &mut self,
+        request_id: u64,
+        language: &str,
+        result: Result<Vec<Value>, String>,
+    ) {
+        let Some(origin) = self
+            .active_window_mut()
+            .pending_hierarchy_requests
+            .remove(&request_id)
+        else {
+            tracing::debug!("Ignoring stale hierarchy response: {}", request_id);
+            return;
+        };
+
+        let translation = self.authority().path_translation.clone();
+        let items = result.map(|raw| {
+            raw.into_iter()
+                .filter_map(|item| to_plugin_item(item, language, translation.as_ref()))
+                .collect::<Vec<_>>()
+        });
+
+        match origin {
+            HierarchyOrigin::Plugin(callback_id) => {
+                let plugins = self.plugin_manager.read().unwrap();
+                match items {
+                    Ok(items) => plugins.resolve_callback(
+                        callback_id,
+                        serde_json::to_string(&items).unwrap_or_else(|_| "[]".to_string()),
+                    ),
+                    Err(e) => plugins.reject_callback(callback_id, e),
+                }
+            }
+            HierarchyOrigin::Command(kind) => {
+                let items = items.unwrap_or_else(|e| {
+                    tracing::warn!("{} hierarchy request failed: {}", kind.as_str(), e);
+                    Vec::new()
+                });
+                let Some(first) = items.first() else {
+                    self.set_status_message(match kind {
+                        HierarchyKind::Call => t!("lsp.no_call_hierarchy").to_string(),
+                        HierarchyKind::Type => t!("lsp.no_type_hierarchy").to_string(),
+                    });
+                    return;
+                };
+                let symbol = first.name.clone();
+                self.set_status_message(match kind {
+                    HierarchyKind::Call => {
+                        t!("lsp.found_call_hierarchy", symbol = &symbol).to_string()
+                    }
+                    HierarchyKind::Type => {
+                        t!("lsp.found_type_hierarchy", symbol = &symbol).to_string()
+                    }
+                });
+                self.plugin_manager.read().unwrap().run_hook(
+                    "lsp_hierarchy",
+                    HookArgs::LspHierarchy {
+                        kind: kind.as_str().to_string(),
+                        symbol,
+                        items,
+                    },
+                );
+            }
+        }
+    }
+}
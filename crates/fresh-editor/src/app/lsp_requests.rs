@@ -23,6 +23,7 @@ use crate::view::prompt::{Prompt, PromptType};
 use crate::services::lsp::async_handler::LspHandle;
 use crate::types::LspFeature;
 
+use super::spell_check::SPELLING_SOURCE;
 use super::{Editor, SemanticTokenRangeRequest};
 
 /// Ensure every line in a docstring is separated by a blank line.
@@ -820,6 +821,90 @@ impl Editor {
         }
     }
 
+    /// Ask the language server for on-type formatting edits when `c`, just
+    /// typed, is one of its trigger characters and `format_on_type` is on for
+    /// the buffer's language. Only with a single cursor: the edits are for
+    /// the position the character went in at.
+    pub(crate) fn maybe_format_on_type(&mut self, c: char) {
+        let buffer_id = self.active_buffer();
+        let state = self.active_state();
+        let language = state.language.clone();
+        let enabled = self
+            .config
+            .languages
+            .get(&language)
+            .is_some_and(|lang| lang.format_on_type);
+        if !enabled || self.active_cursors().count() != 1 {
+            return;
+        }
+        let is_trigger = self
+            .lsp()
+            .is_some_and(|lsp| lsp.is_on_type_formatting_trigger_char(c, &language));
+        if !is_trigger {
+            return;
+        }
+
+        let version = state.buffer.version();
+        let (line, character) = state
+            .buffer
+            .position_to_lsp_position(self.active_cursors().primary().position);
+        let position = lsp_types::Position::new(line as u32, character as u32);
+        let tab_size = state.buffer_settings.tab_size as u32;
+        let insert_spaces = !state.buffer_settings.use_tabs;
+        let request_id = self.active_window().next_lsp_request_id;
+
+        let sent =
+            self.with_lsp_for_buffer(buffer_id, LspFeature::OnTypeFormatting, |handle, uri, _| {
+                handle.on_type_formatting(
+                    request_id,
+                    uri.as_uri().clone(),
+                    position,
+                    c.to_string(),
+                    tab_size,
+                    insert_spaces,
+                )
+            });
+        match sent {
+            Some(Ok(())) => {
+                let window = self.active_window_mut();
+                window.next_lsp_request_id += 1;
+                window.pending_on_type_formatting_request = Some((request_id, buffer_id, version));
+            }
+            Some(Err(e)) => tracing::debug!("Failed to request on-type formatting: {}", e),
+            None => {}
+        }
+    }
+
+    /// Apply on-type formatting edits, unless the buffer changed since the
+    /// trigger character went in.
+    pub(crate) fn handle_lsp_on_type_formatting(
+        &mut self,
+        request_id: u64,
+        edits: Result<Vec<lsp_types::TextEdit>, String>,
+    ) {
+        let window = self.active_window_mut();
+        let Some((pending_id, buffer_id, version)) = window.pending_on_type_formatting_request
+        else {
+            return;
+        };
+        if pending_id != request_id {
+            return;
+        }
+        window.pending_on_type_formatting_request = None;
+        let current = window.buffers.get(&buffer_id).map(|s| s.buffer.version());
+        if current != Some(version) {
+            tracing::debug!("Dropping stale on-type formatting (request {})", request_id);
+            return;
+        }
+        let edits = edits.unwrap_or_else(|e| {
+            tracing::debug!("On-type formatting request failed: {}", e);
+            Vec::new()
+        });
+        if let Err(e) = self.apply_lsp_text_edits(buffer_id, edits) {
+            tracing::warn!("Failed to apply on-type formatting: {}", e);
+        }
+    }
+
     /// Request LSP go-to-definition at current cursor position
     pub(crate) fn request_goto_definition(&mut self) -> AnyhowResult<()> {
         // Get the current buffer and cursor position
@@ -1823,6 +1908,14 @@ impl Editor {
             .clear();
         self.active_window_mut().pending_code_actions = None;
 
+        // Spelling fixes for an underlined word need no server: list them
+        // right away; the servers' actions join them as they arrive.
+        let spelling = self.spelling_code_actions();
+        if !spelling.is_empty() {
+            self.active_window_mut().pending_code_actions = Some(spelling);
+            self.show_code_actions_popup();
+        }
+
         // Get the current buffer and cursor position
         let cursor_pos = self.active_cursors().primary().position;
         let selection_range = self.active_cursors().primary().selection_range();
@@ -1983,12 +2076,19 @@ impl Editor {
             }
         }
 
-        // Build list items from all accumulated code actions
+        self.show_code_actions_popup();
+    }
+
+    /// Show the accumulated `pending_code_actions` in the code actions
+    /// popup, replacing the popup an earlier batch opened.
+    fn show_code_actions_popup(&mut self) {
         use crate::view::popup::{Popup, PopupListItem, PopupPosition};
         use ratatui::style::Style;
 
         let items: Vec<PopupListItem> = {
-            let all_actions = self.active_window().pending_code_actions.as_ref().unwrap();
+            let Some(all_actions) = self.active_window().pending_code_actions.as_ref() else {
+                return;
+            };
             let multiple_servers = {
                 let mut names = std::collections::HashSet::new();
                 for (name, _) in all_actions {
@@ -2070,16 +2170,19 @@ impl Editor {
     /// Execute a code action by index from the stored pending_code_actions.
     pub(crate) fn execute_code_action(&mut self, index: usize) {
         let action = match &self.active_window_mut().pending_code_actions {
-            Some(actions) => actions.get(index).map(|(_, a)| a.clone()),
+            Some(actions) => actions.get(index).cloned(),
             None => None,
         };
 
-        let Some(action) = action else {
+        let Some((server_name, action)) = action else {
             tracing::warn!("Code action index {} out of range", index);
             return;
         };
 
         match action {
+            lsp_types::CodeActionOrCommand::Command(cmd) if server_name == SPELLING_SOURCE => {
+                self.execute_spelling_action(cmd);
+            }
             lsp_types::CodeActionOrCommand::CodeAction(ca) => {
                 // If the action has no edit and no command, it may need resolve first.
                 // Only resolve if the action has `data` and the server supports resolveProvider.
@@ -2717,7 +2820,10 @@ impl Editor {
     }
 
     /// Apply a resource operation (CreateFile, RenameFile, DeleteFile) from a workspace edit.
-    fn apply_resource_operation(&mut self, op: lsp_types::ResourceOp) -> AnyhowResult<()> {
+    pub(super) fn apply_resource_operation(
+        &mut self,
+        op: lsp_types::ResourceOp,
+    ) -> AnyhowResult<()> {
         // Each URI in a resource operation is wire-side and must be
         // translated back to the host before we touch the host
         // filesystem. Wrapping in [`LspUri`] and calling
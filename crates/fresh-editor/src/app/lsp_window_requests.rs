@@ -0,0 +1,282 @@
+//! Server-to-client `window/showMessageRequest` and `window/showDocument`.
+//!
+//! Both are requests the server waits on. A message request becomes a modal
+//! popup listing the server's actions; the chosen one (or `null` when the
+//! popup is dismissed) is the response. A show-document request opens a
+//! file in the editor, or — after the user agrees — an external program.
+//! The answer goes back over the oneshot the LSP task is awaiting. A reply
+//! that is dropped unanswered reads as a dismissal there, so a request whose
+//! popup disappears some other way is dropped to answer it.
+
+use std::collections::{HashMap, HashSet};
+
+use lsp_types::{MessageActionItem, MessageType, ShowDocumentParams, ShowMessageRequestParams};
+use rust_i18n::t;
+use tokio::sync::oneshot;
+
+use super::Editor;
+use crate::model::event::Event;
+use crate::view::popup::{
+    Popup, PopupContent, PopupKind, PopupListItem, PopupPosition, PopupResolver,
+};
+
+/// A server request waiting on the user's answer in a popup.
+pub(crate) enum PendingWindowRequest {
+    /// `window/showMessageRequest`: the popup's rows are `actions`.
+    Message {
+        actions: Vec<MessageActionItem>,
+        reply: oneshot::Sender<Option<MessageActionItem>>,
+    },
+    /// `window/showDocument` with `external`: the user is asked before the
+    /// URI is handed to another program.
+    OpenExternal {
+        url: String,
+        reply: oneshot::Sender<bool>,
+    },
+}
+
+/// Requests whose popups are open, by the id in their
+/// `PopupResolver::LspWindowRequest`.
+#[derive(Default)]
+pub(crate) struct PendingWindowRequests {
+    next_id: u64,
+    pending: HashMap<u64, PendingWindowRequest>,
+}
+
+impl PendingWindowRequests {
+    fn insert(&mut self, request: PendingWindowRequest) -> u64 {
+        self.next_id += 1;
+        self.pending.insert(self.next_id, request);
+        self.next_id
+    }
+
+    fn take(&mut self, id: u64) -> Option<PendingWindowRequest> {
+        self.pending.remove(&id)
+    }
+}
+
+impl Editor {
+    /// Show a server's message with its actions as choices.
+    pub(super) fn handle_lsp_show_message_request(
+        &mut self,
+        server_name: String,
+        params: ShowMessageRequestParams,
+        reply: oneshot::Sender<Option<MessageActionItem>>,
+    ) {
+        let actions = params.actions.unwrap_or_default();
+        let mut items: Vec<PopupListItem> = actions
+            .iter()
+            .enumerate()
+            .map(|(index, action)| {
+                PopupListItem::new(action.title.clone()).with_data(index.to_string())
+            })
+            .collect();
+        if items.is_empty() {
+            // Nothing to choose: acknowledging the message answers null.
+            items.push(PopupListItem::new(
+                t!("lsp.message_request_dismiss").to_string(),
+            ));
+        }
+        let title = match params.typ {
+            MessageType::ERROR => t!("lsp.message_request_error", server = &server_name),
+            MessageType::WARNING => t!("lsp.message_request_warning", server = &server_name),
+            _ => t!("lsp.message_request_info", server = &server_name),
+        }
+        .to_string();
+
+        let id = self
+            .pending_window_requests
+            .insert(PendingWindowRequest::Message { actions, reply });
+        self.show_window_request_popup(id, title, params.message, items);
+    }
+
+    /// Show a document a server asked for, answering whether it was shown.
+    #[allow(clippy::let_underscore_must_use)] // reply.send() is best-effort; the server may have exited
+    pub(super) fn handle_lsp_show_document(
+        &mut self,
+        server_name: String,
+        params: ShowDocumentParams,
+        reply: oneshot::Sender<bool>,
+    ) {
+        if params.external == Some(true) {
+            let url = params.uri.as_str().to_string();
+            let items = vec![
+                PopupListItem::new(t!("lsp.show_document_open").to_string())
+                    .with_data("open".to_string()),
+                PopupListItem::new(t!("lsp.show_document_cancel").to_string())
+                    .with_data("cancel".to_string()),
+            ];
+            let title = t!("lsp.show_document_title", server = &server_name).to_string();
+            let description = t!("lsp.show_document_external", url = &url).to_string();
+            let id = self
+                .pending_window_requests
+                .insert(PendingWindowRequest::OpenExternal { url, reply });
+            self.show_window_request_popup(id, title, description, items);
+            return;
+        }
+        if params.uri.scheme().is_none_or(|s| s.as_str() != "file") {
+            // Only files can be shown inside the editor.
+            self.set_status_message(
+                t!("lsp.show_document_unsupported", uri = params.uri.as_str()).to_string(),
+            );
+            let _ = reply.send(false);
+            return;
+        }
+        let shown = self.show_lsp_file(&params);
+        let _ = reply.send(shown);
+    }
+
+    /// Open the file of a `window/showDocument`, select its range, and hand
+    /// focus back when the server asked not to take it.
+    fn show_lsp_file(&mut self, params: &ShowDocumentParams) -> bool {
+        let previous = self.active_buffer();
+        let wire = crate::app::types::LspUri::from_wire(params.uri.clone());
+        let buffer_id = match self.open_lsp_uri_target(&wire) {
+            Ok(buffer_id) => buffer_id,
+            Err(e) => {
+                self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+                return false;
+            }
+        };
+        if let Some(range) = params.selection {
+            let (start, end) = {
+                let buffer = &self.active_state().buffer;
+                (
+                    buffer.lsp_position_to_byte(
+                        range.start.line as usize,
+                        range.start.character as usize,
+                    ),
+                    buffer.lsp_position_to_byte(
+                        range.end.line as usize,
+                        range.end.character as usize,
+                    ),
+                )
+            };
+            let cursors = self.active_cursors();
+            let primary = cursors.primary();
+            let event = Event::MoveCursor {
+                cursor_id: cursors.primary_id(),
+                old_position: primary.position,
+                new_position: end,
+                old_anchor: primary.anchor,
+                new_anchor: (start != end).then_some(start),
+                old_sticky_column: primary.sticky_column,
+                new_sticky_column: None,
+            };
+            self.apply_event_to_active_buffer(&event);
+            self.apply_event_to_active_buffer(&Event::Recenter);
+        }
+        if params.take_focus == Some(false) && previous != buffer_id {
+            self.set_active_buffer(previous);
+        }
+        true
+    }
+
+    fn show_window_request_popup(
+        &mut self,
+        id: u64,
+        title: String,
+        description: String,
+        items: Vec<PopupListItem>,
+    ) {
+        let width = items
+            .iter()
+            .map(|item| unicode_width::UnicodeWidthStr::width(item.text.as_str()))
+            .chain(
+                description
+                    .lines()
+                    .map(unicode_width::UnicodeWidthStr::width),
+            )
+            .chain(std::iter::once(unicode_width::UnicodeWidthStr::width(
+                title.as_str(),
+            )))
+            .max()
+            .unwrap_or(40)
+            + 4;
+        let (popup_bg, popup_border_fg) = {
+            let theme = self.theme();
+            (theme.popup_bg, theme.popup_border_fg)
+        };
+        let popup = Popup {
+            kind: PopupKind::List,
+            title: Some(title),
+            description: Some(description),
+            transient: false,
+            content: PopupContent::List { items, selected: 0 },
+            position: PopupPosition::Centered,
+            width: (width as u16).clamp(40, 90),
+            max_height: 15,
+            bordered: true,
+            border_style: ratatui::style::Style::default().fg(popup_border_fg),
+            background_style: ratatui::style::Style::default().bg(popup_bg),
+            scroll_offset: 0,
+            text_selection: None,
+            accept_key_hint: None,
+            resolver: PopupResolver::LspWindowRequest { id },
+            focused: true,
+            focus_key_hint: None,
+        };
+        self.global_popups.show(popup);
+    }
+
+    /// Answer, as dismissed, every request whose popup is no longer shown —
+    /// one closed along with other menus, or cleared without going through
+    /// confirm or cancel. Dropping the reply is the answer: `null` for a
+    /// message request, `success: false` for a document.
+    pub(crate) fn drop_orphaned_lsp_window_requests(&mut self) {
+        if self.pending_window_requests.pending.is_empty() {
+            return;
+        }
+        let shown: HashSet<u64> = self
+            .global_popups
+            .all()
+            .iter()
+            .filter_map(|popup| match popup.resolver {
+                PopupResolver::LspWindowRequest { id } => Some(id),
+                _ => None,
+            })
+            .collect();
+        self.pending_window_requests
+            .pending
+            .retain(|id, _| shown.contains(id));
+    }
+
+    /// Answer the request behind popup `id` with the selected row's `data`
+    /// (`None` when the popup was dismissed).
+    #[allow(clippy::let_underscore_must_use)] // reply.send() is best-effort; the server may have exited
+    pub(crate) fn resolve_lsp_window_request(&mut self, id: u64, choice: Option<String>) {
+        match self.pending_window_requests.take(id) {
+            Some(PendingWindowRequest::Message { actions, reply }) => {
+                let chosen = choice
+                    .and_then(|data| data.parse::<usize>().ok())
+                    .and_then(|index| actions.into_iter().nth(index));
+                let _ = reply.send(chosen);
+            }
+            Some(PendingWindowRequest::OpenExternal { url, reply }) => {
+                let shown = match choice.as_deref() {
+                    Some("open") => self.open_external(&url),
+                    _ => {
+                        self.set_status_message(t!("lsp.show_document_declined").to_string());
+                        false
+                    }
+                };
+                let _ = reply.send(shown);
+            }
+            None => {}
+        }
+    }
+
+    fn open_external(&mut self, url: &str) -> bool {
+        #[cfg(feature = "runtime")]
+        if let Err(e) = open::that(url) {
+            self.set_status_message(
+                t!("lsp.document_link_open_failed", error = e.to_string()).to_string(),
+            );
+            return false;
+        }
+        self.set_status_message(t!("lsp.document_link_opening", url = url).to_string());
+        true
+    }
+}
@@ -17,10 +17,15 @@ pub(crate) mod chrome;
 mod click_geometry;
 mod click_handlers;
 mod clipboard;
+mod code_lens;
 mod composite_buffer_actions;
 mod dabbrev_actions;
+mod debug_actions;
+mod debugger;
 mod diagnostic_jumps;
 pub(crate) mod diff_baselines;
+mod document_colors;
+mod document_links;
 mod editor_accessors;
 mod editor_init;
 mod event_apply;
@@ -41,12 +46,19 @@ mod input_dispatch;
 mod input_helpers;
 pub mod keybinding_editor;
 mod keybinding_editor_actions;
+mod kill_ring_actions;
 mod lifecycle;
+#[cfg(feature = "plugins")]
+mod line_edit_actions;
 mod line_scan;
+mod linked_editing;
 mod lsp_actions;
 mod lsp_event_notify;
+mod lsp_file_operations;
+mod lsp_hierarchy;
 mod lsp_requests;
 mod lsp_status;
+mod lsp_window_requests;
 mod macro_actions;
 mod macro_codegen;
 mod macros;
@@ -71,10 +83,12 @@ mod popup_dialogs;
 mod popup_overlay_actions;
 mod prompt_actions;
 mod prompt_lifecycle;
+mod quick_open_symbols;
 mod recovery_actions;
 mod regex_replace;
 pub(crate) mod render;
 mod scan_orchestrators;
+mod scope_headers;
 mod scroll_sync;
 mod scrollbar_input;
 mod scrollbar_math;
@@ -84,14 +98,22 @@ mod settings_actions;
 mod settings_prompts;
 mod shell_command;
 mod smart_home;
+mod smart_selection;
+mod spell_check;
 mod split_actions;
 mod stdin_stream;
+#[cfg(feature = "plugins")]
+mod structural_search_actions;
 mod tab_drag;
+mod task_actions;
+mod tasks;
 mod terminal;
 pub use terminal::PluginTerminalSpec;
+mod terminal_blocks;
 mod terminal_input;
 mod terminal_link;
 mod terminal_mouse;
+mod text_objects;
 mod text_ops;
 mod theme_inspect;
 mod toggle_actions;
@@ -240,7 +262,8 @@ use crate::input::buffer_mode::ModeRegistry;
 use crate::input::command_registry::CommandRegistry;
 use crate::input::keybindings::{Action, KeyContext, KeybindingResolver};
 use crate::input::quick_open::{
-    BufferProvider, CommandProvider, FileProvider, GotoLineProvider, QuickOpenRegistry,
+    BufferProvider, CommandProvider, DocumentSymbolProvider, FileProvider, GotoLineProvider,
+    QuickOpenRegistry,
 };
 use crate::model::cursor::Cursors;
 use crate::model::event::{Event, EventLog, LeafId, SplitDirection};
@@ -378,6 +401,20 @@ pub struct DabbrevCycleState {
     pub index: usize,
 }
 
+/// State for a yank that can still be replaced by yank-pop (`M-y`).
+///
+/// Recorded by every buffer paste; the session is reset when any other
+/// action is taken, so `M-y` only ever rewrites text that was just yanked.
+#[derive(Debug, Clone)]
+pub struct YankCycleState {
+    /// Buffer the yank landed in.
+    pub buffer_id: BufferId,
+    /// Kill ring index of the yanked entry.
+    pub index: usize,
+    /// Text inserted at each cursor; each cursor sits right after its text.
+    pub inserted: Vec<(crate::model::event::CursorId, String)>,
+}
+
 /// Snapshot of cursor and viewport state used to restore the original position
 /// when a goto-line preview is abandoned (cancel, or the user edits the input
 /// so it no longer targets a line).
@@ -603,6 +640,13 @@ pub struct Editor {
     /// Shared clipboard (handles both internal and system clipboard)
     clipboard: crate::services::clipboard::Clipboard,
 
+    /// Kill ring shared by all buffers (copies, cuts and kill commands)
+    kill_ring: crate::model::kill_ring::KillRing,
+
+    /// Spell checker for the configured dictionary, loaded the first time
+    /// it is needed while `editor.spell_check` is on
+    spell_checker: Option<spell_check::LoadedSpellChecker>,
+
     /// Should the editor quit?
     should_quit: bool,
 
@@ -1278,6 +1322,14 @@ pub struct Editor {
     /// `PopupResolver::PluginAction` — no parallel side-channel stack.
     pub(crate) global_popups: crate::view::popup::PopupManager,
 
+    /// Language-server requests waiting on the user's answer in one of
+    /// the global popups.
+    pub(crate) pending_window_requests: lsp_window_requests::PendingWindowRequests,
+
+    /// Explorer operation held back for `workspace/will*Files` answers or
+    /// for the user to review the edits that came back.
+    pub(crate) pending_file_operation: Option<lsp_file_operations::PendingFileOperation>,
+
     // composite_buffers + composite_view_states moved onto `Window` —
     // composite-buffer panels (Live Grep results, Diagnostics list,
     // References, etc.) belong to the window that opened the panel.
@@ -990,6 +990,10 @@ impl Editor {
             PointerGrab::HScrollbar => {
                 self.handle_hscrollbar_drag(col, row)?;
             }
+            // Minimap drag: keep the viewport centred under the pointer.
+            PointerGrab::Minimap => {
+                self.handle_minimap_drag(col, row)?;
+            }
             // Selecting text in an info popup: extend the selection.
             PointerGrab::PopupSelect => {
                 self.handle_popup_select_drag(col, row);
@@ -1073,6 +1077,7 @@ impl Editor {
         ms.dragging_horizontal_scrollbar = None;
         ms.drag_start_hcol = None;
         ms.drag_start_left_column = None;
+        ms.dragging_minimap = None;
         ms.dragging_separator = None;
         ms.drag_start_position = None;
         ms.drag_start_ratio = None;
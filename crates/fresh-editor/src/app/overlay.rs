@@ -178,12 +178,19 @@ impl Editor {
         // it must own the keyboard regardless of which pane is focused.
         // Opening a *directory* focuses the file-explorer pane, which would
         // otherwise short-circuit below and leave the (rendered) prompt
-        // un-interactable.
-        let trust_prompt_up = self
-            .global_popups
-            .top()
-            .is_some_and(|p| p.focused && matches!(p.resolver, PopupResolver::WorkspaceTrust));
-        if trust_prompt_up {
+        // un-interactable. The file-operation review is raised *from* the
+        // explorer, and a language server's question can arrive while it is
+        // focused, so they need the same exemption.
+        let modal_up = self.global_popups.top().is_some_and(|p| {
+            p.focused
+                && matches!(
+                    p.resolver,
+                    PopupResolver::WorkspaceTrust
+                        | PopupResolver::FileOperationReview
+                        | PopupResolver::LspWindowRequest { .. }
+                )
+        });
+        if modal_up {
             return true;
         }
         if matches!(self.active_window().key_context, KeyContext::FileExplorer) {
@@ -16,7 +16,7 @@ use std::sync::Arc;
 use super::Editor;
 
 /// Directory names to always skip during project file walking.
-const IGNORED_DIRS: &[&str] = &[
+pub(super) const IGNORED_DIRS: &[&str] = &[
     ".git",
     "node_modules",
     "target",
@@ -59,7 +59,7 @@ fn make_search_opts(
 }
 
 /// Fields of a `PluginCommand::BeginSearch`, grouped so
-/// [`Editor::handle_begin_search`] takes one argument instead of seven.
+/// [`Editor::handle_begin_search`] takes one argument instead of nine.
 pub(super) struct BeginSearchArgs {
     pub pattern: String,
     pub fixed_string: bool,
@@ -68,16 +68,38 @@ pub(super) struct BeginSearchArgs {
     pub whole_words: bool,
     pub file_glob: String,
     pub source_buffer_id: usize,
+    pub structural: bool,
+    pub rewrite: String,
     pub handle_id: u64,
 }
 
+/// Mark a search handle as terminal so the consumer's next take() observes
+/// `done = true` and the JS wrapper stops pumping.
+pub(super) fn finish_search_handle(
+    handle: &fresh_core::api::SearchHandleState,
+    truncated: bool,
+    error: Option<String>,
+) {
+    let mut state = match handle.state.lock() {
+        Ok(s) => s,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    state.done = true;
+    if truncated {
+        state.truncated = true;
+    }
+    if let Some(e) = error {
+        state.error = Some(e);
+    }
+}
+
 /// Return whether a workspace-relative file path is included by a
 /// comma-separated set of globs from the search panel.
 ///
 /// Patterns with a path separator match the whole relative path (`src/**`),
 /// while patterns without one match the basename at any depth (`*.rs`).
 /// Whitespace around comma-separated patterns is ignored.
-fn search_file_glob_matches(file_glob: &str, relative_path: &str) -> bool {
+pub(super) fn search_file_glob_matches(file_glob: &str, relative_path: &str) -> bool {
     let relative_path = relative_path.trim_start_matches(['/', '\\']);
     let file_name = relative_path
         .rsplit(['/', '\\'])
@@ -3357,6 +3379,8 @@ impl Editor {
             whole_words,
             file_glob,
             source_buffer_id,
+            structural,
+            rewrite,
             handle_id,
         } = args;
         // Look up the handle the plugin pre-registered. If it's missing
@@ -3377,26 +3401,20 @@ impl Editor {
             return;
         };
 
-        // Helper: mark the handle as terminal so the consumer's next take()
-        // observes `done = true` and the JS wrapper stops pumping.
-        let finish_with = |handle: &Arc<fresh_core::api::SearchHandleState>,
-                           truncated: bool,
-                           error: Option<String>| {
-            let mut state = match handle.state.lock() {
-                Ok(s) => s,
-                Err(poisoned) => poisoned.into_inner(),
-            };
-            state.done = true;
-            if truncated {
-                state.truncated = true;
-            }
-            if let Some(e) = error {
-                state.error = Some(e);
-            }
-        };
-
         if pattern.is_empty() {
-            finish_with(&handle, false, None);
+            finish_search_handle(&handle, false, None);
+            return;
+        }
+
+        if structural {
+            self.begin_structural_search(
+                handle,
+                &pattern,
+                &rewrite,
+                max_results,
+                file_glob,
+                source_buffer_id,
+            );
             return;
         }
 
@@ -3404,7 +3422,7 @@ impl Editor {
         let regex = match crate::model::filesystem::build_search_regex(&pattern, &fs_opts) {
             Ok(re) => re,
             Err(e) => {
-                finish_with(&handle, false, Some(format!("Invalid regex: {}", e)));
+                finish_search_handle(&handle, false, Some(format!("Invalid regex: {}", e)));
                 return;
             }
         };
@@ -3480,6 +3498,7 @@ impl Editor {
                         line: running_line,
                         column: start - line_start + 1,
                         context: String::from_utf8_lossy(&bytes[line_start..line_end]).into_owned(),
+                        replacement: None,
                     });
                 }
                 if !grep_matches.is_empty() {
@@ -3517,7 +3536,7 @@ impl Editor {
             .filter(|path| search_file_glob_matches(&file_glob, &path.to_string_lossy()));
 
         let Some(runtime) = &self.tokio_runtime else {
-            finish_with(
+            finish_search_handle(
                 &handle,
                 false,
                 Some("No tokio runtime available".to_string()),
@@ -3645,6 +3664,7 @@ impl Editor {
                                     line: m.line,
                                     column: m.column,
                                     context: m.context,
+                                    replacement: None,
                                 })
                                 .collect();
                             match_count.fetch_add(
@@ -3702,6 +3722,7 @@ impl Editor {
                                     line: m.line,
                                     column: m.column,
                                     context: m.context,
+                                    replacement: None,
                                 })
                                 .collect();
                             push_matches(&task_handle, file_matches);
@@ -3738,6 +3759,7 @@ impl Editor {
         buffer_id: usize,
         matches: Vec<(usize, usize)>,
         replacement: String,
+        match_replacements: Vec<String>,
         callback_id: JsCallbackId,
     ) {
         if matches.is_empty() {
@@ -3753,6 +3775,20 @@ impl Editor {
             return;
         }
 
+        // Per-match texts must line up with the matches one to one; anything
+        // else is a plugin bug, and the file is left untouched.
+        if !match_replacements.is_empty() && match_replacements.len() != matches.len() {
+            self.plugin_manager.read().unwrap().reject_callback(
+                callback_id,
+                format!(
+                    "replaceInFile: got {} replacements for {} matches",
+                    match_replacements.len(),
+                    matches.len()
+                ),
+            );
+            return;
+        }
+
         // Resolve the target buffer. A non-zero, still-live `buffer_id` wins —
         // this is how unnamed/unsaved buffers (which have no path to match on)
         // are addressed. Otherwise fall back to matching the open buffer by
@@ -3806,29 +3842,36 @@ impl Editor {
             }
         };
 
+        // Pair each match with its text — per match when the plugin sent one
+        // for each (structural rewrites), otherwise the shared replacement.
+        let mut sorted_matches: Vec<(usize, usize, String)> = if match_replacements.is_empty() {
+            matches
+                .into_iter()
+                .map(|(offset, len)| (offset, len, replacement.clone()))
+                .collect()
+        } else {
+            matches
+                .into_iter()
+                .zip(match_replacements)
+                .map(|((offset, len), text)| (offset, len, text))
+                .collect()
+        };
         // Sort matches by byte offset descending — editing from end backwards
         // prevents earlier edits from shifting later offsets
-        let mut sorted_matches = matches;
         sorted_matches.sort_by_key(|a| std::cmp::Reverse(a.0));
 
         // Build bulk edits: (start, del_len, replacement)
         let edits: Vec<(usize, usize, &str)> = sorted_matches
             .iter()
-            .map(|&(offset, len)| (offset, len, replacement.as_str()))
+            .map(|(offset, len, text)| (*offset, *len, text.as_str()))
             .collect();
 
         let replacements = edits.len();
-
-        // Owned tuples for helpers that don't take references.
-        let edits_owned: Vec<(usize, usize, String)> = sorted_matches
-            .iter()
-            .map(|&(offset, len)| (offset, len, replacement.clone()))
-            .collect();
         // Merged edit-lengths list for marker/margin replay on undo/redo.
         // Mirrors the merging logic in `apply_events_as_bulk_edit`.
         let edit_lengths: Vec<(usize, usize, usize)> = {
             let mut lengths: Vec<(usize, usize, usize)> = Vec::new();
-            for (pos, del_len, text) in &edits_owned {
+            for (pos, del_len, text) in &sorted_matches {
                 if let Some(last) = lengths.last_mut() {
                     if last.0 == *pos {
                         last.1 += del_len;
@@ -3853,7 +3896,7 @@ impl Editor {
             .buffer_state_mut(buffer_id)
         {
             let old_snapshot = state.buffer.snapshot_buffer_state();
-            let displaced_markers = state.capture_displaced_markers_bulk(&edits_owned);
+            let displaced_markers = state.capture_displaced_markers_bulk(&sorted_matches);
 
             // Apply all edits as a single bulk operation
             state.buffer.apply_bulk_edits(&edits);
@@ -4072,6 +4115,31 @@ mod tests {
         (editor, temp_dir)
     }
 
+    /// A plugin that sends per-match replacements must send one for every
+    /// match; a short list is rejected rather than padded with the shared
+    /// replacement, and the file is neither opened nor rewritten.
+    #[test]
+    fn replace_in_buffer_rejects_mismatched_match_replacements() {
+        let (mut editor, temp) = make_editor();
+        let path = temp.path().join("target.txt");
+        std::fs::write(&path, "one two three").unwrap();
+
+        editor.handle_replace_in_buffer(
+            path.clone(),
+            0,
+            vec![(0, 3), (4, 3), (8, 5)],
+            "X".to_string(),
+            vec!["1".to_string(), "2".to_string()],
+            fresh_core::api::JsCallbackId::new(1),
+        );
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "one two three");
+        assert!(editor
+            .buffers()
+            .iter()
+            .all(|(_, state)| state.buffer.file_path() != Some(&path)));
+    }
+
     #[test]
     fn search_file_glob_supports_basenames_paths_and_lists() {
         assert!(search_file_glob_matches("", "src/nested/lib.rs"));
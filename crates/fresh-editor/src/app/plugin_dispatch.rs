@@ -1235,6 +1235,63 @@ impl Editor {
                 self.handle_send_lsp_request(language, method, params, request_id);
             }
 
+            // ==================== Undo History ====================
+            PluginCommand::GetUndoHistory {
+                buffer_id,
+                request_id,
+            } => {
+                self.handle_get_undo_history(buffer_id, request_id);
+            }
+            PluginCommand::GetUndoStateText {
+                buffer_id,
+                state,
+                request_id,
+            } => {
+                self.handle_get_undo_state_text(buffer_id, state, request_id);
+            }
+            PluginCommand::UndoToState { buffer_id, state } => {
+                self.handle_undo_to_state(buffer_id, state);
+            }
+
+            // ==================== Line Edits ====================
+            PluginCommand::ApplyLineEdits { edits, request_id } => {
+                self.handle_apply_line_edits(edits, request_id);
+            }
+
+            // ==================== Text Objects ====================
+            PluginCommand::GetTextObjectRange {
+                buffer_id,
+                position,
+                object,
+                request_id,
+            } => {
+                self.handle_get_text_object_range(buffer_id, position, object, request_id);
+            }
+
+            PluginCommand::PrepareHierarchy {
+                kind,
+                buffer_id,
+                position,
+                request_id,
+            } => {
+                self.handle_prepare_hierarchy(kind, buffer_id, position, request_id);
+            }
+            PluginCommand::ExpandHierarchy {
+                direction,
+                item,
+                request_id,
+            } => {
+                self.handle_expand_hierarchy(direction, item, request_id);
+            }
+
+            PluginCommand::DebugRequest {
+                command,
+                arguments,
+                request_id,
+            } => {
+                self.handle_debug_request(command, arguments, request_id);
+            }
+
             // ==================== Clipboard Commands ====================
             PluginCommand::SetClipboard { text } => {
                 self.handle_set_clipboard(text);
@@ -1602,6 +1659,7 @@ impl Editor {
                 layout,
                 sources,
                 hunks,
+                sections,
                 initial_focus_hunk,
                 request_id,
             } => {
@@ -1612,6 +1670,7 @@ impl Editor {
                         layout_config: layout,
                         source_configs: sources,
                         hunks,
+                        sections,
                         initial_focus_hunk,
                         request_id,
                     },
@@ -1620,6 +1679,12 @@ impl Editor {
             PluginCommand::UpdateCompositeAlignment { buffer_id, hunks } => {
                 self.handle_update_composite_alignment(buffer_id, hunks);
             }
+            PluginCommand::UpdateCompositeSections {
+                buffer_id,
+                sections,
+            } => {
+                self.handle_update_composite_sections(buffer_id, sections);
+            }
             PluginCommand::CloseCompositeBuffer { buffer_id } => {
                 self.active_window_mut().close_composite_buffer(buffer_id);
             }
@@ -1879,6 +1944,8 @@ impl Editor {
                 whole_words,
                 file_glob,
                 source_buffer_id,
+                structural,
+                rewrite,
                 handle_id,
             } => {
                 self.handle_begin_search(crate::app::plugin_commands::BeginSearchArgs {
@@ -1889,6 +1956,8 @@ impl Editor {
                     whole_words,
                     file_glob,
                     source_buffer_id,
+                    structural,
+                    rewrite,
                     handle_id,
                 });
             }
@@ -1898,6 +1967,7 @@ impl Editor {
                 buffer_id,
                 matches,
                 replacement,
+                replacements,
                 callback_id,
             } => {
                 self.handle_replace_in_buffer(
@@ -1905,6 +1975,7 @@ impl Editor {
                     buffer_id,
                     matches,
                     replacement,
+                    replacements,
                     callback_id,
                 );
             }
@@ -194,6 +194,7 @@ pub(crate) fn grep_project(cap: OffLoop, req: GrepProjectRequest) {
                     line: m.line,
                     column: m.column,
                     context: m.context,
+                    replacement: None,
                 }));
             }
         }
@@ -260,6 +261,7 @@ fn search_one(
                 line: m.line,
                 column: m.column,
                 context: m.context,
+                replacement: None,
             })
             .collect();
     }
@@ -283,6 +285,7 @@ fn search_one(
             line: m.line,
             column: m.column,
             context: m.context,
+            replacement: None,
         })
         .collect()
 }
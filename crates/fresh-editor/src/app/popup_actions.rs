@@ -93,6 +93,50 @@ impl Editor {
                 PopupConfirmResult::EarlyReturn
             }
 
+            Some(PopupResolver::ColorPresentation) => {
+                let data = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.clone());
+                self.hide_popup();
+                if let Some(data) = data {
+                    self.execute_color_presentation_choice(&data);
+                }
+                PopupConfirmResult::EarlyReturn
+            }
+
+            Some(PopupResolver::CodeLens) => {
+                let selected_index = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.as_ref())
+                    .and_then(|data| data.parse::<usize>().ok());
+                self.hide_popup();
+                if let Some(index) = selected_index {
+                    self.execute_code_lens_choice(index);
+                }
+                self.active_window_mut().pending_code_lens_commands = None;
+                PopupConfirmResult::EarlyReturn
+            }
+
+            Some(PopupResolver::Breadcrumb { files }) => {
+                let data = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.clone());
+                self.hide_popup();
+                if let Some(data) = data {
+                    self.execute_breadcrumb_choice(&data, files);
+                }
+                PopupConfirmResult::EarlyReturn
+            }
+
             Some(PopupResolver::LspConfirm { language }) => {
                 let action = self
                     .active_state()
@@ -152,6 +196,34 @@ impl Editor {
                 PopupConfirmResult::EarlyReturn
             }
 
+            Some(PopupResolver::FileOperationReview) => {
+                let action_key = self
+                    .global_popups
+                    .top()
+                    .or_else(|| self.active_state().popups.top())
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.clone());
+                // Preview rows carry no action; keep the review open.
+                let Some(key) = action_key else {
+                    return PopupConfirmResult::EarlyReturn;
+                };
+                self.hide_popup();
+                self.handle_file_operation_review_action(&key);
+                PopupConfirmResult::EarlyReturn
+            }
+
+            Some(PopupResolver::LspWindowRequest { id }) => {
+                let choice = self
+                    .global_popups
+                    .top()
+                    .or_else(|| self.active_state().popups.top())
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.clone());
+                self.hide_popup();
+                self.resolve_lsp_window_request(id, choice);
+                PopupConfirmResult::EarlyReturn
+            }
+
             Some(PopupResolver::WorkspaceTrust) => {
                 // The trust prompt lives on the global stack; read its
                 // selection there (global-first, matching the resolver lookup).
@@ -399,6 +471,20 @@ impl Editor {
                 self.hide_popup();
             }
 
+            Some(PopupResolver::CodeLens) => {
+                self.active_window_mut().pending_code_lens_commands = None;
+                self.hide_popup();
+            }
+
+            Some(PopupResolver::ColorPresentation) => {
+                self.active_window_mut().color_pick = None;
+                self.hide_popup();
+            }
+
+            Some(PopupResolver::Breadcrumb { .. }) => {
+                self.hide_popup();
+            }
+
             Some(PopupResolver::LspConfirm { language: _ }) => {
                 self.set_status_message(t!("lsp.startup_cancelled_msg").to_string());
                 self.hide_popup();
@@ -421,6 +507,16 @@ impl Editor {
                 self.hide_popup();
             }
 
+            Some(PopupResolver::FileOperationReview) => {
+                self.hide_popup();
+                self.handle_file_operation_review_action("cancel");
+            }
+
+            Some(PopupResolver::LspWindowRequest { id }) => {
+                self.hide_popup();
+                self.resolve_lsp_window_request(id, None);
+            }
+
             Some(PopupResolver::WorkspaceTrust) => {
                 // The trust prompt is a forced choice: there is no "undecided"
                 // outcome, so Escape does nothing. The user must pick Trust /
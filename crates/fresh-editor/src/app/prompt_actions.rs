@@ -521,10 +521,16 @@ impl Editor {
             PromptType::ConfirmMultiDelete { paths } => {
                 let input_lower = input.trim().to_lowercase();
                 if input_lower == "y" || input_lower == "yes" {
-                    for path in paths {
-                        let is_dir = self.authority().filesystem.is_dir(&path).unwrap_or(false);
-                        self.perform_file_explorer_delete(path, is_dir);
-                    }
+                    let paths = paths
+                        .into_iter()
+                        .map(|path| {
+                            let is_dir = self.authority().filesystem.is_dir(&path).unwrap_or(false);
+                            (path, is_dir)
+                        })
+                        .collect();
+                    self.run_explorer_file_action(
+                        super::lsp_file_operations::ExplorerFileAction::Delete { paths },
+                    );
                 } else {
                     self.set_status_message(t!("explorer.delete_cancelled").to_string());
                 }
@@ -622,6 +628,9 @@ impl Editor {
             PromptType::RestartLspServer => {
                 self.handle_restart_lsp_server(&input);
             }
+            PromptType::RunTask => {
+                self.run_task(input.trim());
+            }
             PromptType::SelectTheme { .. } => {
                 self.apply_theme(input.trim());
             }
@@ -637,6 +646,12 @@ impl Editor {
             PromptType::CopyWithFormattingTheme => {
                 self.copy_selection_with_theme(input.trim());
             }
+            PromptType::KillRing => {
+                self.clear_kill_ring_preview();
+                if let Ok(index) = input.trim().parse::<usize>() {
+                    self.yank_kill_ring_entry(index);
+                }
+            }
             PromptType::SwitchToTab => {
                 if let Ok(id) = input.trim().parse::<usize>() {
                     self.switch_to_tab(BufferId(id));
@@ -660,6 +675,9 @@ impl Editor {
             PromptType::SetTabSize => {
                 self.handle_set_tab_size(&input);
             }
+            PromptType::LspPickColor => {
+                self.handle_pick_color_input(&input);
+            }
             PromptType::SetLineEnding => {
                 self.handle_set_line_ending(&input);
             }
@@ -1765,6 +1783,10 @@ impl Editor {
                 }
                 PromptResult::Done
             }
+            QuickOpenResult::GotoSymbol(target) => {
+                self.goto_quick_open_symbol(target);
+                PromptResult::Done
+            }
             QuickOpenResult::None => {
                 self.set_status_message(t!("status.no_selection").to_string());
                 PromptResult::Done
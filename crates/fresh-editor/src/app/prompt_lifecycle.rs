@@ -292,11 +292,13 @@ impl Editor {
             has_lsp_config,
             buffer_caps: self.buffer_capabilities(),
             relative_line_numbers: self.config.editor.relative_line_numbers,
+            nerd_font_icons: self.config.editor.nerd_font_icons,
         }
     }
 
     /// Update Quick Open suggestions based on current input, dispatching through the registry
     pub(super) fn update_quick_open_suggestions(&mut self, input: &str) {
+        self.request_quick_open_symbols(input);
         let context = self.build_quick_open_context();
         let suggestions = if let Some((provider, query)) =
             self.quick_open_registry.get_provider_for_input(input)
@@ -321,7 +323,8 @@ impl Editor {
         // the cursor to its pre-preview position.
         //
         // Relative input (`:+N`/`:-N`) is intentionally not previewed: the
-        // target shifts on every digit typed, which is disorienting.
+        // target shifts on every digit typed, which is disorienting. In `@`
+        // and `#` modes the selected symbol is previewed instead.
         //
         // A large file without a line index (byte-offset mode) has no
         // line→offset mapping, so a preview jump would land on the wrong
@@ -332,7 +335,11 @@ impl Editor {
         } else {
             None
         };
-        self.apply_goto_line_preview(target);
+        if target.is_some() {
+            self.apply_goto_line_preview(target);
+        } else {
+            self.preview_quick_open_symbol();
+        }
     }
 
     /// Parse a Quick Open input string for a `:<N>` goto-line preview target.
@@ -988,6 +995,7 @@ impl Editor {
                     // where it was before the prompt was opened.
                     self.restore_goto_line_preview_snapshot();
                 }
+                PromptType::KillRing => self.clear_kill_ring_preview(),
                 _ => {}
             }
         }
@@ -1100,12 +1108,14 @@ impl Editor {
                     | PromptType::SaveFileAs
                     | PromptType::StopLspServer
                     | PromptType::RestartLspServer
+                    | PromptType::RunTask
                     | PromptType::SelectTheme { .. }
                     | PromptType::SelectLocale
                     | PromptType::SwitchToTab
                     | PromptType::SetLanguage
                     | PromptType::SetEncoding
                     | PromptType::SetLineEnding
+                    | PromptType::KillRing
                     | PromptType::Plugin { .. }
                     // Resume re-opens Live Grep as a core-driven
                     // PromptType::LiveGrep whose suggestions carry the
@@ -1405,6 +1415,7 @@ impl Editor {
             | PromptType::SelectTheme { .. }
             | PromptType::StopLspServer
             | PromptType::RestartLspServer
+            | PromptType::RunTask
             | PromptType::SetLanguage
             | PromptType::SetEncoding
             | PromptType::SetLineEnding => {
@@ -1412,6 +1423,12 @@ impl Editor {
                     prompt.filter_suggestions(false);
                 }
             }
+            PromptType::KillRing => {
+                if let Some(prompt) = &mut self.active_window_mut().prompt {
+                    prompt.filter_suggestions(false);
+                }
+                self.preview_kill_ring_selection();
+            }
             PromptType::SelectLocale => {
                 // Locale selection also matches on description (language names)
                 if let Some(prompt) = &mut self.active_window_mut().prompt {
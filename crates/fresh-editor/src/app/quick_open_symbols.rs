@@ -0,0 +1,477 @@
+//! Symbol sources for Quick Open's `@` and `#` modes.
+//!
+//! `@` asks the active buffer's language server for
+//! `textDocument/documentSymbol` once per buffer version, and falls back to
+//! [`crate::primitives::outline`] when no server answers. `#` sends its query
+//! as `workspace/symbol` to every running server that supports it; each
+//! answer is merged into the `#` provider's list as it arrives.
+//!
+//! While a symbol in the active buffer is selected, the cursor previews it
+//! the same way `:<N>` previews a line.
+
+use lsp_types::{
+    DocumentSymbol, DocumentSymbolResponse, Location, OneOf, SymbolInformation,
+    WorkspaceSymbolResponse,
+};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::app::types::LspUri;
+use crate::input::quick_open::symbols::{DocumentSymbols, SymbolEntry};
+use crate::input::quick_open::{
+    BufferProvider, DocumentSymbolProvider, QuickOpenResult, SymbolTarget,
+};
+use crate::model::event::{BufferId, Event};
+use crate::primitives::outline::document_outline;
+use crate::types::LspFeature;
+use crate::view::prompt::PromptType;
+
+impl Editor {
+    fn document_symbol_provider(&self) -> Option<&DocumentSymbolProvider> {
+        let (provider, _) = self.quick_open_registry.get_provider_for_input("@")?;
+        provider.as_any().downcast_ref::<DocumentSymbolProvider>()
+    }
+
+    fn buffer_provider(&self) -> Option<&BufferProvider> {
+        let (provider, _) = self.quick_open_registry.get_provider_for_input("#")?;
+        provider.as_any().downcast_ref::<BufferProvider>()
+    }
+
+    /// Make sure the symbols Quick Open's `input` lists are cached or on
+    /// their way.
+    pub(super) fn request_quick_open_symbols(&mut self, input: &str) {
+        if input.starts_with('@') {
+            self.request_document_symbols();
+        } else if let Some(query) = input.strip_prefix('#') {
+            self.request_workspace_symbols(query.trim());
+        }
+    }
+
+    fn request_document_symbols(&mut self) {
+        let buffer_id = self.active_buffer();
+        let version = self.active_state().buffer.version();
+        if self
+            .document_symbol_provider()
+            .is_none_or(|p| p.is_current(buffer_id.0, version))
+        {
+            return;
+        }
+
+        let request_id = self.active_window().next_lsp_request_id;
+        let sent =
+            self.with_lsp_for_buffer(buffer_id, LspFeature::DocumentSymbols, |handle, uri, _| {
+                handle.document_symbols(request_id, uri.as_uri().clone())
+            });
+        let symbols = match sent {
+            Some(Ok(())) => {
+                let window = self.active_window_mut();
+                window.next_lsp_request_id += 1;
+                window.pending_document_symbol_request = Some((request_id, buffer_id, version));
+                None
+            }
+            Some(Err(e)) => {
+                tracing::debug!("Failed to request document symbols: {}", e);
+                Some(self.outline_symbols(buffer_id))
+            }
+            None => Some(self.outline_symbols(buffer_id)),
+        };
+        if let Some(provider) = self.document_symbol_provider() {
+            provider.set(DocumentSymbols {
+                buffer_id: buffer_id.0,
+                version,
+                loading: symbols.is_none(),
+                symbols: symbols.unwrap_or_default(),
+            });
+        }
+    }
+
+    /// The local outline of `buffer_id`, for when no server answers.
+    fn outline_symbols(&self, buffer_id: BufferId) -> Vec<SymbolEntry> {
+        let Some(state) = self.buffers().get(&buffer_id) else {
+            return Vec::new();
+        };
+        document_outline(&state.buffer, state.highlighter.language(), &state.language)
+            .into_iter()
+            .map(|symbol| {
+                let (line, character) = state.buffer.position_to_lsp_position(symbol.position);
+                SymbolEntry {
+                    name: symbol.name,
+                    kind: symbol.kind,
+                    container: symbol.container,
+                    depth: symbol.depth,
+                    target: SymbolTarget {
+                        uri: None,
+                        line: line as u32,
+                        character: character as u32,
+                    },
+                    label: None,
+                }
+            })
+            .collect()
+    }
+
+    pub(super) fn handle_lsp_document_symbols(
+        &mut self,
+        request_id: u64,
+        symbols: Result<Option<DocumentSymbolResponse>, String>,
+    ) {
+        let window = self.active_window_mut();
+        let Some((_, buffer_id, version)) = window
+            .pending_document_symbol_request
+            .filter(|&(id, _, _)| id == request_id)
+        else {
+            return;
+        };
+        window.pending_document_symbol_request = None;
+        self.update_scope_outline(buffer_id, version, &symbols);
+
+        let mut symbols = match symbols {
+            Ok(Some(response)) => document_symbol_entries(response),
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                tracing::debug!("Document symbol request failed: {}", e);
+                Vec::new()
+            }
+        };
+        // Some servers answer with nothing for files they only half
+        // understand; the outline still has something to offer there.
+        if symbols.is_empty() {
+            symbols = self.outline_symbols(buffer_id);
+        }
+        if let Some(provider) = self.document_symbol_provider() {
+            provider.set(DocumentSymbols {
+                buffer_id: buffer_id.0,
+                version,
+                loading: false,
+                symbols,
+            });
+        }
+        self.refresh_quick_open_symbols('@');
+    }
+
+    fn request_workspace_symbols(&mut self, query: &str) {
+        let Some(provider) = self.buffer_provider() else {
+            return;
+        };
+        if provider.workspace_symbols().query() == query {
+            return;
+        }
+
+        let mut request_id = self.active_window().next_lsp_request_id;
+        let mut sent = Vec::new();
+        if let Some(lsp) = self.lsp().filter(|_| !query.is_empty()) {
+            for server in lsp.handles_for_workspace_feature(LspFeature::WorkspaceSymbols) {
+                match server
+                    .handle
+                    .workspace_symbols(request_id, query.to_string())
+                {
+                    Ok(()) => {
+                        sent.push(request_id);
+                        request_id += 1;
+                    }
+                    Err(e) => tracing::debug!(
+                        "Failed to request workspace symbols from {}: {}",
+                        server.name,
+                        e
+                    ),
+                }
+            }
+        }
+
+        let window = self.active_window_mut();
+        window.next_lsp_request_id = request_id;
+        // Answers to the previous query are no longer wanted.
+        window.pending_workspace_symbol_requests.clear();
+        for id in &sent {
+            window
+                .pending_workspace_symbol_requests
+                .insert(*id, query.to_string());
+        }
+        if let Some(provider) = self.buffer_provider() {
+            provider.workspace_symbols().begin(query, sent.len());
+        }
+    }
+
+    pub(super) fn handle_lsp_workspace_symbols(
+        &mut self,
+        request_id: u64,
+        symbols: Result<Option<WorkspaceSymbolResponse>, String>,
+    ) {
+        let Some(query) = self
+            .active_window_mut()
+            .pending_workspace_symbol_requests
+            .remove(&request_id)
+        else {
+            return;
+        };
+        let symbols = match symbols {
+            Ok(Some(response)) => self.workspace_symbol_entries(response),
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                tracing::debug!("Workspace symbol request failed: {}", e);
+                Vec::new()
+            }
+        };
+        if let Some(provider) = self.buffer_provider() {
+            provider.workspace_symbols().add(&query, symbols);
+        }
+        self.refresh_quick_open_symbols('#');
+    }
+
+    fn workspace_symbol_entries(&self, response: WorkspaceSymbolResponse) -> Vec<SymbolEntry> {
+        let symbols: Vec<(String, _, Option<String>, lsp_types::Uri, u32, u32)> = match response {
+            WorkspaceSymbolResponse::Flat(symbols) => symbols
+                .into_iter()
+                .map(|s| {
+                    let start = s.location.range.start;
+                    (
+                        s.name,
+                        s.kind,
+                        s.container_name,
+                        s.location.uri,
+                        start.line,
+                        start.character,
+                    )
+                })
+                .collect(),
+            WorkspaceSymbolResponse::Nested(symbols) => symbols
+                .into_iter()
+                .map(|s| {
+                    // Without `workspaceSymbol/resolve` a bare URI means the
+                    // start of the file.
+                    let (uri, start) = match s.location {
+                        OneOf::Left(Location { uri, range }) => (uri, range.start),
+                        OneOf::Right(location) => (location.uri, Default::default()),
+                    };
+                    (
+                        s.name,
+                        s.kind,
+                        s.container_name,
+                        uri,
+                        start.line,
+                        start.character,
+                    )
+                })
+                .collect(),
+        };
+
+        let translation = self.authority().path_translation.as_ref();
+        let working_dir = self.working_dir();
+        symbols
+            .into_iter()
+            .map(|(name, kind, container, uri, line, character)| {
+                let wire = uri.as_str().to_string();
+                let label = LspUri::from_wire(uri)
+                    .to_host_path(translation)
+                    .map(|path| {
+                        path.strip_prefix(working_dir)
+                            .unwrap_or(&path)
+                            .display()
+                            .to_string()
+                    })
+                    .unwrap_or_else(|| wire.clone());
+                SymbolEntry {
+                    name,
+                    kind,
+                    container: container.filter(|c| !c.is_empty()),
+                    depth: 0,
+                    target: SymbolTarget {
+                        uri: Some(wire),
+                        line,
+                        character,
+                    },
+                    label: Some(label),
+                }
+            })
+            .collect()
+    }
+
+    /// Recompute the Quick Open list after symbols for `prefix` arrived,
+    /// keeping the selected row selected.
+    fn refresh_quick_open_symbols(&mut self, prefix: char) {
+        let Some(prompt) = self
+            .active_window()
+            .prompt
+            .as_ref()
+            .filter(|p| p.prompt_type == PromptType::QuickOpen)
+        else {
+            return;
+        };
+        let input = prompt.input_str().to_string();
+        if !input.starts_with(prefix) {
+            return;
+        }
+        let selected_value = prompt
+            .selected_suggestion
+            .and_then(|i| prompt.suggestions.get(i))
+            .and_then(|s| s.value.clone());
+
+        self.update_quick_open_suggestions(&input);
+
+        let Some(value) = selected_value else {
+            return;
+        };
+        let moved = self.active_window_mut().prompt.as_mut().is_some_and(|p| {
+            let index = p
+                .suggestions
+                .iter()
+                .position(|s| s.value.as_ref() == Some(&value));
+            match index {
+                Some(index) if p.selected_suggestion != Some(index) => {
+                    p.selected_suggestion = Some(index);
+                    true
+                }
+                _ => false,
+            }
+        });
+        if moved {
+            self.preview_quick_open_symbol();
+        }
+    }
+
+    /// The symbol selected in the Quick Open prompt, if any.
+    fn selected_quick_open_symbol(&self) -> Option<SymbolTarget> {
+        let prompt = self
+            .active_window()
+            .prompt
+            .as_ref()
+            .filter(|p| p.prompt_type == PromptType::QuickOpen)?;
+        let input = prompt.input_str();
+        if !input.starts_with(['@', '#']) {
+            return None;
+        }
+        let suggestion = prompt
+            .selected_suggestion
+            .and_then(|i| prompt.suggestions.get(i));
+        let (provider, query) = self.quick_open_registry.get_provider_for_input(input)?;
+        match provider.on_select(suggestion, query, &self.build_quick_open_context()) {
+            QuickOpenResult::GotoSymbol(target) => Some(target),
+            _ => None,
+        }
+    }
+
+    /// Byte offset of `target` if it is in the active buffer.
+    fn symbol_position_in_active_buffer(&self, target: &SymbolTarget) -> Option<usize> {
+        let buffer_id = self.active_buffer();
+        if let Some(uri) = &target.uri {
+            let metadata = self.active_window().buffer_metadata.get(&buffer_id)?;
+            if metadata.file_uri()?.as_str() != uri {
+                return None;
+            }
+        }
+        let buffer = &self.buffers().get(&buffer_id)?.buffer;
+        Some(buffer.lsp_position_to_byte(target.line as usize, target.character as usize))
+    }
+
+    /// Show the selected symbol if it is in the active buffer, or put the
+    /// cursor back where it was before the preview.
+    pub(super) fn preview_quick_open_symbol(&mut self) {
+        let position = self
+            .selected_quick_open_symbol()
+            .and_then(|target| self.symbol_position_in_active_buffer(&target));
+        match position {
+            Some(position) => {
+                self.save_goto_line_preview_snapshot();
+                self.goto_byte_offset(position);
+                self.apply_event_to_active_buffer(&Event::Recenter);
+                let new_position = self.active_cursors().primary().position;
+                if let Some(snap) = self.active_window_mut().goto_line_preview.as_mut() {
+                    snap.last_jump_position = new_position;
+                }
+            }
+            None => self.restore_goto_line_preview_snapshot(),
+        }
+    }
+
+    /// Move the cursor to a symbol picked in Quick Open, opening its file
+    /// first if it lives elsewhere.
+    pub(super) fn goto_quick_open_symbol(&mut self, target: SymbolTarget) {
+        if self.symbol_position_in_active_buffer(&target).is_none() {
+            let Some(uri) = target.uri.as_deref() else {
+                return;
+            };
+            let uri = match uri.parse::<lsp_types::Uri>() {
+                Ok(uri) => LspUri::from_wire(uri),
+                Err(e) => {
+                    tracing::warn!("Invalid symbol URI '{}': {}", uri, e);
+                    return;
+                }
+            };
+            if let Err(e) = self.open_lsp_uri_target(&uri) {
+                if let Some(confirmation) =
+                    e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
+                {
+                    self.start_large_file_encoding_confirmation(confirmation);
+                } else {
+                    self.set_status_message(
+                        t!("file.error_opening", error = e.to_string()).to_string(),
+                    );
+                }
+                return;
+            }
+        }
+        let position = self
+            .active_state()
+            .buffer
+            .lsp_position_to_byte(target.line as usize, target.character as usize);
+        self.goto_byte_offset(position);
+        self.apply_event_to_active_buffer(&Event::Recenter);
+    }
+}
+
+/// Flatten a `textDocument/documentSymbol` answer into document order.
+fn document_symbol_entries(response: DocumentSymbolResponse) -> Vec<SymbolEntry> {
+    let mut entries = Vec::new();
+    match response {
+        DocumentSymbolResponse::Nested(symbols) => {
+            flatten_document_symbols(symbols, None, 0, &mut entries);
+        }
+        DocumentSymbolResponse::Flat(symbols) => {
+            entries.extend(symbols.into_iter().map(flat_symbol_entry));
+        }
+    }
+    entries.sort_by_key(|e| (e.target.line, e.target.character));
+    entries
+}
+
+fn flatten_document_symbols(
+    symbols: Vec<DocumentSymbol>,
+    container: Option<&str>,
+    depth: usize,
+    out: &mut Vec<SymbolEntry>,
+) {
+    for symbol in symbols {
+        let start = symbol.selection_range.start;
+        out.push(SymbolEntry {
+            name: symbol.name.clone(),
+            kind: symbol.kind,
+            container: container.map(str::to_string),
+            depth,
+            target: SymbolTarget {
+                uri: None,
+                line: start.line,
+                character: start.character,
+            },
+            label: None,
+        });
+        if let Some(children) = symbol.children {
+            flatten_document_symbols(children, Some(&symbol.name), depth + 1, out);
+        }
+    }
+}
+
+fn flat_symbol_entry(symbol: SymbolInformation) -> SymbolEntry {
+    let start = symbol.location.range.start;
+    SymbolEntry {
+        name: symbol.name,
+        kind: symbol.kind,
+        depth: usize::from(symbol.container_name.is_some()),
+        container: symbol.container_name,
+        target: SymbolTarget {
+            uri: None,
+            line: start.line,
+            character: start.character,
+        },
+        label: None,
+    }
+}
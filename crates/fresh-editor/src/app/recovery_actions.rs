@@ -26,8 +26,15 @@ impl Editor {
             .sync_lsp_after_recovery_replay(buffer_id);
     }
 
-    /// Start the recovery session (call on editor startup after recovery check)
+    /// Start the recovery session (call on editor startup after recovery check).
+    /// Also prunes stale persistent undo histories in the background.
     pub fn start_recovery_session(&mut self) -> AnyhowResult<()> {
+        if self.config.editor.persistent_undo {
+            self.spawn_off_loop_effect(
+                "prune_undo_history",
+                crate::workspace::PersistedUndoStore::prune,
+            );
+        }
         Ok(self.recovery_service.lock().unwrap().start_session()?)
     }
 
@@ -580,6 +580,8 @@ impl Editor {
             view_line_mappings,
             horizontal_scrollbar_areas,
             grouped_separator_areas,
+            minimap_areas,
+            scope_header_areas,
         ) = __win
             .buffers
             .with_all_mut(|__buffers_mut, __mgr, __vs_map| {
@@ -824,6 +826,8 @@ impl Editor {
 
         self.active_layout_mut().split_areas = split_areas;
         self.active_layout_mut().horizontal_scrollbar_areas = horizontal_scrollbar_areas;
+        self.active_layout_mut().minimap_areas = minimap_areas;
+        self.active_layout_mut().scope_header_areas = scope_header_areas;
         self.active_layout_mut().tab_layouts = tab_layouts;
         self.active_layout_mut().close_split_areas = close_split_areas;
         self.active_layout_mut().maximize_split_areas = maximize_split_areas;
@@ -2605,7 +2609,8 @@ impl Editor {
     }
 
     /// Compute the visible byte range for each split and issue debounced LSP
-    /// requests for semantic tokens and folding ranges.
+    /// requests for semantic tokens, folding ranges and code lenses, plus the
+    /// linked editing ranges at the active cursor.
     fn request_semantic_ranges_for_visible_splits(&mut self) {
         let mut semantic_ranges: std::collections::HashMap<BufferId, (usize, usize)> =
             std::collections::HashMap::new();
@@ -2653,7 +2658,12 @@ impl Editor {
             self.maybe_request_semantic_tokens_range(buffer_id, start_line, end_line);
             self.maybe_request_semantic_tokens_full_debounced(buffer_id);
             self.maybe_request_folding_ranges_debounced(buffer_id);
+            self.maybe_request_code_lens(buffer_id, start_line, end_line);
+            self.maybe_request_document_links(buffer_id);
+            self.maybe_request_document_colors(buffer_id);
+            self.maybe_request_document_scopes(buffer_id);
         }
+        self.maybe_request_linked_editing_ranges();
     }
 
     /// Pre-load viewport data for each visible buffer.
@@ -2683,13 +2693,28 @@ impl Editor {
                 })
                 .collect()
         };
-        let win_buffers = &mut self
+        // Hand each visible buffer the spell checker (or `None`, which
+        // clears its underlines); the overlay checks during decoration.
+        // Terminals and read-only views are never checked.
+        let spell_checker = self.spell_checker();
+        let window = self
             .windows
             .get_mut(&active_id)
-            .expect("active window must exist")
-            .buffers;
+            .expect("active window must exist");
+        let terminals: std::collections::HashSet<BufferId> = prep_targets
+            .iter()
+            .map(|(buffer_id, _, _)| *buffer_id)
+            .filter(|buffer_id| window.is_terminal_buffer(*buffer_id))
+            .collect();
+        let win_buffers = &mut window.buffers;
         for (buffer_id, top_byte, height) in prep_targets {
             if let Some(state) = win_buffers.get_mut(&buffer_id) {
+                let checked = !state.editing_disabled
+                    && !state.is_composite_buffer
+                    && !terminals.contains(&buffer_id);
+                state
+                    .spell_check_overlay
+                    .set_checker(spell_checker.clone().filter(|_| checked));
                 if let Err(e) = state.prepare_for_render(top_byte, height) {
                     tracing::error!("Failed to prepare buffer for render: {}", e);
                 }
@@ -3133,13 +3158,19 @@ impl Editor {
         // without the dead embed.
         let preview_draw_tab_bar = !self.suppress_chrome_cells;
         // Same immutable render settings as the live editor, but with
-        // scrollbars and tildes suppressed — they're noisy in a small
-        // preview rect where the active session's chrome is authoritative.
+        // scrollbars, minimaps, sticky scroll, breadcrumbs and tildes
+        // suppressed — they're noisy in a small preview rect where the
+        // active session's chrome is authoritative. A zero minimap width
+        // also beats per-buffer pins.
         // Built before the `&mut self.windows` borrow (it only borrows
         // `self.config`).
         let preview_cfg = crate::view::ui::EditorRenderConfig {
             show_vertical_scrollbar: false,
             show_horizontal_scrollbar: false,
+            show_minimap: false,
+            minimap_width: 0,
+            show_sticky_scroll: false,
+            show_breadcrumbs: false,
             show_tilde: false,
             ..crate::view::ui::EditorRenderConfig::new(
                 &self.config.editor,
@@ -4697,6 +4728,7 @@ impl Editor {
             .expect("active window must exist");
         let tab_bar_visible = __win_l.tab_bar_visible;
         let theme = self.theme.read().unwrap().clone();
+        let buffer_metadata = &__win_l.buffer_metadata;
         let view_line_mappings = __win_l
             .buffers
             .with_all_mut(|buffers, mgr, vs_map| {
@@ -4704,6 +4736,7 @@ impl Editor {
                     editor_content_area,
                     &*mgr,
                     buffers,
+                    buffer_metadata,
                     vs_map,
                     &theme,
                     false, // lsp_waiting — not relevant for layout
@@ -4716,6 +4749,9 @@ impl Editor {
                     tab_bar_visible,
                     self.config.editor.show_vertical_scrollbar,
                     self.config.editor.show_horizontal_scrollbar,
+                    self.config.editor.show_minimap,
+                    self.config.editor.minimap_width,
+                    self.config.editor.show_breadcrumbs,
                     self.config.editor.diagnostics_inline_text,
                     self.config.editor.show_tilde,
                     crate::view::bracket_highlight_overlay::BracketHighlightSettings::from_config(
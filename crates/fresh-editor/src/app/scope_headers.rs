@@ -0,0 +1,320 @@
+//! Sticky scroll and the breadcrumb bar, editor side.
+//!
+//! Keeps the [`ScopeOutline`](crate::view::scope_outline::ScopeOutline) of
+//! each visible buffer fed from the language server's
+//! `textDocument/documentSymbol`, and handles clicks on what
+//! [`scope_header`](crate::view::ui::split_rendering::scope_header) draws: a
+//! pinned line jumps to its definition, and a breadcrumb segment opens a
+//! picker of the symbols next to it — or, for the path, of the files next
+//! to the buffer's.
+
+use anyhow::Result as AnyhowResult;
+use lsp_types::{DocumentSymbol, DocumentSymbolResponse, Position};
+use ratatui::style::Style;
+use rust_i18n::t;
+
+use super::Editor;
+use crate::input::quick_open::symbols::symbol_kind_icon;
+use crate::model::buffer::Buffer;
+use crate::model::event::{BufferId, Event};
+use crate::model::filesystem::EntryType;
+use crate::primitives::outline::OutlineSymbol;
+use crate::types::LspFeature;
+use crate::view::popup::{Popup, PopupKind, PopupListItem, PopupPosition, PopupResolver};
+use crate::view::ui::split_rendering::scope_header::BreadcrumbTarget;
+
+impl Editor {
+    /// Ask the language server for the outline of `buffer_id` when sticky
+    /// scroll or the breadcrumb bar needs it and the buffer changed since
+    /// the last ask. Without a server the local outline stands.
+    pub(crate) fn maybe_request_document_scopes(&mut self, buffer_id: BufferId) {
+        if !self.config.editor.show_sticky_scroll && !self.config.editor.show_breadcrumbs {
+            return;
+        }
+        let window = self.active_window();
+        // One documentSymbol request at a time, shared with Quick Open's
+        // `@`: its answer feeds both.
+        if window.pending_document_symbol_request.is_some() {
+            return;
+        }
+        let Some(state) = window.buffers.get(&buffer_id) else {
+            return;
+        };
+        let version = state.buffer.version();
+        if !state.scope_outline.needs_request(version) {
+            return;
+        }
+
+        let request_id = window.next_lsp_request_id;
+        let sent =
+            self.with_lsp_for_buffer(buffer_id, LspFeature::DocumentSymbols, |handle, uri, _| {
+                handle.document_symbols(request_id, uri.as_uri().clone())
+            });
+        let window = self.active_window_mut();
+        let asked = match sent {
+            Some(Ok(())) => {
+                window.next_lsp_request_id += 1;
+                window.pending_document_symbol_request = Some((request_id, buffer_id, version));
+                true
+            }
+            Some(Err(e)) => {
+                tracing::debug!("Failed to request document scopes: {}", e);
+                false
+            }
+            None => false,
+        };
+        if let Some(state) = window.buffers.get_mut(&buffer_id) {
+            state.scope_outline.mark_requested(version);
+            if !asked {
+                state.scope_outline.clear_lsp();
+            }
+        }
+    }
+
+    /// Take a `textDocument/documentSymbol` answer for `version` of
+    /// `buffer_id` into its scope outline. An empty or failed answer falls
+    /// back to the local outline.
+    pub(super) fn update_scope_outline(
+        &mut self,
+        buffer_id: BufferId,
+        version: u64,
+        response: &Result<Option<DocumentSymbolResponse>, String>,
+    ) {
+        let Some(state) = self.active_window_mut().buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let symbols = match response {
+            Ok(Some(response)) => scope_symbols(&state.buffer, response),
+            _ => Vec::new(),
+        };
+        if symbols.is_empty() {
+            state.scope_outline.clear_lsp();
+        } else {
+            state.scope_outline.set_from_lsp(version, symbols);
+        }
+    }
+
+    /// Click on a pinned sticky-scroll line: focus its split and jump to
+    /// the definition it heads.
+    pub(crate) fn handle_click_sticky_scroll(
+        &mut self,
+        col: u16,
+        row: u16,
+    ) -> Option<AnyhowResult<()>> {
+        let (split_id, buffer_id, target) = self
+            .active_layout()
+            .scope_header_areas
+            .iter()
+            .find_map(|area| {
+                Some((
+                    area.split_id,
+                    area.buffer_id,
+                    area.sticky_target_at(col, row)?,
+                ))
+            })?;
+        self.focus_split(split_id, buffer_id);
+        self.goto_byte_offset(target);
+        self.apply_event_to_active_buffer(&Event::Recenter);
+        Some(Ok(()))
+    }
+
+    /// Click on the breadcrumb row: focus its split and, on a segment, open
+    /// the picker for it below the segment.
+    pub(crate) fn handle_click_breadcrumbs(
+        &mut self,
+        col: u16,
+        row: u16,
+    ) -> Option<AnyhowResult<()>> {
+        let (split_id, buffer_id, segment) = self
+            .active_layout()
+            .scope_header_areas
+            .iter()
+            .find_map(|area| {
+                let rect = area.breadcrumb_rect?;
+                (row == rect.y && col >= rect.x && col < rect.x + rect.width).then(|| {
+                    (
+                        area.split_id,
+                        area.buffer_id,
+                        area.breadcrumb_at(col, row).cloned(),
+                    )
+                })
+            })?;
+        self.focus_split(split_id, buffer_id);
+        if let Some(segment) = segment {
+            let position = PopupPosition::Fixed {
+                x: segment.columns.start,
+                y: row + 1,
+            };
+            match segment.target {
+                BreadcrumbTarget::File => self.show_breadcrumb_files(buffer_id, position),
+                BreadcrumbTarget::Symbol(symbol) => {
+                    self.show_breadcrumb_symbols(buffer_id, symbol, position)
+                }
+            }
+        }
+        Some(Ok(()))
+    }
+
+    /// Picker of the symbols sharing a parent with the one named at
+    /// `symbol`.
+    fn show_breadcrumb_symbols(
+        &mut self,
+        buffer_id: BufferId,
+        symbol: usize,
+        position: PopupPosition,
+    ) {
+        let nerd_fonts = self.config.editor.nerd_font_icons;
+        let Some(state) = self.buffers().get(&buffer_id) else {
+            return;
+        };
+        let outline = &state.scope_outline;
+        let Some(current) = outline
+            .enclosing(symbol)
+            .into_iter()
+            .rfind(|s| s.position == symbol)
+        else {
+            return;
+        };
+        let siblings = outline.siblings(current);
+        let selected = siblings.iter().position(|s| s.position == symbol);
+        let items = siblings
+            .into_iter()
+            .map(|s| PopupListItem {
+                text: s.name.clone(),
+                detail: Some(format!(":{}", state.buffer.get_line_number(s.position) + 1)),
+                icon: Some(symbol_kind_icon(s.kind, nerd_fonts).to_string()),
+                data: Some(s.position.to_string()),
+                disabled: false,
+            })
+            .collect();
+        self.show_breadcrumb_popup(items, selected, position, false);
+    }
+
+    /// Picker of the files in the directory of `buffer_id`'s file.
+    fn show_breadcrumb_files(&mut self, buffer_id: BufferId, position: PopupPosition) {
+        let Some(path) = self
+            .active_window()
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_path())
+            .cloned()
+        else {
+            return;
+        };
+        let Some(dir) = path.parent() else {
+            return;
+        };
+        let Some(state) = self.buffers().get(&buffer_id) else {
+            return;
+        };
+        let mut entries = match state.buffer.filesystem().read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::debug!("Failed to list {}: {}", dir.display(), e);
+                return;
+            }
+        };
+        entries.retain(|e| e.entry_type != EntryType::Directory && !e.symlink_target_is_dir);
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        let selected = entries.iter().position(|e| e.path == path);
+        let items = entries
+            .into_iter()
+            .map(|e| PopupListItem {
+                text: e.name,
+                detail: None,
+                icon: None,
+                data: Some(e.path.to_string_lossy().into_owned()),
+                disabled: false,
+            })
+            .collect();
+        self.show_breadcrumb_popup(items, selected, position, true);
+    }
+
+    fn show_breadcrumb_popup(
+        &mut self,
+        items: Vec<PopupListItem>,
+        selected: Option<usize>,
+        position: PopupPosition,
+        files: bool,
+    ) {
+        let theme = self.theme.read().unwrap();
+        let mut popup = Popup::list(items, &theme);
+        popup.kind = PopupKind::Action;
+        popup.position = position;
+        popup.width = 40;
+        popup.max_height = 15;
+        popup.border_style = Style::default().fg(theme.popup_border_fg);
+        popup.background_style = Style::default().bg(theme.popup_bg);
+        drop(theme);
+        popup.resolver = PopupResolver::Breadcrumb { files };
+        popup.focused = true;
+        if let Some(index) = selected {
+            popup.select_index(index);
+        }
+
+        let buffer_id = self.active_buffer();
+        if let Some(state) = self.active_window_mut().buffers.get_mut(&buffer_id) {
+            state.popups.show_or_replace(popup);
+        }
+    }
+
+    /// Go to the breadcrumb picker's choice: `data` is a byte offset in the
+    /// active buffer, or a file path when `files` is set.
+    pub(crate) fn execute_breadcrumb_choice(&mut self, data: &str, files: bool) {
+        if files {
+            if let Err(e) = self.open_file(std::path::Path::new(data)) {
+                self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+            }
+        } else if let Ok(position) = data.parse::<usize>() {
+            self.goto_byte_offset(position);
+            self.apply_event_to_active_buffer(&Event::Recenter);
+        }
+    }
+}
+
+/// The symbols of a `textDocument/documentSymbol` answer, with byte ranges.
+fn scope_symbols(buffer: &Buffer, response: &DocumentSymbolResponse) -> Vec<OutlineSymbol> {
+    let byte = |position: &Position| {
+        buffer.lsp_position_to_byte(position.line as usize, position.character as usize)
+    };
+    let mut symbols = Vec::new();
+    match response {
+        DocumentSymbolResponse::Nested(roots) => {
+            fn walk(
+                symbol: &DocumentSymbol,
+                depth: usize,
+                container: Option<&str>,
+                byte: &dyn Fn(&Position) -> usize,
+                symbols: &mut Vec<OutlineSymbol>,
+            ) {
+                symbols.push(OutlineSymbol {
+                    name: symbol.name.clone(),
+                    kind: symbol.kind,
+                    position: byte(&symbol.selection_range.start),
+                    depth,
+                    container: container.map(str::to_string),
+                    range: byte(&symbol.range.start)..byte(&symbol.range.end),
+                });
+                for child in symbol.children.iter().flatten() {
+                    walk(child, depth + 1, Some(&symbol.name), byte, symbols);
+                }
+            }
+            for root in roots {
+                walk(root, 0, None, &byte, &mut symbols);
+            }
+        }
+        DocumentSymbolResponse::Flat(infos) => {
+            symbols.extend(infos.iter().map(|info| OutlineSymbol {
+                name: info.name.clone(),
+                kind: info.kind,
+                position: byte(&info.location.range.start),
+                depth: 0,
+                container: info.container_name.clone(),
+                range: byte(&info.location.range.start)..byte(&info.location.range.end),
+            }));
+        }
+    }
+    symbols
+}
@@ -611,6 +611,33 @@ impl crate::app::window::Window {
         Ok(())
     }
 
+    /// Minimap click or drag: centre the split's viewport on the byte
+    /// offset behind the minimap row under the pointer.
+    pub(super) fn handle_minimap_jump(
+        &mut self,
+        split_id: LeafId,
+        buffer_id: BufferId,
+        target_byte: usize,
+    ) -> AnyhowResult<()> {
+        self.buffers.with_all_mut(|buffers, _, view_states| {
+            let (Some(state), Some(view_state)) =
+                (buffers.get_mut(&buffer_id), view_states.get_mut(&split_id))
+            else {
+                return;
+            };
+            let target_byte = target_byte.min(state.buffer.len());
+            view_state
+                .viewport
+                .center_on_position(&mut state.buffer, target_byte);
+            // Skip ensure_visible so the scroll position isn't undone during render
+            view_state.viewport.set_skip_ensure_visible();
+        });
+
+        self.move_cursor_to_visible_area(split_id, buffer_id);
+
+        Ok(())
+    }
+
     /// Handle scrollbar jump (click on track) for composite buffers.
     /// Maps the click ratio to a row-based scroll position.
     fn handle_composite_scrollbar_jump(
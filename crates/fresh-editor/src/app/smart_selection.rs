@@ -0,0 +1,420 @@
+//! Smart Expand / Shrink Selection.
+//!
+//! Each cursor's selection grows (or shrinks) to the next enclosing syntax
+//! range. Ranges come from the language server (`textDocument/selectionRange`)
+//! when it supports them, otherwise from
+//! [`crate::primitives::selection_ranges`] (tree-sitter, then a
+//! bracket/quote heuristic). All cursors move in one `Event::Batch`, so a
+//! single undo restores them.
+//!
+//! Expanding records the selections it replaced; shrinking pops them back as
+//! long as the cursors still sit where the last step left them. Without that
+//! history, shrink falls back to the largest range inside the selection.
+
+use std::ops::Range;
+
+use lsp_types::{Position, SelectionRange};
+
+use super::Editor;
+use crate::model::buffer::Buffer;
+use crate::model::cursor::Cursors;
+use crate::model::event::{BufferId, CursorId, Event};
+use crate::primitives::selection_ranges::{next_larger, next_smaller, selection_chain};
+use crate::state::EditorState;
+use crate::types::LspFeature;
+
+/// Which way a smart selection step goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SelectionStep {
+    Expand,
+    Shrink,
+}
+
+/// A cursor's `(position, anchor)` at the time of a step.
+type CursorSelections = Vec<(CursorId, usize, Option<usize>)>;
+
+/// An in-flight `textDocument/selectionRange`, with the selections it was
+/// computed for; the answer is dropped if they have changed since.
+#[derive(Clone, Debug)]
+pub(crate) struct SelectionRangeRequest {
+    buffer_id: BufferId,
+    version: u64,
+    step: SelectionStep,
+    selections: CursorSelections,
+}
+
+/// Selections replaced by successive expand steps, innermost last.
+#[derive(Debug)]
+pub(crate) struct SelectionHistory {
+    buffer_id: BufferId,
+    version: u64,
+    /// Where the last expand step left the cursors.
+    current: CursorSelections,
+    stack: Vec<CursorSelections>,
+}
+
+fn snapshot(cursors: &Cursors) -> CursorSelections {
+    cursors
+        .iter()
+        .map(|(id, cursor)| (id, cursor.position, cursor.anchor))
+        .collect()
+}
+
+fn selection_range(position: usize, anchor: Option<usize>) -> Range<usize> {
+    let anchor = anchor.unwrap_or(position);
+    position.min(anchor)..position.max(anchor)
+}
+
+/// Flatten a parent-linked LSP selection range into byte ranges, innermost
+/// first.
+fn lsp_chain(buffer: &Buffer, range: &SelectionRange) -> Vec<Range<usize>> {
+    let to_byte = |p: &Position| buffer.lsp_position_to_byte(p.line as usize, p.character as usize);
+    let mut chain = Vec::new();
+    let mut next = Some(range);
+    while let Some(r) = next {
+        chain.push(to_byte(&r.range.start)..to_byte(&r.range.end));
+        next = r.parent.as_deref();
+    }
+    chain
+}
+
+/// The range a selection moves to, given the chain around it.
+fn step_target(
+    step: SelectionStep,
+    chain: &[Range<usize>],
+    current: &Range<usize>,
+    position: usize,
+) -> Option<Range<usize>> {
+    match step {
+        SelectionStep::Expand => next_larger(chain, current),
+        SelectionStep::Shrink => next_smaller(chain, current)
+            .or_else(|| (!current.is_empty()).then_some(position..position)),
+    }
+}
+
+/// Local (non-LSP) target for one selection.
+fn local_target(
+    state: &EditorState,
+    step: SelectionStep,
+    position: usize,
+    anchor: Option<usize>,
+) -> Option<Range<usize>> {
+    let current = selection_range(position, anchor);
+    let around = match step {
+        SelectionStep::Expand => current.clone(),
+        SelectionStep::Shrink => position..position,
+    };
+    let chain = selection_chain(&state.buffer, state.highlighter.language(), around);
+    step_target(step, &chain, &current, position)
+}
+
+impl Editor {
+    /// Grow every selection to the next enclosing syntax range.
+    pub(crate) fn smart_expand_selection(&mut self) {
+        self.smart_select(SelectionStep::Expand);
+    }
+
+    /// Undo the last expand step, or shrink every selection to the largest
+    /// syntax range inside it.
+    pub(crate) fn smart_shrink_selection(&mut self) {
+        if !self.shrink_from_history() {
+            self.smart_select(SelectionStep::Shrink);
+        }
+    }
+
+    fn smart_select(&mut self, step: SelectionStep) {
+        let buffer_id = self.active_buffer();
+        let selections = snapshot(self.active_cursors());
+        if self.request_selection_ranges(buffer_id, step, &selections) {
+            return;
+        }
+        let state = self.active_state();
+        let targets = selections
+            .iter()
+            .map(|&(_, position, anchor)| local_target(state, step, position, anchor))
+            .collect();
+        self.apply_selection_step(buffer_id, step, selections, targets);
+    }
+
+    /// Ask the language server for selection ranges. Returns false when no
+    /// server provides them, so the caller computes the step locally.
+    fn request_selection_ranges(
+        &mut self,
+        buffer_id: BufferId,
+        step: SelectionStep,
+        selections: &CursorSelections,
+    ) -> bool {
+        let window = self.active_window();
+        let Some(state) = window.buffers.get(&buffer_id) else {
+            return false;
+        };
+        let version = state.buffer.version();
+        let positions: Vec<Position> = selections
+            .iter()
+            .map(|&(_, position, anchor)| {
+                // Expanding asks about the start of the selection, so the
+                // nodes that begin there are part of the answer; shrinking
+                // asks about the cursor itself.
+                let at = match step {
+                    SelectionStep::Expand => selection_range(position, anchor).start,
+                    SelectionStep::Shrink => position,
+                };
+                let (line, character) = state.buffer.position_to_lsp_position(at);
+                Position::new(line as u32, character as u32)
+            })
+            .collect();
+        let request_id = window.next_lsp_request_id;
+
+        let sent =
+            self.with_lsp_for_buffer(buffer_id, LspFeature::SelectionRange, |handle, uri, _| {
+                handle.selection_range(request_id, uri.as_uri().clone(), positions)
+            });
+        match sent {
+            Some(Ok(())) => {
+                let window = self.active_window_mut();
+                window.next_lsp_request_id += 1;
+                window.pending_selection_range_requests.insert(
+                    request_id,
+                    SelectionRangeRequest {
+                        buffer_id,
+                        version,
+                        step,
+                        selections: selections.clone(),
+                    },
+                );
+                true
+            }
+            Some(Err(e)) => {
+                tracing::debug!("Failed to request selection ranges: {}", e);
+                false
+            }
+            None => false,
+        }
+    }
+
+    pub(super) fn handle_lsp_selection_ranges(
+        &mut self,
+        request_id: u64,
+        ranges: Result<Vec<SelectionRange>, String>,
+    ) {
+        let Some(request) = self
+            .active_window_mut()
+            .pending_selection_range_requests
+            .remove(&request_id)
+        else {
+            return;
+        };
+        if self.active_buffer() != request.buffer_id
+            || self.active_state().buffer.version() != request.version
+            || snapshot(self.active_cursors()) != request.selections
+        {
+            tracing::debug!("Dropping stale selection ranges (request {})", request_id);
+            return;
+        }
+        let ranges = ranges.unwrap_or_else(|e| {
+            tracing::debug!("Selection range request failed: {}", e);
+            Vec::new()
+        });
+
+        let state = self.active_state();
+        let targets = request
+            .selections
+            .iter()
+            .enumerate()
+            .map(|(i, &(_, position, anchor))| {
+                let current = selection_range(position, anchor);
+                ranges
+                    .get(i)
+                    .and_then(|r| {
+                        step_target(
+                            request.step,
+                            &lsp_chain(&state.buffer, r),
+                            &current,
+                            position,
+                        )
+                    })
+                    // Servers may stop short of the whole file or answer
+                    // with nothing; the local chain covers those cursors.
+                    .or_else(|| local_target(state, request.step, position, anchor))
+            })
+            .collect();
+        self.apply_selection_step(request.buffer_id, request.step, request.selections, targets);
+    }
+
+    /// Move each cursor to its target as one undoable batch and record the
+    /// step for a later shrink.
+    fn apply_selection_step(
+        &mut self,
+        buffer_id: BufferId,
+        step: SelectionStep,
+        selections: CursorSelections,
+        targets: Vec<Option<Range<usize>>>,
+    ) {
+        let cursors = self.active_cursors();
+        let mut events = Vec::new();
+        let mut after = Vec::with_capacity(selections.len());
+        for (&(id, position, anchor), target) in selections.iter().zip(targets) {
+            let (new_position, new_anchor) = match target {
+                // Keep the cursor on the side it was on.
+                Some(r) if r.is_empty() => (r.start, None),
+                Some(r) if anchor.is_some_and(|a| position < a) => (r.start, Some(r.end)),
+                Some(r) => (r.end, Some(r.start)),
+                None => (position, anchor),
+            };
+            after.push((id, new_position, new_anchor));
+            if (new_position, new_anchor) != (position, anchor) {
+                events.push(Event::MoveCursor {
+                    cursor_id: id,
+                    old_position: position,
+                    new_position,
+                    old_anchor: anchor,
+                    new_anchor,
+                    old_sticky_column: cursors.get(id).and_then(|c| c.sticky_column),
+                    new_sticky_column: None,
+                });
+            }
+        }
+        if events.is_empty() {
+            return;
+        }
+
+        let batch = Event::Batch {
+            events,
+            description: match step {
+                SelectionStep::Expand => "Expand selection".to_string(),
+                SelectionStep::Shrink => "Shrink selection".to_string(),
+            },
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+
+        let version = self.active_state().buffer.version();
+        let window = self.active_window_mut();
+        match step {
+            SelectionStep::Expand => {
+                let mut stack = match window.selection_history.take() {
+                    Some(h)
+                        if h.buffer_id == buffer_id
+                            && h.version == version
+                            && h.current == selections =>
+                    {
+                        h.stack
+                    }
+                    _ => Vec::new(),
+                };
+                stack.push(selections);
+                window.selection_history = Some(SelectionHistory {
+                    buffer_id,
+                    version,
+                    current: after,
+                    stack,
+                });
+            }
+            SelectionStep::Shrink => window.selection_history = None,
+        }
+    }
+
+    /// Restore the selections the last expand step replaced. Returns false
+    /// if there is no history for the cursors as they are now.
+    fn shrink_from_history(&mut self) -> bool {
+        let buffer_id = self.active_buffer();
+        let version = self.active_state().buffer.version();
+        let current = snapshot(self.active_cursors());
+        let Some(mut history) = self.active_window_mut().selection_history.take() else {
+            return false;
+        };
+        if history.buffer_id != buffer_id
+            || history.version != version
+            || history.current != current
+        {
+            return false;
+        }
+        let Some(previous) = history.stack.pop() else {
+            return false;
+        };
+
+        let cursors = self.active_cursors();
+        let events: Vec<Event> = current
+            .iter()
+            .zip(&previous)
+            .filter(|(now, before)| now != before)
+            .map(
+                |(&(id, position, anchor), &(_, new_position, new_anchor))| Event::MoveCursor {
+                    cursor_id: id,
+                    old_position: position,
+                    new_position,
+                    old_anchor: anchor,
+                    new_anchor,
+                    old_sticky_column: cursors.get(id).and_then(|c| c.sticky_column),
+                    new_sticky_column: None,
+                },
+            )
+            .collect();
+        if !events.is_empty() {
+            let batch = Event::Batch {
+                events,
+                description: "Shrink selection".to_string(),
+            };
+            self.active_event_log_mut().append(batch.clone());
+            self.apply_event_to_active_buffer(&batch);
+        }
+
+        if !history.stack.is_empty() {
+            history.current = previous;
+            self.active_window_mut().selection_history = Some(history);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lsp_range(
+        start: (u32, u32),
+        end: (u32, u32),
+        parent: Option<SelectionRange>,
+    ) -> SelectionRange {
+        SelectionRange {
+            range: lsp_types::Range::new(
+                Position::new(start.0, start.1),
+                Position::new(end.0, end.1),
+            ),
+            parent: parent.map(Box::new),
+        }
+    }
+
+    #[test]
+    fn test_step_target_expand_skips_equal_ranges() {
+        let chain = vec![2..5, 2..5, 0..9];
+        assert_eq!(
+            step_target(SelectionStep::Expand, &chain, &(2..5), 5),
+            Some(0..9)
+        );
+        assert_eq!(step_target(SelectionStep::Expand, &chain, &(0..9), 9), None);
+    }
+
+    #[test]
+    fn test_step_target_shrink_collapses_without_inner_range() {
+        let chain = vec![2..5, 0..9];
+        assert_eq!(
+            step_target(SelectionStep::Shrink, &chain, &(0..9), 4),
+            Some(2..5)
+        );
+        assert_eq!(
+            step_target(SelectionStep::Shrink, &chain, &(2..5), 4),
+            Some(4..4)
+        );
+        assert_eq!(step_target(SelectionStep::Shrink, &chain, &(4..4), 4), None);
+    }
+
+    #[test]
+    fn test_lsp_chain_flattens_parents_innermost_first() {
+        let buffer = Buffer::from_str_test("let x = foo(bar);\n");
+        let outer = lsp_range((0, 0), (1, 0), None);
+        let call = lsp_range((0, 8), (0, 16), Some(outer));
+        let arg = lsp_range((0, 12), (0, 15), Some(call));
+        assert_eq!(lsp_chain(&buffer, &arg), vec![12..15, 8..16, 0..18]);
+    }
+}
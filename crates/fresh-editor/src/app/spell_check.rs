@@ -0,0 +1,251 @@
+//! Spell checking: loading the checker, the on/off toggle, and the spelling
+//! entries of the code-action popup.
+//!
+//! The checker is loaded lazily the first time a frame needs it and shared
+//! with every visible buffer's [`SpellCheckOverlay`], which does the actual
+//! checking. Code actions on an underlined word offer the dictionary's
+//! suggestions and adding the word to the workspace or user dictionary;
+//! they are tagged [`SPELLING_SOURCE`] and never reach a language server.
+//!
+//! [`SpellCheckOverlay`]: crate::view::spell_check_overlay::SpellCheckOverlay
+
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use lsp_types::{CodeActionOrCommand, Command};
+use rust_i18n::t;
+use serde_json::{json, Value};
+
+use super::Editor;
+use crate::config_keys;
+use crate::model::event::Event;
+use crate::services::spell::{SpellChecker, WordListScope};
+use crate::view::spell_check_overlay::spell_check_namespace;
+
+/// Server name the spelling entries carry in `pending_code_actions`.
+pub(crate) const SPELLING_SOURCE: &str = "spelling";
+
+const REPLACE_COMMAND: &str = "spelling.replace";
+const ADD_TO_WORKSPACE_COMMAND: &str = "spelling.addToWorkspace";
+const ADD_TO_USER_COMMAND: &str = "spelling.addToUser";
+
+/// Corrections offered for one word.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// The spell checker and the settings it was loaded for. A failed load is
+/// kept too, so a missing dictionary is reported once instead of every
+/// frame.
+pub(crate) struct LoadedSpellChecker {
+    language: String,
+    working_dir: PathBuf,
+    checker: Option<Arc<SpellChecker>>,
+}
+
+impl Editor {
+    /// The spell checker for the current settings, loading it if the
+    /// language or working directory changed. `None` when spell checking is
+    /// off or the dictionary could not be loaded.
+    pub(crate) fn spell_checker(&mut self) -> Option<Arc<SpellChecker>> {
+        if !self.config.editor.spell_check {
+            return None;
+        }
+        let language = self.config.editor.spell_check_language.clone();
+        let working_dir = self.working_dir().to_path_buf();
+        if let Some(loaded) = &self.spell_checker {
+            if loaded.language == language && loaded.working_dir == working_dir {
+                return loaded.checker.clone();
+            }
+        }
+
+        let dir = self.dir_context.dictionaries_dir();
+        let checker = match SpellChecker::load(&dir, &language, &working_dir) {
+            Ok(checker) => {
+                tracing::info!("Loaded spell-check dictionary {language} from {dir:?}");
+                Some(Arc::new(checker))
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load spell-check dictionary {language} from {dir:?}: {e}"
+                );
+                self.set_status_message(
+                    t!(
+                        "spell.dictionary_missing",
+                        language = &language,
+                        path = dir.display().to_string()
+                    )
+                    .to_string(),
+                );
+                None
+            }
+        };
+        self.spell_checker = Some(LoadedSpellChecker {
+            language,
+            working_dir,
+            checker: checker.clone(),
+        });
+        checker
+    }
+
+    /// Toggle spell checking on/off
+    pub fn toggle_spell_check(&mut self) {
+        let new_value = !self.config.editor.spell_check;
+        self.config_mut().editor.spell_check = new_value;
+        self.persist_config_change(config_keys::EDITOR_SPELL_CHECK, new_value);
+
+        if new_value {
+            // The dictionary may have been installed since a failed load.
+            if self
+                .spell_checker
+                .as_ref()
+                .is_some_and(|loaded| loaded.checker.is_none())
+            {
+                self.spell_checker = None;
+            }
+            // On failure the load has already said why.
+            if self.spell_checker().is_some() {
+                self.set_status_message(t!("spell.enabled").to_string());
+            }
+        } else {
+            self.set_status_message(t!("spell.disabled").to_string());
+        }
+    }
+
+    /// The underlined word at or just before the primary cursor.
+    fn misspelling_at_cursor(&self) -> Option<(Range<usize>, String)> {
+        let state = self.active_state();
+        state.spell_check_overlay.checker()?;
+        let cursor = self.active_cursors().primary().position;
+        let ns = spell_check_namespace();
+        let range = [cursor, cursor.saturating_sub(1)]
+            .into_iter()
+            .find_map(|pos| {
+                state
+                    .overlays
+                    .at_position(pos, &state.marker_list)
+                    .into_iter()
+                    .find(|overlay| overlay.namespace.as_ref() == Some(&ns))
+                    .map(|overlay| overlay.range(&state.marker_list))
+            })?;
+        let word = String::from_utf8(state.buffer.slice_bytes(range.clone())).ok()?;
+        Some((range, word))
+    }
+
+    /// Code actions for the misspelled word at the cursor: each suggested
+    /// correction, then adding the word to the workspace or user dictionary.
+    /// Empty when the cursor is not on an underlined word.
+    pub(crate) fn spelling_code_actions(&mut self) -> Vec<(String, CodeActionOrCommand)> {
+        let Some((range, word)) = self.misspelling_at_cursor() else {
+            return Vec::new();
+        };
+        let Some(checker) = self.active_state().spell_check_overlay.checker().cloned() else {
+            return Vec::new();
+        };
+
+        let command = |title: String, command: &str, arguments: Vec<Value>| {
+            (
+                SPELLING_SOURCE.to_string(),
+                CodeActionOrCommand::Command(Command {
+                    title,
+                    command: command.to_string(),
+                    arguments: Some(arguments),
+                }),
+            )
+        };
+        let mut actions: Vec<_> = checker
+            .suggest(&word, MAX_SUGGESTIONS)
+            .into_iter()
+            .map(|suggestion| {
+                command(
+                    t!("spell.change_to", word = &suggestion).to_string(),
+                    REPLACE_COMMAND,
+                    vec![
+                        json!(range.start),
+                        json!(range.end),
+                        json!(word),
+                        json!(suggestion),
+                    ],
+                )
+            })
+            .collect();
+        actions.push(command(
+            t!("spell.add_to_workspace", word = &word).to_string(),
+            ADD_TO_WORKSPACE_COMMAND,
+            vec![json!(word)],
+        ));
+        actions.push(command(
+            t!("spell.add_to_user", word = &word).to_string(),
+            ADD_TO_USER_COMMAND,
+            vec![json!(word)],
+        ));
+        actions
+    }
+
+    /// Run a code action produced by [`Self::spelling_code_actions`].
+    pub(crate) fn execute_spelling_action(&mut self, cmd: Command) {
+        let args = cmd.arguments.unwrap_or_default();
+        let arg_str = |i: usize| args.get(i).and_then(Value::as_str);
+        let arg_pos = |i: usize| args.get(i).and_then(Value::as_u64).map(|n| n as usize);
+        match cmd.command.as_str() {
+            REPLACE_COMMAND => {
+                if let (Some(start), Some(end), Some(word), Some(replacement)) =
+                    (arg_pos(0), arg_pos(1), arg_str(2), arg_str(3))
+                {
+                    self.replace_misspelling(start..end, word, replacement);
+                }
+            }
+            ADD_TO_WORKSPACE_COMMAND => {
+                if let Some(word) = arg_str(0) {
+                    self.add_to_spelling_dictionary(word, WordListScope::Workspace);
+                }
+            }
+            ADD_TO_USER_COMMAND => {
+                if let Some(word) = arg_str(0) {
+                    self.add_to_spelling_dictionary(word, WordListScope::User);
+                }
+            }
+            other => tracing::warn!("Unknown spelling command {other}"),
+        }
+    }
+
+    fn replace_misspelling(&mut self, range: Range<usize>, word: &str, replacement: &str) {
+        // The text may have changed while the popup was open.
+        if self.active_state().buffer.slice_bytes(range.clone()) != word.as_bytes() {
+            self.set_status_message(t!("spell.word_changed", word = word).to_string());
+            return;
+        }
+        let cursor_id = self.active_cursors().primary_id();
+        let batch = Event::Batch {
+            events: vec![
+                Event::Delete {
+                    range: range.clone(),
+                    deleted_text: word.to_string(),
+                    cursor_id,
+                },
+                Event::Insert {
+                    position: range.start,
+                    text: replacement.to_string(),
+                    cursor_id,
+                },
+            ],
+            description: "Fix spelling".to_string(),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+    }
+
+    fn add_to_spelling_dictionary(&mut self, word: &str, scope: WordListScope) {
+        let Some(checker) = self.spell_checker() else {
+            return;
+        };
+        let message = match checker.add_word(word, scope) {
+            Ok(()) => t!(
+                "spell.word_added",
+                word = word,
+                path = checker.word_list_path(scope).display().to_string()
+            ),
+            Err(e) => t!("spell.word_add_failed", word = word, error = e.to_string()),
+        };
+        self.set_status_message(message.to_string());
+    }
+}
@@ -0,0 +1,314 @@
+//! Structural project search: the `structural` mode of `beginSearch`.
+//!
+//! Walks the same files as the textual search, but instead of scanning lines
+//! it parses each file with the grammar for its language and matches the
+//! query's code pattern against the tree (see
+//! [`crate::primitives::structural_search`]). Results stream into the same
+//! search handle as `GrepMatch`es; with a rewrite, each carries the text to
+//! put in its place, which the search/replace panel previews and hands back
+//! to `replaceInFile`.
+
+use super::plugin_commands::{finish_search_handle, search_file_glob_matches, IGNORED_DIRS};
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::highlighter::Language;
+use crate::primitives::structural_search::{StructuralMatch, StructuralQuery, MAX_PARSE_BYTES};
+use fresh_core::api::{GrepMatch, SearchHandleState};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// An open buffer's content, searched in place of the file on disk.
+struct BufferText {
+    buffer_id: BufferId,
+    language: Option<Language>,
+    text: Vec<u8>,
+}
+
+/// Convert one file's structural matches into search results.
+fn to_grep_matches(
+    file: &str,
+    buffer_id: usize,
+    source: &[u8],
+    matches: Vec<StructuralMatch>,
+) -> Vec<GrepMatch> {
+    let mut line = 1;
+    let mut counted_to = 0;
+    matches
+        .into_iter()
+        .map(|m| {
+            let start = m.range.start;
+            line += source[counted_to..start]
+                .iter()
+                .filter(|&&b| b == b'\n')
+                .count();
+            counted_to = start;
+            let line_start = source[..start]
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(0, |p| p + 1);
+            let line_end = source[start..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(source.len(), |p| start + p);
+            GrepMatch {
+                file: file.to_string(),
+                buffer_id,
+                byte_offset: start,
+                length: m.range.len(),
+                line,
+                column: start - line_start + 1,
+                context: String::from_utf8_lossy(&source[line_start..line_end]).into_owned(),
+                replacement: m.replacement,
+            }
+        })
+        .collect()
+}
+
+/// Shared state of one structural search run.
+struct SearchRun {
+    query: StructuralQuery,
+    handle: Arc<SearchHandleState>,
+    max_results: usize,
+    match_count: AtomicUsize,
+    /// Whether the pattern parsed in the language of any searched file.
+    compiled_any: AtomicBool,
+    /// Why the pattern didn't parse, reported when it parsed nowhere.
+    compile_error: Mutex<Option<String>>,
+}
+
+impl SearchRun {
+    fn cancelled(&self) -> bool {
+        self.handle.cancel.load(Ordering::Relaxed)
+            || self.match_count.load(Ordering::Relaxed) >= self.max_results
+    }
+
+    /// Whether the pattern can be matched against `language`, recording the
+    /// compile error if not.
+    fn supports(&self, language: &Language) -> bool {
+        match self.query.supports(language) {
+            Ok(()) => {
+                self.compiled_any.store(true, Ordering::Relaxed);
+                true
+            }
+            Err(e) => {
+                let mut error = self.compile_error.lock().unwrap_or_else(|e| e.into_inner());
+                error.get_or_insert(e);
+                false
+            }
+        }
+    }
+
+    /// Match `source` and push the results to the handle.
+    fn search(&self, file: &str, buffer_id: usize, language: &Language, source: &[u8]) {
+        if self.cancelled() || source.len() > MAX_PARSE_BYTES || !self.supports(language) {
+            return;
+        }
+        let mut matches = match self.query.find(language, source) {
+            Ok(matches) => matches,
+            Err(e) => {
+                tracing::debug!("structural search failed for {}: {}", file, e);
+                return;
+            }
+        };
+        let remaining = self
+            .max_results
+            .saturating_sub(self.match_count.load(Ordering::Relaxed));
+        matches.truncate(remaining);
+        if matches.is_empty() {
+            return;
+        }
+        self.match_count.fetch_add(matches.len(), Ordering::Relaxed);
+        let file_matches = to_grep_matches(file, buffer_id, source, matches);
+        let count = file_matches.len();
+        let mut state = match self.handle.state.lock() {
+            Ok(s) => s,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        state.pending.extend(file_matches);
+        state.total_seen += count;
+    }
+
+    fn finish(&self) {
+        let error = if self.compiled_any.load(Ordering::Relaxed) {
+            None
+        } else {
+            self.compile_error
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .take()
+        };
+        let truncated = self.match_count.load(Ordering::Relaxed) >= self.max_results;
+        finish_search_handle(&self.handle, truncated, error);
+    }
+}
+
+impl Editor {
+    /// Start a structural search; results stream into `handle`.
+    pub(super) fn begin_structural_search(
+        &mut self,
+        handle: Arc<SearchHandleState>,
+        pattern: &str,
+        rewrite: &str,
+        max_results: usize,
+        file_glob: String,
+        source_buffer_id: usize,
+    ) {
+        let rewrite = (!rewrite.is_empty()).then_some(rewrite);
+        let query = match StructuralQuery::new(pattern, rewrite) {
+            Ok(query) => query,
+            Err(e) => {
+                finish_search_handle(&handle, false, Some(e));
+                return;
+            }
+        };
+
+        // Snapshot modified buffers on the main thread (the piece tree isn't
+        // `Send`), plus the source buffer when it has no file to walk to.
+        // An open buffer is matched in the language it's displayed in.
+        let mut open_texts: HashMap<PathBuf, BufferText> = HashMap::new();
+        let mut unnamed_source = None;
+        for (&buffer_id, state) in self.buffers().iter() {
+            let path = state.buffer.file_path();
+            let wanted = match path {
+                Some(_) => state.buffer.is_modified(),
+                None => {
+                    buffer_id.0 == source_buffer_id
+                        && search_file_glob_matches(&file_glob, "[No Name]")
+                }
+            };
+            let Some(text) = wanted.then(|| state.buffer.to_string()).flatten() else {
+                continue;
+            };
+            let entry = BufferText {
+                buffer_id,
+                language: Language::from_id(&state.language)
+                    .or_else(|| path.and_then(Language::from_path)),
+                text: text.into_bytes(),
+            };
+            match path {
+                Some(path) => {
+                    open_texts.insert(path.to_path_buf(), entry);
+                }
+                None => unnamed_source = Some(entry),
+            }
+        }
+
+        let filesystem = self.authority().filesystem.clone();
+        let cwd = self.working_dir().to_path_buf();
+        // A source file outside the workspace root is never walked; queue it
+        // explicitly, as the textual search does.
+        let out_of_root_source = (source_buffer_id != 0)
+            .then(|| self.buffers().get(&BufferId(source_buffer_id)))
+            .flatten()
+            .and_then(|state| state.buffer.file_path().map(|p| p.to_path_buf()))
+            .filter(|path| !path.starts_with(&cwd))
+            .filter(|path| search_file_glob_matches(&file_glob, &path.to_string_lossy()));
+
+        let Some(runtime) = &self.tokio_runtime else {
+            finish_search_handle(
+                &handle,
+                false,
+                Some("No tokio runtime available".to_string()),
+            );
+            return;
+        };
+
+        let run = Arc::new(SearchRun {
+            query,
+            handle,
+            max_results,
+            match_count: AtomicUsize::new(0),
+            compiled_any: AtomicBool::new(false),
+            compile_error: Mutex::new(None),
+        });
+        runtime.spawn(async move {
+            let (path_tx, mut path_rx) = tokio::sync::mpsc::channel::<PathBuf>(256);
+
+            let walk_run = Arc::clone(&run);
+            let walk_fs = filesystem.clone();
+            let walk_tx = path_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                if let Err(e) = walk_fs.walk_files(
+                    &cwd,
+                    IGNORED_DIRS,
+                    &walk_run.handle.cancel,
+                    &mut |path, rel| {
+                        if search_file_glob_matches(&file_glob, rel) {
+                            walk_tx.blocking_send(path.to_path_buf()).is_ok()
+                        } else {
+                            true
+                        }
+                    },
+                ) {
+                    tracing::warn!("structural search walk_files failed: {}", e);
+                }
+            });
+            if let Some(path) = out_of_root_source {
+                path_tx.send(path).await.ok();
+            }
+            drop(path_tx);
+
+            let mut joins: Vec<tokio::task::JoinHandle<()>> = Vec::new();
+            if let Some(BufferText {
+                buffer_id,
+                language: Some(language),
+                text,
+            }) = unnamed_source
+            {
+                let run = Arc::clone(&run);
+                joins.push(tokio::task::spawn_blocking(move || {
+                    run.search("[No Name]", buffer_id.0, &language, &text);
+                }));
+            }
+
+            let semaphore = Arc::new(tokio::sync::Semaphore::new(8));
+            while let Some(path) = path_rx.recv().await {
+                if run.cancelled() {
+                    break;
+                }
+                let Ok(permit) = semaphore.clone().acquire_owned().await else {
+                    break;
+                };
+                let open = open_texts.remove(&path);
+                let fs = filesystem.clone();
+                let run = Arc::clone(&run);
+                joins.push(tokio::task::spawn_blocking(move || {
+                    let _permit = permit;
+                    let file = path.to_string_lossy();
+                    match open {
+                        Some(BufferText {
+                            buffer_id,
+                            language,
+                            text,
+                        }) => {
+                            if let Some(language) = language {
+                                run.search(&file, buffer_id.0, &language, &text);
+                            }
+                        }
+                        None => {
+                            let Some(language) = Language::from_path(&path) else {
+                                return;
+                            };
+                            if run.cancelled() || !run.supports(&language) {
+                                return;
+                            }
+                            match fs.read_file(&path) {
+                                Ok(bytes) => run.search(&file, 0, &language, &bytes),
+                                Err(e) => {
+                                    tracing::debug!("read_file failed {:?}: {}", path, e)
+                                }
+                            }
+                        }
+                    }
+                }));
+            }
+
+            for join in joins {
+                drop(join.await);
+            }
+            run.finish();
+        });
+    }
+}
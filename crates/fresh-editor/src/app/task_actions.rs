@@ -0,0 +1,662 @@
+//! Task orchestrators.
+//!
+//! Starting and stopping runs, streaming their output into the utility dock
+//! and publishing what the problem matchers find. Runs are spawned through
+//! the window's authority, so a task in a remote or container workspace runs
+//! there, and the paths it prints are mapped back to host paths before they
+//! become diagnostics.
+//!
+//! Async messages carry only a run id; handlers look up the owning window
+//! with [`Editor::task_run_window_id`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use lsp_types::Diagnostic;
+use rust_i18n::t;
+
+use crate::app::tasks::{
+    next_run_id, problem_to_diagnostic, resolve_problem_path, task_diagnostic_source,
+    LocatedProblem, OutputLines, TaskRun,
+};
+use crate::app::types::LspUri;
+use crate::input::commands::Suggestion;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::lsp::diagnostics::AnchoredDiagnostic;
+use crate::services::tasks::problem_matcher::{ProblemCollector, ProblemMatcher, TaskProblem};
+use crate::services::tasks::{parse_tasks_file, shell_invocation, WORKSPACE_TASKS_PATH};
+use crate::types::{TaskConfig, TaskPresentation};
+use crate::view::prompt::{Prompt, PromptType};
+
+use super::Editor;
+
+impl Editor {
+    /// The tasks that can be run: the `tasks` setting, with the workspace
+    /// task file's entries added (and winning on a name clash). A task file
+    /// that doesn't parse is reported and skipped.
+    fn available_tasks(&mut self) -> BTreeMap<String, TaskConfig> {
+        let mut tasks: BTreeMap<String, TaskConfig> = self
+            .config()
+            .tasks
+            .iter()
+            .map(|(name, task)| (name.clone(), task.clone()))
+            .collect();
+        let window = self.active_window();
+        let path = window.root.join(WORKSPACE_TASKS_PATH);
+        let Ok(bytes) = window.authority().filesystem.read_file(&path) else {
+            return tasks;
+        };
+        match parse_tasks_file(&String::from_utf8_lossy(&bytes)) {
+            Ok(workspace_tasks) => tasks.extend(workspace_tasks),
+            Err(error) => {
+                self.set_status_message(
+                    t!(
+                        "task.file_invalid",
+                        path = WORKSPACE_TASKS_PATH,
+                        error = error
+                    )
+                    .to_string(),
+                );
+            }
+        }
+        tasks
+    }
+
+    /// Open the "Run Task" picker.
+    pub(crate) fn start_run_task_prompt(&mut self) {
+        let tasks = self.available_tasks();
+        if tasks.is_empty() {
+            self.set_status_message(t!("task.none").to_string());
+            return;
+        }
+        let running: Vec<String> = self
+            .active_window()
+            .tasks
+            .runs
+            .values()
+            .map(|run| run.name.clone())
+            .collect();
+        let suggestions: Vec<Suggestion> = tasks
+            .iter()
+            .map(|(name, task)| Suggestion {
+                description_spans: None,
+                text: name.clone(),
+                description: Some(if running.contains(name) {
+                    t!("task.running_description", command = task.command.as_str()).to_string()
+                } else {
+                    task.command.clone()
+                }),
+                value: Some(name.clone()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+        self.active_window_mut().prompt = Some(Prompt::with_suggestions(
+            t!("task.prompt").to_string(),
+            PromptType::RunTask,
+            suggestions,
+        ));
+        if let Some(prompt) = self.active_window_mut().prompt.as_mut() {
+            if !prompt.suggestions.is_empty() {
+                prompt.selected_suggestion = Some(0);
+            }
+        }
+    }
+
+    /// Run the task named `name`, restarting it if it is already running.
+    pub(crate) fn run_task(&mut self, name: &str) {
+        let Some(task) = self.available_tasks().remove(name) else {
+            self.set_status_message(t!("task.unknown", name = name).to_string());
+            return;
+        };
+        let collectors = match task
+            .problem_matchers
+            .iter()
+            .map(|m| ProblemMatcher::from_config(m).map(ProblemCollector::new))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(collectors) => collectors,
+            Err(error) => {
+                self.set_status_message(
+                    t!("task.bad_matcher", name = name, error = error).to_string(),
+                );
+                return;
+            }
+        };
+
+        if let Some(run_id) = self.active_window().tasks.run_id_of(name) {
+            self.stop_task_run(run_id);
+        }
+
+        let root = self.active_window().root.clone();
+        let cwd = match &task.cwd {
+            Some(dir) => crate::app::path_utils::normalize_path(&root.join(dir)),
+            None => root,
+        };
+        let (program, args) = shell_invocation(&task);
+        let run_id = next_run_id();
+        let mut run = TaskRun {
+            name: name.to_string(),
+            background: task.background,
+            collectors,
+            problems: Vec::new(),
+            cwd: cwd.clone(),
+            output_buffer: None,
+            output: OutputLines::default(),
+            terminal: None,
+            kill: None,
+        };
+
+        match task.presentation {
+            TaskPresentation::Output => {
+                let Some(runtime) = self.tokio_runtime.clone() else {
+                    self.set_status_message(t!("task.unavailable").to_string());
+                    return;
+                };
+                let buffer_id = self.task_output_buffer(name);
+                let header = format!("$ {}\n", task_command_line(&task));
+                run.output.push(&header);
+                if let Err(e) = self
+                    .set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(header)])
+                {
+                    tracing::warn!("Failed to reset task output buffer: {}", e);
+                }
+                self.show_in_utility_dock(buffer_id);
+                run.output_buffer = Some(buffer_id);
+
+                let window = self.active_window();
+                let remote_cwd = window
+                    .authority()
+                    .path_translation
+                    .as_ref()
+                    .and_then(|t| t.host_to_remote(&cwd))
+                    .unwrap_or(cwd);
+                let spawner = window.authority().process_spawner.clone();
+                let sender = window.bridge.sender();
+                let (kill_tx, kill_rx) = tokio::sync::oneshot::channel();
+                run.kill = Some(kill_tx);
+
+                runtime.spawn(async move {
+                    let (lines_tx, mut lines_rx) = tokio::sync::mpsc::unbounded_channel();
+                    // Lines that arrive together go out as one message, so a
+                    // burst of compiler output is one buffer update.
+                    let output_sender = sender.clone();
+                    let forward = tokio::spawn(async move {
+                        while let Some(first) = lines_rx.recv().await {
+                            let mut lines = vec![first];
+                            while let Ok(line) = lines_rx.try_recv() {
+                                lines.push(line);
+                            }
+                            if output_sender
+                                .send(AsyncMessage::TaskOutput { run_id, lines })
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                    });
+                    let result = spawner
+                        .spawn_streaming(
+                            program,
+                            args,
+                            Some(remote_cwd.to_string_lossy().into_owned()),
+                            lines_tx,
+                            kill_rx,
+                        )
+                        .await
+                        .map_err(|e| e.to_string());
+                    // The line sender went with the spawn, so this ends once
+                    // everything it sent has been forwarded.
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = forward.await;
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = sender.send(AsyncMessage::TaskFinished { run_id, result });
+                });
+            }
+            TaskPresentation::Terminal => {
+                let mut argv = vec![program];
+                argv.extend(args);
+                let Some(terminal_id) = self.active_window_mut().spawn_terminal_session(
+                    Some(cwd),
+                    false,
+                    Some(argv),
+                    HashMap::new(),
+                ) else {
+                    return;
+                };
+                if let Some(handle) = self.active_window().terminal_manager.get(terminal_id) {
+                    if let Ok(mut state) = handle.state.lock() {
+                        state.tap_lines();
+                    }
+                }
+                let buffer_id = self.create_terminal_buffer_detached(terminal_id);
+                self.show_in_utility_dock(buffer_id);
+                run.terminal = Some(terminal_id);
+            }
+        }
+
+        let window = self.active_window_mut();
+        window.tasks.runs.insert(run_id, run);
+        window.tasks.last_task = Some(name.to_string());
+        window.set_status_message(t!("task.started", name = name).to_string());
+    }
+
+    /// Run the last task started in this window again.
+    pub(crate) fn rerun_last_task(&mut self) {
+        match self.active_window().tasks.last_task.clone() {
+            Some(name) => self.run_task(&name),
+            None => self.start_run_task_prompt(),
+        }
+    }
+
+    /// Stop every task running in this window.
+    pub(crate) fn stop_tasks(&mut self) {
+        let run_ids: Vec<u64> = self.active_window().tasks.runs.keys().copied().collect();
+        if run_ids.is_empty() {
+            self.set_status_message(t!("task.not_running").to_string());
+            return;
+        }
+        for run_id in &run_ids {
+            self.stop_task_run(*run_id);
+        }
+        self.set_status_message(t!("task.stopped", count = run_ids.len()).to_string());
+    }
+
+    /// Kill one run and forget it. Output still queued for it is dropped on
+    /// arrival, and its published problems stay until the task runs again.
+    fn stop_task_run(&mut self, run_id: u64) {
+        let Some(run) = self.active_window_mut().tasks.runs.remove(&run_id) else {
+            return;
+        };
+        if let Some(kill) = run.kill {
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = kill.send(());
+        }
+        if let Some(terminal_id) = run.terminal {
+            self.active_window_mut().terminal_manager.close(terminal_id);
+        }
+    }
+
+    /// The window running `run_id`, if the run is still live.
+    pub(crate) fn task_run_window_id(&self, run_id: u64) -> Option<fresh_core::WindowId> {
+        self.windows
+            .iter()
+            .find(|(_, window)| window.tasks.runs.contains_key(&run_id))
+            .map(|(id, _)| *id)
+    }
+
+    /// Lines from an output-buffer run: appended to its buffer, and fed to
+    /// the matchers. A background task publishes whenever a cycle ends.
+    pub(super) fn handle_task_output(&mut self, run_id: u64, lines: Vec<String>) {
+        let Some(window_id) = self.task_run_window_id(run_id) else {
+            return;
+        };
+        let window = self.windows.get_mut(&window_id).expect("window present");
+        let run = window.tasks.runs.get_mut(&run_id).expect("run present");
+        let mut text = String::new();
+        let mut cycle_ended = false;
+        for line in &lines {
+            text.push_str(line);
+            text.push('\n');
+            cycle_ended |= run.feed(line);
+        }
+        let drop_front = run.output.push(&text);
+        if let Some(buffer_id) = run.output_buffer {
+            if let Err(e) = window.append_virtual_buffer_text(buffer_id, &text, drop_front) {
+                tracing::debug!("Task output buffer gone: {}", e);
+            }
+        }
+        if cycle_ended {
+            self.publish_task_run(window_id, run_id);
+        }
+    }
+
+    /// An output-buffer run's process exited (or failed to start).
+    pub(super) fn handle_task_finished(&mut self, run_id: u64, result: Result<i32, String>) {
+        let Some(window_id) = self.task_run_window_id(run_id) else {
+            return;
+        };
+        self.finish_task_run(window_id, run_id, result);
+    }
+
+    /// New output in a terminal: if a task runs in it, feed the lines it
+    /// finished to the matchers, so a background task publishes as its
+    /// cycles end.
+    pub(super) fn handle_task_terminal_output(&mut self, terminal: fresh_core::WindowTerminalId) {
+        let Some((run_id, lines)) = self.take_task_terminal_lines(terminal, false) else {
+            return;
+        };
+        let window_id = terminal.window;
+        let window = self.windows.get_mut(&window_id).expect("window present");
+        let run = window.tasks.runs.get_mut(&run_id).expect("run present");
+        let mut cycle_ended = false;
+        for line in &lines {
+            cycle_ended |= run.feed(line);
+        }
+        if cycle_ended {
+            self.publish_task_run(window_id, run_id);
+        }
+    }
+
+    /// A terminal exited; if a task was running in it, feed what it printed
+    /// last and finish the run. Call while the terminal is still open.
+    pub(super) fn handle_task_terminal_exited(
+        &mut self,
+        terminal: fresh_core::WindowTerminalId,
+        exit_code: Option<i32>,
+    ) {
+        let Some((run_id, lines)) = self.take_task_terminal_lines(terminal, true) else {
+            return;
+        };
+        let window_id = terminal.window;
+        let window = self.windows.get_mut(&window_id).expect("window present");
+        let run = window.tasks.runs.get_mut(&run_id).expect("run present");
+        for line in &lines {
+            run.feed(line);
+        }
+        let result = exit_code.ok_or_else(|| t!("task.killed").to_string());
+        self.finish_task_run(window_id, run_id, result);
+    }
+
+    /// The run in `terminal`, in the window that owns it, with the lines the
+    /// terminal completed since the last call (see `take_tapped_lines`).
+    fn take_task_terminal_lines(
+        &self,
+        terminal: fresh_core::WindowTerminalId,
+        to_end: bool,
+    ) -> Option<(u64, Vec<String>)> {
+        let window = self.windows.get(&terminal.window)?;
+        let (&run_id, _) = window
+            .tasks
+            .runs
+            .iter()
+            .find(|(_, run)| run.terminal == Some(terminal.terminal))?;
+        let lines = window
+            .terminal_manager
+            .get(terminal.terminal)
+            .and_then(|handle| {
+                handle
+                    .state
+                    .lock()
+                    .ok()
+                    .map(|mut s| s.take_tapped_lines(to_end))
+            })
+            .unwrap_or_default();
+        Some((run_id, lines))
+    }
+
+    fn finish_task_run(
+        &mut self,
+        window_id: fresh_core::WindowId,
+        run_id: u64,
+        result: Result<i32, String>,
+    ) {
+        let count = self.publish_task_run(window_id, run_id);
+        let window = self.windows.get_mut(&window_id).expect("window present");
+        let Some(run) = window.tasks.runs.remove(&run_id) else {
+            return;
+        };
+        let message = match result {
+            Ok(0) => t!("task.succeeded", name = run.name, count = count),
+            Ok(code) => t!("task.failed", name = run.name, code = code, count = count),
+            Err(error) => t!("task.error", name = run.name, error = error),
+        };
+        window.set_status_message(message.to_string());
+    }
+
+    /// Publish a run's current problems; returns how many there are.
+    fn publish_task_run(&mut self, window_id: fresh_core::WindowId, run_id: u64) -> usize {
+        let window = self.windows.get(&window_id).expect("window present");
+        let Some(run) = window.tasks.runs.get(&run_id) else {
+            return 0;
+        };
+        let (name, cwd, problems) = (run.name.clone(), run.cwd.clone(), run.problems.clone());
+        self.publish_task_problems(window_id, &name, &cwd, &problems);
+        problems.len()
+    }
+
+    /// Replace everything `task` previously published with `problems`:
+    /// into the push-diagnostics store (clearing files that are clean now)
+    /// and the problem navigation list.
+    fn publish_task_problems(
+        &mut self,
+        window_id: fresh_core::WindowId,
+        task: &str,
+        cwd: &Path,
+        problems: &[TaskProblem],
+    ) {
+        let source = task_diagnostic_source(task);
+        let window = self.windows.get(&window_id).expect("window present");
+        let translation = window.authority().path_translation.clone();
+
+        let mut by_uri: BTreeMap<String, Vec<Diagnostic>> = BTreeMap::new();
+        let mut located = Vec::new();
+        for problem in problems {
+            let path = resolve_problem_path(&problem.file, cwd, |remote| {
+                translation.as_ref().and_then(|t| t.remote_to_host(remote))
+            });
+            let Some(uri) = LspUri::from_host_path(&path, translation.as_ref()) else {
+                continue;
+            };
+            by_uri
+                .entry(uri.as_str().to_string())
+                .or_default()
+                .push(problem_to_diagnostic(problem, &source));
+            located.push(LocatedProblem {
+                task: task.to_string(),
+                path,
+                line: problem.line,
+                column: problem.column,
+                severity: problem.severity,
+                message: problem.message.clone(),
+            });
+        }
+
+        let is_active = window_id == self.active_window;
+        let anchored: Vec<(String, Vec<AnchoredDiagnostic>)> = by_uri
+            .into_iter()
+            .map(|(uri, diagnostics)| {
+                // Only the active window's buffers can be looked up by URI;
+                // the rest are anchored when their file is next opened.
+                let anchored = if is_active {
+                    self.anchor_diagnostics(&uri, diagnostics)
+                } else {
+                    diagnostics
+                        .into_iter()
+                        .map(|d| AnchoredDiagnostic::capture(d, None))
+                        .collect()
+                };
+                (uri, anchored)
+            })
+            .collect();
+
+        let window = self.windows.get_mut(&window_id).expect("window present");
+        let published: Vec<String> = anchored.iter().map(|(uri, _)| uri.clone()).collect();
+        let previous = window
+            .tasks
+            .published_uris
+            .insert(task.to_string(), published.clone())
+            .unwrap_or_default();
+        window.tasks.set_task_problems(task, located);
+        for uri in &previous {
+            if published.contains(uri) {
+                continue;
+            }
+            if let Some(sources) = window.stored_push_diagnostics.get_mut(uri) {
+                sources.remove(&source);
+                if sources.is_empty() {
+                    window.stored_push_diagnostics.remove(uri);
+                }
+            }
+        }
+        for (uri, diagnostics) in anchored {
+            window
+                .stored_push_diagnostics
+                .entry(uri)
+                .or_default()
+                .insert(source.clone(), diagnostics);
+        }
+
+        if is_active {
+            for uri in previous.iter().chain(published.iter()) {
+                crate::services::lsp::diagnostics::invalidate_cache_for_file(uri);
+                self.merge_and_apply_diagnostics(uri);
+            }
+        }
+    }
+
+    /// Show task problems stored for a file that was not open when they
+    /// were published.
+    pub(super) fn apply_task_diagnostics_to_opened_file(&mut self, path: &Path) {
+        let window = self.active_window();
+        let Some(uri) = LspUri::from_host_path(path, window.authority().path_translation.as_ref())
+        else {
+            return;
+        };
+        let task_sources: Vec<String> = window
+            .tasks
+            .published_uris
+            .keys()
+            .map(|task| task_diagnostic_source(task))
+            .collect();
+        let has_task_problems = window
+            .stored_push_diagnostics
+            .get(uri.as_str())
+            .is_some_and(|sources| sources.keys().any(|s| task_sources.contains(s)));
+        if has_task_problems {
+            self.merge_and_apply_diagnostics(uri.as_str());
+        }
+    }
+
+    /// Jump to the next (or previous) problem reported by a task.
+    pub(crate) fn goto_task_problem(&mut self, forward: bool) {
+        let tasks = &mut self.active_window_mut().tasks;
+        let count = tasks.problems.len();
+        let Some(problem) = tasks.step_problem(forward).cloned() else {
+            self.set_status_message(t!("task.no_problems").to_string());
+            return;
+        };
+        let index = tasks.problem_cursor.unwrap_or(0) + 1;
+        if let Err(e) = self.open_file(&problem.path) {
+            self.set_status_message(
+                t!(
+                    "task.problem_open_failed",
+                    path = problem.path.display().to_string(),
+                    error = e.to_string()
+                )
+                .to_string(),
+            );
+            return;
+        }
+        self.goto_line_col(problem.line as usize, problem.column.map(|c| c as usize));
+        // Set after navigation so it isn't replaced by the "Opened" message.
+        self.set_status_message(
+            t!(
+                "task.problem",
+                index = index,
+                count = count,
+                message = problem.message
+            )
+            .to_string(),
+        );
+    }
+
+    /// The output buffer of `task`, reused across runs while it is open.
+    fn task_output_buffer(&mut self, task: &str) -> BufferId {
+        let window = self.active_window_mut();
+        if let Some(&buffer_id) = window.tasks.output_buffers.get(task) {
+            if window.buffers.contains_key(&buffer_id) {
+                return buffer_id;
+            }
+        }
+        let buffer_id = window.create_virtual_buffer_detached(
+            format!("*Task: {}*", task),
+            "task-output".to_string(),
+            true,
+        );
+        window
+            .tasks
+            .output_buffers
+            .insert(task.to_string(), buffer_id);
+        buffer_id
+    }
+
+    /// Show `buffer_id` in the utility dock, creating the dock if there is
+    /// none, without moving focus out of the editing split.
+    fn show_in_utility_dock(&mut self, buffer_id: BufferId) {
+        use crate::model::event::SplitDirection;
+        use crate::view::split::SplitRole;
+
+        let Some((manager, _)) = self.active_window().buffers.splits() else {
+            return;
+        };
+        let focused = manager.active_split();
+        if let Some(dock_leaf) = manager.find_leaf_by_role(SplitRole::UtilityDock) {
+            let window = self.active_window_mut();
+            if let Some(view_state) = window
+                .split_view_states_mut()
+                .and_then(|states| states.get_mut(&dock_leaf))
+            {
+                view_state.add_buffer(buffer_id);
+                view_state.switch_buffer(buffer_id);
+            }
+            if let Some(manager) = window.split_manager_mut() {
+                manager.set_split_buffer(dock_leaf, buffer_id);
+            }
+        } else {
+            let split = self.split_manager_mut().split_root_positioned(
+                SplitDirection::Horizontal,
+                buffer_id,
+                0.7,
+                false,
+            );
+            let new_leaf = match split {
+                Ok(leaf) => leaf,
+                Err(e) => {
+                    tracing::warn!("Failed to create dock for task output: {}", e);
+                    return;
+                }
+            };
+            let mut view_state = crate::view::split::SplitViewState::with_buffer(
+                self.terminal_width,
+                self.terminal_height,
+                buffer_id,
+            );
+            view_state.apply_config_defaults(crate::view::split::ViewConfigDefaults {
+                line_numbers: false,
+                highlight_current_line: false,
+                line_wrap: self.active_window().resolve_line_wrap_for_buffer(buffer_id),
+                wrap_indent: self.config.editor.wrap_indent,
+                wrap_column: self
+                    .active_window()
+                    .resolve_wrap_column_for_buffer(buffer_id),
+                rulers: Vec::new(),
+                scroll_offset: 0,
+            });
+            let window = self.active_window_mut();
+            if let Some(states) = window.split_view_states_mut() {
+                states.insert(new_leaf, view_state);
+            }
+            if let Some(manager) = window.split_manager_mut() {
+                manager.set_leaf_role(new_leaf, Some(SplitRole::UtilityDock));
+            }
+        }
+        let window = self.active_window_mut();
+        if let Some(manager) = window.split_manager_mut() {
+            manager.set_active_split(focused);
+        }
+        window.resize_visible_terminals();
+    }
+}
+
+/// The task's command line as shown at the top of its output.
+fn task_command_line(task: &TaskConfig) -> String {
+    let (_, args) = shell_invocation(&TaskConfig {
+        env: HashMap::new(),
+        ..task.clone()
+    });
+    args.into_iter().last().unwrap_or_default()
+}
@@ -0,0 +1,287 @@
+//! Task runs owned by each window.
+//!
+//! A run is one execution of a configured task. Its output is shown in the
+//! utility dock — in a read-only output buffer or a terminal — and fed
+//! through the task's problem matchers. The problems are published into the
+//! window's push-diagnostics store under the source `task: <name>`, next to
+//! the language servers', so the gutter, overlays and diagnostics panel show
+//! them without knowing where they came from. Spawning, streaming and
+//! publishing live in `task_actions`.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+
+use crate::model::event::BufferId;
+use crate::services::tasks::problem_matcher::{ProblemCollector, TaskProblem};
+use crate::services::terminal::TerminalId;
+use crate::types::TaskProblemSeverity;
+
+/// Diagnostics-store source of a task's problems.
+pub(crate) fn task_diagnostic_source(name: &str) -> String {
+    format!("task: {}", name)
+}
+
+/// Id for a new run. Global rather than per window: async messages carry
+/// only the id, and handlers find the owning window by it.
+pub(crate) fn next_run_id() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Lines kept in a task's output buffer; older ones are dropped past this.
+pub(crate) const MAX_OUTPUT_LINES: usize = 10_000;
+
+/// Byte length of each line in a task's output buffer, oldest first, so the
+/// buffer can be trimmed from the front as output streams in.
+#[derive(Debug, Default)]
+pub(crate) struct OutputLines {
+    lengths: VecDeque<usize>,
+}
+
+impl OutputLines {
+    /// Record `text`, whole lines, appended to the buffer. Returns how many
+    /// bytes of the oldest lines to drop to stay within `MAX_OUTPUT_LINES`.
+    pub(crate) fn push(&mut self, text: &str) -> usize {
+        self.lengths
+            .extend(text.split_inclusive('\n').map(str::len));
+        let excess = self.lengths.len().saturating_sub(MAX_OUTPUT_LINES);
+        self.lengths.drain(..excess).sum()
+    }
+}
+
+/// One execution of a task.
+pub(crate) struct TaskRun {
+    pub name: String,
+    pub background: bool,
+    pub collectors: Vec<ProblemCollector>,
+    /// Problems found so far in this run, or in the current cycle of a
+    /// background task.
+    pub problems: Vec<TaskProblem>,
+    /// Host directory relative problem paths resolve against.
+    pub cwd: PathBuf,
+    /// Output buffer, when the output goes to one.
+    pub output_buffer: Option<BufferId>,
+    /// Lines shown in the output buffer so far.
+    pub output: OutputLines,
+    /// Terminal the task runs in, when it runs in one.
+    pub terminal: Option<TerminalId>,
+    /// Fired to kill the process (output-buffer runs only).
+    pub kill: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+impl TaskRun {
+    /// Feed one output line through the matchers. Returns whether the line
+    /// ended a background cycle, i.e. the problems are ready to publish.
+    pub(crate) fn feed(&mut self, line: &str) -> bool {
+        use crate::services::tasks::problem_matcher::Boundary;
+
+        let mut cycle_ended = false;
+        for collector in &mut self.collectors {
+            if self.background {
+                match collector.matcher().boundary(line) {
+                    Some(Boundary::Begins) => {
+                        self.problems.clear();
+                        collector.reset();
+                        continue;
+                    }
+                    Some(Boundary::Ends) => {
+                        collector.reset();
+                        cycle_ended = true;
+                        continue;
+                    }
+                    None => {}
+                }
+            }
+            if let Some(problem) = collector.feed(line) {
+                self.problems.push(problem);
+            }
+        }
+        cycle_ended
+    }
+}
+
+/// A published problem, resolved to a host file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LocatedProblem {
+    pub task: String,
+    pub path: PathBuf,
+    /// 1-based, as printed.
+    pub line: u32,
+    /// 1-based, as printed.
+    pub column: Option<u32>,
+    pub severity: TaskProblemSeverity,
+    pub message: String,
+}
+
+/// Per-window task state: the runs in flight and the problems they found.
+#[derive(Default)]
+pub(crate) struct TaskState {
+    pub runs: HashMap<u64, TaskRun>,
+    /// Output buffer of each task, reused by its later runs.
+    pub output_buffers: HashMap<String, BufferId>,
+    /// Last task started, for "Rerun Last Task".
+    pub last_task: Option<String>,
+    /// Diagnostics-store URIs each task has published problems for, so
+    /// the next publish can clear the files that are clean now.
+    pub published_uris: HashMap<String, Vec<String>>,
+    /// Every published problem in output order, for "Next/Previous
+    /// Problem".
+    pub problems: Vec<LocatedProblem>,
+    /// Index into `problems` of the last one visited.
+    pub problem_cursor: Option<usize>,
+}
+
+impl TaskState {
+    /// The run of `name` in flight, if any.
+    pub(crate) fn run_id_of(&self, name: &str) -> Option<u64> {
+        self.runs
+            .iter()
+            .find(|(_, run)| run.name == name)
+            .map(|(id, _)| *id)
+    }
+
+    /// Replace the problems `task` contributes to the navigation list.
+    pub(crate) fn set_task_problems(&mut self, task: &str, problems: Vec<LocatedProblem>) {
+        self.problems.retain(|p| p.task != task);
+        self.problems.extend(problems);
+        self.problem_cursor = None;
+    }
+
+    /// Step to the next (or previous) problem, wrapping around. Returns the
+    /// problem landed on.
+    pub(crate) fn step_problem(&mut self, forward: bool) -> Option<&LocatedProblem> {
+        let count = self.problems.len();
+        if count == 0 {
+            return None;
+        }
+        let index = match (self.problem_cursor, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        };
+        self.problem_cursor = Some(index);
+        self.problems.get(index)
+    }
+}
+
+/// Resolve a path printed by a task to a host path. Relative paths are
+/// relative to the task's working directory; absolute ones are in the
+/// authority's namespace, so `remote_to_host` maps them back.
+pub(crate) fn resolve_problem_path(
+    printed: &str,
+    cwd: &Path,
+    remote_to_host: impl Fn(&Path) -> Option<PathBuf>,
+) -> PathBuf {
+    let printed = Path::new(printed);
+    let path = if printed.is_absolute() {
+        remote_to_host(printed).unwrap_or_else(|| printed.to_path_buf())
+    } else {
+        cwd.join(printed)
+    };
+    crate::app::path_utils::normalize_path(&path)
+}
+
+/// The diagnostic a problem is published as. Without an end column the
+/// range covers the character at the start.
+pub(crate) fn problem_to_diagnostic(problem: &TaskProblem, source: &str) -> Diagnostic {
+    let line = problem.line.saturating_sub(1);
+    let character = problem.column.unwrap_or(1).saturating_sub(1);
+    let end_line = problem.end_line.map_or(line, |l| l.saturating_sub(1));
+    let end_character = match problem.end_column {
+        Some(c) => c.saturating_sub(1),
+        None if end_line == line => character + 1,
+        None => 0,
+    };
+    Diagnostic {
+        range: Range::new(
+            Position::new(line, character),
+            Position::new(end_line, end_character),
+        ),
+        severity: Some(match problem.severity {
+            TaskProblemSeverity::Error => DiagnosticSeverity::ERROR,
+            TaskProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+            TaskProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+            TaskProblemSeverity::Hint => DiagnosticSeverity::HINT,
+        }),
+        code: problem.code.clone().map(NumberOrString::String),
+        source: Some(source.to_string()),
+        message: problem.message.clone(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(line: u32, column: Option<u32>) -> TaskProblem {
+        TaskProblem {
+            file: "src/main.rs".to_string(),
+            line,
+            column,
+            end_line: None,
+            end_column: None,
+            severity: TaskProblemSeverity::Warning,
+            code: Some("E1".to_string()),
+            message: "oops".to_string(),
+        }
+    }
+
+    #[test]
+    fn problems_become_zero_based_diagnostics() {
+        let diagnostic = problem_to_diagnostic(&problem(4, Some(18)), "task: build");
+        assert_eq!(diagnostic.range.start, Position::new(3, 17));
+        assert_eq!(diagnostic.range.end, Position::new(3, 18));
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(diagnostic.source.as_deref(), Some("task: build"));
+
+        let whole_line = problem_to_diagnostic(&problem(1, None), "task: build");
+        assert_eq!(whole_line.range.start, Position::new(0, 0));
+    }
+
+    #[test]
+    fn output_lines_drop_the_oldest_past_the_cap() {
+        let mut output = OutputLines::default();
+        assert_eq!(output.push("$ make\n"), 0);
+        assert_eq!(output.push(&"x\n".repeat(MAX_OUTPUT_LINES - 1)), 0);
+        assert_eq!(output.push("last\nline\n"), "$ make\n".len() + "x\n".len());
+    }
+
+    #[test]
+    fn problem_paths_resolve_against_the_task_directory() {
+        let cwd = Path::new("/work/crate");
+        assert_eq!(
+            resolve_problem_path("../lib/src/a.rs", cwd, |_| None),
+            PathBuf::from("/work/lib/src/a.rs")
+        );
+        assert_eq!(
+            resolve_problem_path("/workspaces/app/a.rs", cwd, |p| p
+                .strip_prefix("/workspaces/app")
+                .ok()
+                .map(|rest| Path::new("/home/me/app").join(rest))),
+            PathBuf::from("/home/me/app/a.rs")
+        );
+    }
+
+    #[test]
+    fn problem_navigation_wraps_both_ways() {
+        let located = |line| LocatedProblem {
+            task: "build".to_string(),
+            path: PathBuf::from("/a.rs"),
+            line,
+            column: None,
+            severity: TaskProblemSeverity::Error,
+            message: String::new(),
+        };
+        let mut state = TaskState::default();
+        state.set_task_problems("build", vec![located(1), located(2)]);
+        assert_eq!(state.step_problem(false).map(|p| p.line), Some(2));
+        assert_eq!(state.step_problem(true).map(|p| p.line), Some(1));
+        assert_eq!(state.step_problem(true).map(|p| p.line), Some(2));
+        assert_eq!(state.step_problem(true).map(|p| p.line), Some(1));
+    }
+}
@@ -2323,13 +2323,15 @@ impl Window {
             // Pushing it here keeps it on the same one-directional funnel as
             // the PTY size, for every split showing the terminal.
             if grid_cols > 0 {
+                let wrap_column =
+                    self.terminal_scrollback_wrap_column(buffer_id, grid_cols as usize);
                 if let Some(vs) = self
                     .buffers
                     .split_view_states_mut()
                     .and_then(|vs_map| vs_map.get_mut(&split_id))
                 {
                     if let Some(buf_state) = vs.buffer_state_mut(buffer_id) {
-                        buf_state.viewport.wrap_column = Some(grid_cols as usize);
+                        buf_state.viewport.wrap_column = Some(wrap_column);
                     }
                 }
             }
@@ -2370,12 +2372,16 @@ impl Window {
         // Grid width at capture time — the scroll-back view wraps at this
         // exact column count so it lays out identically to the live grid.
         let mut grid_cols: Option<usize> = None;
+        // Command blocks recorded so far; their line numbers already count
+        // the visible screen appended below.
+        let mut command_blocks = Vec::new();
         if let Some(handle) = self.terminal_manager.get(terminal_id) {
             if let Ok(mut state) = handle.state.lock() {
                 use std::io::BufWriter;
 
                 let (cols, _) = state.size();
                 grid_cols = Some(cols as usize);
+                command_blocks = state.command_blocks().to_vec();
 
                 // Flush any scrollback that has scrolled off but isn't in the
                 // file yet — in particular the lines a resize spilled from the
@@ -2468,6 +2474,8 @@ impl Window {
             state.editing_disabled = true;
             state.margins.configure_for_line_numbers(false);
         }
+        self.apply_terminal_command_blocks(buffer_id, command_blocks);
+        let grid_cols = grid_cols.map(|cols| self.terminal_scrollback_wrap_column(buffer_id, cols));
 
         // Refresh line-wrap state for the scroll-back view and arm the
         // skip_ensure_visible flag so the next render does *not* run
@@ -0,0 +1,371 @@
+//! Shell-integration command blocks in the embedded terminal.
+//!
+//! `TerminalState` records a [`CommandBlock`] for every prompt the shell
+//! marks with OSC 133 (see [`crate::services::terminal::command_blocks`]).
+//! Each time the scroll-back buffer is (re)loaded from the backing file, the
+//! blocks are laid onto it: an exit-status mark in the gutter next to each
+//! prompt and a fold range over each command's output, so `toggle_fold`
+//! collapses it like any other region. The actions here navigate between
+//! prompts, select / copy a command's output, re-run a command and fold long
+//! outputs. Invoked from the live terminal, they act on the most recent
+//! command and drop into scroll-back to show the result.
+
+use crate::app::window::Window;
+use crate::app::Editor;
+use crate::model::event::BufferId;
+use crate::services::terminal::command_blocks::{self, CommandBlock};
+use crate::view::folding::FoldManager;
+use crate::view::margin::LineIndicator;
+use ratatui::style::Color;
+use rust_i18n::t;
+
+/// Gutter namespace of the exit-status marks.
+const COMMAND_BLOCKS_NAMESPACE: &str = "terminal-commands";
+
+/// Outputs at least this many lines long are collapsed by
+/// [`Editor::toggle_terminal_output_folds`].
+const FOLD_MIN_OUTPUT_LINES: usize = 20;
+
+fn exit_status_indicator(block: &CommandBlock) -> LineIndicator {
+    match block.exit_code {
+        Some(0) => LineIndicator::new("✔", Color::Green, 10),
+        Some(_) => LineIndicator::new("✘", Color::Red, 10),
+        None => LineIndicator::new("•", Color::DarkGray, 10),
+    }
+}
+
+impl Window {
+    /// Lay `blocks` onto the freshly loaded scroll-back buffer of a terminal:
+    /// exit-status marks on prompt lines and fold ranges over outputs. Folds
+    /// collapsed during an earlier visit pointed into the replaced buffer, so
+    /// they are dropped.
+    pub(crate) fn apply_terminal_command_blocks(
+        &mut self,
+        buffer_id: BufferId,
+        blocks: Vec<CommandBlock>,
+    ) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        state
+            .margins
+            .clear_line_indicators_for_namespace(COMMAND_BLOCKS_NAMESPACE);
+        let line_count = state.buffer.line_count().unwrap_or(0);
+        let mut ranges = Vec::new();
+        for block in &blocks {
+            let Some(output) = block.output_range(line_count) else {
+                continue;
+            };
+            if let Some(offset) = state.buffer.line_start_offset(block.prompt_line) {
+                state.margins.set_line_indicator(
+                    offset,
+                    COMMAND_BLOCKS_NAMESPACE.to_string(),
+                    exit_status_indicator(block),
+                );
+            }
+            // The header is the line the command was typed on.
+            if output.start > 0 && !output.is_empty() {
+                ranges.push(lsp_types::FoldingRange {
+                    start_line: (output.start - 1) as u32,
+                    end_line: (output.end - 1) as u32,
+                    collapsed_text: Some(
+                        t!("terminal.folded_output", count = output.len()).to_string(),
+                    ),
+                    ..Default::default()
+                });
+            }
+        }
+        state
+            .folding_ranges
+            .set_from_lsp(&state.buffer, &mut state.marker_list, ranges);
+        state.margins.keep_indicator_slot = !blocks.is_empty();
+
+        if let Some(view_states) = self.buffers.split_view_states_mut() {
+            for vs in view_states.values_mut() {
+                if let Some(buf_state) = vs.buffer_state_mut(buffer_id) {
+                    buf_state.folds = FoldManager::new();
+                }
+            }
+        }
+        self.terminal_command_blocks.insert(buffer_id, blocks);
+    }
+
+    /// The column a terminal's scroll-back view wraps at, given the grid
+    /// width: the exit-status gutter, when shown, comes out of it.
+    pub(crate) fn terminal_scrollback_wrap_column(
+        &self,
+        buffer_id: BufferId,
+        cols: usize,
+    ) -> usize {
+        let gutter = self
+            .buffers
+            .get(&buffer_id)
+            .is_some_and(|state| state.margins.keep_indicator_slot);
+        if gutter {
+            cols.saturating_sub(1).max(1)
+        } else {
+            cols
+        }
+    }
+
+    /// The blocks recorded by a terminal's emulator, for acting on the most
+    /// recent command while the terminal is live.
+    fn live_terminal_command_blocks(&self, buffer_id: BufferId) -> Vec<CommandBlock> {
+        self.get_terminal_id(buffer_id)
+            .and_then(|id| self.terminal_manager.get(id))
+            .and_then(|handle| {
+                handle
+                    .state
+                    .lock()
+                    .ok()
+                    .map(|state| state.command_blocks().to_vec())
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Editor {
+    /// The scroll-back line the cursor is on.
+    fn terminal_cursor_line(&self) -> usize {
+        let position = self.active_cursors().primary().position;
+        self.active_state().buffer.get_line_number(position)
+    }
+
+    /// The blocks laid onto the active terminal's scroll-back buffer, or
+    /// `None` (with a status message) when it has none.
+    fn scrollback_command_blocks(&mut self) -> Option<Vec<CommandBlock>> {
+        let buffer_id = self.active_buffer();
+        let blocks = self
+            .active_window()
+            .terminal_command_blocks
+            .get(&buffer_id)
+            .filter(|blocks| !blocks.is_empty())
+            .cloned();
+        if blocks.is_none() {
+            self.set_status_message(t!("terminal.no_command_blocks").to_string());
+        }
+        blocks
+    }
+
+    /// Scroll `top_line` to the top of the view and move the cursor to the
+    /// start of line `end` — selecting from the start of line `from` up to
+    /// the end of the line before `end` when `from` is given.
+    fn reveal_terminal_lines(&mut self, top_line: usize, from: Option<usize>, end: usize) {
+        let buffer_id = self.active_buffer();
+        let (start, end) = {
+            let buffer = &self.active_state().buffer;
+            let line_start = |line| buffer.line_start_offset(line).unwrap_or(buffer.len());
+            (from.map(line_start), line_start(end))
+        };
+        match start {
+            Some(start) => {
+                // Stop short of the newline ending the last selected line.
+                let end = if end > start { end - 1 } else { end };
+                self.goto_byte_offset(start);
+                self.extend_terminal_selection_to(end);
+            }
+            None => self.goto_byte_offset(end),
+        }
+        let split = self.active_window().effective_active_split();
+        self.active_window_mut()
+            .scroll_split_viewport_to(buffer_id, split, top_line, true);
+    }
+
+    /// Move the cursor to `position`, anchoring a selection where it was.
+    fn extend_terminal_selection_to(&mut self, position: usize) {
+        let buffer_id = self.active_buffer();
+        let cursors = self.active_cursors();
+        let cursor = cursors.primary();
+        let event = crate::model::event::Event::MoveCursor {
+            cursor_id: cursors.primary_id(),
+            old_position: cursor.position,
+            new_position: position,
+            old_anchor: cursor.anchor,
+            new_anchor: Some(cursor.position),
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: None,
+        };
+        let split = self.active_window().effective_active_split();
+        self.active_window_mut()
+            .apply_event_to_buffer(buffer_id, split, &event);
+    }
+
+    /// Jump to the previous (or next) shell prompt in the active terminal.
+    /// From the live terminal, "previous" drops into scroll-back at the
+    /// prompt before the one waiting for input.
+    pub fn jump_to_terminal_prompt(&mut self, forward: bool) {
+        if !self
+            .active_window()
+            .is_terminal_buffer(self.active_buffer())
+        {
+            return;
+        }
+        let from_live = self.active_window().focused_terminal_live();
+        if from_live {
+            if forward {
+                return;
+            }
+            self.enter_terminal_scrollback();
+        }
+        let Some(blocks) = self.scrollback_command_blocks() else {
+            return;
+        };
+        let from_line = match blocks.last() {
+            Some(last) if from_live && last.output_line.is_none() => last.prompt_line,
+            _ if from_live => usize::MAX,
+            _ => self.terminal_cursor_line(),
+        };
+        let target = if forward {
+            command_blocks::next_prompt(&blocks, from_line)
+        } else {
+            command_blocks::previous_prompt(&blocks, from_line)
+        };
+        match target {
+            Some(line) => self.reveal_terminal_lines(line, None, line),
+            None => self.set_status_message(t!("terminal.no_more_prompts").to_string()),
+        }
+    }
+
+    /// Select the output of the command at the cursor (the most recent
+    /// finished one from the live terminal) and, with `copy`, copy it.
+    pub fn select_terminal_command_output(&mut self, copy: bool) {
+        if !self
+            .active_window()
+            .is_terminal_buffer(self.active_buffer())
+        {
+            return;
+        }
+        let from_live = self.active_window().focused_terminal_live();
+        if from_live {
+            self.enter_terminal_scrollback();
+        }
+        let Some(blocks) = self.scrollback_command_blocks() else {
+            return;
+        };
+        let block = if from_live {
+            command_blocks::last_finished(&blocks)
+        } else {
+            command_blocks::block_at(&blocks, self.terminal_cursor_line())
+        };
+        let line_count = self.active_state().buffer.line_count().unwrap_or(0);
+        let Some((block, output)) = block
+            .and_then(|b| b.output_range(line_count).map(|output| (b, output)))
+            .filter(|(_, output)| !output.is_empty())
+        else {
+            self.set_status_message(t!("terminal.no_command_output").to_string());
+            return;
+        };
+        self.reveal_terminal_lines(block.prompt_line, Some(output.start), output.end);
+        if copy {
+            self.copy_selection();
+            self.set_status_message(
+                t!("terminal.copied_command_output", count = output.len()).to_string(),
+            );
+        }
+    }
+
+    /// Run the command at the cursor (the most recent one from the live
+    /// terminal) again, back in the live terminal.
+    pub fn rerun_terminal_command(&mut self) {
+        let buffer_id = self.active_buffer();
+        if !self.active_window().is_terminal_buffer(buffer_id) {
+            return;
+        }
+        let command = if self.active_window().focused_terminal_live() {
+            self.active_window()
+                .live_terminal_command_blocks(buffer_id)
+                .into_iter()
+                .rev()
+                .find_map(|block| block.command)
+        } else {
+            let line = self.terminal_cursor_line();
+            self.active_window()
+                .terminal_command_blocks
+                .get(&buffer_id)
+                .and_then(|blocks| command_blocks::block_at(blocks, line))
+                .and_then(|block| block.command.clone())
+        };
+        let Some(command) = command else {
+            self.set_status_message(t!("terminal.no_command_to_rerun").to_string());
+            return;
+        };
+        let alive = self
+            .active_window()
+            .get_terminal_id(buffer_id)
+            .and_then(|id| self.active_window().terminal_manager.get(id))
+            .is_some_and(|handle| handle.is_alive());
+        if !alive {
+            self.set_status_message(t!("terminal.not_running").to_string());
+            return;
+        }
+        if !self.active_window().focused_terminal_live() {
+            self.enter_terminal_mode();
+        }
+        self.active_window_mut()
+            .send_terminal_input(format!("{}\r", command).as_bytes());
+        self.set_status_message(t!("terminal.rerunning_command", command = &command).to_string());
+    }
+
+    /// Collapse every command output of at least [`FOLD_MIN_OUTPUT_LINES`]
+    /// lines in the terminal's scroll-back view, or expand them again when
+    /// anything is folded.
+    pub fn toggle_terminal_output_folds(&mut self) {
+        let buffer_id = self.active_buffer();
+        if !self.active_window().is_terminal_buffer(buffer_id) {
+            return;
+        }
+        if self.active_window().focused_terminal_live() {
+            self.enter_terminal_scrollback();
+        }
+        let folded = self
+            .active_window()
+            .buffers
+            .split_view_states()
+            .is_some_and(|view_states| {
+                view_states
+                    .values()
+                    .filter_map(|vs| vs.keyed_states.get(&buffer_id))
+                    .any(|buf_state| !buf_state.folds.is_empty())
+            });
+        if folded {
+            self.active_window_mut().clear_folds(buffer_id);
+            self.set_status_message(t!("terminal.command_outputs_expanded").to_string());
+            return;
+        }
+        let Some(blocks) = self.scrollback_command_blocks() else {
+            return;
+        };
+        let ranges: Vec<(usize, usize, usize)> = {
+            let buffer = &self.active_state().buffer;
+            let line_count = buffer.line_count().unwrap_or(0);
+            blocks
+                .iter()
+                .filter_map(|block| block.output_range(line_count))
+                .filter(|output| output.start > 0 && output.len() >= FOLD_MIN_OUTPUT_LINES)
+                .filter_map(|output| {
+                    let start = buffer.line_start_offset(output.start)?;
+                    let end = buffer.line_start_offset(output.end).unwrap_or(buffer.len());
+                    Some((start, end, output.len()))
+                })
+                .collect()
+        };
+        if ranges.is_empty() {
+            self.set_status_message(
+                t!(
+                    "terminal.no_long_command_outputs",
+                    count = FOLD_MIN_OUTPUT_LINES
+                )
+                .to_string(),
+            );
+            return;
+        }
+        for &(start, end, lines) in &ranges {
+            let placeholder = t!("terminal.folded_output", count = lines).to_string();
+            self.active_window_mut()
+                .add_fold(buffer_id, start, end, Some(placeholder));
+        }
+        self.set_status_message(
+            t!("terminal.command_outputs_folded", count = ranges.len()).to_string(),
+        );
+    }
+}
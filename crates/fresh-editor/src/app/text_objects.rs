@@ -0,0 +1,222 @@
+//! Tree-sitter text objects: select a function, class, parameter, comment or
+//! block, jump between functions and classes, and swap a parameter with its
+//! neighbour.
+//!
+//! Occurrences come from the language's `textobjects.scm`
+//! ([`crate::primitives::text_objects`]). Selecting moves every cursor in one
+//! `Event::Batch`; a swap is one delete and one insert over the span of both
+//! parameters, so a single undo restores it.
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::Event;
+use crate::primitives::text_objects::{
+    find_text_objects, parameter_swap, select_text_object, text_object_start, TextObject,
+    TextObjectKind, TextObjectRange,
+};
+
+impl Editor {
+    /// Occurrences of `kind` in the active buffer, or a status message and
+    /// `None` when its language has no text objects.
+    fn active_text_objects(&mut self, kind: TextObjectKind) -> Option<Vec<TextObjectRange>> {
+        let state = self.active_state();
+        let found = state
+            .highlighter
+            .language()
+            .and_then(|language| find_text_objects(&state.buffer, language, kind));
+        if found.is_none() {
+            self.set_status_message(t!("text_object.unsupported").to_string());
+        }
+        found
+    }
+
+    fn parse_text_object(&mut self, name: &str) -> Option<TextObject> {
+        let object = TextObject::parse(name);
+        if object.is_none() {
+            self.set_status_message(t!("text_object.unknown", object = name).to_string());
+        }
+        object
+    }
+
+    /// Select the text object `name` (e.g. `function.inner`) around each
+    /// cursor, or the next one after it.
+    pub(crate) fn select_text_object(&mut self, name: &str) {
+        let Some(object) = self.parse_text_object(name) else {
+            return;
+        };
+        let Some(objects) = self.active_text_objects(object.kind) else {
+            return;
+        };
+
+        let mut events = Vec::new();
+        for (cursor_id, cursor) in self.active_cursors().iter() {
+            let anchor = cursor.anchor.unwrap_or(cursor.position);
+            let current = cursor.position.min(anchor)..cursor.position.max(anchor);
+            let Some(target) = select_text_object(&objects, object.inner, &current) else {
+                continue;
+            };
+            events.push(Event::MoveCursor {
+                cursor_id,
+                old_position: cursor.position,
+                new_position: target.end,
+                old_anchor: cursor.anchor,
+                new_anchor: (!target.is_empty()).then_some(target.start),
+                old_sticky_column: cursor.sticky_column,
+                new_sticky_column: None,
+            });
+        }
+        if events.is_empty() {
+            self.set_status_message(t!("text_object.not_found", object = name).to_string());
+            return;
+        }
+        let batch = Event::Batch {
+            events,
+            description: "Select text object".to_string(),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+    }
+
+    /// Move each cursor to the start of the next (or previous) occurrence of
+    /// the text object `name`, e.g. `function`.
+    pub(crate) fn goto_text_object(&mut self, name: &str, forward: bool) {
+        let Some(object) = self.parse_text_object(name) else {
+            return;
+        };
+        let Some(objects) = self.active_text_objects(object.kind) else {
+            return;
+        };
+
+        let mut events = Vec::new();
+        for (cursor_id, cursor) in self.active_cursors().iter() {
+            let Some(start) = text_object_start(&objects, cursor.position, forward) else {
+                continue;
+            };
+            events.push(Event::MoveCursor {
+                cursor_id,
+                old_position: cursor.position,
+                new_position: start,
+                old_anchor: cursor.anchor,
+                new_anchor: None,
+                old_sticky_column: cursor.sticky_column,
+                new_sticky_column: None,
+            });
+        }
+        if events.is_empty() {
+            self.set_status_message(t!("text_object.not_found", object = name).to_string());
+            return;
+        }
+        let batch = Event::Batch {
+            events,
+            description: "Go to text object".to_string(),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+    }
+
+    /// Swap the parameter (or argument, or list element) under the primary
+    /// cursor with the next or previous one. The cursor stays on the
+    /// parameter it was on, at its new place.
+    pub(crate) fn swap_parameter(&mut self, forward: bool) {
+        if self.refuse_if_editing_disabled() {
+            return;
+        }
+        let Some(parameters) = self.active_text_objects(TextObjectKind::Parameter) else {
+            return;
+        };
+        let cursors = self.active_cursors();
+        let cursor_id = cursors.primary_id();
+        let cursor = *cursors.primary();
+        let Some((current, other)) = parameter_swap(&parameters, cursor.position, forward) else {
+            self.set_status_message(t!("text_object.no_swap").to_string());
+            return;
+        };
+
+        let (first, second) = if current.start < other.start {
+            (current.clone(), other)
+        } else {
+            (other, current.clone())
+        };
+        let state = self.active_state_mut();
+        let span = state.get_text_range(first.start, second.end);
+        let first_text = state.get_text_range(first.start, first.end);
+        let between = state.get_text_range(first.end, second.start);
+        let second_text = state.get_text_range(second.start, second.end);
+        let swapped = format!("{second_text}{between}{first_text}");
+
+        // Where the cursor's parameter starts once the two have traded places.
+        let moved_start = if current.start == first.start {
+            second.end - first_text.len()
+        } else {
+            first.start
+        };
+        let offset = cursor
+            .position
+            .saturating_sub(current.start)
+            .min(current.len());
+
+        let events = vec![
+            Event::Delete {
+                range: first.start..second.end,
+                deleted_text: span,
+                cursor_id,
+            },
+            Event::Insert {
+                position: first.start,
+                text: swapped,
+                cursor_id,
+            },
+            // The insert leaves the cursor after both parameters.
+            Event::MoveCursor {
+                cursor_id,
+                old_position: second.end,
+                new_position: moved_start + offset,
+                old_anchor: None,
+                new_anchor: None,
+                old_sticky_column: None,
+                new_sticky_column: None,
+            },
+        ];
+        let batch = Event::Batch {
+            events,
+            description: "Swap parameters".to_string(),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+    }
+
+    /// Handle `GetTextObjectRange` from a plugin.
+    #[cfg(feature = "plugins")]
+    pub(super) fn handle_get_text_object_range(
+        &mut self,
+        buffer_id: crate::model::event::BufferId,
+        position: usize,
+        object: String,
+        request_id: u64,
+    ) {
+        let callback_id = fresh_core::api::JsCallbackId::from(request_id);
+        let plugins = self.plugin_manager.read().unwrap();
+        let Some(parsed) = TextObject::parse(&object) else {
+            plugins.reject_callback(callback_id, format!("Unknown text object: {object}"));
+            return;
+        };
+        let span = self
+            .active_window()
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| {
+                let language = state.highlighter.language()?;
+                find_text_objects(&state.buffer, language, parsed.kind)
+            })
+            .and_then(|objects| select_text_object(&objects, parsed.inner, &(position..position)))
+            .map(|range| fresh_core::api::TextObjectSpan {
+                start: range.start,
+                end: range.end,
+            });
+        plugins.resolve_callback(
+            callback_id,
+            serde_json::to_string(&span).unwrap_or_else(|_| "null".to_string()),
+        );
+    }
+}
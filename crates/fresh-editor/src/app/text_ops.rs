@@ -181,12 +181,26 @@ impl Editor {
     /// Toggle comment on the current line or selection
     pub(super) fn toggle_comment(&mut self) {
         // Determine comment prefix from language config
-        // If no language detected or no comment prefix configured, do nothing
-        let language = &self.active_state().language;
+        // If no language detected or no comment prefix configured, do nothing.
+        // Inside code embedded in another language (a `<script>` in Templ, a
+        // tagged template in JavaScript) that language's config decides, when
+        // it has one.
+        let state = self.active_state();
+        let cursor = self.active_cursors().primary();
+        let position = cursor
+            .selection_range()
+            .map_or(cursor.position, |range| range.start);
+        let injected = state
+            .highlighter
+            .injected_language_at(&state.buffer, position);
+        let language = injected
+            .map(|injected| injected.language_id)
+            .filter(|id| self.config.languages.contains_key(id))
+            .unwrap_or_else(|| state.language.clone());
         let comment_prefix = self
             .config
             .languages
-            .get(language)
+            .get(&language)
             .and_then(|lang_config| lang_config.comment_prefix.clone());
 
         let comment_prefix: String = match comment_prefix {
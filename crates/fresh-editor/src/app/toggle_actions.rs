@@ -420,6 +420,50 @@ impl Editor {
         self.set_status_message(t!("view.fold_indicators_state", state = status).to_string());
     }
 
+    /// Toggle the minimap column for the current buffer only.
+    ///
+    /// Pins the (split, buffer) pair on or off regardless of
+    /// `editor.show_minimap`; the override is persisted in the per-file
+    /// workspace state.
+    pub fn toggle_minimap_current_buffer(&mut self) {
+        let active_split = self
+            .windows
+            .get(&self.active_window)
+            .and_then(|w| w.buffers.splits())
+            .map(|(mgr, _)| mgr)
+            .expect("active window must have a populated split layout")
+            .active_split();
+        // Virtual buffers ignore the global default (see `minimap_enabled`),
+        // so the first toggle on one turns the minimap on.
+        let is_virtual = self
+            .active_window()
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .is_some_and(|m| m.is_virtual());
+        let default = self.config.editor.show_minimap && !is_virtual;
+        let Some(new_value) = self
+            .windows
+            .get_mut(&self.active_window)
+            .and_then(|w| w.split_view_states_mut())
+            .expect("active window must have a populated split layout")
+            .get_mut(&active_split)
+            .map(|vs| {
+                let new_value = !vs.minimap_visible(default);
+                vs.minimap_override = Some(new_value);
+                new_value
+            })
+        else {
+            return;
+        };
+
+        let status = if new_value {
+            t!("view.state_enabled").to_string()
+        } else {
+            t!("view.state_disabled").to_string()
+        };
+        self.set_status_message(t!("view.minimap_state", state = status).to_string());
+    }
+
     /// Kick off the full-screen wave animation: a crest of wave glyphs
     /// rises from the bottom edge and bounces every painted cell — text,
     /// gutter, menu bar, status bar — up, down, and sideways before they
@@ -621,6 +665,32 @@ impl Editor {
         self.set_status_message(status.to_string());
     }
 
+    /// Toggle the sticky scroll headers in every split
+    pub fn toggle_sticky_scroll(&mut self) {
+        let new_value = !self.config.editor.show_sticky_scroll;
+        self.config_mut().editor.show_sticky_scroll = new_value;
+        self.persist_config_change(config_keys::EDITOR_SHOW_STICKY_SCROLL, new_value);
+        let status = if new_value {
+            t!("toggle.sticky_scroll_shown")
+        } else {
+            t!("toggle.sticky_scroll_hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Toggle the breadcrumb bar in every split
+    pub fn toggle_breadcrumbs(&mut self) {
+        let new_value = !self.config.editor.show_breadcrumbs;
+        self.config_mut().editor.show_breadcrumbs = new_value;
+        self.persist_config_change(config_keys::EDITOR_SHOW_BREADCRUMBS, new_value);
+        let status = if new_value {
+            t!("toggle.breadcrumbs_shown")
+        } else {
+            t!("toggle.breadcrumbs_hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
+
     /// Resolve the whitespace-indicator visibility a buffer would get from the
     /// current config: the flat editor config, refined by the buffer language's
     /// `show_whitespace_tabs` override. This is the same resolution used when a
@@ -716,6 +786,7 @@ impl Editor {
                 view_state.highlight_current_line_override = None;
                 view_state.indentation_guide_user_override = None;
                 view_state.fold_indicators_override = None;
+                view_state.minimap_override = None;
                 view_state.apply_config_defaults(crate::view::split::ViewConfigDefaults {
                     line_numbers: self.config.editor.line_numbers,
                     highlight_current_line: self.config.editor.highlight_current_line,
@@ -843,6 +914,19 @@ impl Editor {
             {
                 state.virtual_texts.clear(&mut state.marker_list);
             }
+            let lensed: Vec<_> = self.active_window().code_lenses.keys().copied().collect();
+            for buffer_id in lensed {
+                self.active_window_mut().render_code_lenses(buffer_id);
+            }
+            let colored: Vec<_> = self
+                .active_window()
+                .document_colors
+                .keys()
+                .copied()
+                .collect();
+            for buffer_id in colored {
+                self.active_window_mut().render_document_colors(buffer_id);
+            }
             self.set_status_message(t!("toggle.inlay_hints_disabled").to_string());
         }
     }
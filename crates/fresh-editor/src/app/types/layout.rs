@@ -230,6 +230,10 @@ pub(crate) struct WindowLayoutCache {
     /// Horizontal scrollbar areas per split
     /// (split_id, buffer_id, horizontal_scrollbar_rect, max_content_width, thumb_start_col, thumb_end_col)
     pub horizontal_scrollbar_areas: Vec<(LeafId, BufferId, Rect, usize, usize, usize)>,
+    /// Minimap columns per split, with the byte offset each row scrolls to
+    pub minimap_areas: Vec<crate::view::ui::split_rendering::minimap::MinimapArea>,
+    /// Sticky scroll lines and breadcrumb rows per split
+    pub scope_header_areas: Vec<crate::view::ui::split_rendering::scope_header::ScopeHeaderArea>,
     /// Split separator positions for drag resize
     /// (container_id, direction, x, y, length)
     pub separator_areas: Vec<(ContainerId, SplitDirection, u16, u16, u16)>,
@@ -1,8 +1,82 @@
 //! Undo and redo action handlers.
+//!
+//! Besides linear undo/redo this covers the undo tree kept by
+//! [`EventLog`]: "undo/redo in time" (vim's `g-` / `g+`) steps through
+//! states in the order they were created, across branches, and plugins
+//! (the bundled `undo_history` panel) list the tree, preview any state's
+//! text and jump to it.
 
 use super::Editor;
+use crate::model::buffer::TextBuffer;
+use crate::model::event::{BufferId, Event, EventLog};
+use crate::state::EditorState;
 use rust_i18n::t;
 
+/// Longest text excerpt shown in an undo state label.
+const LABEL_EXCERPT_CHARS: usize = 24;
+
+/// Short description of an edit for the undo history, e.g. `Insert "foo"`.
+pub(crate) fn undo_label(event: &Event) -> String {
+    fn excerpt(text: &str) -> String {
+        let mut out: String = text
+            .chars()
+            .take(LABEL_EXCERPT_CHARS)
+            .map(|c| if c == '\n' { '⏎' } else { c })
+            .collect();
+        if text.chars().count() > LABEL_EXCERPT_CHARS {
+            out.push('…');
+        }
+        out
+    }
+    match event {
+        Event::Insert { text, .. } => format!("Insert \"{}\"", excerpt(text)),
+        Event::Delete { deleted_text, .. } => format!("Delete \"{}\"", excerpt(deleted_text)),
+        Event::Batch {
+            description,
+            events,
+        } => match events.first() {
+            Some(first) if description.is_empty() => undo_label(first),
+            _ => description.clone(),
+        },
+        Event::BulkEdit { description, .. } => description.clone(),
+        _ => "Edit".to_string(),
+    }
+}
+
+/// Replay the text side of an event onto a scratch buffer. `None` for a
+/// bulk edit without the snapshot it leads to (one loaded from disk): its
+/// text cannot be rebuilt.
+fn replay_text_edit(buffer: &mut TextBuffer, event: &Event) -> Option<()> {
+    match event {
+        Event::Insert { position, text, .. } => buffer.insert(*position, text),
+        Event::Delete { range, .. } => buffer.delete(range.clone()),
+        Event::Batch { events, .. } => {
+            for event in events {
+                replay_text_edit(buffer, event)?;
+            }
+        }
+        Event::BulkEdit { new_snapshot, .. } => {
+            buffer.restore_buffer_state(new_snapshot.as_ref()?);
+        }
+        _ => {}
+    }
+    Some(())
+}
+
+/// A buffer's text as of undo state `seq`, computed on a scratch copy so
+/// the buffer itself is left alone. `None` for an unknown state, a buffer
+/// whose content is not fully loaded, or a state past a bulk edit that
+/// cannot be replayed.
+pub(crate) fn undo_state_text(state: &EditorState, log: &EventLog, seq: u64) -> Option<String> {
+    let events = log.events_to_state(seq)?;
+    let mut scratch = TextBuffer::empty(std::sync::Arc::clone(state.buffer.filesystem()));
+    scratch.restore_buffer_state(&state.buffer.snapshot_buffer_state());
+    for (event, _) in &events {
+        replay_text_edit(&mut scratch, event)?;
+    }
+    scratch.to_string()
+}
+
 impl Editor {
     /// Handle Undo action - revert the last edit operation.
     pub fn handle_undo(&mut self) {
@@ -10,6 +84,10 @@ impl Editor {
             self.set_status_message(t!("buffer.editing_disabled").to_string());
             return;
         }
+        if self.active_event_log().undo_blocked() {
+            self.set_status_message(t!("undo.unreplayable").to_string());
+            return;
+        }
 
         let event_log = self.active_event_log_mut();
         let before_idx = event_log.current_index();
@@ -25,25 +103,27 @@ impl Editor {
             events.len()
         );
 
-        // Apply all inverse events collected during undo.
-        // Each event may carry displaced markers that need restoration after apply.
-        for (event, displaced_markers) in &events {
+        self.apply_undo_events(&events);
+
+        // Update modified status based on event log position
+        self.active_window_mut().update_modified_from_event_log();
+    }
+
+    /// Apply inverse events collected from the event log.
+    /// Each event may carry displaced markers that need restoration after apply.
+    fn apply_undo_events(&mut self, events: &[crate::model::event::UndoStep]) {
+        for (event, displaced_markers) in events {
             tracing::debug!("Undo applying event: {:?}", event);
             self.apply_event_to_active_buffer(event);
 
             // Restore displaced markers from LogEntry (for single Delete events).
             // Skip for BulkEdit — they handle displaced markers internally
             // in state.apply(BulkEdit) via the Event's own displaced_markers field.
-            if !displaced_markers.is_empty()
-                && !matches!(event, crate::model::event::Event::BulkEdit { .. })
-            {
+            if !displaced_markers.is_empty() && !matches!(event, Event::BulkEdit { .. }) {
                 self.active_state_mut()
                     .restore_displaced_markers(displaced_markers);
             }
         }
-
-        // Update modified status based on event log position
-        self.active_window_mut().update_modified_from_event_log();
     }
 
     /// Handle Redo action - reapply an undone edit operation.
@@ -63,4 +143,136 @@ impl Editor {
         // Update modified status based on event log position
         self.active_window_mut().update_modified_from_event_log();
     }
+
+    /// Move the active buffer to undo state `seq`, wherever it is in the
+    /// undo tree. Returns `false` for an unknown state, or one that cannot
+    /// be reached by replaying the history.
+    pub fn goto_undo_state(&mut self, seq: u64) -> bool {
+        if self.active_window().is_editing_disabled() {
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            return false;
+        }
+
+        let Some(events) = self.active_event_log_mut().goto_state(seq) else {
+            if self.active_event_log().has_state(seq) {
+                self.set_status_message(t!("undo.unreplayable").to_string());
+            }
+            return false;
+        };
+        tracing::debug!(
+            "Undo tree: jumped to state {} ({} events)",
+            seq,
+            events.len()
+        );
+        self.apply_undo_events(&events);
+        self.active_window_mut().update_modified_from_event_log();
+
+        let total = self.active_event_log().last_state();
+        self.set_status_message(t!("undo.state", seq = seq, total = total).to_string());
+        true
+    }
+
+    /// Handle UndoInTime action - go to the state created before the
+    /// current one, even if it is on another branch (vim's `g-`).
+    pub fn handle_undo_in_time(&mut self) {
+        match self.active_event_log().earlier_state() {
+            Some(seq) => {
+                self.goto_undo_state(seq);
+            }
+            None => self.set_status_message(t!("undo.oldest_state").to_string()),
+        }
+    }
+
+    /// Handle RedoInTime action - go to the state created after the
+    /// current one (vim's `g+`).
+    pub fn handle_redo_in_time(&mut self) {
+        match self.active_event_log().later_state() {
+            Some(seq) => {
+                self.goto_undo_state(seq);
+            }
+            None => self.set_status_message(t!("undo.newest_state").to_string()),
+        }
+    }
+
+    /// Handle `GetUndoHistory` from a plugin.
+    #[cfg(feature = "plugins")]
+    pub(super) fn handle_get_undo_history(&mut self, buffer_id: BufferId, request_id: u64) {
+        use fresh_core::api::{UndoHistory, UndoHistoryState};
+
+        let callback_id = fresh_core::api::JsCallbackId::from(request_id);
+        let plugins = self.plugin_manager.read().unwrap();
+        let Some(log) = self.active_window().event_logs.get(&buffer_id) else {
+            plugins.reject_callback(callback_id, format!("No buffer {}", buffer_id.0));
+            return;
+        };
+        let history = UndoHistory {
+            current: log.current_state(),
+            saved: log.saved_state(),
+            states: log
+                .undo_tree()
+                .into_iter()
+                .map(|node| UndoHistoryState {
+                    seq: node.seq,
+                    parent: node.parent,
+                    timestamp: node.timestamp,
+                    label: undo_label(&node.event),
+                    edits: node.edits as u32,
+                })
+                .collect(),
+        };
+        plugins.resolve_callback(
+            callback_id,
+            serde_json::to_string(&history).unwrap_or_else(|_| "null".to_string()),
+        );
+    }
+
+    /// Handle `GetUndoStateText` from a plugin.
+    #[cfg(feature = "plugins")]
+    pub(super) fn handle_get_undo_state_text(
+        &mut self,
+        buffer_id: BufferId,
+        seq: u64,
+        request_id: u64,
+    ) {
+        let callback_id = fresh_core::api::JsCallbackId::from(request_id);
+        let window = self.active_window();
+        let text = window
+            .buffers
+            .get(&buffer_id)
+            .zip(window.event_logs.get(&buffer_id))
+            .and_then(|(state, log)| undo_state_text(state, log, seq));
+        let plugins = self.plugin_manager.read().unwrap();
+        match text {
+            Some(text) => plugins.resolve_callback(
+                callback_id,
+                serde_json::to_string(&text).unwrap_or_else(|_| "\"\"".to_string()),
+            ),
+            None => plugins.reject_callback(
+                callback_id,
+                format!(
+                    "Cannot rebuild undo state {} of buffer {}",
+                    seq, buffer_id.0
+                ),
+            ),
+        }
+    }
+
+    /// Handle `UndoToState` from a plugin.
+    #[cfg(feature = "plugins")]
+    pub(super) fn handle_undo_to_state(&mut self, buffer_id: BufferId, seq: u64) {
+        if self.active_buffer() != buffer_id {
+            tracing::warn!(
+                "undoToState: buffer {} is not the active buffer",
+                buffer_id.0
+            );
+            return;
+        }
+        if !self.goto_undo_state(seq) {
+            tracing::warn!(
+                "undoToState: no undo state {} in buffer {}",
+                seq,
+                buffer_id.0
+            );
+        }
+    }
 }
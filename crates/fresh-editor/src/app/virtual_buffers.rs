@@ -505,4 +505,50 @@ impl crate::app::window::Window {
             });
         Ok(())
     }
+
+    /// Append plain `text` to a virtual buffer after dropping its first
+    /// `drop_front` bytes, for output that streams in (task runs) where
+    /// rewriting the whole buffer per batch would be quadratic. Cursors stay
+    /// on the text they were on; one inside the dropped head moves to the
+    /// start. Returns `Err` when the buffer is missing.
+    pub fn append_virtual_buffer_text(
+        &mut self,
+        buffer_id: BufferId,
+        text: &str,
+        drop_front: usize,
+    ) -> Result<(), String> {
+        let state = self
+            .buffers
+            .get_mut(&buffer_id)
+            .ok_or_else(|| "Buffer not found".to_string())?;
+        let drop_front = drop_front.min(state.buffer.len());
+        if drop_front > 0 {
+            // Same reasoning as `set_virtual_buffer_content`: the raw
+            // buffer delete doesn't move markers, so no overlay may keep one.
+            state.overlays.clear(&mut state.marker_list);
+            state.buffer.delete_bytes(0, drop_front);
+        }
+        let end = state.buffer.len();
+        state.buffer.insert(end, text);
+        state.buffer.clear_modified();
+
+        if drop_front > 0 {
+            self.buffers
+                .with_buffer_and_view_states(buffer_id, |state, vs_map| {
+                    let buffer = &state.buffer;
+                    let shift =
+                        |pos: usize| buffer.snap_to_char_boundary(pos.saturating_sub(drop_front));
+                    for view_state in vs_map.values_mut() {
+                        let Some(buf_state) = view_state.keyed_states.get_mut(&buffer_id) else {
+                            continue;
+                        };
+                        buf_state.cursors.map(|cursor| {
+                            cursor.position = shift(cursor.position);
+                            cursor.anchor = cursor.anchor.map(shift);
+                        });
+                    }
+                });
+        }
+        Ok(())
+    }
 }
@@ -436,6 +436,13 @@ pub struct Window {
     /// Dabbrev cycling state (Alt+/ session).
     pub dabbrev_state: Option<crate::app::DabbrevCycleState>,
 
+    /// Just-yanked text that yank-pop (`M-y`) may replace.
+    pub yank_state: Option<crate::app::YankCycleState>,
+
+    /// Whether the previous action was a kill, so the next kill appends
+    /// to the same kill ring entry.
+    pub kill_chain: bool,
+
     /// Pending LSP go-to-definition request id.
     pub pending_goto_definition_request: Option<u64>,
 
@@ -447,6 +454,9 @@ pub struct Window {
     pub pending_implementation_request: Option<u64>,
     pub pending_implementation_symbol: String,
 
+    /// In-flight call/type hierarchy requests, keyed by LSP request id.
+    pub(crate) pending_hierarchy_requests: HashMap<u64, crate::app::lsp_hierarchy::HierarchyOrigin>,
+
     /// Pending LSP signature-help request id.
     pub pending_signature_help_request: Option<u64>,
 
@@ -466,6 +476,58 @@ pub struct Window {
     pub folding_ranges_in_flight: std::collections::HashMap<BufferId, (u64, u64)>,
     pub folding_ranges_debounce: std::collections::HashMap<BufferId, std::time::Instant>,
 
+    /// Code lenses per buffer, pending lens/resolve requests, the
+    /// per-buffer refresh debounce, and the commands behind an open
+    /// "Run Code Lens" chooser.
+    pub(crate) code_lenses: HashMap<BufferId, crate::app::code_lens::BufferCodeLenses>,
+    pub(crate) pending_code_lens_requests: HashMap<u64, crate::app::code_lens::CodeLensRequest>,
+    pub(crate) pending_code_lens_resolves:
+        HashMap<u64, crate::app::code_lens::CodeLensResolveRequest>,
+    pub code_lens_debounce: HashMap<BufferId, std::time::Instant>,
+    pub pending_code_lens_commands: Option<Vec<lsp_types::Command>>,
+
+    /// Document links per buffer, pending link requests, the per-buffer
+    /// refresh debounce, and the resolve behind a "Follow Link" on a link
+    /// that came without a target.
+    pub(crate) document_links: HashMap<BufferId, crate::app::document_links::BufferDocumentLinks>,
+    pub(crate) pending_document_link_requests:
+        HashMap<u64, crate::app::document_links::DocumentLinkRequest>,
+    pub document_link_debounce: HashMap<BufferId, std::time::Instant>,
+    pub(crate) pending_document_link_resolve:
+        Option<crate::app::document_links::DocumentLinkResolveRequest>,
+
+    /// Colour literals per buffer, pending colour requests, the per-buffer
+    /// refresh debounce, and the literal an open "Pick Color" is rewriting.
+    pub(crate) document_colors:
+        HashMap<BufferId, crate::app::document_colors::BufferDocumentColors>,
+    pub(crate) pending_document_color_requests:
+        HashMap<u64, crate::app::document_colors::DocumentColorRequest>,
+    pub document_color_debounce: HashMap<BufferId, std::time::Instant>,
+    pub(crate) pending_color_presentation_request: Option<u64>,
+    pub(crate) color_pick: Option<crate::app::document_colors::ColorPick>,
+
+    /// Pending selection-range requests, and the selections replaced by
+    /// Smart Expand Selection so Smart Shrink Selection can restore them.
+    pub(crate) pending_selection_range_requests:
+        HashMap<u64, crate::app::smart_selection::SelectionRangeRequest>,
+    pub(crate) selection_history: Option<crate::app::smart_selection::SelectionHistory>,
+
+    /// Pending linked-editing-range request, the `(buffer, version, cursor)`
+    /// last asked about, and the cursors linked editing added.
+    pub(crate) pending_linked_editing_request:
+        Option<crate::app::linked_editing::LinkedEditingRequest>,
+    pub(crate) linked_editing_asked: Option<(BufferId, u64, usize)>,
+    pub(crate) linked_editing: Option<crate::app::linked_editing::LinkedEditing>,
+
+    /// Pending on-type formatting request (request id, buffer, version
+    /// after the trigger character went in).
+    pub(crate) pending_on_type_formatting_request: Option<(u64, BufferId, u64)>,
+
+    /// Pending symbol requests behind Quick Open's `@` (request id, buffer,
+    /// buffer version) and `#` (request id → query).
+    pub(crate) pending_document_symbol_request: Option<(u64, BufferId, u64)>,
+    pub(crate) pending_workspace_symbol_requests: HashMap<u64, String>,
+
     /// Pending semantic-tokens-full requests + per-buffer in-flight tracking +
     /// the next-allowed-refresh debounce.
     pub(crate) pending_semantic_token_requests:
@@ -568,6 +630,12 @@ pub struct Window {
     /// between polls without re-running the syscall.
     pub(crate) terminal_fg_cache: HashMap<BufferId, String>,
 
+    /// Shell-integration command blocks last laid onto each terminal's
+    /// scroll-back buffer (see `app::terminal_blocks`). Line numbers match
+    /// that buffer; refreshed every time it is reloaded.
+    pub(crate) terminal_command_blocks:
+        HashMap<BufferId, Vec<crate::services::terminal::CommandBlock>>,
+
     /// Plugin-managed per-window state. Outer key is plugin name,
     /// inner is the plugin-defined key. Read via
     /// `editor.getWindowState(key)` and written via
@@ -733,6 +801,14 @@ pub struct Window {
     /// route through the active window's LSP.
     pub(crate) hover: crate::app::hover::HoverState,
 
+    /// Breakpoints and the active debug session. Per-window because the
+    /// adapter is spawned through this window's authority.
+    pub(crate) debug: crate::app::debugger::DebugState,
+
+    /// Task runs and the problems they reported. Per-window because tasks
+    /// are spawned through this window's authority.
+    pub(crate) tasks: crate::app::tasks::TaskState,
+
     /// Active find-in-buffer search session (if any).
     pub(crate) search_state: Option<crate::app::types::SearchState>,
 
@@ -2266,6 +2342,7 @@ impl Window {
             terminal_explicit_titles: std::collections::HashSet::new(),
             terminal_fg_poll_at: None,
             terminal_fg_cache: HashMap::new(),
+            terminal_command_blocks: HashMap::new(),
             event_logs: HashMap::new(),
             status_message: None,
             plugin_status_message: None,
@@ -2278,11 +2355,14 @@ impl Window {
             pending_completion_resolve_request: None,
             scheduled_completion_trigger: None,
             dabbrev_state: None,
+            yank_state: None,
+            kill_chain: false,
             pending_goto_definition_request: None,
             pending_references_request: None,
             pending_references_symbol: String::new(),
             pending_implementation_request: None,
             pending_implementation_symbol: String::new(),
+            pending_hierarchy_requests: HashMap::new(),
             pending_signature_help_request: None,
             pending_code_actions_requests: std::collections::HashSet::new(),
             pending_code_actions_server_names: std::collections::HashMap::new(),
@@ -2291,6 +2371,28 @@ impl Window {
             pending_folding_range_requests: std::collections::HashMap::new(),
             folding_ranges_in_flight: std::collections::HashMap::new(),
             folding_ranges_debounce: std::collections::HashMap::new(),
+            code_lenses: HashMap::new(),
+            pending_code_lens_requests: HashMap::new(),
+            pending_code_lens_resolves: HashMap::new(),
+            code_lens_debounce: HashMap::new(),
+            pending_code_lens_commands: None,
+            document_links: HashMap::new(),
+            pending_document_link_requests: HashMap::new(),
+            document_link_debounce: HashMap::new(),
+            pending_document_link_resolve: None,
+            document_colors: HashMap::new(),
+            pending_document_color_requests: HashMap::new(),
+            document_color_debounce: HashMap::new(),
+            pending_color_presentation_request: None,
+            color_pick: None,
+            pending_selection_range_requests: HashMap::new(),
+            selection_history: None,
+            pending_linked_editing_request: None,
+            linked_editing_asked: None,
+            linked_editing: None,
+            pending_on_type_formatting_request: None,
+            pending_document_symbol_request: None,
+            pending_workspace_symbol_requests: HashMap::new(),
             pending_semantic_token_requests: std::collections::HashMap::new(),
             semantic_tokens_in_flight: std::collections::HashMap::new(),
             semantic_tokens_full_debounce: std::collections::HashMap::new(),
@@ -2331,6 +2433,8 @@ impl Window {
             file_explorer_slot_override_cache:
                 crate::view::file_tree::FileExplorerSlotOverrideCache::default(),
             hover: crate::app::hover::HoverState::default(),
+            debug: crate::app::debugger::DebugState::default(),
+            tasks: crate::app::tasks::TaskState::default(),
             search_state: None,
             search_namespace: crate::view::overlay::OverlayNamespace::from_string(
                 "search".to_string(),
@@ -3131,6 +3235,92 @@ impl Window {
         self.restore_buffer_state_in_split(buffer_id, split_id, &file_state);
     }
 
+    /// Restore the undo history a previous session left for `path`
+    /// (`editor.persistent_undo`). Only applied when the buffer still holds
+    /// exactly the content the history was saved for.
+    pub fn restore_undo_history(&mut self, buffer_id: BufferId, path: &std::path::Path) {
+        use crate::workspace::PersistedUndoStore;
+
+        if !self.config().editor.persistent_undo {
+            return;
+        }
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        if state.buffer.is_large_file() {
+            return;
+        }
+        let content = state.buffer.slice_bytes(0..state.buffer.len());
+        if content.len() != state.buffer.len() {
+            return;
+        }
+        let Some(entries) = PersistedUndoStore::load(
+            path,
+            &content,
+            &self.config().editor.ephemeral_file_patterns,
+        ) else {
+            return;
+        };
+        tracing::debug!(
+            "Restored {} undo history entries for {:?}",
+            entries.len(),
+            path
+        );
+        self.event_logs.insert(
+            buffer_id,
+            crate::model::event::EventLog::from_saved_history(entries),
+        );
+    }
+
+    /// Snapshot a buffer's undo history for `PersistedUndoStore::save`: the
+    /// file path, its content, and the log entries leading up to it. `None`
+    /// unless `editor.persistent_undo` is on and the buffer is a fully
+    /// loaded file that matches what is on disk.
+    pub fn undo_history_snapshot(
+        &self,
+        buffer_id: BufferId,
+    ) -> Option<(
+        std::path::PathBuf,
+        Vec<u8>,
+        Vec<crate::model::event::LogEntry>,
+    )> {
+        if !self.config().editor.persistent_undo {
+            return None;
+        }
+        let path = self
+            .buffer_metadata
+            .get(&buffer_id)?
+            .file_path()?
+            .to_path_buf();
+        let log = self.event_logs.get(&buffer_id)?;
+        if !log.is_at_saved_position() {
+            return None;
+        }
+        let entries = log.saved_history()?.to_vec();
+        let buffer = &self.buffers.get(&buffer_id)?.buffer;
+        if buffer.is_large_file() {
+            return None;
+        }
+        let content = buffer.slice_bytes(0..buffer.len());
+        (content.len() == buffer.len()).then_some((path, content, entries))
+    }
+
+    /// Persist the undo history of every file buffer in this window.
+    pub(crate) fn save_all_undo_histories(&self) {
+        let editor = &self.config().editor;
+        for &buffer_id in self.event_logs.keys() {
+            if let Some((path, content, entries)) = self.undo_history_snapshot(buffer_id) {
+                crate::workspace::PersistedUndoStore::save(
+                    &path,
+                    &content,
+                    entries,
+                    editor.persistent_undo_max_bytes,
+                    &editor.ephemeral_file_patterns,
+                );
+            }
+        }
+    }
+
     /// Snapshot the per-file session state to persist when a buffer is
     /// closed. Walks this window's splits to find one that has the buffer;
     /// `None` if no split contains it or the buffer isn't a real on-disk
@@ -3187,13 +3377,14 @@ impl Window {
             view_mode: Default::default(),
             compose_width: None,
             // Per-buffer line-number / line-wrap / virtual-space /
-            // indentation-guide / fold-indicator overrides are workspace-scoped,
+            // indentation-guide / fold-indicator / minimap overrides are workspace-scoped,
             // not part of the cross-project global per-file state.
             line_numbers: None,
             line_wrap: None,
             virtual_space: None,
             indentation_guide: None,
             fold_indicators: None,
+            minimap: None,
             use_tabs: None,
             whitespace_indicators: None,
             tab_indicators: None,
@@ -3324,10 +3515,10 @@ impl Window {
         max_byte_pos
     }
 
-    /// Find the split whose content or scrollbar area contains the
-    /// screen cell `(col, row)`. Returns the split id and its buffer
-    /// id, or `None` when the position falls outside every split's
-    /// content rect and outside every scrollbar gutter.
+    /// Find the split whose content, minimap, breadcrumb or scrollbar area
+    /// contains the screen cell `(col, row)`. Returns the split id and its
+    /// buffer id, or `None` when the position falls outside every split's
+    /// content rect, minimap, breadcrumb row and scrollbar gutter.
     pub fn split_at_position(&self, col: u16, row: u16) -> Option<(LeafId, BufferId)> {
         for &(split_id, buffer_id, content_rect, scrollbar_rect, _, _) in
             &self.layout_cache.split_areas
@@ -3346,7 +3537,27 @@ impl Window {
                 return Some((split_id, buffer_id));
             }
         }
-        None
+        self.layout_cache
+            .minimap_areas
+            .iter()
+            .find(|minimap| {
+                col >= minimap.rect.x
+                    && col < minimap.rect.x + minimap.rect.width
+                    && row >= minimap.rect.y
+                    && row < minimap.rect.y + minimap.rect.height
+            })
+            .map(|minimap| (minimap.split_id, minimap.buffer_id))
+            .or_else(|| {
+                self.layout_cache
+                    .scope_header_areas
+                    .iter()
+                    .find(|area| {
+                        area.breadcrumb_rect.is_some_and(|r| {
+                            col >= r.x && col < r.x + r.width && row >= r.y && row < r.y + r.height
+                        })
+                    })
+                    .map(|area| (area.split_id, area.buffer_id))
+            })
     }
 
     /// If a per-edit diagnostic-pull debounce has fired, send a fresh
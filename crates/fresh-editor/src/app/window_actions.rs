@@ -1145,6 +1145,7 @@ impl crate::app::Editor {
         let ephemeral = src.ephemeral_terminals.remove(&terminal_id);
         let explicit_title = src.terminal_explicit_titles.remove(&buffer_id);
         let fg_name = src.terminal_fg_cache.remove(&buffer_id);
+        let blocks = src.terminal_command_blocks.remove(&buffer_id);
         let pid = handle.pid();
         if let Some(pid) = pid {
             src.process_groups.forget(pid);
@@ -1181,6 +1182,9 @@ impl crate::app::Editor {
         if let Some(n) = fg_name {
             tgt.terminal_fg_cache.insert(buffer_id, n);
         }
+        if let Some(b) = blocks {
+            tgt.terminal_command_blocks.insert(buffer_id, b);
+        }
         if let Some(pid) = pid {
             tgt.process_groups
                 .register(pid, format!("terminal #{}", new_id.0));
@@ -465,9 +465,10 @@ impl Editor {
         };
 
         // Ensure terminal backing files have complete state, and persist
-        // per-file global states, before snapshotting.
+        // per-file global states and undo histories, before snapshotting.
         win.sync_terminal_backing_files();
         win.save_all_global_file_states();
+        win.save_all_undo_histories();
 
         // The single funnel for every workspace write, so `--no-restore`
         // suppresses checkpoints as well as quit-time saves (#2735).
@@ -1124,6 +1125,10 @@ impl crate::app::window::Window {
             crate::app::terminal::terminal_backing_fs(),
         ) {
             self.install_terminal_buffer_state(buffer_id, new_state);
+            let blocks = crate::services::terminal::command_blocks::load_blocks(
+                &crate::services::terminal::command_blocks::blocks_path(backing_path),
+            );
+            self.apply_terminal_command_blocks(buffer_id, blocks);
         }
     }
 
@@ -1606,7 +1611,7 @@ impl crate::app::window::Window {
                         // render time, so restoring the override is all it
                         // takes for the buffer to come back looking the way
                         // the user left it.
-                        // Guide/fold pins are per (split, buffer): they land
+                        // Guide/fold/minimap pins are per (split, buffer): they land
                         // on this split's view state, not on the shared
                         // BufferSettings.
                         if let Some(indentation_guide) = file_state.indentation_guide {
@@ -1615,6 +1620,9 @@ impl crate::app::window::Window {
                         if let Some(fold_indicators) = file_state.fold_indicators {
                             buf_state.fold_indicators_override = Some(fold_indicators);
                         }
+                        if let Some(minimap) = file_state.minimap {
+                            buf_state.minimap_override = Some(minimap);
+                        }
                         if let Some(use_tabs) = file_state.use_tabs {
                             state.buffer_settings.use_tabs = use_tabs;
                             state.buffer_settings.use_tabs_override = Some(use_tabs);
@@ -2091,6 +2099,7 @@ impl crate::app::window::Window {
             virtual_space: None,
             indentation_guide: None,
             fold_indicators: None,
+            minimap: None,
             use_tabs: None,
             whitespace_indicators: None,
             tab_indicators: None,
@@ -3259,6 +3268,7 @@ fn serialize_split_view_state(
                     .and_then(|state| state.buffer_settings.virtual_space_override),
                 indentation_guide: buf_state.indentation_guide_user_override,
                 fold_indicators: buf_state.fold_indicators_override,
+                minimap: buf_state.minimap_override,
                 use_tabs: buffers
                     .get(buffer_id)
                     .and_then(|state| state.buffer_settings.use_tabs_override),
@@ -1,4 +1,7 @@
-use crate::types::{context_keys, LspFeature, LspLanguageConfig, LspServerConfig, ProcessLimits};
+use crate::types::{
+    context_keys, DebugAdapterConfig, DebugRequestKind, LspFeature, LspLanguageConfig,
+    LspServerConfig, ProcessLimits, TaskConfig,
+};
 
 use rust_i18n::t;
 use schemars::JsonSchema;
@@ -542,6 +545,16 @@ pub struct Config {
     #[serde(default)]
     pub universal_lsp: HashMap<String, LspLanguageConfig>,
 
+    /// Debug adapter configurations by language (Debug Adapter Protocol).
+    /// The adapter for the active buffer's language is used by "Debug: Start".
+    #[serde(default)]
+    pub debug_adapters: HashMap<String, DebugAdapterConfig>,
+
+    /// Tasks that can be run from the editor ("Run Task"), keyed by name.
+    /// The workspace's `.fresh/tasks.json` can add more, in the same shape.
+    #[serde(default)]
+    pub tasks: HashMap<String, TaskConfig>,
+
     /// Warning notification settings
     #[serde(default)]
     pub warnings: WarningsConfig,
@@ -1295,6 +1308,45 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub show_horizontal_scrollbar: bool,
 
+    /// Show a minimap column at the right edge of each split: a compressed,
+    /// syntax-coloured overview of the buffer that highlights the visible
+    /// region and the diagnostic, search and git-gutter markers. Click or
+    /// drag in it to scroll. Can be toggled per buffer via the command palette.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_minimap: bool,
+
+    /// Width of the minimap column in terminal cells, including its marker lane.
+    /// Each cell covers two source columns and four source lines.
+    /// Default: 12
+    #[serde(default = "default_minimap_width")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub minimap_width: u16,
+
+    /// Pin the header lines of the scopes enclosing the top of the viewport
+    /// (impl, class, function, …) to the top of each split while scrolling
+    /// through their bodies. Click a pinned line to jump to it.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_sticky_scroll: bool,
+
+    /// Most header lines sticky scroll pins at once; the innermost scopes
+    /// give way first. Never more than half the split's height.
+    /// Default: 5
+    #[serde(default = "default_sticky_scroll_max_lines")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub sticky_scroll_max_lines: u16,
+
+    /// Show a breadcrumb row at the top of each split with the file path and
+    /// the symbols enclosing the cursor. Click a segment to pick one of its
+    /// siblings.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_breadcrumbs: bool,
+
     /// Show tilde (~) markers on lines after the end of the file.
     /// These vim-style markers indicate lines that are not part of the file content.
     /// Default: true
@@ -1572,6 +1624,24 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Bracket Matching"))]
     pub rainbow_brackets: bool,
 
+    // ===== Spell Check =====
+    /// Underline misspelled words. In source code only comments and strings
+    /// are checked; Markdown, plain text and commit messages are checked
+    /// throughout, except for code blocks and links. Needs a Hunspell
+    /// dictionary (`<language>.aff` and `<language>.dic`) in the
+    /// `dictionaries` folder of the config directory.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Spell Check"))]
+    pub spell_check: bool,
+
+    /// Dictionary to spell check against, by file name without extension
+    /// (e.g. "en_US" for `en_US.aff` / `en_US.dic`).
+    /// Default: "en_US"
+    #[serde(default = "default_spell_check_language")]
+    #[schemars(extend("x-section" = "Spell Check"))]
+    pub spell_check_language: String,
+
     // ===== Completion =====
     /// Automatically show the completion popup while typing.
     /// When false (default), the popup only appears when explicitly invoked
@@ -1763,6 +1833,23 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Recovery"))]
     pub auto_recovery_save_interval_secs: u32,
 
+    /// Whether to keep undo history across sessions. When enabled, a file's
+    /// undo history is written to the data directory when it is saved or
+    /// closed, and restored when the file is reopened, provided the file
+    /// has not changed on disk in the meantime.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub persistent_undo: bool,
+
+    /// Maximum size in bytes of the undo history kept for one file.
+    /// The oldest undo steps are dropped to stay under this limit.
+    /// Only effective when persistent_undo is true.
+    /// Default: 1048576 (1 MiB)
+    #[serde(default = "default_persistent_undo_max_bytes")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub persistent_undo_max_bytes: u64,
+
     /// Poll interval in milliseconds for auto-reverting open buffers.
     /// When auto-revert is enabled, file modification times are checked at this interval.
     /// Lower values detect external changes faster but use more CPU.
@@ -1889,6 +1976,14 @@ fn default_read_concurrency() -> usize {
     64
 }
 
+fn default_minimap_width() -> u16 {
+    12
+}
+
+fn default_sticky_scroll_max_lines() -> u16 {
+    5
+}
+
 fn default_true() -> bool {
     true
 }
@@ -1925,6 +2020,14 @@ fn default_auto_recovery_save_interval() -> u32 {
     2 // 2 seconds between recovery saves
 }
 
+fn default_persistent_undo_max_bytes() -> u64 {
+    1024 * 1024
+}
+
+fn default_spell_check_language() -> String {
+    "en_US".to_string()
+}
+
 /// Anything under a `.git` directory: the whole tree, so linked worktrees
 /// (`<main>/.git/worktrees/<name>/…`) are covered too. `.github/` and
 /// `.gitignore` are unaffected — the pattern requires a `.git` path
@@ -1996,6 +2099,8 @@ impl Default for EditorConfig {
             auto_create_empty_buffer_on_last_buffer_close: true,
             recovery_enabled: true,
             auto_recovery_save_interval_secs: default_auto_recovery_save_interval(),
+            persistent_undo: false,
+            persistent_undo_max_bytes: default_persistent_undo_max_bytes(),
             highlight_context_bytes: default_highlight_context_bytes(),
             mouse_hover_enabled: default_mouse_hover_enabled(),
             mouse_hover_delay_ms: default_mouse_hover_delay(),
@@ -2009,6 +2114,8 @@ impl Default for EditorConfig {
             auto_read_only: true,
             highlight_matching_brackets: true,
             rainbow_brackets: true,
+            spell_check: false,
+            spell_check_language: default_spell_check_language(),
             cursor_style: CursorStyle::default(),
             keyboard_disambiguate_escape_codes: true,
             keyboard_report_event_types: false,
@@ -2028,6 +2135,11 @@ impl Default for EditorConfig {
             show_prompt_line: false,
             show_vertical_scrollbar: true,
             show_horizontal_scrollbar: false,
+            show_minimap: false,
+            minimap_width: default_minimap_width(),
+            show_sticky_scroll: false,
+            sticky_scroll_max_lines: default_sticky_scroll_max_lines(),
+            show_breadcrumbs: false,
             show_tilde: true,
             nerd_font_icons: false,
             use_terminal_bg: false,
@@ -2828,6 +2940,20 @@ pub struct LanguageConfig {
     /// them back for plain text.
     #[serde(default)]
     pub indentation_guide: Option<bool>,
+
+    /// Whether editing one of a pair of linked ranges — an HTML/JSX opening
+    /// tag name and its closing tag — edits the other too, as extra cursors.
+    /// Only takes effect when the language server supports
+    /// `textDocument/linkedEditingRange`.
+    #[serde(default = "default_true")]
+    pub linked_editing: bool,
+
+    /// Whether typing one of the language server's trigger characters (such
+    /// as `}` or `;`) applies the server's formatting edits for that line.
+    /// Only takes effect when the language server supports
+    /// `textDocument/onTypeFormatting`.
+    #[serde(default)]
+    pub format_on_type: bool,
 }
 
 /// User-overridable auto-indentation rules for a language.
@@ -3227,6 +3353,8 @@ impl Default for Config {
             lsp_enabled: true,
             lsp: Self::default_lsp_config(),
             universal_lsp: Self::default_universal_lsp_config(),
+            debug_adapters: Self::default_debug_adapters(),
+            tasks: HashMap::new(),
             warnings: WarningsConfig::default(),
             plugins: HashMap::new(),
             packages: PackagesConfig::default(),
@@ -3747,6 +3875,20 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.selection.smart_expand_selection").to_string(),
+                        action: "smart_expand_selection".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.selection.smart_shrink_selection").to_string(),
+                        action: "smart_shrink_selection".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.selection.add_cursor_above").to_string(),
@@ -3878,6 +4020,20 @@ impl MenuConfig {
                         when: Some(context_keys::LSP_AVAILABLE.to_string()),
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.call_hierarchy").to_string(),
+                        action: "lsp_call_hierarchy".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::LSP_AVAILABLE.to_string()),
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.type_hierarchy").to_string(),
+                        action: "lsp_type_hierarchy".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::LSP_AVAILABLE.to_string()),
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: t!("menu.lsp.rename_symbol").to_string(),
                         action: "lsp_rename".to_string(),
@@ -3907,6 +4063,27 @@ impl MenuConfig {
                         when: Some(context_keys::LSP_AVAILABLE.to_string()),
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.code_lens").to_string(),
+                        action: "lsp_code_lens".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::LSP_AVAILABLE.to_string()),
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.follow_link").to_string(),
+                        action: "lsp_follow_link".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::LSP_AVAILABLE.to_string()),
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.pick_color").to_string(),
+                        action: "lsp_pick_color".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::LSP_AVAILABLE.to_string()),
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.lsp.toggle_inlay_hints").to_string(),
@@ -4256,6 +4433,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4288,6 +4467,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4320,6 +4501,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4356,6 +4539,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4383,6 +4568,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4415,6 +4602,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4454,6 +4643,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4481,6 +4672,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4523,6 +4716,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4550,6 +4745,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4581,6 +4778,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4608,6 +4807,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4640,6 +4841,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4700,6 +4903,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4727,6 +4932,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4759,6 +4966,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4786,6 +4995,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4819,6 +5030,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4846,6 +5059,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4873,6 +5088,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4900,6 +5117,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4927,6 +5146,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -4970,6 +5191,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5002,6 +5225,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5029,6 +5254,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5056,6 +5283,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5083,6 +5312,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5110,6 +5341,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5146,6 +5379,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5173,6 +5408,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5200,6 +5437,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5227,6 +5466,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5264,6 +5505,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5314,6 +5557,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5341,6 +5586,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5368,6 +5615,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5395,6 +5644,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5422,6 +5673,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5449,6 +5702,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5476,6 +5731,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5503,6 +5760,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5531,6 +5790,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5563,6 +5824,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5595,6 +5858,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5622,6 +5887,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5649,6 +5916,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5676,6 +5945,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5707,6 +5978,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5734,6 +6007,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5761,6 +6036,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5788,6 +6065,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5815,6 +6094,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5842,6 +6123,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5869,6 +6152,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5896,6 +6181,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5928,6 +6215,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5955,6 +6244,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -5982,6 +6273,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6009,6 +6302,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6036,6 +6331,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6063,6 +6360,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6095,6 +6394,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6122,6 +6423,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6149,6 +6452,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6176,6 +6481,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6203,6 +6510,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6230,6 +6539,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6262,6 +6573,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6289,6 +6602,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6319,6 +6634,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6348,6 +6665,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6379,6 +6698,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6406,6 +6727,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6433,6 +6756,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6460,6 +6785,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6487,6 +6814,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6514,6 +6843,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6541,6 +6872,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6568,6 +6901,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6595,6 +6930,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6622,6 +6959,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6649,6 +6988,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6676,6 +7017,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6703,6 +7046,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6730,6 +7075,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6757,6 +7104,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6784,6 +7133,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6811,6 +7162,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6838,6 +7191,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6865,6 +7220,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6892,6 +7249,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6921,6 +7280,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6948,6 +7309,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -6975,6 +7338,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -7002,6 +7367,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -7036,6 +7403,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -7067,6 +7436,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -7094,6 +7465,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -7121,6 +7494,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -7148,6 +7523,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -7175,6 +7552,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -7202,6 +7581,8 @@ impl Config {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -7281,6 +7662,54 @@ impl Config {
         HashMap::new()
     }
 
+    /// Create default debug adapter configurations.
+    ///
+    /// Only stdio adapters are listed: debugpy for Python and lldb-dap for
+    /// the LLVM languages. TCP-only adapters (delve, older codelldb) can be
+    /// configured with a stdio bridge as their command.
+    fn default_debug_adapters() -> HashMap<String, DebugAdapterConfig> {
+        let mut adapters = HashMap::new();
+
+        adapters.insert(
+            "python".to_string(),
+            DebugAdapterConfig {
+                command: "python3".to_string(),
+                args: vec!["-m".to_string(), "debugpy.adapter".to_string()],
+                request: DebugRequestKind::Launch,
+                configuration: serde_json::json!({
+                    "program": "${file}",
+                    "cwd": "${workspaceFolder}",
+                    "console": "internalConsole",
+                    "justMyCode": true,
+                }),
+                env: HashMap::new(),
+            },
+        );
+
+        let lldb = DebugAdapterConfig {
+            command: "lldb-dap".to_string(),
+            args: vec![],
+            request: DebugRequestKind::Launch,
+            configuration: serde_json::json!({
+                "program": "${workspaceFolder}/target/debug/${workspaceFolderBasename}",
+                "cwd": "${workspaceFolder}",
+            }),
+            env: HashMap::new(),
+        };
+        adapters.insert("rust".to_string(), lldb.clone());
+        let native = DebugAdapterConfig {
+            configuration: serde_json::json!({
+                "program": "${fileDirname}/${fileBasenameNoExtension}",
+                "cwd": "${workspaceFolder}",
+            }),
+            ..lldb
+        };
+        adapters.insert("c".to_string(), native.clone());
+        adapters.insert("cpp".to_string(), native);
+
+        adapters
+    }
+
     #[cfg(feature = "runtime")]
     fn populate_lsp_config(lsp: &mut HashMap<String, LspLanguageConfig>, ra_log_path: String) {
         // rust-analyzer: full mode by default (no init param restrictions, no process limits).
@@ -9290,6 +9719,8 @@ mod tests {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -1188,6 +1188,11 @@ impl DirectoryContext {
         self.config_dir.join("plugins")
     }
 
+    /// Get the spell-check dictionaries directory path
+    pub fn dictionaries_dir(&self) -> std::path::PathBuf {
+        self.config_dir.join("dictionaries")
+    }
+
     /// Get the default config directory path (static/internal version).
     ///
     /// This is used internally by `from_system()` to determine the config directory.
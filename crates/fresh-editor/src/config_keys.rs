@@ -85,12 +85,15 @@ setting_keys! {
     EDITOR_HIGHLIGHT_OCCURRENCES: bool = "/editor/highlight_occurrences";
     EDITOR_ENABLE_INLAY_HINTS: bool = "/editor/enable_inlay_hints";
     EDITOR_MOUSE_HOVER_ENABLED: bool = "/editor/mouse_hover_enabled";
+    EDITOR_SPELL_CHECK: bool = "/editor/spell_check";
     EDITOR_SHOW_MENU_BAR: bool = "/editor/show_menu_bar";
     EDITOR_SHOW_TAB_BAR: bool = "/editor/show_tab_bar";
     EDITOR_SHOW_STATUS_BAR: bool = "/editor/show_status_bar";
     EDITOR_SHOW_PROMPT_LINE: bool = "/editor/show_prompt_line";
     EDITOR_SHOW_VERTICAL_SCROLLBAR: bool = "/editor/show_vertical_scrollbar";
     EDITOR_SHOW_HORIZONTAL_SCROLLBAR: bool = "/editor/show_horizontal_scrollbar";
+    EDITOR_SHOW_STICKY_SCROLL: bool = "/editor/show_sticky_scroll";
+    EDITOR_SHOW_BREADCRUMBS: bool = "/editor/show_breadcrumbs";
     FILE_EXPLORER_SHOW_HIDDEN: bool = "/file_explorer/show_hidden";
     FILE_EXPLORER_SHOW_GITIGNORED: bool = "/file_explorer/show_gitignored";
     FILE_EXPLORER_SIDE: crate::config::FileExplorerSide = "/file_explorer/side";
@@ -711,6 +711,20 @@ fn buffer_indent_rules(
     })
 }
 
+/// Indentation rules of the language embedded at `position` — JavaScript in a
+/// Templ `<script>`, CSS in a tagged template — when the cursor is inside such
+/// a region and that language has rules. The host's grammar knows nothing of
+/// the embedded code's blocks, so its rules take over there.
+fn injected_indent_rules(
+    state: &EditorState,
+    position: usize,
+) -> Option<std::sync::Arc<crate::primitives::indent_rules::IndentRules>> {
+    let injected = state
+        .highlighter
+        .injected_language_at(&state.buffer, position)?;
+    crate::primitives::indent_rules::rules_for_id(&injected.language_id)
+}
+
 /// Try the per-language regex indentation rules for a buffer that has no
 /// tree-sitter grammar. Returns `None` when no rules exist for the language so
 /// the caller can fall back.
@@ -744,6 +758,17 @@ fn calculate_closing_delimiter_indent(
     ch: char,
     tab_size: usize,
 ) -> usize {
+    if let Some(rules) = injected_indent_rules(state, insert_position) {
+        if let Some(indent) = rules.calculate_dedent_for_delimiter(
+            &state.buffer,
+            insert_position,
+            ch,
+            tab_size,
+            |b| byte_is_code(state, b),
+        ) {
+            return indent;
+        }
+    }
     if let Some(language) = state.highlighter.language() {
         if language.ts_language().is_some() {
             return state
@@ -1484,8 +1509,16 @@ fn handle_insert_newline(
             // tier (keyed by syntax name, with comment/string scope masking),
             // falling back to the language-agnostic heuristic for unknown
             // syntaxes (.txt, …). `IndentCalculator` itself also falls back to
-            // the rules tier when a language has no grammar.
+            // the rules tier when a language has no grammar. Inside code
+            // embedded in another language, the embedded language's rules
+            // come first.
+            let injected_rules = injected_indent_rules(state, indent_position);
             let indent_width_opt = match state.highlighter.language() {
+                _ if injected_rules.is_some() => injected_rules.map(|rules| {
+                    rules.calculate_indent(&state.buffer, indent_position, tab_size, |b| {
+                        byte_is_code(state, b)
+                    })
+                }),
                 Some(language) if language.ts_language().is_some() => state
                     .indent_calculator
                     .borrow_mut()
@@ -3350,6 +3383,11 @@ pub fn action_to_events(
         | Action::CopyRelativeFilePath
         | Action::Cut
         | Action::Paste
+        | Action::KillLine
+        | Action::KillWordForward
+        | Action::KillWordBackward
+        | Action::YankPop
+        | Action::PasteFromKillRing
         | Action::YankWordForward
         | Action::YankWordBackward
         | Action::YankToLineEnd
@@ -3363,6 +3401,7 @@ pub fn action_to_events(
         | Action::QuickOpen
         | Action::QuickOpenBuffers
         | Action::QuickOpenFiles
+        | Action::QuickOpenSymbols
         | Action::OpenLiveGrep
         | Action::ResumeLiveGrep
         | Action::ToggleUtilityDock
@@ -3380,6 +3419,8 @@ pub fn action_to_events(
         | Action::ToggleMaximizeSplit
         | Action::Undo
         | Action::Redo
+        | Action::UndoInTime
+        | Action::RedoInTime
         | Action::GoToMatchingBracket
         | Action::JumpToNextError
         | Action::JumpToPreviousError
@@ -3391,6 +3432,13 @@ pub fn action_to_events(
         | Action::ShowReadOnlyMenu
         | Action::ClearWarnings
         | Action::SmartHome
+        | Action::SmartExpandSelection
+        | Action::SmartShrinkSelection
+        | Action::SelectTextObject(_)
+        | Action::GotoNextTextObject(_)
+        | Action::GotoPreviousTextObject(_)
+        | Action::SwapParameterNext
+        | Action::SwapParameterPrevious
         | Action::ToggleComment
         | Action::DabbrevExpand
         | Action::ToggleFold
@@ -3463,6 +3511,8 @@ pub fn action_to_events(
         | Action::TogglePromptLine
         | Action::ToggleVerticalScrollbar
         | Action::ToggleHorizontalScrollbar
+        | Action::ToggleStickyScroll
+        | Action::ToggleBreadcrumbs
         | Action::FocusFileExplorer
         | Action::FocusEditor
         | Action::ToggleDockFocus
@@ -3498,21 +3548,42 @@ pub fn action_to_events(
         | Action::LspGotoDefinition
         | Action::LspReferences
         | Action::LspImplementation
+        | Action::LspCallHierarchy
+        | Action::LspTypeHierarchy
         | Action::LspRename
         | Action::LspHover
         | Action::LspSignatureHelp
         | Action::LspCodeActions
+        | Action::LspCodeLens
+        | Action::LspFollowLink
+        | Action::LspPickColor
         | Action::LspRestart
         | Action::LspStop
         | Action::LspToggleForBuffer
         | Action::ToggleInlayHints
         | Action::ToggleMouseHover
+        | Action::ToggleSpellCheck
+        | Action::DebugStart
+        | Action::DebugStop
+        | Action::DebugContinue
+        | Action::DebugPause
+        | Action::DebugStepOver
+        | Action::DebugStepInto
+        | Action::DebugStepOut
+        | Action::DebugToggleBreakpoint
+        | Action::DebugClearBreakpoints
+        | Action::RunTask
+        | Action::RerunLastTask
+        | Action::StopTasks
+        | Action::NextProblem
+        | Action::PreviousProblem
         | Action::ToggleLineNumbers
         | Action::ToggleLineNumbersCurrentBuffer
         | Action::ToggleLineWrapCurrentBuffer
         | Action::ToggleVirtualSpaceCurrentBuffer
         | Action::ToggleIndentationGuideCurrentBuffer
         | Action::ToggleFoldIndicatorsCurrentBuffer
+        | Action::ToggleMinimapCurrentBuffer
         | Action::ToggleCurrentLineHighlightCurrentBuffer
         | Action::ToggleOccurrenceHighlightCurrentBuffer
         | Action::TriggerWaveAnimation
@@ -3564,6 +3635,12 @@ pub fn action_to_events(
         | Action::ToggleKeyboardCapture
         | Action::TerminalPaste
         | Action::SendSelectionToTerminal
+        | Action::TerminalPreviousPrompt
+        | Action::TerminalNextPrompt
+        | Action::TerminalSelectCommandOutput
+        | Action::TerminalCopyCommandOutput
+        | Action::TerminalRerunCommand
+        | Action::TerminalFoldCommandOutputs
         | Action::OpenSettings
         | Action::CloseSettings
         | Action::SettingsSave
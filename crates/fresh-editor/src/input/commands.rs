@@ -342,6 +342,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.quick_open_symbols",
+        desc_key: "cmd.quick_open_symbols_desc",
+        action: || Action::QuickOpenSymbols,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     // Edit operations
     CommandDef {
         name_key: "cmd.undo",
@@ -357,6 +364,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.undo_in_time",
+        desc_key: "cmd.undo_in_time_desc",
+        action: || Action::UndoInTime,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.redo_in_time",
+        desc_key: "cmd.redo_in_time_desc",
+        action: || Action::RedoInTime,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.copy",
         desc_key: "cmd.copy_desc",
@@ -399,6 +420,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.paste_from_kill_ring",
+        desc_key: "cmd.paste_from_kill_ring_desc",
+        action: || Action::PasteFromKillRing,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.yank_pop",
+        desc_key: "cmd.yank_pop_desc",
+        action: || Action::YankPop,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.delete_line",
         desc_key: "cmd.delete_line_desc",
@@ -526,6 +561,105 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.smart_expand_selection",
+        desc_key: "cmd.smart_expand_selection_desc",
+        action: || Action::SmartExpandSelection,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.smart_shrink_selection",
+        desc_key: "cmd.smart_shrink_selection_desc",
+        action: || Action::SmartShrinkSelection,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    // Tree-sitter text objects
+    CommandDef {
+        name_key: "cmd.select_function",
+        desc_key: "cmd.select_function_desc",
+        action: || Action::SelectTextObject("function.outer".to_string()),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.select_function_body",
+        desc_key: "cmd.select_function_body_desc",
+        action: || Action::SelectTextObject("function.inner".to_string()),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.select_class",
+        desc_key: "cmd.select_class_desc",
+        action: || Action::SelectTextObject("class.outer".to_string()),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.select_parameter",
+        desc_key: "cmd.select_parameter_desc",
+        action: || Action::SelectTextObject("parameter.inner".to_string()),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.select_comment",
+        desc_key: "cmd.select_comment_desc",
+        action: || Action::SelectTextObject("comment.outer".to_string()),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.select_block",
+        desc_key: "cmd.select_block_desc",
+        action: || Action::SelectTextObject("block.inner".to_string()),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.goto_next_function",
+        desc_key: "cmd.goto_next_function_desc",
+        action: || Action::GotoNextTextObject("function".to_string()),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.goto_previous_function",
+        desc_key: "cmd.goto_previous_function_desc",
+        action: || Action::GotoPreviousTextObject("function".to_string()),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.goto_next_class",
+        desc_key: "cmd.goto_next_class_desc",
+        action: || Action::GotoNextTextObject("class".to_string()),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.goto_previous_class",
+        desc_key: "cmd.goto_previous_class_desc",
+        action: || Action::GotoPreviousTextObject("class".to_string()),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.swap_parameter_next",
+        desc_key: "cmd.swap_parameter_next_desc",
+        action: || Action::SwapParameterNext,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.swap_parameter_previous",
+        desc_key: "cmd.swap_parameter_previous_desc",
+        action: || Action::SwapParameterPrevious,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Multi-cursor
     CommandDef {
         name_key: "cmd.add_cursor_above",
@@ -728,6 +862,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_minimap_current_buffer",
+        desc_key: "cmd.toggle_minimap_current_buffer_desc",
+        action: || Action::ToggleMinimapCurrentBuffer,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_current_line_highlight_current_buffer",
         desc_key: "cmd.toggle_current_line_highlight_current_buffer_desc",
@@ -944,6 +1085,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal, FileExplorer, Terminal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_sticky_scroll",
+        desc_key: "cmd.toggle_sticky_scroll_desc",
+        action: || Action::ToggleStickyScroll,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.toggle_breadcrumbs",
+        desc_key: "cmd.toggle_breadcrumbs_desc",
+        action: || Action::ToggleBreadcrumbs,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.focus_file_explorer",
         desc_key: "cmd.focus_file_explorer_desc",
@@ -1192,6 +1347,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.call_hierarchy",
+        desc_key: "cmd.call_hierarchy_desc",
+        action: || Action::LspCallHierarchy,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.type_hierarchy",
+        desc_key: "cmd.type_hierarchy_desc",
+        action: || Action::LspTypeHierarchy,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.show_signature_help",
         desc_key: "cmd.show_signature_help_desc",
@@ -1206,6 +1375,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.code_lens",
+        desc_key: "cmd.code_lens_desc",
+        action: || Action::LspCodeLens,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.follow_link",
+        desc_key: "cmd.follow_link_desc",
+        action: || Action::LspFollowLink,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.pick_color",
+        desc_key: "cmd.pick_color_desc",
+        action: || Action::LspPickColor,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.start_restart_lsp",
         desc_key: "cmd.start_restart_lsp_desc",
@@ -1234,6 +1424,113 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_spell_check",
+        desc_key: "cmd.toggle_spell_check_desc",
+        action: || Action::ToggleSpellCheck,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    // Debugger commands
+    CommandDef {
+        name_key: "cmd.debug_start",
+        desc_key: "cmd.debug_start_desc",
+        action: || Action::DebugStart,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_stop",
+        desc_key: "cmd.debug_stop_desc",
+        action: || Action::DebugStop,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_continue",
+        desc_key: "cmd.debug_continue_desc",
+        action: || Action::DebugContinue,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_pause",
+        desc_key: "cmd.debug_pause_desc",
+        action: || Action::DebugPause,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_over",
+        desc_key: "cmd.debug_step_over_desc",
+        action: || Action::DebugStepOver,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_into",
+        desc_key: "cmd.debug_step_into_desc",
+        action: || Action::DebugStepInto,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_out",
+        desc_key: "cmd.debug_step_out_desc",
+        action: || Action::DebugStepOut,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_toggle_breakpoint",
+        desc_key: "cmd.debug_toggle_breakpoint_desc",
+        action: || Action::DebugToggleBreakpoint,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_clear_breakpoints",
+        desc_key: "cmd.debug_clear_breakpoints_desc",
+        action: || Action::DebugClearBreakpoints,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    // Task commands
+    CommandDef {
+        name_key: "cmd.run_task",
+        desc_key: "cmd.run_task_desc",
+        action: || Action::RunTask,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.rerun_last_task",
+        desc_key: "cmd.rerun_last_task_desc",
+        action: || Action::RerunLastTask,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.stop_tasks",
+        desc_key: "cmd.stop_tasks_desc",
+        action: || Action::StopTasks,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.next_problem",
+        desc_key: "cmd.next_problem_desc",
+        action: || Action::NextProblem,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.previous_problem",
+        desc_key: "cmd.previous_problem_desc",
+        action: || Action::PreviousProblem,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.navigate_back",
         desc_key: "cmd.navigate_back_desc",
@@ -1554,6 +1851,50 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    // Command blocks work from the live terminal and from its scroll-back
+    // view (`Normal`); the live ones drop into scroll-back to show the result.
+    CommandDef {
+        name_key: "cmd.terminal_previous_prompt",
+        desc_key: "cmd.terminal_previous_prompt_desc",
+        action: || Action::TerminalPreviousPrompt,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.terminal_next_prompt",
+        desc_key: "cmd.terminal_next_prompt_desc",
+        action: || Action::TerminalNextPrompt,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.terminal_select_command_output",
+        desc_key: "cmd.terminal_select_command_output_desc",
+        action: || Action::TerminalSelectCommandOutput,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.terminal_copy_command_output",
+        desc_key: "cmd.terminal_copy_command_output_desc",
+        action: || Action::TerminalCopyCommandOutput,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.terminal_rerun_command",
+        desc_key: "cmd.terminal_rerun_command_desc",
+        action: || Action::TerminalRerunCommand,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.terminal_fold_command_outputs",
+        desc_key: "cmd.terminal_fold_command_outputs_desc",
+        action: || Action::TerminalFoldCommandOutputs,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.exit_terminal_mode",
         desc_key: "cmd.exit_terminal_mode_desc",
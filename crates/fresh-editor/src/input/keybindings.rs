@@ -413,6 +413,13 @@ pub enum Action {
     SelectWord,
     SelectLine,
     ExpandSelection,
+    SmartExpandSelection,
+    SmartShrinkSelection,
+    SelectTextObject(String), // Select a tree-sitter text object (e.g., "function.inner")
+    GotoNextTextObject(String), // Jump to the next text object of a kind (e.g., "function")
+    GotoPreviousTextObject(String),
+    SwapParameterNext,
+    SwapParameterPrevious,
 
     // Block/rectangular selection (column-wise)
     BlockSelectLeft,
@@ -451,6 +458,17 @@ pub enum Action {
     /// Copy the active buffer's file path relative to the workspace root, falling
     /// back to the absolute path if the file lives outside the workspace.
     CopyRelativeFilePath,
+    /// Kill to the end of the line (or the line break at the end) into the
+    /// kill ring; consecutive kills append to the same entry (Emacs `C-k`)
+    KillLine,
+    /// Kill to the start of the next word into the kill ring (Emacs `M-d`)
+    KillWordForward,
+    /// Kill to the start of the previous word into the kill ring (Emacs `M-DEL`)
+    KillWordBackward,
+    /// Replace the just-yanked text with the previous kill ring entry (Emacs `M-y`)
+    YankPop,
+    /// Pick a kill ring entry to paste, with a live preview
+    PasteFromKillRing,
 
     // Vi-style yank (copy without selection, then restore cursor)
     YankWordForward,
@@ -534,6 +552,10 @@ pub enum Action {
     // Undo/redo
     Undo,
     Redo,
+    /// Step to the previous undo state in time, across branches (vim `g-`)
+    UndoInTime,
+    /// Step to the next undo state in time, across branches (vim `g+`)
+    RedoInTime,
 
     // View
     ScrollUp,
@@ -557,6 +579,8 @@ pub enum Action {
     QuickOpenBuffers,
     /// Quick Open - files (empty prefix)
     QuickOpenFiles,
+    /// Quick Open - symbols in the active buffer (prefix: "@")
+    QuickOpenSymbols,
     /// Open Live Grep as a floating overlay (issue #1796).
     OpenLiveGrep,
     /// Re-open Live Grep with the prior query and selection.
@@ -686,6 +710,9 @@ pub enum Action {
     // Scrollbar visibility
     ToggleVerticalScrollbar,
     ToggleHorizontalScrollbar,
+    // Sticky scroll headers and the breadcrumb bar
+    ToggleStickyScroll,
+    ToggleBreadcrumbs,
     FocusFileExplorer,
     FocusEditor,
     /// Toggle keyboard focus between the editor/explorer area and the
@@ -724,15 +751,39 @@ pub enum Action {
     LspGotoDefinition,
     LspReferences,
     LspImplementation,
+    LspCallHierarchy,
+    LspTypeHierarchy,
     LspRename,
     LspHover,
     LspSignatureHelp,
     LspCodeActions,
+    LspCodeLens,
+    LspFollowLink,
+    LspPickColor,
     LspRestart,
     LspStop,
     LspToggleForBuffer,
     ToggleInlayHints,
     ToggleMouseHover,
+    ToggleSpellCheck,
+
+    // Debugger (Debug Adapter Protocol)
+    DebugStart,
+    DebugStop,
+    DebugContinue,
+    DebugPause,
+    DebugStepOver,
+    DebugStepInto,
+    DebugStepOut,
+    DebugToggleBreakpoint,
+    DebugClearBreakpoints,
+
+    // Tasks
+    RunTask,
+    RerunLastTask,
+    StopTasks,
+    NextProblem,
+    PreviousProblem,
 
     // View toggles
     ToggleLineNumbers,
@@ -754,6 +805,9 @@ pub enum Action {
     /// (per-buffer override that persists across restart). Existing folds are
     /// left alone; only the ▾/▸ arrows are hidden.
     ToggleFoldIndicatorsCurrentBuffer,
+    /// Toggle the minimap column for the current buffer only (per-buffer
+    /// override of `editor.show_minimap` that persists across restart).
+    ToggleMinimapCurrentBuffer,
     /// Toggle the current-line highlight for the current buffer only
     /// (per-buffer override that persists across restart).
     ToggleCurrentLineHighlightCurrentBuffer,
@@ -840,6 +894,12 @@ pub enum Action {
     ToggleKeyboardCapture, // Toggle keyboard capture mode (all keys go to terminal)
     TerminalPaste,  // Paste clipboard contents into terminal as a single batch
     SendSelectionToTerminal, // Run the selection (or current line) in the last-focused terminal
+    TerminalPreviousPrompt, // Jump to the previous shell prompt (OSC 133 command blocks)
+    TerminalNextPrompt, // Jump to the next shell prompt
+    TerminalSelectCommandOutput, // Select the output of the command at the cursor (or the last one)
+    TerminalCopyCommandOutput, // Copy the output of the command at the cursor (or the last one)
+    TerminalRerunCommand, // Run the command at the cursor (or the last one) again
+    TerminalFoldCommandOutputs, // Fold / unfold long command outputs in scroll-back
 
     // Shell command operations
     ShellCommand,        // Run shell command on buffer/selection, output to new buffer
@@ -1005,6 +1065,10 @@ impl Action {
             "select_word" => SelectWord,
             "select_line" => SelectLine,
             "expand_selection" => ExpandSelection,
+            "smart_expand_selection" => SmartExpandSelection,
+            "smart_shrink_selection" => SmartShrinkSelection,
+            "swap_parameter_next" => SwapParameterNext,
+            "swap_parameter_previous" => SwapParameterPrevious,
 
             "block_select_left" => BlockSelectLeft,
             "block_select_right" => BlockSelectRight,
@@ -1032,6 +1096,11 @@ impl Action {
             "paste" => Paste,
             "copy_file_path" => CopyFilePath,
             "copy_relative_file_path" => CopyRelativeFilePath,
+            "kill_line" => KillLine,
+            "kill_word_forward" => KillWordForward,
+            "kill_word_backward" => KillWordBackward,
+            "yank_pop" => YankPop,
+            "paste_from_kill_ring" => PasteFromKillRing,
 
             "yank_word_forward" => YankWordForward,
             "yank_word_backward" => YankWordBackward,
@@ -1094,6 +1163,8 @@ impl Action {
 
             "undo" => Undo,
             "redo" => Redo,
+            "undo_in_time" => UndoInTime,
+            "redo_in_time" => RedoInTime,
 
             "scroll_up" => ScrollUp,
             "scroll_down" => ScrollDown,
@@ -1111,6 +1182,7 @@ impl Action {
             "quick_open" => QuickOpen,
             "quick_open_buffers" => QuickOpenBuffers,
             "quick_open_files" => QuickOpenFiles,
+            "quick_open_symbols" => QuickOpenSymbols,
             "open_live_grep" => OpenLiveGrep,
             "resume_live_grep" => ResumeLiveGrep,
             "toggle_utility_dock" => ToggleUtilityDock,
@@ -1197,6 +1269,8 @@ impl Action {
             "toggle_prompt_line" => TogglePromptLine,
             "toggle_vertical_scrollbar" => ToggleVerticalScrollbar,
             "toggle_horizontal_scrollbar" => ToggleHorizontalScrollbar,
+            "toggle_sticky_scroll" => ToggleStickyScroll,
+            "toggle_breadcrumbs" => ToggleBreadcrumbs,
             "focus_file_explorer" => FocusFileExplorer,
             "focus_editor" => FocusEditor,
             "toggle_dock_focus" => ToggleDockFocus,
@@ -1231,15 +1305,37 @@ impl Action {
             "lsp_goto_definition" => LspGotoDefinition,
             "lsp_references" => LspReferences,
             "lsp_implementation" => LspImplementation,
+            "lsp_call_hierarchy" => LspCallHierarchy,
+            "lsp_type_hierarchy" => LspTypeHierarchy,
             "lsp_rename" => LspRename,
             "lsp_hover" => LspHover,
             "lsp_signature_help" => LspSignatureHelp,
             "lsp_code_actions" => LspCodeActions,
+            "lsp_code_lens" => LspCodeLens,
+            "lsp_follow_link" => LspFollowLink,
+            "lsp_pick_color" => LspPickColor,
             "lsp_restart" => LspRestart,
             "lsp_stop" => LspStop,
             "lsp_toggle_for_buffer" => LspToggleForBuffer,
             "toggle_inlay_hints" => ToggleInlayHints,
             "toggle_mouse_hover" => ToggleMouseHover,
+            "toggle_spell_check" => ToggleSpellCheck,
+
+            "debug_start" => DebugStart,
+            "debug_stop" => DebugStop,
+            "debug_continue" => DebugContinue,
+            "debug_pause" => DebugPause,
+            "debug_step_over" => DebugStepOver,
+            "debug_step_into" => DebugStepInto,
+            "debug_step_out" => DebugStepOut,
+            "debug_toggle_breakpoint" => DebugToggleBreakpoint,
+            "debug_clear_breakpoints" => DebugClearBreakpoints,
+
+            "run_task" => RunTask,
+            "rerun_last_task" => RerunLastTask,
+            "stop_tasks" => StopTasks,
+            "next_problem" => NextProblem,
+            "previous_problem" => PreviousProblem,
 
             "toggle_line_numbers" => ToggleLineNumbers,
             "toggle_line_numbers_current_buffer" => ToggleLineNumbersCurrentBuffer,
@@ -1247,6 +1343,7 @@ impl Action {
             "toggle_virtual_space_current_buffer" => ToggleVirtualSpaceCurrentBuffer,
             "toggle_indentation_guide_current_buffer" => ToggleIndentationGuideCurrentBuffer,
             "toggle_fold_indicators_current_buffer" => ToggleFoldIndicatorsCurrentBuffer,
+            "toggle_minimap_current_buffer" => ToggleMinimapCurrentBuffer,
             "toggle_current_line_highlight_current_buffer" => ToggleCurrentLineHighlightCurrentBuffer,
             "toggle_occurrence_highlight_current_buffer" => ToggleOccurrenceHighlightCurrentBuffer,
             "trigger_wave_animation" => TriggerWaveAnimation,
@@ -1304,6 +1401,12 @@ impl Action {
             "toggle_keyboard_capture" => ToggleKeyboardCapture,
             "terminal_paste" => TerminalPaste,
             "send_selection_to_terminal" => SendSelectionToTerminal,
+            "terminal_previous_prompt" => TerminalPreviousPrompt,
+            "terminal_next_prompt" => TerminalNextPrompt,
+            "terminal_select_command_output" => TerminalSelectCommandOutput,
+            "terminal_copy_command_output" => TerminalCopyCommandOutput,
+            "terminal_rerun_command" => TerminalRerunCommand,
+            "terminal_fold_command_outputs" => TerminalFoldCommandOutputs,
 
             "shell_command" => ShellCommand,
             "shell_command_replace" => ShellCommandReplace,
@@ -1380,6 +1483,18 @@ impl Action {
                 let text = args.get("text")?.as_str()?;
                 Self::PromptConfirmWithText(text.to_string())
             },
+            "select_text_object" => SelectTextObject : {
+                let object = args.get("object")?.as_str()?;
+                Self::SelectTextObject(object.to_string())
+            },
+            "goto_next_text_object" => GotoNextTextObject : {
+                let object = args.get("object")?.as_str()?;
+                Self::GotoNextTextObject(object.to_string())
+            },
+            "goto_previous_text_object" => GotoPreviousTextObject : {
+                let object = args.get("object")?.as_str()?;
+                Self::GotoPreviousTextObject(object.to_string())
+            },
         }
     }
 
@@ -1393,6 +1508,9 @@ impl Action {
         match bare_action {
             "menu_open" => Some("name"),
             "switch_keybinding_map" => Some("map"),
+            "select_text_object" | "goto_next_text_object" | "goto_previous_text_object" => {
+                Some("object")
+            }
             _ => None,
         }
     }
@@ -1417,6 +1535,11 @@ impl Action {
         match self {
             Self::MenuOpen(name) => format!("menu_open:{}", name),
             Self::SwitchKeybindingMap(map) => format!("switch_keybinding_map:{}", map),
+            Self::SelectTextObject(object) => format!("select_text_object:{}", object),
+            Self::GotoNextTextObject(object) => format!("goto_next_text_object:{}", object),
+            Self::GotoPreviousTextObject(object) => {
+                format!("goto_previous_text_object:{}", object)
+            }
             other => other.to_action_str(),
         }
     }
@@ -1460,6 +1583,11 @@ impl Action {
             Self::PromptConfirmWithText(text) => {
                 args.insert("text".to_string(), Value::String(text.clone()));
             }
+            Self::SelectTextObject(object)
+            | Self::GotoNextTextObject(object)
+            | Self::GotoPreviousTextObject(object) => {
+                args.insert("object".to_string(), Value::String(object.clone()));
+            }
             // No-arg actions (motions, edits, commands): empty args.
             _ => {}
         }
@@ -1535,6 +1663,13 @@ impl Action {
                 | Action::SelectWord
                 | Action::SelectLine
                 | Action::ExpandSelection
+                | Action::SmartExpandSelection
+                | Action::SmartShrinkSelection
+                | Action::SelectTextObject(_)
+                | Action::GotoNextTextObject(_)
+                | Action::GotoPreviousTextObject(_)
+                | Action::SwapParameterNext
+                | Action::SwapParameterPrevious
                 // Block selection
                 | Action::BlockSelectLeft
                 | Action::BlockSelectRight
@@ -1559,9 +1694,15 @@ impl Action {
                 // Clipboard editing (but not Copy)
                 | Action::Cut
                 | Action::Paste
+                | Action::KillLine
+                | Action::KillWordForward
+                | Action::KillWordBackward
+                | Action::YankPop
                 // Undo/Redo
                 | Action::Undo
                 | Action::Redo
+                | Action::UndoInTime
+                | Action::RedoInTime
         )
     }
 
@@ -1588,6 +1729,10 @@ impl Action {
                 | Action::MoveLineDown
                 | Action::Cut
                 | Action::Paste
+                | Action::KillLine
+                | Action::KillWordForward
+                | Action::KillWordBackward
+                | Action::YankPop
         )
     }
 }
@@ -1970,8 +2115,15 @@ impl KeybindingResolver {
             action,
             Action::Undo
                 | Action::Redo
+                | Action::UndoInTime
+                | Action::RedoInTime
                 | Action::Cut
                 | Action::Paste
+                | Action::KillLine
+                | Action::KillWordForward
+                | Action::KillWordBackward
+                | Action::YankPop
+                | Action::PasteFromKillRing
                 | Action::DeleteLine
                 | Action::DeleteWordBackward
                 | Action::DeleteWordForward
@@ -2001,6 +2153,7 @@ impl KeybindingResolver {
                 | Action::QuickOpen
                 | Action::QuickOpenBuffers
                 | Action::QuickOpenFiles
+                | Action::QuickOpenSymbols
                 | Action::OpenLiveGrep
                 | Action::ResumeLiveGrep
                 | Action::CycleLiveGrepProvider
@@ -2050,6 +2203,26 @@ impl KeybindingResolver {
                 | Action::OpenTerminalBelow
                 | Action::CloseTerminal
                 | Action::TerminalPaste
+                | Action::TerminalPreviousPrompt
+                | Action::TerminalNextPrompt
+                | Action::TerminalSelectCommandOutput
+                | Action::TerminalCopyCommandOutput
+                | Action::TerminalRerunCommand
+                | Action::TerminalFoldCommandOutputs
+        )
+    }
+
+    /// Command-block actions: bound in the terminal context, but also
+    /// resolved there while the terminal shows its scroll-back view.
+    pub fn is_terminal_command_block_action(action: &Action) -> bool {
+        matches!(
+            action,
+            Action::TerminalPreviousPrompt
+                | Action::TerminalNextPrompt
+                | Action::TerminalSelectCommandOutput
+                | Action::TerminalCopyCommandOutput
+                | Action::TerminalRerunCommand
+                | Action::TerminalFoldCommandOutputs
         )
     }
 
@@ -2719,6 +2892,17 @@ impl KeybindingResolver {
             Action::SelectWord => t!("action.select_word"),
             Action::SelectLine => t!("action.select_line"),
             Action::ExpandSelection => t!("action.expand_selection"),
+            Action::SmartExpandSelection => t!("action.smart_expand_selection"),
+            Action::SmartShrinkSelection => t!("action.smart_shrink_selection"),
+            Action::SelectTextObject(object) => t!("action.select_text_object", object = object),
+            Action::GotoNextTextObject(object) => {
+                t!("action.goto_next_text_object", object = object)
+            }
+            Action::GotoPreviousTextObject(object) => {
+                t!("action.goto_previous_text_object", object = object)
+            }
+            Action::SwapParameterNext => t!("action.swap_parameter_next"),
+            Action::SwapParameterPrevious => t!("action.swap_parameter_previous"),
             Action::BlockSelectLeft => t!("action.block_select_left"),
             Action::BlockSelectRight => t!("action.block_select_right"),
             Action::BlockSelectUp => t!("action.block_select_up"),
@@ -2745,6 +2929,11 @@ impl KeybindingResolver {
             Action::Paste => t!("action.paste"),
             Action::CopyFilePath => t!("action.copy_file_path"),
             Action::CopyRelativeFilePath => t!("action.copy_relative_file_path"),
+            Action::KillLine => t!("action.kill_line"),
+            Action::KillWordForward => t!("action.kill_word_forward"),
+            Action::KillWordBackward => t!("action.kill_word_backward"),
+            Action::YankPop => t!("action.yank_pop"),
+            Action::PasteFromKillRing => t!("action.paste_from_kill_ring"),
             Action::YankWordForward => t!("action.yank_word_forward"),
             Action::YankWordBackward => t!("action.yank_word_backward"),
             Action::YankToLineEnd => t!("action.yank_to_line_end"),
@@ -2804,6 +2993,8 @@ impl KeybindingResolver {
             Action::PromptJumpToBookmark => t!("action.prompt_jump_to_bookmark"),
             Action::Undo => t!("action.undo"),
             Action::Redo => t!("action.redo"),
+            Action::UndoInTime => t!("action.undo_in_time"),
+            Action::RedoInTime => t!("action.redo_in_time"),
             Action::ScrollUp => t!("action.scroll_up"),
             Action::ScrollDown => t!("action.scroll_down"),
             Action::ShowHelp => t!("action.show_help"),
@@ -2820,6 +3011,7 @@ impl KeybindingResolver {
             Action::QuickOpen => t!("action.quick_open"),
             Action::QuickOpenBuffers => t!("action.quick_open_buffers"),
             Action::QuickOpenFiles => t!("action.quick_open_files"),
+            Action::QuickOpenSymbols => t!("action.quick_open_symbols"),
             Action::OpenLiveGrep => t!("action.open_live_grep"),
             Action::ResumeLiveGrep => t!("action.resume_live_grep"),
             Action::ToggleUtilityDock => t!("action.toggle_utility_dock"),
@@ -2901,6 +3093,8 @@ impl KeybindingResolver {
             Action::TogglePromptLine => t!("action.toggle_prompt_line"),
             Action::ToggleVerticalScrollbar => t!("action.toggle_vertical_scrollbar"),
             Action::ToggleHorizontalScrollbar => t!("action.toggle_horizontal_scrollbar"),
+            Action::ToggleStickyScroll => t!("action.toggle_sticky_scroll"),
+            Action::ToggleBreadcrumbs => t!("action.toggle_breadcrumbs"),
             Action::FocusFileExplorer => t!("action.focus_file_explorer"),
             Action::FocusEditor => t!("action.focus_editor"),
             Action::ToggleDockFocus => t!("action.toggle_dock_focus"),
@@ -2936,15 +3130,35 @@ impl KeybindingResolver {
             Action::LspGotoDefinition => t!("action.lsp_goto_definition"),
             Action::LspReferences => t!("action.lsp_references"),
             Action::LspImplementation => t!("action.lsp_implementation"),
+            Action::LspCallHierarchy => t!("action.lsp_call_hierarchy"),
+            Action::LspTypeHierarchy => t!("action.lsp_type_hierarchy"),
             Action::LspRename => t!("action.lsp_rename"),
             Action::LspHover => t!("action.lsp_hover"),
             Action::LspSignatureHelp => t!("action.lsp_signature_help"),
             Action::LspCodeActions => t!("action.lsp_code_actions"),
+            Action::LspCodeLens => t!("action.lsp_code_lens"),
+            Action::LspFollowLink => t!("action.lsp_follow_link"),
+            Action::LspPickColor => t!("action.lsp_pick_color"),
             Action::LspRestart => t!("action.lsp_restart"),
             Action::LspStop => t!("action.lsp_stop"),
             Action::LspToggleForBuffer => t!("action.lsp_toggle_for_buffer"),
             Action::ToggleInlayHints => t!("action.toggle_inlay_hints"),
             Action::ToggleMouseHover => t!("action.toggle_mouse_hover"),
+            Action::ToggleSpellCheck => t!("action.toggle_spell_check"),
+            Action::DebugStart => t!("action.debug_start"),
+            Action::DebugStop => t!("action.debug_stop"),
+            Action::DebugContinue => t!("action.debug_continue"),
+            Action::DebugPause => t!("action.debug_pause"),
+            Action::DebugStepOver => t!("action.debug_step_over"),
+            Action::DebugStepInto => t!("action.debug_step_into"),
+            Action::DebugStepOut => t!("action.debug_step_out"),
+            Action::DebugToggleBreakpoint => t!("action.debug_toggle_breakpoint"),
+            Action::DebugClearBreakpoints => t!("action.debug_clear_breakpoints"),
+            Action::RunTask => t!("action.run_task"),
+            Action::RerunLastTask => t!("action.rerun_last_task"),
+            Action::StopTasks => t!("action.stop_tasks"),
+            Action::NextProblem => t!("action.next_problem"),
+            Action::PreviousProblem => t!("action.previous_problem"),
             Action::ToggleLineNumbers => t!("action.toggle_line_numbers"),
             Action::ToggleLineNumbersCurrentBuffer => {
                 t!("action.toggle_line_numbers_current_buffer")
@@ -2959,6 +3173,7 @@ impl KeybindingResolver {
             Action::ToggleFoldIndicatorsCurrentBuffer => {
                 t!("action.toggle_fold_indicators_current_buffer")
             }
+            Action::ToggleMinimapCurrentBuffer => t!("action.toggle_minimap_current_buffer"),
             Action::ToggleCurrentLineHighlightCurrentBuffer => {
                 t!("action.toggle_current_line_highlight_current_buffer")
             }
@@ -3021,6 +3236,12 @@ impl KeybindingResolver {
             Action::ToggleKeyboardCapture => t!("action.toggle_keyboard_capture"),
             Action::TerminalPaste => t!("action.terminal_paste"),
             Action::SendSelectionToTerminal => t!("action.send_selection_to_terminal"),
+            Action::TerminalPreviousPrompt => t!("action.terminal_previous_prompt"),
+            Action::TerminalNextPrompt => t!("action.terminal_next_prompt"),
+            Action::TerminalSelectCommandOutput => t!("action.terminal_select_command_output"),
+            Action::TerminalCopyCommandOutput => t!("action.terminal_copy_command_output"),
+            Action::TerminalRerunCommand => t!("action.terminal_rerun_command"),
+            Action::TerminalFoldCommandOutputs => t!("action.terminal_fold_command_outputs"),
             Action::OpenSettings => t!("action.open_settings"),
             Action::CloseSettings => t!("action.close_settings"),
             Action::SettingsSave => t!("action.settings_save"),
@@ -3795,6 +4016,8 @@ mod tests {
             Action::MenuOpen("File".to_string()),
             Action::SwitchKeybindingMap("emacs".to_string()),
             Action::CopyWithTheme("one-dark".to_string()),
+            Action::SelectTextObject("function.inner".to_string()),
+            Action::GotoPreviousTextObject("class".to_string()),
         ];
         for action in cases {
             let spec = action.to_action_spec();
@@ -4533,7 +4756,8 @@ mod tests {
     }
 
     /// Emacs `C-k` is kill-line: it kills from point to end of line. Binding
-    /// it to `delete_line` threw away the text *before* the cursor too.
+    /// it to `delete_line` threw away the text *before* the cursor too. The
+    /// kill lands in the kill ring, and `M-y` cycles the yank through it.
     #[test]
     fn emacs_ctrl_k_kills_to_end_of_line() {
         let config = Config {
@@ -4544,7 +4768,12 @@ mod tests {
         let event = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL);
         assert_eq!(
             resolver.resolve(&event, KeyContext::Normal),
-            Action::DeleteToLineEnd
+            Action::KillLine
+        );
+        let event = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::ALT);
+        assert_eq!(
+            resolver.resolve(&event, KeyContext::Normal),
+            Action::YankPop
         );
     }
 
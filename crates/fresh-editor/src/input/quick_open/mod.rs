@@ -4,16 +4,18 @@
 //! Inspired by VSCode's Quick Open (Ctrl+P) which supports:
 //! - Empty prefix: file finder
 //! - `>`: command palette
-//! - `#`: symbol finder (future)
-//! - `@`: go to symbol in file (future)
+//! - `#`: open buffers, then matching workspace symbols
+//! - `@`: go to symbol in file
 //! - `:`: go to line
 //!
 //! Providers are registered with a prefix and handle suggestion generation
 //! and selection for their domain.
 
 pub mod providers;
+pub mod symbols;
 
 pub use providers::{BufferProvider, CommandProvider, FileProvider, GotoLineProvider};
+pub use symbols::{DocumentSymbolProvider, SymbolTarget};
 
 use crate::input::commands::Suggestion;
 use crate::input::keybindings::Action;
@@ -39,6 +41,8 @@ pub enum QuickOpenResult {
     ShowBufferGroup(usize),
     /// Go to a line in the current buffer
     GotoLine(GotoLineTarget),
+    /// Go to a symbol, in the current buffer or another file
+    GotoSymbol(SymbolTarget),
     /// Do nothing (provider handled it internally)
     None,
     /// Show an error message
@@ -118,6 +122,8 @@ pub struct QuickOpenContext {
     pub buffer_caps: crate::app::buffer_capabilities::BufferCapabilities,
     /// Whether relative line numbers are enabled
     pub relative_line_numbers: bool,
+    /// Whether Nerd Font icons are enabled (`editor.nerd_font_icons`)
+    pub nerd_font_icons: bool,
 }
 
 /// Information about an open buffer
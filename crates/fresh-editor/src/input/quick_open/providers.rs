@@ -3,9 +3,11 @@
 //! This module contains the standard providers:
 //! - FileProvider: Find files in the project (default, no prefix)
 //! - CommandProvider: Command palette (prefix: ">")
-//! - BufferProvider: Switch between open buffers (prefix: "#")
+//! - BufferProvider: Switch between open buffers, or jump to a workspace
+//!   symbol (prefix: "#")
 //! - GotoLineProvider: Go to a specific line (prefix: ":")
 
+use super::symbols::{SymbolTarget, WorkspaceSymbols};
 use super::{
     parse_goto_line_input, GotoLineTarget, QuickOpenContext, QuickOpenProvider, QuickOpenResult,
 };
@@ -115,18 +117,25 @@ impl QuickOpenProvider for CommandProvider {
 /// id. The two are separate id spaces and would otherwise collide.
 const GROUP_VALUE_PREFIX: &str = "group:";
 
-/// Provider for switching between open buffers
-pub struct BufferProvider;
+/// Marks a suggestion value as a workspace symbol location.
+const SYMBOL_VALUE_PREFIX: &str = "symbol:";
+
+/// Provider for switching between open buffers. Workspace symbols matching
+/// the query are listed after the buffers; the editor fills them in as
+/// language servers answer (see [`Self::workspace_symbols`]).
+#[derive(Default)]
+pub struct BufferProvider {
+    workspace_symbols: std::sync::Mutex<WorkspaceSymbols>,
+}
 
 impl BufferProvider {
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
-}
 
-impl Default for BufferProvider {
-    fn default() -> Self {
-        Self::new()
+    /// The workspace symbol results listed after the buffers.
+    pub fn workspace_symbols(&self) -> std::sync::MutexGuard<'_, WorkspaceSymbols> {
+        self.workspace_symbols.lock().unwrap()
     }
 }
 
@@ -171,7 +180,18 @@ impl QuickOpenProvider for BufferProvider {
 
         // Sort by score (higher is better), then by ID (lower = older = higher priority when tied)
         scored.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.2.cmp(&b.2)));
-        scored.into_iter().map(|(s, _, _)| s).collect()
+        let mut suggestions: Vec<Suggestion> = scored.into_iter().map(|(s, _, _)| s).collect();
+
+        suggestions.extend(
+            self.workspace_symbols()
+                .suggestions(query, context.nerd_font_icons)
+                .into_iter()
+                .map(|mut s| {
+                    s.value = s.value.map(|v| format!("{SYMBOL_VALUE_PREFIX}{v}"));
+                    s
+                }),
+        );
+        suggestions
     }
 
     fn on_select(
@@ -180,9 +200,17 @@ impl QuickOpenProvider for BufferProvider {
         _query: &str,
         _context: &QuickOpenContext,
     ) -> QuickOpenResult {
-        let Some(value) = suggestion.and_then(|s| s.value.as_deref()) else {
+        let Some(value) = suggestion
+            .filter(|s| !s.disabled)
+            .and_then(|s| s.value.as_deref())
+        else {
             return QuickOpenResult::None;
         };
+        if let Some(symbol) = value.strip_prefix(SYMBOL_VALUE_PREFIX) {
+            return SymbolTarget::from_value(symbol)
+                .map(QuickOpenResult::GotoSymbol)
+                .unwrap_or(QuickOpenResult::None);
+        }
         match value.strip_prefix(GROUP_VALUE_PREFIX) {
             Some(leaf) => leaf
                 .parse::<usize>()
@@ -1067,6 +1095,7 @@ mod tests {
                 any_modified: true,
             },
             relative_line_numbers: false,
+            nerd_font_icons: false,
         }
     }
 
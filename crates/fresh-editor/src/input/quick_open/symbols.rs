@@ -0,0 +1,430 @@
+//! Symbol Quick Open
+//!
+//! - [`DocumentSymbolProvider`] (`@`): the symbols of the active file, from
+//!   the language server's `textDocument/documentSymbol` or, without one, the
+//!   local outline in [`crate::primitives::outline`].
+//! - [`WorkspaceSymbols`]: the `workspace/symbol` results the `#` provider
+//!   lists below the open buffers. Each server's answer is merged in as it
+//!   arrives, so the list grows while slower servers are still searching.
+//!
+//! Neither talks to the language server itself: the editor sends the
+//! requests, fills these caches from the responses and refreshes the prompt.
+
+use super::{QuickOpenContext, QuickOpenProvider, QuickOpenResult};
+use crate::input::commands::Suggestion;
+use crate::input::fuzzy::FuzzyMatcher;
+use lsp_types::SymbolKind;
+use rust_i18n::t;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Most workspace symbols listed at once.
+pub const MAX_WORKSPACE_SYMBOLS: usize = 200;
+
+/// One symbol listed by `@` or `#`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolEntry {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Name of the symbol this one is declared in.
+    pub container: Option<String>,
+    /// Nesting depth within its document.
+    pub depth: usize,
+    /// Where the symbol is. `uri` is `None` for the active buffer's own
+    /// symbols; `label` is the path shown for symbols in other files.
+    pub target: SymbolTarget,
+    pub label: Option<String>,
+}
+
+/// A symbol location: an LSP position (0-based line, UTF-16 column) in the
+/// document at `uri`, or in the active buffer when `uri` is `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolTarget {
+    pub uri: Option<String>,
+    pub line: u32,
+    pub character: u32,
+}
+
+impl SymbolTarget {
+    /// Encode as a suggestion value. The URI goes last since it may
+    /// contain `:` itself.
+    fn to_value(&self) -> String {
+        match &self.uri {
+            Some(uri) => format!("{}:{}:{}", self.line, self.character, uri),
+            None => format!("{}:{}", self.line, self.character),
+        }
+    }
+
+    /// Decode a suggestion value written by [`Self::to_value`].
+    pub fn from_value(value: &str) -> Option<Self> {
+        let mut parts = value.splitn(3, ':');
+        let line = parts.next()?.parse().ok()?;
+        let character = parts.next()?.parse().ok()?;
+        let uri = parts.next().map(str::to_string);
+        Some(Self {
+            uri,
+            line,
+            character,
+        })
+    }
+}
+
+/// Icon shown before a symbol. The default set uses single BMP glyphs, as
+/// the completion popup does; the Nerd Font set uses the codicon symbols.
+pub fn symbol_kind_icon(kind: SymbolKind, nerd_fonts: bool) -> &'static str {
+    if nerd_fonts {
+        return match kind {
+            SymbolKind::FUNCTION | SymbolKind::METHOD | SymbolKind::CONSTRUCTOR => "\u{ea8c}",
+            SymbolKind::CLASS => "\u{eb5b}",
+            SymbolKind::STRUCT | SymbolKind::OBJECT => "\u{ea91}",
+            SymbolKind::INTERFACE => "\u{eb61}",
+            SymbolKind::ENUM => "\u{ea95}",
+            SymbolKind::ENUM_MEMBER => "\u{eb5e}",
+            SymbolKind::MODULE | SymbolKind::NAMESPACE | SymbolKind::PACKAGE => "\u{ea8b}",
+            SymbolKind::CONSTANT => "\u{eb5d}",
+            SymbolKind::FIELD => "\u{eb5f}",
+            SymbolKind::PROPERTY => "\u{eb65}",
+            SymbolKind::VARIABLE => "\u{ea88}",
+            SymbolKind::TYPE_PARAMETER => "\u{ea92}",
+            SymbolKind::STRING => "\u{eb8d}",
+            SymbolKind::KEY => "\u{ea93}",
+            SymbolKind::EVENT => "\u{ea86}",
+            SymbolKind::OPERATOR => "\u{eb64}",
+            _ => "\u{eb5f}",
+        };
+    }
+    match kind {
+        SymbolKind::FUNCTION | SymbolKind::METHOD | SymbolKind::CONSTRUCTOR => "λ",
+        SymbolKind::CLASS => "C",
+        SymbolKind::STRUCT | SymbolKind::OBJECT => "S",
+        SymbolKind::INTERFACE => "I",
+        SymbolKind::ENUM => "E",
+        SymbolKind::ENUM_MEMBER => "e",
+        SymbolKind::MODULE | SymbolKind::NAMESPACE | SymbolKind::PACKAGE => "M",
+        SymbolKind::CONSTANT => "c",
+        SymbolKind::FIELD | SymbolKind::PROPERTY | SymbolKind::VARIABLE => "v",
+        SymbolKind::TYPE_PARAMETER => "T",
+        SymbolKind::STRING => "#",
+        SymbolKind::KEY => "k",
+        _ => "•",
+    }
+}
+
+/// Rank `symbols` against `query` and build their suggestions. Matches are
+/// grouped by container, groups ordered by their best match; an empty query
+/// keeps document order and indents by depth.
+pub fn symbol_suggestions(
+    symbols: &[SymbolEntry],
+    query: &str,
+    nerd_fonts: bool,
+    limit: usize,
+) -> Vec<Suggestion> {
+    let query = query.trim();
+    if query.is_empty() {
+        return symbols
+            .iter()
+            .take(limit)
+            .map(|s| symbol_suggestion(s, nerd_fonts, true))
+            .collect();
+    }
+
+    let mut matcher = FuzzyMatcher::new(query);
+    let scored: Vec<(&SymbolEntry, i32)> = symbols
+        .iter()
+        .filter_map(|s| {
+            let m = matcher.match_target(&s.name);
+            m.matched.then_some((s, m.score))
+        })
+        .collect();
+
+    let mut best: HashMap<Option<&str>, i32> = HashMap::new();
+    for (symbol, score) in &scored {
+        let group = best.entry(symbol.container.as_deref()).or_insert(*score);
+        *group = (*group).max(*score);
+    }
+    let mut ranked = scored;
+    ranked.sort_by(|(a, a_score), (b, b_score)| {
+        let a_group = best[&a.container.as_deref()];
+        let b_group = best[&b.container.as_deref()];
+        b_group
+            .cmp(&a_group)
+            .then_with(|| a.container.cmp(&b.container))
+            .then_with(|| b_score.cmp(a_score))
+            .then_with(|| a.label.cmp(&b.label))
+            .then_with(|| a.target.line.cmp(&b.target.line))
+    });
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(s, _)| symbol_suggestion(s, nerd_fonts, false))
+        .collect()
+}
+
+fn symbol_suggestion(symbol: &SymbolEntry, nerd_fonts: bool, indented: bool) -> Suggestion {
+    let indent = if indented {
+        "  ".repeat(symbol.depth)
+    } else {
+        String::new()
+    };
+    let text = format!(
+        "{}{} {}",
+        indent,
+        symbol_kind_icon(symbol.kind, nerd_fonts),
+        symbol.name
+    );
+    // `:N` reads as a line number, as in the `:` provider.
+    let line = format!(":{}", symbol.target.line + 1);
+    let location = match &symbol.label {
+        Some(label) => format!("{}{}", label, line),
+        None => line,
+    };
+    // Indentation already shows the container in document order.
+    let description = match &symbol.container {
+        Some(container) if !indented => format!("{}  {}", container, location),
+        _ => location,
+    };
+    Suggestion::new(text)
+        .with_description(description)
+        .with_value(symbol.target.to_value())
+}
+
+// ============================================================================
+// Document Symbol Provider (prefix: "@")
+// ============================================================================
+
+/// The symbols of one version of one buffer.
+#[derive(Debug, Clone)]
+pub struct DocumentSymbols {
+    pub buffer_id: usize,
+    pub version: u64,
+    /// Set while the language server's answer is awaited.
+    pub loading: bool,
+    pub symbols: Vec<SymbolEntry>,
+}
+
+/// Provider for going to a symbol in the active file
+#[derive(Default)]
+pub struct DocumentSymbolProvider {
+    cache: Mutex<Option<DocumentSymbols>>,
+}
+
+impl DocumentSymbolProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the cache holds (or is loading) the symbols of `version` of
+    /// `buffer_id`.
+    pub fn is_current(&self, buffer_id: usize, version: u64) -> bool {
+        self.cache
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|c| c.buffer_id == buffer_id && c.version == version)
+    }
+
+    /// Replace the cached symbols.
+    pub fn set(&self, symbols: DocumentSymbols) {
+        *self.cache.lock().unwrap() = Some(symbols);
+    }
+}
+
+impl QuickOpenProvider for DocumentSymbolProvider {
+    fn prefix(&self) -> &str {
+        "@"
+    }
+
+    fn suggestions(&self, query: &str, context: &QuickOpenContext) -> Vec<Suggestion> {
+        let cache = self.cache.lock().unwrap();
+        let Some(cache) = cache
+            .as_ref()
+            .filter(|c| c.buffer_id == context.active_buffer_id)
+        else {
+            return vec![Suggestion::disabled(
+                t!("quick_open.symbols_loading").to_string(),
+            )];
+        };
+        if cache.loading {
+            return vec![Suggestion::disabled(
+                t!("quick_open.symbols_loading").to_string(),
+            )];
+        }
+        let suggestions =
+            symbol_suggestions(&cache.symbols, query, context.nerd_font_icons, usize::MAX);
+        if suggestions.is_empty() {
+            let message = if cache.symbols.is_empty() {
+                t!("quick_open.no_symbols")
+            } else {
+                t!("quick_open.no_matching_symbols")
+            };
+            return vec![Suggestion::disabled(message.to_string())];
+        }
+        suggestions
+    }
+
+    fn on_select(
+        &self,
+        suggestion: Option<&Suggestion>,
+        _query: &str,
+        _context: &QuickOpenContext,
+    ) -> QuickOpenResult {
+        suggestion
+            .filter(|s| !s.disabled)
+            .and_then(|s| s.value.as_deref())
+            .and_then(SymbolTarget::from_value)
+            .map(QuickOpenResult::GotoSymbol)
+            .unwrap_or(QuickOpenResult::None)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+// ============================================================================
+// Workspace symbols (listed by the "#" provider)
+// ============================================================================
+
+/// Workspace symbol results for one query, merged across servers.
+#[derive(Debug, Default)]
+pub struct WorkspaceSymbols {
+    query: String,
+    /// Servers that have not answered yet.
+    pending: usize,
+    symbols: Vec<SymbolEntry>,
+}
+
+impl WorkspaceSymbols {
+    /// The query the current results are for.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Start a new search, dropping the previous results.
+    pub fn begin(&mut self, query: &str, servers: usize) {
+        self.query = query.to_string();
+        self.pending = servers;
+        self.symbols.clear();
+    }
+
+    /// Merge one server's answer to `query`. Answers to an older query are
+    /// ignored.
+    pub fn add(&mut self, query: &str, symbols: Vec<SymbolEntry>) {
+        if query != self.query {
+            return;
+        }
+        self.pending = self.pending.saturating_sub(1);
+        for symbol in symbols {
+            if !self.symbols.contains(&symbol) {
+                self.symbols.push(symbol);
+            }
+        }
+    }
+
+    /// Whether some server is still searching.
+    pub fn is_searching(&self) -> bool {
+        self.pending > 0
+    }
+
+    /// Suggestions for `query`, or none if the results are for another one.
+    pub fn suggestions(&self, query: &str, nerd_fonts: bool) -> Vec<Suggestion> {
+        if query.trim() != self.query || self.query.is_empty() {
+            return Vec::new();
+        }
+        let mut suggestions =
+            symbol_suggestions(&self.symbols, query, nerd_fonts, MAX_WORKSPACE_SYMBOLS);
+        if self.is_searching() {
+            suggestions.push(Suggestion::disabled(
+                t!("quick_open.searching_symbols").to_string(),
+            ));
+        }
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(name: &str, container: Option<&str>, line: u32) -> SymbolEntry {
+        SymbolEntry {
+            name: name.to_string(),
+            kind: SymbolKind::FUNCTION,
+            container: container.map(str::to_string),
+            depth: usize::from(container.is_some()),
+            target: SymbolTarget {
+                uri: None,
+                line,
+                character: 0,
+            },
+            label: None,
+        }
+    }
+
+    #[test]
+    fn test_symbol_target_value_round_trip_keeps_uri_colons() {
+        let target = SymbolTarget {
+            uri: Some("file:///C:/src/main.rs".to_string()),
+            line: 12,
+            character: 4,
+        };
+        assert_eq!(SymbolTarget::from_value(&target.to_value()), Some(target));
+        assert_eq!(
+            SymbolTarget::from_value("3:1"),
+            Some(SymbolTarget {
+                uri: None,
+                line: 3,
+                character: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_empty_query_keeps_document_order_with_indentation() {
+        let symbols = vec![
+            symbol("Parser", None, 0),
+            symbol("parse", Some("Parser"), 3),
+        ];
+        let suggestions = symbol_suggestions(&symbols, "", false, usize::MAX);
+        assert_eq!(suggestions[0].text, "λ Parser");
+        assert_eq!(suggestions[1].text, "  λ parse");
+        assert_eq!(suggestions[1].value.as_deref(), Some("3:0"));
+    }
+
+    #[test]
+    fn test_matches_are_grouped_by_container() {
+        let symbols = vec![
+            symbol("read", Some("File"), 1),
+            symbol("reader", Some("Socket"), 2),
+            symbol("ready", Some("File"), 3),
+            symbol("close", Some("File"), 4),
+        ];
+        let texts: Vec<String> = symbol_suggestions(&symbols, "read", false, usize::MAX)
+            .into_iter()
+            .map(|s| s.text)
+            .collect();
+        assert_eq!(texts, vec!["λ read", "λ ready", "λ reader"]);
+    }
+
+    #[test]
+    fn test_workspace_symbols_merge_answers_for_current_query_only() {
+        let mut results = WorkspaceSymbols::default();
+        results.begin("par", 2);
+        results.add("pa", vec![symbol("stale", None, 0)]);
+        results.add("par", vec![symbol("parse", None, 0)]);
+        assert!(results.is_searching());
+        let suggestions = results.suggestions("par", false);
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].text, "λ parse");
+        assert!(suggestions[1].disabled);
+
+        results.add("par", vec![symbol("parser", None, 1)]);
+        assert!(!results.is_searching());
+        assert_eq!(results.suggestions("par", false).len(), 2);
+        assert!(results.suggestions("other", false).is_empty());
+    }
+}
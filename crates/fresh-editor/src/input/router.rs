@@ -663,6 +663,8 @@ pub fn cancels_pending_lsp(action: &Action) -> bool {
             | Action::LspGotoDefinition
             | Action::LspReferences
             | Action::LspImplementation
+            | Action::LspCallHierarchy
+            | Action::LspTypeHierarchy
             | Action::LspHover
             | Action::None
     )
@@ -1331,6 +1331,14 @@ impl TextBuffer {
         Some(result)
     }
 
+    /// Get text from a byte offset range without loading anything.
+    /// Returns None if any part of the range lives in a chunk that is not in
+    /// memory yet. For callers that only sample the file (the minimap) and
+    /// must not trigger chunk loads while rendering.
+    pub fn peek_text_range(&self, offset: usize, bytes: usize) -> Option<Vec<u8>> {
+        self.get_text_range(offset, bytes)
+    }
+
     /// Get text from a byte offset range with lazy loading
     /// This will load unloaded chunks on-demand and always returns complete data
     ///
@@ -405,6 +405,46 @@ impl LineAlignment {
         Self { rows }
     }
 
+    /// Create alignment from aligned sections, for any number of panes.
+    /// Line `i` of every pane in a section shares a row; shorter panes are
+    /// padded. Every section but a context one starts with a hunk header.
+    pub fn from_sections(sections: &[AlignedSection]) -> Self {
+        let mut rows = Vec::new();
+        for section in sections {
+            let height = section
+                .lines
+                .iter()
+                .map(|&(_, count)| count)
+                .max()
+                .unwrap_or(0);
+            if height == 0 {
+                continue;
+            }
+            if section.row_type != RowType::Context {
+                rows.push(AlignedRow {
+                    pane_lines: vec![None; section.lines.len()],
+                    row_type: RowType::HunkHeader,
+                });
+            }
+            for i in 0..height {
+                rows.push(AlignedRow {
+                    pane_lines: section
+                        .lines
+                        .iter()
+                        .map(|&(start, count)| {
+                            (i < count).then(|| SourceLineRef {
+                                line: start + i,
+                                byte_range: 0..0,
+                            })
+                        })
+                        .collect(),
+                    row_type: section.row_type,
+                });
+            }
+        }
+        Self { rows }
+    }
+
     /// Get the aligned row at the given display index
     pub fn get_row(&self, display_row: usize) -> Option<&AlignedRow> {
         self.rows.get(display_row)
@@ -555,6 +595,16 @@ impl DiffHunk {
     }
 }
 
+/// A run of aligned lines across any number of panes — the N-pane
+/// counterpart of [`DiffHunk`], used by three-way merge views
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlignedSection {
+    /// `(start, count)` lines of each pane, in pane order
+    pub lines: Vec<(usize, usize)>,
+    /// Style of the section's rows
+    pub row_type: RowType,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -580,6 +630,35 @@ mod tests {
         assert_eq!(alignment.rows[2].row_type, RowType::HunkHeader);
     }
 
+    #[test]
+    fn test_line_alignment_from_sections() {
+        // Three panes: two shared lines, then a change that is 1 line in
+        // the first pane, 2 in the second and absent from the third.
+        let sections = vec![
+            AlignedSection {
+                lines: vec![(0, 2), (0, 2), (0, 2)],
+                row_type: RowType::Context,
+            },
+            AlignedSection {
+                lines: vec![(2, 1), (2, 2), (2, 0)],
+                row_type: RowType::Modification,
+            },
+        ];
+        let alignment = LineAlignment::from_sections(&sections);
+
+        assert_eq!(alignment.rows.len(), 5);
+        assert_eq!(alignment.rows[1].row_type, RowType::Context);
+        assert_eq!(alignment.rows[2].row_type, RowType::HunkHeader);
+        assert_eq!(alignment.rows[2].pane_lines.len(), 3);
+
+        let last = &alignment.rows[4];
+        assert_eq!(last.row_type, RowType::Modification);
+        assert!(!last.has_content(0));
+        assert_eq!(last.get_pane_line(1).map(|l| l.line), Some(3));
+        assert!(!last.has_content(2));
+        assert_eq!(alignment.next_hunk_row(0), Some(2));
+    }
+
     #[test]
     fn test_composite_buffer_focus() {
         let sources = vec![
@@ -522,6 +522,31 @@ impl Event {
         }
     }
 
+    /// Whether undo and redo can replay this event. A bulk edit swaps in
+    /// whole-buffer snapshots, which are not serialized, so one read back
+    /// from disk cannot be replayed.
+    pub fn is_replayable(&self) -> bool {
+        match self {
+            Self::BulkEdit {
+                old_snapshot,
+                new_snapshot,
+                ..
+            } => old_snapshot.is_some() && new_snapshot.is_some(),
+            Self::Batch { events, .. } => events.iter().all(|e| e.is_replayable()),
+            _ => true,
+        }
+    }
+
+    /// Whether this event is or contains a bulk edit, whose snapshots do
+    /// not survive serialization.
+    fn has_bulk_edit(&self) -> bool {
+        match self {
+            Self::BulkEdit { .. } => true,
+            Self::Batch { events, .. } => events.iter().any(|e| e.has_bulk_edit()),
+            _ => false,
+        }
+    }
+
     /// Returns true if this event is a write action (modifies state in a way that should be undoable)
     /// Returns false for readonly actions like cursor movement, scrolling, viewport changes, etc.
     ///
@@ -563,6 +588,10 @@ impl Event {
     }
 }
 
+/// An event to apply when moving through the undo tree, paired with the
+/// displaced markers to restore after applying it.
+pub type UndoStep = (Event, Vec<(u64, usize)>);
+
 /// A log entry containing an event and metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEntry {
@@ -588,6 +617,11 @@ pub struct LogEntry {
     /// back to per-entry undo.
     #[serde(skip)]
     pub group_id: Option<u64>,
+
+    /// Undo state this entry belongs to (see [`EventLog::current_state`]).
+    /// Runtime-only like `group_id`: reloaded logs are renumbered.
+    #[serde(skip)]
+    pub seq: u64,
 }
 
 impl LogEntry {
@@ -601,6 +635,7 @@ impl LogEntry {
             description: None,
             displaced_markers: Vec::new(),
             group_id: None,
+            seq: 0,
         }
     }
 
@@ -624,9 +659,43 @@ pub struct Snapshot {
     pub cursor_positions: Vec<(CursorId, usize, Option<usize>)>,
 }
 
+/// One node of the undo tree, as listed by [`EventLog::undo_tree`]
+#[derive(Debug, Clone)]
+pub struct UndoNode {
+    /// State number, in order of creation (1-based; 0 is the empty history)
+    pub seq: u64,
+    /// State this one was reached from
+    pub parent: u64,
+    /// When the edit was made (milliseconds since epoch)
+    pub timestamp: u64,
+    /// The first edit of the state (an undo group can hold several)
+    pub event: Event,
+    /// Number of edits in the state
+    pub edits: usize,
+}
+
+/// A redo tail abandoned by editing after an undo
+///
+/// Instead of discarding the tail, the log keeps it so the history forms a
+/// tree. `entries` continue from the end of state `parent`, which is either
+/// on the current path or inside another branch.
+struct UndoBranch {
+    parent: u64,
+    entries: Vec<LogEntry>,
+}
+
+/// Most undo branches kept off the current path. Past this the one left
+/// longest ago is dropped, with the branches forking from it.
+const MAX_UNDO_BRANCHES: usize = 64;
+
 /// The event log - append-only log of all events
+///
+/// `entries` is the current path through the undo tree: the root-to-tip
+/// history that plain undo/redo walk. Branches left behind by editing
+/// after an undo are kept in `branches` and brought back onto the path by
+/// [`EventLog::goto_state`].
 pub struct EventLog {
-    /// All logged events
+    /// All logged events on the current path
     entries: Vec<LogEntry>,
 
     /// Current position in the log (for undo/redo)
@@ -655,6 +724,16 @@ pub struct EventLog {
     /// Nesting depth of open undo groups. The group is closed (and a fresh id
     /// allocated for the next group) only when this returns to zero.
     group_depth: u32,
+
+    /// Undo branches off the current path.
+    branches: Vec<UndoBranch>,
+
+    /// Last undo state number handed out.
+    last_seq: u64,
+
+    /// Saved state while it lies on a branch off the current path
+    /// (`saved_at_index` is `None` meanwhile).
+    saved_off_path: Option<u64>,
 }
 
 impl EventLog {
@@ -671,6 +750,9 @@ impl EventLog {
             next_group_id: 0,
             current_group: None,
             group_depth: 0,
+            branches: Vec::new(),
+            last_seq: 0,
+            saved_off_path: None,
         }
     }
 
@@ -699,13 +781,18 @@ impl EventLog {
     /// Call this when the buffer is saved to disk
     pub fn mark_saved(&mut self) {
         self.saved_at_index = Some(self.current_index);
+        self.saved_off_path = None;
     }
 
     /// Invalidate the saved position so the buffer is always considered modified.
     /// Call this after hot exit recovery, where the buffer content differs from
-    /// disk but the event log has no record of the changes.
+    /// disk but the event log has no record of the changes. Any history already
+    /// in the log (e.g. undo history restored when the file was opened) is
+    /// dropped, since it describes the content that was just replaced.
     pub fn clear_saved_position(&mut self) {
+        self.clear();
         self.saved_at_index = None;
+        self.saved_off_path = None;
     }
 
     /// Check if the buffer is at the saved position (not modified)
@@ -819,15 +906,9 @@ impl EventLog {
         // navigation after undo does not destroy the redo chain.
         if self.current_index < self.entries.len() {
             if event.is_write_action() {
-                // Write action: truncate redo history and log normally
-                self.entries.truncate(self.current_index);
-
-                // Invalidate saved_at_index if it pointed to a truncated entry
-                if let Some(saved_idx) = self.saved_at_index {
-                    if saved_idx > self.current_index {
-                        self.saved_at_index = None;
-                    }
-                }
+                // Write action: move the redo history onto a branch of the
+                // undo tree and log normally
+                self.stash_tail(self.current_index);
             } else {
                 // Non-write event while redo exists: skip logging to preserve redo
                 return self.current_index;
@@ -856,6 +937,7 @@ impl EventLog {
 
         let mut entry = LogEntry::new(event);
         entry.group_id = self.current_group;
+        entry.seq = self.seq_for_next(&entry);
         self.entries.push(entry);
         self.current_index = self.entries.len();
 
@@ -894,12 +976,33 @@ impl EventLog {
 
     /// Can we undo?
     pub fn can_undo(&self) -> bool {
-        self.current_index > 0
+        self.current_index > self.replay_floor()
     }
 
     /// Can we redo?
     pub fn can_redo(&self) -> bool {
-        self.current_index < self.entries.len()
+        self.entries
+            .get(self.current_index)
+            .is_some_and(|e| e.event.is_replayable())
+    }
+
+    /// Position undo cannot go below: just past the last entry on the path
+    /// that cannot be replayed (see [`Event::is_replayable`]), or 0.
+    fn replay_floor(&self) -> usize {
+        self.entries[..self.current_index]
+            .iter()
+            .rposition(|e| !e.event.is_replayable())
+            .map_or(0, |idx| idx + 1)
+    }
+
+    /// Whether the next undo would have to replay an entry that cannot be
+    /// replayed, so undo stops here rather than at the start of the history.
+    pub fn undo_blocked(&self) -> bool {
+        let floor = self.replay_floor();
+        floor > 0
+            && !self.entries[floor..self.current_index]
+                .iter()
+                .any(|e| e.event.is_write_action())
     }
 
     /// Move back through events (for undo)
@@ -914,8 +1017,9 @@ impl EventLog {
         // While set, the walk keeps consuming entries belonging to the same
         // group so a whole grouped edit (e.g. a macro replay) reverts at once.
         let mut group: Option<u64> = None;
+        let floor = self.replay_floor();
 
-        while self.can_undo() {
+        while self.current_index > floor {
             let idx = self.current_index - 1;
             let is_write = self.entries[idx].event.is_write_action();
             let entry_group = self.entries[idx].group_id;
@@ -1009,6 +1113,9 @@ impl EventLog {
         self.entries.clear();
         self.current_index = 0;
         self.snapshots.clear();
+        self.branches.clear();
+        self.last_seq = 0;
+        self.saved_off_path = None;
     }
 
     /// Save event log to JSON Lines format
@@ -1043,10 +1150,292 @@ impl EventLog {
         }
 
         log.current_index = log.entries.len();
+        log.renumber_states();
 
         Ok(log)
     }
 
+    /// Entries up to the saved position: the history that leads to the
+    /// content on disk. `None` when the saved position is unknown (e.g.
+    /// after hot exit recovery), since the disk content then has no place
+    /// in the log.
+    ///
+    /// The history starts after the last bulk edit: its snapshots are not
+    /// serialized, so it could not be undone in a later session.
+    pub fn saved_history(&self) -> Option<&[LogEntry]> {
+        let end = self.saved_at_index?;
+        let start = self.entries[..end]
+            .iter()
+            .rposition(|e| e.event.has_bulk_edit())
+            .map_or(0, |idx| idx + 1);
+        self.entries.get(start..end)
+    }
+
+    /// Rebuild a log from [`Self::saved_history`] entries of a previous
+    /// session, positioned (and saved) after the last one.
+    pub fn from_saved_history(entries: Vec<LogEntry>) -> Self {
+        let mut log = Self::new();
+        log.current_index = entries.len();
+        log.saved_at_index = Some(entries.len());
+        log.entries = entries;
+        log.renumber_states();
+        log
+    }
+
+    // ------------------------------------------------------------------
+    // Undo tree
+    // ------------------------------------------------------------------
+
+    /// Undo state number for an entry about to be appended. A write action
+    /// starts a new state unless it continues the open undo group; anything
+    /// else belongs to the state it follows.
+    fn seq_for_next(&mut self, entry: &LogEntry) -> u64 {
+        if !entry.event.is_write_action() {
+            return self.entries.last().map_or(0, |e| e.seq);
+        }
+        if entry.group_id.is_some() {
+            let group_write = self
+                .entries
+                .iter()
+                .rev()
+                .take_while(|e| e.group_id == entry.group_id)
+                .find(|e| e.event.is_write_action());
+            if let Some(previous) = group_write {
+                return previous.seq;
+            }
+        }
+        self.last_seq += 1;
+        self.last_seq
+    }
+
+    /// Number the states of a log rebuilt from disk (groups are not
+    /// persisted, so every write action is a state of its own).
+    fn renumber_states(&mut self) {
+        let mut seq = 0;
+        for entry in &mut self.entries {
+            if entry.event.is_write_action() {
+                seq += 1;
+            }
+            entry.seq = seq;
+        }
+        self.last_seq = seq;
+        self.branches.clear();
+    }
+
+    /// Undo state the buffer is in: the number of the last state applied,
+    /// or 0 at the start of the history.
+    pub fn current_state(&self) -> u64 {
+        self.current_index
+            .checked_sub(1)
+            .map_or(0, |idx| self.entries[idx].seq)
+    }
+
+    /// Number of undo states created so far. States are numbered
+    /// `1..=last_state()` in creation order, across all branches.
+    pub fn last_state(&self) -> u64 {
+        self.last_seq
+    }
+
+    /// Undo state the buffer was last saved in, if it is still known.
+    pub fn saved_state(&self) -> Option<u64> {
+        match self.saved_at_index {
+            Some(0) => Some(0),
+            Some(idx) => self.entries.get(idx - 1).map(|e| e.seq),
+            None => self.saved_off_path,
+        }
+    }
+
+    /// Every undo state as a node of the tree, in creation order.
+    pub fn undo_tree(&self) -> Vec<UndoNode> {
+        let mut nodes: Vec<Option<UndoNode>> = vec![None; self.last_seq as usize];
+        let paths = std::iter::once((0, self.entries.as_slice())).chain(
+            self.branches
+                .iter()
+                .map(|b| (b.parent, b.entries.as_slice())),
+        );
+        for (start, entries) in paths {
+            let mut previous = start;
+            for entry in entries {
+                if entry.seq != previous && entry.seq != 0 {
+                    if let Some(slot) = nodes.get_mut(entry.seq as usize - 1) {
+                        slot.get_or_insert_with(|| UndoNode {
+                            seq: entry.seq,
+                            parent: previous,
+                            timestamp: entry.timestamp,
+                            event: entry.event.clone(),
+                            edits: 0,
+                        });
+                    }
+                }
+                if entry.seq != 0 && entry.event.is_write_action() {
+                    if let Some(Some(node)) = nodes.get_mut(entry.seq as usize - 1) {
+                        node.edits += 1;
+                    }
+                }
+                previous = entry.seq;
+            }
+        }
+        nodes.into_iter().flatten().collect()
+    }
+
+    /// Position on the current path just past state `seq`.
+    fn state_position(&self, seq: u64) -> Option<usize> {
+        if seq == 0 {
+            return Some(self.entries.iter().take_while(|e| e.seq == 0).count());
+        }
+        self.entries
+            .iter()
+            .rposition(|e| e.seq == seq)
+            .map(|idx| idx + 1)
+    }
+
+    /// The branch holding state `seq`, if it is off the current path.
+    fn branch_of(&self, seq: u64) -> Option<usize> {
+        self.branches
+            .iter()
+            .position(|b| b.entries.iter().any(|e| e.seq == seq))
+    }
+
+    /// All entries from the start of the history to the end of state `seq`.
+    fn path_to(&self, seq: u64) -> Option<Vec<&LogEntry>> {
+        if let Some(pos) = self.state_position(seq) {
+            return Some(self.entries[..pos].iter().collect());
+        }
+        let branch = &self.branches[self.branch_of(seq)?];
+        let mut path = self.path_to(branch.parent)?;
+        let end = branch.entries.iter().rposition(|e| e.seq == seq)? + 1;
+        path.extend(&branch.entries[..end]);
+        Some(path)
+    }
+
+    /// Events that take the buffer from the current state to state `seq`:
+    /// inverses back to the common ancestor, then the edits forward from
+    /// it. Each event is paired with the displaced markers to restore after
+    /// applying it, as for [`Self::undo`]. `None` for an unknown state, or
+    /// one reached only through an entry that cannot be replayed.
+    pub fn events_to_state(&self, seq: u64) -> Option<Vec<UndoStep>> {
+        let target = self.path_to(seq)?;
+        let current = &self.entries[..self.current_index];
+        let common = current
+            .iter()
+            .zip(&target)
+            .take_while(|(a, b)| std::ptr::eq(*a, **b))
+            .count();
+        if current[common..]
+            .iter()
+            .chain(target[common..].iter().copied())
+            .any(|e| !e.event.is_replayable())
+        {
+            return None;
+        }
+
+        let mut events: Vec<_> = current[common..]
+            .iter()
+            .rev()
+            .filter_map(|e| Some((e.event.inverse()?, e.displaced_markers.clone())))
+            .collect();
+        events.extend(
+            target[common..]
+                .iter()
+                .map(|e| (e.event.clone(), Vec::new())),
+        );
+        Some(events)
+    }
+
+    /// Jump to undo state `seq`, wherever it is in the tree, and return the
+    /// events to apply (see [`Self::events_to_state`]). The branch holding
+    /// the state becomes the current path, so plain redo follows it.
+    pub fn goto_state(&mut self, seq: u64) -> Option<Vec<UndoStep>> {
+        let events = self.events_to_state(seq)?;
+
+        // Branches to splice in, innermost (the one holding `seq`) first.
+        let mut chain = Vec::new();
+        let mut cursor = seq;
+        while self.state_position(cursor).is_none() {
+            let branch = self.branch_of(cursor)?;
+            cursor = self.branches[branch].parent;
+            chain.push(self.branches[branch].entries[0].seq);
+        }
+        for first in chain.into_iter().rev() {
+            let branch = self.branch_of(first)?;
+            // `remove` keeps the rest in the order they were left.
+            let branch = self.branches.remove(branch);
+            let fork = self.state_position(branch.parent)?;
+            self.stash_tail(fork);
+            self.entries.extend(branch.entries);
+        }
+        if let Some(saved) = self.saved_off_path {
+            if let Some(pos) = self.state_position(saved) {
+                self.saved_at_index = Some(pos);
+                self.saved_off_path = None;
+            }
+        }
+
+        self.current_index = self.state_position(seq)?;
+        Some(events)
+    }
+
+    /// The state before the current one in time (`g-`), which may be on
+    /// another branch.
+    pub fn earlier_state(&self) -> Option<u64> {
+        (0..self.current_state())
+            .rev()
+            .find(|&seq| self.has_state(seq))
+    }
+
+    /// The state after the current one in time (`g+`).
+    pub fn later_state(&self) -> Option<u64> {
+        (self.current_state() + 1..=self.last_seq).find(|&seq| self.has_state(seq))
+    }
+
+    /// Whether state `seq` is still in the tree (its branch may have been
+    /// dropped past `MAX_UNDO_BRANCHES`).
+    pub fn has_state(&self, seq: u64) -> bool {
+        self.state_position(seq).is_some() || self.branch_of(seq).is_some()
+    }
+
+    /// Move `entries[fork..]` onto a branch off the end of the path prefix.
+    fn stash_tail(&mut self, fork: usize) {
+        if fork >= self.entries.len() {
+            return;
+        }
+        let parent = fork.checked_sub(1).map_or(0, |idx| self.entries[idx].seq);
+        if let Some(saved_idx) = self.saved_at_index {
+            if saved_idx > fork {
+                self.saved_off_path = self.entries.get(saved_idx - 1).map(|e| e.seq);
+                self.saved_at_index = None;
+            }
+        }
+        let tail = self.entries.split_off(fork);
+        // A tail without edits (cursor moves only) is no state of its own.
+        if tail.iter().any(|e| e.event.is_write_action()) {
+            self.branches.push(UndoBranch {
+                parent,
+                entries: tail,
+            });
+            self.prune_branches();
+        }
+    }
+
+    /// Drop the branches left longest ago past `MAX_UNDO_BRANCHES`, with
+    /// the branches forking from them, which could no longer be reached.
+    fn prune_branches(&mut self) {
+        while self.branches.len() > MAX_UNDO_BRANCHES {
+            let mut dropped = vec![self.branches.remove(0)];
+            while let Some(branch) = dropped.pop() {
+                let holds = |seq: u64| branch.entries.iter().any(|e| e.seq == seq);
+                if self.saved_off_path.is_some_and(holds) {
+                    self.saved_off_path = None;
+                }
+                let (orphans, kept) = std::mem::take(&mut self.branches)
+                    .into_iter()
+                    .partition(|b| holds(b.parent));
+                self.branches = kept;
+                dropped.extend(orphans);
+            }
+        }
+    }
+
     /// Set snapshot interval
     pub fn set_snapshot_interval(&mut self, interval: usize) {
         self.snapshot_interval = interval;
@@ -1324,6 +1713,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_saved_history_round_trip() {
+        let mut log = EventLog::new();
+        for (position, text) in [(0, "a"), (1, "b")] {
+            log.append(Event::Insert {
+                position,
+                text: text.to_string(),
+                cursor_id: CursorId(0),
+            });
+        }
+        log.mark_saved();
+        log.append(Event::Insert {
+            position: 2,
+            text: "c".to_string(),
+            cursor_id: CursorId(0),
+        });
+
+        // Only the part leading up to the saved content is kept.
+        let history = log.saved_history().unwrap().to_vec();
+        assert_eq!(history.len(), 2);
+
+        let mut restored = EventLog::from_saved_history(history);
+        assert!(restored.is_at_saved_position());
+        assert!(!restored.can_redo());
+        let undone = restored.undo();
+        assert!(matches!(
+            undone.as_slice(),
+            [(Event::Delete { range, .. }, _)] if *range == (1..2)
+        ));
+        assert!(!restored.is_at_saved_position());
+
+        log.clear_saved_position();
+        assert!(log.saved_history().is_none());
+    }
+
     #[test]
     fn test_undo_group_reverts_and_reapplies_atomically() {
         // Three grouped inserts (e.g. a macro replay) collapse into one
@@ -1508,4 +1932,199 @@ mod tests {
             "Should not be at saved position after undo + new edit"
         );
     }
+
+    fn insert(position: usize, text: &str) -> Event {
+        Event::Insert {
+            position,
+            text: text.to_string(),
+            cursor_id: CursorId(0),
+        }
+    }
+
+    /// A bulk edit as logged in a session, with its snapshots.
+    fn bulk_edit() -> Event {
+        let snapshot = crate::model::buffer::Buffer::from_str_test("").snapshot_buffer_state();
+        Event::BulkEdit {
+            old_snapshot: Some(Arc::clone(&snapshot)),
+            new_snapshot: Some(snapshot),
+            old_cursors: Vec::new(),
+            new_cursors: Vec::new(),
+            description: "Replace all".to_string(),
+            edits: Vec::new(),
+            displaced_markers: Vec::new(),
+        }
+    }
+
+    /// Replay the text effect of undo/redo events onto a string.
+    fn apply_text(text: &mut String, events: &[(Event, Vec<(u64, usize)>)]) {
+        for (event, _) in events {
+            match event {
+                Event::Insert {
+                    position, text: t, ..
+                } => text.insert_str(*position, t),
+                Event::Delete { range, .. } => text.replace_range(range.clone(), ""),
+                other => panic!("unexpected event {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_saved_history_starts_after_the_last_bulk_edit() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(bulk_edit());
+        log.append(insert(1, "b"));
+        log.mark_saved();
+
+        // As `PersistedUndoStore` writes and reads it back.
+        let json = serde_json::to_string(log.saved_history().unwrap()).unwrap();
+        let mut reloaded = EventLog::from_saved_history(serde_json::from_str(&json).unwrap());
+
+        // Undo takes the text back to what the bulk edit left, no further.
+        let mut text = "Ab".to_string();
+        while reloaded.can_undo() {
+            apply_text(&mut text, &reloaded.undo());
+        }
+        assert_eq!(text, "A");
+        assert_eq!(reloaded.current_state(), 0);
+    }
+
+    #[test]
+    fn test_undo_stops_before_an_unreplayable_entry() {
+        // A history saved with a bulk edit in it, whose snapshots did not
+        // survive the trip.
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(bulk_edit());
+        log.append(insert(1, "b"));
+        let json = serde_json::to_string(log.entries()).unwrap();
+        let mut reloaded = EventLog::from_saved_history(serde_json::from_str(&json).unwrap());
+
+        let mut text = "Ab".to_string();
+        apply_text(&mut text, &reloaded.undo());
+        assert_eq!(text, "A");
+        assert!(reloaded.undo_blocked());
+        assert!(!reloaded.can_undo());
+        assert!(reloaded.undo().is_empty());
+
+        // Jumps across it are refused and leave the log where it was.
+        let earlier = reloaded.earlier_state().unwrap();
+        assert!(reloaded.has_state(earlier));
+        assert!(reloaded.goto_state(earlier).is_none());
+        assert_eq!(reloaded.current_state(), 2);
+
+        assert_eq!(reloaded.redo().len(), 1);
+        assert!(!reloaded.undo_blocked());
+    }
+
+    #[test]
+    fn test_undo_tree_keeps_branch_after_new_edit() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(insert(1, "b"));
+        log.undo();
+        // Editing after an undo starts a new branch instead of dropping "b"
+        log.append(insert(1, "c"));
+
+        assert_eq!(log.current_state(), 3);
+        let tree = log.undo_tree();
+        let parents: Vec<(u64, u64)> = tree.iter().map(|n| (n.seq, n.parent)).collect();
+        assert_eq!(parents, vec![(1, 0), (2, 1), (3, 1)]);
+
+        // Jump back onto the undone branch
+        let mut text = "ac".to_string();
+        let events = log.goto_state(2).unwrap();
+        apply_text(&mut text, &events);
+        assert_eq!(text, "ab");
+        assert_eq!(log.current_state(), 2);
+
+        // ...and over to the other one again
+        let events = log.goto_state(3).unwrap();
+        apply_text(&mut text, &events);
+        assert_eq!(text, "ac");
+        assert!(log.goto_state(42).is_none());
+    }
+
+    #[test]
+    fn test_undo_in_time_walks_across_branches() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(insert(1, "b"));
+        log.undo();
+        log.append(insert(1, "c"));
+
+        // g- from state 3 goes to state 2, which lives on the other branch
+        let mut text = "ac".to_string();
+        let earlier = log.earlier_state().unwrap();
+        assert_eq!(earlier, 2);
+        apply_text(&mut text, &log.goto_state(earlier).unwrap());
+        assert_eq!(text, "ab");
+
+        let earlier = log.earlier_state().unwrap();
+        apply_text(&mut text, &log.goto_state(earlier).unwrap());
+        assert_eq!(text, "a");
+        apply_text(&mut text, &log.goto_state(0).unwrap());
+        assert_eq!(text, "");
+        assert_eq!(log.earlier_state(), None);
+
+        // g+ retraces the same order
+        for expected in ["a", "ab", "ac"] {
+            let later = log.later_state().unwrap();
+            apply_text(&mut text, &log.goto_state(later).unwrap());
+            assert_eq!(text, expected);
+        }
+        assert_eq!(log.later_state(), None);
+    }
+
+    #[test]
+    fn test_undo_tree_saved_state_survives_branch_switch() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(insert(1, "b"));
+        log.mark_saved();
+        log.undo();
+        log.append(insert(1, "c"));
+        assert!(!log.is_at_saved_position());
+        assert_eq!(log.saved_state(), Some(2));
+
+        log.goto_state(2).unwrap();
+        assert!(log.is_at_saved_position());
+        log.goto_state(3).unwrap();
+        assert!(!log.is_at_saved_position());
+    }
+
+    #[test]
+    fn test_undo_state_text_preview_does_not_move_log() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(insert(1, "b"));
+        log.undo();
+        log.append(insert(1, "c"));
+
+        let mut text = "ac".to_string();
+        apply_text(&mut text, &log.events_to_state(2).unwrap());
+        assert_eq!(text, "ab");
+        assert_eq!(log.current_state(), 3);
+    }
+
+    #[test]
+    fn test_undo_branches_are_capped() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        // Each undo-then-edit leaves the undone edit on a branch.
+        for _ in 0..MAX_UNDO_BRANCHES + 10 {
+            log.append(insert(1, "b"));
+            log.undo();
+        }
+        log.append(insert(1, "c"));
+        assert_eq!(log.branches.len(), MAX_UNDO_BRANCHES);
+
+        // The oldest branches are gone; g- skips over them.
+        assert!(log.goto_state(2).is_none());
+        let last = log.last_state();
+        assert_eq!(log.earlier_state(), Some(last - 1));
+        log.goto_state(12).unwrap();
+        assert_eq!(log.earlier_state(), Some(1));
+        assert!(log.undo_tree().len() < last as usize);
+    }
 }
@@ -0,0 +1,251 @@
+//! Kill ring: a bounded history of killed and copied text (Emacs-style).
+//!
+//! The ring is shared by every buffer in the editor. Each entry keeps the
+//! text one part per cursor, so a multi-cursor kill can later be yanked back
+//! with each cursor receiving its own part — the same distribution the
+//! column-mode paste applies to a multi-line clipboard.
+//!
+//! Index 0 is always the most recent entry. Consecutive kills are merged
+//! into that entry by [`KillRing::append`] instead of pushing a new one, so
+//! `C-k C-k C-k` yanks back as a single block.
+
+use std::collections::VecDeque;
+
+/// Number of entries kept before the oldest is dropped.
+pub const DEFAULT_KILL_RING_CAPACITY: usize = 60;
+
+/// Which side of the previous kill a follow-up kill lands on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillDirection {
+    /// Killing forward (`C-k`, `M-d`): the new text goes after the old.
+    Forward,
+    /// Killing backward (`M-DEL`): the new text goes before the old.
+    Backward,
+}
+
+/// One kill ring entry: the killed text, one part per cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KillEntry {
+    parts: Vec<String>,
+}
+
+impl KillEntry {
+    /// Build an entry from per-cursor parts, ordered top to bottom.
+    pub fn new(parts: Vec<String>) -> Self {
+        Self { parts }
+    }
+
+    /// The per-cursor parts, ordered top to bottom.
+    pub fn parts(&self) -> &[String] {
+        &self.parts
+    }
+
+    /// The entry as a single string: the parts joined by newlines, which is
+    /// also what lands on the clipboard.
+    pub fn text(&self) -> String {
+        self.parts.join("\n")
+    }
+
+    /// A single-line excerpt for pickers: newlines shown as `↵`, tabs as a
+    /// space, truncated to `max_chars` with an ellipsis.
+    pub fn excerpt(&self, max_chars: usize) -> String {
+        let flat: String = self
+            .text()
+            .chars()
+            .map(|c| match c {
+                '\n' => '↵',
+                '\r' => ' ',
+                '\t' => ' ',
+                c => c,
+            })
+            .collect();
+        if flat.chars().count() > max_chars {
+            let mut cut: String = flat.chars().take(max_chars.saturating_sub(1)).collect();
+            cut.push('…');
+            cut
+        } else {
+            flat
+        }
+    }
+}
+
+/// Bounded ring of kills, most recent first.
+#[derive(Debug, Clone)]
+pub struct KillRing {
+    entries: VecDeque<KillEntry>,
+    capacity: usize,
+}
+
+impl Default for KillRing {
+    fn default() -> Self {
+        Self::new(DEFAULT_KILL_RING_CAPACITY)
+    }
+}
+
+impl KillRing {
+    /// Create an empty ring holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Number of entries in the ring.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True when nothing has been killed yet.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The entry `index` steps back from the most recent one.
+    pub fn get(&self, index: usize) -> Option<&KillEntry> {
+        self.entries.get(index)
+    }
+
+    /// The most recent entry.
+    pub fn head(&self) -> Option<&KillEntry> {
+        self.entries.front()
+    }
+
+    /// Entries from most recent to oldest.
+    pub fn iter(&self) -> impl Iterator<Item = &KillEntry> {
+        self.entries.iter()
+    }
+
+    /// Record a new kill as the most recent entry. Empty kills are ignored,
+    /// and a kill identical to the current head is not duplicated.
+    pub fn push(&mut self, parts: Vec<String>) {
+        if parts.iter().all(|p| p.is_empty()) {
+            return;
+        }
+        let entry = KillEntry::new(parts);
+        if self.head() == Some(&entry) {
+            return;
+        }
+        self.entries.push_front(entry);
+        self.entries.truncate(self.capacity);
+    }
+
+    /// Merge a follow-up kill into the most recent entry. Each part joins
+    /// the matching part of the head; when the cursor count changed since
+    /// the previous kill the parts can't be matched up, so the kill starts a
+    /// new entry instead.
+    pub fn append(&mut self, parts: Vec<String>, direction: KillDirection) {
+        let Some(head) = self.entries.front_mut() else {
+            self.push(parts);
+            return;
+        };
+        if head.parts.len() != parts.len() {
+            self.push(parts);
+            return;
+        }
+        for (old, new) in head.parts.iter_mut().zip(parts) {
+            match direction {
+                KillDirection::Forward => old.push_str(&new),
+                KillDirection::Backward => old.insert_str(0, &new),
+            }
+        }
+    }
+
+    /// Position of the entry whose text is `text`, if any. Line endings
+    /// are ignored: kills keep the buffer's endings, while pasted text
+    /// arrives normalised to LF.
+    pub fn position(&self, text: &str) -> Option<usize> {
+        let text = normalize_line_endings(text);
+        self.entries
+            .iter()
+            .position(|e| normalize_line_endings(&e.text()) == text)
+    }
+
+    /// The ring index of `text` when it is about to be yanked. Text that
+    /// came from outside the ring (another application's clipboard, a
+    /// terminal paste) is pushed first, so later yank-pops can cycle back
+    /// to it.
+    pub fn index_for_yank(&mut self, text: &str) -> usize {
+        if let Some(index) = self.position(text) {
+            return index;
+        }
+        self.push(vec![text.to_string()]);
+        0
+    }
+}
+
+/// Convert CRLF and lone CR line endings to LF.
+pub fn normalize_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_push_keeps_most_recent_first_and_is_bounded() {
+        let mut ring = KillRing::new(2);
+        ring.push(parts(&["one"]));
+        ring.push(parts(&["two"]));
+        ring.push(parts(&["three"]));
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.get(0).unwrap().text(), "three");
+        assert_eq!(ring.get(1).unwrap().text(), "two");
+    }
+
+    #[test]
+    fn test_push_skips_empty_and_duplicate_head() {
+        let mut ring = KillRing::default();
+        ring.push(parts(&[""]));
+        assert!(ring.is_empty());
+        ring.push(parts(&["a"]));
+        ring.push(parts(&["a"]));
+        assert_eq!(ring.len(), 1);
+    }
+
+    #[test]
+    fn test_append_joins_per_cursor_parts_in_kill_direction() {
+        let mut ring = KillRing::default();
+        ring.push(parts(&["foo", "bar"]));
+        ring.append(parts(&[" x", " y"]), KillDirection::Forward);
+        ring.append(parts(&["<", ">"]), KillDirection::Backward);
+        assert_eq!(ring.len(), 1);
+        assert_eq!(ring.head().unwrap().parts(), &parts(&["<foo x", ">bar y"]));
+        assert_eq!(ring.head().unwrap().text(), "<foo x\n>bar y");
+    }
+
+    #[test]
+    fn test_append_with_different_cursor_count_starts_new_entry() {
+        let mut ring = KillRing::default();
+        ring.push(parts(&["a", "b"]));
+        ring.append(parts(&["c"]), KillDirection::Forward);
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.head().unwrap().text(), "c");
+    }
+
+    #[test]
+    fn test_index_for_yank_adopts_outside_text() {
+        let mut ring = KillRing::default();
+        ring.push(parts(&["old"]));
+        ring.push(parts(&["new"]));
+        assert_eq!(ring.index_for_yank("old"), 1);
+        assert_eq!(ring.index_for_yank("from elsewhere"), 0);
+        assert_eq!(ring.len(), 3);
+
+        ring.push(parts(&["crlf\r\ntext"]));
+        assert_eq!(ring.index_for_yank("crlf\ntext"), 0);
+        assert_eq!(ring.len(), 4);
+    }
+
+    #[test]
+    fn test_excerpt_flattens_and_truncates() {
+        let entry = KillEntry::new(parts(&["line one\nline two"]));
+        assert_eq!(entry.excerpt(40), "line one↵line two");
+        assert_eq!(entry.excerpt(6), "line …");
+    }
+}
@@ -14,6 +14,7 @@ pub mod encoding;
 pub mod encoding_heuristics;
 pub mod event;
 pub mod filesystem;
+pub mod kill_ring;
 pub mod marker;
 pub mod marker_tree;
 pub mod piece_tree;
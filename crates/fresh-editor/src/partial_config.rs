@@ -91,6 +91,8 @@ pub struct PartialConfig {
     pub lsp_enabled: Option<bool>,
     pub lsp: Option<HashMap<String, LspLanguageConfig>>,
     pub universal_lsp: Option<HashMap<String, LspLanguageConfig>>,
+    pub debug_adapters: Option<HashMap<String, crate::types::DebugAdapterConfig>>,
+    pub tasks: Option<HashMap<String, crate::types::TaskConfig>>,
     pub warnings: Option<PartialWarningsConfig>,
     pub plugins: Option<HashMap<String, PartialPluginConfig>>,
     pub packages: Option<PartialPackagesConfig>,
@@ -128,6 +130,8 @@ impl Merge for PartialConfig {
         self.lsp_enabled.merge_from(&other.lsp_enabled);
         merge_hashmap(&mut self.lsp, &other.lsp);
         merge_hashmap(&mut self.universal_lsp, &other.universal_lsp);
+        merge_hashmap(&mut self.debug_adapters, &other.debug_adapters);
+        merge_hashmap(&mut self.tasks, &other.tasks);
         merge_hashmap_recursive(&mut self.plugins, &other.plugins);
 
         self.active_keybinding_map
@@ -176,6 +180,8 @@ pub struct PartialEditorConfig {
     pub diagnostics_inline_text: Option<bool>,
     pub recovery_enabled: Option<bool>,
     pub auto_recovery_save_interval_secs: Option<u32>,
+    pub persistent_undo: Option<bool>,
+    pub persistent_undo_max_bytes: Option<u64>,
     pub auto_save_enabled: Option<bool>,
     pub auto_save_interval_secs: Option<u32>,
     pub hot_exit: Option<bool>,
@@ -197,6 +203,8 @@ pub struct PartialEditorConfig {
     pub auto_read_only: Option<bool>,
     pub highlight_matching_brackets: Option<bool>,
     pub rainbow_brackets: Option<bool>,
+    pub spell_check: Option<bool>,
+    pub spell_check_language: Option<String>,
     pub cursor_style: Option<CursorStyle>,
     pub keyboard_disambiguate_escape_codes: Option<bool>,
     pub keyboard_report_event_types: Option<bool>,
@@ -216,6 +224,11 @@ pub struct PartialEditorConfig {
     pub show_prompt_line: Option<bool>,
     pub show_vertical_scrollbar: Option<bool>,
     pub show_horizontal_scrollbar: Option<bool>,
+    pub show_minimap: Option<bool>,
+    pub minimap_width: Option<u16>,
+    pub show_sticky_scroll: Option<bool>,
+    pub sticky_scroll_max_lines: Option<u16>,
+    pub show_breadcrumbs: Option<bool>,
     pub show_tilde: Option<bool>,
     pub nerd_font_icons: Option<bool>,
     pub use_terminal_bg: Option<bool>,
@@ -272,6 +285,9 @@ impl Merge for PartialEditorConfig {
         self.recovery_enabled.merge_from(&other.recovery_enabled);
         self.auto_recovery_save_interval_secs
             .merge_from(&other.auto_recovery_save_interval_secs);
+        self.persistent_undo.merge_from(&other.persistent_undo);
+        self.persistent_undo_max_bytes
+            .merge_from(&other.persistent_undo_max_bytes);
         self.auto_save_enabled.merge_from(&other.auto_save_enabled);
         self.auto_save_interval_secs
             .merge_from(&other.auto_save_interval_secs);
@@ -308,6 +324,9 @@ impl Merge for PartialEditorConfig {
         self.highlight_matching_brackets
             .merge_from(&other.highlight_matching_brackets);
         self.rainbow_brackets.merge_from(&other.rainbow_brackets);
+        self.spell_check.merge_from(&other.spell_check);
+        self.spell_check_language
+            .merge_from(&other.spell_check_language);
         self.cursor_style.merge_from(&other.cursor_style);
         self.keyboard_disambiguate_escape_codes
             .merge_from(&other.keyboard_disambiguate_escape_codes);
@@ -341,6 +360,13 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.show_vertical_scrollbar);
         self.show_horizontal_scrollbar
             .merge_from(&other.show_horizontal_scrollbar);
+        self.show_minimap.merge_from(&other.show_minimap);
+        self.minimap_width.merge_from(&other.minimap_width);
+        self.show_sticky_scroll
+            .merge_from(&other.show_sticky_scroll);
+        self.sticky_scroll_max_lines
+            .merge_from(&other.sticky_scroll_max_lines);
+        self.show_breadcrumbs.merge_from(&other.show_breadcrumbs);
         self.show_tilde.merge_from(&other.show_tilde);
         self.nerd_font_icons.merge_from(&other.nerd_font_icons);
         self.use_terminal_bg.merge_from(&other.use_terminal_bg);
@@ -568,6 +594,8 @@ pub struct PartialLanguageConfig {
     pub word_characters: Option<Option<String>>,
     pub indent: Option<crate::config::IndentRulesConfig>,
     pub indentation_guide: Option<bool>,
+    pub linked_editing: Option<bool>,
+    pub format_on_type: Option<bool>,
 }
 
 impl Merge for PartialLanguageConfig {
@@ -594,6 +622,8 @@ impl Merge for PartialLanguageConfig {
         self.word_characters.merge_from(&other.word_characters);
         self.indent.merge_from(&other.indent);
         self.indentation_guide.merge_from(&other.indentation_guide);
+        self.linked_editing.merge_from(&other.linked_editing);
+        self.format_on_type.merge_from(&other.format_on_type);
     }
 }
 
@@ -630,6 +660,8 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             diagnostics_inline_text: Some(cfg.diagnostics_inline_text),
             recovery_enabled: Some(cfg.recovery_enabled),
             auto_recovery_save_interval_secs: Some(cfg.auto_recovery_save_interval_secs),
+            persistent_undo: Some(cfg.persistent_undo),
+            persistent_undo_max_bytes: Some(cfg.persistent_undo_max_bytes),
             auto_save_enabled: Some(cfg.auto_save_enabled),
             auto_save_interval_secs: Some(cfg.auto_save_interval_secs),
             hot_exit: Some(cfg.hot_exit),
@@ -655,6 +687,8 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             auto_read_only: Some(cfg.auto_read_only),
             highlight_matching_brackets: Some(cfg.highlight_matching_brackets),
             rainbow_brackets: Some(cfg.rainbow_brackets),
+            spell_check: Some(cfg.spell_check),
+            spell_check_language: Some(cfg.spell_check_language.clone()),
             cursor_style: Some(cfg.cursor_style),
             keyboard_disambiguate_escape_codes: Some(cfg.keyboard_disambiguate_escape_codes),
             keyboard_report_event_types: Some(cfg.keyboard_report_event_types),
@@ -676,6 +710,11 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             show_prompt_line: Some(cfg.show_prompt_line),
             show_vertical_scrollbar: Some(cfg.show_vertical_scrollbar),
             show_horizontal_scrollbar: Some(cfg.show_horizontal_scrollbar),
+            show_minimap: Some(cfg.show_minimap),
+            minimap_width: Some(cfg.minimap_width),
+            show_sticky_scroll: Some(cfg.show_sticky_scroll),
+            sticky_scroll_max_lines: Some(cfg.sticky_scroll_max_lines),
+            show_breadcrumbs: Some(cfg.show_breadcrumbs),
             show_tilde: Some(cfg.show_tilde),
             nerd_font_icons: Some(cfg.nerd_font_icons),
             use_terminal_bg: Some(cfg.use_terminal_bg),
@@ -757,6 +796,10 @@ impl PartialEditorConfig {
             auto_recovery_save_interval_secs: self
                 .auto_recovery_save_interval_secs
                 .unwrap_or(defaults.auto_recovery_save_interval_secs),
+            persistent_undo: self.persistent_undo.unwrap_or(defaults.persistent_undo),
+            persistent_undo_max_bytes: self
+                .persistent_undo_max_bytes
+                .unwrap_or(defaults.persistent_undo_max_bytes),
             auto_save_enabled: self.auto_save_enabled.unwrap_or(defaults.auto_save_enabled),
             auto_save_interval_secs: self
                 .auto_save_interval_secs
@@ -808,6 +851,10 @@ impl PartialEditorConfig {
                 .highlight_matching_brackets
                 .unwrap_or(defaults.highlight_matching_brackets),
             rainbow_brackets: self.rainbow_brackets.unwrap_or(defaults.rainbow_brackets),
+            spell_check: self.spell_check.unwrap_or(defaults.spell_check),
+            spell_check_language: self
+                .spell_check_language
+                .unwrap_or_else(|| defaults.spell_check_language.clone()),
             cursor_style: self.cursor_style.unwrap_or(defaults.cursor_style),
             keyboard_disambiguate_escape_codes: self
                 .keyboard_disambiguate_escape_codes
@@ -853,6 +900,15 @@ impl PartialEditorConfig {
             show_horizontal_scrollbar: self
                 .show_horizontal_scrollbar
                 .unwrap_or(defaults.show_horizontal_scrollbar),
+            show_minimap: self.show_minimap.unwrap_or(defaults.show_minimap),
+            minimap_width: self.minimap_width.unwrap_or(defaults.minimap_width),
+            show_sticky_scroll: self
+                .show_sticky_scroll
+                .unwrap_or(defaults.show_sticky_scroll),
+            sticky_scroll_max_lines: self
+                .sticky_scroll_max_lines
+                .unwrap_or(defaults.sticky_scroll_max_lines),
+            show_breadcrumbs: self.show_breadcrumbs.unwrap_or(defaults.show_breadcrumbs),
             show_tilde: self.show_tilde.unwrap_or(defaults.show_tilde),
             nerd_font_icons: self.nerd_font_icons.unwrap_or(defaults.nerd_font_icons),
             use_terminal_bg: self.use_terminal_bg.unwrap_or(defaults.use_terminal_bg),
@@ -1104,6 +1160,8 @@ impl From<&LanguageConfig> for PartialLanguageConfig {
             word_characters: Some(cfg.word_characters.clone()),
             indent: cfg.indent.clone(),
             indentation_guide: cfg.indentation_guide,
+            linked_editing: Some(cfg.linked_editing),
+            format_on_type: Some(cfg.format_on_type),
         }
     }
 }
@@ -1142,6 +1200,8 @@ impl PartialLanguageConfig {
                 .unwrap_or_else(|| defaults.word_characters.clone()),
             indent: self.indent.or_else(|| defaults.indent.clone()),
             indentation_guide: self.indentation_guide.or(defaults.indentation_guide),
+            linked_editing: self.linked_editing.unwrap_or(defaults.linked_editing),
+            format_on_type: self.format_on_type.unwrap_or(defaults.format_on_type),
         }
     }
 }
@@ -1201,6 +1261,8 @@ impl From<&crate::config::Config> for PartialConfig {
                     })
                     .collect(),
             ),
+            debug_adapters: Some(cfg.debug_adapters.clone()),
+            tasks: Some(cfg.tasks.clone()),
             warnings: Some(PartialWarningsConfig::from(&cfg.warnings)),
             // Only include plugins that differ from defaults
             // Path is auto-discovered at runtime and should never be saved
@@ -1319,6 +1381,24 @@ impl PartialConfig {
             result
         };
 
+        // Resolve debug_adapters HashMap - user entries replace defaults per language
+        let debug_adapters = {
+            let mut result = defaults.debug_adapters.clone();
+            if let Some(partial_adapters) = self.debug_adapters {
+                result.extend(partial_adapters);
+            }
+            result
+        };
+
+        // Resolve tasks HashMap - user entries replace defaults per name
+        let tasks = {
+            let mut result = defaults.tasks.clone();
+            if let Some(partial_tasks) = self.tasks {
+                result.extend(partial_tasks);
+            }
+            result
+        };
+
         // Resolve keybinding_maps HashMap - merge with defaults
         let keybinding_maps = {
             let mut result = defaults.keybinding_maps.clone();
@@ -1384,6 +1464,8 @@ impl PartialConfig {
             lsp_enabled: self.lsp_enabled.unwrap_or(defaults.lsp_enabled),
             lsp,
             universal_lsp,
+            debug_adapters,
+            tasks,
             warnings: self
                 .warnings
                 .map(|e| e.resolve(&defaults.warnings))
@@ -1426,6 +1508,8 @@ impl Default for LanguageConfig {
             on_save: Vec::new(),
             word_characters: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
             indentation_guide: None,
         }
     }
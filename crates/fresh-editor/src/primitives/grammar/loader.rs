@@ -10,6 +10,9 @@ use std::sync::Arc;
 
 use syntect::parsing::{SyntaxSet, SyntaxSetBuilder};
 
+use super::tree_sitter_loader::{
+    is_tree_sitter_grammar, load_tree_sitter_dir, load_tree_sitter_grammar,
+};
 use super::types::{GrammarInfo, GrammarRegistry, GrammarSource, GrammarSpec, PackageManifest};
 
 /// Trait for loading grammar files from various sources.
@@ -105,6 +108,9 @@ impl GrammarRegistry {
     /// 2. Embedded grammars (TOML, Odin, etc.)
     /// 3. User-installed grammars from ~/.config/fresh/grammars/
     /// 4. Language pack grammars from ~/.config/fresh/languages/packages/
+    ///
+    /// Compiled tree-sitter parsers found along the way are registered with
+    /// `fresh_languages::runtime` rather than the syntax set.
     pub fn load(loader: &dyn GrammarLoader) -> Self {
         Self::load_with_additional(loader, &[])
     }
@@ -138,6 +144,16 @@ impl GrammarRegistry {
         // Start with built-in extra extension mappings, user grammars override these
         let mut user_extensions = Self::build_extra_extensions();
 
+        // Compiled tree-sitter parsers don't go through the syntect builder.
+        let (tree_sitter_specs, additional): (Vec<&GrammarSpec>, Vec<&GrammarSpec>) = additional
+            .iter()
+            .partition(|spec| is_tree_sitter_grammar(&spec.path));
+        for spec in tree_sitter_specs {
+            if let Err(e) = load_tree_sitter_grammar(loader, &spec.language, &spec.path) {
+                tracing::warn!("[grammar-build] {}", e);
+            }
+        }
+
         // Check if there are any user grammars or language packs to add
         let has_user_grammars = loader.grammars_dir().is_some_and(|dir| loader.exists(&dir));
         let has_language_packs = loader
@@ -234,7 +250,7 @@ impl GrammarRegistry {
                     "[grammar-build] Adding {} plugin-registered grammars...",
                     additional.len()
                 );
-                for spec in additional {
+                for &spec in &additional {
                     match Self::load_grammar_file(&spec.path) {
                         Ok(syntax) => {
                             let scope = syntax.scope.to_string();
@@ -364,6 +380,9 @@ fn load_user_grammars(
         // Check for direct grammar files
         let mut found_any = false;
         load_direct_grammar(loader, &path, builder, &mut found_any, grammar_sources);
+        // ...and a compiled tree-sitter parser, in a directory named after
+        // its language (e.g. `grammars/ruby/parser.so`)
+        load_tree_sitter_dir(loader, &path);
     }
 }
 
@@ -517,6 +536,9 @@ struct FreshGrammarConfig {
     /// Optional short name alias for this grammar (e.g., "hare")
     #[serde(default)]
     short_name: Option<String>,
+    /// Compiled tree-sitter parser for the language, relative to the package
+    #[serde(rename = "treeSitter", default)]
+    tree_sitter: Option<String>,
 }
 
 /// Load grammars from Fresh language packages (installed via pkg manager).
@@ -585,6 +607,13 @@ fn load_language_pack_grammars(
             None => continue,
         };
 
+        if let Some(library) = &grammar_config.tree_sitter {
+            let library = package_path.join(library);
+            if let Err(e) = load_tree_sitter_grammar(loader, &manifest.name, &library) {
+                tracing::warn!("Language pack '{}': {}", manifest.name, e);
+            }
+        }
+
         let grammar_path = package_path.join(&grammar_config.file);
         if !loader.exists(&grammar_path) {
             tracing::warn!(
@@ -914,6 +943,8 @@ mod tests {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
         registry.apply_language_config(&languages);
@@ -3,6 +3,7 @@
 //! This module is split into:
 //! - `types`: Pure data types and lookup methods (WASM-compatible, no filesystem access)
 //! - `loader`: I/O operations with `GrammarLoader` trait abstraction (runtime only)
+//! - `tree_sitter_loader`: compiled tree-sitter parsers loaded from disk (runtime only)
 //!
 //! # Example
 //!
@@ -30,9 +31,13 @@
 #[cfg(feature = "runtime")]
 mod loader;
 mod shebang;
+#[cfg(feature = "runtime")]
+mod tree_sitter_loader;
 mod types;
 
 // Re-export all public items for backward compatibility
 #[cfg(feature = "runtime")]
 pub use loader::*;
+#[cfg(feature = "runtime")]
+pub use tree_sitter_loader::{is_tree_sitter_grammar, load_tree_sitter_grammar};
 pub use types::*;
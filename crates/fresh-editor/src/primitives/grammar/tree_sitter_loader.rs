@@ -0,0 +1,217 @@
+//! Tree-sitter grammars loaded from disk.
+//!
+//! Only a few tree-sitter grammars are compiled into Fresh. A compiled parser
+//! (`.so` / `.dylib` / `.dll`) for any other built-in language can be dropped
+//! into the grammars directory, shipped in a package, or passed to the plugin
+//! `registerGrammar` API. It is registered with
+//! [`fresh_languages::runtime`], after which `Language::ts_language` returns it
+//! and tree-sitter indentation and reference highlighting light up for that
+//! language. Its `highlights.scm`, `indents.scm`, `locals.scm` and
+//! `injections.scm` are read from the parser's directory or a `queries/`
+//! directory next to it.
+//!
+//! Only native parsers are supported. WASM parsers would need tree-sitter's
+//! wasm store and a wasmtime runtime, which Fresh does not link, so `.wasm`
+//! files are not discovered as grammars.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use fresh_languages::runtime::{self, RuntimeGrammar};
+use fresh_languages::Language;
+
+use super::loader::GrammarLoader;
+
+/// Shared-library extensions a compiled parser may use.
+const LIBRARY_EXTENSIONS: &[&str] = &["so", "dylib", "dll"];
+
+/// Libraries backing registered grammars. A `tree_sitter::Language` points
+/// into its library, so these are never unloaded.
+static LIBRARIES: Mutex<Vec<libloading::Library>> = Mutex::new(Vec::new());
+
+/// Whether `path` is a compiled tree-sitter parser rather than a TextMate
+/// grammar.
+pub fn is_tree_sitter_grammar(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| LIBRARY_EXTENSIONS.contains(&ext))
+}
+
+/// Load the parser at `library` and its queries, and register it as the
+/// tree-sitter grammar of the built-in language `language_id`.
+pub fn load_tree_sitter_grammar(
+    loader: &dyn GrammarLoader,
+    language_id: &str,
+    library: &Path,
+) -> Result<Language, String> {
+    let language = Language::from_id(language_id).ok_or_else(|| {
+        format!(
+            "'{language_id}' is not a built-in language; only those can use a tree-sitter grammar"
+        )
+    })?;
+    let parser = open_parser(library, &runtime::symbol_names(language))?;
+
+    let dir = library.parent().unwrap_or(Path::new("."));
+    let grammar = RuntimeGrammar {
+        language: parser,
+        highlights: read_query(loader, dir, language, "highlights.scm").unwrap_or_default(),
+        indents: read_query(loader, dir, language, "indents.scm"),
+        locals: read_query(loader, dir, language, "locals.scm"),
+        injections: read_query(loader, dir, language, "injections.scm"),
+    };
+    runtime::register(language, grammar)?;
+    tracing::info!(
+        "[grammar-build] Loaded tree-sitter grammar for '{}' from {:?}",
+        language.id(),
+        library
+    );
+    Ok(language)
+}
+
+/// Load the parser in a `grammars/<language>/` directory, if it has one.
+pub(super) fn load_tree_sitter_dir(loader: &dyn GrammarLoader, dir: &Path) {
+    let Some(library) = find_library(loader, dir) else {
+        return;
+    };
+    let Some(language_id) = dir.file_name().and_then(|n| n.to_str()) else {
+        return;
+    };
+    if let Err(e) = load_tree_sitter_grammar(loader, language_id, &library) {
+        tracing::warn!("[grammar-build] {}", e);
+    }
+}
+
+/// The parser library in `dir`: a file with a shared-library extension,
+/// preferring the platform's own.
+fn find_library(loader: &dyn GrammarLoader, dir: &Path) -> Option<PathBuf> {
+    let mut candidates: Vec<PathBuf> = loader
+        .read_dir(dir)
+        .ok()?
+        .into_iter()
+        .filter(|p| is_tree_sitter_grammar(p) && !loader.is_dir(p))
+        .collect();
+    candidates.sort_by_key(|p| {
+        let native = p
+            .extension()
+            .is_some_and(|e| e == std::env::consts::DLL_EXTENSION);
+        (!native, p.clone())
+    });
+    candidates.into_iter().next()
+}
+
+/// `name` from the first of `dir`, `dir/queries/<language>` and `dir/queries`
+/// that has it.
+fn read_query(
+    loader: &dyn GrammarLoader,
+    dir: &Path,
+    language: Language,
+    name: &str,
+) -> Option<String> {
+    [
+        dir.join(name),
+        dir.join("queries").join(language.id()).join(name),
+        dir.join("queries").join(name),
+    ]
+    .iter()
+    .find(|path| loader.exists(path))
+    .and_then(|path| loader.read_file(path).ok())
+}
+
+/// Open `path` and call the first of `symbols` it exports to obtain the
+/// parser.
+fn open_parser(
+    path: &Path,
+    symbols: &[String],
+) -> Result<fresh_languages::tree_sitter::Language, String> {
+    type LanguageFn =
+        unsafe extern "C" fn() -> *const fresh_languages::tree_sitter::ffi::TSLanguage;
+
+    // SAFETY: loading a parser runs its initialisers; the user installed it as
+    // a tree-sitter grammar, which have none beyond static data.
+    let library = unsafe { libloading::Library::new(path) }
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    let raw = symbols
+        .iter()
+        .find_map(|symbol| {
+            // SAFETY: tree-sitter parsers export `tree_sitter_<name>` with
+            // exactly this signature.
+            let function = unsafe { library.get::<LanguageFn>(symbol.as_bytes()) }.ok()?;
+            // SAFETY: the function takes no arguments and returns a pointer
+            // to static data inside the library.
+            Some(unsafe { function() })
+        })
+        .ok_or_else(|| format!("{}: exports none of {}", path.display(), symbols.join(", ")))?;
+    if raw.is_null() {
+        return Err(format!("{}: parser returned no language", path.display()));
+    }
+    // SAFETY: `raw` is a valid TSLanguage that lives as long as `library`,
+    // which is kept loaded for the rest of the process below.
+    let language = unsafe { fresh_languages::tree_sitter::Language::from_raw(raw) };
+    LIBRARIES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(library);
+    Ok(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::grammar::LocalGrammarLoader;
+
+    #[test]
+    fn test_is_tree_sitter_grammar() {
+        assert!(is_tree_sitter_grammar(Path::new("rust/parser.so")));
+        assert!(is_tree_sitter_grammar(Path::new("rust/parser.dylib")));
+        assert!(!is_tree_sitter_grammar(Path::new("lua.wasm")));
+        assert!(!is_tree_sitter_grammar(Path::new("Hare.sublime-syntax")));
+        assert!(!is_tree_sitter_grammar(Path::new("rust/highlights.scm")));
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_language_and_bad_library() {
+        let temp = tempfile::tempdir().unwrap();
+        let loader = LocalGrammarLoader::embedded_only();
+        let library = temp.path().join("parser.so");
+        std::fs::write(&library, b"not a shared library").unwrap();
+
+        let err = load_tree_sitter_grammar(&loader, "zig", &library).unwrap_err();
+        assert!(err.contains("not a built-in language"), "{err}");
+
+        let err = load_tree_sitter_grammar(&loader, "ruby", &library).unwrap_err();
+        assert!(err.contains("parser.so"), "{err}");
+        assert!(fresh_languages::runtime::grammar(Language::Ruby).is_none());
+    }
+
+    #[test]
+    fn test_find_library_ignores_wasm() {
+        let temp = tempfile::tempdir().unwrap();
+        let loader = LocalGrammarLoader::embedded_only();
+        std::fs::write(temp.path().join("parser.wasm"), b"\0asm").unwrap();
+        assert_eq!(find_library(&loader, temp.path()), None);
+
+        let library = temp.path().join("parser.so");
+        std::fs::write(&library, b"").unwrap();
+        assert_eq!(find_library(&loader, temp.path()), Some(library));
+    }
+
+    #[test]
+    fn test_queries_found_next_to_or_under_the_library() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("queries/lua")).unwrap();
+        std::fs::write(dir.join("highlights.scm"), "(comment) @comment").unwrap();
+        std::fs::write(dir.join("queries/lua/indents.scm"), "(block) @indent").unwrap();
+        let loader = LocalGrammarLoader::embedded_only();
+
+        assert_eq!(
+            read_query(&loader, dir, Language::Lua, "highlights.scm").as_deref(),
+            Some("(comment) @comment")
+        );
+        assert_eq!(
+            read_query(&loader, dir, Language::Lua, "indents.scm").as_deref(),
+            Some("(block) @indent")
+        );
+        assert_eq!(read_query(&loader, dir, Language::Lua, "locals.scm"), None);
+    }
+}
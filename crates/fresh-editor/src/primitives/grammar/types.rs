@@ -1630,6 +1630,19 @@ impl GrammarRegistry {
                 spec.path,
                 spec.extensions
             );
+            // Compiled tree-sitter parsers are registered with
+            // `fresh_languages::runtime`, not the syntax set.
+            #[cfg(feature = "runtime")]
+            if super::is_tree_sitter_grammar(&spec.path) {
+                if let Err(e) = super::load_tree_sitter_grammar(
+                    &super::LocalGrammarLoader::embedded_only(),
+                    &spec.language,
+                    &spec.path,
+                ) {
+                    tracing::warn!("Failed to load grammar for '{}': {}", spec.language, e);
+                }
+                continue;
+            }
             match Self::load_grammar_file(&spec.path) {
                 Ok(syntax) => {
                     let scope = syntax.scope.to_string();
@@ -1999,6 +2012,8 @@ mod tests {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
         registry.apply_language_config(&languages);
@@ -2045,6 +2060,8 @@ mod tests {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
         registry.apply_language_config(&languages);
@@ -2094,6 +2111,8 @@ mod tests {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -2122,6 +2141,8 @@ mod tests {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -2597,6 +2618,8 @@ mod tests {
             word_characters: None,
             indentation_guide: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
         }
     }
 
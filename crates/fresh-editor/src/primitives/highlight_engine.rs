@@ -42,12 +42,13 @@ use crate::primitives::grammar::GrammarRegistry;
 use crate::primitives::highlighter::{
     highlight_bg, highlight_color, HighlightCategory, HighlightSpan, Highlighter, Language,
 };
+use crate::primitives::injections::{self, InjectedLanguage, Injection};
 use crate::view::theme::Theme;
 use fresh_core::hooks::{RegionLine, TableLine, TableRole};
 use std::collections::HashMap;
 use std::ops::Range;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use syntect::parsing::SyntaxSet;
 
 /// Map TextMate scope to highlight category
@@ -288,6 +289,9 @@ pub struct TextMateEngine {
     // Scope→Category memo. Syntect Scope atoms are append-only-interned
     // globally, so entries never need invalidation.
     scope_category_cache: HashMap<syntect::parsing::Scope, Option<HighlightCategory>>,
+    // Tree-sitter injected regions with the buffer version they were found
+    // at. Syntect doesn't report them, so they are parsed on demand.
+    injections: Mutex<Option<(u64, Vec<Injection>)>>,
 }
 
 /// Counters for monitoring highlighting performance in tests.
@@ -819,6 +823,7 @@ impl TextMateEngine {
             host_span_scratch: Vec::new(),
             child_span_scratch: Vec::new(),
             scope_category_cache: HashMap::new(),
+            injections: Mutex::new(None),
         }
     }
 
@@ -837,6 +842,26 @@ impl TextMateEngine {
         self.ts_language.as_ref()
     }
 
+    /// Language of the injected region at `position`, reparsing only when
+    /// the buffer changed since the last lookup.
+    pub fn injected_language_at(
+        &self,
+        buffer: &Buffer,
+        position: usize,
+    ) -> Option<InjectedLanguage> {
+        let host = self.ts_language?;
+        host.injections_query()?;
+        let mut memo = self.injections.lock().unwrap_or_else(|e| e.into_inner());
+        if memo.as_ref().map(|(version, _)| *version) != Some(buffer.version()) {
+            *memo = Some((
+                buffer.version(),
+                injections::buffer_injections(buffer, host).unwrap_or_default(),
+            ));
+        }
+        let (_, found) = memo.as_ref()?;
+        injections::innermost_at(found, position).map(|i| i.language.clone())
+    }
+
     /// Buffer-insert notification. Shifts span offsets in place and marks
     /// the cache dirty so the partial-update path runs on next render.
     pub fn notify_insert(&mut self, position: usize, length: usize) {
@@ -2261,7 +2286,7 @@ impl HighlightEngine {
         }
         if let Some(lang) = entry.engines.tree_sitter {
             if let Ok(highlighter) = Highlighter::new(lang) {
-                return Self::TreeSitter(Box::new(highlighter));
+                return Self::TreeSitter(Box::new(highlighter.with_syntax_set(syntax_set)));
             }
         }
         Self::None
@@ -2448,6 +2473,20 @@ impl HighlightEngine {
         }
     }
 
+    /// Language of the innermost injected region containing `position`, or
+    /// `None` in the host language (see `injections::injected_language_at`).
+    pub fn injected_language_at(
+        &self,
+        buffer: &Buffer,
+        position: usize,
+    ) -> Option<InjectedLanguage> {
+        match self {
+            Self::TreeSitter(h) => h.injected_language_at(buffer, position),
+            Self::TextMate(h) => h.injected_language_at(buffer, position),
+            Self::None => None,
+        }
+    }
+
     /// Get the tree-sitter Language for non-highlighting features
     /// Returns the language even when using TextMate for highlighting
     pub fn language(&self) -> Option<&Language> {
@@ -2470,15 +2509,40 @@ pub fn highlight_string(
     registry: &GrammarRegistry,
     theme: &Theme,
 ) -> Vec<HighlightSpan> {
-    use syntect::parsing::{ParseState, ScopeStack};
-
     // Find syntax by language token (handles aliases like "py" -> Python)
     let syntax = match registry.syntax_set().find_syntax_by_token(lang_hint) {
         Some(s) => s,
         None => return Vec::new(),
     };
 
-    let syntax_set = registry.syntax_set();
+    let mut spans: Vec<HighlightSpan> = syntect_categories(code, syntax, registry.syntax_set())
+        .into_iter()
+        .map(|(range, category)| HighlightSpan {
+            range,
+            color: highlight_color(category, theme),
+            bg: highlight_bg(category, theme),
+            category: Some(category),
+        })
+        .collect();
+
+    // Merge adjacent spans with same color
+    merge_adjacent_highlight_spans(&mut spans);
+
+    spans
+}
+
+/// Highlight categories for `code` parsed from scratch with `syntax`, as
+/// byte ranges relative to `code`.
+///
+/// Shared by `highlight_string` and the tree-sitter highlighter, which uses
+/// it for injected languages that only have a TextMate grammar.
+pub(crate) fn syntect_categories(
+    code: &str,
+    syntax: &syntect::parsing::SyntaxReference,
+    syntax_set: &SyntaxSet,
+) -> Vec<(Range<usize>, HighlightCategory)> {
+    use syntect::parsing::{ParseState, ScopeStack};
+
     let mut state = ParseState::new(syntax);
     let mut spans = Vec::new();
     let mut current_scopes = ScopeStack::new();
@@ -2515,12 +2579,7 @@ pub fn highlight_string(
                     let byte_start = line_start + syntect_offset;
                     let byte_end = line_start + clamped_op_offset;
                     if byte_start < byte_end {
-                        spans.push(HighlightSpan {
-                            range: byte_start..byte_end,
-                            color: highlight_color(category, theme),
-                            bg: highlight_bg(category, theme),
-                            category: Some(category),
-                        });
+                        spans.push((byte_start..byte_end, category));
                     }
                 }
             }
@@ -2536,12 +2595,7 @@ pub fn highlight_string(
                 let byte_start = line_start + syntect_offset;
                 let byte_end = line_start + line_content_len;
                 if byte_start < byte_end {
-                    spans.push(HighlightSpan {
-                        range: byte_start..byte_end,
-                        color: highlight_color(category, theme),
-                        bg: highlight_bg(category, theme),
-                        category: Some(category),
-                    });
+                    spans.push((byte_start..byte_end, category));
                 }
             }
         }
@@ -2549,9 +2603,6 @@ pub fn highlight_string(
         current_offset += line_len;
     }
 
-    // Merge adjacent spans with same color
-    merge_adjacent_highlight_spans(&mut spans);
-
     spans
 }
 
@@ -12,15 +12,23 @@
 //!   the viewport plus `context_bytes` of surrounding text is parsed, keeping
 //!   a jump into a 1GB file instant at the cost of best-effort accuracy.
 //! - **Lazy initialization**: Parsing happens on first render
+//! - **Injections**: Regions the grammar's `injections.scm` marks as another
+//!   language (see `injections`) are highlighted with that language's
+//!   tree-sitter grammar, or with its TextMate grammar when it has none.
 
 use crate::model::buffer::Buffer;
+use crate::primitives::highlight_engine::syntect_categories;
+use crate::primitives::injections::{self, find_injections, InjectedLanguage, Injection};
 use crate::view::theme::Theme;
 use fresh_languages::tree_sitter_highlight::{
     HighlightConfiguration, HighlightEvent, Highlighter as TSHighlighter,
 };
 pub use fresh_languages::{HighlightCategory, Language};
 use ratatui::style::Color;
+use std::collections::HashMap;
 use std::ops::Range;
+use std::sync::Arc;
+use syntect::parsing::SyntaxSet;
 
 /// Maximum bytes to parse in a single operation (for viewport highlighting).
 ///
@@ -103,6 +111,8 @@ struct HighlightCache {
     range: Range<usize>,
     /// Highlighted spans within this range (stores categories for theme-independent caching)
     spans: Vec<CachedSpan>,
+    /// Injected regions found in this range, in buffer offsets
+    injections: Vec<Injection>,
 }
 
 /// Syntax highlighter with incremental viewport-based parsing
@@ -117,6 +127,11 @@ pub struct Highlighter {
     cache: Option<HighlightCache>,
     /// Last known buffer length (for detecting complete buffer changes)
     last_buffer_len: usize,
+    /// TextMate grammars for injected languages without a tree-sitter grammar
+    syntax_set: Option<Arc<SyntaxSet>>,
+    /// Highlight configurations of injected languages, by injected name
+    /// (`None` when the language has no usable grammar)
+    injected_configs: HashMap<String, Option<HighlightConfiguration>>,
 }
 
 impl Highlighter {
@@ -129,9 +144,18 @@ impl Highlighter {
             config,
             cache: None,
             last_buffer_len: 0,
+            syntax_set: None,
+            injected_configs: HashMap::new(),
         })
     }
 
+    /// Highlight injected languages that have no tree-sitter grammar with
+    /// their TextMate grammar from `syntax_set`.
+    pub fn with_syntax_set(mut self, syntax_set: Arc<SyntaxSet>) -> Self {
+        self.syntax_set = Some(syntax_set);
+        self
+    }
+
     /// Highlight the visible viewport range
     ///
     /// This only parses the visible lines for instant performance with large files.
@@ -215,12 +239,14 @@ impl Highlighter {
         // single-slot tracker would mark them as "no highlight" and
         // the editor would render them with the surrounding default
         // foreground (the trailing variable colour, in practice).
+        let injections = self.prepare_injections(&source);
+        let injected_configs = &self.injected_configs;
         let mut cached_spans = Vec::new();
         match self.ts_highlighter.highlight(
             &self.config,
             &source,
-            None,     // cancellation flag
-            |_| None, // injection callback
+            None, // cancellation flag
+            move |name| injected_configs.get(name).and_then(Option::as_ref),
         ) {
             Ok(highlights) => {
                 let mut highlight_stack: Vec<usize> = Vec::new();
@@ -259,11 +285,40 @@ impl Highlighter {
                 tracing::error!("Failed to highlight: {}", e);
             }
         }
+        if let Some(syntax_set) = &self.syntax_set {
+            for injection in &injections {
+                let Some(index) = injection.language.syntect else {
+                    continue;
+                };
+                let Ok(code) = std::str::from_utf8(&source[injection.range.clone()]) else {
+                    continue;
+                };
+                let offset = parse_start + injection.range.start;
+                let inner = syntect_categories(code, &syntax_set.syntaxes()[index], syntax_set)
+                    .into_iter()
+                    .map(|(range, category)| CachedSpan {
+                        range: offset + range.start..offset + range.end,
+                        category,
+                    });
+                overlay_spans(
+                    &mut cached_spans,
+                    offset..parse_start + injection.range.end,
+                    inner,
+                );
+            }
+        }
 
         // Update cache
         self.cache = Some(HighlightCache {
             range: parse_range,
             spans: cached_spans.clone(),
+            injections: injections
+                .into_iter()
+                .map(|injection| Injection {
+                    range: parse_start + injection.range.start..parse_start + injection.range.end,
+                    language: injection.language,
+                })
+                .collect(),
         });
         self.last_buffer_len = buffer.len();
 
@@ -283,6 +338,32 @@ impl Highlighter {
             .collect()
     }
 
+    /// Find the injected regions of `source` and make sure every injected
+    /// tree-sitter language has a highlight configuration.
+    fn prepare_injections(&mut self, source: &[u8]) -> Vec<Injection> {
+        if self.language.injections_query().is_none() {
+            return Vec::new();
+        }
+        let injections = find_injections(source, self.language, self.syntax_set.as_deref());
+        for injection in &injections {
+            let Some(language) = injection.language.tree_sitter else {
+                continue;
+            };
+            if self.injected_configs.contains_key(&injection.language.name) {
+                continue;
+            }
+            // Highlights inside an injection are reported as indices into
+            // the host's capture list, so configure it with that list.
+            let config = language.highlight_config().ok().map(|mut config| {
+                config.configure(self.language.highlight_capture_names());
+                config
+            });
+            self.injected_configs
+                .insert(injection.language.name.clone(), config);
+        }
+        injections
+    }
+
     /// Invalidate cache for an edited range
     ///
     /// Call this when the buffer is edited to mark the cache as stale.
@@ -313,12 +394,65 @@ impl Highlighter {
             .map(|span| span.category)
     }
 
+    /// The language of the innermost injected region containing `position`
+    /// (see [`injections::injected_language_at`]). Answered from the regions
+    /// found by the last highlight pass when it covered `position` and the
+    /// buffer hasn't changed since; otherwise the buffer is searched.
+    pub fn injected_language_at(
+        &self,
+        buffer: &Buffer,
+        position: usize,
+    ) -> Option<InjectedLanguage> {
+        match &self.cache {
+            Some(cache)
+                if self.last_buffer_len == buffer.len()
+                    && cache.range.start <= position
+                    && position <= cache.range.end =>
+            {
+                injections::innermost_at(&cache.injections, position)
+                    .map(|injection| injection.language.clone())
+            }
+            _ => injections::injected_language_at(buffer, self.language, position),
+        }
+    }
+
     /// Get the current language
     pub fn language(&self) -> &Language {
         &self.language
     }
 }
 
+/// Replace whatever `spans` has inside `region` with `inner`, keeping the
+/// parts of spans that stick out of it.
+fn overlay_spans(
+    spans: &mut Vec<CachedSpan>,
+    region: Range<usize>,
+    inner: impl Iterator<Item = CachedSpan>,
+) {
+    let mut result = Vec::with_capacity(spans.len());
+    for span in spans.drain(..) {
+        if span.range.end <= region.start || span.range.start >= region.end {
+            result.push(span);
+            continue;
+        }
+        if span.range.start < region.start {
+            result.push(CachedSpan {
+                range: span.range.start..region.start,
+                category: span.category,
+            });
+        }
+        if span.range.end > region.end {
+            result.push(CachedSpan {
+                range: region.end..span.range.end,
+                category: span.category,
+            });
+        }
+    }
+    result.extend(inner);
+    result.sort_by_key(|span| span.range.start);
+    *spans = result;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -633,4 +767,27 @@ mod tests {
             "Themes should have different keyword colors"
         );
     }
+
+    #[test]
+    fn test_injected_languages_are_highlighted() {
+        let source = "package main\n\ntempl page() {\n\t<script>\n\t\tconst x = 1;\n\t</script>\n\t<style>\n\t\tp { color: red; }\n\t</style>\n}\n";
+        let buffer = Buffer::from_str_test(source);
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+        let mut highlighter = Highlighter::new(Language::Templ)
+            .unwrap()
+            .with_syntax_set(Arc::new(SyntaxSet::load_defaults_newlines()));
+        let spans = highlighter.highlight_viewport(&buffer, 0, buffer.len(), &theme, 100_000);
+        let category_of = |needle: &str| {
+            let start = source.find(needle).unwrap();
+            spans
+                .iter()
+                .find(|s| s.range.start <= start && start < s.range.end)
+                .and_then(|s| s.category)
+        };
+
+        // JavaScript in <script>, through its tree-sitter grammar.
+        assert_eq!(category_of("const"), Some(HighlightCategory::Keyword));
+        // CSS in <style> has only a TextMate grammar.
+        assert!(category_of("color").is_some(), "{spans:?}");
+    }
 }
@@ -117,6 +117,12 @@ impl IndentCalculator {
                 Language::Templ => ("templ", include_str!("../../queries/go/indents.scm")),
             };
 
+            // A grammar loaded at runtime may ship its own indent query,
+            // written against its node names.
+            let runtime_query = fresh_languages::runtime::grammar(*language)
+                .and_then(|grammar| grammar.indents.clone());
+            let query_str = runtime_query.as_deref().unwrap_or(query_str);
+
             // Check if we already have this config
             if !self.configs.contains_key(lang_name) {
                 // Create parser
@@ -0,0 +1,317 @@
+//! Tree-sitter language injection: code in one language embedded in another
+//!
+//! A grammar's `injections.scm` marks nodes whose text is another language —
+//! `<script>` and `<style>` in Templ, tagged templates such as
+//! `` css`...` `` in JavaScript. Each match names the embedded language,
+//! either through an `@injection.language` capture (the tag) or a
+//! `#set! injection.language` property, and captures the code itself as
+//! `@injection.content`.
+//!
+//! The name is resolved the way a Markdown fence is: a built-in language with
+//! a tree-sitter grammar is highlighted by tree-sitter, anything else by the
+//! TextMate grammar syntect finds for the token. Regions in a tree-sitter
+//! language are searched again for their own injections, so a tagged template
+//! inside a Templ `<script>` is found too.
+//!
+//! Besides highlighting, the innermost region at the cursor decides which
+//! language comment toggling and indentation use. Those look the region up in
+//! what the highlighter last found rather than parsing again, and compiled
+//! queries are shared by every buffer.
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use fresh_languages::tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::{Arc, Mutex, OnceLock};
+use syntect::parsing::SyntaxSet;
+
+/// Largest buffer that is searched for injections.
+const MAX_PARSE_BYTES: usize = 2 * 1024 * 1024;
+
+/// How deep injections inside injections are followed.
+const MAX_DEPTH: usize = 3;
+
+/// The language of an injected region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InjectedLanguage {
+    /// The name the injections query gave, e.g. `css` or `javascript`.
+    pub name: String,
+    /// The language id it resolved to, as used in `languages` config.
+    pub language_id: String,
+    /// The built-in language, when it has a tree-sitter grammar.
+    pub tree_sitter: Option<Language>,
+    /// Index of its syntect syntax, when it has only a TextMate grammar.
+    pub syntect: Option<usize>,
+}
+
+/// A region of the source written in another language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Injection {
+    /// Byte range of the embedded code.
+    pub range: Range<usize>,
+    pub language: InjectedLanguage,
+}
+
+/// Resolve the language name an injections query produced.
+///
+/// Built-in languages with a tree-sitter grammar win; otherwise `syntax_set`
+/// is searched the same way fenced code blocks are (by name or extension).
+/// Returns `None` for an empty name.
+pub fn resolve_injected_language(
+    name: &str,
+    syntax_set: Option<&SyntaxSet>,
+) -> Option<InjectedLanguage> {
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let builtin = Language::from_id(name).or_else(|| Language::from_name(name));
+    let tree_sitter = builtin.filter(|lang| lang.ts_language().is_some());
+    let syntect = match (tree_sitter, syntax_set) {
+        (None, Some(set)) => set
+            .find_syntax_by_token(name)
+            .and_then(|syntax| set.syntaxes().iter().position(|s| std::ptr::eq(s, syntax))),
+        _ => None,
+    };
+    let language_id = match (builtin, syntect.zip(syntax_set)) {
+        (Some(lang), _) => lang.id().to_string(),
+        (None, Some((index, set))) => {
+            let syntax_name = &set.syntaxes()[index].name;
+            Language::from_name(syntax_name)
+                .map(|lang| lang.id().to_string())
+                .unwrap_or_else(|| syntax_name.to_lowercase())
+        }
+        (None, None) => name.to_lowercase(),
+    };
+    Some(InjectedLanguage {
+        name: name.to_string(),
+        language_id,
+        tree_sitter,
+        syntect,
+    })
+}
+
+/// Every injected region in `source`, a document in `host`, outermost first.
+///
+/// Nested regions follow the region they are in. Returns nothing when the
+/// host has no injections query or the source is too large to parse.
+pub fn find_injections(
+    source: &[u8],
+    host: Language,
+    syntax_set: Option<&SyntaxSet>,
+) -> Vec<Injection> {
+    let mut injections = Vec::new();
+    if source.len() <= MAX_PARSE_BYTES {
+        collect(
+            source,
+            0..source.len(),
+            host,
+            0,
+            syntax_set,
+            &mut injections,
+        );
+    }
+    injections
+}
+
+/// The language of the innermost injected region containing `position`, or
+/// `None` when it is in the host language itself.
+///
+/// Both ends of a region count as inside it: a cursor on the first byte of a
+/// `<script>` body is on JavaScript, and typing at the end of the body still
+/// uses JavaScript rather than falling back to the host.
+pub fn injected_language_at(
+    buffer: &Buffer,
+    host: Language,
+    position: usize,
+) -> Option<InjectedLanguage> {
+    let injections = buffer_injections(buffer, host)?;
+    innermost_at(&injections, position).map(|i| i.language.clone())
+}
+
+/// Every injected region in `buffer`, or `None` when `host` has no
+/// injections query or the buffer is too large or not fully loaded.
+pub fn buffer_injections(buffer: &Buffer, host: Language) -> Option<Vec<Injection>> {
+    host.injections_query()?;
+    let len = buffer.len();
+    if len > MAX_PARSE_BYTES {
+        return None;
+    }
+    let text = buffer.slice_bytes(0..len);
+    if text.len() != len {
+        // Part of a lazily loaded buffer is not in memory.
+        return None;
+    }
+    Some(find_injections(&text, host, None))
+}
+
+/// The innermost of `injections` containing `position`, both ends included
+/// (see [`injected_language_at`]).
+pub fn innermost_at(injections: &[Injection], position: usize) -> Option<&Injection> {
+    injections
+        .iter()
+        .filter(|injection| injection.range.start <= position && position <= injection.range.end)
+        .min_by_key(|injection| injection.range.len())
+}
+
+/// The compiled injections query of `language`, compiled once per process.
+fn compiled_query(language: Language) -> Option<Arc<Query>> {
+    static QUERIES: OnceLock<Mutex<HashMap<Language, Option<Arc<Query>>>>> = OnceLock::new();
+    let mut queries = QUERIES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    queries
+        .entry(language)
+        .or_insert_with(|| {
+            let source = language.injections_query()?;
+            let ts_language = language.ts_language()?;
+            Query::new(&ts_language, &source)
+                .map_err(|e| tracing::warn!("Invalid injections query for {:?}: {}", language, e))
+                .ok()
+                .map(Arc::new)
+        })
+        .clone()
+}
+
+/// Add the injections of `source[range]`, written in `language`, to `out`.
+fn collect(
+    source: &[u8],
+    range: Range<usize>,
+    language: Language,
+    depth: usize,
+    syntax_set: Option<&SyntaxSet>,
+    out: &mut Vec<Injection>,
+) {
+    let Some(ts_language) = language.ts_language() else {
+        return;
+    };
+    let Some(query) = compiled_query(language) else {
+        return;
+    };
+    let Some(content_index) = query.capture_index_for_name("injection.content") else {
+        return;
+    };
+    let language_index = query.capture_index_for_name("injection.language");
+
+    let mut parser = Parser::new();
+    if parser.set_language(&ts_language).is_err() {
+        return;
+    }
+    let text = &source[range.clone()];
+    let Some(tree) = parser.parse(text, None) else {
+        return;
+    };
+
+    let first = out.len();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(&query, tree.root_node(), text);
+    while let Some(m) = matches.next() {
+        let mut name = query
+            .property_settings(m.pattern_index)
+            .iter()
+            .find(|property| &*property.key == "injection.language")
+            .and_then(|property| property.value.as_deref())
+            .map(str::to_string);
+        if let Some(node) = m
+            .captures
+            .iter()
+            .find(|c| Some(c.index) == language_index)
+            .map(|c| c.node)
+        {
+            name = std::str::from_utf8(&text[node.byte_range()])
+                .ok()
+                .map(str::to_string);
+        }
+        let Some(injected) = name
+            .as_deref()
+            .and_then(|name| resolve_injected_language(name, syntax_set))
+        else {
+            continue;
+        };
+        for capture in m.captures.iter().filter(|c| c.index == content_index) {
+            let node_range = capture.node.byte_range();
+            if node_range.is_empty() {
+                continue;
+            }
+            out.push(Injection {
+                range: range.start + node_range.start..range.start + node_range.end,
+                language: injected.clone(),
+            });
+        }
+    }
+
+    if depth + 1 >= MAX_DEPTH {
+        return;
+    }
+    let found: Vec<(Range<usize>, Language)> = out[first..]
+        .iter()
+        .filter_map(|i| Some((i.range.clone(), i.language.tree_sitter?)))
+        .collect();
+    for (inner, inner_language) in found {
+        collect(source, inner, inner_language, depth + 1, syntax_set, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPL: &str = "package main\n\ntempl page() {\n\t<script>\n\t\tconst x = 1;\n\t</script>\n\t<style>\n\t\tp { color: red; }\n\t</style>\n}\n";
+
+    #[test]
+    fn test_templ_script_and_style_are_injected() {
+        let injections = find_injections(TEMPL.as_bytes(), Language::Templ, None);
+        let script = injections
+            .iter()
+            .find(|i| i.language.language_id == "javascript")
+            .expect("script injection");
+        assert!(TEMPL[script.range.clone()].contains("const x = 1;"));
+        assert_eq!(script.language.tree_sitter, Some(Language::JavaScript));
+
+        let style = injections
+            .iter()
+            .find(|i| i.language.language_id == "css")
+            .expect("style injection");
+        assert!(TEMPL[style.range.clone()].contains("color: red"));
+        assert_eq!(style.language.tree_sitter, None);
+    }
+
+    #[test]
+    fn test_tagged_template_language_comes_from_the_tag() {
+        let source = "const q = sql`SELECT 1`;\n";
+        let set = SyntaxSet::load_defaults_newlines();
+        let injections = find_injections(source.as_bytes(), Language::JavaScript, Some(&set));
+        let sql = injections
+            .iter()
+            .find(|i| i.language.name == "sql")
+            .expect("sql injection");
+        assert_eq!(&source[sql.range.clone()], "SELECT 1");
+        assert!(sql.language.syntect.is_some());
+    }
+
+    #[test]
+    fn test_injections_nested_in_injected_javascript_are_found() {
+        let source = "package main\n\ntempl page() {\n\t<script>\n\t\tconst s = css`p {}`;\n\t</script>\n}\n";
+        let injections = find_injections(source.as_bytes(), Language::Templ, None);
+        let css = injections
+            .iter()
+            .find(|i| i.language.name == "css")
+            .expect("nested css injection");
+        assert_eq!(&source[css.range.clone()], "p {}");
+    }
+
+    #[test]
+    fn test_innermost_language_at_position() {
+        let buffer = Buffer::from_str_test(TEMPL);
+        let in_script = TEMPL.find("const x").unwrap();
+        let in_style = TEMPL.find("color").unwrap();
+        let in_go = TEMPL.find("page").unwrap();
+
+        let at = |pos| injected_language_at(&buffer, Language::Templ, pos).map(|l| l.language_id);
+        assert_eq!(at(in_script).as_deref(), Some("javascript"));
+        assert_eq!(at(in_style).as_deref(), Some("css"));
+        assert_eq!(at(in_go), None);
+    }
+}
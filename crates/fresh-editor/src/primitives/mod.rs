@@ -80,4 +80,14 @@ pub mod highlighter;
 #[cfg(feature = "runtime")]
 pub mod indent;
 #[cfg(feature = "runtime")]
+pub mod injections;
+#[cfg(feature = "runtime")]
+pub mod outline;
+#[cfg(feature = "runtime")]
 pub mod reference_highlighter;
+#[cfg(feature = "runtime")]
+pub mod selection_ranges;
+#[cfg(feature = "runtime")]
+pub mod structural_search;
+#[cfg(feature = "runtime")]
+pub mod text_objects;
@@ -0,0 +1,465 @@
+//! Local document outline for Go to Symbol in File
+//!
+//! Used when no language server answers `textDocument/documentSymbol`, so the
+//! `@` quick-open provider still lists the definitions of the file. Two tiers,
+//! in order of preference:
+//! 1. **Tree-sitter**: named definition nodes (functions, classes, types, …),
+//!    nested by their position in the parse tree.
+//! 2. **Regex**: one pattern set for the common definition keywords across
+//!    languages (`fn`, `def`, `func`, `class`, `struct`, …), nested by
+//!    indentation; Markdown headings nest by level.
+//!
+//! Symbols come back in document order. Each one records its nesting depth,
+//! the name of the symbol it is nested in and the byte range it spans, which
+//! sticky scroll and the breadcrumb bar use to tell which scopes enclose a
+//! line. The regex tier ends a scope where the indentation drops back.
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use lsp_types::SymbolKind;
+use regex::Regex;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// Largest buffer the outline is computed for.
+const MAX_OUTLINE_BYTES: usize = 2 * 1024 * 1024;
+
+/// One definition found in a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Byte offset of the symbol's name.
+    pub position: usize,
+    /// Number of symbols this one is nested in.
+    pub depth: usize,
+    /// Name of the innermost symbol this one is nested in.
+    pub container: Option<String>,
+    /// The whole definition: from the start of its first line (or node) to
+    /// the end of its body.
+    pub range: Range<usize>,
+}
+
+/// The definitions in `buffer`, in document order. `language_id` is the
+/// buffer's language name (e.g. `"markdown"`), used to pick heading rules.
+pub fn document_outline(
+    buffer: &Buffer,
+    language: Option<&Language>,
+    language_id: &str,
+) -> Vec<OutlineSymbol> {
+    let len = buffer.len();
+    if len > MAX_OUTLINE_BYTES {
+        return Vec::new();
+    }
+    let text = buffer.slice_bytes(0..len);
+    if text.len() != len {
+        // Part of a lazily loaded buffer is not in memory.
+        return Vec::new();
+    }
+    if let Some(symbols) = language.and_then(|language| tree_sitter_outline(&text, language)) {
+        return symbols;
+    }
+    let text = String::from_utf8_lossy(&text);
+    if language_id == "markdown" {
+        markdown_outline(&text)
+    } else {
+        regex_outline(&text)
+    }
+}
+
+/// Kinds whose nested functions are methods.
+fn is_type_like(kind: SymbolKind) -> bool {
+    matches!(
+        kind,
+        SymbolKind::CLASS
+            | SymbolKind::STRUCT
+            | SymbolKind::INTERFACE
+            | SymbolKind::OBJECT
+            | SymbolKind::ENUM
+    )
+}
+
+#[cfg(feature = "tree-sitter")]
+fn tree_sitter_outline(text: &[u8], language: &Language) -> Option<Vec<OutlineSymbol>> {
+    use fresh_languages::tree_sitter::{Node, Parser};
+
+    fn definition(node: &Node, text: &[u8]) -> Option<(String, SymbolKind, usize)> {
+        let kind = match node.kind() {
+            "function_declaration"
+            | "function_definition"
+            | "function_item"
+            | "generator_function_declaration" => SymbolKind::FUNCTION,
+            "method_definition" | "method_declaration" => SymbolKind::METHOD,
+            "class_declaration" | "class_definition" | "abstract_class_declaration" => {
+                SymbolKind::CLASS
+            }
+            "interface_declaration" | "trait_item" => SymbolKind::INTERFACE,
+            "enum_declaration" | "enum_item" => SymbolKind::ENUM,
+            "struct_item" => SymbolKind::STRUCT,
+            "type_alias_declaration" | "type_item" => SymbolKind::TYPE_PARAMETER,
+            "mod_item" | "module" | "internal_module" => SymbolKind::MODULE,
+            "const_item" | "static_item" => SymbolKind::CONSTANT,
+            "impl_item" => SymbolKind::OBJECT,
+            "type_spec" => match node.child_by_field_name("type").map(|t| t.kind()) {
+                Some("struct_type") => SymbolKind::STRUCT,
+                Some("interface_type") => SymbolKind::INTERFACE,
+                _ => SymbolKind::TYPE_PARAMETER,
+            },
+            // `const handler = () => …` names a function.
+            "variable_declarator" => match node.child_by_field_name("value").map(|v| v.kind()) {
+                Some("arrow_function" | "function_expression" | "function") => SymbolKind::FUNCTION,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let field = if node.kind() == "impl_item" {
+            "type"
+        } else {
+            "name"
+        };
+        let name_node = node.child_by_field_name(field)?;
+        let name = std::str::from_utf8(&text[name_node.byte_range()]).ok()?;
+        Some((name.to_string(), kind, name_node.start_byte()))
+    }
+
+    let ts_language = language.ts_language()?;
+    let mut parser = Parser::new();
+    parser.set_language(&ts_language).ok()?;
+    let tree = parser.parse(text, None)?;
+
+    let mut symbols: Vec<OutlineSymbol> = Vec::new();
+    // (node, index of the enclosing symbol) in document order.
+    let mut stack = vec![(tree.root_node(), None::<usize>)];
+    while let Some((node, parent)) = stack.pop() {
+        let mut enclosing = parent;
+        if let Some((name, mut kind, position)) = definition(&node, text) {
+            let parent_symbol = parent.map(|i| &symbols[i]);
+            if kind == SymbolKind::FUNCTION && parent_symbol.is_some_and(|p| is_type_like(p.kind)) {
+                kind = SymbolKind::METHOD;
+            }
+            symbols.push(OutlineSymbol {
+                name,
+                kind,
+                position,
+                depth: parent_symbol.map_or(0, |p| p.depth + 1),
+                container: parent_symbol.map(|p| p.name.clone()),
+                range: node.byte_range(),
+            });
+            enclosing = Some(symbols.len() - 1);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev().map(|child| (child, enclosing)));
+    }
+    Some(symbols)
+}
+
+#[cfg(not(feature = "tree-sitter"))]
+fn tree_sitter_outline(_text: &[u8], _language: &Language) -> Option<Vec<OutlineSymbol>> {
+    None
+}
+
+/// Definition patterns, tried in order on each line with its indentation
+/// removed. Each captures the symbol as `name`; patterns without a fixed
+/// kind also capture the defining keyword as `kw`.
+fn definition_patterns() -> &'static [(Regex, Option<SymbolKind>)] {
+    static PATTERNS: OnceLock<Vec<(Regex, Option<SymbolKind>)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        const MODIFIERS: &str = r#"^(?:(?:pub(?:\([^)]*\))?|export|default|public|private|protected|internal|static|abstract|final|sealed|open|override|async|unsafe|const|extern(?:\s+"[^"]*")?|inline|virtual|suspend|data|partial)\s+)*"#;
+        let pattern = |body: &str| Regex::new(&format!("{MODIFIERS}{body}")).expect("valid regex");
+        vec![
+            (
+                pattern(r"(?:fn|def|func|fun|function\*?)\s+(?:\([^)]*\)\s*)?(?P<name>[A-Za-z_$][\w$]*)"),
+                Some(SymbolKind::FUNCTION),
+            ),
+            (
+                pattern(r"(?P<kw>class|struct|enum|trait|interface|union|record|object)\s+(?P<name>[A-Za-z_$][\w$]*)"),
+                None,
+            ),
+            (
+                pattern(r"(?:mod|module|namespace)\s+(?P<name>[A-Za-z_][\w.:]*)"),
+                Some(SymbolKind::MODULE),
+            ),
+            (
+                pattern(r"type\s+(?P<name>[A-Za-z_]\w*)(?:\s*\[[^\]]*\])?\s+(?P<kw>struct|interface)\b"),
+                None,
+            ),
+            (
+                pattern(r"type\s+(?P<name>[A-Za-z_]\w*)"),
+                Some(SymbolKind::TYPE_PARAMETER),
+            ),
+            (
+                pattern(r"impl(?:<[^>]*>)?\s+(?P<name>[^{]+?)\s*(?:where\b.*)?\{?$"),
+                Some(SymbolKind::OBJECT),
+            ),
+            (
+                pattern(r"(?:const|static)\s+(?P<name>[A-Z_][A-Z0-9_]*)\s*:"),
+                Some(SymbolKind::CONSTANT),
+            ),
+            // Shell functions: `name() {`
+            (
+                Regex::new(r"^(?P<name>[A-Za-z_][\w-]*)\s*\(\)\s*\{?\s*$").expect("valid regex"),
+                Some(SymbolKind::FUNCTION),
+            ),
+        ]
+    })
+}
+
+fn keyword_kind(keyword: &str) -> SymbolKind {
+    match keyword {
+        "class" | "record" => SymbolKind::CLASS,
+        "struct" | "union" => SymbolKind::STRUCT,
+        "enum" => SymbolKind::ENUM,
+        "trait" | "interface" => SymbolKind::INTERFACE,
+        _ => SymbolKind::OBJECT,
+    }
+}
+
+/// Line comments in the languages the patterns cover.
+fn is_comment(line: &str) -> bool {
+    ["//", "#", "/*", "*", "--", ";"]
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
+}
+
+/// Whether `body` (a line without its indentation) closes the block opened
+/// at the same indentation, so the scope should include it.
+fn is_block_close(body: &str) -> bool {
+    body.starts_with(['}', ']', ')']) || body == "end" || body.starts_with("end ")
+}
+
+fn regex_outline(text: &str) -> Vec<OutlineSymbol> {
+    let mut symbols: Vec<OutlineSymbol> = Vec::new();
+    // (indentation, index into `symbols`) of the symbols still open.
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    // End of the last line with content, where an open scope ends unless a
+    // closing bracket follows.
+    let mut content_end = 0;
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        let start = line_start;
+        line_start += line.len();
+        let content = line.trim_end_matches(['\n', '\r']);
+        let body = content.trim_start();
+        if body.is_empty() || is_comment(body) {
+            continue;
+        }
+        let indent = content.len() - body.len();
+
+        // Any line indented no deeper than an open scope's header ends it.
+        while let Some(&(open_indent, index)) = open.last() {
+            if open_indent < indent {
+                break;
+            }
+            symbols[index].range.end = if open_indent == indent && is_block_close(body) {
+                start + content.len()
+            } else {
+                content_end
+            };
+            open.pop();
+        }
+        content_end = start + content.len();
+
+        let Some((name, mut kind, offset)) =
+            definition_patterns().iter().find_map(|(regex, kind)| {
+                let captures = regex.captures(body)?;
+                let name = captures.name("name")?;
+                let kind = kind.unwrap_or_else(|| {
+                    keyword_kind(captures.name("kw").map_or("", |k| k.as_str()))
+                });
+                Some((name.as_str().trim().to_string(), kind, name.start()))
+            })
+        else {
+            continue;
+        };
+
+        let parent = open.last().map(|&(_, i)| &symbols[i]);
+        if kind == SymbolKind::FUNCTION && parent.is_some_and(|p| is_type_like(p.kind)) {
+            kind = SymbolKind::METHOD;
+        }
+        symbols.push(OutlineSymbol {
+            name,
+            kind,
+            position: start + indent + offset,
+            depth: open.len(),
+            container: parent.map(|p| p.name.clone()),
+            range: start..content_end,
+        });
+        open.push((indent, symbols.len() - 1));
+    }
+    for (_, index) in open {
+        symbols[index].range.end = content_end;
+    }
+    symbols
+}
+
+fn markdown_outline(text: &str) -> Vec<OutlineSymbol> {
+    let mut symbols: Vec<OutlineSymbol> = Vec::new();
+    // (heading level, index into `symbols`) of the enclosing headings.
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    let mut in_fence = false;
+    // End of the last non-blank line: a section ends there, before the
+    // blank lines leading up to the next heading.
+    let mut content_end = 0;
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        let start = line_start;
+        line_start += line.len();
+        let content = line.trim_end_matches(['\n', '\r']);
+        let previous_end = content_end;
+        if !content.trim().is_empty() {
+            content_end = start + content.len();
+        }
+        if content.trim_start().starts_with("```") || content.trim_start().starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let level = content.bytes().take_while(|&b| b == b'#').count();
+        if level == 0 || level > 6 || !content[level..].starts_with([' ', '\t']) {
+            continue;
+        }
+        let title = content[level..].trim().trim_end_matches('#').trim_end();
+        if title.is_empty() {
+            continue;
+        }
+        let offset = level + (content[level..].len() - content[level..].trim_start().len());
+
+        while let Some(&(open_level, index)) = open.last() {
+            if open_level < level {
+                break;
+            }
+            symbols[index].range.end = previous_end;
+            open.pop();
+        }
+        let parent = open.last().map(|&(_, i)| &symbols[i]);
+        symbols.push(OutlineSymbol {
+            name: title.to_string(),
+            kind: SymbolKind::STRING,
+            position: start + offset,
+            depth: open.len(),
+            container: parent.map(|p| p.name.clone()),
+            range: start..content_end,
+        });
+        open.push((level, symbols.len() - 1));
+    }
+    for (_, index) in open {
+        symbols[index].range.end = content_end;
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outline(text: &str, language_id: &str) -> Vec<(String, SymbolKind, usize)> {
+        let buffer = Buffer::from_str_test(text);
+        document_outline(&buffer, None, language_id)
+            .into_iter()
+            .map(|s| (s.name, s.kind, s.depth))
+            .collect()
+    }
+
+    #[test]
+    fn test_regex_outline_nests_by_indentation() {
+        let text = "pub struct Point {\n    x: i32,\n}\n\nimpl Point {\n    pub fn new() -> Self {\n        todo!()\n    }\n}\n\n// fn commented() {}\nasync fn main() {}\n";
+        assert_eq!(
+            outline(text, "rust"),
+            vec![
+                ("Point".to_string(), SymbolKind::STRUCT, 0),
+                ("Point".to_string(), SymbolKind::OBJECT, 0),
+                ("new".to_string(), SymbolKind::METHOD, 1),
+                ("main".to_string(), SymbolKind::FUNCTION, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_regex_outline_positions_and_containers() {
+        let text = "class Greeter:\n    def hello(self):\n        pass\n";
+        let buffer = Buffer::from_str_test(text);
+        let symbols = document_outline(&buffer, None, "python");
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols[1].name, "hello");
+        assert_eq!(symbols[1].position, text.find("hello").unwrap());
+        assert_eq!(symbols[1].container.as_deref(), Some("Greeter"));
+        assert_eq!(symbols[1].kind, SymbolKind::METHOD);
+    }
+
+    #[test]
+    fn test_regex_outline_ranges_end_where_indentation_drops() {
+        let text = "impl Point {\n    fn new() {\n        todo!()\n    }\n\n    fn x() {}\n}\nfn main() {}\n";
+        let buffer = Buffer::from_str_test(text);
+        let symbols = document_outline(&buffer, None, "rust");
+        let ranges: Vec<(&str, &str)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &text[s.range.clone()]))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                ("Point", &text[..text.find("\nfn main").unwrap()]),
+                ("new", "    fn new() {\n        todo!()\n    }"),
+                ("x", "    fn x() {}"),
+                ("main", "fn main() {}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markdown_outline_sections_end_before_the_next_heading() {
+        let text = "# Title\nintro\n\n## Install\nsteps\n\n# Next\n";
+        let buffer = Buffer::from_str_test(text);
+        let sections: Vec<(String, &str)> = document_outline(&buffer, None, "markdown")
+            .into_iter()
+            .map(|s| (s.name, &text[s.range]))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("Title".to_string(), "# Title\nintro\n\n## Install\nsteps"),
+                ("Install".to_string(), "## Install\nsteps"),
+                ("Next".to_string(), "# Next"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markdown_outline_nests_headings_and_skips_fences() {
+        let text = "# Title\n\n## Install\n\n```sh\n# not a heading\n```\n\n### From source\n\n## Usage ##\n";
+        assert_eq!(
+            outline(text, "markdown"),
+            vec![
+                ("Title".to_string(), SymbolKind::STRING, 0),
+                ("Install".to_string(), SymbolKind::STRING, 1),
+                ("From source".to_string(), SymbolKind::STRING, 2),
+                ("Usage".to_string(), SymbolKind::STRING, 1),
+            ]
+        );
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_tree_sitter_outline_uses_parse_tree() {
+        let text =
+            "class Cart {\n  add(item) {}\n}\nconst total = () => 0;\nfunction helper() {}\n";
+        let buffer = Buffer::from_str_test(text);
+        let symbols: Vec<(String, SymbolKind, usize)> =
+            document_outline(&buffer, Some(&Language::JavaScript), "javascript")
+                .into_iter()
+                .map(|s| (s.name, s.kind, s.depth))
+                .collect();
+        assert_eq!(
+            symbols,
+            vec![
+                ("Cart".to_string(), SymbolKind::CLASS, 0),
+                ("add".to_string(), SymbolKind::METHOD, 1),
+                ("total".to_string(), SymbolKind::FUNCTION, 0),
+                ("helper".to_string(), SymbolKind::FUNCTION, 0),
+            ]
+        );
+    }
+}
@@ -273,8 +273,14 @@ impl ReferenceHighlighter {
                 return;
             }
 
-            // Try to create locals query for scope-aware highlighting
-            if let Some(locals_source) = get_locals_query(language) {
+            // Try to create locals query for scope-aware highlighting,
+            // preferring one shipped with a grammar loaded at runtime
+            let runtime_locals = fresh_languages::runtime::grammar(*language)
+                .and_then(|grammar| grammar.locals.clone());
+            if let Some(locals_source) = runtime_locals
+                .as_deref()
+                .or_else(|| get_locals_query(language))
+            {
                 match Query::new(&ts_language, locals_source) {
                     Ok(query) => {
                         // Extract capture indices
@@ -0,0 +1,393 @@
+//! Structural selection ranges for Smart Expand / Shrink Selection
+//!
+//! A *chain* is the list of nested byte ranges around a selection, innermost
+//! first — the same shape as the parent-linked `SelectionRange` an LSP server
+//! returns. Expanding picks the first range in the chain that strictly
+//! contains the current selection ([`next_larger`]); shrinking picks the
+//! largest one strictly inside it ([`next_smaller`]).
+//!
+//! The language server is the preferred source and is handled by the caller.
+//! This module provides the local fallbacks, in order of preference:
+//! 1. **Tree-sitter**: ancestors of the smallest named node covering the
+//!    selection, plus the inside of every bracketed or quoted node.
+//! 2. **Heuristic**: word, quoted string, bracket pair, and line — for the
+//!    syntect-only languages that have no parse tree.
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use crate::primitives::word_navigation::is_word_char;
+use std::ops::Range;
+
+/// Largest buffer that is read and parsed in full. Beyond this only a window
+/// around the selection is scanned, with the heuristic.
+const MAX_PARSE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Bytes scanned on each side of the selection in buffers too large to parse.
+const HEURISTIC_WINDOW_BYTES: usize = 64 * 1024;
+
+/// Enclosing bracket levels collected by the heuristic.
+const MAX_BRACKET_LEVELS: usize = 64;
+
+/// Nested ranges enclosing `range`, innermost first, ending with the whole
+/// buffer. Every entry contains `range` and the entry before it.
+pub fn selection_chain(
+    buffer: &Buffer,
+    language: Option<&Language>,
+    range: Range<usize>,
+) -> Vec<Range<usize>> {
+    let len = buffer.len();
+    let range = range.start.min(len)..range.end.min(len);
+
+    let (window_start, window_end) = if len <= MAX_PARSE_BYTES {
+        (0, len)
+    } else {
+        (
+            range.start.saturating_sub(HEURISTIC_WINDOW_BYTES),
+            range.end.saturating_add(HEURISTIC_WINDOW_BYTES).min(len),
+        )
+    };
+    let whole = 0..len;
+    let text = buffer.slice_bytes(window_start..window_end);
+    if text.len() != window_end - window_start {
+        // Part of a lazily loaded buffer is not in memory.
+        return vec![whole];
+    }
+    let local = range.start - window_start..range.end - window_start;
+
+    let mut candidates = heuristic_ranges(&text, local.clone());
+    if let Some(nodes) = language
+        .filter(|_| window_start == 0 && window_end == len)
+        .and_then(|language| tree_sitter_ranges(&text, language, local.clone()))
+    {
+        // The parse tree supersedes the heuristic, except for the word range:
+        // it covers the word left of the cursor when the tree picks the
+        // token to its right.
+        candidates.retain(|r| is_word(&text, r));
+        candidates.extend(nodes);
+    }
+    let mut chain: Vec<Range<usize>> = candidates
+        .into_iter()
+        .map(|r| r.start + window_start..r.end + window_start)
+        .collect();
+    chain.push(whole);
+    normalize(chain, &range)
+}
+
+/// The first range in `chain` that strictly contains `current`.
+pub fn next_larger(chain: &[Range<usize>], current: &Range<usize>) -> Option<Range<usize>> {
+    chain
+        .iter()
+        .find(|r| contains(r, current) && *r != current)
+        .cloned()
+}
+
+/// The largest range in `chain` strictly inside `current`.
+pub fn next_smaller(chain: &[Range<usize>], current: &Range<usize>) -> Option<Range<usize>> {
+    chain
+        .iter()
+        .rev()
+        .find(|r| contains(current, r) && *r != current)
+        .cloned()
+}
+
+fn contains(outer: &Range<usize>, inner: &Range<usize>) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// Sort candidates from innermost to outermost and keep only a properly
+/// nested chain around `range`.
+fn normalize(mut candidates: Vec<Range<usize>>, range: &Range<usize>) -> Vec<Range<usize>> {
+    candidates.retain(|r| contains(r, range));
+    candidates.sort_by_key(|r| (r.len(), r.start));
+    let mut chain: Vec<Range<usize>> = Vec::with_capacity(candidates.len());
+    for r in candidates {
+        match chain.last() {
+            Some(last) if *last == r || !contains(&r, last) => {}
+            _ => chain.push(r),
+        }
+    }
+    chain
+}
+
+fn is_word(text: &[u8], r: &Range<usize>) -> bool {
+    !r.is_empty() && text[r.clone()].iter().all(|&b| is_word_char(b))
+}
+
+#[cfg(feature = "tree-sitter")]
+fn tree_sitter_ranges(
+    text: &[u8],
+    language: &Language,
+    range: Range<usize>,
+) -> Option<Vec<Range<usize>>> {
+    use fresh_languages::tree_sitter::Parser;
+
+    let ts_language = language.ts_language()?;
+    let mut parser = Parser::new();
+    parser.set_language(&ts_language).ok()?;
+    let tree = parser.parse(text, None)?;
+    let mut node = tree
+        .root_node()
+        .named_descendant_for_byte_range(range.start, range.end)?;
+
+    let mut ranges = Vec::new();
+    loop {
+        if node.child_count() >= 2 {
+            let first = node.child(0)?;
+            let last = node.child(node.child_count() as u32 - 1)?;
+            if !first.is_named() && !last.is_named() && closer_for(first.kind()) == last.kind() {
+                ranges.push(first.end_byte()..last.start_byte());
+            }
+        }
+        ranges.push(node.byte_range());
+        match node.parent() {
+            Some(parent) => node = parent,
+            None => break,
+        }
+    }
+    Some(ranges)
+}
+
+#[cfg(not(feature = "tree-sitter"))]
+fn tree_sitter_ranges(
+    _text: &[u8],
+    _language: &Language,
+    _range: Range<usize>,
+) -> Option<Vec<Range<usize>>> {
+    None
+}
+
+#[cfg(feature = "tree-sitter")]
+fn closer_for(opener: &str) -> &'static str {
+    match opener {
+        "(" => ")",
+        "[" => "]",
+        "{" => "}",
+        "\"" => "\"",
+        "'" => "'",
+        "`" => "`",
+        _ => "",
+    }
+}
+
+/// Word, quote, bracket and line ranges around `range` (unsorted).
+fn heuristic_ranges(text: &[u8], range: Range<usize>) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+
+    // Word (or the selection extended to whole words).
+    let mut start = range.start;
+    while start > 0 && is_word_char(text[start - 1]) {
+        start -= 1;
+    }
+    let mut end = range.end;
+    while end < text.len() && is_word_char(text[end]) {
+        end += 1;
+    }
+    if start < end {
+        ranges.push(start..end);
+    }
+
+    let line_start = text[..range.start]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let line_end = text[range.end..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(text.len(), |i| range.end + i);
+
+    quote_ranges(text, line_start..line_end, &range, &mut ranges);
+    bracket_ranges(text, &range, &mut ranges);
+
+    // The line without its indentation, then the whole line.
+    let trimmed = trim(text, line_start..line_end);
+    if !trimmed.is_empty() {
+        ranges.push(trimmed);
+    }
+    let line_end = line_end + usize::from(line_end < text.len());
+    ranges.push(line_start..line_end);
+    ranges
+}
+
+/// Inside and outside of each quoted string on the selection's line(s) that
+/// encloses the selection. Quotes are paired left to right, skipping escaped
+/// ones.
+fn quote_ranges(
+    text: &[u8],
+    line: Range<usize>,
+    range: &Range<usize>,
+    out: &mut Vec<Range<usize>>,
+) {
+    for quote in [b'"', b'\'', b'`'] {
+        let mut open = None;
+        let mut i = line.start;
+        while i < line.end {
+            match text[i] {
+                b'\\' => i += 1,
+                b if b == quote => match open.take() {
+                    None => open = Some(i),
+                    Some(start) => {
+                        if start < range.start && range.end <= i {
+                            out.push(start + 1..i);
+                            out.push(start..i + 1);
+                        }
+                    }
+                },
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Inside (raw and whitespace-trimmed) and outside of every bracket pair
+/// enclosing the selection.
+fn bracket_ranges(text: &[u8], range: &Range<usize>, out: &mut Vec<Range<usize>>) {
+    let mut levels = 0;
+    let mut pos = range.start;
+    while levels < MAX_BRACKET_LEVELS {
+        let Some(open) = unmatched_opener_before(text, pos) else {
+            break;
+        };
+        if let Some(close) = matching_closer(text, open) {
+            if close >= range.end {
+                out.push(open + 1..close);
+                out.push(trim(text, open + 1..close));
+                out.push(open..close + 1);
+                levels += 1;
+            }
+        }
+        pos = open;
+    }
+}
+
+fn unmatched_opener_before(text: &[u8], pos: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for i in (0..pos).rev() {
+        match text[i] {
+            b')' | b']' | b'}' => depth += 1,
+            b'(' | b'[' | b'{' => {
+                if depth == 0 {
+                    return Some(i);
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn matching_closer(text: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, &b) in text.iter().enumerate().skip(open) {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn trim(text: &[u8], r: Range<usize>) -> Range<usize> {
+    let mut start = r.start;
+    let mut end = r.end;
+    while start < end && text[start].is_ascii_whitespace() {
+        start += 1;
+    }
+    while end > start && text[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_all(text: &str, language: Option<&Language>, cursor: usize) -> Vec<String> {
+        let buffer = Buffer::from_str_test(text);
+        let mut current = cursor..cursor;
+        let mut steps = Vec::new();
+        loop {
+            let chain = selection_chain(&buffer, language, current.clone());
+            let Some(next) = next_larger(&chain, &current) else {
+                break;
+            };
+            steps.push(text[next.clone()].to_string());
+            current = next;
+        }
+        steps
+    }
+
+    #[test]
+    fn test_heuristic_walks_word_quotes_brackets_line() {
+        let text = "call(a, \"hello world\")\nnext";
+        let cursor = text.find("world").unwrap() + 1;
+        assert_eq!(
+            expand_all(text, None, cursor),
+            vec![
+                "world",
+                "hello world",
+                "\"hello world\"",
+                "a, \"hello world\"",
+                "(a, \"hello world\")",
+                "call(a, \"hello world\")",
+                "call(a, \"hello world\")\n",
+                text,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heuristic_block_inner_is_trimmed() {
+        let text = "fn f() {\n    body();\n}\n";
+        let cursor = text.find("body").unwrap();
+        let steps = expand_all(text, None, cursor);
+        assert!(steps.contains(&"body();".to_string()), "{steps:?}");
+        let inner = steps.iter().position(|s| s == "body();").unwrap();
+        assert_eq!(steps[inner + 1], "    body();\n");
+        assert_eq!(steps[inner + 2], "\n    body();\n");
+        assert_eq!(steps[inner + 3], "{\n    body();\n}");
+    }
+
+    #[test]
+    fn test_shrink_returns_previous_level() {
+        let text = "f(g(x))";
+        let buffer = Buffer::from_str_test(text);
+        let cursor = text.find('x').unwrap();
+        let chain = selection_chain(&buffer, None, cursor..cursor);
+        let outer = 0..text.len();
+        let smaller = next_smaller(&chain, &outer).unwrap();
+        assert_eq!(&text[smaller.clone()], "(g(x))");
+        let smaller = next_smaller(&chain, &smaller).unwrap();
+        assert_eq!(&text[smaller], "g(x)");
+    }
+
+    #[test]
+    fn test_escaped_quotes_are_skipped() {
+        let text = r#"s = "a \"b\" c";"#;
+        let cursor = text.find('c').unwrap();
+        let steps = expand_all(text, None, cursor);
+        assert_eq!(steps[1], r#"a \"b\" c"#);
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_tree_sitter_walks_syntax_nodes() {
+        let text = "const x = foo(1, bar + 2);\n";
+        let cursor = text.find("bar").unwrap();
+        let steps = expand_all(text, Some(&Language::JavaScript), cursor);
+        assert_eq!(steps[0], "bar");
+        assert_eq!(steps[1], "bar + 2");
+        assert_eq!(steps[2], "1, bar + 2");
+        assert_eq!(steps[3], "(1, bar + 2)");
+        assert_eq!(steps[4], "foo(1, bar + 2)");
+        assert_eq!(steps.last().unwrap(), text);
+    }
+}
@@ -0,0 +1,522 @@
+//! Structural search and replace over tree-sitter parse trees
+//!
+//! A query is a code pattern with metavariables, `foo($A, $B)`, and an
+//! optional rewrite template, `bar($B, $A)`. The pattern is parsed with the
+//! grammar of each file searched and compared node by node against that
+//! file's tree, so layout never matters: whitespace isn't in the tree and
+//! comments (tree-sitter "extras") are skipped on both sides.
+//!
+//! Metavariables:
+//! - `$NAME` (uppercase letters, digits, `_`) matches any one node; a name
+//!   used twice must match the same code both times.
+//! - `$$$NAME` matches any run of sibling nodes, including none, so
+//!   `foo($$$ARGS)` matches every call to `foo`.
+//! - `$_` and `$$$` match without binding.
+//!
+//! A `$` not followed by one of these forms is literal, so PHP and shell
+//! variables can still be written in patterns.
+//!
+//! Matching never crosses a language boundary: a file is matched in its own
+//! language only, and code embedded in it (a `<script>` in HTML, SQL in a
+//! string) is an opaque node of the host tree, never parsed with the
+//! pattern's grammar.
+
+use crate::primitives::highlighter::Language;
+use fresh_languages::tree_sitter::{Node, Parser, Tree};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+/// Files larger than this are skipped rather than parsed.
+pub const MAX_PARSE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Where a pattern may sit so that it parses, tried in order. Most grammars
+/// accept an expression or statement at top level; the rest need it inside
+/// a function body (Rust, Go, C-family, Java/C#, Python, PHP).
+const WRAPPERS: &[(&str, &str)] = &[
+    ("", ""),
+    ("", ";"),
+    ("fn __fresh_wrap() { ", " }"),
+    ("func __fresh_wrap() {\n", "\n}"),
+    ("void __fresh_wrap() { ", "; }"),
+    ("class __FreshWrap { void __fresh_wrap() { ", "; } }"),
+    ("def __fresh_wrap():\n    ", "\n"),
+    ("<?php ", ";"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MetaVar {
+    /// `_` for the anonymous forms, which never bind.
+    name: String,
+    variadic: bool,
+}
+
+impl MetaVar {
+    /// The identifier that stands in for the metavariable when the pattern
+    /// is parsed. Plain identifiers are valid in every grammar we parse.
+    fn placeholder(&self) -> String {
+        let kind = if self.variadic { "mvs" } else { "mv" };
+        format!("__{kind}_{}", self.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    Text(String),
+    Var(MetaVar),
+}
+
+/// Split a pattern or rewrite template into literal text and metavariables.
+fn tokenize(template: &str) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut rest = template;
+    while let Some(dollar) = rest.find('$') {
+        text.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        let (variadic, after) = match after.strip_prefix("$$") {
+            Some(after) => (true, after),
+            None => (false, after),
+        };
+        let name_len = after
+            .find(|c: char| !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'))
+            .unwrap_or(after.len());
+        let name = &after[..name_len];
+        let valid = if variadic {
+            !name.starts_with(|c: char| c.is_ascii_digit())
+        } else {
+            name.starts_with(|c: char| c.is_ascii_uppercase() || c == '_')
+        };
+        if !valid {
+            text.push('$');
+            rest = &rest[dollar + 1..];
+            continue;
+        }
+        if !text.is_empty() {
+            pieces.push(Piece::Text(std::mem::take(&mut text)));
+        }
+        pieces.push(Piece::Var(MetaVar {
+            name: if name.is_empty() { "_" } else { name }.to_string(),
+            variadic,
+        }));
+        rest = &after[name_len..];
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        pieces.push(Piece::Text(text));
+    }
+    pieces
+}
+
+/// A structural match: the byte range of the matched node(s) and, when the
+/// query has a rewrite, the text to replace them with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructuralMatch {
+    pub range: Range<usize>,
+    pub replacement: Option<String>,
+}
+
+/// The pattern parsed for one language.
+struct CompiledPattern {
+    tree: Tree,
+    /// The pattern as parsed, inside its wrapper.
+    text: Vec<u8>,
+    /// Byte range of the pattern's node within `text`.
+    range: Range<usize>,
+}
+
+/// A parsed search pattern and rewrite, ready to run against files of any
+/// language with a tree-sitter grammar.
+pub struct StructuralQuery {
+    /// The trimmed pattern with every metavariable replaced by its
+    /// placeholder.
+    source: String,
+    metavars: HashMap<String, MetaVar>,
+    rewrite: Option<Vec<Piece>>,
+    /// Per-language compile results, errors included, so a search over many
+    /// files parses the pattern once per language.
+    compiled: Mutex<HashMap<Language, Result<Arc<CompiledPattern>, String>>>,
+}
+
+impl StructuralQuery {
+    /// Parse `pattern` and `rewrite`. Fails when the pattern has no code
+    /// besides metavariables, or the rewrite uses a metavariable the pattern
+    /// doesn't bind.
+    pub fn new(pattern: &str, rewrite: Option<&str>) -> Result<Self, String> {
+        let pieces = tokenize(pattern.trim());
+        if !pieces
+            .iter()
+            .any(|p| matches!(p, Piece::Text(t) if !t.trim().is_empty()))
+        {
+            return Err("The pattern needs some code besides metavariables".to_string());
+        }
+        let mut source = String::new();
+        let mut metavars = HashMap::new();
+        for piece in &pieces {
+            match piece {
+                Piece::Text(text) => source.push_str(text),
+                Piece::Var(var) => {
+                    let placeholder = var.placeholder();
+                    source.push_str(&placeholder);
+                    metavars.insert(placeholder, var.clone());
+                }
+            }
+        }
+        let rewrite = rewrite.map(tokenize);
+        for piece in rewrite.iter().flatten() {
+            if let Piece::Var(var) = piece {
+                let bound = var.name != "_" && metavars.values().any(|m| m.name == var.name);
+                if !bound {
+                    let sigil = if var.variadic { "$$$" } else { "$" };
+                    return Err(format!(
+                        "The rewrite uses {sigil}{}, which the pattern doesn't bind",
+                        var.name
+                    ));
+                }
+            }
+        }
+        Ok(Self {
+            source,
+            metavars,
+            rewrite,
+            compiled: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Whether the pattern parses as `language`.
+    pub fn supports(&self, language: &Language) -> Result<(), String> {
+        self.compile(language).map(|_| ())
+    }
+
+    /// Every match in `source`, in document order. Matches don't nest: once
+    /// a node matches, its descendants aren't searched. Files over
+    /// [`MAX_PARSE_BYTES`] yield nothing. Fails when the pattern doesn't
+    /// parse as `language`.
+    pub fn find(&self, language: &Language, source: &[u8]) -> Result<Vec<StructuralMatch>, String> {
+        let pattern = self.compile(language)?;
+        if source.len() > MAX_PARSE_BYTES {
+            return Ok(Vec::new());
+        }
+        let ts_language = language.ts_language().ok_or_else(|| no_grammar(language))?;
+        let mut parser = Parser::new();
+        parser
+            .set_language(&ts_language)
+            .map_err(|e| e.to_string())?;
+        let Some(tree) = parser.parse(source, None) else {
+            return Ok(Vec::new());
+        };
+        let pattern_root = pattern
+            .tree
+            .root_node()
+            .descendant_for_byte_range(pattern.range.start, pattern.range.end)
+            .expect("compiled pattern node");
+        let matcher = Matcher {
+            metavars: &self.metavars,
+            pattern: &pattern.text,
+            source,
+        };
+        let root_is_meta = matcher.metavar(pattern_root).is_some();
+
+        let mut matches = Vec::new();
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if !node.is_extra() && (root_is_meta || node.kind() == pattern_root.kind()) {
+                let mut bindings = Bindings::new();
+                if matcher.match_node(pattern_root, node, &mut bindings) {
+                    matches.push(StructuralMatch {
+                        range: node.byte_range(),
+                        replacement: self.rewrite.as_ref().map(|r| render(r, &bindings, source)),
+                    });
+                    continue;
+                }
+            }
+            let mut cursor = node.walk();
+            let children: Vec<Node> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+        Ok(matches)
+    }
+
+    fn compile(&self, language: &Language) -> Result<Arc<CompiledPattern>, String> {
+        let mut compiled = self.compiled.lock().unwrap_or_else(|e| e.into_inner());
+        compiled
+            .entry(*language)
+            .or_insert_with(|| compile_pattern(&self.source, language).map(Arc::new))
+            .clone()
+    }
+}
+
+fn no_grammar(language: &Language) -> String {
+    format!("No tree-sitter grammar for {}", language.display_name())
+}
+
+/// Parse `source` as `language` inside the first wrapper that yields an
+/// error-free tree with a single node spanning exactly the pattern.
+fn compile_pattern(source: &str, language: &Language) -> Result<CompiledPattern, String> {
+    let ts_language = language.ts_language().ok_or_else(|| no_grammar(language))?;
+    let mut parser = Parser::new();
+    parser
+        .set_language(&ts_language)
+        .map_err(|e| e.to_string())?;
+    for (before, after) in WRAPPERS {
+        let text = format!("{before}{source}{after}");
+        let Some(tree) = parser.parse(&text, None) else {
+            continue;
+        };
+        if tree.root_node().has_error() {
+            continue;
+        }
+        let range = before.len()..before.len() + source.len();
+        let spans_pattern = tree
+            .root_node()
+            .descendant_for_byte_range(range.start, range.end)
+            .is_some_and(|node| node.byte_range() == range);
+        if spans_pattern {
+            return Ok(CompiledPattern {
+                tree,
+                text: text.into_bytes(),
+                range,
+            });
+        }
+    }
+    Err(format!(
+        "The pattern isn't a single piece of {} code",
+        language.display_name()
+    ))
+}
+
+/// Metavariable name → the target nodes it matched.
+type Bindings<'q, 't> = HashMap<&'q str, Vec<Node<'t>>>;
+
+struct Matcher<'q> {
+    metavars: &'q HashMap<String, MetaVar>,
+    /// Text of the compiled pattern (wrapper included).
+    pattern: &'q [u8],
+    /// Text of the file being searched.
+    source: &'q [u8],
+}
+
+impl<'q> Matcher<'q> {
+    /// The metavariable a pattern node stands for. A variadic placeholder
+    /// in statement position parses as a statement, so its trailing `;`
+    /// is allowed.
+    fn metavar(&self, node: Node) -> Option<&'q MetaVar> {
+        let text = std::str::from_utf8(&self.pattern[node.byte_range()]).ok()?;
+        self.metavars.get(text).or_else(|| {
+            self.metavars
+                .get(text.strip_suffix(';')?.trim_end())
+                .filter(|var| var.variadic)
+        })
+    }
+
+    fn match_node<'t>(&self, pattern: Node, target: Node<'t>, b: &mut Bindings<'q, 't>) -> bool {
+        if let Some(var) = self.metavar(pattern) {
+            return self.bind(var, &[target], b);
+        }
+        if pattern.kind() != target.kind() {
+            return false;
+        }
+        let pattern_children = significant_children(pattern);
+        let target_children = significant_children(target);
+        if pattern_children.is_empty() && target_children.is_empty() {
+            return self.pattern[pattern.byte_range()] == self.source[target.byte_range()];
+        }
+        self.match_seq(&pattern_children, &target_children, b)
+    }
+
+    fn match_seq<'t>(
+        &self,
+        pattern: &[Node],
+        target: &[Node<'t>],
+        b: &mut Bindings<'q, 't>,
+    ) -> bool {
+        let Some((&first, rest)) = pattern.split_first() else {
+            return target.is_empty();
+        };
+        if let Some(var) = self.metavar(first).filter(|var| var.variadic) {
+            // Shortest run first; backtrack into longer ones.
+            for take in 0..=target.len() {
+                let saved = b.clone();
+                if self.bind(var, &target[..take], b) && self.match_seq(rest, &target[take..], b) {
+                    return true;
+                }
+                *b = saved;
+            }
+            return false;
+        }
+        let Some((&head, tail)) = target.split_first() else {
+            return false;
+        };
+        let saved = b.clone();
+        if self.match_node(first, head, b) && self.match_seq(rest, tail, b) {
+            return true;
+        }
+        *b = saved;
+        false
+    }
+
+    fn bind<'t>(&self, var: &'q MetaVar, nodes: &[Node<'t>], b: &mut Bindings<'q, 't>) -> bool {
+        if var.name == "_" {
+            return true;
+        }
+        match b.get(var.name.as_str()) {
+            Some(bound) => {
+                bound.len() == nodes.len()
+                    && bound.iter().zip(nodes).all(|(&x, &y)| self.same_code(x, y))
+            }
+            None => {
+                b.insert(&var.name, nodes.to_vec());
+                true
+            }
+        }
+    }
+
+    /// Whether two nodes of the searched file are the same code, ignoring
+    /// layout and comments.
+    fn same_code(&self, a: Node, b: Node) -> bool {
+        if a.kind() != b.kind() {
+            return false;
+        }
+        let a_children = significant_children(a);
+        let b_children = significant_children(b);
+        if a_children.is_empty() && b_children.is_empty() {
+            return self.source[a.byte_range()] == self.source[b.byte_range()];
+        }
+        a_children.len() == b_children.len()
+            && a_children
+                .iter()
+                .zip(&b_children)
+                .all(|(&x, &y)| self.same_code(x, y))
+    }
+}
+
+/// Children that take part in matching: everything but extras (comments).
+fn significant_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| !child.is_extra())
+        .collect()
+}
+
+/// Fill in a rewrite template with the source text each metavariable bound.
+fn render(template: &[Piece], bindings: &Bindings, source: &[u8]) -> String {
+    let mut out = String::new();
+    for piece in template {
+        match piece {
+            Piece::Text(text) => out.push_str(text),
+            Piece::Var(var) => {
+                if let Some((first, last)) = bindings
+                    .get(var.name.as_str())
+                    .and_then(|nodes| Some((nodes.first()?, nodes.last()?)))
+                {
+                    out.push_str(&String::from_utf8_lossy(
+                        &source[first.start_byte()..last.end_byte()],
+                    ));
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_splits_metavariables_and_keeps_literal_dollars() {
+        let var = |name: &str, variadic| {
+            Piece::Var(MetaVar {
+                name: name.to_string(),
+                variadic,
+            })
+        };
+        assert_eq!(
+            tokenize("f($A, $$$REST, $_, $$$) + $x"),
+            vec![
+                Piece::Text("f(".to_string()),
+                var("A", false),
+                Piece::Text(", ".to_string()),
+                var("REST", true),
+                Piece::Text(", ".to_string()),
+                var("_", false),
+                Piece::Text(", ".to_string()),
+                var("_", true),
+                Piece::Text(") + $x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rewrite_may_only_use_bound_metavariables() {
+        assert!(StructuralQuery::new("foo($A)", Some("bar($A)")).is_ok());
+        let err = StructuralQuery::new("foo($A)", Some("bar($B)"))
+            .err()
+            .unwrap();
+        assert!(err.contains("$B"), "{err}");
+        assert!(StructuralQuery::new("$A", None).is_err());
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    fn rewrite(pattern: &str, rewrite: &str, source: &str) -> String {
+        let query = StructuralQuery::new(pattern, Some(rewrite)).unwrap();
+        let matches = query
+            .find(&Language::JavaScript, source.as_bytes())
+            .unwrap();
+        let mut out = source.to_string();
+        for m in matches.iter().rev() {
+            out.replace_range(m.range.clone(), m.replacement.as_deref().unwrap());
+        }
+        out
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn swaps_arguments_ignoring_layout_and_comments() {
+        let source = "foo(a, b);\nfoo(\n  x + 1, // first\n  g(y)\n);\nfoo(a);\nfood(a, b);\n";
+        assert_eq!(
+            rewrite("foo($A, $B)", "bar($B, $A)", source),
+            "bar(b, a);\nbar(g(y), x + 1);\nfoo(a);\nfood(a, b);\n"
+        );
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn variadic_metavariables_match_any_run_of_siblings() {
+        assert_eq!(
+            rewrite(
+                "log($$$ARGS)",
+                "console.log($$$ARGS)",
+                "log(); log(1, 2, 3); x.log(4);"
+            ),
+            "console.log(); console.log(1, 2, 3); x.log(4);"
+        );
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn repeated_metavariables_must_bind_the_same_code() {
+        let query = StructuralQuery::new("$A === $A", None).unwrap();
+        let source = "if (x === x) {} if (x === y) {} if (f( a ) === f(a)) {}";
+        let found: Vec<&str> = query
+            .find(&Language::JavaScript, source.as_bytes())
+            .unwrap()
+            .iter()
+            .map(|m| &source[m.range.clone()])
+            .collect();
+        assert_eq!(found, vec!["x === x", "f( a ) === f(a)"]);
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn pattern_must_parse_in_the_file_language() {
+        let query = StructuralQuery::new("fn $NAME() {}", None).unwrap();
+        assert!(query.supports(&Language::JavaScript).is_err());
+        assert!(
+            query.supports(&Language::Rust).is_err(),
+            "no Rust grammar is bundled"
+        );
+        let query = StructuralQuery::new("func $NAME() {}", None).unwrap();
+        assert!(query.supports(&Language::Go).is_ok());
+    }
+}
@@ -0,0 +1,487 @@
+//! Tree-sitter text objects: functions, classes, parameters, comments and
+//! blocks
+//!
+//! Each language with a bundled grammar has a `textobjects.scm` next to its
+//! `indents.scm`. Captures are named `<object>.outer` (the whole construct)
+//! and `<object>.inner` (its contents), as in nvim-treesitter-textobjects:
+//! - An inner capture on a bracketed node (a function body, a class body)
+//!   is narrowed to the text between the brackets, without surrounding
+//!   whitespace.
+//! - An object without an inner capture uses its outer node the same way.
+//! - Parameters are captured as `parameter.inner` only; the outer range adds
+//!   the separating comma and spaces, so deleting it leaves a valid list.
+//!
+//! Selection, motions and the parameter swap are computed here on the list
+//! of occurrences; the editor turns the resulting ranges into cursor moves
+//! and edits.
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use fresh_languages::tree_sitter::{Node, Parser, Query, QueryCursor, StreamingIterator};
+use std::ops::Range;
+
+/// Largest buffer that is parsed for text objects.
+const MAX_PARSE_BYTES: usize = 2 * 1024 * 1024;
+
+/// The kinds of text object a `textobjects.scm` can capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextObjectKind {
+    Function,
+    Class,
+    Parameter,
+    Comment,
+    Block,
+}
+
+impl TextObjectKind {
+    fn capture_prefix(self) -> &'static str {
+        match self {
+            Self::Function => "function",
+            Self::Class => "class",
+            Self::Parameter => "parameter",
+            Self::Comment => "comment",
+            Self::Block => "block",
+        }
+    }
+}
+
+/// A text object and which part of it is wanted, written the way keymaps
+/// and plugins name it: `function.inner`, `class.outer`, `parameter`.
+/// A bare kind means the outer range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextObject {
+    pub kind: TextObjectKind,
+    pub inner: bool,
+}
+
+impl TextObject {
+    pub fn parse(name: &str) -> Option<Self> {
+        let (kind, part) = name.split_once('.').unwrap_or((name, "outer"));
+        let kind = match kind {
+            "function" => TextObjectKind::Function,
+            "class" => TextObjectKind::Class,
+            "parameter" => TextObjectKind::Parameter,
+            "comment" => TextObjectKind::Comment,
+            "block" => TextObjectKind::Block,
+            _ => return None,
+        };
+        let inner = match part {
+            "inner" => true,
+            "outer" => false,
+            _ => return None,
+        };
+        Some(Self { kind, inner })
+    }
+}
+
+/// One occurrence of a text object in the buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextObjectRange {
+    pub outer: Range<usize>,
+    pub inner: Range<usize>,
+    /// Id of the syntax node the occurrence sits in. Parameters of the same
+    /// list share it, which is what makes them swappable.
+    pub parent: usize,
+}
+
+impl TextObjectRange {
+    pub fn range(&self, inner: bool) -> Range<usize> {
+        if inner {
+            self.inner.clone()
+        } else {
+            self.outer.clone()
+        }
+    }
+}
+
+/// The `textobjects.scm` source for a language, if it has one and a bundled
+/// grammar to run it against.
+fn query_source(language: &Language) -> Option<&'static str> {
+    match language {
+        Language::JavaScript => Some(include_str!("../../queries/javascript/textobjects.scm")),
+        Language::TypeScript => Some(include_str!("../../queries/typescript/textobjects.scm")),
+        Language::Go => Some(include_str!("../../queries/go/textobjects.scm")),
+        Language::Json | Language::Jsonc => {
+            Some(include_str!("../../queries/json/textobjects.scm"))
+        }
+        // Templ extends Go's grammar, so Go's objects apply, plus components.
+        Language::Templ => Some(concat!(
+            include_str!("../../queries/go/textobjects.scm"),
+            include_str!("../../queries/templ/textobjects.scm"),
+        )),
+        _ => None,
+    }
+}
+
+/// Every occurrence of `kind` in the buffer, sorted by start.
+///
+/// Returns `None` when the language has no text objects, or the buffer is
+/// too large or not fully loaded to be parsed.
+pub fn find_text_objects(
+    buffer: &Buffer,
+    language: &Language,
+    kind: TextObjectKind,
+) -> Option<Vec<TextObjectRange>> {
+    let source = query_source(language)?;
+    let ts_language = language.ts_language()?;
+    let len = buffer.len();
+    if len > MAX_PARSE_BYTES {
+        return None;
+    }
+    let text = buffer.slice_bytes(0..len);
+    if text.len() != len {
+        // Part of a lazily loaded buffer is not in memory.
+        return None;
+    }
+
+    let query = match Query::new(&ts_language, source) {
+        Ok(query) => query,
+        Err(e) => {
+            tracing::warn!("Invalid textobjects query for {:?}: {}", language, e);
+            return None;
+        }
+    };
+    let prefix = kind.capture_prefix();
+    let outer_name = format!("{prefix}.outer");
+    let inner_name = format!("{prefix}.inner");
+    let capture_index = |name: &str| {
+        query
+            .capture_names()
+            .iter()
+            .position(|n| *n == name)
+            .map(|i| i as u32)
+    };
+    let outer_index = capture_index(&outer_name);
+    let inner_index = capture_index(&inner_name);
+    if outer_index.is_none() && inner_index.is_none() {
+        return Some(Vec::new());
+    }
+
+    let mut parser = Parser::new();
+    parser.set_language(&ts_language).ok()?;
+    let tree = parser.parse(&text, None)?;
+
+    let mut objects = Vec::new();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(&query, tree.root_node(), text.as_slice());
+    while let Some(m) = matches.next() {
+        let mut outer = None;
+        let mut inner = None;
+        for capture in m.captures {
+            if Some(capture.index) == outer_index {
+                outer = Some(capture.node);
+            } else if Some(capture.index) == inner_index {
+                inner = Some(capture.node);
+            }
+        }
+        let object = match (outer, inner) {
+            (Some(outer), inner) => TextObjectRange {
+                outer: outer.byte_range(),
+                inner: bracket_inside(&text, inner.unwrap_or(outer)),
+                parent: outer.parent().map_or(0, |p| p.id()),
+            },
+            (None, Some(inner)) => {
+                if inner.kind() == "comment" {
+                    // Comments between arguments are not arguments.
+                    continue;
+                }
+                let range = inner.byte_range();
+                TextObjectRange {
+                    outer: if kind == TextObjectKind::Parameter {
+                        with_separator(&text, range.clone())
+                    } else {
+                        range.clone()
+                    },
+                    inner: range,
+                    parent: inner.parent().map_or(0, |p| p.id()),
+                }
+            }
+            (None, None) => continue,
+        };
+        objects.push(object);
+    }
+    objects.sort_by_key(|o| (o.outer.start, std::cmp::Reverse(o.outer.end)));
+    objects.dedup_by(|a, b| a.outer == b.outer);
+    Some(objects)
+}
+
+/// The text between a node's opening and closing bracket, trimmed of
+/// whitespace; the whole node when it is not bracketed.
+fn bracket_inside(text: &[u8], node: Node) -> Range<usize> {
+    let count = node.child_count() as u32;
+    if count >= 2 {
+        if let Some(last) = node.child(count - 1).filter(|n| !n.is_named()) {
+            let opener = match last.kind() {
+                "}" => "{",
+                ")" => "(",
+                "]" => "[",
+                _ => "",
+            };
+            let first = (0..count - 1)
+                .filter_map(|i| node.child(i))
+                .find(|n| !n.is_named() && n.kind() == opener);
+            if let Some(first) = first {
+                return trim(text, first.end_byte()..last.start_byte());
+            }
+        }
+    }
+    node.byte_range()
+}
+
+/// A parameter with the comma and spaces that separate it from the next
+/// one, or from the previous one when it is last.
+fn with_separator(text: &[u8], range: Range<usize>) -> Range<usize> {
+    let is_space = |b: u8| b == b' ' || b == b'\t';
+    let mut end = range.end;
+    while end < text.len() && is_space(text[end]) {
+        end += 1;
+    }
+    if text.get(end) == Some(&b',') {
+        end += 1;
+        while end < text.len() && is_space(text[end]) {
+            end += 1;
+        }
+        return range.start..end;
+    }
+    let mut start = range.start;
+    while start > 0 && text[start - 1].is_ascii_whitespace() {
+        start -= 1;
+    }
+    if start > 0 && text[start - 1] == b',' {
+        return start - 1..range.end;
+    }
+    range
+}
+
+fn trim(text: &[u8], r: Range<usize>) -> Range<usize> {
+    let mut start = r.start;
+    let mut end = r.end;
+    while start < end && text[start].is_ascii_whitespace() {
+        start += 1;
+    }
+    while end > start && text[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    start..end
+}
+
+fn contains(outer: &Range<usize>, inner: &Range<usize>) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// The range to select for an object, given the current selection.
+///
+/// The innermost occurrence around the selection wins, skipping those whose
+/// range is already selected so that repeating the command grows outward.
+/// When the selection is not inside any occurrence, the next one after it
+/// is picked. A cursor right after an occurrence belongs to the one it is
+/// on rather than the one it ends, like a block cursor.
+pub fn select_text_object(
+    objects: &[TextObjectRange],
+    inner: bool,
+    current: &Range<usize>,
+) -> Option<Range<usize>> {
+    objects
+        .iter()
+        .filter(|o| contains(&o.outer, current))
+        .filter(|o| !contains(current, &o.range(inner)))
+        .min_by_key(|o| {
+            (
+                current.is_empty() && o.outer.end == current.end,
+                o.outer.len(),
+            )
+        })
+        .or_else(|| objects.iter().find(|o| o.outer.start >= current.end))
+        .map(|o| o.range(inner))
+}
+
+/// Start of the next (or previous) occurrence after (or before) `position`.
+pub fn text_object_start(
+    objects: &[TextObjectRange],
+    position: usize,
+    forward: bool,
+) -> Option<usize> {
+    if forward {
+        objects
+            .iter()
+            .map(|o| o.outer.start)
+            .filter(|&s| s > position)
+            .min()
+    } else {
+        objects
+            .iter()
+            .map(|o| o.outer.start)
+            .filter(|&s| s < position)
+            .max()
+    }
+}
+
+/// The innermost parameter at `position` and its next (or previous)
+/// sibling in the same list, as `(current, sibling)` inner ranges.
+pub fn parameter_swap(
+    parameters: &[TextObjectRange],
+    position: usize,
+    forward: bool,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let current = parameters
+        .iter()
+        .filter(|p| p.outer.start <= position && position <= p.outer.end)
+        .min_by_key(|p| (p.outer.end == position, p.outer.len()))?;
+    let siblings: Vec<&TextObjectRange> = parameters
+        .iter()
+        .filter(|p| p.parent == current.parent)
+        .collect();
+    let index = siblings.iter().position(|p| p.inner == current.inner)?;
+    let other = if forward {
+        siblings.get(index + 1)?
+    } else {
+        siblings.get(index.checked_sub(1)?)?
+    };
+    Some((current.inner.clone(), other.inner.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn objects(text: &str, language: Language, name: &str) -> Vec<TextObjectRange> {
+        let buffer = Buffer::from_str_test(text);
+        let object = TextObject::parse(name).unwrap();
+        find_text_objects(&buffer, &language, object.kind).unwrap()
+    }
+
+    fn select<'a>(text: &'a str, language: Language, name: &str, cursor: usize) -> &'a str {
+        let found = objects(text, language, name);
+        let inner = TextObject::parse(name).unwrap().inner;
+        let range = select_text_object(&found, inner, &(cursor..cursor)).unwrap();
+        &text[range]
+    }
+
+    #[test]
+    fn test_parse_object_names() {
+        assert_eq!(
+            TextObject::parse("function.inner"),
+            Some(TextObject {
+                kind: TextObjectKind::Function,
+                inner: true
+            })
+        );
+        assert_eq!(
+            TextObject::parse("class"),
+            Some(TextObject {
+                kind: TextObjectKind::Class,
+                inner: false
+            })
+        );
+        assert_eq!(TextObject::parse("function.middle"), None);
+        assert_eq!(TextObject::parse("loop.inner"), None);
+    }
+
+    #[test]
+    fn test_separator_follows_or_precedes_parameter() {
+        let text = b"f(a, b, c)";
+        assert_eq!(with_separator(text, 2..3), 2..5);
+        assert_eq!(with_separator(text, 8..9), 6..9);
+        assert_eq!(with_separator(b"f(a)", 2..3), 2..3);
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_queries_compile() {
+        for language in [
+            Language::JavaScript,
+            Language::TypeScript,
+            Language::Go,
+            Language::Json,
+            Language::Templ,
+        ] {
+            let source = query_source(&language).unwrap();
+            let ts_language = language.ts_language().unwrap();
+            if let Err(e) = Query::new(&ts_language, source) {
+                panic!("textobjects.scm for {language:?} does not compile: {e}");
+            }
+        }
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_javascript_function_inner_and_outer() {
+        let text = "function add(a, b) {\n  return a + b;\n}\n";
+        let cursor = text.find("return").unwrap();
+        assert_eq!(
+            select(text, Language::JavaScript, "function.inner", cursor),
+            "return a + b;"
+        );
+        assert_eq!(
+            select(text, Language::JavaScript, "function.outer", cursor),
+            "function add(a, b) {\n  return a + b;\n}"
+        );
+        // From the signature, the inner range is still the body.
+        assert_eq!(
+            select(text, Language::JavaScript, "function.inner", 3),
+            "return a + b;"
+        );
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_repeated_selection_grows_to_enclosing_function() {
+        let text = "function outer() {\n  const f = () => {\n    go();\n  };\n}\n";
+        let found = objects(text, Language::JavaScript, "function.inner");
+        let cursor = text.find("go").unwrap();
+        let first = select_text_object(&found, true, &(cursor..cursor)).unwrap();
+        assert_eq!(&text[first.clone()], "go();");
+        let second = select_text_object(&found, true, &first).unwrap();
+        assert_eq!(&text[second], "const f = () => {\n    go();\n  };");
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_selection_seeks_forward_outside_objects() {
+        let text = "const x = 1;\nclass A {\n  m() {}\n}\n";
+        assert_eq!(
+            select(text, Language::JavaScript, "class.outer", 0),
+            "class A {\n  m() {}\n}"
+        );
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_go_parameters_and_motions() {
+        let text =
+            "package main\n\nfunc a(x int, y string) {}\n\nfunc b() {\n\tcall(1, g(2, 3))\n}\n";
+        let cursor = text.find("y string").unwrap();
+        assert_eq!(
+            select(text, Language::Go, "parameter.inner", cursor),
+            "y string"
+        );
+        assert_eq!(
+            select(text, Language::Go, "parameter.outer", cursor),
+            ", y string"
+        );
+
+        let functions = objects(text, Language::Go, "function");
+        let b = text.find("func b").unwrap();
+        assert_eq!(text_object_start(&functions, 0, true), Some(14));
+        assert_eq!(text_object_start(&functions, 14, true), Some(b));
+        assert_eq!(text_object_start(&functions, b, false), Some(14));
+        assert_eq!(text_object_start(&functions, b, true), None);
+
+        // The innermost list is the one swapped.
+        let parameters = objects(text, Language::Go, "parameter");
+        let two = text.find("2,").unwrap();
+        let (current, other) = parameter_swap(&parameters, two, true).unwrap();
+        assert_eq!((&text[current], &text[other]), ("2", "3"));
+        let one = text.find("1,").unwrap();
+        assert_eq!(parameter_swap(&parameters, one, false), None);
+        let (current, other) = parameter_swap(&parameters, one, true).unwrap();
+        assert_eq!((&text[current], &text[other]), ("1", "g(2, 3)"));
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_unsupported_language_has_no_objects() {
+        let buffer = Buffer::from_str_test("fn main() {}\n");
+        assert!(find_text_objects(&buffer, &Language::Rust, TextObjectKind::Function).is_none());
+    }
+}
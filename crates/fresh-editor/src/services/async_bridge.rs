@@ -166,6 +166,14 @@ pub enum AsyncMessage {
         locations: Vec<Location>,
     },
 
+    /// LSP call / type hierarchy response: the raw items of a `prepare`
+    /// or expansion request, in server order
+    LspHierarchy {
+        language: String,
+        request_id: u64,
+        result: Result<Vec<Value>, String>,
+    },
+
     /// LSP rename response
     LspRename {
         request_id: u64,
@@ -253,6 +261,88 @@ pub enum AsyncMessage {
         ranges: Vec<FoldingRange>,
     },
 
+    /// LSP code lens response (textDocument/codeLens)
+    LspCodeLens {
+        request_id: u64,
+        uri: String,
+        lenses: Vec<lsp_types::CodeLens>,
+    },
+
+    /// LSP codeLens/resolve response
+    LspCodeLensResolved {
+        request_id: u64,
+        lens: Result<lsp_types::CodeLens, String>,
+    },
+
+    /// LSP selection range response (textDocument/selectionRange), one
+    /// entry per requested position
+    LspSelectionRanges {
+        request_id: u64,
+        uri: String,
+        ranges: Result<Vec<lsp_types::SelectionRange>, String>,
+    },
+
+    /// LSP linked editing range response (textDocument/linkedEditingRange)
+    LspLinkedEditingRanges {
+        request_id: u64,
+        uri: String,
+        ranges: Result<Option<lsp_types::LinkedEditingRanges>, String>,
+    },
+
+    /// LSP on-type formatting response (textDocument/onTypeFormatting)
+    LspOnTypeFormatting {
+        request_id: u64,
+        uri: String,
+        edits: Result<Vec<lsp_types::TextEdit>, String>,
+    },
+
+    /// LSP document link response (textDocument/documentLink)
+    LspDocumentLinks {
+        request_id: u64,
+        uri: String,
+        links: Vec<lsp_types::DocumentLink>,
+    },
+
+    /// LSP documentLink/resolve response
+    LspDocumentLinkResolved {
+        request_id: u64,
+        link: Result<lsp_types::DocumentLink, String>,
+    },
+
+    /// LSP document color response (textDocument/documentColor)
+    LspDocumentColors {
+        request_id: u64,
+        uri: String,
+        colors: Vec<lsp_types::ColorInformation>,
+    },
+
+    /// LSP color presentation response (textDocument/colorPresentation)
+    LspColorPresentations {
+        request_id: u64,
+        uri: String,
+        presentations: Result<Vec<lsp_types::ColorPresentation>, String>,
+    },
+
+    /// LSP document symbol response (textDocument/documentSymbol)
+    LspDocumentSymbols {
+        request_id: u64,
+        uri: String,
+        symbols: Result<Option<lsp_types::DocumentSymbolResponse>, String>,
+    },
+
+    /// LSP file operation response (workspace/will{Create,Rename,Delete}Files)
+    /// from one server
+    LspWillFileOperation {
+        request_id: u64,
+        edit: Result<Option<lsp_types::WorkspaceEdit>, String>,
+    },
+
+    /// LSP workspace symbol response (workspace/symbol) from one server
+    LspWorkspaceSymbols {
+        request_id: u64,
+        symbols: Result<Option<lsp_types::WorkspaceSymbolResponse>, String>,
+    },
+
     /// LSP semantic tokens response (full, full/delta, or range)
     LspSemanticTokens {
         request_id: u64,
@@ -279,6 +369,10 @@ pub enum AsyncMessage {
     /// tokens for all open documents.
     LspSemanticTokensRefresh { language: String },
 
+    /// LSP server requests a code-lens refresh (workspace/codeLens/refresh).
+    /// Client should re-pull code lenses for all open documents.
+    LspCodeLensRefresh { language: String },
+
     /// LSP server registered (`client/registerCapability`) or unregistered
     /// (`client/unregisterCapability`) one or more capabilities dynamically.
     /// Many servers advertise little or nothing statically in their
@@ -406,6 +500,25 @@ pub enum AsyncMessage {
         label: Option<String>,
     },
 
+    /// LSP window/showMessageRequest (server -> client request). The action
+    /// the user picks, or `None` when they dismiss it, is sent on `reply`
+    /// and becomes the response.
+    LspShowMessageRequest {
+        language: String,
+        server_name: String,
+        params: lsp_types::ShowMessageRequestParams,
+        reply: tokio::sync::oneshot::Sender<Option<lsp_types::MessageActionItem>>,
+    },
+
+    /// LSP window/showDocument (server -> client request). Whether the
+    /// document was shown is sent on `reply`.
+    LspShowDocument {
+        language: String,
+        server_name: String,
+        params: lsp_types::ShowDocumentParams,
+        reply: tokio::sync::oneshot::Sender<bool>,
+    },
+
     /// LSP codeAction/resolve response
     LspCodeActionResolved {
         request_id: u64,
@@ -428,6 +541,37 @@ pub enum AsyncMessage {
         result: Result<Value, String>,
     },
 
+    /// Debug adapter event (`stopped`, `output`, `initialized`, …)
+    DapEvent {
+        session_id: u64,
+        event: String,
+        body: Option<Value>,
+    },
+
+    /// Response to a request sent through a `DapHandle`
+    DapResponse {
+        session_id: u64,
+        origin: crate::services::dap::DapRequestOrigin,
+        command: String,
+        result: Result<Value, String>,
+    },
+
+    /// Debug session is over — the adapter exited, the handshake failed, or
+    /// the adapter could not be spawned (`error` set in the last two cases).
+    DapSessionEnded {
+        session_id: u64,
+        error: Option<String>,
+    },
+
+    /// Output lines of a running task, in the order it printed them
+    TaskOutput { run_id: u64, lines: Vec<String> },
+
+    /// A task's process exited (`Ok(exit_code)`) or could not be spawned
+    TaskFinished {
+        run_id: u64,
+        result: Result<i32, String>,
+    },
+
     /// Plugin process completed with output
     PluginProcessOutput {
         /// Unique ID for this process (to match with callback)
@@ -0,0 +1,561 @@
+//! Async debug-adapter session task and its editor-side handle.
+//!
+//! Mirrors the LSP split: [`DapHandle`] is the synchronous handle the
+//! editor loop holds; the session itself runs on the tokio runtime, spawned
+//! through the active authority's
+//! [`LongRunningSpawner`](crate::services::remote::LongRunningSpawner) so an
+//! SSH, devcontainer or kube workspace runs the adapter where the code lives.
+//!
+//! The task only owns what must happen before the editor can talk to the
+//! adapter at all: `initialize`, then `launch`/`attach`. Everything after
+//! that — breakpoints, `configurationDone`, stepping — is driven by the
+//! editor through [`DapHandle::request`], with responses and events
+//! delivered as [`AsyncMessage`]s.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc as std_mpsc, Arc};
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::io::{AsyncBufRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+
+use super::protocol::{self, DapMessage, DapRequest, DapResponse};
+use crate::services::async_bridge::AsyncMessage;
+
+/// How long a `disconnect` may take before the adapter is killed.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Who asked for a request, so the response can be routed back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DapRequestOrigin {
+    /// Issued by the editor's own debug orchestration.
+    Editor,
+    /// Issued by a plugin via `editor.debugRequest`; carries the callback id.
+    Plugin(u64),
+}
+
+/// Everything needed to start one debug session.
+#[derive(Debug, Clone)]
+pub struct DapLaunch {
+    pub command: String,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    /// `adapterID` sent in `initialize` (the language key of the config).
+    pub adapter_id: String,
+    /// `"launch"` or `"attach"`.
+    pub request: String,
+    /// Arguments of the launch/attach request, already variable-expanded.
+    pub arguments: Value,
+}
+
+enum DapCommand {
+    Request {
+        origin: DapRequestOrigin,
+        command: String,
+        arguments: Option<Value>,
+    },
+    Disconnect,
+}
+
+/// Editor-side handle to a running debug session.
+#[derive(Debug)]
+pub struct DapHandle {
+    session_id: u64,
+    command_tx: mpsc::Sender<DapCommand>,
+}
+
+impl std::fmt::Debug for DapCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Request { command, .. } => write!(f, "Request({})", command),
+            Self::Disconnect => write!(f, "Disconnect"),
+        }
+    }
+}
+
+// Channel sends are best-effort: a failed `async_tx.send` means the editor
+// is shutting down and nobody is left to read the result.
+#[allow(clippy::let_underscore_must_use)]
+impl DapHandle {
+    /// Spawn the adapter and start a session. Failures (missing adapter,
+    /// failed handshake) surface as [`AsyncMessage::DapSessionEnded`] with
+    /// an error rather than here, because spawning happens off-loop.
+    pub fn spawn(
+        runtime: &tokio::runtime::Handle,
+        launch: DapLaunch,
+        async_tx: std_mpsc::Sender<AsyncMessage>,
+        long_running_spawner: Arc<dyn crate::services::remote::LongRunningSpawner>,
+    ) -> Self {
+        let session_id = NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed);
+        let (command_tx, command_rx) = mpsc::channel(64);
+
+        runtime.spawn(async move {
+            tracing::info!(
+                "Spawning debug adapter: {} {:?}",
+                launch.command,
+                launch.args
+            );
+            if !long_running_spawner.command_exists(&launch.command).await {
+                let _ = async_tx.send(AsyncMessage::DapSessionEnded {
+                    session_id,
+                    error: Some(format!(
+                        "Debug adapter '{}' not found in the active authority's PATH",
+                        launch.command
+                    )),
+                });
+                return;
+            }
+            let mut child = match long_running_spawner
+                .spawn_stdio(
+                    &launch.command,
+                    &launch.args,
+                    launch.env.clone(),
+                    None,
+                    None,
+                )
+                .await
+            {
+                Ok(child) => child,
+                Err(e) => {
+                    let _ = async_tx.send(AsyncMessage::DapSessionEnded {
+                        session_id,
+                        error: Some(format!(
+                            "Failed to spawn debug adapter '{}': {}",
+                            launch.command, e
+                        )),
+                    });
+                    return;
+                }
+            };
+            let (Some(stdin), Some(stdout)) = (child.take_stdin(), child.take_stdout()) else {
+                let _ = async_tx.send(AsyncMessage::DapSessionEnded {
+                    session_id,
+                    error: Some("Debug adapter has no stdio pipes".to_string()),
+                });
+                return;
+            };
+            // Adapter stderr is diagnostics about the adapter itself, not
+            // program output (that arrives as `output` events), so it goes
+            // to the log rather than the debug console.
+            if let Some(stderr) = child.take_stderr() {
+                tokio::spawn(async move {
+                    use tokio::io::AsyncBufReadExt;
+                    let mut lines = BufReader::new(stderr).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        tracing::debug!("debug adapter stderr: {}", line);
+                    }
+                });
+            }
+
+            let error = run_session(
+                session_id,
+                stdin,
+                BufReader::new(stdout),
+                &launch,
+                command_rx,
+                &async_tx,
+            )
+            .await
+            .err();
+            if let Err(e) = child.kill().await {
+                tracing::debug!("Debug adapter already exited: {}", e);
+            }
+            let _ = async_tx.send(AsyncMessage::DapSessionEnded { session_id, error });
+        });
+
+        Self {
+            session_id,
+            command_tx,
+        }
+    }
+
+    /// Process-unique id of this session; tags every message it produces.
+    pub fn session_id(&self) -> u64 {
+        self.session_id
+    }
+
+    /// Send an arbitrary DAP request. The response arrives as
+    /// [`AsyncMessage::DapResponse`] tagged with `origin`.
+    pub fn request(
+        &self,
+        origin: DapRequestOrigin,
+        command: &str,
+        arguments: Option<Value>,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(DapCommand::Request {
+                origin,
+                command: command.to_string(),
+                arguments,
+            })
+            .map_err(|e| format!("Debug session is not accepting requests: {}", e))
+    }
+
+    /// Ask the adapter to end the session (terminating the debuggee). The
+    /// adapter is killed if it doesn't answer within a short timeout.
+    pub fn disconnect(&self) {
+        let _ = self.command_tx.try_send(DapCommand::Disconnect);
+    }
+}
+
+/// Drive one session over an already-connected transport until the adapter
+/// goes away or the editor disconnects. Generic over the transport so tests
+/// can run it against an in-memory pipe.
+#[allow(clippy::let_underscore_must_use)]
+async fn run_session<W, R>(
+    session_id: u64,
+    mut writer: W,
+    mut reader: R,
+    launch: &DapLaunch,
+    mut command_rx: mpsc::Receiver<DapCommand>,
+    async_tx: &std_mpsc::Sender<AsyncMessage>,
+) -> Result<(), String>
+where
+    W: AsyncWrite + Unpin,
+    R: AsyncBufRead + Unpin + Send + 'static,
+{
+    // Reading happens on its own task: `read_message` is not cancel-safe,
+    // so it can't sit directly in the `select!` below.
+    let (message_tx, mut message_rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        loop {
+            match protocol::read_message(&mut reader).await {
+                Ok(message) => {
+                    if message_tx.send(message).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!("Debug adapter stream ended: {}", e);
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut next_seq: i64 = 1;
+    let mut pending: HashMap<i64, (DapRequestOrigin, String)> = HashMap::new();
+
+    send_request(
+        &mut writer,
+        &mut next_seq,
+        &mut pending,
+        DapRequestOrigin::Editor,
+        "initialize",
+        Some(protocol::initialize_arguments(&launch.adapter_id)),
+    )
+    .await?;
+
+    let mut disconnect_deadline: Option<tokio::time::Instant> = None;
+    loop {
+        let deadline = disconnect_deadline;
+        tokio::select! {
+            message = message_rx.recv() => {
+                let Some(message) = message else {
+                    return Ok(());
+                };
+                match message {
+                    DapMessage::Response(response) => {
+                        let Some((origin, command)) = pending.remove(&response.request_seq) else {
+                            tracing::debug!("Unsolicited DAP response: {:?}", response);
+                            continue;
+                        };
+                        if command == "initialize" {
+                            if !response.success {
+                                return Err(format!(
+                                    "Debug adapter rejected initialize: {}",
+                                    response.message.unwrap_or_default()
+                                ));
+                            }
+                            // Some adapters (debugpy) only answer `launch` after
+                            // `configurationDone`, so the launch response is not
+                            // awaited here — the `initialized` event drives the
+                            // rest of the handshake on the editor side.
+                            send_request(
+                                &mut writer,
+                                &mut next_seq,
+                                &mut pending,
+                                DapRequestOrigin::Editor,
+                                &launch.request,
+                                Some(launch.arguments.clone()),
+                            )
+                            .await?;
+                        }
+                        let done = command == "disconnect";
+                        let result = if response.success {
+                            Ok(response.body.unwrap_or(Value::Null))
+                        } else {
+                            Err(response
+                                .message
+                                .unwrap_or_else(|| format!("{} failed", command)))
+                        };
+                        let _ = async_tx.send(AsyncMessage::DapResponse {
+                            session_id,
+                            origin,
+                            command,
+                            result,
+                        });
+                        if done {
+                            return Ok(());
+                        }
+                    }
+                    DapMessage::Event(event) => {
+                        let _ = async_tx.send(AsyncMessage::DapEvent {
+                            session_id,
+                            event: event.event,
+                            body: event.body,
+                        });
+                    }
+                    DapMessage::Request(request) => {
+                        // Reverse requests (`runInTerminal`, `startDebugging`)
+                        // are not advertised in `initialize`; answer so the
+                        // adapter doesn't wait forever.
+                        let reply = DapMessage::Response(DapResponse {
+                            seq: next_seq,
+                            request_seq: request.seq,
+                            success: false,
+                            command: request.command,
+                            message: Some("not supported".to_string()),
+                            body: None,
+                        });
+                        next_seq += 1;
+                        write_message(&mut writer, &reply).await?;
+                    }
+                }
+            }
+            command = command_rx.recv(), if disconnect_deadline.is_none() => {
+                match command {
+                    Some(DapCommand::Request { origin, command, arguments }) => {
+                        send_request(&mut writer, &mut next_seq, &mut pending, origin, &command, arguments).await?;
+                    }
+                    Some(DapCommand::Disconnect) | None => {
+                        send_request(
+                            &mut writer,
+                            &mut next_seq,
+                            &mut pending,
+                            DapRequestOrigin::Editor,
+                            "disconnect",
+                            Some(serde_json::json!({ "terminateDebuggee": true })),
+                        )
+                        .await?;
+                        disconnect_deadline = Some(tokio::time::Instant::now() + DISCONNECT_TIMEOUT);
+                    }
+                }
+            }
+            _ = async {
+                match deadline {
+                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                tracing::warn!("Debug adapter did not answer disconnect in time");
+                return Ok(());
+            }
+        }
+    }
+}
+
+async fn send_request<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    next_seq: &mut i64,
+    pending: &mut HashMap<i64, (DapRequestOrigin, String)>,
+    origin: DapRequestOrigin,
+    command: &str,
+    arguments: Option<Value>,
+) -> Result<(), String> {
+    let seq = *next_seq;
+    *next_seq += 1;
+    pending.insert(seq, (origin, command.to_string()));
+    let request = DapMessage::Request(DapRequest {
+        seq,
+        command: command.to_string(),
+        arguments,
+    });
+    write_message(writer, &request).await
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    message: &DapMessage,
+) -> Result<(), String> {
+    let bytes = protocol::encode_message(message)?;
+    writer
+        .write_all(&bytes)
+        .await
+        .map_err(|e| format!("Failed to write to debug adapter: {}", e))?;
+    writer
+        .flush()
+        .await
+        .map_err(|e| format!("Failed to flush debug adapter stdin: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::dap::protocol::DapEvent;
+
+    fn launch() -> DapLaunch {
+        DapLaunch {
+            command: "fake".to_string(),
+            args: vec![],
+            env: vec![],
+            adapter_id: "python".to_string(),
+            request: "launch".to_string(),
+            arguments: serde_json::json!({ "program": "/w/main.py" }),
+        }
+    }
+
+    async fn expect_request<R: AsyncBufRead + Unpin>(reader: &mut R, command: &str) -> DapRequest {
+        match protocol::read_message(reader).await.unwrap() {
+            DapMessage::Request(request) => {
+                assert_eq!(request.command, command);
+                request
+            }
+            other => panic!("expected {} request, got {:?}", command, other),
+        }
+    }
+
+    async fn reply<W: AsyncWrite + Unpin>(writer: &mut W, request: &DapRequest, body: Value) {
+        write_message(
+            writer,
+            &DapMessage::Response(DapResponse {
+                seq: 100 + request.seq,
+                request_seq: request.seq,
+                success: true,
+                command: request.command.clone(),
+                message: None,
+                body: Some(body),
+            }),
+        )
+        .await
+        .unwrap();
+    }
+
+    /// The session performs initialize → launch on its own, forwards events
+    /// and routes responses to whoever asked, and ends on `disconnect`.
+    #[tokio::test]
+    async fn session_handshake_routes_events_and_responses() {
+        let (client_out, adapter_in) = tokio::io::duplex(4096);
+        let (mut adapter_out, client_in) = tokio::io::duplex(4096);
+        let (command_tx, command_rx) = mpsc::channel(8);
+        let (async_tx, async_rx) = std_mpsc::channel();
+
+        let session = tokio::spawn(async move {
+            let launch = launch();
+            run_session(
+                7,
+                client_out,
+                BufReader::new(client_in),
+                &launch,
+                command_rx,
+                &async_tx,
+            )
+            .await
+        });
+
+        let mut adapter_in = BufReader::new(adapter_in);
+        let initialize = expect_request(&mut adapter_in, "initialize").await;
+        assert_eq!(
+            initialize.arguments.as_ref().unwrap()["linesStartAt1"],
+            true
+        );
+        reply(&mut adapter_out, &initialize, serde_json::json!({})).await;
+        let launch_request = expect_request(&mut adapter_in, "launch").await;
+        assert_eq!(
+            launch_request.arguments.as_ref().unwrap()["program"],
+            "/w/main.py"
+        );
+
+        write_message(
+            &mut adapter_out,
+            &DapMessage::Event(DapEvent {
+                seq: 1,
+                event: "initialized".to_string(),
+                body: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        command_tx
+            .send(DapCommand::Request {
+                origin: DapRequestOrigin::Plugin(42),
+                command: "threads".to_string(),
+                arguments: None,
+            })
+            .await
+            .unwrap();
+        let threads = expect_request(&mut adapter_in, "threads").await;
+        reply(
+            &mut adapter_out,
+            &threads,
+            serde_json::json!({ "threads": [{ "id": 1, "name": "main" }] }),
+        )
+        .await;
+
+        command_tx.send(DapCommand::Disconnect).await.unwrap();
+        let disconnect = expect_request(&mut adapter_in, "disconnect").await;
+        reply(&mut adapter_out, &disconnect, Value::Null).await;
+
+        session.await.unwrap().unwrap();
+
+        let messages: Vec<AsyncMessage> = async_rx.try_iter().collect();
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            AsyncMessage::DapEvent { session_id: 7, event, .. } if event == "initialized"
+        )));
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            AsyncMessage::DapResponse {
+                origin: DapRequestOrigin::Plugin(42),
+                command,
+                result: Ok(body),
+                ..
+            } if command == "threads" && body["threads"][0]["name"] == "main"
+        )));
+    }
+
+    #[tokio::test]
+    async fn failed_initialize_ends_the_session_with_an_error() {
+        let (client_out, adapter_in) = tokio::io::duplex(4096);
+        let (mut adapter_out, client_in) = tokio::io::duplex(4096);
+        let (_command_tx, command_rx) = mpsc::channel(8);
+        let (async_tx, _async_rx) = std_mpsc::channel();
+
+        let session = tokio::spawn(async move {
+            let launch = launch();
+            run_session(
+                1,
+                client_out,
+                BufReader::new(client_in),
+                &launch,
+                command_rx,
+                &async_tx,
+            )
+            .await
+        });
+
+        let mut adapter_in = BufReader::new(adapter_in);
+        let initialize = expect_request(&mut adapter_in, "initialize").await;
+        write_message(
+            &mut adapter_out,
+            &DapMessage::Response(DapResponse {
+                seq: 1,
+                request_seq: initialize.seq,
+                success: false,
+                command: "initialize".to_string(),
+                message: Some("unsupported client".to_string()),
+                body: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        let err = session.await.unwrap().unwrap_err();
+        assert!(err.contains("unsupported client"), "{}", err);
+    }
+}
@@ -0,0 +1,17 @@
+//! Debug Adapter Protocol client.
+//!
+//! Launches or attaches to debug adapters (debugpy, lldb-dap, codelldb,
+//! delve, …) over stdio and exchanges DAP messages with them. The editor
+//! side — breakpoints in the gutter, the stopped-line highlight, stepping
+//! actions — lives in `app::debug_actions`; the call stack, variables,
+//! watch and debug console panels are the `debugger` plugin, which talks
+//! to the session through `editor.debugRequest` and the `debug_*` hooks.
+//!
+//! Only stdio transport is supported so the adapter can be spawned through
+//! the active authority like a language server. Adapters that only listen
+//! on a TCP port need a small stdio bridge as their configured command.
+
+mod client;
+pub mod protocol;
+
+pub use client::{DapHandle, DapLaunch, DapRequestOrigin};
@@ -0,0 +1,284 @@
+//! Debug Adapter Protocol wire types and framing.
+//!
+//! DAP uses the same `Content-Length` header framing as LSP, but its
+//! payload is not JSON-RPC: every message carries a `seq` and a `type`
+//! discriminator (`request`, `response` or `event`). Only the handful of
+//! typed bodies the editor itself interprets (stopped events, stack frames,
+//! output) are modelled here — everything else travels as raw JSON so
+//! plugins can drive requests the core knows nothing about.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+
+/// A single DAP protocol message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DapMessage {
+    Request(DapRequest),
+    Response(DapResponse),
+    Event(DapEvent),
+}
+
+/// A request, sent by the client — or by the adapter for reverse requests
+/// such as `runInTerminal`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DapRequest {
+    pub seq: i64,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Value>,
+}
+
+/// Response to a request. `body` is only meaningful when `success` is true;
+/// on failure `message` carries the adapter's short error string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DapResponse {
+    pub seq: i64,
+    pub request_seq: i64,
+    pub success: bool,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+/// An event pushed by the adapter (`stopped`, `output`, `terminated`, …).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DapEvent {
+    pub seq: i64,
+    pub event: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+/// Body of the `stopped` event.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoppedEventBody {
+    pub reason: String,
+    #[serde(default)]
+    pub thread_id: Option<i64>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub all_threads_stopped: bool,
+}
+
+/// Body of the `output` event.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OutputEventBody {
+    #[serde(default)]
+    pub category: Option<String>,
+    pub output: String,
+}
+
+/// Source reference inside a stack frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Source {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+/// One frame of a `stackTrace` response. `line`/`column` are 1-based, which
+/// is what the editor asks for in `initialize` (`linesStartAt1`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StackFrame {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub source: Option<Source>,
+    pub line: i64,
+    pub column: i64,
+}
+
+/// Body of the `stackTrace` response.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackTraceBody {
+    pub stack_frames: Vec<StackFrame>,
+}
+
+/// Arguments for the `initialize` request.
+pub fn initialize_arguments(adapter_id: &str) -> Value {
+    serde_json::json!({
+        "clientID": "fresh",
+        "clientName": "Fresh",
+        "adapterID": adapter_id,
+        "locale": "en-US",
+        "linesStartAt1": true,
+        "columnsStartAt1": true,
+        "pathFormat": "path",
+        "supportsVariableType": true,
+        "supportsVariablePaging": false,
+        "supportsRunInTerminalRequest": false,
+        "supportsProgressReporting": false,
+    })
+}
+
+/// Arguments for `setBreakpoints`: replaces every breakpoint in `path` with
+/// the given 0-based editor lines.
+pub fn set_breakpoints_arguments(path: &str, lines: &[usize]) -> Value {
+    let breakpoints: Vec<Value> = lines
+        .iter()
+        .map(|line| serde_json::json!({ "line": line + 1 }))
+        .collect();
+    serde_json::json!({
+        "source": { "path": path },
+        "breakpoints": breakpoints,
+    })
+}
+
+/// Serialize a message with its `Content-Length` header.
+pub fn encode_message(message: &DapMessage) -> Result<Vec<u8>, String> {
+    let json = serde_json::to_string(message)
+        .map_err(|e| format!("Failed to serialize DAP message: {}", e))?;
+    let mut out = format!("Content-Length: {}\r\n\r\n", json.len()).into_bytes();
+    out.extend_from_slice(json.as_bytes());
+    Ok(out)
+}
+
+/// Read one framed message. Returns an error on EOF so the caller can treat
+/// a closed stream as the adapter going away.
+pub async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<DapMessage, String> {
+    let mut content_length: Option<usize> = None;
+
+    loop {
+        let mut line = String::new();
+        let bytes_read = reader
+            .read_line(&mut line)
+            .await
+            .map_err(|e| format!("Failed to read from debug adapter: {}", e))?;
+
+        if bytes_read == 0 {
+            return Err("Debug adapter closed its output (EOF)".to_string());
+        }
+
+        if line == "\r\n" || line == "\n" {
+            break;
+        }
+
+        if let Some(len_str) = line.strip_prefix("Content-Length:") {
+            content_length = Some(
+                len_str
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("Invalid Content-Length: {}", e))?,
+            );
+        }
+    }
+
+    let content_length =
+        content_length.ok_or_else(|| "Missing Content-Length header".to_string())?;
+
+    let mut content = vec![0u8; content_length];
+    reader
+        .read_exact(&mut content)
+        .await
+        .map_err(|e| format!("Failed to read content: {}", e))?;
+
+    tracing::trace!(
+        "Received DAP message: {}",
+        String::from_utf8_lossy(&content)
+    );
+
+    serde_json::from_slice(&content).map_err(|e| format!("Failed to deserialize message: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn encode_then_read_round_trips() {
+        let message = DapMessage::Request(DapRequest {
+            seq: 3,
+            command: "continue".to_string(),
+            arguments: Some(serde_json::json!({ "threadId": 1 })),
+        });
+        let bytes = encode_message(&message).unwrap();
+        let mut reader = BufReader::new(bytes.as_slice());
+        assert_eq!(read_message(&mut reader).await.unwrap(), message);
+    }
+
+    #[tokio::test]
+    async fn reads_consecutive_messages_and_reports_eof() {
+        let mut bytes = encode_message(&DapMessage::Event(DapEvent {
+            seq: 1,
+            event: "initialized".to_string(),
+            body: None,
+        }))
+        .unwrap();
+        bytes.extend(
+            encode_message(&DapMessage::Response(DapResponse {
+                seq: 2,
+                request_seq: 1,
+                success: false,
+                command: "launch".to_string(),
+                message: Some("boom".to_string()),
+                body: None,
+            }))
+            .unwrap(),
+        );
+        let mut reader = BufReader::new(bytes.as_slice());
+        assert!(matches!(
+            read_message(&mut reader).await.unwrap(),
+            DapMessage::Event(DapEvent { ref event, .. }) if event == "initialized"
+        ));
+        assert!(matches!(
+            read_message(&mut reader).await.unwrap(),
+            DapMessage::Response(DapResponse { success: false, .. })
+        ));
+        assert!(read_message(&mut reader).await.is_err());
+    }
+
+    #[test]
+    fn response_uses_snake_case_request_seq() {
+        let message: DapMessage = serde_json::from_str(
+            r#"{"seq":4,"type":"response","request_seq":2,"success":true,"command":"launch"}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            message,
+            DapMessage::Response(DapResponse {
+                request_seq: 2,
+                success: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn stack_trace_body_parses_adapter_payload() {
+        let body: StackTraceBody = serde_json::from_value(serde_json::json!({
+            "stackFrames": [
+                { "id": 7, "name": "main", "line": 4, "column": 1,
+                  "source": { "name": "main.py", "path": "/w/main.py" } },
+                { "id": 8, "name": "<module>", "line": 10, "column": 1 }
+            ],
+            "totalFrames": 2
+        }))
+        .unwrap();
+        assert_eq!(body.stack_frames.len(), 2);
+        assert_eq!(
+            body.stack_frames[0]
+                .source
+                .as_ref()
+                .and_then(|s| s.path.as_deref()),
+            Some("/w/main.py")
+        );
+        assert!(body.stack_frames[1].source.is_none());
+    }
+
+    #[test]
+    fn set_breakpoints_arguments_are_one_based() {
+        let args = set_breakpoints_arguments("/w/main.py", &[0, 9]);
+        assert_eq!(args["breakpoints"][0]["line"], 1);
+        assert_eq!(args["breakpoints"][1]["line"], 10);
+        assert_eq!(args["source"]["path"], "/w/main.py");
+    }
+}
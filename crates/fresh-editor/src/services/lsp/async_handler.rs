@@ -15,6 +15,8 @@ use crate::services::async_bridge::{
     AsyncBridge, AsyncMessage, LspMessageType, LspProgressValue, LspSemanticTokensResponse,
     LspServerStatus,
 };
+use crate::services::lsp::file_operations::{FileOperation, FileOperationPhase};
+use crate::services::lsp::hierarchy::HierarchyRequest;
 use crate::services::process_limits::ProcessLimits;
 use lsp_types::{
     notification::{
@@ -59,6 +61,12 @@ const DID_OPEN_GRACE_PERIOD_MS: u64 = 200;
 /// answers) from leaving features wedged in their loading state forever.
 const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 30_000;
 
+/// Timeout for `workspace/will*Files`. The explorer holds the rename, move,
+/// create or delete until every asked server has answered, so a slow server
+/// gets much less time than a regular request before the operation goes
+/// ahead without its edits.
+const WILL_FILE_OPERATION_TIMEOUT_MS: u64 = 5_000;
+
 /// LSP error codes that should not surface as user-visible warnings.
 ///
 /// From [LSP 3.17 specification](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/):
@@ -111,6 +119,16 @@ fn is_informational_method(method: &str) -> bool {
             | "textDocument/documentSymbol"
             | "textDocument/inlayHint"
             | "textDocument/foldingRange"
+            | "textDocument/codeLens"
+            | "codeLens/resolve"
+            | "textDocument/selectionRange"
+            | "workspace/symbol"
+            | "textDocument/prepareCallHierarchy"
+            | "textDocument/prepareTypeHierarchy"
+            | "callHierarchy/incomingCalls"
+            | "callHierarchy/outgoingCalls"
+            | "typeHierarchy/supertypes"
+            | "typeHierarchy/subtypes"
     )
 }
 
@@ -366,25 +384,37 @@ impl LspClientState {
 fn create_client_capabilities() -> ClientCapabilities {
     use lsp_types::{
         CodeActionClientCapabilities, CodeActionKindLiteralSupport, CodeActionLiteralSupport,
+        CodeLensClientCapabilities, CodeLensWorkspaceClientCapabilities,
         CompletionClientCapabilities, CompletionItemCapability,
         CompletionItemCapabilityResolveSupport, DiagnosticClientCapabilities, DiagnosticTag,
-        DiagnosticWorkspaceClientCapabilities, DocumentFormattingClientCapabilities,
-        DocumentHighlightClientCapabilities, DocumentRangeFormattingClientCapabilities,
-        DocumentSymbolClientCapabilities, DynamicRegistrationClientCapabilities,
-        FoldingRangeCapability, FoldingRangeClientCapabilities, FoldingRangeKind,
-        FoldingRangeKindCapability, GeneralClientCapabilities, GotoCapability,
-        HoverClientCapabilities, InlayHintClientCapabilities, InlayHintWorkspaceClientCapabilities,
-        MarkupKind, PublishDiagnosticsClientCapabilities, RenameClientCapabilities,
-        SemanticTokensWorkspaceClientCapabilities, SignatureHelpClientCapabilities, TagSupport,
-        TextDocumentClientCapabilities, TextDocumentSyncClientCapabilities,
-        WorkspaceClientCapabilities, WorkspaceEditClientCapabilities,
+        DiagnosticWorkspaceClientCapabilities, DocumentColorClientCapabilities,
+        DocumentFormattingClientCapabilities, DocumentHighlightClientCapabilities,
+        DocumentLinkClientCapabilities, DocumentOnTypeFormattingClientCapabilities,
+        DocumentRangeFormattingClientCapabilities, DocumentSymbolClientCapabilities,
+        DynamicRegistrationClientCapabilities, FoldingRangeCapability,
+        FoldingRangeClientCapabilities, FoldingRangeKind, FoldingRangeKindCapability,
+        GeneralClientCapabilities, GotoCapability, HoverClientCapabilities,
+        InlayHintClientCapabilities, InlayHintWorkspaceClientCapabilities,
+        LinkedEditingRangeClientCapabilities, MarkupKind, MessageActionItemCapabilities,
+        PublishDiagnosticsClientCapabilities, RenameClientCapabilities,
+        SelectionRangeClientCapabilities, SemanticTokensWorkspaceClientCapabilities,
+        ShowDocumentClientCapabilities, ShowMessageRequestClientCapabilities,
+        SignatureHelpClientCapabilities, TagSupport, TextDocumentClientCapabilities,
+        TextDocumentSyncClientCapabilities, WorkspaceClientCapabilities,
+        WorkspaceEditClientCapabilities, WorkspaceFileOperationsClientCapabilities,
         WorkspaceSymbolClientCapabilities,
     };
 
     ClientCapabilities {
         window: Some(WindowClientCapabilities {
             work_done_progress: Some(true),
-            ..Default::default()
+            // Action items come back verbatim, extra properties included.
+            show_message: Some(ShowMessageRequestClientCapabilities {
+                message_action_item: Some(MessageActionItemCapabilities {
+                    additional_properties_support: Some(true),
+                }),
+            }),
+            show_document: Some(ShowDocumentClientCapabilities { support: true }),
         }),
         workspace: Some(WorkspaceClientCapabilities {
             apply_edit: Some(true),
@@ -428,6 +458,20 @@ fn create_client_capabilities() -> ClientCapabilities {
             semantic_tokens: Some(SemanticTokensWorkspaceClientCapabilities {
                 refresh_support: Some(true),
             }),
+            code_lens: Some(CodeLensWorkspaceClientCapabilities {
+                refresh_support: Some(true),
+            }),
+            // The file explorer asks for import updates before it creates,
+            // renames, moves or deletes files and reports them afterwards.
+            file_operations: Some(WorkspaceFileOperationsClientCapabilities {
+                dynamic_registration: Some(true),
+                did_create: Some(true),
+                will_create: Some(true),
+                did_rename: Some(true),
+                will_rename: Some(true),
+                did_delete: Some(true),
+                will_delete: Some(true),
+            }),
             ..Default::default()
         }),
         text_document: Some(TextDocumentClientCapabilities {
@@ -498,6 +542,12 @@ fn create_client_capabilities() -> ClientCapabilities {
             references: Some(DynamicRegistrationClientCapabilities {
                 dynamic_registration: Some(true),
             }),
+            call_hierarchy: Some(DynamicRegistrationClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            type_hierarchy: Some(DynamicRegistrationClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
             document_highlight: Some(DocumentHighlightClientCapabilities {
                 dynamic_registration: Some(true),
             }),
@@ -511,6 +561,12 @@ fn create_client_capabilities() -> ClientCapabilities {
             range_formatting: Some(DocumentRangeFormattingClientCapabilities {
                 dynamic_registration: Some(true),
             }),
+            on_type_formatting: Some(DocumentOnTypeFormattingClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            linked_editing_range: Some(LinkedEditingRangeClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
             code_action: Some(CodeActionClientCapabilities {
                 dynamic_registration: Some(true),
                 // Without `codeActionLiteralSupport`, rust-analyzer (and
@@ -554,6 +610,19 @@ fn create_client_capabilities() -> ClientCapabilities {
                 dynamic_registration: Some(true),
                 ..Default::default()
             }),
+            code_lens: Some(CodeLensClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            document_link: Some(DocumentLinkClientCapabilities {
+                dynamic_registration: Some(true),
+                tooltip_support: Some(true),
+            }),
+            color_provider: Some(DocumentColorClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            selection_range: Some(SelectionRangeClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
             diagnostic: Some(DiagnosticClientCapabilities {
                 dynamic_registration: Some(true),
                 ..Default::default()
@@ -702,6 +771,13 @@ fn extract_capability_summary(caps: &ServerCapabilities) -> ServerCapabilitySumm
             lsp_types::ImplementationProviderCapability::Simple(v) => *v,
             lsp_types::ImplementationProviderCapability::Options(_) => true,
         }),
+        call_hierarchy: bool_or_options(&caps.call_hierarchy_provider, |p| match p {
+            lsp_types::CallHierarchyServerCapability::Simple(v) => *v,
+            lsp_types::CallHierarchyServerCapability::Options(_) => true,
+        }),
+        // lsp-types has no `typeHierarchyProvider` field; the initialize
+        // handler reads it from the raw response.
+        type_hierarchy: false,
         references: bool_or_options(&caps.references_provider, |p| match p {
             lsp_types::OneOf::Left(v) => *v,
             lsp_types::OneOf::Right(_) => true,
@@ -716,6 +792,15 @@ fn extract_capability_summary(caps: &ServerCapabilities) -> ServerCapabilitySumm
                 lsp_types::OneOf::Right(_) => true,
             }
         }),
+        on_type_formatting_trigger_characters: caps
+            .document_on_type_formatting_provider
+            .as_ref()
+            .map(|p| {
+                std::iter::once(p.first_trigger_character.clone())
+                    .chain(p.more_trigger_character.iter().flatten().cloned())
+                    .collect()
+            })
+            .unwrap_or_default(),
         rename: bool_or_options(&caps.rename_provider, |p| match p {
             lsp_types::OneOf::Left(v) => *v,
             lsp_types::OneOf::Right(_) => true,
@@ -747,6 +832,30 @@ fn extract_capability_summary(caps: &ServerCapabilities) -> ServerCapabilitySumm
             }
             _ => false,
         }),
+        code_lens: caps.code_lens_provider.is_some(),
+        code_lens_resolve: caps
+            .code_lens_provider
+            .as_ref()
+            .and_then(|p| p.resolve_provider)
+            .unwrap_or(false),
+        document_link: caps.document_link_provider.is_some(),
+        document_link_resolve: caps
+            .document_link_provider
+            .as_ref()
+            .and_then(|p| p.resolve_provider)
+            .unwrap_or(false),
+        document_color: bool_or_options(&caps.color_provider, |p| match p {
+            lsp_types::ColorProviderCapability::Simple(v) => *v,
+            _ => true,
+        }),
+        selection_range: bool_or_options(&caps.selection_range_provider, |p| match p {
+            lsp_types::SelectionRangeProviderCapability::Simple(v) => *v,
+            _ => true,
+        }),
+        linked_editing_range: bool_or_options(&caps.linked_editing_range_provider, |p| match p {
+            lsp_types::LinkedEditingRangeServerCapabilities::Simple(v) => *v,
+            _ => true,
+        }),
         document_symbols: bool_or_options(&caps.document_symbol_provider, |p| match p {
             lsp_types::OneOf::Left(v) => *v,
             lsp_types::OneOf::Right(_) => true,
@@ -756,6 +865,11 @@ fn extract_capability_summary(caps: &ServerCapabilities) -> ServerCapabilitySumm
             lsp_types::OneOf::Right(_) => true,
         }),
         diagnostics: caps.diagnostic_provider.is_some(),
+        file_operations: crate::services::lsp::file_operations::FileOperationFilters::from_server(
+            caps.workspace
+                .as_ref()
+                .and_then(|w| w.file_operations.as_ref()),
+        ),
     }
 }
 
@@ -889,6 +1003,80 @@ enum LspCommand {
     /// Request folding ranges for a document
     FoldingRange { request_id: u64, uri: Uri },
 
+    /// Request code lenses for a document (textDocument/codeLens)
+    CodeLens { request_id: u64, uri: Uri },
+
+    /// Resolve a code lens to get its command (codeLens/resolve)
+    CodeLensResolve {
+        request_id: u64,
+        lens: Box<lsp_types::CodeLens>,
+    },
+
+    /// Request selection ranges at one or more positions
+    /// (textDocument/selectionRange)
+    SelectionRange {
+        request_id: u64,
+        uri: Uri,
+        positions: Vec<lsp_types::Position>,
+    },
+
+    /// Request the ranges linked to a position, such as a matching tag
+    /// name (textDocument/linkedEditingRange)
+    LinkedEditingRange {
+        request_id: u64,
+        uri: Uri,
+        position: lsp_types::Position,
+    },
+
+    /// Request formatting edits after a trigger character was typed
+    /// (textDocument/onTypeFormatting)
+    OnTypeFormatting {
+        request_id: u64,
+        uri: Uri,
+        position: lsp_types::Position,
+        ch: String,
+        tab_size: u32,
+        insert_spaces: bool,
+    },
+
+    /// Request the links of a document (textDocument/documentLink)
+    DocumentLinks { request_id: u64, uri: Uri },
+
+    /// Resolve a document link to get its target (documentLink/resolve)
+    DocumentLinkResolve {
+        request_id: u64,
+        link: Box<lsp_types::DocumentLink>,
+    },
+
+    /// Request the colour literals of a document (textDocument/documentColor)
+    DocumentColors { request_id: u64, uri: Uri },
+
+    /// Request the ways a colour can be written at a range
+    /// (textDocument/colorPresentation)
+    ColorPresentations {
+        request_id: u64,
+        uri: Uri,
+        color: lsp_types::Color,
+        range: lsp_types::Range,
+    },
+
+    /// Request the symbols of a document (textDocument/documentSymbol)
+    DocumentSymbols { request_id: u64, uri: Uri },
+
+    /// Search symbols across the workspace (workspace/symbol)
+    WorkspaceSymbols { request_id: u64, query: String },
+
+    /// Ask for the edits to make before files are created, renamed or
+    /// deleted (workspace/will{Create,Rename,Delete}Files)
+    WillFileOperation {
+        request_id: u64,
+        operation: FileOperation,
+    },
+
+    /// Notify that files were created, renamed or deleted
+    /// (workspace/did{Create,Rename,Delete}Files)
+    DidFileOperation { operation: FileOperation },
+
     /// Request semantic tokens for the entire document
     SemanticTokensFull { request_id: u64, uri: Uri },
 
@@ -952,6 +1140,12 @@ enum LspCommand {
         character: u32,
     },
 
+    /// Call / type hierarchy request (prepare or one-level expansion)
+    Hierarchy {
+        request_id: u64,
+        request: HierarchyRequest,
+    },
+
     /// Cancel a pending request
     CancelRequest {
         /// Editor's request ID to cancel
@@ -1328,9 +1522,16 @@ impl LspState {
             ..Default::default()
         };
 
-        let result: InitializeResult = self
+        let raw_result: Value = self
             .send_request_sequential(Initialize::METHOD, Some(params), pending)
             .await?;
+        // lsp-types' `ServerCapabilities` drops `typeHierarchyProvider`, so
+        // it is read from the raw response before deserializing.
+        let type_hierarchy = raw_result
+            .pointer("/capabilities/typeHierarchyProvider")
+            .is_some_and(|v| !v.is_null() && v != &Value::Bool(false));
+        let result: InitializeResult = serde_json::from_value(raw_result)
+            .map_err(|e| format!("Failed to deserialize response: {}", e))?;
 
         tracing::info!(
             "LSP initialize result: position_encoding={:?}",
@@ -1344,7 +1545,8 @@ impl LspState {
 
         self.initialized.store(true, Ordering::SeqCst);
 
-        let capabilities = extract_capability_summary(&result.capabilities);
+        let mut capabilities = extract_capability_summary(&result.capabilities);
+        capabilities.type_hierarchy = type_hierarchy;
 
         // Notify main loop
         let _ = self.async_tx.send(AsyncMessage::LspInitialized {
@@ -2571,189 +2773,641 @@ impl LspState {
         }
     }
 
-    /// Handle folding range request
-    async fn handle_folding_ranges(
+    /// Handle code lens request
+    async fn handle_code_lens(
         &self,
         request_id: u64,
         uri: Uri,
         pending: &PendingRequests,
     ) -> Result<(), String> {
-        use lsp_types::FoldingRangeParams;
+        use lsp_types::CodeLensParams;
 
-        tracing::trace!("LSP: folding range request for {}", uri.as_str());
+        tracing::trace!("LSP: code lens request for {}", uri.as_str());
 
-        let params = FoldingRangeParams {
+        let params = CodeLensParams {
             text_document: TextDocumentIdentifier { uri: uri.clone() },
             work_done_progress_params: WorkDoneProgressParams::default(),
             partial_result_params: PartialResultParams::default(),
         };
 
-        match self
-            .send_request_sequential::<_, Option<Vec<lsp_types::FoldingRange>>>(
-                "textDocument/foldingRange",
+        let result = self
+            .send_request_sequential::<_, Option<Vec<lsp_types::CodeLens>>>(
+                "textDocument/codeLens",
                 Some(params),
                 pending,
             )
-            .await
-        {
-            Ok(ranges) => {
-                let ranges = ranges.unwrap_or_default();
-                let uri_string = uri.as_str().to_string();
-
-                tracing::trace!(
-                    "LSP: received {} folding ranges for {}",
-                    ranges.len(),
-                    uri_string
-                );
-
-                let _ = self.async_tx.send(AsyncMessage::LspFoldingRanges {
-                    request_id,
-                    uri: uri_string,
-                    ranges,
-                });
-
-                Ok(())
-            }
+            .await;
+        let lenses = match &result {
+            Ok(lenses) => lenses.clone().unwrap_or_default(),
             Err(e) => {
-                tracing::debug!("Folding range request failed: {}", e);
-                let _ = self.async_tx.send(AsyncMessage::LspFoldingRanges {
-                    request_id,
-                    uri: uri.as_str().to_string(),
-                    ranges: Vec::new(),
-                });
-                Err(e)
+                tracing::debug!("Code lens request failed: {}", e);
+                Vec::new()
             }
+        };
+        let _ = self.async_tx.send(AsyncMessage::LspCodeLens {
+            request_id,
+            uri: uri.as_str().to_string(),
+            lenses,
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle codeLens/resolve request
+    async fn handle_code_lens_resolve(
+        &self,
+        request_id: u64,
+        lens: lsp_types::CodeLens,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        let result = self
+            .send_request_sequential::<_, lsp_types::CodeLens>(
+                "codeLens/resolve",
+                Some(lens),
+                pending,
+            )
+            .await;
+        if let Err(e) = &result {
+            tracing::debug!("codeLens/resolve failed: {}", e);
         }
+        let _ = self.async_tx.send(AsyncMessage::LspCodeLensResolved {
+            request_id,
+            lens: result.clone(),
+        });
+        result.map(|_| ())
     }
 
-    async fn handle_semantic_tokens_full(
+    /// Handle selection range request
+    async fn handle_selection_range(
         &self,
         request_id: u64,
         uri: Uri,
+        positions: Vec<lsp_types::Position>,
         pending: &PendingRequests,
     ) -> Result<(), String> {
-        use lsp_types::request::SemanticTokensFullRequest;
+        use lsp_types::SelectionRangeParams;
 
-        tracing::trace!("LSP: semanticTokens/full request for {}", uri.as_str());
+        tracing::trace!(
+            "LSP: selection range request for {} ({} positions)",
+            uri.as_str(),
+            positions.len()
+        );
 
-        let params = SemanticTokensParams {
+        let params = SelectionRangeParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            positions,
             work_done_progress_params: WorkDoneProgressParams::default(),
             partial_result_params: PartialResultParams::default(),
-            text_document: TextDocumentIdentifier { uri: uri.clone() },
         };
 
-        match self
-            .send_request_sequential_tracked::<_, Option<SemanticTokensResult>>(
-                SemanticTokensFullRequest::METHOD,
+        let result = self
+            .send_request_sequential::<_, Option<Vec<lsp_types::SelectionRange>>>(
+                "textDocument/selectionRange",
                 Some(params),
                 pending,
-                Some(request_id),
             )
-            .await
-        {
-            Ok(result) => {
-                let _ = self.async_tx.send(AsyncMessage::LspSemanticTokens {
-                    request_id,
-                    uri: uri.as_str().to_string(),
-                    response: LspSemanticTokensResponse::Full(Ok(result)),
-                });
-                Ok(())
-            }
-            Err(e) => {
-                tracing::debug!("Semantic tokens request failed: {}", e);
-                let _ = self.async_tx.send(AsyncMessage::LspSemanticTokens {
-                    request_id,
-                    uri: uri.as_str().to_string(),
-                    response: LspSemanticTokensResponse::Full(Err(e.clone())),
-                });
-                Err(e)
-            }
-        }
+            .await;
+        let _ = self.async_tx.send(AsyncMessage::LspSelectionRanges {
+            request_id,
+            uri: uri.as_str().to_string(),
+            ranges: result.clone().map(Option::unwrap_or_default),
+        });
+        result.map(|_| ())
     }
 
-    async fn handle_semantic_tokens_full_delta(
+    /// Handle linked editing range request
+    async fn handle_linked_editing_range(
         &self,
         request_id: u64,
         uri: Uri,
-        previous_result_id: String,
+        position: lsp_types::Position,
         pending: &PendingRequests,
     ) -> Result<(), String> {
-        use lsp_types::{
-            request::SemanticTokensFullDeltaRequest, SemanticTokensDeltaParams,
-            SemanticTokensFullDeltaResult,
-        };
+        use lsp_types::LinkedEditingRangeParams;
 
         tracing::trace!(
-            "LSP: semanticTokens/full/delta request for {}",
-            uri.as_str()
+            "LSP: linked editing range request for {} at {}:{}",
+            uri.as_str(),
+            position.line,
+            position.character
         );
 
-        let params = SemanticTokensDeltaParams {
+        let params = LinkedEditingRangeParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position,
+            },
             work_done_progress_params: WorkDoneProgressParams::default(),
-            partial_result_params: PartialResultParams::default(),
-            text_document: TextDocumentIdentifier { uri: uri.clone() },
-            previous_result_id,
         };
 
-        match self
-            .send_request_sequential_tracked::<_, Option<SemanticTokensFullDeltaResult>>(
-                SemanticTokensFullDeltaRequest::METHOD,
+        let result = self
+            .send_request_sequential::<_, Option<lsp_types::LinkedEditingRanges>>(
+                "textDocument/linkedEditingRange",
                 Some(params),
                 pending,
-                Some(request_id),
             )
-            .await
-        {
-            Ok(result) => {
-                let _ = self.async_tx.send(AsyncMessage::LspSemanticTokens {
-                    request_id,
-                    uri: uri.as_str().to_string(),
-                    response: LspSemanticTokensResponse::FullDelta(Ok(result)),
-                });
-                Ok(())
-            }
-            Err(e) => {
-                tracing::debug!("Semantic tokens delta request failed: {}", e);
-                let _ = self.async_tx.send(AsyncMessage::LspSemanticTokens {
-                    request_id,
-                    uri: uri.as_str().to_string(),
-                    response: LspSemanticTokensResponse::FullDelta(Err(e.clone())),
-                });
-                Err(e)
-            }
-        }
+            .await;
+        let _ = self.async_tx.send(AsyncMessage::LspLinkedEditingRanges {
+            request_id,
+            uri: uri.as_str().to_string(),
+            ranges: result.clone(),
+        });
+        result.map(|_| ())
     }
 
-    async fn handle_semantic_tokens_range(
+    /// Handle on-type formatting request
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_on_type_formatting(
         &self,
         request_id: u64,
         uri: Uri,
-        range: lsp_types::Range,
+        position: lsp_types::Position,
+        ch: String,
+        tab_size: u32,
+        insert_spaces: bool,
         pending: &PendingRequests,
     ) -> Result<(), String> {
-        use lsp_types::{request::SemanticTokensRangeRequest, SemanticTokensRangeParams};
+        use lsp_types::{DocumentOnTypeFormattingParams, FormattingOptions};
 
-        tracing::trace!("LSP: semanticTokens/range request for {}", uri.as_str());
+        tracing::trace!(
+            "LSP: on-type formatting request for {} after {:?}",
+            uri.as_str(),
+            ch
+        );
 
-        let params = SemanticTokensRangeParams {
+        let params = DocumentOnTypeFormattingParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position,
+            },
+            ch,
+            options: FormattingOptions {
+                tab_size,
+                insert_spaces,
+                ..Default::default()
+            },
+        };
+
+        let result = self
+            .send_request_sequential::<_, Option<Vec<lsp_types::TextEdit>>>(
+                "textDocument/onTypeFormatting",
+                Some(params),
+                pending,
+            )
+            .await;
+        let _ = self.async_tx.send(AsyncMessage::LspOnTypeFormatting {
+            request_id,
+            uri: uri.as_str().to_string(),
+            edits: result.clone().map(Option::unwrap_or_default),
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle document link request
+    async fn handle_document_links(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::DocumentLinkParams;
+
+        tracing::trace!("LSP: document link request for {}", uri.as_str());
+
+        let params = DocumentLinkParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
             work_done_progress_params: WorkDoneProgressParams::default(),
             partial_result_params: PartialResultParams::default(),
-            text_document: TextDocumentIdentifier { uri: uri.clone() },
-            range,
         };
 
-        match self
-            .send_request_sequential_tracked::<_, Option<lsp_types::SemanticTokensRangeResult>>(
-                SemanticTokensRangeRequest::METHOD,
+        let result = self
+            .send_request_sequential::<_, Option<Vec<lsp_types::DocumentLink>>>(
+                "textDocument/documentLink",
                 Some(params),
                 pending,
-                Some(request_id),
             )
-            .await
-        {
-            Ok(result) => {
-                let _ = self.async_tx.send(AsyncMessage::LspSemanticTokens {
-                    request_id,
+            .await;
+        let links = match &result {
+            Ok(links) => links.clone().unwrap_or_default(),
+            Err(e) => {
+                tracing::debug!("Document link request failed: {}", e);
+                Vec::new()
+            }
+        };
+        let _ = self.async_tx.send(AsyncMessage::LspDocumentLinks {
+            request_id,
+            uri: uri.as_str().to_string(),
+            links,
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle documentLink/resolve request
+    async fn handle_document_link_resolve(
+        &self,
+        request_id: u64,
+        link: lsp_types::DocumentLink,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        let result = self
+            .send_request_sequential::<_, lsp_types::DocumentLink>(
+                "documentLink/resolve",
+                Some(link),
+                pending,
+            )
+            .await;
+        if let Err(e) = &result {
+            tracing::debug!("documentLink/resolve failed: {}", e);
+        }
+        let _ = self.async_tx.send(AsyncMessage::LspDocumentLinkResolved {
+            request_id,
+            link: result.clone(),
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle document color request
+    async fn handle_document_colors(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::DocumentColorParams;
+
+        tracing::trace!("LSP: document color request for {}", uri.as_str());
+
+        let params = DocumentColorParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Vec<lsp_types::ColorInformation>>(
+                "textDocument/documentColor",
+                Some(params),
+                pending,
+            )
+            .await;
+        let colors = match &result {
+            Ok(colors) => colors.clone(),
+            Err(e) => {
+                tracing::debug!("Document color request failed: {}", e);
+                Vec::new()
+            }
+        };
+        let _ = self.async_tx.send(AsyncMessage::LspDocumentColors {
+            request_id,
+            uri: uri.as_str().to_string(),
+            colors,
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle color presentation request
+    async fn handle_color_presentations(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        color: lsp_types::Color,
+        range: lsp_types::Range,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::ColorPresentationParams;
+
+        let params = ColorPresentationParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            color,
+            range,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Vec<lsp_types::ColorPresentation>>(
+                "textDocument/colorPresentation",
+                Some(params),
+                pending,
+            )
+            .await;
+        let _ = self.async_tx.send(AsyncMessage::LspColorPresentations {
+            request_id,
+            uri: uri.as_str().to_string(),
+            presentations: result.clone(),
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle document symbol request
+    async fn handle_document_symbols(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::DocumentSymbolParams;
+
+        tracing::trace!("LSP: document symbol request for {}", uri.as_str());
+
+        let params = DocumentSymbolParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Option<lsp_types::DocumentSymbolResponse>>(
+                "textDocument/documentSymbol",
+                Some(params),
+                pending,
+            )
+            .await;
+        let _ = self.async_tx.send(AsyncMessage::LspDocumentSymbols {
+            request_id,
+            uri: uri.as_str().to_string(),
+            symbols: result.clone(),
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle workspace symbol request
+    async fn handle_workspace_symbols(
+        &self,
+        request_id: u64,
+        query: String,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::WorkspaceSymbolParams;
+
+        tracing::trace!("LSP: workspace symbol request for {:?}", query);
+
+        let params = WorkspaceSymbolParams {
+            query,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Option<lsp_types::WorkspaceSymbolResponse>>(
+                "workspace/symbol",
+                Some(params),
+                pending,
+            )
+            .await;
+        let _ = self.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+            request_id,
+            symbols: result.clone(),
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle a `workspace/will*Files` request
+    async fn handle_will_file_operation(
+        &self,
+        request_id: u64,
+        operation: FileOperation,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::WorkspaceEdit;
+
+        let method = operation.method(FileOperationPhase::Will);
+        tracing::trace!("LSP: {} request", method);
+
+        let timeout = Duration::from_millis(WILL_FILE_OPERATION_TIMEOUT_MS);
+        let result = match operation {
+            FileOperation::Create(params) => {
+                self.send_request_with_timeout::<_, Option<WorkspaceEdit>>(
+                    method,
+                    Some(params),
+                    pending,
+                    None,
+                    timeout,
+                )
+                .await
+            }
+            FileOperation::Rename(params) => {
+                self.send_request_with_timeout::<_, Option<WorkspaceEdit>>(
+                    method,
+                    Some(params),
+                    pending,
+                    None,
+                    timeout,
+                )
+                .await
+            }
+            FileOperation::Delete(params) => {
+                self.send_request_with_timeout::<_, Option<WorkspaceEdit>>(
+                    method,
+                    Some(params),
+                    pending,
+                    None,
+                    timeout,
+                )
+                .await
+            }
+        };
+        let _ = self.async_tx.send(AsyncMessage::LspWillFileOperation {
+            request_id,
+            edit: result.clone(),
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle a `workspace/did*Files` notification
+    async fn handle_did_file_operation(&self, operation: FileOperation) -> Result<(), String> {
+        use lsp_types::notification::{DidCreateFiles, DidDeleteFiles, DidRenameFiles};
+
+        tracing::trace!("LSP: {}", operation.method(FileOperationPhase::Did));
+        match operation {
+            FileOperation::Create(params) => self.send_notification::<DidCreateFiles>(params).await,
+            FileOperation::Rename(params) => self.send_notification::<DidRenameFiles>(params).await,
+            FileOperation::Delete(params) => self.send_notification::<DidDeleteFiles>(params).await,
+        }
+    }
+
+    /// Handle folding range request
+    async fn handle_folding_ranges(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::FoldingRangeParams;
+
+        tracing::trace!("LSP: folding range request for {}", uri.as_str());
+
+        let params = FoldingRangeParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Option<Vec<lsp_types::FoldingRange>>>(
+                "textDocument/foldingRange",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(ranges) => {
+                let ranges = ranges.unwrap_or_default();
+                let uri_string = uri.as_str().to_string();
+
+                tracing::trace!(
+                    "LSP: received {} folding ranges for {}",
+                    ranges.len(),
+                    uri_string
+                );
+
+                let _ = self.async_tx.send(AsyncMessage::LspFoldingRanges {
+                    request_id,
+                    uri: uri_string,
+                    ranges,
+                });
+
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Folding range request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspFoldingRanges {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    ranges: Vec::new(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    async fn handle_semantic_tokens_full(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::request::SemanticTokensFullRequest;
+
+        tracing::trace!("LSP: semanticTokens/full request for {}", uri.as_str());
+
+        let params = SemanticTokensParams {
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+        };
+
+        match self
+            .send_request_sequential_tracked::<_, Option<SemanticTokensResult>>(
+                SemanticTokensFullRequest::METHOD,
+                Some(params),
+                pending,
+                Some(request_id),
+            )
+            .await
+        {
+            Ok(result) => {
+                let _ = self.async_tx.send(AsyncMessage::LspSemanticTokens {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    response: LspSemanticTokensResponse::Full(Ok(result)),
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Semantic tokens request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspSemanticTokens {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    response: LspSemanticTokensResponse::Full(Err(e.clone())),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    async fn handle_semantic_tokens_full_delta(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        previous_result_id: String,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{
+            request::SemanticTokensFullDeltaRequest, SemanticTokensDeltaParams,
+            SemanticTokensFullDeltaResult,
+        };
+
+        tracing::trace!(
+            "LSP: semanticTokens/full/delta request for {}",
+            uri.as_str()
+        );
+
+        let params = SemanticTokensDeltaParams {
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            previous_result_id,
+        };
+
+        match self
+            .send_request_sequential_tracked::<_, Option<SemanticTokensFullDeltaResult>>(
+                SemanticTokensFullDeltaRequest::METHOD,
+                Some(params),
+                pending,
+                Some(request_id),
+            )
+            .await
+        {
+            Ok(result) => {
+                let _ = self.async_tx.send(AsyncMessage::LspSemanticTokens {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    response: LspSemanticTokensResponse::FullDelta(Ok(result)),
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Semantic tokens delta request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspSemanticTokens {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    response: LspSemanticTokensResponse::FullDelta(Err(e.clone())),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    async fn handle_semantic_tokens_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        range: lsp_types::Range,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{request::SemanticTokensRangeRequest, SemanticTokensRangeParams};
+
+        tracing::trace!("LSP: semanticTokens/range request for {}", uri.as_str());
+
+        let params = SemanticTokensRangeParams {
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            range,
+        };
+
+        match self
+            .send_request_sequential_tracked::<_, Option<lsp_types::SemanticTokensRangeResult>>(
+                SemanticTokensRangeRequest::METHOD,
+                Some(params),
+                pending,
+                Some(request_id),
+            )
+            .await
+        {
+            Ok(result) => {
+                let _ = self.async_tx.send(AsyncMessage::LspSemanticTokens {
+                    request_id,
                     uri: uri.as_str().to_string(),
                     response: LspSemanticTokensResponse::Range(Ok(result)),
                 });
@@ -2771,6 +3425,31 @@ impl LspState {
         }
     }
 
+    /// Handle a call / type hierarchy request. Items stay raw JSON so they
+    /// can be sent back verbatim when the caller expands them.
+    async fn handle_hierarchy(
+        &self,
+        request_id: u64,
+        request: HierarchyRequest,
+        pending: &PendingRequests,
+    ) {
+        let method = request.method();
+        tracing::trace!("LSP: {} request {}", method, request_id);
+
+        let result = self
+            .send_request_sequential::<_, Value>(method, Some(request.params()), pending)
+            .await
+            .map(|value| request.items_from_response(value));
+        if let Err(e) = &result {
+            tracing::debug!("{} request failed: {}", method, e);
+        }
+        let _ = self.async_tx.send(AsyncMessage::LspHierarchy {
+            language: (*self.language).clone(),
+            request_id,
+            result,
+        });
+    }
+
     /// Handle a plugin-initiated request by forwarding it to the server
     async fn handle_plugin_request(
         &self,
@@ -3455,154 +4134,351 @@ impl LspTask {
                     new_name,
                 } => {
                     if initialized {
-                        tracing::info!("Processing Rename request for {}", uri.as_str());
+                        tracing::info!("Processing Rename request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_rename(request_id, uri, line, character, new_name, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot rename");
+                        let _ = state.async_tx.send(AsyncMessage::LspRename {
+                            request_id,
+                            result: Err("LSP not initialized".to_string()),
+                        });
+                    }
+                }
+                LspCommand::Hover {
+                    request_id,
+                    uri,
+                    line,
+                    character,
+                } => {
+                    if initialized {
+                        tracing::info!("Processing Hover request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_hover(request_id, uri, line, character, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get hover");
+                        let _ = state.async_tx.send(AsyncMessage::LspHover {
+                            request_id,
+                            contents: String::new(),
+                            is_markdown: false,
+                            range: None,
+                        });
+                    }
+                }
+                LspCommand::References {
+                    request_id,
+                    uri,
+                    line,
+                    character,
+                } => {
+                    if initialized {
+                        tracing::info!("Processing References request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_references(request_id, uri, line, character, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get references");
+                        let _ = state.async_tx.send(AsyncMessage::LspReferences {
+                            request_id,
+                            locations: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::SignatureHelp {
+                    request_id,
+                    uri,
+                    line,
+                    character,
+                } => {
+                    if initialized {
+                        tracing::info!("Processing SignatureHelp request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_signature_help(request_id, uri, line, character, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get signature help");
+                        let _ = state.async_tx.send(AsyncMessage::LspSignatureHelp {
+                            request_id,
+                            signature_help: None,
+                        });
+                    }
+                }
+                LspCommand::CodeActions {
+                    request_id,
+                    uri,
+                    start_line,
+                    start_char,
+                    end_line,
+                    end_char,
+                    diagnostics,
+                } => {
+                    if initialized {
+                        tracing::info!("Processing CodeActions request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_code_actions(
+                                request_id,
+                                uri,
+                                start_line,
+                                start_char,
+                                end_line,
+                                end_char,
+                                diagnostics,
+                                &p,
+                            )
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get code actions");
+                        let _ = state.async_tx.send(AsyncMessage::LspCodeActions {
+                            request_id,
+                            actions: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::DocumentDiagnostic {
+                    request_id,
+                    uri,
+                    previous_result_id,
+                } => {
+                    if initialized {
+                        tracing::info!(
+                            "Processing DocumentDiagnostic request for {}",
+                            uri.as_str()
+                        );
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_document_diagnostic(request_id, uri, previous_result_id, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get document diagnostics");
+                        let _ = state.async_tx.send(AsyncMessage::LspPulledDiagnostics {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            server_name: (*state.server_name).clone(),
+                            result_id: None,
+                            diagnostics: Vec::new(),
+                            unchanged: false,
+                        });
+                    }
+                }
+                LspCommand::InlayHints {
+                    request_id,
+                    uri,
+                    start_line,
+                    start_char,
+                    end_line,
+                    end_char,
+                } => {
+                    if initialized {
+                        tracing::info!("Processing InlayHints request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_inlay_hints(
+                                request_id, uri, start_line, start_char, end_line, end_char, &p,
+                            )
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get inlay hints");
+                        let _ = state.async_tx.send(AsyncMessage::LspInlayHints {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            hints: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::CodeLens { request_id, uri } => {
+                    if initialized {
+                        tracing::info!("Processing CodeLens request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_code_lens(request_id, uri, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get code lenses");
+                        let _ = state.async_tx.send(AsyncMessage::LspCodeLens {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            lenses: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::CodeLensResolve { request_id, lens } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_code_lens_resolve(request_id, *lens, &p)
+                            .await);
+                    } else {
+                        let _ = state.async_tx.send(AsyncMessage::LspCodeLensResolved {
+                            request_id,
+                            lens: Err("LSP not initialized".to_string()),
+                        });
+                    }
+                }
+                LspCommand::SelectionRange {
+                    request_id,
+                    uri,
+                    positions,
+                } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_selection_range(request_id, uri, positions, &p)
+                            .await);
+                    } else {
+                        let _ = state.async_tx.send(AsyncMessage::LspSelectionRanges {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            ranges: Err("LSP not initialized".to_string()),
+                        });
+                    }
+                }
+                LspCommand::LinkedEditingRange {
+                    request_id,
+                    uri,
+                    position,
+                } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_linked_editing_range(request_id, uri, position, &p)
+                            .await);
+                    } else {
+                        let _ = state.async_tx.send(AsyncMessage::LspLinkedEditingRanges {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            ranges: Err("LSP not initialized".to_string()),
+                        });
+                    }
+                }
+                LspCommand::OnTypeFormatting {
+                    request_id,
+                    uri,
+                    position,
+                    ch,
+                    tab_size,
+                    insert_spaces,
+                } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_on_type_formatting(
+                                request_id,
+                                uri,
+                                position,
+                                ch,
+                                tab_size,
+                                insert_spaces,
+                                &p
+                            )
+                            .await);
+                    } else {
+                        let _ = state.async_tx.send(AsyncMessage::LspOnTypeFormatting {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            edits: Err("LSP not initialized".to_string()),
+                        });
+                    }
+                }
+                LspCommand::DocumentLinks { request_id, uri } => {
+                    if initialized {
                         spawn_request!(state, pending, |s, p| s
-                            .handle_rename(request_id, uri, line, character, new_name, &p)
+                            .handle_document_links(request_id, uri, &p)
                             .await);
                     } else {
-                        tracing::trace!("LSP not initialized, cannot rename");
-                        let _ = state.async_tx.send(AsyncMessage::LspRename {
+                        let _ = state.async_tx.send(AsyncMessage::LspDocumentLinks {
                             request_id,
-                            result: Err("LSP not initialized".to_string()),
+                            uri: uri.as_str().to_string(),
+                            links: Vec::new(),
                         });
                     }
                 }
-                LspCommand::Hover {
-                    request_id,
-                    uri,
-                    line,
-                    character,
-                } => {
+                LspCommand::DocumentLinkResolve { request_id, link } => {
                     if initialized {
-                        tracing::info!("Processing Hover request for {}", uri.as_str());
                         spawn_request!(state, pending, |s, p| s
-                            .handle_hover(request_id, uri, line, character, &p)
+                            .handle_document_link_resolve(request_id, *link, &p)
                             .await);
                     } else {
-                        tracing::trace!("LSP not initialized, cannot get hover");
-                        let _ = state.async_tx.send(AsyncMessage::LspHover {
+                        let _ = state.async_tx.send(AsyncMessage::LspDocumentLinkResolved {
                             request_id,
-                            contents: String::new(),
-                            is_markdown: false,
-                            range: None,
+                            link: Err("LSP not initialized".to_string()),
                         });
                     }
                 }
-                LspCommand::References {
-                    request_id,
-                    uri,
-                    line,
-                    character,
-                } => {
+                LspCommand::DocumentColors { request_id, uri } => {
                     if initialized {
-                        tracing::info!("Processing References request for {}", uri.as_str());
                         spawn_request!(state, pending, |s, p| s
-                            .handle_references(request_id, uri, line, character, &p)
+                            .handle_document_colors(request_id, uri, &p)
                             .await);
                     } else {
-                        tracing::trace!("LSP not initialized, cannot get references");
-                        let _ = state.async_tx.send(AsyncMessage::LspReferences {
+                        let _ = state.async_tx.send(AsyncMessage::LspDocumentColors {
                             request_id,
-                            locations: Vec::new(),
+                            uri: uri.as_str().to_string(),
+                            colors: Vec::new(),
                         });
                     }
                 }
-                LspCommand::SignatureHelp {
+                LspCommand::ColorPresentations {
                     request_id,
                     uri,
-                    line,
-                    character,
+                    color,
+                    range,
                 } => {
                     if initialized {
-                        tracing::info!("Processing SignatureHelp request for {}", uri.as_str());
                         spawn_request!(state, pending, |s, p| s
-                            .handle_signature_help(request_id, uri, line, character, &p)
+                            .handle_color_presentations(request_id, uri, color, range, &p)
                             .await);
                     } else {
-                        tracing::trace!("LSP not initialized, cannot get signature help");
-                        let _ = state.async_tx.send(AsyncMessage::LspSignatureHelp {
+                        let _ = state.async_tx.send(AsyncMessage::LspColorPresentations {
                             request_id,
-                            signature_help: None,
+                            uri: uri.as_str().to_string(),
+                            presentations: Err("LSP not initialized".to_string()),
                         });
                     }
                 }
-                LspCommand::CodeActions {
-                    request_id,
-                    uri,
-                    start_line,
-                    start_char,
-                    end_line,
-                    end_char,
-                    diagnostics,
-                } => {
+                LspCommand::DocumentSymbols { request_id, uri } => {
                     if initialized {
-                        tracing::info!("Processing CodeActions request for {}", uri.as_str());
                         spawn_request!(state, pending, |s, p| s
-                            .handle_code_actions(
-                                request_id,
-                                uri,
-                                start_line,
-                                start_char,
-                                end_line,
-                                end_char,
-                                diagnostics,
-                                &p,
-                            )
+                            .handle_document_symbols(request_id, uri, &p)
                             .await);
                     } else {
-                        tracing::trace!("LSP not initialized, cannot get code actions");
-                        let _ = state.async_tx.send(AsyncMessage::LspCodeActions {
+                        let _ = state.async_tx.send(AsyncMessage::LspDocumentSymbols {
                             request_id,
-                            actions: Vec::new(),
+                            uri: uri.as_str().to_string(),
+                            symbols: Err("LSP not initialized".to_string()),
                         });
                     }
                 }
-                LspCommand::DocumentDiagnostic {
+                LspCommand::WillFileOperation {
                     request_id,
-                    uri,
-                    previous_result_id,
+                    operation,
                 } => {
                     if initialized {
-                        tracing::info!(
-                            "Processing DocumentDiagnostic request for {}",
-                            uri.as_str()
-                        );
                         spawn_request!(state, pending, |s, p| s
-                            .handle_document_diagnostic(request_id, uri, previous_result_id, &p)
+                            .handle_will_file_operation(request_id, operation, &p)
                             .await);
                     } else {
-                        tracing::trace!("LSP not initialized, cannot get document diagnostics");
-                        let _ = state.async_tx.send(AsyncMessage::LspPulledDiagnostics {
+                        let _ = state.async_tx.send(AsyncMessage::LspWillFileOperation {
                             request_id,
-                            uri: uri.as_str().to_string(),
-                            server_name: (*state.server_name).clone(),
-                            result_id: None,
-                            diagnostics: Vec::new(),
-                            unchanged: false,
+                            edit: Err("LSP not initialized".to_string()),
                         });
                     }
                 }
-                LspCommand::InlayHints {
-                    request_id,
-                    uri,
-                    start_line,
-                    start_char,
-                    end_line,
-                    end_char,
-                } => {
+                LspCommand::DidFileOperation { operation } => {
+                    if initialized {
+                        let _ = state.handle_did_file_operation(operation).await;
+                    } else {
+                        // Only servers that registered for file operations
+                        // are told, and registering needs initialization.
+                        tracing::trace!(
+                            "Dropping {} before initialization",
+                            operation.method(FileOperationPhase::Did)
+                        );
+                    }
+                }
+                LspCommand::WorkspaceSymbols { request_id, query } => {
                     if initialized {
-                        tracing::info!("Processing InlayHints request for {}", uri.as_str());
                         spawn_request!(state, pending, |s, p| s
-                            .handle_inlay_hints(
-                                request_id, uri, start_line, start_char, end_line, end_char, &p,
-                            )
+                            .handle_workspace_symbols(request_id, query, &p)
                             .await);
                     } else {
-                        tracing::trace!("LSP not initialized, cannot get inlay hints");
-                        let _ = state.async_tx.send(AsyncMessage::LspInlayHints {
+                        let _ = state.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
                             request_id,
-                            uri: uri.as_str().to_string(),
-                            hints: Vec::new(),
+                            symbols: Err("LSP not initialized".to_string()),
                         });
                     }
                 }
@@ -3784,6 +4660,23 @@ impl LspTask {
                     // Notification: inline so cancels reach the server promptly.
                     let _ = state.handle_cancel_request(request_id).await;
                 }
+                LspCommand::Hierarchy {
+                    request_id,
+                    request,
+                } => {
+                    if initialized {
+                        tracing::info!("Processing {} request", request.method());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_hierarchy(request_id, request, &p)
+                            .await);
+                    } else {
+                        let _ = state.async_tx.send(AsyncMessage::LspHierarchy {
+                            language: language_clone.clone(),
+                            request_id,
+                            result: Err("LSP not initialized".to_string()),
+                        });
+                    }
+                }
                 LspCommand::PluginRequest {
                     request_id,
                     method,
@@ -4037,6 +4930,29 @@ fn null_response(id: JsonRpcId) -> JsonRpcResponse {
     }
 }
 
+/// Deserialize the params of a server-to-client request.
+fn parse_request_params<T: serde::de::DeserializeOwned>(
+    params: Option<Value>,
+) -> Result<T, String> {
+    serde_json::from_value(params.unwrap_or(Value::Null)).map_err(|e| e.to_string())
+}
+
+/// JSON-RPC "Invalid params" error response for a server request we can't
+/// make sense of.
+fn invalid_params_response(id: JsonRpcId, method: &str, error: String) -> JsonRpcResponse {
+    tracing::warn!("Invalid params for server request {}: {}", method, error);
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        id,
+        result: None,
+        error: Some(JsonRpcError {
+            code: -32602,
+            message: format!("Invalid params for {}: {}", method, error),
+            data: None,
+        }),
+    }
+}
+
 /// Parse the `{type, message}` body common to `window/showMessage` and
 /// `window/logMessage`. Returns `None` for malformed params.
 fn parse_window_message(
@@ -4331,6 +5247,18 @@ async fn handle_message_dispatch(
                     });
                     null_response(request.id)
                 }
+                "workspace/codeLens/refresh" => {
+                    // Same as inlayHint/refresh: lenses such as reference
+                    // counts go stale when other files change.
+                    tracing::info!(
+                        "LSP ({}) requested code-lens refresh (workspace/codeLens/refresh)",
+                        language
+                    );
+                    let _ = async_tx.send(AsyncMessage::LspCodeLensRefresh {
+                        language: language.to_string(),
+                    });
+                    null_response(request.id)
+                }
                 "workspace/semanticTokens/refresh" => {
                     // Same idea as inlayHint/refresh, for semantic highlighting.
                     tracing::info!(
@@ -4375,6 +5303,71 @@ async fn handle_message_dispatch(
                         error: None,
                     }
                 }
+                "window/showMessageRequest" => {
+                    // The answer is whichever action the user picks, so the
+                    // response is written once they do; keep reading the
+                    // server meanwhile.
+                    match parse_request_params::<lsp_types::ShowMessageRequestParams>(
+                        request.params,
+                    ) {
+                        Ok(params) => {
+                            let (reply, answer) = oneshot::channel();
+                            let _ = async_tx.send(AsyncMessage::LspShowMessageRequest {
+                                language: language.to_string(),
+                                server_name: server_name.to_string(),
+                                params,
+                                reply,
+                            });
+                            let stdin_writer = Arc::clone(stdin_writer);
+                            let id = request.id;
+                            tokio::spawn(async move {
+                                // A dropped reply (editor shutting down) reads
+                                // as a dismissal: null.
+                                let chosen = answer.await.ok().flatten();
+                                let response = JsonRpcResponse {
+                                    jsonrpc: "2.0".to_string(),
+                                    id,
+                                    result: Some(serde_json::to_value(chosen).unwrap_or_default()),
+                                    error: None,
+                                };
+                                write_server_response(&stdin_writer, &response).await;
+                            });
+                            return Ok(());
+                        }
+                        Err(e) => invalid_params_response(request.id, &request.method, e),
+                    }
+                }
+                "window/showDocument" => {
+                    // Opening an external program waits for the user's
+                    // go-ahead, so this answer is deferred too.
+                    match parse_request_params::<lsp_types::ShowDocumentParams>(request.params) {
+                        Ok(params) => {
+                            let (reply, answer) = oneshot::channel();
+                            let _ = async_tx.send(AsyncMessage::LspShowDocument {
+                                language: language.to_string(),
+                                server_name: server_name.to_string(),
+                                params,
+                                reply,
+                            });
+                            let stdin_writer = Arc::clone(stdin_writer);
+                            let id = request.id;
+                            tokio::spawn(async move {
+                                let success = answer.await.unwrap_or(false);
+                                let response = JsonRpcResponse {
+                                    jsonrpc: "2.0".to_string(),
+                                    id,
+                                    result: Some(serde_json::json!(
+                                        lsp_types::ShowDocumentResult { success }
+                                    )),
+                                    error: None,
+                                };
+                                write_server_response(&stdin_writer, &response).await;
+                            });
+                            return Ok(());
+                        }
+                        Err(e) => invalid_params_response(request.id, &request.method, e),
+                    }
+                }
                 _ => {
                     // For unknown methods, notify plugins and return null to acknowledge receipt
                     tracing::debug!("Server request for plugins: {}", request.method);
@@ -4389,24 +5382,36 @@ async fn handle_message_dispatch(
             };
 
             // Write response directly to stdin (avoids deadlock when main loop is waiting for LSP response)
-            let json = serde_json::to_string(&response)
-                .map_err(|e| format!("Failed to serialize response: {}", e))?;
-            let message = format!("Content-Length: {}\r\n\r\n{}", json.len(), json);
-
-            let mut stdin = stdin_writer.lock().await;
-            use tokio::io::AsyncWriteExt;
-            if let Err(e) = stdin.write_all(message.as_bytes()).await {
-                tracing::error!("Failed to write server response: {}", e);
-            }
-            if let Err(e) = stdin.flush().await {
-                tracing::error!("Failed to flush server response: {}", e);
-            }
-            tracing::trace!("Sent response to server request id={}", response.id);
+            write_server_response(stdin_writer, &response).await;
         }
     }
     Ok(())
 }
 
+/// Write the response to a server-to-client request.
+async fn write_server_response(
+    stdin_writer: &Arc<tokio::sync::Mutex<ChildStdin>>,
+    response: &JsonRpcResponse,
+) {
+    let json = match serde_json::to_string(response) {
+        Ok(json) => json,
+        Err(e) => {
+            tracing::error!("Failed to serialize server response: {}", e);
+            return;
+        }
+    };
+    let message = format!("Content-Length: {}\r\n\r\n{}", json.len(), json);
+
+    let mut stdin = stdin_writer.lock().await;
+    if let Err(e) = stdin.write_all(message.as_bytes()).await {
+        tracing::error!("Failed to write server response: {}", e);
+    }
+    if let Err(e) = stdin.flush().await {
+        tracing::error!("Failed to flush server response: {}", e);
+    }
+    tracing::trace!("Sent response to server request id={}", response.id);
+}
+
 /// Standalone function to handle notifications (for reader task)
 #[allow(clippy::let_underscore_must_use)] // async_tx.send() is best-effort; receiver drop means editor shutdown
 async fn handle_notification_dispatch(
@@ -4908,6 +5913,16 @@ impl LspHandle {
             .map_err(|_| "Failed to send implementation command".to_string())
     }
 
+    /// Request a call / type hierarchy step
+    pub fn hierarchy(&self, request_id: u64, request: HierarchyRequest) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::Hierarchy {
+                request_id,
+                request,
+            })
+            .map_err(|_| "Failed to send hierarchy command".to_string())
+    }
+
     /// Request rename
     pub fn rename(
         &self,
@@ -5158,6 +6173,162 @@ impl LspHandle {
             .map_err(|_| "Failed to send inlay_hints command".to_string())
     }
 
+    /// Request code lenses for a document
+    pub fn code_lens(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::CodeLens { request_id, uri })
+            .map_err(|_| "Failed to send code_lens command".to_string())
+    }
+
+    /// Resolve a code lens to get its command (codeLens/resolve)
+    pub fn code_lens_resolve(
+        &self,
+        request_id: u64,
+        lens: lsp_types::CodeLens,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::CodeLensResolve {
+                request_id,
+                lens: Box::new(lens),
+            })
+            .map_err(|_| "Failed to send code_lens_resolve command".to_string())
+    }
+
+    /// Request selection ranges at the given positions
+    pub fn selection_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        positions: Vec<lsp_types::Position>,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::SelectionRange {
+                request_id,
+                uri,
+                positions,
+            })
+            .map_err(|_| "Failed to send selection_range command".to_string())
+    }
+
+    /// Request the ranges linked to `position`, such as a matching tag name
+    pub fn linked_editing_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        position: lsp_types::Position,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::LinkedEditingRange {
+                request_id,
+                uri,
+                position,
+            })
+            .map_err(|_| "Failed to send linked_editing_range command".to_string())
+    }
+
+    /// Request the formatting edits for typing `ch` at `position`
+    pub fn on_type_formatting(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        position: lsp_types::Position,
+        ch: String,
+        tab_size: u32,
+        insert_spaces: bool,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::OnTypeFormatting {
+                request_id,
+                uri,
+                position,
+                ch,
+                tab_size,
+                insert_spaces,
+            })
+            .map_err(|_| "Failed to send on_type_formatting command".to_string())
+    }
+
+    /// Request the links of a document
+    pub fn document_links(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentLinks { request_id, uri })
+            .map_err(|_| "Failed to send document_links command".to_string())
+    }
+
+    /// Resolve a document link to get its target (documentLink/resolve)
+    pub fn document_link_resolve(
+        &self,
+        request_id: u64,
+        link: lsp_types::DocumentLink,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentLinkResolve {
+                request_id,
+                link: Box::new(link),
+            })
+            .map_err(|_| "Failed to send document_link_resolve command".to_string())
+    }
+
+    /// Request the colour literals of a document
+    pub fn document_colors(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentColors { request_id, uri })
+            .map_err(|_| "Failed to send document_colors command".to_string())
+    }
+
+    /// Request the presentations of `color` for the literal at `range`
+    pub fn color_presentations(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        color: lsp_types::Color,
+        range: lsp_types::Range,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::ColorPresentations {
+                request_id,
+                uri,
+                color,
+                range,
+            })
+            .map_err(|_| "Failed to send color_presentations command".to_string())
+    }
+
+    /// Request the symbols of a document
+    pub fn document_symbols(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentSymbols { request_id, uri })
+            .map_err(|_| "Failed to send document_symbols command".to_string())
+    }
+
+    /// Search symbols across the workspace
+    pub fn workspace_symbols(&self, request_id: u64, query: String) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::WorkspaceSymbols { request_id, query })
+            .map_err(|_| "Failed to send workspace_symbols command".to_string())
+    }
+
+    /// Ask for the edits to make before `operation` touches the disk
+    pub fn will_file_operation(
+        &self,
+        request_id: u64,
+        operation: FileOperation,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::WillFileOperation {
+                request_id,
+                operation,
+            })
+            .map_err(|_| "Failed to send will_file_operation command".to_string())
+    }
+
+    /// Notify that `operation` has happened on disk
+    pub fn did_file_operation(&self, operation: FileOperation) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DidFileOperation { operation })
+            .map_err(|_| "Failed to send did_file_operation command".to_string())
+    }
+
     /// Request folding ranges for a document
     pub fn folding_ranges(&self, request_id: u64, uri: Uri) -> Result<(), String> {
         self.command_tx
@@ -5958,6 +7129,23 @@ mod tests {
         );
     }
 
+    /// Malformed params get an "Invalid params" error instead of a result.
+    #[test]
+    fn malformed_show_document_params_are_rejected() {
+        let error = parse_request_params::<lsp_types::ShowDocumentParams>(Some(
+            serde_json::json!({ "external": true }),
+        ))
+        .expect_err("uri is required");
+        let json = serde_json::to_string(&invalid_params_response(
+            JsonRpcId::Number(7),
+            "window/showDocument",
+            error,
+        ))
+        .expect("serialize error response");
+        assert!(json.contains(r#""code":-32602"#), "got: {json}");
+        assert!(!json.contains(r#""result""#), "got: {json}");
+    }
+
     #[test]
     fn test_json_rpc_message_deserialization_response() {
         let json = r#"{"jsonrpc":"2.0","id":1,"result":{"success":true}}"#;
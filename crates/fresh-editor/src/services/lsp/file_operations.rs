@@ -0,0 +1,304 @@
+//! Workspace file operations (`workspace/will*Files` / `workspace/did*Files`).
+//!
+//! Servers say which creates, renames and deletes they want to hear about
+//! with glob filters, either in `initialize` (`workspace.fileOperations`) or
+//! by registering the methods later. The file explorer asks the interested
+//! servers for a `WorkspaceEdit` before it touches the disk (the `will*`
+//! requests) and tells them once it has (the `did*` notifications).
+
+use std::path::Path;
+
+use lsp_types::{
+    CreateFilesParams, DeleteFilesParams, FileOperationFilter, FileOperationPatternKind,
+    FileOperationRegistrationOptions, RenameFilesParams, WorkspaceFileOperationsServerCapabilities,
+};
+
+use crate::primitives::glob_match::path_glob_matches;
+
+/// Whether a file operation is announced before or after it happens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileOperationPhase {
+    /// `workspace/will*Files`: a request answered with a `WorkspaceEdit`.
+    Will,
+    /// `workspace/did*Files`: a notification.
+    Did,
+}
+
+/// A batch of files going through one explorer operation, in the shape the
+/// protocol sends it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileOperation {
+    Create(CreateFilesParams),
+    Rename(RenameFilesParams),
+    Delete(DeleteFilesParams),
+}
+
+impl FileOperation {
+    /// The protocol method for this operation in `phase`.
+    pub fn method(&self, phase: FileOperationPhase) -> &'static str {
+        use FileOperationPhase::{Did, Will};
+        match (self, phase) {
+            (FileOperation::Create(_), Will) => "workspace/willCreateFiles",
+            (FileOperation::Create(_), Did) => "workspace/didCreateFiles",
+            (FileOperation::Rename(_), Will) => "workspace/willRenameFiles",
+            (FileOperation::Rename(_), Did) => "workspace/didRenameFiles",
+            (FileOperation::Delete(_), Will) => "workspace/willDeleteFiles",
+            (FileOperation::Delete(_), Did) => "workspace/didDeleteFiles",
+        }
+    }
+
+    /// Whether the batch carries no files.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            FileOperation::Create(params) => params.files.is_empty(),
+            FileOperation::Rename(params) => params.files.is_empty(),
+            FileOperation::Delete(params) => params.files.is_empty(),
+        }
+    }
+}
+
+/// The file-operation filters a server registered, one list per method;
+/// `None` when the server isn't interested in that method at all.
+#[derive(Debug, Clone, Default)]
+pub struct FileOperationFilters {
+    pub will_create: Option<Vec<FileOperationFilter>>,
+    pub did_create: Option<Vec<FileOperationFilter>>,
+    pub will_rename: Option<Vec<FileOperationFilter>>,
+    pub did_rename: Option<Vec<FileOperationFilter>>,
+    pub will_delete: Option<Vec<FileOperationFilter>>,
+    pub did_delete: Option<Vec<FileOperationFilter>>,
+}
+
+impl FileOperationFilters {
+    /// Read the filters a server advertised in its `initialize` result.
+    pub fn from_server(caps: Option<&WorkspaceFileOperationsServerCapabilities>) -> Self {
+        let Some(caps) = caps else {
+            return Self::default();
+        };
+        let filters = |opts: &Option<FileOperationRegistrationOptions>| {
+            opts.as_ref().map(|opts| opts.filters.clone())
+        };
+        Self {
+            will_create: filters(&caps.will_create),
+            did_create: filters(&caps.did_create),
+            will_rename: filters(&caps.will_rename),
+            did_rename: filters(&caps.did_rename),
+            will_delete: filters(&caps.will_delete),
+            did_delete: filters(&caps.did_delete),
+        }
+    }
+
+    /// Apply a dynamic (un)registration of one of the file-operation
+    /// methods. Returns `false` for any other method.
+    pub fn apply_registration(
+        &mut self,
+        method: &str,
+        register_options: Option<&serde_json::Value>,
+        register: bool,
+    ) -> bool {
+        let slot = match method {
+            "workspace/willCreateFiles" => &mut self.will_create,
+            "workspace/didCreateFiles" => &mut self.did_create,
+            "workspace/willRenameFiles" => &mut self.will_rename,
+            "workspace/didRenameFiles" => &mut self.did_rename,
+            "workspace/willDeleteFiles" => &mut self.will_delete,
+            "workspace/didDeleteFiles" => &mut self.did_delete,
+            _ => return false,
+        };
+        *slot = register.then(|| {
+            register_options
+                .and_then(|opts| {
+                    serde_json::from_value::<FileOperationRegistrationOptions>(opts.clone()).ok()
+                })
+                .map(|opts| opts.filters)
+                .unwrap_or_default()
+        });
+        true
+    }
+
+    /// Whether the server registered for any file operation.
+    pub fn any(&self) -> bool {
+        [
+            &self.will_create,
+            &self.did_create,
+            &self.will_rename,
+            &self.did_rename,
+            &self.will_delete,
+            &self.did_delete,
+        ]
+        .iter()
+        .any(|filters| filters.is_some())
+    }
+
+    fn filters(
+        &self,
+        operation: &FileOperation,
+        phase: FileOperationPhase,
+    ) -> Option<&[FileOperationFilter]> {
+        use FileOperationPhase::{Did, Will};
+        let filters = match (operation, phase) {
+            (FileOperation::Create(_), Will) => &self.will_create,
+            (FileOperation::Create(_), Did) => &self.did_create,
+            (FileOperation::Rename(_), Will) => &self.will_rename,
+            (FileOperation::Rename(_), Did) => &self.did_rename,
+            (FileOperation::Delete(_), Will) => &self.will_delete,
+            (FileOperation::Delete(_), Did) => &self.did_delete,
+        };
+        filters.as_deref()
+    }
+
+    /// Whether the server wants to hear about `operation` in `phase`, given
+    /// the host paths it touches and whether each is a directory. A rename
+    /// is matched on its old paths.
+    pub fn wants(
+        &self,
+        operation: &FileOperation,
+        phase: FileOperationPhase,
+        paths: &[(&Path, bool)],
+    ) -> bool {
+        self.filters(operation, phase).is_some_and(|filters| {
+            paths.iter().any(|&(path, is_dir)| {
+                filters
+                    .iter()
+                    .any(|filter| filter_matches(filter, path, is_dir))
+            })
+        })
+    }
+}
+
+/// Whether a `file://` path matches one registered filter.
+fn filter_matches(filter: &FileOperationFilter, path: &Path, is_dir: bool) -> bool {
+    if filter.scheme.as_deref().is_some_and(|s| s != "file") {
+        return false;
+    }
+    let pattern = &filter.pattern;
+    match pattern.matches {
+        Some(FileOperationPatternKind::File) if is_dir => return false,
+        Some(FileOperationPatternKind::Folder) if !is_dir => return false,
+        _ => {}
+    }
+    let ignore_case = pattern
+        .options
+        .as_ref()
+        .and_then(|o| o.ignore_case)
+        .unwrap_or(false);
+    let mut path = path.to_string_lossy().replace('\\', "/");
+    let mut glob = pattern.glob.clone();
+    if ignore_case {
+        path = path.to_lowercase();
+        glob = glob.to_lowercase();
+    }
+    expand_braces(&glob)
+        .iter()
+        .any(|glob| path_glob_matches(glob, &path))
+}
+
+/// Expand `{a,b}` groups into one glob per alternative (`**/*.{ts,js}` →
+/// `**/*.ts`, `**/*.js`). Groups may nest; an unclosed `{` is literal.
+fn expand_braces(glob: &str) -> Vec<String> {
+    let Some(open) = glob.find('{') else {
+        return vec![glob.to_string()];
+    };
+    let mut depth = 0;
+    let mut close = None;
+    let mut commas = Vec::new();
+    for (i, c) in glob[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(open + i);
+                    break;
+                }
+            }
+            ',' if depth == 1 => commas.push(open + i),
+            _ => {}
+        }
+    }
+    let Some(close) = close else {
+        return vec![glob.to_string()];
+    };
+    let (prefix, suffix) = (&glob[..open], &glob[close + 1..]);
+    let mut bounds = vec![open];
+    bounds.extend(commas);
+    bounds.push(close);
+    bounds
+        .windows(2)
+        .flat_map(|w| expand_braces(&format!("{prefix}{}{suffix}", &glob[w[0] + 1..w[1]])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{FileOperationPattern, FileOperationPatternOptions, FileRename};
+
+    fn filter(glob: &str, matches: Option<FileOperationPatternKind>) -> FileOperationFilter {
+        FileOperationFilter {
+            scheme: Some("file".to_string()),
+            pattern: FileOperationPattern {
+                glob: glob.to_string(),
+                matches,
+                options: None,
+            },
+        }
+    }
+
+    fn rename() -> FileOperation {
+        FileOperation::Rename(RenameFilesParams {
+            files: vec![FileRename {
+                old_uri: "file:///p/src/a.ts".to_string(),
+                new_uri: "file:///p/src/b.ts".to_string(),
+            }],
+        })
+    }
+
+    #[test]
+    fn braces_expand_to_each_alternative() {
+        assert_eq!(expand_braces("**/*.{ts,tsx}"), vec!["**/*.ts", "**/*.tsx"]);
+        assert_eq!(
+            expand_braces("{src,lib}/*.{rs,toml}"),
+            vec!["src/*.rs", "src/*.toml", "lib/*.rs", "lib/*.toml"]
+        );
+        assert_eq!(expand_braces("a{b"), vec!["a{b"]);
+    }
+
+    #[test]
+    fn filters_match_globs_and_kinds() {
+        let ts = filter("**/*.{ts,js}", Some(FileOperationPatternKind::File));
+        assert!(filter_matches(&ts, Path::new("/p/src/a.ts"), false));
+        assert!(!filter_matches(&ts, Path::new("/p/src/a.rs"), false));
+        assert!(!filter_matches(&ts, Path::new("/p/src/dir.ts"), true));
+
+        let folders = filter("**", Some(FileOperationPatternKind::Folder));
+        assert!(filter_matches(&folders, Path::new("/p/src"), true));
+        assert!(!filter_matches(&folders, Path::new("/p/src/a.ts"), false));
+
+        let mut upper = filter("**/*.TS", None);
+        assert!(!filter_matches(&upper, Path::new("/p/a.ts"), false));
+        upper.pattern.options = Some(FileOperationPatternOptions {
+            ignore_case: Some(true),
+        });
+        assert!(filter_matches(&upper, Path::new("/p/a.ts"), false));
+    }
+
+    #[test]
+    fn registration_sets_and_clears_one_method() {
+        let mut filters = FileOperationFilters::default();
+        assert!(!filters.any());
+        let opts = serde_json::json!({
+            "filters": [{ "pattern": { "glob": "**/*.ts" } }]
+        });
+        assert!(filters.apply_registration("workspace/willRenameFiles", Some(&opts), true));
+        assert!(filters.any());
+
+        let path = Path::new("/p/src/a.ts");
+        assert!(filters.wants(&rename(), FileOperationPhase::Will, &[(path, false)]));
+        assert!(!filters.wants(&rename(), FileOperationPhase::Did, &[(path, false)]));
+
+        assert!(filters.apply_registration("workspace/willRenameFiles", None, false));
+        assert!(!filters.wants(&rename(), FileOperationPhase::Will, &[(path, false)]));
+        assert!(!filters.apply_registration("workspace/symbol", None, true));
+    }
+}
@@ -0,0 +1,244 @@
+//! Call and type hierarchy requests.
+//!
+//! Both hierarchies use the same two-step protocol: a `prepare` request
+//! resolves the symbol at a position to one or more items, and each item is
+//! then expanded one level at a time (incoming / outgoing calls, super /
+//! subtypes). Items are kept as the server's raw JSON so they can be sent
+//! back verbatim on expansion — servers stash private state in `data`, and
+//! lsp-types' `TypeHierarchyItem` rejects spec-conformant `tags` arrays.
+
+use lsp_types::{Position, Range, TextDocumentIdentifier, TextDocumentPositionParams, Uri};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Which hierarchy a request belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyKind {
+    Call,
+    Type,
+}
+
+impl HierarchyKind {
+    /// Parse the name used by commands, hooks and the plugin API.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "call" => Some(Self::Call),
+            "type" => Some(Self::Type),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Call => "call",
+            Self::Type => "type",
+        }
+    }
+
+    fn prepare_method(self) -> &'static str {
+        match self {
+            Self::Call => "textDocument/prepareCallHierarchy",
+            Self::Type => "textDocument/prepareTypeHierarchy",
+        }
+    }
+}
+
+/// Direction in which a hierarchy item is expanded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyDirection {
+    /// Callers of the item (`callHierarchy/incomingCalls`)
+    Incoming,
+    /// Callees of the item (`callHierarchy/outgoingCalls`)
+    Outgoing,
+    /// Types the item derives from (`typeHierarchy/supertypes`)
+    Supertypes,
+    /// Types deriving from the item (`typeHierarchy/subtypes`)
+    Subtypes,
+}
+
+impl HierarchyDirection {
+    /// Parse the name used by the plugin API.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "incoming" => Some(Self::Incoming),
+            "outgoing" => Some(Self::Outgoing),
+            "supertypes" => Some(Self::Supertypes),
+            "subtypes" => Some(Self::Subtypes),
+            _ => None,
+        }
+    }
+
+    pub fn kind(self) -> HierarchyKind {
+        match self {
+            Self::Incoming | Self::Outgoing => HierarchyKind::Call,
+            Self::Supertypes | Self::Subtypes => HierarchyKind::Type,
+        }
+    }
+
+    fn method(self) -> &'static str {
+        match self {
+            Self::Incoming => "callHierarchy/incomingCalls",
+            Self::Outgoing => "callHierarchy/outgoingCalls",
+            Self::Supertypes => "typeHierarchy/supertypes",
+            Self::Subtypes => "typeHierarchy/subtypes",
+        }
+    }
+}
+
+/// One hierarchy request, either step of the protocol.
+#[derive(Debug, Clone)]
+pub enum HierarchyRequest {
+    /// Resolve the symbol at a position to hierarchy items
+    Prepare {
+        kind: HierarchyKind,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    },
+    /// Fetch the items one level away from a previously returned item
+    Expand {
+        direction: HierarchyDirection,
+        item: Value,
+    },
+}
+
+impl HierarchyRequest {
+    pub fn method(&self) -> &'static str {
+        match self {
+            Self::Prepare { kind, .. } => kind.prepare_method(),
+            Self::Expand { direction, .. } => direction.method(),
+        }
+    }
+
+    pub fn params(&self) -> Value {
+        match self {
+            Self::Prepare {
+                uri,
+                line,
+                character,
+                ..
+            } => serde_json::to_value(TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position {
+                    line: *line,
+                    character: *character,
+                },
+            })
+            .unwrap_or(Value::Null),
+            Self::Expand { item, .. } => serde_json::json!({ "item": item }),
+        }
+    }
+
+    /// Pull the items out of the response. Call expansions wrap each item
+    /// in an `{ from | to, fromRanges }` record; everything else is a bare
+    /// item array. `null` means "nothing here".
+    pub fn items_from_response(&self, result: Value) -> Vec<Value> {
+        let Value::Array(entries) = result else {
+            return Vec::new();
+        };
+        let wrapper = match self {
+            Self::Expand {
+                direction: HierarchyDirection::Incoming,
+                ..
+            } => Some("from"),
+            Self::Expand {
+                direction: HierarchyDirection::Outgoing,
+                ..
+            } => Some("to"),
+            _ => None,
+        };
+        match wrapper {
+            Some(field) => entries
+                .into_iter()
+                .filter_map(|mut entry| entry.get_mut(field).map(Value::take))
+                .collect(),
+            None => entries,
+        }
+    }
+}
+
+/// The fields of a hierarchy item the editor displays and navigates to.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HierarchyItemHeader {
+    pub name: String,
+    pub kind: u32,
+    #[serde(default)]
+    pub detail: Option<String>,
+    pub uri: Uri,
+    pub selection_range: Range,
+}
+
+impl HierarchyItemHeader {
+    pub fn from_item(item: &Value) -> Option<Self> {
+        serde_json::from_value(item.clone()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn item(name: &str) -> Value {
+        json!({
+            "name": name,
+            "kind": 12,
+            "tags": [1],
+            "uri": "file:///w/src/main.rs",
+            "range": {"start": {"line": 3, "character": 0}, "end": {"line": 9, "character": 1}},
+            "selectionRange": {"start": {"line": 3, "character": 3}, "end": {"line": 3, "character": 7}},
+            "data": {"opaque": true},
+        })
+    }
+
+    #[test]
+    fn call_expansions_unwrap_from_and_to() {
+        let incoming = HierarchyRequest::Expand {
+            direction: HierarchyDirection::Incoming,
+            item: item("main"),
+        };
+        let response = json!([{ "from": item("caller"), "fromRanges": [] }]);
+        let items = incoming.items_from_response(response);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["name"], "caller");
+
+        let outgoing = HierarchyRequest::Expand {
+            direction: HierarchyDirection::Outgoing,
+            item: item("main"),
+        };
+        let response = json!([{ "to": item("callee"), "fromRanges": [] }]);
+        assert_eq!(outgoing.items_from_response(response)[0]["name"], "callee");
+    }
+
+    #[test]
+    fn prepare_and_type_responses_are_bare_items() {
+        let subtypes = HierarchyRequest::Expand {
+            direction: HierarchyDirection::Subtypes,
+            item: item("Shape"),
+        };
+        assert_eq!(subtypes.method(), "typeHierarchy/subtypes");
+        let items = subtypes.items_from_response(json!([item("Circle"), item("Square")]));
+        assert_eq!(items.len(), 2);
+        assert!(subtypes.items_from_response(Value::Null).is_empty());
+    }
+
+    #[test]
+    fn expand_sends_item_back_verbatim() {
+        let raw = item("main");
+        let request = HierarchyRequest::Expand {
+            direction: HierarchyDirection::Outgoing,
+            item: raw.clone(),
+        };
+        assert_eq!(request.params(), json!({ "item": raw }));
+    }
+
+    #[test]
+    fn header_reads_items_with_tag_arrays() {
+        let header = HierarchyItemHeader::from_item(&item("main")).unwrap();
+        assert_eq!(header.name, "main");
+        assert_eq!(header.kind, 12);
+        assert_eq!(header.selection_range.start.line, 3);
+        assert_eq!(header.selection_range.start.character, 3);
+    }
+}
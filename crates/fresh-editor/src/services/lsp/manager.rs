@@ -8,6 +8,9 @@
 
 use crate::services::async_bridge::AsyncBridge;
 use crate::services::lsp::async_handler::LspHandle;
+use crate::services::lsp::file_operations::{
+    FileOperation, FileOperationFilters, FileOperationPhase,
+};
 use crate::types::{FeatureFilter, LspFeature, LspServerConfig};
 use lsp_types::{SemanticTokensLegend, Uri};
 use std::collections::HashMap;
@@ -201,6 +204,8 @@ pub struct ServerCapabilitySummary {
     pub definition: bool,
     pub implementation: bool,
     pub references: bool,
+    pub call_hierarchy: bool,
+    pub type_hierarchy: bool,
     pub document_formatting: bool,
     pub document_range_formatting: bool,
     pub rename: bool,
@@ -214,9 +219,18 @@ pub struct ServerCapabilitySummary {
     pub document_highlight: bool,
     pub code_action: bool,
     pub code_action_resolve: bool,
+    pub code_lens: bool,
+    pub code_lens_resolve: bool,
+    pub selection_range: bool,
+    pub linked_editing_range: bool,
+    pub on_type_formatting_trigger_characters: Vec<String>,
+    pub document_link: bool,
+    pub document_link_resolve: bool,
+    pub document_color: bool,
     pub document_symbols: bool,
     pub workspace_symbols: bool,
     pub diagnostics: bool,
+    pub file_operations: FileOperationFilters,
 }
 
 impl ServerCapabilitySummary {
@@ -242,6 +256,12 @@ impl ServerCapabilitySummary {
     ) -> bool {
         use lsp_types::SemanticTokensFullOptions;
 
+        if self
+            .file_operations
+            .apply_registration(method, register_options, register)
+        {
+            return true;
+        }
         match method {
             "textDocument/hover" => self.hover = register,
             "textDocument/completion" => {
@@ -271,6 +291,8 @@ impl ServerCapabilitySummary {
             "textDocument/definition" => self.definition = register,
             "textDocument/implementation" => self.implementation = register,
             "textDocument/references" => self.references = register,
+            "textDocument/prepareCallHierarchy" => self.call_hierarchy = register,
+            "textDocument/prepareTypeHierarchy" => self.type_hierarchy = register,
             "textDocument/formatting" => self.document_formatting = register,
             "textDocument/rangeFormatting" => self.document_range_formatting = register,
             "textDocument/rename" => self.rename = register,
@@ -291,6 +313,42 @@ impl ServerCapabilitySummary {
                     self.code_action_resolve = false;
                 }
             }
+            "textDocument/codeLens" => {
+                self.code_lens = register;
+                self.code_lens_resolve = register
+                    && register_options
+                        .and_then(|opts| opts.get("resolveProvider"))
+                        .and_then(serde_json::Value::as_bool)
+                        .unwrap_or(false);
+            }
+            "textDocument/selectionRange" => self.selection_range = register,
+            "textDocument/linkedEditingRange" => self.linked_editing_range = register,
+            "textDocument/onTypeFormatting" => {
+                self.on_type_formatting_trigger_characters.clear();
+                if register {
+                    let opts = register_options.and_then(|opts| {
+                        serde_json::from_value::<lsp_types::DocumentOnTypeFormattingOptions>(
+                            opts.clone(),
+                        )
+                        .ok()
+                    });
+                    if let Some(opts) = opts {
+                        self.on_type_formatting_trigger_characters
+                            .push(opts.first_trigger_character);
+                        self.on_type_formatting_trigger_characters
+                            .extend(opts.more_trigger_character.unwrap_or_default());
+                    }
+                }
+            }
+            "textDocument/documentLink" => {
+                self.document_link = register;
+                self.document_link_resolve = register
+                    && register_options
+                        .and_then(|opts| opts.get("resolveProvider"))
+                        .and_then(serde_json::Value::as_bool)
+                        .unwrap_or(false);
+            }
+            "textDocument/documentColor" => self.document_color = register,
             "textDocument/documentSymbol" => self.document_symbols = register,
             "workspace/symbol" => self.workspace_symbols = register,
             "textDocument/diagnostic" => self.diagnostics = register,
@@ -374,6 +432,8 @@ impl ServerHandle {
             LspFeature::Definition => self.capabilities.definition,
             LspFeature::Implementation => self.capabilities.implementation,
             LspFeature::References => self.capabilities.references,
+            LspFeature::CallHierarchy => self.capabilities.call_hierarchy,
+            LspFeature::TypeHierarchy => self.capabilities.type_hierarchy,
             LspFeature::Format => {
                 self.capabilities.document_formatting || self.capabilities.document_range_formatting
             }
@@ -386,9 +446,19 @@ impl ServerHandle {
             }
             LspFeature::DocumentHighlight => self.capabilities.document_highlight,
             LspFeature::CodeAction => self.capabilities.code_action,
+            LspFeature::CodeLens => self.capabilities.code_lens,
+            LspFeature::SelectionRange => self.capabilities.selection_range,
+            LspFeature::LinkedEditingRange => self.capabilities.linked_editing_range,
+            LspFeature::OnTypeFormatting => !self
+                .capabilities
+                .on_type_formatting_trigger_characters
+                .is_empty(),
+            LspFeature::DocumentLink => self.capabilities.document_link,
+            LspFeature::DocumentColor => self.capabilities.document_color,
             LspFeature::DocumentSymbols => self.capabilities.document_symbols,
             LspFeature::WorkspaceSymbols => self.capabilities.workspace_symbols,
             LspFeature::Diagnostics => self.capabilities.diagnostics,
+            LspFeature::FileOperations => self.capabilities.file_operations.any(),
         }
     }
 }
@@ -702,6 +772,19 @@ impl LspManager {
         })
     }
 
+    /// Check if a character triggers on-type formatting for any running
+    /// language server.
+    pub fn is_on_type_formatting_trigger_char(&self, ch: char, language: &str) -> bool {
+        let ch_str = ch.to_string();
+        self.get_handles(language).iter().any(|sh| {
+            sh.feature_filter.allows(LspFeature::OnTypeFormatting)
+                && sh
+                    .capabilities
+                    .on_type_formatting_trigger_characters
+                    .contains(&ch_str)
+        })
+    }
+
     /// Try to spawn an LSP server, checking auto_start configuration
     ///
     /// This is the main entry point for spawning LSP servers on file open.
@@ -1061,6 +1144,24 @@ impl LspManager {
             .collect()
     }
 
+    /// Get every running handle whose file-operation filters take in one of
+    /// `paths` (host path, is a directory) for `operation` in `phase`.
+    pub fn handles_for_file_operation(
+        &self,
+        operation: &FileOperation,
+        phase: FileOperationPhase,
+        paths: &[(&Path, bool)],
+    ) -> Vec<&ServerHandle> {
+        self.handles_for_workspace_feature(LspFeature::FileOperations)
+            .into_iter()
+            .filter(|sh| {
+                sh.capabilities
+                    .file_operations
+                    .wants(operation, phase, paths)
+            })
+            .collect()
+    }
+
     /// Get all mutable handles for a language that allow a given feature.
     /// Like `handle_for_feature_mut`, also checks per-server capabilities.
     /// Includes both per-language and universal handles.
@@ -1076,6 +1177,16 @@ impl LspManager {
             .collect()
     }
 
+    /// Get every running handle, whatever its language, that allows a given
+    /// feature and reported the capability. For workspace-wide requests such
+    /// as `workspace/symbol` that are not tied to one document.
+    pub fn handles_for_workspace_feature(&self, feature: LspFeature) -> Vec<&ServerHandle> {
+        self.handles
+            .iter()
+            .filter(|sh| sh.feature_filter.allows(feature) && sh.has_capability(feature))
+            .collect()
+    }
+
     /// Consult the spawn throttle for `language` and, on `Allow`, record
     /// the attempt.
     ///
@@ -2334,6 +2445,8 @@ mod tests {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
         languages.insert(
@@ -2360,6 +2473,8 @@ mod tests {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
         languages.insert(
@@ -2386,6 +2501,8 @@ mod tests {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
         languages
@@ -2453,6 +2570,8 @@ mod tests {
                 word_characters: None,
                 indentation_guide: None,
                 indent: None,
+                linked_editing: true,
+                format_on_type: false,
             },
         );
 
@@ -2570,6 +2689,8 @@ mod tests {
             word_characters: None,
             indentation_guide: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
         };
         languages.insert(
             "c".to_string(),
@@ -2837,6 +2958,41 @@ mod tests {
         assert!(caps.completion_trigger_characters.is_empty());
     }
 
+    #[test]
+    fn dynamic_registration_parses_on_type_formatting_triggers() {
+        let mut caps = ServerCapabilitySummary::default();
+        let opts = serde_json::json!({
+            "documentSelector": [{ "language": "rust" }],
+            "firstTriggerCharacter": "}",
+            "moreTriggerCharacter": [";", "\n"],
+        });
+        let recognized =
+            caps.apply_dynamic_registration("textDocument/onTypeFormatting", Some(&opts), true);
+        assert!(recognized);
+        assert_eq!(
+            caps.on_type_formatting_trigger_characters,
+            vec!["}", ";", "\n"]
+        );
+
+        caps.apply_dynamic_registration("textDocument/onTypeFormatting", None, false);
+        assert!(caps.on_type_formatting_trigger_characters.is_empty());
+    }
+
+    #[test]
+    fn dynamic_registration_parses_code_lens_resolve_provider() {
+        let mut caps = ServerCapabilitySummary::default();
+        let opts = serde_json::json!({ "resolveProvider": true });
+        let recognized =
+            caps.apply_dynamic_registration("textDocument/codeLens", Some(&opts), true);
+        assert!(recognized);
+        assert!(caps.code_lens);
+        assert!(caps.code_lens_resolve);
+
+        caps.apply_dynamic_registration("textDocument/codeLens", None, false);
+        assert!(!caps.code_lens);
+        assert!(!caps.code_lens_resolve);
+    }
+
     #[test]
     fn dynamic_registration_parses_semantic_tokens_legend() {
         let mut caps = ServerCapabilitySummary::default();
@@ -77,6 +77,12 @@
 //! - **`diagnostics`**: Converts LSP diagnostics to editor overlays (colored
 //!   underlines for errors, warnings, etc.).
 //!
+//! - **`hierarchy`**: Request/response shapes for call and type hierarchies
+//!   (`prepare*` plus one-level expansion), shared by commands and plugins.
+//!
+//! - **`file_operations`**: The `workspace/will*Files` / `did*Files` filters
+//!   servers register, and the batches the file explorer sends them.
+//!
 //! # Message Flow
 //!
 //! ## Outgoing Requests (Editor → Server)
@@ -116,6 +122,8 @@
 
 pub mod async_handler;
 pub mod diagnostics;
+pub mod file_operations;
+pub mod hierarchy;
 pub mod manager;
 pub mod semantic_tokens;
 
@@ -149,6 +149,13 @@ pub struct GrammarManifestConfig {
     /// Must be unique across all grammars; collisions are rejected with a warning.
     #[serde(rename = "shortName", default)]
     pub short_name: Option<String>,
+
+    /// Compiled tree-sitter parser (.so, .dylib or .dll), relative to package.
+    /// Only used for Fresh's built-in languages; its highlights.scm, indents.scm
+    /// and locals.scm are read from the same directory or a queries/ directory
+    /// next to it.
+    #[serde(rename = "treeSitter", default)]
+    pub tree_sitter: Option<String>,
 }
 
 /// Language configuration within a package manifest (camelCase to match JSON schema).
@@ -483,6 +490,16 @@ fn process_bundle(pkg_dir: &Path, manifest: &PackageManifest, result: &mut Packa
                     grammar_path
                 );
             }
+
+            // The grammar loader tells parser libraries apart from TextMate
+            // grammars by their extension.
+            if let Some(library) = &grammar.tree_sitter {
+                result.additional_grammars.push(GrammarSpec {
+                    language: lang.id.clone(),
+                    path: pkg_dir.join(library),
+                    extensions: Vec::new(),
+                });
+            }
         }
 
         // Language config
@@ -843,6 +860,47 @@ mod tests {
         assert_eq!(result.bundle_theme_dirs[0], bundle_dir);
     }
 
+    #[test]
+    fn test_scan_bundle_tree_sitter_parser() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_dir = temp_dir.path();
+        let bundle_dir = config_dir.join("bundles/packages/ruby-bundle");
+        std::fs::create_dir_all(bundle_dir.join("grammars")).unwrap();
+        std::fs::write(
+            bundle_dir.join("grammars/Ruby.sublime-syntax"),
+            "# dummy grammar",
+        )
+        .unwrap();
+        std::fs::write(
+            bundle_dir.join("package.json"),
+            r#"{
+                "name": "ruby-bundle",
+                "type": "bundle",
+                "fresh": {
+                    "languages": [
+                        {
+                            "id": "ruby",
+                            "grammar": {
+                                "file": "grammars/Ruby.sublime-syntax",
+                                "extensions": ["rb"],
+                                "treeSitter": "tree-sitter/ruby.so"
+                            }
+                        }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let result = scan_installed_packages(config_dir);
+
+        assert_eq!(result.additional_grammars.len(), 2);
+        let parser = &result.additional_grammars[1];
+        assert_eq!(parser.language, "ruby");
+        assert_eq!(parser.path, bundle_dir.join("tree-sitter/ruby.so"));
+        assert!(parser.extensions.is_empty());
+    }
+
     #[test]
     fn test_scan_skips_malformed_manifest() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -3,5 +3,6 @@
 //! Re-exports hook system types from fresh-core for backward compatibility.
 
 pub use fresh_core::hooks::{
-    hook_args_to_json, HookArgs, HookCallback, HookRegistry, LineInfo, LspLocation, RegionLine,
+    hook_args_to_json, DebugStackFrame, HookArgs, HookCallback, HookRegistry, LineInfo,
+    LspLocation, RegionLine,
 };
@@ -167,6 +167,62 @@ pub trait ProcessSpawner: Send + Sync {
             None => self.spawn(command, args, cwd).await,
         }
     }
+
+    /// Spawn a process and send its output into `lines` one line at a
+    /// time as it arrives, stdout and stderr interleaved, returning the
+    /// exit code. `kill_rx` kills it as in [`Self::spawn_cancellable`].
+    ///
+    /// Default impl has no streaming path: it waits for the process, then
+    /// sends stdout's lines followed by stderr's. Local override streams.
+    async fn spawn_streaming(
+        &self,
+        command: String,
+        args: Vec<String>,
+        cwd: Option<String>,
+        lines: tokio::sync::mpsc::UnboundedSender<String>,
+        kill_rx: tokio::sync::oneshot::Receiver<()>,
+    ) -> Result<i32, SpawnError> {
+        let result = self
+            .spawn_cancellable(command, args, cwd, None, kill_rx)
+            .await?;
+        for line in result.stdout.lines().chain(result.stderr.lines()) {
+            // A dropped receiver means nobody is listening any more.
+            if lines.send(line.to_string()).is_err() {
+                break;
+            }
+        }
+        Ok(result.exit_code)
+    }
+}
+
+/// Forward `reader` into `lines` one (lossily decoded) line at a time until
+/// EOF or until the receiver is dropped.
+fn forward_lines<R>(
+    reader: R,
+    lines: tokio::sync::mpsc::UnboundedSender<String>,
+) -> tokio::task::JoinHandle<()>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    use tokio::io::AsyncBufReadExt;
+
+    tokio::spawn(async move {
+        let mut reader = tokio::io::BufReader::new(reader);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buf);
+                    let line = line.trim_end_matches(['\r', '\n']).to_string();
+                    if lines.send(line).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    })
 }
 
 /// Local process spawner using tokio.
@@ -346,6 +402,75 @@ impl ProcessSpawner for LocalProcessSpawner {
         })
     }
 
+    /// Streaming override: both pipes are read line by line as the child
+    /// writes them. A killed child's own children (a shell's pipeline) can
+    /// keep the pipes open, so on kill the readers are dropped rather than
+    /// drained.
+    async fn spawn_streaming(
+        &self,
+        command: String,
+        args: Vec<String>,
+        cwd: Option<String>,
+        lines: tokio::sync::mpsc::UnboundedSender<String>,
+        kill_rx: tokio::sync::oneshot::Receiver<()>,
+    ) -> Result<i32, SpawnError> {
+        use std::process::Stdio;
+
+        gate(&self.trust, &command, cwd.as_deref())?;
+        let mut cmd = tokio::process::Command::new(resolve_program(&command).as_ref());
+        cmd.args(&args);
+        self.apply_env(&mut cmd).await;
+        cmd.hide_window();
+        cmd.stdin(Stdio::null());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+        if let Some(ref dir) = cwd {
+            cmd.current_dir(dir);
+        }
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| SpawnError::Process(e.to_string()))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| SpawnError::Process("child stdout missing".to_string()))?;
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| SpawnError::Process("child stderr missing".to_string()))?;
+        let readers = [
+            forward_lines(stdout, lines.clone()),
+            forward_lines(stderr, lines),
+        ];
+
+        let (exit_code, killed) = tokio::select! {
+            status = child.wait() => (
+                status.map(|s| s.code().unwrap_or(-1)).unwrap_or(-1),
+                false,
+            ),
+            _ = kill_rx => {
+                // start_kill fails only when the process already exited;
+                // the wait below reaps it either way.
+                if let Err(e) = child.start_kill() {
+                    tracing::debug!("spawn_streaming: start_kill (already exited?): {}", e);
+                }
+                (
+                    child.wait().await.map(|s| s.code().unwrap_or(-1)).unwrap_or(-1),
+                    true,
+                )
+            }
+        };
+        for reader in readers {
+            if killed {
+                reader.abort();
+            } else if let Err(e) = reader.await {
+                tracing::debug!("spawn_streaming: output reader failed: {}", e);
+            }
+        }
+        Ok(exit_code)
+    }
+
     /// Streaming override: pipe child stdout straight into `stdout_to`
     /// via `tokio::io::copy`. The 43 MB stdout of `git show` for the
     /// bun-rust-rewrite commit never lands in a single `String`.
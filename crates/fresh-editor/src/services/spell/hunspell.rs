@@ -0,0 +1,814 @@
+//! Hunspell dictionaries (`.aff` + `.dic`).
+//!
+//! A pure-Rust reader for the subset of the Hunspell format that checking
+//! and suggesting prose needs:
+//!
+//! - `SET` (UTF-8 and the ISO-8859-1 family), `FLAG` (`long`, `num`,
+//!   `UTF-8` or the default single-character flags) and `AF` flag aliases
+//! - `PFX` / `SFX` rules with strip strings, conditions and cross products
+//! - `TRY`, `REP` and `IGNORE`
+//! - the `FORBIDDENWORD`, `NEEDAFFIX`, `ONLYINCOMPOUND`, `KEEPCASE` and
+//!   `NOSUGGEST` word flags
+//!
+//! Compounding and two-level affix stripping are not implemented; words that
+//! only exist as compounds are reported as misspelled.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::Path;
+
+/// A word or affix flag, normalized from whichever `FLAG` encoding the
+/// `.aff` file uses.
+type Flag = u32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlagFormat {
+    /// One character per flag (the default).
+    Short,
+    /// Two characters per flag.
+    Long,
+    /// Comma-separated decimal numbers.
+    Numeric,
+    /// One Unicode character per flag.
+    Utf8,
+}
+
+impl FlagFormat {
+    fn parse(self, text: &str) -> Vec<Flag> {
+        match self {
+            Self::Short | Self::Utf8 => text.chars().map(|c| c as Flag).collect(),
+            Self::Long => {
+                let chars: Vec<char> = text.chars().collect();
+                chars
+                    .chunks(2)
+                    .map(|pair| {
+                        let high = pair[0] as Flag & 0xFFFF;
+                        let low = pair.get(1).map_or(0, |c| *c as Flag & 0xFFFF);
+                        (high << 16) | low
+                    })
+                    .collect()
+            }
+            Self::Numeric => text
+                .split(',')
+                .filter_map(|n| n.trim().parse().ok())
+                .collect(),
+        }
+    }
+}
+
+/// One element of an affix condition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CharClass {
+    Any,
+    Char(char),
+    Set(Vec<char>),
+    NotSet(Vec<char>),
+}
+
+impl CharClass {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Char(expected) => *expected == c,
+            Self::Set(set) => set.contains(&c),
+            Self::NotSet(set) => !set.contains(&c),
+        }
+    }
+}
+
+/// An affix condition such as `[^aeiou]y`: a sequence of character classes
+/// the stem has to start (prefixes) or end (suffixes) with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Condition(Vec<CharClass>);
+
+impl Condition {
+    fn parse(text: &str) -> Self {
+        if text == "." {
+            return Self::default();
+        }
+        let mut classes = Vec::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '.' => classes.push(CharClass::Any),
+                '[' => {
+                    let mut set = Vec::new();
+                    let mut negated = false;
+                    for (i, c) in chars.by_ref().enumerate() {
+                        match c {
+                            '^' if i == 0 => negated = true,
+                            ']' => break,
+                            c => set.push(c),
+                        }
+                    }
+                    classes.push(if negated {
+                        CharClass::NotSet(set)
+                    } else {
+                        CharClass::Set(set)
+                    });
+                }
+                c => classes.push(CharClass::Char(c)),
+            }
+        }
+        Self(classes)
+    }
+
+    fn matches_start(&self, word: &str) -> bool {
+        let mut chars = word.chars();
+        self.0
+            .iter()
+            .all(|class| chars.next().is_some_and(|c| class.matches(c)))
+    }
+
+    fn matches_end(&self, word: &str) -> bool {
+        let mut chars = word.chars().rev();
+        self.0
+            .iter()
+            .rev()
+            .all(|class| chars.next().is_some_and(|c| class.matches(c)))
+    }
+}
+
+/// One `PFX` or `SFX` rule line.
+#[derive(Debug, Clone)]
+struct Affix {
+    flag: Flag,
+    cross_product: bool,
+    /// Characters removed from the root before `add` is attached.
+    strip: String,
+    condition: Condition,
+}
+
+/// A `REP` entry: a likely typo and its fix, optionally anchored to the
+/// start (`^`) or end (`$`) of the word.
+#[derive(Debug, Clone)]
+struct Replacement {
+    from: String,
+    to: String,
+    at_start: bool,
+    at_end: bool,
+}
+
+/// How a word is capitalized; decides which dictionary forms may match it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Casing {
+    Lower,
+    /// First letter upper, the rest lower ("Paris").
+    Title,
+    /// Every letter upper ("NASA").
+    Upper,
+    /// Anything else ("iPhone", "McDonald").
+    Mixed,
+}
+
+fn casing(word: &str) -> Casing {
+    let mut letters = word.chars().filter(|c| c.is_alphabetic());
+    let Some(first) = letters.next() else {
+        return Casing::Lower;
+    };
+    let (mut upper, mut lower) = (0, 0);
+    for c in letters {
+        if c.is_uppercase() {
+            upper += 1;
+        } else if c.is_lowercase() {
+            lower += 1;
+        }
+    }
+    match (first.is_uppercase(), upper, lower) {
+        (false, 0, _) => Casing::Lower,
+        (true, 0, _) => Casing::Title,
+        (true, _, 0) => Casing::Upper,
+        _ => Casing::Mixed,
+    }
+}
+
+fn title_case(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Re-apply the capitalization of the word the user typed to a suggestion
+/// that was looked up in lower case.
+fn restore_case(suggestion: &str, casing: Casing) -> String {
+    match casing {
+        Casing::Title => title_case(suggestion),
+        Casing::Upper => suggestion.to_uppercase(),
+        Casing::Lower | Casing::Mixed => suggestion.to_string(),
+    }
+}
+
+/// Decode a dictionary file per its `SET` line. The ISO-8859-1 family maps
+/// byte-for-byte onto the first 256 code points; everything else is read as
+/// UTF-8.
+fn decode(bytes: &[u8], encoding: &str) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let encoding = encoding.to_ascii_uppercase();
+    if encoding.starts_with("ISO8859") || encoding.starts_with("ISO-8859") {
+        bytes.iter().map(|&b| b as char).collect()
+    } else {
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+}
+
+/// Find the `SET` line in raw `.aff` bytes. Directive names are ASCII, so
+/// this works before the encoding is known.
+fn declared_encoding(aff: &[u8]) -> String {
+    aff.split(|&b| b == b'\n')
+        .filter_map(|line| line.strip_prefix(b"SET"))
+        .find_map(|rest| {
+            let rest = String::from_utf8_lossy(rest);
+            let encoding = rest.split_whitespace().next()?;
+            Some(encoding.to_string())
+        })
+        .unwrap_or_else(|| "UTF-8".to_string())
+}
+
+/// Split a `.dic` entry into the word and its flag string, honoring `\/`
+/// as an escaped slash inside the word.
+fn split_entry(entry: &str) -> (String, &str) {
+    let mut word = String::new();
+    let mut chars = entry.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if chars.peek().is_some_and(|(_, next)| *next == '/') => {
+                word.push('/');
+                chars.next();
+            }
+            '/' if i > 0 => return (word, &entry[i + 1..]),
+            c => word.push(c),
+        }
+    }
+    (word, "")
+}
+
+/// A loaded Hunspell dictionary.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    /// Root words, each with the flag sets of its homonyms.
+    words: HashMap<String, Vec<Vec<Flag>>>,
+    /// Prefix rules keyed by the text they add.
+    prefixes: HashMap<String, Vec<Affix>>,
+    /// Suffix rules keyed by the text they add.
+    suffixes: HashMap<String, Vec<Affix>>,
+    /// Longest `add` string among the prefix rules, in characters.
+    max_prefix: usize,
+    /// Longest `add` string among the suffix rules, in characters.
+    max_suffix: usize,
+    try_chars: Vec<char>,
+    replacements: Vec<Replacement>,
+    ignore: HashSet<char>,
+    forbidden: Option<Flag>,
+    need_affix: Option<Flag>,
+    only_in_compound: Option<Flag>,
+    keep_case: Option<Flag>,
+    no_suggest: Option<Flag>,
+}
+
+impl Dictionary {
+    /// Load `<path>.aff` and `<path>.dic`.
+    pub fn load(aff_path: &Path, dic_path: &Path) -> io::Result<Self> {
+        let aff = std::fs::read(aff_path)?;
+        let dic = std::fs::read(dic_path)?;
+        Ok(Self::parse(&aff, &dic))
+    }
+
+    /// Build a dictionary from the raw contents of its two files. Lines the
+    /// reader does not understand are skipped rather than rejected, so a
+    /// dictionary using unsupported features still checks everything else.
+    pub fn parse(aff: &[u8], dic: &[u8]) -> Self {
+        let encoding = declared_encoding(aff);
+        let mut dict = Self::default();
+        let aliases = dict.parse_aff(&decode(aff, &encoding));
+        dict.parse_dic(&decode(dic, &encoding), &aliases);
+        dict
+    }
+
+    fn parse_aff(&mut self, text: &str) -> (FlagFormat, Vec<Vec<Flag>>) {
+        let mut format = FlagFormat::Short;
+        let mut aliases: Vec<Vec<Flag>> = Vec::new();
+        let mut alias_header_seen = false;
+        let mut rep_header_seen = false;
+        // Entries still expected after each PFX/SFX header, and whether that
+        // header allowed cross products.
+        let mut open_rules: HashMap<(bool, Flag), (usize, bool)> = HashMap::new();
+
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let Some(&directive) = fields.first() else {
+                continue;
+            };
+            let single_flag = || fields.get(1).and_then(|f| format.parse(f).first().copied());
+            match directive {
+                "FLAG" => {
+                    format = match fields.get(1).copied() {
+                        Some("long") => FlagFormat::Long,
+                        Some("num") => FlagFormat::Numeric,
+                        Some("UTF-8") => FlagFormat::Utf8,
+                        _ => FlagFormat::Short,
+                    }
+                }
+                "AF" => {
+                    if !alias_header_seen {
+                        alias_header_seen = true;
+                    } else if let Some(flags) = fields.get(1) {
+                        aliases.push(format.parse(flags));
+                    }
+                }
+                "TRY" => {
+                    if let Some(chars) = fields.get(1) {
+                        self.try_chars = chars.chars().collect();
+                    }
+                }
+                "REP" => {
+                    if !rep_header_seen {
+                        rep_header_seen = true;
+                    } else if let (Some(from), Some(to)) = (fields.get(1), fields.get(2)) {
+                        let at_start = from.starts_with('^');
+                        let at_end = from.ends_with('$') && from.len() > 1;
+                        let from = from.trim_start_matches('^').trim_end_matches('$');
+                        self.replacements.push(Replacement {
+                            from: from.replace('_', " "),
+                            to: to.replace('_', " "),
+                            at_start,
+                            at_end,
+                        });
+                    }
+                }
+                "IGNORE" => {
+                    if let Some(chars) = fields.get(1) {
+                        self.ignore = chars.chars().collect();
+                    }
+                }
+                "FORBIDDENWORD" => self.forbidden = single_flag(),
+                "NEEDAFFIX" | "PSEUDOROOT" => self.need_affix = single_flag(),
+                "ONLYINCOMPOUND" => self.only_in_compound = single_flag(),
+                "KEEPCASE" => self.keep_case = single_flag(),
+                "NOSUGGEST" => self.no_suggest = single_flag(),
+                "PFX" | "SFX" => {
+                    let is_prefix = directive == "PFX";
+                    let Some(flag) = single_flag() else {
+                        continue;
+                    };
+                    match open_rules.get_mut(&(is_prefix, flag)) {
+                        Some((remaining, cross_product)) if *remaining > 0 => {
+                            *remaining -= 1;
+                            let cross_product = *cross_product;
+                            self.add_affix(is_prefix, flag, cross_product, &fields);
+                        }
+                        _ => {
+                            let cross_product = fields.get(2) == Some(&"Y");
+                            let count = fields.get(3).and_then(|n| n.parse().ok()).unwrap_or(0);
+                            open_rules.insert((is_prefix, flag), (count, cross_product));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        (format, aliases)
+    }
+
+    fn add_affix(&mut self, is_prefix: bool, flag: Flag, cross_product: bool, fields: &[&str]) {
+        let (Some(strip), Some(add)) = (fields.get(2), fields.get(3)) else {
+            return;
+        };
+        let strip = if *strip == "0" { "" } else { strip };
+        // Continuation classes (`add/flags`) enable two-level affixes, which
+        // are not supported; the rule still applies on its own.
+        let add = add.split('/').next().unwrap_or("");
+        let add = if add == "0" { "" } else { add };
+        let condition = Condition::parse(fields.get(4).copied().unwrap_or("."));
+        let affix = Affix {
+            flag,
+            cross_product,
+            strip: strip.to_string(),
+            condition,
+        };
+        let len = add.chars().count();
+        let (rules, max) = if is_prefix {
+            (&mut self.prefixes, &mut self.max_prefix)
+        } else {
+            (&mut self.suffixes, &mut self.max_suffix)
+        };
+        *max = (*max).max(len);
+        rules.entry(add.to_string()).or_default().push(affix);
+    }
+
+    fn parse_dic(&mut self, text: &str, (format, aliases): &(FlagFormat, Vec<Vec<Flag>>)) {
+        let mut lines = text.lines();
+        // The first line is the approximate word count.
+        let mut first = lines.next();
+        if first.is_some_and(|l| l.trim().parse::<usize>().is_ok()) {
+            first = None;
+        }
+        for line in first.into_iter().chain(lines) {
+            // Morphological fields follow the entry after a tab or space.
+            let Some(entry) = line.split(['\t', ' ']).next().filter(|e| !e.is_empty()) else {
+                continue;
+            };
+            let (word, flags) = split_entry(entry);
+            if word.is_empty() {
+                continue;
+            }
+            let flags = if aliases.is_empty() {
+                format.parse(flags)
+            } else {
+                flags
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| aliases.get(n.wrapping_sub(1)))
+                    .cloned()
+                    .unwrap_or_default()
+            };
+            self.words.entry(word).or_default().push(flags);
+        }
+    }
+
+    /// Number of root words (homonyms counted once).
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Whether `word` is spelled correctly.
+    pub fn check(&self, word: &str) -> bool {
+        let stripped;
+        let word = if self.ignore.is_empty() {
+            word
+        } else {
+            stripped = word
+                .chars()
+                .filter(|c| !self.ignore.contains(c))
+                .collect::<String>();
+            &stripped
+        };
+        if word.is_empty() {
+            return true;
+        }
+        if self.check_form(word, false) {
+            return true;
+        }
+        match casing(word) {
+            Casing::Lower | Casing::Mixed => false,
+            Casing::Title => self.check_form(&word.to_lowercase(), true),
+            Casing::Upper => {
+                let lower = word.to_lowercase();
+                self.check_form(&title_case(&lower), true) || self.check_form(&lower, true)
+            }
+        }
+    }
+
+    fn has(flags: &[Flag], flag: Option<Flag>) -> bool {
+        flag.is_some_and(|f| flags.contains(&f))
+    }
+
+    /// `word` as written, either as a root or as a root plus affixes.
+    /// `case_changed` is set when `word` is a re-cased form of what the user
+    /// typed, which `KEEPCASE` roots must not match.
+    fn check_form(&self, word: &str, case_changed: bool) -> bool {
+        if let Some(homonyms) = self.words.get(word) {
+            if homonyms.iter().any(|f| Self::has(f, self.forbidden)) {
+                return false;
+            }
+            if homonyms
+                .iter()
+                .any(|f| !Self::has(f, self.need_affix) && self.standalone(f, case_changed))
+            {
+                return true;
+            }
+        }
+        self.check_suffixed(word, case_changed) || self.check_prefixed(word, case_changed)
+    }
+
+    /// Whether a homonym with `flags` may appear outside a compound in the
+    /// capitalization being checked.
+    fn standalone(&self, flags: &[Flag], case_changed: bool) -> bool {
+        let recased_keep_case = case_changed && Self::has(flags, self.keep_case);
+        !recased_keep_case && !Self::has(flags, self.only_in_compound)
+    }
+
+    /// Whether `root` is a dictionary word carrying every flag in `flags`.
+    fn root_has(&self, root: &str, flags: &[Flag], case_changed: bool) -> bool {
+        self.words.get(root).is_some_and(|homonyms| {
+            homonyms.iter().any(|f| {
+                flags.iter().all(|flag| f.contains(flag))
+                    && !Self::has(f, self.forbidden)
+                    && self.standalone(f, case_changed)
+            })
+        })
+    }
+
+    /// Byte offsets splitting `word` into a non-empty stem and an affix of
+    /// at most `max` characters, from the shortest affix up.
+    fn suffix_splits(word: &str, max: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::once(word.len())
+            .chain(word.char_indices().rev().map(|(i, _)| i))
+            .filter(|&i| i > 0)
+            .take(max + 1)
+    }
+
+    fn prefix_splits(word: &str, max: usize) -> impl Iterator<Item = usize> + '_ {
+        word.char_indices()
+            .map(|(i, _)| i)
+            .take(max + 1)
+            .filter(move |&i| i < word.len())
+    }
+
+    fn check_suffixed(&self, word: &str, case_changed: bool) -> bool {
+        for split in Self::suffix_splits(word, self.max_suffix) {
+            let Some(rules) = self.suffixes.get(&word[split..]) else {
+                continue;
+            };
+            for rule in rules {
+                let root = format!("{}{}", &word[..split], rule.strip);
+                if !rule.condition.matches_end(&root) {
+                    continue;
+                }
+                if self.root_has(&root, &[rule.flag], case_changed) {
+                    return true;
+                }
+                if rule.cross_product && self.check_cross(&root, rule.flag, case_changed) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// `stem` (a word with its suffix already removed) also carrying a
+    /// cross-product prefix.
+    fn check_cross(&self, stem: &str, suffix_flag: Flag, case_changed: bool) -> bool {
+        for split in Self::prefix_splits(stem, self.max_prefix) {
+            let Some(rules) = self.prefixes.get(&stem[..split]) else {
+                continue;
+            };
+            for rule in rules.iter().filter(|r| r.cross_product) {
+                let root = format!("{}{}", rule.strip, &stem[split..]);
+                if rule.condition.matches_start(&root)
+                    && self.root_has(&root, &[rule.flag, suffix_flag], case_changed)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn check_prefixed(&self, word: &str, case_changed: bool) -> bool {
+        for split in Self::prefix_splits(word, self.max_prefix) {
+            let Some(rules) = self.prefixes.get(&word[..split]) else {
+                continue;
+            };
+            for rule in rules {
+                let root = format!("{}{}", rule.strip, &word[split..]);
+                if rule.condition.matches_start(&root)
+                    && self.root_has(&root, &[rule.flag], case_changed)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Whether `word` may be offered as a suggestion.
+    fn suggestible(&self, word: &str) -> bool {
+        let no_suggest = self
+            .words
+            .get(word)
+            .is_some_and(|homonyms| homonyms.iter().all(|f| Self::has(f, self.no_suggest)));
+        !no_suggest && self.check(word)
+    }
+
+    /// Up to `limit` corrections for `word`, most likely first: `REP` table
+    /// fixes, then a different capitalization, then single edits (swapping
+    /// neighbours, replacing, deleting or inserting one character), then
+    /// splitting the word in two.
+    pub fn suggest(&self, word: &str, limit: usize) -> Vec<String> {
+        let casing = casing(word);
+        let base = match casing {
+            Casing::Title | Casing::Upper => word.to_lowercase(),
+            Casing::Lower | Casing::Mixed => word.to_string(),
+        };
+        let mut seen = HashSet::new();
+        let mut out = Vec::new();
+        let mut offer = |candidate: String, recase: bool| {
+            if out.len() >= limit || candidate == word {
+                return;
+            }
+            let candidate = if recase {
+                restore_case(&candidate, casing)
+            } else {
+                candidate
+            };
+            if seen.insert(candidate.clone()) && self.suggestible_phrase(&candidate) {
+                out.push(candidate);
+            }
+        };
+
+        for candidate in self.replacement_candidates(&base) {
+            offer(candidate, true);
+        }
+        offer(title_case(&base), false);
+        offer(base.to_uppercase(), false);
+
+        let chars: Vec<char> = base.chars().collect();
+        let try_chars = self.try_alphabet(&chars);
+        let build = |chars: &[char]| chars.iter().collect::<String>();
+
+        for i in 0..chars.len().saturating_sub(1) {
+            let mut swapped = chars.clone();
+            swapped.swap(i, i + 1);
+            offer(build(&swapped), true);
+        }
+        for i in 0..chars.len() {
+            for &c in &try_chars {
+                if c != chars[i] {
+                    let mut replaced = chars.clone();
+                    replaced[i] = c;
+                    offer(build(&replaced), true);
+                }
+            }
+        }
+        for i in 0..chars.len() {
+            let mut deleted = chars.clone();
+            deleted.remove(i);
+            offer(build(&deleted), true);
+        }
+        for i in 0..=chars.len() {
+            for &c in &try_chars {
+                let mut inserted = chars.clone();
+                inserted.insert(i, c);
+                offer(build(&inserted), true);
+            }
+        }
+        for i in 1..chars.len() {
+            offer(
+                format!("{} {}", build(&chars[..i]), build(&chars[i..])),
+                true,
+            );
+        }
+        out
+    }
+
+    /// A suggestion is acceptable when each of its space-separated words is.
+    fn suggestible_phrase(&self, phrase: &str) -> bool {
+        phrase
+            .split(' ')
+            .all(|w| !w.is_empty() && self.suggestible(w))
+    }
+
+    fn replacement_candidates(&self, word: &str) -> Vec<String> {
+        let mut out = Vec::new();
+        for rep in &self.replacements {
+            if rep.from.is_empty() {
+                continue;
+            }
+            for (i, _) in word.match_indices(rep.from.as_str()) {
+                let end = i + rep.from.len();
+                if (rep.at_start && i != 0) || (rep.at_end && end != word.len()) {
+                    continue;
+                }
+                out.push(format!("{}{}{}", &word[..i], rep.to, &word[end..]));
+            }
+        }
+        out
+    }
+
+    /// The characters single-character edits try: the dictionary's `TRY`
+    /// line, or the letters of the word and the basic Latin alphabet when
+    /// it has none.
+    fn try_alphabet(&self, word: &[char]) -> Vec<char> {
+        let mut chars: Vec<char> = if self.try_chars.is_empty() {
+            ('a'..='z').collect()
+        } else {
+            self.try_chars
+                .iter()
+                .copied()
+                .filter(|c| c.is_alphabetic() || *c == '\'' || *c == '-')
+                .collect()
+        };
+        for &c in word {
+            if !chars.contains(&c) {
+                chars.push(c);
+            }
+        }
+        chars
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "SET UTF-8
+TRY esianrtolcdugmphbyfvkwzESIANRTOLCDUGMPHBYFVKWZ'
+KEEPCASE K
+NOSUGGEST !
+FORBIDDENWORD F
+REP 2
+REP f ph
+REP alot a_lot
+
+PFX U Y 1
+PFX U   0     un         .
+
+SFX S Y 4
+SFX S   y     ies        [^aeiou]y
+SFX S   0     s          [aeiou]y
+SFX S   0     es         [sxzh]
+SFX S   0     s          [^sxzhy]
+
+SFX D Y 2
+SFX D   0     d          e
+SFX D   0     ed         [^e]
+";
+
+    const DIC: &str = "11
+a
+lot
+happy/U
+city/S
+day/S
+box/S
+work/DS
+hope/DS
+phone/S
+NASA/K
+shit/!
+";
+
+    fn dictionary() -> Dictionary {
+        Dictionary::parse(AFF.as_bytes(), DIC.as_bytes())
+    }
+
+    #[test]
+    fn checks_roots_and_affixed_forms() {
+        let dict = dictionary();
+        for word in [
+            "happy", "unhappy", "cities", "days", "boxes", "worked", "hoped", "works",
+        ] {
+            assert!(dict.check(word), "{word} should be accepted");
+        }
+        for word in ["citys", "dayies", "boxs", "hopeed", "unwork", "happyed"] {
+            assert!(!dict.check(word), "{word} should be rejected");
+        }
+    }
+
+    #[test]
+    fn capitalization_follows_the_dictionary_form() {
+        let dict = dictionary();
+        assert!(dict.check("Happy"));
+        assert!(dict.check("HAPPY"));
+        assert!(dict.check("NASA"));
+        // KEEPCASE: only the form in the dictionary.
+        assert!(!dict.check("Nasa"));
+        assert!(!dict.check("nasa"));
+        // Mixed case does not fall back to lower case.
+        assert!(!dict.check("hAppy"));
+    }
+
+    #[test]
+    fn suggests_rep_fixes_and_single_edits() {
+        let dict = dictionary();
+        assert_eq!(
+            dict.suggest("fone", 3).first().map(String::as_str),
+            Some("phone")
+        );
+        assert!(dict.suggest("alot", 5).contains(&"a lot".to_string()));
+        assert_eq!(dict.suggest("hapy", 1), vec!["happy".to_string()]);
+        assert_eq!(dict.suggest("Hapy", 1), vec!["Happy".to_string()]);
+        assert!(dict.suggest("citeis", 5).contains(&"cities".to_string()));
+    }
+
+    #[test]
+    fn nosuggest_words_are_accepted_but_never_offered() {
+        let dict = dictionary();
+        assert!(dict.check("shit"));
+        assert!(!dict.suggest("shitt", 10).contains(&"shit".to_string()));
+    }
+
+    #[test]
+    fn long_flags_and_aliases() {
+        let aff = "FLAG long\nAF 1\nAF SsDd\nSFX Ss Y 1\nSFX Ss 0 s .\nSFX Dd Y 1\nSFX Dd 0 ed .\n";
+        let dic = "1\nwalk/1\n";
+        let dict = Dictionary::parse(aff.as_bytes(), dic.as_bytes());
+        assert!(dict.check("walks"));
+        assert!(dict.check("walked"));
+        assert!(!dict.check("walking"));
+    }
+
+    #[test]
+    fn latin1_dictionaries_are_decoded() {
+        let aff = b"SET ISO8859-1\n";
+        let dic = b"1\ncaf\xe9\n";
+        let dict = Dictionary::parse(aff, dic);
+        assert!(dict.check("café"));
+    }
+}
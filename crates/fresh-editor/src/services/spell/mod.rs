@@ -0,0 +1,216 @@
+//! Offline spell checking.
+//!
+//! A [`SpellChecker`] combines a Hunspell dictionary loaded from
+//! `<config_dir>/dictionaries/<language>.aff` / `.dic` with two plain word
+//! lists, one word per line, that grow from the "add to dictionary" code
+//! actions:
+//!
+//! - the user dictionary, `<config_dir>/dictionaries/user.txt`, shared by
+//!   every project
+//! - the workspace dictionary, `<working_dir>/.fresh/dictionary.txt`, meant
+//!   to be committed alongside the project
+//!
+//! Nothing here touches the network or spawns a process. Which text gets
+//! checked is decided by the caller (see `view::spell_check_overlay`);
+//! [`words`] picks out the words inside it.
+
+pub mod hunspell;
+mod words;
+
+pub use hunspell::Dictionary;
+pub use words::words;
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// File name of the user dictionary inside the dictionaries directory.
+pub const USER_DICTIONARY_FILE: &str = "user.txt";
+
+/// Path of the workspace dictionary relative to the working directory.
+pub const WORKSPACE_DICTIONARY_PATH: &str = ".fresh/dictionary.txt";
+
+/// Which personal word list a word is added to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordListScope {
+    User,
+    Workspace,
+}
+
+/// A personal word list backed by a text file.
+#[derive(Debug)]
+struct WordList {
+    path: PathBuf,
+    words: RwLock<HashSet<String>>,
+}
+
+impl WordList {
+    /// Read `path`; a missing file is an empty list.
+    fn load(path: PathBuf) -> Self {
+        let words = std::fs::read_to_string(&path)
+            .map(|text| {
+                text.lines()
+                    .map(str::trim)
+                    .filter(|w| !w.is_empty() && !w.starts_with('#'))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            path,
+            words: RwLock::new(words),
+        }
+    }
+
+    /// Listed words match as written or in lower case, so a word added in
+    /// lower case is also accepted at the start of a sentence.
+    fn contains(&self, word: &str) -> bool {
+        let words = self.words.read().unwrap_or_else(|e| e.into_inner());
+        words.contains(word) || words.contains(&word.to_lowercase())
+    }
+
+    fn add(&self, word: &str) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{word}")?;
+        self.words
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(word.to_string());
+        Ok(())
+    }
+}
+
+/// A dictionary plus the user's and the workspace's own word lists.
+#[derive(Debug)]
+pub struct SpellChecker {
+    language: String,
+    dictionary: Dictionary,
+    user_words: WordList,
+    workspace_words: WordList,
+    /// Bumped whenever a word list changes, so cached results are redone.
+    generation: AtomicU64,
+}
+
+impl SpellChecker {
+    /// Paths of the `.aff` and `.dic` files for `language`.
+    pub fn dictionary_paths(dictionaries_dir: &Path, language: &str) -> (PathBuf, PathBuf) {
+        (
+            dictionaries_dir.join(format!("{language}.aff")),
+            dictionaries_dir.join(format!("{language}.dic")),
+        )
+    }
+
+    /// Load the `language` dictionary from `dictionaries_dir`, the user
+    /// dictionary next to it, and the workspace dictionary of `working_dir`.
+    pub fn load(dictionaries_dir: &Path, language: &str, working_dir: &Path) -> io::Result<Self> {
+        let (aff, dic) = Self::dictionary_paths(dictionaries_dir, language);
+        let dictionary = Dictionary::load(&aff, &dic)?;
+        Ok(Self::new(
+            language,
+            dictionary,
+            dictionaries_dir.join(USER_DICTIONARY_FILE),
+            working_dir.join(WORKSPACE_DICTIONARY_PATH),
+        ))
+    }
+
+    pub fn new(
+        language: &str,
+        dictionary: Dictionary,
+        user_words: PathBuf,
+        workspace_words: PathBuf,
+    ) -> Self {
+        Self {
+            language: language.to_string(),
+            dictionary,
+            user_words: WordList::load(user_words),
+            workspace_words: WordList::load(workspace_words),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Counter that changes whenever the accepted words do.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Whether `word` is spelled correctly. Curly apostrophes count as
+    /// `'`, which is what Hunspell dictionaries use.
+    pub fn check(&self, word: &str) -> bool {
+        let normalized;
+        let word = if word.contains('\u{2019}') {
+            normalized = word.replace('\u{2019}', "'");
+            &normalized
+        } else {
+            word
+        };
+        self.workspace_words.contains(word)
+            || self.user_words.contains(word)
+            || self.dictionary.check(word)
+    }
+
+    /// Up to `limit` corrections for `word`, most likely first.
+    pub fn suggest(&self, word: &str, limit: usize) -> Vec<String> {
+        self.dictionary.suggest(word, limit)
+    }
+
+    /// Accept `word` from now on, recording it in the chosen word list.
+    pub fn add_word(&self, word: &str, scope: WordListScope) -> io::Result<()> {
+        match scope {
+            WordListScope::User => self.user_words.add(word)?,
+            WordListScope::Workspace => self.workspace_words.add(word)?,
+        }
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// File a word added with `scope` is written to.
+    pub fn word_list_path(&self, scope: WordListScope) -> &Path {
+        match scope {
+            WordListScope::User => &self.user_words.path,
+            WordListScope::Workspace => &self.workspace_words.path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn added_words_are_accepted_and_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let dictionary = Dictionary::parse(b"SET UTF-8\n", b"1\nhello\n");
+        let checker = SpellChecker::new(
+            "en_US",
+            dictionary,
+            dir.path().join("user.txt"),
+            dir.path().join("project/.fresh/dictionary.txt"),
+        );
+        assert!(checker.check("hello"));
+        assert!(!checker.check("frobnicate"));
+
+        let generation = checker.generation();
+        checker
+            .add_word("frobnicate", WordListScope::Workspace)
+            .unwrap();
+        assert!(checker.check("frobnicate"));
+        assert!(checker.check("Frobnicate"));
+        assert_ne!(checker.generation(), generation);
+
+        let written =
+            std::fs::read_to_string(dir.path().join("project/.fresh/dictionary.txt")).unwrap();
+        assert_eq!(written, "frobnicate\n");
+    }
+}
@@ -0,0 +1,170 @@
+//! Picking out the words in a run of text that are worth spell checking.
+//!
+//! Comments and prose are full of things that are not words: URLs, paths,
+//! identifiers, version numbers, acronyms. Rather than flag them all, the
+//! tokenizer skips any whitespace-separated chunk that looks like code and
+//! splits `camelCase` into its parts.
+
+/// Whether a whitespace-separated chunk looks like a URL, path, e-mail
+/// address, identifier or inline code, and should be skipped entirely.
+fn is_code_like(chunk: &str) -> bool {
+    if chunk.contains("://") || chunk.contains("::") {
+        return true;
+    }
+    if chunk.chars().any(|c| {
+        matches!(
+            c,
+            '@' | '/' | '\\' | '_' | '`' | '=' | '<' | '>' | '{' | '}'
+        )
+    }) {
+        return true;
+    }
+    // `example.com`, `std.io`, `e.g.`: a dot between two letters or digits.
+    let chars: Vec<char> = chunk.chars().collect();
+    chars
+        .windows(3)
+        .any(|w| w[1] == '.' && w[0].is_alphanumeric() && w[2].is_alphanumeric())
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '\u{2019}'
+}
+
+/// Split a run of letters at `camelCase` and `HTTPServer` boundaries.
+fn split_camel_case(run: &str, offset: usize, out: &mut Vec<(usize, String)>) {
+    let chars: Vec<(usize, char)> = run.char_indices().collect();
+    let mut start = 0;
+    for i in 1..chars.len() {
+        let (_, prev) = chars[i - 1];
+        let (_, cur) = chars[i];
+        let next_lower = chars.get(i + 1).is_some_and(|(_, c)| c.is_lowercase());
+        let boundary = (prev.is_lowercase() && cur.is_uppercase())
+            || (prev.is_uppercase() && cur.is_uppercase() && next_lower);
+        if boundary {
+            push_word(
+                &run[chars[start].0..chars[i].0],
+                offset + chars[start].0,
+                out,
+            );
+            start = i;
+        }
+    }
+    if let Some(&(byte, _)) = chars.get(start) {
+        push_word(&run[byte..], offset + byte, out);
+    }
+}
+
+/// Keep `word` unless it is a single letter or an acronym.
+fn push_word(word: &str, offset: usize, out: &mut Vec<(usize, String)>) {
+    let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+    let all_caps = word.chars().all(|c| !c.is_lowercase());
+    if letters > 1 && !all_caps {
+        out.push((offset, word.to_string()));
+    }
+}
+
+/// The words in `text` to spell check, each with its byte offset into
+/// `text`.
+pub fn words(text: &str) -> Vec<(usize, String)> {
+    let mut out = Vec::new();
+    let mut chunk_start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (c.is_whitespace(), chunk_start) {
+            (false, None) => chunk_start = Some(i),
+            (true, Some(start)) => {
+                words_in_chunk(&text[start..i], start, &mut out);
+                chunk_start = None;
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+fn words_in_chunk(chunk: &str, offset: usize, out: &mut Vec<(usize, String)>) {
+    if is_code_like(chunk) {
+        return;
+    }
+    let chars: Vec<(usize, char)> = chunk.char_indices().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].1.is_alphabetic() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        // Letters, with apostrophes allowed between two letters ("don't").
+        while i < chars.len()
+            && (chars[i].1.is_alphabetic()
+                || (is_apostrophe(chars[i].1)
+                    && chars.get(i + 1).is_some_and(|(_, c)| c.is_alphabetic())))
+        {
+            i += 1;
+        }
+        let touches_digit = |j: Option<usize>| {
+            j.and_then(|j| chars.get(j))
+                .is_some_and(|(_, c)| c.is_ascii_digit())
+        };
+        if touches_digit(start.checked_sub(1)) || touches_digit(Some(i)) {
+            continue;
+        }
+        let begin = chars[start].0;
+        let end = chars.get(i).map_or(chunk.len(), |(b, _)| *b);
+        split_camel_case(&chunk[begin..end], offset + begin, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(text: &str) -> Vec<String> {
+        words(text).into_iter().map(|(_, w)| w).collect()
+    }
+
+    #[test]
+    fn finds_words_with_offsets() {
+        assert_eq!(
+            words("// Teh quick, brown fox."),
+            vec![
+                (3, "Teh".to_string()),
+                (7, "quick".to_string()),
+                (14, "brown".to_string()),
+                (20, "fox".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_contractions() {
+        assert_eq!(texts("don't 'quoted' it’s"), ["don't", "quoted", "it’s"]);
+    }
+
+    #[test]
+    fn skips_code_like_chunks() {
+        assert_eq!(
+            texts("see https://exmaple.com or a@b.c in src/mian.rs with snake_caes and `cdoe`"),
+            ["see", "or", "in", "with", "and"]
+        );
+        assert_eq!(
+            texts("std::collections e.g. exmaple.com"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn skips_acronyms_single_letters_and_words_touching_digits() {
+        assert_eq!(texts("HTTP x utf8 3rd v2 ok"), ["ok"]);
+    }
+
+    #[test]
+    fn splits_camel_case() {
+        assert_eq!(
+            words("parseHTTPResponse"),
+            vec![(0, "parse".to_string()), (9, "Response".to_string())]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Tasks: named commands (a build, a linter, a test run) started from the
+//! editor, whose output problem matchers turn into diagnostics.
+//!
+//! This module holds what doesn't need the editor: reading the workspace
+//! task file, building the shell invocation and the
+//! [`problem_matcher`]s. Running tasks and publishing their problems lives
+//! in `app::task_actions`.
+
+pub mod problem_matcher;
+
+use std::collections::HashMap;
+
+use crate::types::TaskConfig;
+
+/// Workspace task file, relative to the workspace root. Same shape as the
+/// `tasks` setting: an object mapping task names to task definitions.
+pub const WORKSPACE_TASKS_PATH: &str = ".fresh/tasks.json";
+
+/// Parse the workspace task file. Comments and trailing commas are allowed,
+/// as in the config files.
+pub fn parse_tasks_file(contents: &str) -> Result<HashMap<String, TaskConfig>, String> {
+    let value = crate::config::parse_config_jsonc(contents).map_err(|e| e.to_string())?;
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+/// The program and arguments that run `task` through the shell, with its
+/// extra arguments quoted onto the command line and its environment
+/// exported first. The spawner API has no environment parameter, and an
+/// export reaches every command of a pipeline.
+pub fn shell_invocation(task: &TaskConfig) -> (String, Vec<String>) {
+    let mut line = String::new();
+    let mut env: Vec<_> = task.env.iter().collect();
+    env.sort();
+    for (name, value) in env {
+        line.push_str(&format!("export {}={}; ", name, shell_quote(value)));
+    }
+    line.push_str(&task.command);
+    for arg in &task.args {
+        line.push(' ');
+        line.push_str(&shell_quote(arg));
+    }
+    ("sh".to_string(), vec!["-c".to_string(), line])
+}
+
+/// Quote a single argument for POSIX `sh -c`.
+fn shell_quote(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars().all(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | '+' | ':' | '@' | '=')
+        })
+    {
+        s.to_string()
+    } else {
+        let escaped = s.replace('\'', "'\\''");
+        format!("'{}'", escaped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ProblemMatcherConfig, TaskPresentation};
+
+    #[test]
+    fn tasks_file_accepts_comments_and_builtin_or_custom_matchers() {
+        let tasks = parse_tasks_file(
+            r#"{
+                // Type-check in watch mode.
+                "watch": {
+                    "command": "tsc --watch",
+                    "background": true,
+                    "problem_matchers": ["tsc"],
+                },
+                "lint": {
+                    "command": "./lint.sh",
+                    "presentation": "terminal",
+                    "problem_matchers": [{ "pattern": ["^(?P<file>\\S+):(?P<line>\\d+) (?P<message>.*)$"] }]
+                }
+            }"#,
+        )
+        .unwrap();
+        assert!(tasks["watch"].background);
+        assert_eq!(
+            tasks["watch"].problem_matchers,
+            vec![ProblemMatcherConfig::Builtin("tsc".to_string())]
+        );
+        assert_eq!(tasks["lint"].presentation, TaskPresentation::Terminal);
+        assert!(matches!(
+            tasks["lint"].problem_matchers[0],
+            ProblemMatcherConfig::Custom(_)
+        ));
+    }
+
+    #[test]
+    fn shell_invocation_quotes_args_and_exports_env() {
+        let task = TaskConfig {
+            command: "cargo test".to_string(),
+            args: vec!["--".to_string(), "it's".to_string()],
+            env: HashMap::from([("RUST_LOG".to_string(), "debug x".to_string())]),
+            ..Default::default()
+        };
+        let (program, args) = shell_invocation(&task);
+        assert_eq!(program, "sh");
+        assert_eq!(
+            args,
+            vec![
+                "-c".to_string(),
+                "export RUST_LOG='debug x'; cargo test -- 'it'\\''s'".to_string()
+            ]
+        );
+    }
+}
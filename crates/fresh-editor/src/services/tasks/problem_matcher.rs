@@ -0,0 +1,399 @@
+//! Problem matchers: turn compiler, linter and test-runner output into
+//! located problems.
+//!
+//! A matcher is one or more regular expressions matched against consecutive
+//! output lines. Their named groups (`file`, `line`, `column`, `end_line`,
+//! `end_column`, `severity`, `code`, `message`) accumulate until the last
+//! pattern matches, which yields a problem. rustc prints the message on one
+//! line and the location on the next; eslint names a file once and then
+//! lists its problems, which `repeat_last` covers.
+
+use regex::{Captures, Regex};
+
+use crate::types::{CustomProblemMatcher, ProblemMatcherConfig, TaskProblemSeverity};
+
+/// Names of the built-in matchers, in the order they are offered.
+pub const BUILTIN_MATCHERS: &[&str] = &["rustc", "gcc", "tsc", "eslint", "pytest"];
+
+/// The definition of a built-in matcher.
+fn builtin(name: &str) -> Option<CustomProblemMatcher> {
+    let matcher = |pattern: &[&str]| CustomProblemMatcher {
+        pattern: pattern.iter().map(|p| p.to_string()).collect(),
+        ..Default::default()
+    };
+    Some(match name {
+        // `error[E0308]: mismatched types` / `  --> src/main.rs:4:18`
+        "rustc" => matcher(&[
+            r"^(?P<severity>error|warning)(?:\[(?P<code>[^\]]+)\])?: (?P<message>.+)$",
+            r"^\s*--> (?P<file>.+?):(?P<line>\d+):(?P<column>\d+)$",
+        ]),
+        // `src/main.c:3:5: error: expected ';' before 'return'`
+        "gcc" => matcher(&[
+            r"^(?P<file>[^\s:][^:]*):(?P<line>\d+):(?P<column>\d+):\s+(?:fatal\s+)?(?P<severity>error|warning|note):\s+(?P<message>.+)$",
+        ]),
+        // `src/a.ts(3,7): error TS2322: Type 'number' is not assignable ...`
+        "tsc" => CustomProblemMatcher {
+            begins: Some(r"Starting (?:incremental )?compilation".to_string()),
+            ends: Some(r"Found \d+ errors?\b".to_string()),
+            ..matcher(&[
+                r"^(?P<file>[^\s(][^(]*)\((?P<line>\d+),(?P<column>\d+)\):\s+(?P<severity>error|warning|info)\s+(?P<code>TS\d+)\s*:\s*(?P<message>.+)$",
+            ])
+        },
+        // The default "stylish" format: a file, then `  2:5  error  msg  rule`
+        // for each of its problems.
+        "eslint" => CustomProblemMatcher {
+            repeat_last: true,
+            ..matcher(&[
+                r"^(?P<file>\S.*)$",
+                r"^\s+(?P<line>\d+):(?P<column>\d+)\s+(?P<severity>error|warning|info)\s+(?P<message>.+?)(?:\s\s+(?P<code>\S+))?$",
+            ])
+        },
+        // `tests/test_app.py:12: AssertionError`
+        "pytest" => matcher(&[
+            r"^(?P<file>[^\s:][^:]*\.py):(?P<line>\d+): (?P<message>\w*(?:Error|Exception)\b.*|Failed\b.*)$",
+        ]),
+        _ => return None,
+    })
+}
+
+/// A problem found in task output. Lines and columns are 1-based, as the
+/// tools print them; `file` is as printed too, often relative to the
+/// task's working directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskProblem {
+    pub file: String,
+    pub line: u32,
+    pub column: Option<u32>,
+    pub end_line: Option<u32>,
+    pub end_column: Option<u32>,
+    pub severity: TaskProblemSeverity,
+    pub code: Option<String>,
+    pub message: String,
+}
+
+/// A line of a background task's output that starts or ends a cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    Begins,
+    Ends,
+}
+
+/// A compiled problem matcher.
+#[derive(Debug, Clone)]
+pub struct ProblemMatcher {
+    patterns: Vec<Regex>,
+    repeat_last: bool,
+    severity: TaskProblemSeverity,
+    begins: Option<Regex>,
+    ends: Option<Regex>,
+}
+
+impl ProblemMatcher {
+    /// Compile a configured matcher, resolving built-in names.
+    pub fn from_config(config: &ProblemMatcherConfig) -> Result<Self, String> {
+        match config {
+            ProblemMatcherConfig::Builtin(name) => {
+                let definition =
+                    builtin(name).ok_or_else(|| format!("unknown problem matcher '{}'", name))?;
+                Self::compile(&definition)
+            }
+            ProblemMatcherConfig::Custom(definition) => Self::compile(definition),
+        }
+    }
+
+    fn compile(definition: &CustomProblemMatcher) -> Result<Self, String> {
+        let compile = |pattern: &str| {
+            Regex::new(pattern).map_err(|e| format!("invalid pattern '{}': {}", pattern, e))
+        };
+        if definition.pattern.is_empty() {
+            return Err("a problem matcher needs at least one pattern".to_string());
+        }
+        Ok(Self {
+            patterns: definition
+                .pattern
+                .iter()
+                .map(|p| compile(p))
+                .collect::<Result<_, _>>()?,
+            repeat_last: definition.repeat_last,
+            severity: definition.severity,
+            begins: definition.begins.as_deref().map(compile).transpose()?,
+            ends: definition.ends.as_deref().map(compile).transpose()?,
+        })
+    }
+
+    /// Whether `line` starts or ends a background cycle.
+    pub fn boundary(&self, line: &str) -> Option<Boundary> {
+        if self.begins.as_ref().is_some_and(|re| re.is_match(line)) {
+            Some(Boundary::Begins)
+        } else if self.ends.as_ref().is_some_and(|re| re.is_match(line)) {
+            Some(Boundary::Ends)
+        } else {
+            None
+        }
+    }
+}
+
+/// Named groups captured so far for the problem being assembled.
+#[derive(Debug, Clone, Default)]
+struct Captured {
+    file: Option<String>,
+    line: Option<String>,
+    column: Option<String>,
+    end_line: Option<String>,
+    end_column: Option<String>,
+    severity: Option<String>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl Captured {
+    fn merge(&mut self, caps: &Captures) {
+        let fields = [
+            ("file", &mut self.file),
+            ("line", &mut self.line),
+            ("column", &mut self.column),
+            ("end_line", &mut self.end_line),
+            ("end_column", &mut self.end_column),
+            ("severity", &mut self.severity),
+            ("code", &mut self.code),
+            ("message", &mut self.message),
+        ];
+        for (name, field) in fields {
+            if let Some(m) = caps.name(name) {
+                *field = Some(m.as_str().to_string());
+            }
+        }
+    }
+
+    fn into_problem(self, default_severity: TaskProblemSeverity) -> Option<TaskProblem> {
+        let number = |s: Option<String>| s.and_then(|s| s.parse::<u32>().ok());
+        let file = self.file.map(|f| f.trim().to_string())?;
+        let line = number(self.line)?;
+        let severity = match self.severity.map(|s| s.to_ascii_lowercase()) {
+            Some(s) if s.starts_with("err") || s.starts_with("fatal") => TaskProblemSeverity::Error,
+            Some(s) if s.starts_with("warn") => TaskProblemSeverity::Warning,
+            Some(s) if s.starts_with("info") || s.starts_with("note") => TaskProblemSeverity::Info,
+            Some(s) if s.starts_with("hint") => TaskProblemSeverity::Hint,
+            _ => default_severity,
+        };
+        Some(TaskProblem {
+            file,
+            line,
+            column: number(self.column),
+            end_line: number(self.end_line),
+            end_column: number(self.end_column),
+            severity,
+            message: self
+                .message
+                .map(|m| m.trim().to_string())
+                .or_else(|| self.code.clone())
+                .unwrap_or_default(),
+            code: self.code,
+        })
+    }
+}
+
+/// Progress of one matcher through a stream of output lines.
+#[derive(Debug, Clone)]
+pub struct ProblemCollector {
+    matcher: ProblemMatcher,
+    /// Index of the pattern the next line must match; 0 when idle.
+    stage: usize,
+    /// Groups captured by the patterns before `stage`.
+    partial: Captured,
+}
+
+impl ProblemCollector {
+    pub fn new(matcher: ProblemMatcher) -> Self {
+        Self {
+            matcher,
+            stage: 0,
+            partial: Captured::default(),
+        }
+    }
+
+    /// The matcher this collector runs.
+    pub fn matcher(&self) -> &ProblemMatcher {
+        &self.matcher
+    }
+
+    /// Feed one line of output, returning the problem it completes.
+    pub fn feed(&mut self, line: &str) -> Option<TaskProblem> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let last = self.matcher.patterns.len() - 1;
+
+        if self.stage > 0 {
+            if let Some(caps) = self.matcher.patterns[self.stage].captures(line) {
+                let mut captured = self.partial.clone();
+                captured.merge(&caps);
+                if self.stage == last {
+                    // With `repeat_last` the groups before the last pattern
+                    // stay, ready for the next line.
+                    if !self.matcher.repeat_last {
+                        self.reset();
+                    }
+                    return captured.into_problem(self.matcher.severity);
+                }
+                self.partial = captured;
+                self.stage += 1;
+                return None;
+            }
+            // The sequence broke off; the line may start a new one.
+            self.reset();
+        }
+
+        let caps = self.matcher.patterns[0].captures(line)?;
+        let mut captured = Captured::default();
+        captured.merge(&caps);
+        if last == 0 {
+            return captured.into_problem(self.matcher.severity);
+        }
+        self.partial = captured;
+        self.stage = 1;
+        None
+    }
+
+    /// Drop a partially matched problem.
+    pub fn reset(&mut self) {
+        self.stage = 0;
+        self.partial = Captured::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(matcher: &str, output: &str) -> Vec<TaskProblem> {
+        let matcher =
+            ProblemMatcher::from_config(&ProblemMatcherConfig::Builtin(matcher.to_string()))
+                .unwrap();
+        let mut collector = ProblemCollector::new(matcher);
+        output.lines().filter_map(|l| collector.feed(l)).collect()
+    }
+
+    #[test]
+    fn rustc_pairs_the_message_with_the_location_line() {
+        let output = "\
+   Compiling demo v0.1.0
+error[E0308]: mismatched types
+ --> src/main.rs:4:18
+  |
+4 |     let x: u8 = \"a\";
+warning: unused variable: `y`
+  --> src/lib.rs:10:9
+error: could not compile `demo` due to previous error
+";
+        let problems = collect("rustc", output);
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].file, "src/main.rs");
+        assert_eq!((problems[0].line, problems[0].column), (4, Some(18)));
+        assert_eq!(problems[0].severity, TaskProblemSeverity::Error);
+        assert_eq!(problems[0].code.as_deref(), Some("E0308"));
+        assert_eq!(problems[0].message, "mismatched types");
+        assert_eq!(problems[1].severity, TaskProblemSeverity::Warning);
+        assert_eq!(problems[1].message, "unused variable: `y`");
+    }
+
+    #[test]
+    fn gcc_reads_single_line_diagnostics() {
+        let output = "\
+main.c: In function 'main':
+main.c:3:5: error: expected ';' before 'return'
+main.c:7:1: note: declared here
+";
+        let problems = collect("gcc", output);
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].message, "expected ';' before 'return'");
+        assert_eq!(problems[1].severity, TaskProblemSeverity::Info);
+    }
+
+    #[test]
+    fn tsc_reads_parenthesised_locations() {
+        let problems = collect(
+            "tsc",
+            "src/a.ts(3,7): error TS2322: Type 'number' is not assignable to type 'string'.",
+        );
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].file, "src/a.ts");
+        assert_eq!((problems[0].line, problems[0].column), (3, Some(7)));
+        assert_eq!(problems[0].code.as_deref(), Some("TS2322"));
+    }
+
+    #[test]
+    fn eslint_lists_several_problems_under_one_file() {
+        let output = "\
+/work/app.js
+  1:1   error    Unexpected console statement  no-console
+  4:10  warning  'x' is assigned a value but never used  no-unused-vars
+
+/work/other.js
+  2:3  error  Parsing error: Unexpected token
+
+✖ 3 problems (2 errors, 1 warning)
+";
+        let problems = collect("eslint", output);
+        assert_eq!(problems.len(), 3);
+        assert_eq!(problems[0].file, "/work/app.js");
+        assert_eq!(problems[0].message, "Unexpected console statement");
+        assert_eq!(problems[0].code.as_deref(), Some("no-console"));
+        assert_eq!(problems[1].file, "/work/app.js");
+        assert_eq!(problems[1].severity, TaskProblemSeverity::Warning);
+        assert_eq!(problems[2].file, "/work/other.js");
+        assert_eq!(problems[2].message, "Parsing error: Unexpected token");
+        assert_eq!(problems[2].code, None);
+    }
+
+    #[test]
+    fn pytest_reads_failure_locations_but_not_traceback_frames() {
+        let output = "\
+tests/test_app.py:8: in helper
+tests/test_app.py:12: AssertionError
+";
+        let problems = collect("pytest", output);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 12);
+        assert_eq!(problems[0].message, "AssertionError");
+    }
+
+    #[test]
+    fn custom_matcher_uses_its_default_severity() {
+        let config = ProblemMatcherConfig::Custom(CustomProblemMatcher {
+            pattern: vec![r"^(?P<file>\S+) line (?P<line>\d+): (?P<message>.*)$".to_string()],
+            severity: TaskProblemSeverity::Warning,
+            ..Default::default()
+        });
+        let mut collector = ProblemCollector::new(ProblemMatcher::from_config(&config).unwrap());
+        let problem = collector.feed("lint.sh line 2: quote this").unwrap();
+        assert_eq!(problem.severity, TaskProblemSeverity::Warning);
+        assert_eq!(problem.column, None);
+    }
+
+    #[test]
+    fn background_boundaries_come_from_the_matcher() {
+        let matcher =
+            ProblemMatcher::from_config(&ProblemMatcherConfig::Builtin("tsc".to_string())).unwrap();
+        assert_eq!(
+            matcher.boundary("12:00:01 - Starting incremental compilation..."),
+            Some(Boundary::Begins)
+        );
+        assert_eq!(
+            matcher.boundary("12:00:02 - Found 1 error. Watching for file changes."),
+            Some(Boundary::Ends)
+        );
+        assert_eq!(matcher.boundary("src/a.ts(1,1): error TS1: x"), None);
+    }
+
+    #[test]
+    fn unknown_builtins_and_bad_patterns_are_errors() {
+        assert!(
+            ProblemMatcher::from_config(&ProblemMatcherConfig::Builtin("nope".to_string()))
+                .is_err()
+        );
+        let bad = ProblemMatcherConfig::Custom(CustomProblemMatcher {
+            pattern: vec!["(".to_string()],
+            ..Default::default()
+        });
+        assert!(ProblemMatcher::from_config(&bad).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+//! Shell-integration command blocks.
+//!
+//! A shell set up for FinalTerm-style integration brackets every command with
+//! OSC 133 markers: `A` where the prompt starts, `B` where the typed command
+//! starts, `C` where its output starts and `D[;exit]` once it finished.
+//! `TerminalState` turns that stream into [`CommandBlock`]s, which drive
+//! prompt navigation, output selection / copy, re-run, the exit-status gutter
+//! and output folding in scroll-back mode.
+//!
+//! Every line number here is a *transcript* line: a 0-based logical line of
+//! the backing file, which is also the line number of the scroll-back buffer
+//! loaded from it. Lines still on the live screen get the number they will
+//! have once streamed, so a block never needs rebasing as output scrolls.
+//!
+//! Blocks are kept next to the backing file (see [`blocks_path`]) so they
+//! survive the same round trips the transcript does: scroll-back re-entry,
+//! workspace restore, respawn after exit.
+
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Blocks retained per terminal; the oldest are dropped past this.
+pub const MAX_COMMAND_BLOCKS: usize = 1000;
+
+/// One prompt and the command run from it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandBlock {
+    /// Line the prompt starts on (OSC 133 `A`).
+    pub prompt_line: usize,
+    /// First output line (OSC 133 `C`); `None` until a command runs.
+    #[serde(default)]
+    pub output_line: Option<usize>,
+    /// Line after the last output line; `None` while the command runs.
+    #[serde(default)]
+    pub end_line: Option<usize>,
+    /// The command line as typed, when it fit on one line.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Exit status reported with OSC 133 `D`, if the shell sent one.
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+}
+
+impl CommandBlock {
+    /// A block for a prompt that hasn't run a command yet.
+    pub fn new(prompt_line: usize) -> Self {
+        Self {
+            prompt_line,
+            output_line: None,
+            end_line: None,
+            command: None,
+            exit_code: None,
+        }
+    }
+
+    /// Whether the command has finished (or its output was cut short by the
+    /// next prompt).
+    pub fn is_finished(&self) -> bool {
+        self.end_line.is_some()
+    }
+
+    /// The output lines, when a command ran. A command still running reaches
+    /// up to `last_line` (exclusive), the end of the transcript.
+    pub fn output_range(&self, last_line: usize) -> Option<Range<usize>> {
+        let start = self.output_line?;
+        let end = self.end_line.unwrap_or(last_line).max(start);
+        Some(start..end)
+    }
+}
+
+/// The block `line` belongs to: the last one whose prompt is at or above it.
+pub fn block_at(blocks: &[CommandBlock], line: usize) -> Option<&CommandBlock> {
+    let after = blocks.partition_point(|b| b.prompt_line <= line);
+    after.checked_sub(1).map(|i| &blocks[i])
+}
+
+/// The nearest prompt line above `line`.
+pub fn previous_prompt(blocks: &[CommandBlock], line: usize) -> Option<usize> {
+    let before = blocks.partition_point(|b| b.prompt_line < line);
+    before.checked_sub(1).map(|i| blocks[i].prompt_line)
+}
+
+/// The nearest prompt line below `line`.
+pub fn next_prompt(blocks: &[CommandBlock], line: usize) -> Option<usize> {
+    let after = blocks.partition_point(|b| b.prompt_line <= line);
+    blocks.get(after).map(|b| b.prompt_line)
+}
+
+/// The most recent block whose command has finished.
+pub fn last_finished(blocks: &[CommandBlock]) -> Option<&CommandBlock> {
+    blocks
+        .iter()
+        .rev()
+        .find(|b| b.output_line.is_some() && b.is_finished())
+}
+
+/// Where the blocks of the transcript at `backing_path` are kept:
+/// `fresh-terminal-3.txt` → `fresh-terminal-3.blocks.json`.
+pub fn blocks_path(backing_path: &Path) -> PathBuf {
+    backing_path.with_extension("blocks.json")
+}
+
+/// Read the blocks saved for a transcript. A missing or unreadable file
+/// yields no blocks — the transcript itself is still usable.
+pub fn load_blocks(path: &Path) -> Vec<CommandBlock> {
+    let Ok(contents) = std::fs::read(path) else {
+        return Vec::new();
+    };
+    serde_json::from_slice(&contents).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring unreadable command blocks {:?}: {}", path, e);
+        Vec::new()
+    })
+}
+
+/// Save the blocks of a transcript, replacing what was there.
+pub fn save_blocks(path: &Path, blocks: &[CommandBlock]) -> io::Result<()> {
+    let json = serde_json::to_vec(blocks).map_err(io::Error::other)?;
+    std::fs::write(path, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(prompt: usize, output: usize, end: usize) -> CommandBlock {
+        CommandBlock {
+            prompt_line: prompt,
+            output_line: Some(output),
+            end_line: Some(end),
+            command: Some("ls".to_string()),
+            exit_code: Some(0),
+        }
+    }
+
+    #[test]
+    fn lookups_follow_prompt_lines() {
+        let blocks = vec![block(0, 1, 4), block(4, 5, 9), CommandBlock::new(9)];
+
+        assert_eq!(block_at(&blocks, 2).map(|b| b.prompt_line), Some(0));
+        assert_eq!(block_at(&blocks, 4).map(|b| b.prompt_line), Some(4));
+        assert_eq!(block_at(&blocks, 20).map(|b| b.prompt_line), Some(9));
+
+        assert_eq!(previous_prompt(&blocks, 4), Some(0));
+        assert_eq!(previous_prompt(&blocks, 5), Some(4));
+        assert_eq!(previous_prompt(&blocks, 0), None);
+        assert_eq!(next_prompt(&blocks, 4), Some(9));
+        assert_eq!(next_prompt(&blocks, 9), None);
+
+        assert_eq!(last_finished(&blocks).map(|b| b.prompt_line), Some(4));
+    }
+
+    #[test]
+    fn running_command_output_reaches_the_end() {
+        let mut running = block(3, 4, 0);
+        running.end_line = None;
+        assert_eq!(running.output_range(10), Some(4..10));
+        assert_eq!(block(0, 1, 3).output_range(10), Some(1..3));
+        assert_eq!(CommandBlock::new(0).output_range(10), None);
+    }
+
+    #[test]
+    fn blocks_round_trip_through_the_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        let backing = dir.path().join("fresh-terminal-3.txt");
+        let path = blocks_path(&backing);
+        assert_eq!(path.file_name().unwrap(), "fresh-terminal-3.blocks.json");
+
+        assert!(load_blocks(&path).is_empty());
+        let blocks = vec![block(0, 1, 4), CommandBlock::new(4)];
+        save_blocks(&path, &blocks).unwrap();
+        assert_eq!(load_blocks(&path), blocks);
+    }
+}
@@ -17,7 +17,10 @@
 //! it calls `process_output()` to update the terminal grid, then `flush_new_scrollback()`
 //! to append any new scrollback lines to the backing file. This ensures scrollback is
 //! written incrementally as lines scroll off screen, avoiding O(n) work on mode switches.
+//! Command blocks recorded from OSC 133 markers are saved beside the backing file
+//! whenever they change.
 
+use super::command_blocks;
 use super::term::TerminalState;
 use crate::services::async_bridge::AsyncBridge;
 use crate::services::authority::TerminalWrapper;
@@ -437,6 +440,13 @@ impl TerminalManager {
 
         let log_writer = open_log_writer(log_path.as_deref(), backing_mode);
         let backing_writer = open_backing_writer(backing_path.as_deref(), backing_mode);
+        let blocks_path = backing_path.as_deref().map(command_blocks::blocks_path);
+        if let (BackingMode::Fresh, Some(p)) = (backing_mode, blocks_path.as_ref()) {
+            // Blocks an earlier terminal left on this path describe its
+            // transcript, not the one starting now.
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = std::fs::remove_file(p);
+        }
 
         // Tag output/exit with the owning window so the main loop never has to
         // guess which session a `Terminal-N` belongs to (ids collide across
@@ -455,6 +465,8 @@ impl TerminalManager {
             state: state.clone(),
             response_tx: command_tx.clone(),
             backing_writer,
+            resume_from: backing_path.filter(|_| backing_mode == BackingMode::Continue),
+            blocks_path,
             log_writer,
             async_bridge: self.async_bridge.clone(),
             wt_id: wt_id.clone(),
@@ -804,6 +816,11 @@ struct ReaderLoop {
     response_tx: mpsc::Sender<TerminalCommand>,
     /// Incremental scrollback stream (rendered lines), if a backing file is set.
     backing_writer: Option<std::io::BufWriter<std::fs::File>>,
+    /// Backing file of a continuing terminal, whose lines and command blocks
+    /// are picked up before the first read.
+    resume_from: Option<std::path::PathBuf>,
+    /// Where the command blocks are saved, if a backing file is set.
+    blocks_path: Option<std::path::PathBuf>,
     /// Raw byte log for session-restore replay, if a log file is set.
     log_writer: Option<std::io::BufWriter<std::fs::File>>,
     async_bridge: Option<AsyncBridge>,
@@ -816,6 +833,9 @@ impl ReaderLoop {
     /// Drain the PTY until EOF or error, then mark the terminal dead and flush.
     fn run(mut self) {
         tracing::debug!("Terminal {:?} reader thread started", self.terminal_id);
+        if let Some(path) = self.resume_from.take() {
+            self.resume_transcript(&path);
+        }
         let mut buf = [0u8; 4096];
         let mut total_bytes = 0usize;
         loop {
@@ -909,6 +929,36 @@ impl ReaderLoop {
                 }
             }
         }
+
+        let blocks = state
+            .take_command_blocks_changed()
+            .then(|| state.command_blocks().to_vec());
+        drop(state);
+        if let (Some(blocks), Some(path)) = (blocks, self.blocks_path.as_ref()) {
+            if let Err(e) = command_blocks::save_blocks(path, &blocks) {
+                tracing::warn!("Terminal command blocks write error: {}", e);
+            }
+        }
+    }
+
+    /// Pick up the transcript a continuing terminal appends to: count its
+    /// lines, so new command blocks are numbered after them, and restore the
+    /// blocks saved with it. Runs on the reader thread since the count reads
+    /// the whole file.
+    fn resume_transcript(&self, backing_path: &std::path::Path) {
+        let lines = match std::fs::File::open(backing_path) {
+            Ok(file) => count_lines(std::io::BufReader::new(file)),
+            Err(_) => return,
+        };
+        let blocks = self
+            .blocks_path
+            .as_deref()
+            .map(command_blocks::load_blocks)
+            .unwrap_or_default();
+        if let Ok(mut state) = self.state.lock() {
+            state.set_transcript_lines(lines);
+            state.set_command_blocks(blocks);
+        }
     }
 
     /// Append raw bytes to the session log (for restore replay), if enabled.
@@ -940,6 +990,20 @@ impl ReaderLoop {
     }
 }
 
+/// Count the newline-terminated lines `reader` yields.
+fn count_lines(mut reader: impl std::io::BufRead) -> usize {
+    let mut lines = 0;
+    loop {
+        let chunk = match reader.fill_buf() {
+            Ok([]) | Err(_) => return lines,
+            Ok(chunk) => chunk,
+        };
+        lines += chunk.iter().filter(|&&b| b == b'\n').count();
+        let len = chunk.len();
+        reader.consume(len);
+    }
+}
+
 impl Drop for TerminalManager {
     fn drop(&mut self) {
         self.shutdown_all();
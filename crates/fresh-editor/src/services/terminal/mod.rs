@@ -40,13 +40,22 @@
 //! The `backing_file_history_end` offset marks where scrollback ends, used for truncation
 //! when re-entering terminal mode.
 //!
+//! ## Command Blocks
+//!
+//! OSC 133 shell-integration markers are recorded as command blocks addressed by
+//! backing-file line, so they line up with the scrollback buffer. They are kept in
+//! a `fresh-terminal-{id}.blocks.json` file beside the backing file, saved by the
+//! read loop whenever they change and read back wherever the backing file is.
+//!
 //! ## Module Responsibilities
 //!
 //! - `term.rs`: Terminal state and incremental streaming methods
+//! - `command_blocks.rs`: Command blocks and their on-disk form
 //! - `manager.rs`: PTY lifecycle and read loop with streaming
 //! - `../app/terminal.rs`: Mode switching logic
 //! - `../app/session.rs`: Session save/restore integration
 
+pub mod command_blocks;
 pub mod manager;
 pub mod path_link;
 pub mod pty;
@@ -54,6 +63,7 @@ pub mod term;
 #[cfg(windows)]
 pub mod windows_shell;
 
+pub use command_blocks::CommandBlock;
 pub use manager::{detect_shell, BackingMode, TerminalId, TerminalManager};
 pub use term::{PrependedHead, TerminalCell, TerminalState};
 #[cfg(windows)]
@@ -17,6 +17,8 @@
 //! - `flush_new_scrollback`: Stream new scrollback lines to backing file
 //! - `append_visible_screen`: Append visible screen on mode exit
 //! - `backing_file_history_end`: Get truncation point for mode re-entry
+//! - `command_blocks`: OSC 133 command blocks recorded so far
+//! - `take_tapped_lines`: plain-text output lines for a task's problem matchers
 //!
 //! ## State Tracking
 //!
@@ -35,7 +37,13 @@
 //!
 //! `backing_file_history_end` tracks the byte offset where scrollback ends in the
 //! backing file, used for truncation when re-entering terminal mode.
+//!
+//! `transcript_lines` counts the logical lines in the backing file. Together with
+//! the unstreamed rows it places the cursor on a backing-file line, which is how
+//! OSC 133 markers become [`CommandBlock`]s that line up with the scroll-back
+//! buffer.
 
+use super::command_blocks::{CommandBlock, MAX_COMMAND_BLOCKS};
 use alacritty_terminal::event::{Event, EventListener};
 use alacritty_terminal::grid::Scroll;
 use alacritty_terminal::index::{Column, Line};
@@ -231,6 +239,10 @@ const OSC_ACTIVITY_MAX_PAYLOAD: usize = 64;
 ///
 /// A program that emits neither leaves [`Self::activity`] at `None`, and the
 /// caller keeps its output-timing heuristic unchanged.
+///
+/// The OSC 133 markers are also reported to the caller with their position in
+/// the chunk, so `TerminalState` can record them as command blocks against
+/// the grid as it stood when each one arrived.
 #[derive(Debug, Default)]
 struct OscActivityScanner {
     /// How many bytes of the `ESC ]` introducer have matched (0..2).
@@ -249,19 +261,20 @@ struct OscActivityScanner {
 
 impl OscActivityScanner {
     /// Feed one chunk of PTY output, updating `seen` / `running` for every
-    /// recognised sequence that completes within it.
-    fn feed(&mut self, data: &[u8]) {
-        for &byte in data {
+    /// recognised sequence that completes within it. Each OSC 133 marker is
+    /// pushed to `marks` with the offset just past its terminator.
+    fn feed(&mut self, data: &[u8], marks: &mut Vec<(usize, ShellMark)>) {
+        for (i, &byte) in data.iter().enumerate() {
             if self.collecting {
                 if self.saw_esc {
                     self.saw_esc = false;
                     if byte == b'\\' {
-                        self.finish();
+                        marks.extend(self.finish().map(|mark| (i + 1, mark)));
                     } else {
                         self.reset();
                     }
                 } else if byte == 0x07 {
-                    self.finish();
+                    marks.extend(self.finish().map(|mark| (i + 1, mark)));
                 } else if byte == 0x1b {
                     self.saw_esc = true;
                 } else if self.buf.len() >= OSC_ACTIVITY_MAX_PAYLOAD {
@@ -285,8 +298,10 @@ impl OscActivityScanner {
     }
 
     /// Classify a completed OSC payload (the text between `ESC ]` and its
-    /// terminator) and reset to searching. Unrecognised payloads are ignored.
-    fn finish(&mut self) {
+    /// terminator) and reset to searching, returning the OSC 133 marker it
+    /// was, if any. Unrecognised payloads are ignored.
+    fn finish(&mut self) -> Option<ShellMark> {
+        let mut mark = None;
         if let Ok(s) = std::str::from_utf8(&self.buf) {
             if let Some(rest) = s.strip_prefix("133;") {
                 match rest.as_bytes().first().copied() {
@@ -294,14 +309,27 @@ impl OscActivityScanner {
                     Some(b'C') => {
                         self.seen = true;
                         self.running = true;
+                        mark = Some(ShellMark::OutputStart);
+                    }
+                    // Command finished → idle.
+                    Some(b'D') => {
+                        self.seen = true;
+                        self.running = false;
+                        let exit_code = rest
+                            .strip_prefix("D;")
+                            .and_then(|code| code.split(';').next())
+                            .and_then(|code| code.parse().ok());
+                        mark = Some(ShellMark::CommandFinished(exit_code));
                     }
-                    // Command finished, or a fresh prompt → idle / ready.
-                    Some(b'D') | Some(b'A') => {
+                    // A fresh prompt → idle / ready.
+                    Some(b'A') => {
                         self.seen = true;
                         self.running = false;
+                        mark = Some(ShellMark::PromptStart);
                     }
-                    // 'B' (command line entered, pre-exec) and anything else
-                    // aren't a running/idle edge on their own.
+                    // 'B' (command line entered, pre-exec) isn't a
+                    // running/idle edge on its own.
+                    Some(b'B') => mark = Some(ShellMark::CommandStart),
                     _ => {}
                 }
             } else if let Some(rest) = s.strip_prefix("9;4;") {
@@ -321,6 +349,7 @@ impl OscActivityScanner {
             }
         }
         self.reset();
+        mark
     }
 
     fn reset(&mut self) {
@@ -342,6 +371,19 @@ impl OscActivityScanner {
     }
 }
 
+/// An OSC 133 shell-integration marker, in the order a shell emits them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ShellMark {
+    /// `A`: a prompt is about to be drawn.
+    PromptStart,
+    /// `B`: the prompt ended; the command line is typed from here.
+    CommandStart,
+    /// `C`: the command was entered; its output starts here.
+    OutputStart,
+    /// `D[;exit]`: the command finished, with its exit status if reported.
+    CommandFinished(Option<i32>),
+}
+
 /// Parse the payload of an OSC 7 sequence into a working-directory path.
 ///
 /// The conventional payload is a `file://host/path` URI (the host is usually
@@ -481,6 +523,29 @@ pub struct TerminalState {
     /// lifecycle, OSC 9;4 progress) — the emulator drops these, so we sniff
     /// them from the raw stream too. Drives the workspace working/idle dot.
     osc_activity: OscActivityScanner,
+    /// Logical lines in the backing file: the transcript line the next
+    /// streamed line lands on. Seeded from the file when a terminal continues
+    /// its transcript, then advanced by every line `flush_new_scrollback` writes.
+    transcript_lines: usize,
+    /// Command blocks recorded from OSC 133 markers, oldest first.
+    command_blocks: Vec<CommandBlock>,
+    /// Transcript line and cell offset within it of the last `133;B`, where
+    /// the command line the next `133;C` runs was typed.
+    command_start: Option<(usize, usize)>,
+    /// `command_blocks` changed since `take_command_blocks_changed`.
+    command_blocks_changed: bool,
+    /// Output lines collected for `take_tapped_lines`, once `tap_lines` was
+    /// called.
+    line_tap: Option<LineTap>,
+}
+
+/// Plain-text output lines not yet taken by `take_tapped_lines`.
+#[derive(Debug, Default)]
+struct LineTap {
+    /// Lines streamed to the backing file since the last take.
+    streamed: Vec<String>,
+    /// Transcript line the next taken line is.
+    next_line: usize,
 }
 
 /// What `append_visible_screen` re-attached ahead of the first visible
@@ -538,6 +603,11 @@ impl TerminalState {
             cwd: None,
             osc7: Osc7Scanner::default(),
             osc_activity: OscActivityScanner::default(),
+            transcript_lines: 0,
+            command_blocks: Vec::new(),
+            command_start: None,
+            command_blocks_changed: false,
+            line_tap: None,
         }
     }
 
@@ -586,10 +656,19 @@ impl TerminalState {
         }
 
         // Sniff activity markers (OSC 133 / OSC 9;4) out of the same raw
-        // stream — the emulator discards them too.
-        self.osc_activity.feed(data);
-
-        self.parser.advance(&mut self.term, data);
+        // stream — the emulator discards them too. Each OSC 133 marker is
+        // recorded once the emulator has caught up to it, so the cursor is
+        // where the shell put the marker.
+        let mut marks = Vec::new();
+        self.osc_activity.feed(data, &mut marks);
+
+        let mut fed = 0;
+        for (end, mark) in marks {
+            self.parser.advance(&mut self.term, &data[fed..end]);
+            fed = end;
+            self.record_shell_mark(mark);
+        }
+        self.parser.advance(&mut self.term, &data[fed..]);
         // The parser may have emitted OSC title events (0/1/2) into the
         // listener's pending slot during `advance`. Apply the latest so
         // the stored title reflects what the program requested.
@@ -1005,8 +1084,22 @@ impl TerminalState {
             }
             // Row k ends a logical line spanning rows [line_start ..= k].
             self.write_logical_line(writer, line_start, k, history)?;
+            if self
+                .line_tap
+                .as_ref()
+                .is_some_and(|tap| tap.next_line <= self.transcript_lines)
+            {
+                let text = self.plain_text(
+                    Line(-((history - line_start) as i32)),
+                    Line(-((history - k) as i32)),
+                );
+                if let Some(tap) = self.line_tap.as_mut() {
+                    tap.streamed.push(text);
+                }
+            }
             written += 1;
             self.synced_logical_lines += 1;
+            self.transcript_lines += 1;
             k += 1;
             self.synced_history_lines = k;
             line_start = k;
@@ -1148,6 +1241,24 @@ impl TerminalState {
             .contains(Flags::WRAPLINE)
     }
 
+    /// Rows `first..=last` as one line of plain text, trailing blanks dropped.
+    fn plain_text(&self, first: Line, last: Line) -> String {
+        use alacritty_terminal::term::cell::Flags;
+
+        let grid = self.term.grid();
+        let mut text = String::with_capacity((last.0 - first.0 + 1) as usize * self.cols as usize);
+        for row in first.0..=last.0 {
+            for col in 0..self.cols as usize {
+                let cell = &grid[Line(row)][Column(col)];
+                if !cell.flags.contains(Flags::WIDE_CHAR_SPACER) {
+                    text.push(cell.c);
+                }
+            }
+        }
+        text.truncate(text.trim_end().len());
+        text
+    }
+
     /// Write history rows `line_start..=line_end` (oldest-relative `k` indices,
     /// with `history` the current history size) as one joined logical line.
     fn write_logical_line<W: Write>(
@@ -1338,6 +1449,241 @@ impl TerminalState {
         self.synced_logical_lines = 0;
         self.pending_reflow_resync = false;
         self.backing_file_history_end = 0;
+        self.transcript_lines = 0;
+        self.command_blocks.clear();
+        self.command_start = None;
+        self.command_blocks_changed = true;
+    }
+
+    // =========================================================================
+    // Shell-integration command blocks
+    // =========================================================================
+
+    /// Number of logical lines already in the backing file.
+    pub fn transcript_lines(&self) -> usize {
+        self.transcript_lines
+    }
+
+    /// Seed the backing file's line count when continuing an existing
+    /// transcript, so new blocks number their lines after it.
+    pub fn set_transcript_lines(&mut self, lines: usize) {
+        self.transcript_lines = lines;
+    }
+
+    /// Command blocks recorded so far, oldest first, in transcript lines.
+    pub fn command_blocks(&self) -> &[CommandBlock] {
+        &self.command_blocks
+    }
+
+    /// Replace the recorded blocks (the ones saved with a continued
+    /// transcript).
+    pub fn set_command_blocks(&mut self, blocks: Vec<CommandBlock>) {
+        self.command_blocks = blocks;
+        self.command_start = None;
+    }
+
+    /// Whether the blocks changed since the last call, clearing the flag.
+    pub fn take_command_blocks_changed(&mut self) -> bool {
+        std::mem::take(&mut self.command_blocks_changed)
+    }
+
+    // =========================================================================
+    // Output lines for task problem matchers
+    // =========================================================================
+
+    /// Start collecting output lines for `take_tapped_lines`, from the next
+    /// transcript line on.
+    pub fn tap_lines(&mut self) {
+        self.line_tap = Some(LineTap {
+            streamed: Vec::new(),
+            next_line: self.transcript_lines,
+        });
+    }
+
+    /// Output lines completed since the last call, as plain text: those
+    /// streamed to the backing file, then those finished on screen above the
+    /// cursor's line. With `to_end` (the process exited) the rest of the
+    /// screen is taken too, less trailing blank lines. Empty until
+    /// `tap_lines` is called.
+    pub fn take_tapped_lines(&mut self, to_end: bool) -> Vec<String> {
+        use alacritty_terminal::grid::Dimensions;
+
+        let Some(mut tap) = self.line_tap.take() else {
+            return Vec::new();
+        };
+        let mut lines = std::mem::take(&mut tap.streamed);
+        tap.next_line = tap.next_line.max(self.transcript_lines);
+
+        // A full-screen program's alternate screen never reaches the
+        // transcript, so only what was streamed counts while it is up.
+        if !self.term.mode().contains(TermMode::ALT_SCREEN) {
+            let history = self.term.grid().history_size();
+            let mut row = self.synced_history_lines.min(history) as i32 - history as i32;
+            let last_row = if to_end {
+                self.rows as i32 - 1
+            } else {
+                self.logical_line_start(self.term.grid().cursor.point.line.0) - 1
+            };
+            let mut line = self.transcript_lines;
+            while row <= last_row {
+                let start = row;
+                while row < last_row && self.row_wraps(Line(row)) {
+                    row += 1;
+                }
+                if line >= tap.next_line {
+                    lines.push(self.plain_text(Line(start), Line(row)));
+                    tap.next_line = line + 1;
+                }
+                line += 1;
+                row += 1;
+            }
+        }
+        if to_end {
+            while lines.last().is_some_and(|line| line.is_empty()) {
+                lines.pop();
+            }
+        }
+        self.line_tap = Some(tap);
+        lines
+    }
+
+    /// Apply one OSC 133 marker at the current cursor position. Markers a
+    /// full-screen program sends on the alternate screen are ignored: nothing
+    /// there reaches the transcript.
+    fn record_shell_mark(&mut self, mark: ShellMark) {
+        if self.term.mode().contains(TermMode::ALT_SCREEN) {
+            return;
+        }
+        let (line, offset) = self.cursor_transcript_position();
+        let cursor = self.term.grid().cursor.point;
+        // A marker mid-line belongs to that line; what follows it starts on
+        // the next one.
+        let next_line = if cursor.column.0 == 0 { line } else { line + 1 };
+
+        match mark {
+            ShellMark::PromptStart => {
+                match self.command_blocks.last_mut() {
+                    // A prompt that never ran a command (an empty Enter,
+                    // Ctrl+C) isn't worth keeping.
+                    Some(last) if last.output_line.is_none() => {
+                        self.command_blocks.pop();
+                    }
+                    // No `D`: the output ends where the new prompt starts.
+                    Some(last) if last.end_line.is_none() => {
+                        last.end_line = Some(next_line.max(last.output_line.unwrap_or(0)));
+                    }
+                    _ => {}
+                }
+                self.command_blocks.push(CommandBlock::new(line));
+                let excess = self.command_blocks.len().saturating_sub(MAX_COMMAND_BLOCKS);
+                self.command_blocks.drain(..excess);
+                self.command_start = None;
+            }
+            ShellMark::CommandStart => {
+                self.command_start = Some((line, offset));
+                return;
+            }
+            ShellMark::OutputStart => {
+                let start = self.command_start.take();
+                let command = start.and_then(|start| self.command_text(start, next_line));
+                let Some(block) = self
+                    .command_blocks
+                    .last_mut()
+                    .filter(|b| b.output_line.is_none())
+                else {
+                    return;
+                };
+                block.output_line = Some(next_line);
+                block.command = command;
+            }
+            ShellMark::CommandFinished(exit_code) => {
+                let Some(block) = self
+                    .command_blocks
+                    .last_mut()
+                    .filter(|b| b.end_line.is_none())
+                else {
+                    return;
+                };
+                let Some(output_line) = block.output_line else {
+                    return;
+                };
+                block.end_line = Some(next_line.max(output_line));
+                block.exit_code = exit_code;
+            }
+        }
+        self.command_blocks_changed = true;
+    }
+
+    /// The cursor's transcript line and its cell offset within that logical
+    /// line: lines already in the backing file, plus the logical lines that
+    /// end in unstreamed history or on screen above the cursor.
+    fn cursor_transcript_position(&self) -> (usize, usize) {
+        use alacritty_terminal::grid::Dimensions;
+
+        let history = self.term.grid().history_size();
+        let mut line = self.transcript_lines;
+        for k in self.synced_history_lines.min(history)..history {
+            if !self.row_wraps(Line(-((history - k) as i32))) {
+                line += 1;
+            }
+        }
+        let cursor = self.term.grid().cursor.point;
+        for row in 0..cursor.line.0 {
+            if !self.row_wraps(Line(row)) {
+                line += 1;
+            }
+        }
+        let start = self.logical_line_start(cursor.line.0);
+        let offset = (cursor.line.0 - start) as usize * self.cols as usize + cursor.column.0;
+        (line, offset)
+    }
+
+    /// The first row of the logical line `row` is part of, following wrapped
+    /// rows up into history.
+    fn logical_line_start(&self, mut row: i32) -> i32 {
+        use alacritty_terminal::grid::Dimensions;
+
+        let top = -(self.term.grid().history_size() as i32);
+        while row > top && self.row_wraps(Line(row - 1)) {
+            row -= 1;
+        }
+        row
+    }
+
+    /// The command typed after the `B` marker at `start` (transcript line,
+    /// cell offset), now that its output begins on `output_line`. Only a
+    /// command that fits on one logical line is recovered; a continued one
+    /// would carry its continuation prompts along.
+    fn command_text(&self, (line, offset): (usize, usize), output_line: usize) -> Option<String> {
+        use alacritty_terminal::grid::Dimensions;
+        use alacritty_terminal::term::cell::Flags;
+
+        if output_line != line + 1 {
+            return None;
+        }
+        let cursor = self.term.grid().cursor.point;
+        let last_row = if cursor.column.0 == 0 {
+            self.logical_line_start(cursor.line.0) - 1
+        } else {
+            cursor.line.0
+        };
+        if last_row < -(self.term.grid().history_size() as i32) {
+            return None;
+        }
+        let grid = self.term.grid();
+        let mut text = String::new();
+        let mut index = 0;
+        for row in self.logical_line_start(last_row)..=last_row {
+            for col in 0..self.cols as usize {
+                let cell = &grid[Line(row)][Column(col)];
+                if index >= offset && !cell.flags.contains(Flags::WIDE_CHAR_SPACER) {
+                    text.push(cell.c);
+                }
+                index += 1;
+            }
+        }
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_string())
     }
 }
 
@@ -1517,6 +1863,102 @@ mod tests {
         assert_eq!(state.osc_activity(), Some(true));
     }
 
+    /// Drive one shell-integrated command: prompt, typed command, output
+    /// lines, exit status.
+    fn run_marked_command(state: &mut TerminalState, command: &str, output: &[&str], exit: i32) {
+        state.process_output(b"\x1b]133;A\x07$ \x1b]133;B\x07");
+        state.process_output(format!("{}\r\n\x1b]133;C\x07", command).as_bytes());
+        for line in output {
+            state.process_output(format!("{}\r\n", line).as_bytes());
+        }
+        state.process_output(format!("\x1b]133;D;{}\x07", exit).as_bytes());
+    }
+
+    #[test]
+    fn osc_133_markers_record_command_blocks() {
+        let mut state = TerminalState::new(80, 24);
+        run_marked_command(&mut state, "ls", &["a", "b"], 0);
+        // Enter on an empty prompt: no command ran, so no block is kept.
+        state.process_output(b"\x1b]133;A\x07$ \x1b]133;B\x07\r\n");
+        run_marked_command(&mut state, "false", &[], 1);
+        state.process_output(b"\x1b]133;A\x07$ \x1b]133;B\x07");
+        assert!(state.take_command_blocks_changed());
+
+        let blocks = state.command_blocks();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].prompt_line, 0);
+        assert_eq!(blocks[0].command.as_deref(), Some("ls"));
+        assert_eq!(blocks[0].output_range(0), Some(1..3));
+        assert_eq!(blocks[0].exit_code, Some(0));
+        assert_eq!(blocks[1].prompt_line, 4);
+        assert_eq!(blocks[1].command.as_deref(), Some("false"));
+        assert_eq!(blocks[1].output_range(0), Some(5..5));
+        assert_eq!(blocks[1].exit_code, Some(1));
+        // The prompt waiting for input.
+        assert_eq!(blocks[2].prompt_line, 5);
+        assert_eq!(blocks[2].output_line, None);
+    }
+
+    #[test]
+    fn osc_133_blocks_line_up_with_the_backing_file() {
+        let mut state = TerminalState::new(40, 5);
+        let mut sink = Vec::new();
+        // Output long enough to scroll through history, flushed as the PTY
+        // reader does after every read.
+        let output: Vec<String> = (0..12).map(|i| format!("out {}", i)).collect();
+        let output: Vec<&str> = output.iter().map(String::as_str).collect();
+        state.process_output(b"\x1b]133;A\x07$ \x1b]133;B\x07echo ");
+        state.flush_new_scrollback(&mut sink).unwrap();
+        state.process_output(b"many\r\n\x1b]133;C\x07");
+        for line in &output {
+            state.process_output(format!("{}\r\n", line).as_bytes());
+            state.flush_new_scrollback(&mut sink).unwrap();
+        }
+        state.process_output(b"\x1b]133;D;0\x07\x1b]133;A\x07$ ");
+        state.flush_new_scrollback(&mut sink).unwrap();
+        state.append_visible_screen(&mut sink).unwrap();
+
+        let text = String::from_utf8(sink).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        let blocks = state.command_blocks();
+        assert_eq!(blocks[0].command.as_deref(), Some("echo many"));
+        assert_eq!(lines[blocks[0].prompt_line], "$ echo many");
+        let range = blocks[0].output_range(lines.len()).unwrap();
+        assert_eq!(&lines[range], output.as_slice());
+        assert_eq!(lines[blocks[1].prompt_line], "$");
+    }
+
+    #[test]
+    fn osc_133_blocks_continue_a_seeded_transcript() {
+        let mut state = TerminalState::new(80, 24);
+        state.set_transcript_lines(100);
+        run_marked_command(&mut state, "pwd", &["/tmp"], 0);
+        assert_eq!(state.command_blocks()[0].prompt_line, 100);
+        assert_eq!(state.command_blocks()[0].output_range(0), Some(101..102));
+    }
+
+    /// Tapped lines arrive once each, whether they scrolled into the backing
+    /// file or are still on screen, and the line being written waits.
+    #[test]
+    fn tapped_lines_cover_streamed_and_screen_lines_once() {
+        let mut state = TerminalState::new(20, 4);
+        state.tap_lines();
+        state.process_output(b"one\r\ntwo\r\nthr");
+        assert_eq!(state.take_tapped_lines(false), vec!["one", "two"]);
+
+        state.process_output(b"ee\r\nfour\r\nfive\r\nsix\r\nse");
+        let mut sink = Vec::new();
+        state.flush_new_scrollback(&mut sink).unwrap();
+        assert_eq!(
+            state.take_tapped_lines(false),
+            vec!["three", "four", "five", "six"]
+        );
+        assert!(state.take_tapped_lines(false).is_empty());
+
+        state.process_output(b"ven\r\n");
+        assert_eq!(state.take_tapped_lines(true), vec!["seven"]);
+    }
+
     #[test]
     fn osc_9_4_progress_drives_running_state() {
         let mut state = TerminalState::new(80, 24);
@@ -25,7 +25,9 @@ use crate::view::popup::{
     Popup, PopupContent, PopupKind, PopupListItem, PopupManager, PopupPosition,
 };
 use crate::view::reference_highlight_overlay::ReferenceHighlightOverlay;
+use crate::view::scope_outline::ScopeOutline;
 use crate::view::soft_break::SoftBreakManager;
+use crate::view::spell_check_overlay::SpellCheckOverlay;
 use crate::view::virtual_text::VirtualTextManager;
 use anyhow::Result;
 use ratatui::style::{Color, Style};
@@ -376,6 +378,12 @@ pub struct EditorState {
     /// Bracket matching highlight overlay
     pub bracket_highlight_overlay: BracketHighlightOverlay,
 
+    /// Misspelling underlines for the visible part of the buffer
+    pub spell_check_overlay: SpellCheckOverlay,
+
+    /// Outline with symbol ranges, for sticky scroll and the breadcrumb bar
+    pub scope_outline: ScopeOutline,
+
     /// Host-placed bar following this buffer's cursor. Off unless a plugin
     /// declares one (`setCursorLineOverlay`); placed from the cursor at
     /// paint time, so it can never lag the caret it marks.
@@ -517,6 +525,8 @@ impl EditorState {
             debug_highlight_mode: false,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            spell_check_overlay: SpellCheckOverlay::new(),
+            scope_outline: ScopeOutline::new(),
             cursor_line_overlay: CursorLineOverlay::new(),
             semantic_tokens: None,
             folding_ranges: LspFoldRanges::new(),
@@ -158,6 +158,9 @@ pub enum LspFeature {
     DocumentSymbols,
     /// Workspace symbols (merged: combined from all servers)
     WorkspaceSymbols,
+    /// Workspace file operations: will/did create, rename and delete files
+    /// (merged: every interested server is asked)
+    FileOperations,
     /// Hover information (exclusive: first eligible server wins)
     Hover,
     /// Go to definition, declaration, type definition (exclusive)
@@ -166,6 +169,10 @@ pub enum LspFeature {
     Implementation,
     /// Find references (exclusive)
     References,
+    /// Call hierarchy: incoming / outgoing calls (exclusive)
+    CallHierarchy,
+    /// Type hierarchy: supertypes / subtypes (exclusive)
+    TypeHierarchy,
     /// Document formatting and range formatting (exclusive)
     Format,
     /// Rename and prepare rename (exclusive)
@@ -180,6 +187,18 @@ pub enum LspFeature {
     SemanticTokens,
     /// Document highlight (exclusive)
     DocumentHighlight,
+    /// Code lens (exclusive)
+    CodeLens,
+    /// Selection ranges for expand/shrink selection (exclusive)
+    SelectionRange,
+    /// Linked editing ranges, such as a matching tag name (exclusive)
+    LinkedEditingRange,
+    /// Formatting edits after typing a trigger character (exclusive)
+    OnTypeFormatting,
+    /// Clickable document links such as URLs and include paths (exclusive)
+    DocumentLink,
+    /// Colour literals and their presentations (exclusive)
+    DocumentColor,
 }
 
 impl LspFeature {
@@ -194,6 +213,7 @@ impl LspFeature {
                 | LspFeature::CodeAction
                 | LspFeature::DocumentSymbols
                 | LspFeature::WorkspaceSymbols
+                | LspFeature::FileOperations
         )
     }
 }
@@ -470,6 +490,171 @@ impl LspServerConfig {
     }
 }
 
+/// How a debug session reaches the program being debugged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DebugRequestKind {
+    /// Start the program under the debugger
+    #[default]
+    Launch,
+    /// Connect to an already-running program
+    Attach,
+}
+
+impl DebugRequestKind {
+    /// The DAP request name (`launch` / `attach`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Launch => "launch",
+            Self::Attach => "attach",
+        }
+    }
+}
+
+/// Debug adapter configuration for one language.
+///
+/// The adapter is spawned through the active authority and spoken to over
+/// stdio, so it runs wherever the workspace lives (local, SSH, container).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/command"))]
+pub struct DebugAdapterConfig {
+    /// Command that starts the debug adapter (must speak DAP on stdio).
+    #[serde(default)]
+    #[schemars(extend("x-order" = 1))]
+    pub command: String,
+
+    /// Arguments to pass to the adapter.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 2))]
+    pub args: Vec<String>,
+
+    /// Whether to launch the program or attach to a running one.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 3))]
+    pub request: DebugRequestKind,
+
+    /// Adapter-specific arguments of the launch/attach request.
+    /// String values may use `${file}`, `${fileDirname}`,
+    /// `${fileBasenameNoExtension}`, `${workspaceFolder}` and
+    /// `${workspaceFolderBasename}`, which are expanded when the session starts.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 4))]
+    pub configuration: serde_json::Value,
+
+    /// Environment variables to set for the adapter process.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Advanced", "x-order" = 10))]
+    pub env: HashMap<String, String>,
+}
+
+/// Where a task's output is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskPresentation {
+    /// A read-only output buffer in the utility dock
+    #[default]
+    Output,
+    /// An interactive terminal in the utility dock
+    Terminal,
+}
+
+/// Severity of a problem found in task output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskProblemSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+/// A problem matcher defined in the configuration.
+///
+/// Patterns are regular expressions with named groups: `file`, `line`,
+/// `column`, `end_line`, `end_column`, `severity`, `code` and `message`.
+/// Several patterns match consecutive lines, so a message on one line can be
+/// paired with a location on the next; a problem needs `file` and `line`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CustomProblemMatcher {
+    /// Patterns matched against consecutive output lines.
+    pub pattern: Vec<String>,
+
+    /// Keep matching the last pattern on the lines that follow, producing a
+    /// problem for each (for formats that name a file once, then list its
+    /// problems).
+    #[serde(default)]
+    pub repeat_last: bool,
+
+    /// Severity of problems whose patterns capture none.
+    #[serde(default)]
+    pub severity: TaskProblemSeverity,
+
+    /// For background tasks: a line matching this starts a new run, whose
+    /// problems replace the previous run's once it ends.
+    #[serde(default)]
+    pub begins: Option<String>,
+
+    /// For background tasks: a line matching this ends a run.
+    #[serde(default)]
+    pub ends: Option<String>,
+}
+
+/// A problem matcher: the name of a built-in one (`rustc`, `gcc`, `tsc`,
+/// `eslint`, `pytest`) or a custom definition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemMatcherConfig {
+    Builtin(String),
+    Custom(CustomProblemMatcher),
+}
+
+/// A task that can be run from the editor ("Run Task").
+///
+/// Tasks come from the `tasks` map of the configuration and from the
+/// workspace's `.fresh/tasks.json`. They are spawned through the active
+/// authority, so they run wherever the workspace lives.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/command"))]
+pub struct TaskConfig {
+    /// Command line, run through the shell (e.g. `cargo build`).
+    #[serde(default)]
+    #[schemars(extend("x-order" = 1))]
+    pub command: String,
+
+    /// Extra arguments, quoted and appended to the command line.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 2))]
+    pub args: Vec<String>,
+
+    /// Problem matchers that turn the task's output into diagnostics.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 3))]
+    pub problem_matchers: Vec<ProblemMatcherConfig>,
+
+    /// Where the output is shown.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 4))]
+    pub presentation: TaskPresentation,
+
+    /// The task keeps running and reports in cycles (a watcher). Problems
+    /// are replaced each time a cycle ends instead of when the task exits.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 5))]
+    pub background: bool,
+
+    /// Working directory, relative to the workspace root. Defaults to the
+    /// root.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Advanced", "x-order" = 10))]
+    pub cwd: Option<String>,
+
+    /// Environment variables to set for the task.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Advanced", "x-order" = 11))]
+    pub env: HashMap<String, String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -482,6 +667,7 @@ mod tests {
         assert!(LspFeature::CodeAction.is_merged());
         assert!(LspFeature::DocumentSymbols.is_merged());
         assert!(LspFeature::WorkspaceSymbols.is_merged());
+        assert!(LspFeature::FileOperations.is_merged());
 
         assert!(!LspFeature::Hover.is_merged());
         assert!(!LspFeature::Definition.is_merged());
@@ -493,6 +679,10 @@ mod tests {
         assert!(!LspFeature::FoldingRange.is_merged());
         assert!(!LspFeature::SemanticTokens.is_merged());
         assert!(!LspFeature::DocumentHighlight.is_merged());
+        assert!(!LspFeature::CodeLens.is_merged());
+        assert!(!LspFeature::SelectionRange.is_merged());
+        assert!(!LspFeature::LinkedEditingRange.is_merged());
+        assert!(!LspFeature::OnTypeFormatting.is_merged());
     }
 
     #[test]
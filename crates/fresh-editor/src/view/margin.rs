@@ -314,6 +314,11 @@ pub struct MarginManager {
     /// Maps marker_id -> (namespace -> indicator)
     /// The line number is computed at render time from the marker's byte position
     line_indicators: BTreeMap<u64, BTreeMap<String, LineIndicator>>,
+
+    /// Keep the 1-char indicator slot while line numbers are off, outside
+    /// compose mode too. Set for terminal scroll-back with command blocks,
+    /// whose exit-status marks live in that slot.
+    pub keep_indicator_slot: bool,
 }
 
 impl MarginManager {
@@ -326,6 +331,7 @@ impl MarginManager {
             right_annotations: BTreeMap::new(),
             indicator_markers: MarkerList::new(),
             line_indicators: BTreeMap::new(),
+            keep_indicator_slot: false,
         }
     }
 
@@ -505,6 +511,22 @@ impl MarginManager {
             .unwrap_or_default()
     }
 
+    /// Markers carrying an indicator in `namespace`, with their current byte
+    /// positions. Lets callers that own a namespace (e.g. debugger
+    /// breakpoints) read back where edits have moved their indicators.
+    pub fn indicator_positions_for_namespace(&self, namespace: &str) -> Vec<(MarkerId, usize)> {
+        self.line_indicators
+            .iter()
+            .filter(|(_, indicators)| indicators.contains_key(namespace))
+            .filter_map(|(&marker_id, _)| {
+                let marker_id = MarkerId(marker_id);
+                self.indicator_markers
+                    .get_position(marker_id)
+                    .map(|pos| (marker_id, pos))
+            })
+            .collect()
+    }
+
     /// Query indicator markers in a byte range.
     /// Returns (MarkerId, start, end) tuples for markers in the range.
     pub fn query_indicator_range(&self, start: usize, end: usize) -> Vec<(MarkerId, usize, usize)> {
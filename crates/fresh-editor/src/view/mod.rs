@@ -91,6 +91,10 @@ pub mod query_replace_input;
 pub mod reference_highlight_overlay;
 pub mod scene;
 #[cfg(feature = "runtime")]
+pub mod scope_outline;
+#[cfg(feature = "runtime")]
+pub mod spell_check_overlay;
+#[cfg(feature = "runtime")]
 pub mod split;
 #[cfg(feature = "runtime")]
 pub mod stream;
@@ -105,6 +105,16 @@ pub enum PopupResolver {
     /// `Editor::pending_code_actions` (heavy `lsp_types` payload stays
     /// there to keep the view crate free of LSP types).
     CodeAction,
+    /// "Run Code Lens" chooser. Selected row's `data` is the index into
+    /// `Window::pending_code_lens_commands`.
+    CodeLens,
+    /// "Pick Color" chooser. Selected row's `data` is the index into the
+    /// presentations of `Window::color_pick`, or `custom` for the row that
+    /// prompts for another colour.
+    ColorPresentation,
+    /// Breadcrumb-bar picker. Selected row's `data` is the byte offset of a
+    /// symbol in the active buffer, or a file path when `files` is set.
+    Breadcrumb { files: bool },
     /// Plugin-requested action popup (`editor.showActionPopup`). Confirm
     /// fires `action_popup_result` with this popup's id and the selected
     /// row's `data` as the action id.
@@ -119,6 +129,15 @@ pub enum PopupResolver {
     /// ("trusted" / "restricted" / "blocked") through
     /// `handle_workspace_trust_action`.
     WorkspaceTrust,
+    /// Review of the edits language servers want alongside a file-explorer
+    /// rename, move, create or delete. Confirm dispatches the selected row's
+    /// `data` ("apply" / "skip" / "cancel") through
+    /// `handle_file_operation_review_action`; cancel drops the operation.
+    FileOperationReview,
+    /// A language server's `window/showMessageRequest`, or its request to
+    /// open a document externally. `id` keys `Editor::pending_window_requests`;
+    /// confirm answers with the selected row's `data`, cancel with none.
+    LspWindowRequest { id: u64 },
     /// Read-only indicator menu (anchored to the status bar's `{read_only}`
     /// segment). Confirm dispatches the selected row's `data`
     /// ("toggle_read_only" / "cancel") through `handle_read_only_menu_action`.
@@ -83,6 +83,8 @@ pub enum PromptType {
     RemoveRuler,
     /// Set tab size for current buffer
     SetTabSize,
+    /// Hex value of another colour for "Pick Color"
+    LspPickColor,
     /// Set line ending format for current buffer
     SetLineEnding,
     /// Set text encoding format for current buffer
@@ -93,6 +95,8 @@ pub enum PromptType {
     StopLspServer,
     /// Restart LSP server(s) (select from list)
     RestartLspServer,
+    /// Run a configured task (select from list)
+    RunTask,
     /// Select a theme (select from list)
     /// Stores the original theme name for restoration on cancel
     SelectTheme { original_theme: String },
@@ -104,6 +108,8 @@ pub enum PromptType {
     SelectLocale,
     /// Select a theme for copy with formatting
     CopyWithFormattingTheme,
+    /// Pick a kill ring entry to paste (value is the ring index)
+    KillRing,
     /// Confirm reverting a modified file
     ConfirmRevert,
     /// Confirm saving over a file that changed on disk
@@ -99,6 +99,7 @@ impl InputHandler for Prompt {
                                 crate::view::prompt::PromptType::Plugin { .. }
                                     | crate::view::prompt::PromptType::QuickOpen
                                     | crate::view::prompt::PromptType::LiveGrep
+                                    | crate::view::prompt::PromptType::KillRing
                             );
                         if should_sync {
                             if let Some(suggestion) = self.suggestions.get(new_selected) {
@@ -112,10 +113,13 @@ impl InputHandler for Prompt {
                         ) {
                             ctx.defer(DeferredAction::PreviewThemeFromPrompt);
                         }
-                        // For plugin prompts, notify about selection change (for live preview)
+                        // For plugin prompts, the kill ring picker and Quick Open
+                        // (symbol preview), notify about selection change (for live preview)
                         if matches!(
                             self.prompt_type,
                             crate::view::prompt::PromptType::Plugin { .. }
+                                | crate::view::prompt::PromptType::KillRing
+                                | crate::view::prompt::PromptType::QuickOpen
                         ) {
                             ctx.defer(DeferredAction::PromptSelectionChanged {
                                 selected_index: new_selected,
@@ -143,6 +147,7 @@ impl InputHandler for Prompt {
                                 crate::view::prompt::PromptType::Plugin { .. }
                                     | crate::view::prompt::PromptType::QuickOpen
                                     | crate::view::prompt::PromptType::LiveGrep
+                                    | crate::view::prompt::PromptType::KillRing
                             );
                         if should_sync {
                             if let Some(suggestion) = self.suggestions.get(new_selected) {
@@ -156,10 +161,13 @@ impl InputHandler for Prompt {
                         ) {
                             ctx.defer(DeferredAction::PreviewThemeFromPrompt);
                         }
-                        // For plugin prompts, notify about selection change (for live preview)
+                        // For plugin prompts, the kill ring picker and Quick Open
+                        // (symbol preview), notify about selection change (for live preview)
                         if matches!(
                             self.prompt_type,
                             crate::view::prompt::PromptType::Plugin { .. }
+                                | crate::view::prompt::PromptType::KillRing
+                                | crate::view::prompt::PromptType::QuickOpen
                         ) {
                             ctx.defer(DeferredAction::PromptSelectionChanged {
                                 selected_index: new_selected,
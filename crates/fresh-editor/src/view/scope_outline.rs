@@ -0,0 +1,178 @@
+//! Enclosing scopes for sticky scroll and the breadcrumb bar
+//!
+//! Each buffer keeps the symbols of its document outline together with the
+//! byte range each one spans. The language server's
+//! `textDocument/documentSymbol` answer is preferred; without one the local
+//! outline ([`crate::primitives::outline`]) is computed at render time, once
+//! per buffer version. An outline from the server is kept across edits until
+//! the next answer replaces it, so the headers don't flicker to the local
+//! outline while a request is in flight.
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use crate::primitives::outline::{document_outline, OutlineSymbol};
+
+/// Outline of one buffer, with the byte range of every symbol.
+#[derive(Debug, Default)]
+pub struct ScopeOutline {
+    /// Symbols sorted by the start of their range.
+    symbols: Vec<OutlineSymbol>,
+    /// Buffer version `symbols` was computed for.
+    version: Option<u64>,
+    /// Whether `symbols` came from the language server.
+    from_lsp: bool,
+    /// Last buffer version the language server was asked about.
+    requested: Option<u64>,
+}
+
+impl ScopeOutline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the language server should be asked for the outline of
+    /// `version`: it has not been asked yet and has not answered for it.
+    pub fn needs_request(&self, version: u64) -> bool {
+        self.requested != Some(version) && !(self.from_lsp && self.version == Some(version))
+    }
+
+    /// Record that the language server was asked about `version`.
+    pub fn mark_requested(&mut self, version: u64) {
+        self.requested = Some(version);
+    }
+
+    /// Take the language server's outline of `version`.
+    pub fn set_from_lsp(&mut self, version: u64, mut symbols: Vec<OutlineSymbol>) {
+        symbols.sort_by_key(|s| (s.range.start, std::cmp::Reverse(s.range.end)));
+        self.symbols = symbols;
+        self.version = Some(version);
+        self.from_lsp = true;
+    }
+
+    /// Fall back to the local outline: the server is gone or had nothing to
+    /// say. The next [`refresh_local`](Self::refresh_local) recomputes it.
+    pub fn clear_lsp(&mut self) {
+        if self.from_lsp {
+            self.from_lsp = false;
+            self.version = None;
+        }
+    }
+
+    /// Recompute the local outline if the buffer changed since it was last
+    /// computed. An outline from the server is left for its next answer.
+    pub fn refresh_local(
+        &mut self,
+        buffer: &Buffer,
+        language: Option<&Language>,
+        language_id: &str,
+    ) {
+        let version = buffer.version();
+        if self.from_lsp || self.version == Some(version) {
+            return;
+        }
+        let mut symbols = document_outline(buffer, language, language_id);
+        symbols.sort_by_key(|s| (s.range.start, std::cmp::Reverse(s.range.end)));
+        self.symbols = symbols;
+        self.version = Some(version);
+    }
+
+    /// The symbols whose range contains `byte`, outermost first.
+    pub fn enclosing(&self, byte: usize) -> Vec<&OutlineSymbol> {
+        let end = self.symbols.partition_point(|s| s.range.start <= byte);
+        self.symbols[..end]
+            .iter()
+            .filter(|s| byte < s.range.end || (s.range.is_empty() && byte == s.range.start))
+            .collect()
+    }
+
+    /// The symbols nested directly in the same symbol as `symbol` (or at the
+    /// top level with it), in document order. `symbol` itself is included.
+    pub fn siblings(&self, symbol: &OutlineSymbol) -> Vec<&OutlineSymbol> {
+        let parent = self.parent_of(symbol);
+        self.symbols
+            .iter()
+            .filter(|s| match (self.parent_of(s), parent) {
+                (Some(a), Some(b)) => std::ptr::eq(a, b),
+                (None, None) => true,
+                _ => false,
+            })
+            .collect()
+    }
+
+    /// The innermost symbol strictly enclosing `symbol`.
+    fn parent_of(&self, symbol: &OutlineSymbol) -> Option<&OutlineSymbol> {
+        self.symbols
+            .iter()
+            .filter(|s| {
+                !std::ptr::eq(*s, symbol)
+                    && s.range.start <= symbol.range.start
+                    && symbol.range.end <= s.range.end
+                    && s.range != symbol.range
+            })
+            .max_by_key(|s| s.range.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::SymbolKind;
+
+    fn symbol(name: &str, range: std::ops::Range<usize>) -> OutlineSymbol {
+        OutlineSymbol {
+            name: name.to_string(),
+            kind: SymbolKind::FUNCTION,
+            position: range.start,
+            depth: 0,
+            container: None,
+            range,
+        }
+    }
+
+    fn outline() -> ScopeOutline {
+        let mut outline = ScopeOutline::new();
+        outline.set_from_lsp(
+            1,
+            vec![
+                symbol("b", 20..30),
+                symbol("Point", 0..50),
+                symbol("a", 5..15),
+                symbol("main", 60..80),
+            ],
+        );
+        outline
+    }
+
+    fn names(symbols: Vec<&OutlineSymbol>) -> Vec<&str> {
+        symbols.into_iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn enclosing_lists_outermost_first() {
+        let outline = outline();
+        assert_eq!(names(outline.enclosing(7)), vec!["Point", "a"]);
+        assert_eq!(names(outline.enclosing(17)), vec!["Point"]);
+        assert_eq!(names(outline.enclosing(55)), Vec::<&str>::new());
+        assert_eq!(names(outline.enclosing(60)), vec!["main"]);
+    }
+
+    #[test]
+    fn siblings_share_the_enclosing_symbol() {
+        let outline = outline();
+        let a = outline.enclosing(7)[1];
+        assert_eq!(names(outline.siblings(a)), vec!["a", "b"]);
+        let point = outline.enclosing(7)[0];
+        assert_eq!(names(outline.siblings(point)), vec!["Point", "main"]);
+    }
+
+    #[test]
+    fn server_outline_is_requested_once_per_version() {
+        let mut outline = ScopeOutline::new();
+        assert!(outline.needs_request(3));
+        outline.mark_requested(3);
+        assert!(!outline.needs_request(3));
+        outline.set_from_lsp(3, Vec::new());
+        assert!(!outline.needs_request(3));
+        assert!(outline.needs_request(4));
+    }
+}
@@ -0,0 +1,436 @@
+//! Misspelling underlines using the overlay system
+//!
+//! Only what is on screen is checked. The manager remembers which
+//! line-aligned byte ranges it has already checked at the current buffer
+//! version, so an idle frame does no work and scrolling checks only the
+//! newly exposed lines. An edit, a new checker or a word added to a personal
+//! dictionary starts over; the underlines already placed track their text
+//! through the edit until their lines are checked again.
+//!
+//! In source code only comments and strings are checked. Prose buffers
+//! (Markdown, plain text, commit messages) are checked throughout, except
+//! for fenced code blocks and the spans the highlighter marks as code or
+//! links.
+
+use crate::model::buffer::Buffer;
+use crate::model::marker::MarkerList;
+use crate::primitives::highlight_engine::HighlightEngine;
+use crate::primitives::highlighter::HighlightSpan;
+use crate::services::spell::{words, SpellChecker};
+use crate::view::overlay::{
+    Overlay, OverlayFace, OverlayManager, OverlayNamespace, UnderlineStyle,
+};
+use fresh_languages::HighlightCategory;
+use ratatui::style::Color;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Namespace for misspelling overlays
+pub fn spell_check_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("spell-check".to_string())
+}
+
+/// Languages whose whole text is prose rather than code.
+const PROSE_LANGUAGES: &[&str] = &[
+    "markdown",
+    "text",
+    "git-commit",
+    "git-rebase",
+    "latex",
+    "rst",
+];
+
+/// How far past the viewport edges to look for the enclosing line breaks.
+const MAX_LINE_EXTENSION: usize = 1024;
+
+/// Below diagnostics, which share the underline slot.
+const SPELL_CHECK_PRIORITY: i32 = 5;
+
+/// Whether `language` is checked as prose.
+pub fn is_prose_language(language: &str) -> bool {
+    PROSE_LANGUAGES.contains(&language)
+}
+
+/// Manages misspelling overlays for one buffer
+pub struct SpellCheckOverlay {
+    /// Checker to use; `None` while spell checking is off.
+    checker: Option<Arc<SpellChecker>>,
+    /// Buffer version and checker generation that `checked` refers to.
+    stamp: Option<(u64, u64)>,
+    /// Sorted, disjoint byte ranges already checked at `stamp`.
+    checked: Vec<Range<usize>>,
+    /// Whether any overlays may exist in the namespace.
+    active: bool,
+}
+
+impl SpellCheckOverlay {
+    pub fn new() -> Self {
+        Self {
+            checker: None,
+            stamp: None,
+            checked: Vec::new(),
+            active: false,
+        }
+    }
+
+    /// Switch to `checker`, or turn checking off with `None`. Setting the
+    /// checker already in use keeps the work done so far.
+    pub fn set_checker(&mut self, checker: Option<Arc<SpellChecker>>) {
+        let same = match (&self.checker, &checker) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        if !same {
+            self.checker = checker;
+            self.stamp = None;
+            self.checked.clear();
+        }
+    }
+
+    /// The checker in use, if spell checking is on for this buffer.
+    pub fn checker(&self) -> Option<&Arc<SpellChecker>> {
+        self.checker.as_ref()
+    }
+
+    /// Check whatever part of the viewport has not been checked yet.
+    /// `spans` are the highlight spans covering the viewport.
+    ///
+    /// Returns `true` if overlays were changed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        buffer: &Buffer,
+        overlays: &mut OverlayManager,
+        marker_list: &mut MarkerList,
+        highlighter: &mut HighlightEngine,
+        spans: &[HighlightSpan],
+        language: &str,
+        viewport_start: usize,
+        viewport_end: usize,
+        color: Color,
+    ) -> bool {
+        let Some(checker) = self.checker.clone() else {
+            if self.active {
+                overlays.clear_namespace(&spell_check_namespace(), marker_list);
+                self.active = false;
+                return true;
+            }
+            return false;
+        };
+
+        let stamp = (buffer.version(), checker.generation());
+        if self.stamp != Some(stamp) {
+            self.stamp = Some(stamp);
+            self.checked.clear();
+        }
+
+        let range = line_aligned(buffer, viewport_start, viewport_end);
+        let missing = subtract(&range, &self.checked);
+        if missing.is_empty() {
+            return false;
+        }
+
+        let prose = is_prose_language(language);
+        // Every line of a fenced code block, delimiters included, is code.
+        let code_lines: HashSet<usize> = if prose {
+            highlighter
+                .region_lines_in(buffer, range.clone())
+                .into_iter()
+                .map(|(line_start, _)| line_start)
+                .collect()
+        } else {
+            HashSet::new()
+        };
+
+        let ns = spell_check_namespace();
+        for part in missing {
+            overlays.remove_in_range_for_namespace(&part, &ns, marker_list);
+            let bytes = buffer.slice_bytes(part.clone());
+            let mut line_start = part.start;
+            for line in bytes.split(|&b| b == b'\n') {
+                let line_range = line_start..line_start + line.len();
+                line_start = line_range.end + 1;
+                if code_lines.contains(&line_range.start) {
+                    continue;
+                }
+                let Ok(text) = std::str::from_utf8(line) else {
+                    continue;
+                };
+                for checkable in checkable_ranges(&line_range, spans, prose) {
+                    let sub =
+                        &text[checkable.start - line_range.start..checkable.end - line_range.start];
+                    for (offset, word) in words(sub) {
+                        if checker.check(&word) {
+                            continue;
+                        }
+                        let start = checkable.start + offset;
+                        let overlay = Overlay::with_namespace(
+                            marker_list,
+                            start..start + word.len(),
+                            OverlayFace::Underline {
+                                color,
+                                style: UnderlineStyle::Wavy,
+                            },
+                            ns.clone(),
+                        )
+                        .with_priority_value(SPELL_CHECK_PRIORITY)
+                        .with_theme_key("diagnostic.info_fg");
+                        overlays.add(overlay);
+                    }
+                }
+            }
+            insert_range(&mut self.checked, part);
+        }
+        self.active = true;
+        true
+    }
+}
+
+impl Default for SpellCheckOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Widen `start..end` to whole lines, looking at most
+/// [`MAX_LINE_EXTENSION`] bytes past either edge for the line break.
+fn line_aligned(buffer: &Buffer, start: usize, end: usize) -> Range<usize> {
+    let len = buffer.len();
+    let end = end.min(len);
+    let start = start.min(end);
+    let before = buffer.slice_bytes(start.saturating_sub(MAX_LINE_EXTENSION)..start);
+    let aligned_start = match before.iter().rposition(|&b| b == b'\n') {
+        Some(i) => start - (before.len() - i - 1),
+        None if start <= MAX_LINE_EXTENSION => 0,
+        None => start,
+    };
+    let after = buffer.slice_bytes(end..(end + MAX_LINE_EXTENSION).min(len));
+    let aligned_end = match after.iter().position(|&b| b == b'\n') {
+        Some(i) => end + i,
+        None => end + after.len(),
+    };
+    aligned_start..aligned_end
+}
+
+/// The parts of `range` not covered by `done` (sorted and disjoint).
+fn subtract(range: &Range<usize>, done: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut out = Vec::new();
+    let mut cursor = range.start;
+    for r in done {
+        if r.end <= cursor {
+            continue;
+        }
+        if r.start >= range.end {
+            break;
+        }
+        if r.start > cursor {
+            out.push(cursor..r.start);
+        }
+        cursor = cursor.max(r.end);
+    }
+    if cursor < range.end {
+        out.push(cursor..range.end);
+    }
+    out
+}
+
+/// Add `new` to the sorted, disjoint `ranges`, merging touching ranges.
+fn insert_range(ranges: &mut Vec<Range<usize>>, new: Range<usize>) {
+    let mut merged = new;
+    ranges.retain(|r| {
+        if r.end < merged.start || r.start > merged.end {
+            true
+        } else {
+            merged = merged.start.min(r.start)..merged.end.max(r.end);
+            false
+        }
+    });
+    let at = ranges.partition_point(|r| r.start < merged.start);
+    ranges.insert(at, merged);
+}
+
+/// The parts of `line` to spell check. In code that is comments and strings;
+/// in prose it is everything except code spans and links.
+fn checkable_ranges(
+    line: &Range<usize>,
+    spans: &[HighlightSpan],
+    prose: bool,
+) -> Vec<Range<usize>> {
+    let overlapping = spans
+        .iter()
+        .filter(|s| s.range.start < line.end && line.start < s.range.end);
+    if prose {
+        let skipped: Vec<Range<usize>> = overlapping
+            .filter(|s| {
+                matches!(
+                    s.category,
+                    Some(HighlightCategory::String) | Some(HighlightCategory::Function)
+                )
+            })
+            .map(|s| s.range.start.max(line.start)..s.range.end.min(line.end))
+            .collect();
+        let mut sorted = skipped;
+        sorted.sort_by_key(|r| r.start);
+        let mut merged = Vec::new();
+        for r in sorted {
+            insert_range(&mut merged, r);
+        }
+        subtract(line, &merged)
+    } else {
+        overlapping
+            .filter(|s| {
+                matches!(
+                    s.category,
+                    Some(HighlightCategory::Comment) | Some(HighlightCategory::String)
+                )
+            })
+            .map(|s| s.range.start.max(line.start)..s.range.end.min(line.end))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::spell::Dictionary;
+    use std::path::PathBuf;
+
+    fn checker() -> Arc<SpellChecker> {
+        let dictionary = Dictionary::parse(
+            b"SET UTF-8\n",
+            b"6\nthe\nquick\nbrown\nfox\njumps\nfunction\n",
+        );
+        Arc::new(SpellChecker::new(
+            "en_US",
+            dictionary,
+            PathBuf::from("/nonexistent/user.txt"),
+            PathBuf::from("/nonexistent/.fresh/dictionary.txt"),
+        ))
+    }
+
+    fn span(range: Range<usize>, category: HighlightCategory) -> HighlightSpan {
+        HighlightSpan {
+            range,
+            color: Color::Reset,
+            bg: None,
+            category: Some(category),
+        }
+    }
+
+    fn underlined(buffer: &Buffer, overlays: &OverlayManager, markers: &MarkerList) -> Vec<String> {
+        let mut ranges: Vec<Range<usize>> = overlays
+            .query_viewport(0, buffer.len(), markers)
+            .into_iter()
+            .filter(|(o, _)| o.namespace.as_ref() == Some(&spell_check_namespace()))
+            .map(|(_, r)| r)
+            .collect();
+        ranges.sort_by_key(|r| r.start);
+        ranges
+            .into_iter()
+            .map(|r| String::from_utf8(buffer.slice_bytes(r)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn code_checks_only_comments_and_strings() {
+        let text = "fn quikc() {} // the quick borwn fox\nlet s = \"jmups\";\n";
+        let buffer = Buffer::from_str_test(text);
+        let comment = text.find("//").unwrap();
+        let string = text.find('"').unwrap();
+        let spans = vec![
+            span(
+                comment..text.find('\n').unwrap(),
+                HighlightCategory::Comment,
+            ),
+            span(string..string + 7, HighlightCategory::String),
+        ];
+        let mut overlays = OverlayManager::new();
+        let mut markers = MarkerList::new();
+        let mut highlighter = HighlightEngine::None;
+        let mut manager = SpellCheckOverlay::new();
+        manager.set_checker(Some(checker()));
+
+        assert!(manager.update(
+            &buffer,
+            &mut overlays,
+            &mut markers,
+            &mut highlighter,
+            &spans,
+            "rust",
+            0,
+            buffer.len(),
+            Color::Blue,
+        ));
+        assert_eq!(underlined(&buffer, &overlays, &markers), ["borwn", "jmups"]);
+
+        // Nothing changed: nothing to redo.
+        assert!(!manager.update(
+            &buffer,
+            &mut overlays,
+            &mut markers,
+            &mut highlighter,
+            &spans,
+            "rust",
+            0,
+            buffer.len(),
+            Color::Blue,
+        ));
+
+        // Turning checking off removes the underlines.
+        manager.set_checker(None);
+        manager.update(
+            &buffer,
+            &mut overlays,
+            &mut markers,
+            &mut highlighter,
+            &spans,
+            "rust",
+            0,
+            buffer.len(),
+            Color::Blue,
+        );
+        assert!(underlined(&buffer, &overlays, &markers).is_empty());
+    }
+
+    #[test]
+    fn prose_checks_everything_but_code_and_links() {
+        let text = "the quikc fox `cdoe` and [linkk](http://x)\n";
+        let buffer = Buffer::from_str_test(text);
+        let code = text.find('`').unwrap();
+        let link = text.find('[').unwrap();
+        let spans = vec![
+            span(code..code + 6, HighlightCategory::String),
+            span(link..text.len() - 1, HighlightCategory::Function),
+        ];
+        let mut overlays = OverlayManager::new();
+        let mut markers = MarkerList::new();
+        let mut highlighter = HighlightEngine::None;
+        let mut manager = SpellCheckOverlay::new();
+        manager.set_checker(Some(checker()));
+        manager.update(
+            &buffer,
+            &mut overlays,
+            &mut markers,
+            &mut highlighter,
+            &spans,
+            "markdown",
+            0,
+            buffer.len(),
+            Color::Blue,
+        );
+        assert_eq!(underlined(&buffer, &overlays, &markers), ["quikc", "and"]);
+    }
+
+    #[test]
+    fn ranges_are_merged_and_subtracted() {
+        let mut ranges = Vec::new();
+        insert_range(&mut ranges, 10..20);
+        insert_range(&mut ranges, 30..40);
+        insert_range(&mut ranges, 20..30);
+        assert_eq!(ranges, vec![10..40]);
+        assert_eq!(subtract(&(0..50), &ranges), vec![0..10, 40..50]);
+        assert!(subtract(&(15..25), &ranges).is_empty());
+    }
+}
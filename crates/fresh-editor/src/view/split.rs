@@ -225,6 +225,10 @@ pub struct BufferViewState {
     /// setting in `fold_indicators_visible`, so the toggle action keeps
     /// working while composing.
     pub fold_indicators_plugin_override: Option<bool>,
+    /// Explicit per-(split, buffer) minimap override, set by "Toggle Minimap
+    /// (Current Buffer)". `None` = follow `editor.show_minimap` (virtual
+    /// buffers default off). Persisted in the per-file workspace state.
+    pub minimap_override: Option<bool>,
 
     /// Plugin-managed state (arbitrary key-value pairs).
     /// Plugins can store per-buffer-per-split state here via the `setViewState`/`getViewState` API.
@@ -284,6 +288,7 @@ impl BufferViewState {
             indentation_guide_user_override: None,
             fold_indicators_override: None,
             fold_indicators_plugin_override: None,
+            minimap_override: None,
             plugin_state: std::collections::HashMap::new(),
             folds: FoldManager::new(),
         }
@@ -333,6 +338,12 @@ impl BufferViewState {
             .unwrap_or(default_line_numbers)
     }
 
+    /// Whether this split should draw a minimap column for the buffer, given
+    /// the default resolved from `editor.show_minimap`. The user's pin wins.
+    pub fn minimap_visible(&self, default_minimap: bool) -> bool {
+        self.minimap_override.unwrap_or(default_minimap)
+    }
+
     pub fn apply_config_defaults(&mut self, defaults: ViewConfigDefaults) {
         let ViewConfigDefaults {
             line_numbers,
@@ -392,6 +403,7 @@ impl Clone for BufferViewState {
             // Carried like `view_mode`: a split cloned from a composing one
             // is composing too, and should hide its arrows for the same reason.
             fold_indicators_plugin_override: self.fold_indicators_plugin_override,
+            minimap_override: self.minimap_override,
             plugin_state: self.plugin_state.clone(),
             // Fold markers are per-view; clones start with no folded ranges.
             folds: FoldManager::new(),
@@ -0,0 +1,465 @@
+//! Minimap column: a compressed overview of the buffer drawn between the
+//! text area and the vertical scrollbar.
+//!
+//! Each cell is a braille glyph covering two source columns and four source
+//! lines; a dot is lit when its character is not whitespace, and the cell
+//! takes the syntax colour most of its lit characters share. The first
+//! column is a marker lane for diagnostics, git-gutter marks and search
+//! matches, and the rows the viewport shows are drawn on the current-line
+//! background.
+//!
+//! Buffers above the large-file threshold are never scanned: the minimap
+//! samples one line at evenly spaced byte offsets across the whole file and
+//! reads only chunks that are already in memory, so an unloaded region shows
+//! up blank instead of being loaded by the renderer.
+
+use super::scrollbar::resolve_marker_color;
+use crate::model::event::{BufferId, LeafId};
+use crate::primitives::highlighter::HighlightSpan;
+use crate::state::EditorState;
+use crate::view::overlay::OverlayNamespace;
+use crate::view::theme::Theme;
+use crate::view::viewport::Viewport;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use std::ops::Range;
+
+/// Source lines folded into one minimap row (the braille cell height).
+pub(crate) const LINES_PER_ROW: usize = 4;
+
+/// Source columns folded into one minimap cell (the braille cell width).
+const COLS_PER_CELL: usize = 2;
+
+/// The text area keeps at least this many columns; a split narrower than
+/// `minimap_width + MIN_TEXT_WIDTH` drops its minimap.
+const MIN_TEXT_WIDTH: u16 = 20;
+
+/// Bytes read per sample in large-file mode. Enough to skip to the next line
+/// start and take a line's worth of text after it.
+const SAMPLE_BYTES: usize = 512;
+
+/// Glyph painted in the marker lane.
+const LANE_GLYPH: &str = "▌";
+
+/// Braille dot bits, indexed `[column][line]` within a cell.
+const BRAILLE_DOTS: [[u32; LINES_PER_ROW]; COLS_PER_CELL] =
+    [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// A rendered minimap, kept for mouse hit testing.
+#[derive(Debug, Clone)]
+pub struct MinimapArea {
+    pub split_id: LeafId,
+    pub buffer_id: BufferId,
+    pub rect: Rect,
+    /// Byte offset the viewport centres on when each row is clicked.
+    pub row_bytes: Vec<usize>,
+}
+
+impl MinimapArea {
+    /// Byte offset for a screen row, clamped to the minimap's rows so a drag
+    /// past either end keeps scrolling to the first or last row.
+    pub fn byte_at_row(&self, row: u16) -> Option<usize> {
+        let last = self.row_bytes.len().checked_sub(1)?;
+        let relative = row.saturating_sub(self.rect.y) as usize;
+        self.row_bytes.get(relative.min(last)).copied()
+    }
+}
+
+/// Whether a split draws a minimap for its buffer. Terminals, composite
+/// buffers, non-scrollable panels and the empty-workspace placeholder never
+/// do; otherwise the per-buffer pin wins over `editor.show_minimap`, which
+/// virtual buffers ignore.
+pub(crate) fn minimap_enabled(
+    state: &EditorState,
+    view_state: Option<&crate::view::split::BufferViewState>,
+    metadata: Option<&crate::app::BufferMetadata>,
+    show_minimap: bool,
+) -> bool {
+    let is_terminal = metadata
+        .and_then(|m| m.virtual_mode())
+        .is_some_and(|m| m == "terminal");
+    let is_placeholder = metadata.is_some_and(|m| m.synthetic_placeholder);
+    if state.is_composite_buffer || !state.scrollable || is_terminal || is_placeholder {
+        return false;
+    }
+    let default = show_minimap && !metadata.is_some_and(|m| m.is_virtual());
+    view_state.map_or(default, |vs| vs.minimap_visible(default))
+}
+
+/// Split a minimap column off the right edge of `content_rect`. Returns the
+/// narrowed content rect and the minimap rect, or the content rect unchanged
+/// when the split is too narrow to spare the column.
+pub(crate) fn carve_minimap(content_rect: Rect, width: u16) -> (Rect, Option<Rect>) {
+    if width < 2 || content_rect.width < width.saturating_add(MIN_TEXT_WIDTH) {
+        return (content_rect, None);
+    }
+    let text = Rect::new(
+        content_rect.x,
+        content_rect.y,
+        content_rect.width - width,
+        content_rect.height,
+    );
+    let minimap = Rect::new(
+        content_rect.x + text.width,
+        content_rect.y,
+        width,
+        content_rect.height,
+    );
+    (text, Some(minimap))
+}
+
+/// The source lines behind the minimap rows: one slot per braille line,
+/// `None` for lines past the end of the buffer or samples that are not
+/// loaded. Also records each row's byte span and whether the viewport shows it.
+struct MinimapSource {
+    lines: Vec<Option<(usize, Vec<u8>)>>,
+    row_spans: Vec<Range<usize>>,
+    in_viewport: Vec<bool>,
+}
+
+/// Line-accurate source for buffers under the large-file threshold. The
+/// minimap scrolls proportionally with the viewport so the first and last
+/// lines are reachable while the visible region stays in view.
+fn line_source(state: &EditorState, viewport: &Viewport, rows: usize) -> MinimapSource {
+    let buffer = &state.buffer;
+    let len = buffer.len();
+    let total_lines = buffer.get_line_number(len) + 1;
+    let top_line = buffer.get_line_number(viewport.top_byte());
+    let visible = viewport.height as usize;
+    let capacity = rows * LINES_PER_ROW;
+
+    let first_line = if total_lines <= capacity {
+        0
+    } else {
+        let max_first = total_lines - capacity;
+        let max_top = total_lines.saturating_sub(visible).max(1);
+        (top_line * max_first / max_top).min(max_first)
+    };
+
+    let lines: Vec<Option<(usize, Vec<u8>)>> = (first_line..first_line + capacity)
+        .map(|line| {
+            if line >= total_lines {
+                return None;
+            }
+            Some((buffer.line_start_offset(line)?, buffer.get_line(line)?))
+        })
+        .collect();
+
+    let line_start = |line: usize| {
+        if line >= total_lines {
+            len
+        } else {
+            buffer.line_start_offset(line).unwrap_or(len)
+        }
+    };
+    let row_spans = (0..rows)
+        .map(|row| {
+            let first = first_line + row * LINES_PER_ROW;
+            line_start(first)..line_start(first + LINES_PER_ROW)
+        })
+        .collect();
+    let in_viewport = (0..rows)
+        .map(|row| {
+            let first = first_line + row * LINES_PER_ROW;
+            first < total_lines && first < top_line + visible && first + LINES_PER_ROW > top_line
+        })
+        .collect();
+
+    MinimapSource {
+        lines,
+        row_spans,
+        in_viewport,
+    }
+}
+
+/// Sampled source for large files: the whole file maps onto the minimap and
+/// each braille line shows the first full line after an evenly spaced byte
+/// offset. Only chunks already in memory are read.
+fn sampled_source(
+    state: &EditorState,
+    viewport: &Viewport,
+    rows: usize,
+    estimated_line_length: usize,
+) -> MinimapSource {
+    let buffer = &state.buffer;
+    let len = buffer.len();
+    let slots = rows * LINES_PER_ROW;
+    let offset_at = |slot: usize| (len as u128 * slot as u128 / slots.max(1) as u128) as usize;
+
+    let lines = (0..slots)
+        .map(|slot| {
+            let offset = offset_at(slot);
+            if offset >= len {
+                return None;
+            }
+            let bytes = buffer.peek_text_range(offset, SAMPLE_BYTES.min(len - offset))?;
+            // Mid-line offsets skip to the next line start so every sample
+            // shows a line from its beginning, keeping indentation readable.
+            let skip = if offset == 0 {
+                0
+            } else {
+                bytes
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .map_or(0, |newline| newline + 1)
+            };
+            let rest = &bytes[skip..];
+            let end = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+            Some((offset + skip, rest[..end].to_vec()))
+        })
+        .collect();
+
+    let row_spans: Vec<Range<usize>> = (0..rows)
+        .map(|row| offset_at(row * LINES_PER_ROW)..offset_at((row + 1) * LINES_PER_ROW))
+        .collect();
+    let view_start = viewport.top_byte();
+    let view_end = view_start + (viewport.height as usize).max(1) * estimated_line_length.max(1);
+    let in_viewport = row_spans
+        .iter()
+        .enumerate()
+        .map(|(row, span)| {
+            // The last row also lights up when the top byte lies past it, so
+            // the viewport never disappears from the overview.
+            (span.start < view_end && span.end > view_start)
+                || (row + 1 == rows && view_start >= span.start)
+        })
+        .collect();
+
+    MinimapSource {
+        lines,
+        row_spans,
+        in_viewport,
+    }
+}
+
+/// Visual columns of one source line, each holding the byte offset of a
+/// non-whitespace character (`None` for blanks). Tabs expand to `tab_size`.
+fn line_cells(text: &[u8], max_cols: usize, tab_size: usize) -> Vec<Option<usize>> {
+    let mut cells = Vec::with_capacity(max_cols);
+    let text = String::from_utf8_lossy(text);
+    for (offset, ch) in text.char_indices() {
+        if cells.len() >= max_cols || ch == '\n' || ch == '\r' {
+            break;
+        }
+        if ch == '\t' {
+            let next_stop = (cells.len() / tab_size.max(1) + 1) * tab_size.max(1);
+            cells.resize(next_stop.min(max_cols), None);
+        } else if ch.is_whitespace() {
+            cells.push(None);
+        } else {
+            cells.push(Some(offset));
+        }
+    }
+    cells
+}
+
+/// Colour of the highlight span covering `byte`, if any. `spans` is sorted
+/// by start.
+fn span_color(spans: &[HighlightSpan], byte: usize) -> Option<Color> {
+    let idx = spans.partition_point(|s| s.range.start <= byte);
+    spans[..idx]
+        .iter()
+        .rev()
+        .take(4)
+        .find(|s| s.range.contains(&byte))
+        .map(|s| s.color)
+}
+
+/// Braille glyph for a dot bit mask.
+fn braille(bits: u32) -> char {
+    char::from_u32(0x2800 + bits).unwrap_or(' ')
+}
+
+/// Colour of the marker lane for a row: the highest-priority scrollbar marker
+/// (diagnostics, git gutter) overlapping the row, else a search match.
+fn lane_color(
+    span: &Range<usize>,
+    is_last_row: bool,
+    markers: &[crate::view::scrollbar_marker::ResolvedMarker],
+    search_ranges: &[Range<usize>],
+    theme: &Theme,
+) -> Option<Color> {
+    let touches = |start: usize, end: usize| {
+        let end = end.max(start + 1);
+        (start < span.end || (is_last_row && start >= span.start)) && end > span.start
+    };
+    markers
+        .iter()
+        .filter(|m| touches(m.start, m.end.unwrap_or(m.start)))
+        .max_by_key(|m| m.priority)
+        .map(|m| resolve_marker_color(&m.color, theme))
+        .or_else(|| {
+            search_ranges
+                .iter()
+                .any(|r| touches(r.start, r.end))
+                .then_some(theme.search_match_bg)
+        })
+}
+
+/// Render the minimap for one split into `rect` and return the byte offset
+/// each row scrolls to when clicked.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_minimap(
+    buf: &mut ratatui::buffer::Buffer,
+    state: &mut EditorState,
+    viewport: &Viewport,
+    rect: Rect,
+    theme: &Theme,
+    large_file_threshold_bytes: u64,
+    estimated_line_length: usize,
+    highlight_context_bytes: usize,
+) -> Vec<usize> {
+    let rows = rect.height as usize;
+    if rows == 0 || rect.width < 2 {
+        return Vec::new();
+    }
+    let is_large = state.buffer.len() > large_file_threshold_bytes as usize;
+    let source = if is_large {
+        sampled_source(state, viewport, rows, estimated_line_length)
+    } else {
+        line_source(state, viewport, rows)
+    };
+    let span_start = source.row_spans.first().map_or(0, |s| s.start);
+    let span_end = source.row_spans.last().map_or(0, |s| s.end);
+
+    // Syntax colours only where the highlighter already caches the whole
+    // buffer; a large file renders in the plain foreground colour.
+    let mut spans = if is_large || span_end <= span_start {
+        Vec::new()
+    } else {
+        state.highlighter.highlight_viewport(
+            &state.buffer,
+            span_start,
+            span_end,
+            theme,
+            highlight_context_bytes,
+        )
+    };
+    spans.sort_by_key(|s| s.range.start);
+
+    let markers = state.scrollbar_markers.resolved();
+    let search_ns = OverlayNamespace::from_string("search".to_string());
+    let search_ranges: Vec<Range<usize>> = state
+        .overlays
+        .query_viewport(span_start, span_end.max(span_start + 1), &state.marker_list)
+        .into_iter()
+        .filter(|(overlay, _)| overlay.namespace.as_ref() == Some(&search_ns))
+        .map(|(_, range)| range)
+        .collect();
+
+    let text_cols = rect.width as usize - 1;
+    let tab_size = state.buffer_settings.tab_size;
+    let mut row_bytes = Vec::with_capacity(rows);
+
+    for row in 0..rows {
+        let y = rect.y + row as u16;
+        let bg = if source.in_viewport[row] {
+            theme.current_line_bg
+        } else {
+            theme.editor_bg
+        };
+        let span = &source.row_spans[row];
+        row_bytes.push(span.start);
+
+        if let Some(cell) = buf.cell_mut((rect.x, y)) {
+            match lane_color(span, row + 1 == rows, &markers, &search_ranges, theme) {
+                Some(color) => cell
+                    .set_symbol(LANE_GLYPH)
+                    .set_style(Style::default().fg(color).bg(bg)),
+                None => cell.set_symbol(" ").set_style(Style::default().bg(bg)),
+            };
+        }
+
+        let row_lines: Vec<Option<(usize, Vec<Option<usize>>)>> = (0..LINES_PER_ROW)
+            .map(|dy| {
+                source.lines[row * LINES_PER_ROW + dy]
+                    .as_ref()
+                    .map(|(start, text)| {
+                        (
+                            *start,
+                            line_cells(text, text_cols * COLS_PER_CELL, tab_size),
+                        )
+                    })
+            })
+            .collect();
+
+        for col in 0..text_cols {
+            let mut bits = 0;
+            let mut votes: Vec<(Color, usize)> = Vec::new();
+            for (dy, line) in row_lines.iter().enumerate() {
+                let Some((start, cells)) = line else {
+                    continue;
+                };
+                for (dx, dots) in BRAILLE_DOTS.iter().enumerate() {
+                    let Some(Some(offset)) = cells.get(col * COLS_PER_CELL + dx) else {
+                        continue;
+                    };
+                    bits |= dots[dy];
+                    let color = span_color(&spans, start + offset).unwrap_or(theme.editor_fg);
+                    match votes.iter_mut().find(|(c, _)| *c == color) {
+                        Some((_, n)) => *n += 1,
+                        None => votes.push((color, 1)),
+                    }
+                }
+            }
+            let fg = votes
+                .iter()
+                .max_by_key(|(_, n)| *n)
+                .map_or(theme.editor_fg, |(c, _)| *c);
+            if let Some(cell) = buf.cell_mut((rect.x + 1 + col as u16, y)) {
+                let mut glyph = [0u8; 4];
+                cell.set_symbol(braille(bits).encode_utf8(&mut glyph))
+                    .set_style(Style::default().fg(fg).bg(bg));
+            }
+        }
+    }
+
+    row_bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carve_leaves_narrow_splits_alone() {
+        let content = Rect::new(0, 0, 25, 10);
+        assert_eq!(carve_minimap(content, 12), (content, None));
+
+        let wide = Rect::new(3, 1, 80, 10);
+        let (text, minimap) = carve_minimap(wide, 12);
+        assert_eq!(text, Rect::new(3, 1, 68, 10));
+        assert_eq!(minimap, Some(Rect::new(71, 1, 12, 10)));
+    }
+
+    #[test]
+    fn line_cells_expand_tabs_and_mark_blanks() {
+        let cells = line_cells(b"\tab c\n", 16, 4);
+        assert_eq!(
+            cells,
+            vec![None, None, None, None, Some(1), Some(2), None, Some(4)]
+        );
+        assert_eq!(line_cells(b"abcdef", 3, 4).len(), 3);
+    }
+
+    #[test]
+    fn braille_dots_cover_the_full_cell() {
+        let all = BRAILLE_DOTS.iter().flatten().fold(0, |acc, bit| acc | bit);
+        assert_eq!(braille(all), '⣿');
+        assert_eq!(braille(0), '⠀');
+    }
+
+    #[test]
+    fn row_lookup_clamps_to_the_minimap() {
+        let area = MinimapArea {
+            split_id: LeafId(crate::model::event::SplitId(1)),
+            buffer_id: BufferId(1),
+            rect: Rect::new(70, 5, 12, 3),
+            row_bytes: vec![0, 40, 90],
+        };
+        assert_eq!(area.byte_at_row(5), Some(0));
+        assert_eq!(area.byte_at_row(6), Some(40));
+        assert_eq!(area.byte_at_row(2), Some(0));
+        assert_eq!(area.byte_at_row(20), Some(90));
+    }
+}
@@ -3,8 +3,8 @@
 //! This module is organized into two tiers:
 //!
 //! - **Self-contained leaves** (`spans`, `style`, `char_style`, `base_tokens`,
-//!   `transforms`, `view_data`, `folding`, `scrollbar`, `layout`, `gutter`,
-//!   `post_pass`) — none of these depend on any shared render-time carrier.
+//!   `transforms`, `view_data`, `folding`, `scrollbar`, `minimap`,
+//!   `scope_header`, `layout`, `gutter`, `post_pass`) — none of these depend on any shared render-time carrier.
 //! - **Orchestration** (`orchestration::*`) — the only files that share
 //!   `SelectionContext` / `DecorationContext`. Quarantined in a subdirectory
 //!   so the coupling is visible from `ls` alone.
@@ -18,10 +18,12 @@ mod folding;
 pub(crate) use folding::fold_skip_set;
 mod gutter;
 mod layout;
+pub mod minimap;
 mod orchestration;
 
 pub(crate) use orchestration::render_buffer::wrap_index_geometry_for;
 mod post_pass;
+pub mod scope_header;
 pub(crate) mod scrollbar;
 #[cfg(test)]
 mod scrollbar_marker_scroll_perf;
@@ -66,6 +68,11 @@ pub struct EditorRenderConfig<'a> {
     pub use_terminal_bg: bool,
     pub show_vertical_scrollbar: bool,
     pub show_horizontal_scrollbar: bool,
+    pub show_minimap: bool,
+    pub minimap_width: u16,
+    pub show_sticky_scroll: bool,
+    pub sticky_scroll_max_lines: u16,
+    pub show_breadcrumbs: bool,
     pub diagnostics_inline_text: bool,
     pub show_tilde: bool,
     pub highlight_current_column: bool,
@@ -97,6 +104,11 @@ impl<'a> EditorRenderConfig<'a> {
             use_terminal_bg: editor.use_terminal_bg,
             show_vertical_scrollbar: editor.show_vertical_scrollbar,
             show_horizontal_scrollbar: editor.show_horizontal_scrollbar,
+            show_minimap: editor.show_minimap,
+            minimap_width: editor.minimap_width,
+            show_sticky_scroll: editor.show_sticky_scroll,
+            sticky_scroll_max_lines: editor.sticky_scroll_max_lines,
+            show_breadcrumbs: editor.show_breadcrumbs,
             diagnostics_inline_text: editor.diagnostics_inline_text,
             show_tilde: editor.show_tilde,
             highlight_current_column: editor.highlight_current_column,
@@ -182,6 +194,8 @@ impl SplitRenderer {
             u16,
             u16,
         )>,
+        Vec<minimap::MinimapArea>,
+        Vec<scope_header::ScopeHeaderArea>,
     ) {
         orchestration::render_content(
             buf,
@@ -217,6 +231,7 @@ impl SplitRenderer {
         area: Rect,
         split_manager: &SplitManager,
         buffers: &mut HashMap<BufferId, EditorState>,
+        buffer_metadata: &HashMap<BufferId, BufferMetadata>,
         split_view_states: &mut HashMap<LeafId, crate::view::split::SplitViewState>,
         theme: &crate::view::theme::Theme,
         lsp_waiting: bool,
@@ -229,6 +244,9 @@ impl SplitRenderer {
         tab_bar_visible: bool,
         show_vertical_scrollbar: bool,
         show_horizontal_scrollbar: bool,
+        show_minimap: bool,
+        minimap_width: u16,
+        show_breadcrumbs: bool,
         diagnostics_inline_text: bool,
         show_tilde: bool,
         bracket_highlight: BracketHighlightSettings,
@@ -237,6 +255,7 @@ impl SplitRenderer {
             area,
             split_manager,
             buffers,
+            buffer_metadata,
             split_view_states,
             theme,
             lsp_waiting,
@@ -249,6 +268,9 @@ impl SplitRenderer {
             tab_bar_visible,
             show_vertical_scrollbar,
             show_horizontal_scrollbar,
+            show_minimap,
+            minimap_width,
+            show_breadcrumbs,
             diagnostics_inline_text,
             show_tilde,
             bracket_highlight,
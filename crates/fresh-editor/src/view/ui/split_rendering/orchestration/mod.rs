@@ -24,6 +24,11 @@ use super::layout::{
     render_separator, resolve_view_preferences, split_buffers_for_tabs, split_layout,
     sync_viewport_to_content, SplitLayout,
 };
+use super::minimap::{carve_minimap, minimap_enabled, render_minimap, MinimapArea};
+use super::scope_header::{
+    carve_breadcrumbs, render_breadcrumbs, render_sticky_lines, scope_headers_eligible,
+    sticky_lines, ScopeHeaderArea,
+};
 use super::scrollbar::{
     compute_max_line_length, project_scrollbar_markers, render_composite_scrollbar,
     render_horizontal_scrollbar, render_scrollbar, scrollbar_line_counts,
@@ -34,7 +39,7 @@ use crate::app::BufferMetadata;
 use crate::config::IndentationGuideMode;
 use crate::model::buffer::Buffer;
 use crate::model::event::{BufferId, EventLog, LeafId, SplitDirection};
-use crate::state::EditorState;
+use crate::state::{EditorState, ViewMode};
 use crate::view::bracket_highlight_overlay::BracketHighlightSettings;
 use crate::view::folding::FoldManager;
 use crate::view::split::SplitManager;
@@ -73,6 +78,7 @@ type VisibleBuffer = (LeafId, LeafId, BufferId, Rect, RenderKind);
 
 /// # Returns
 /// * Vec of (split_id, buffer_id, content_rect, scrollbar_rect, thumb_start, thumb_end) for mouse handling
+/// * Minimap hit areas for the splits that draw one
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 pub(crate) fn render_content(
@@ -130,6 +136,8 @@ pub(crate) fn render_content(
         u16,
         u16,
     )>, // hit areas for separators inside active Grouped subtrees
+    Vec<MinimapArea>,
+    Vec<ScopeHeaderArea>,
 ) {
     let _span = tracing::trace_span!("render_content").entered();
 
@@ -141,9 +149,16 @@ pub(crate) fn render_content(
     let crate::view::ui::RenderStyle { theme, cfg, .. } = style;
     let EditorRenderConfig {
         large_file_threshold_bytes,
+        estimated_line_length,
+        highlight_context_bytes,
         use_terminal_bg,
         show_vertical_scrollbar,
         show_horizontal_scrollbar,
+        show_minimap,
+        minimap_width,
+        show_sticky_scroll,
+        sticky_scroll_max_lines,
+        show_breadcrumbs,
         show_tilde,
         highlight_current_column,
         hide_current_line_on_selection,
@@ -172,6 +187,8 @@ pub(crate) fn render_content(
     let mut close_split_areas = Vec::new();
     let mut maximize_split_areas = Vec::new();
     let mut view_line_mappings: HashMap<LeafId, Vec<ViewLineMapping>> = HashMap::new();
+    let mut minimap_areas: Vec<MinimapArea> = Vec::new();
+    let mut scope_header_areas: Vec<ScopeHeaderArea> = Vec::new();
 
     // Render each split.
     for (main_split_id, split_id, buffer_id, split_area, kind) in visible_buffers {
@@ -237,7 +254,7 @@ pub(crate) fn render_content(
         let panel_show_vscroll =
             show_vertical_scrollbar && !is_non_scrollable && !terminal_showing_live_grid;
 
-        let layout = if is_inner_group_leaf {
+        let mut layout = if is_inner_group_leaf {
             // Inner leaf: split_area IS the content rect already.
             SplitLayout {
                 tabs_rect: Rect::new(split_area.x, split_area.y, 0, 0),
@@ -265,6 +282,41 @@ pub(crate) fn render_content(
                 show_horizontal_scrollbar && !is_non_scrollable,
             )
         };
+        // The minimap sits between the text and the scrollbar. Carved before
+        // anything reads `content_rect` so the text area, its wrap width and
+        // the mouse hit areas all see the narrower rect.
+        let minimap_rect = if !skip_content
+            && buffers.get(&buffer_id).is_some_and(|state| {
+                minimap_enabled(
+                    state,
+                    split_view_states
+                        .as_deref()
+                        .and_then(|svs| svs.get(&split_id))
+                        .map(|vs| vs.active_state()),
+                    buffer_metadata.get(&buffer_id),
+                    show_minimap,
+                )
+            }) {
+            let (text_rect, minimap_rect) = carve_minimap(layout.content_rect, minimap_width);
+            layout.content_rect = text_rect;
+            minimap_rect
+        } else {
+            None
+        };
+        // The breadcrumb row takes the top of the text area; sticky lines
+        // are painted over the text once it is drawn.
+        let scope_headers = !skip_content
+            && (show_sticky_scroll || show_breadcrumbs)
+            && buffers.get(&buffer_id).is_some_and(|state| {
+                scope_headers_eligible(state, buffer_metadata.get(&buffer_id))
+            });
+        let breadcrumb_rect = if scope_headers && show_breadcrumbs {
+            let (text_rect, breadcrumb_rect) = carve_breadcrumbs(layout.content_rect);
+            layout.content_rect = text_rect;
+            breadcrumb_rect
+        } else {
+            None
+        };
         let (split_buffers, tab_scroll_offset) = if is_inner_group_leaf {
             (Vec::new(), 0)
         } else {
@@ -510,6 +562,7 @@ pub(crate) fn render_content(
                 viewport.left_column = viewport.left_column.min(max_scroll);
             }
 
+            let is_page_view = matches!(view_prefs.view_mode, ViewMode::PageView);
             let split_view_mappings = render_buffer_in_split(
                 buf,
                 state,
@@ -540,6 +593,77 @@ pub(crate) fn render_content(
 
             drop(_render_buf_span);
 
+            if scope_headers {
+                let _span = tracing::trace_span!("render_scope_headers").entered();
+                state.scope_outline.refresh_local(
+                    &state.buffer,
+                    state.highlighter.language(),
+                    &state.language,
+                );
+                let cursor = split_cursors.primary().position;
+                // Page view lays text out in a centred column of its own;
+                // pinned lines drawn at the gutter would not line up with it.
+                let max_lines = if show_sticky_scroll && !is_page_view {
+                    (sticky_scroll_max_lines as usize).min(layout.content_rect.height as usize / 2)
+                } else {
+                    0
+                };
+                let lines = sticky_lines(
+                    &state.scope_outline,
+                    &state.buffer,
+                    &split_view_mappings,
+                    cursor,
+                    max_lines,
+                );
+                let sticky_rect = (!lines.is_empty()).then(|| {
+                    Rect::new(
+                        layout.content_rect.x,
+                        layout.content_rect.y,
+                        layout.content_rect.width,
+                        lines.len() as u16,
+                    )
+                });
+                if let Some(rect) = sticky_rect {
+                    let left_column = if viewport.line_wrap_enabled {
+                        0
+                    } else {
+                        viewport.left_column
+                    };
+                    render_sticky_lines(
+                        buf,
+                        state,
+                        &lines,
+                        rect,
+                        left_column,
+                        theme,
+                        highlight_context_bytes,
+                    );
+                }
+                let breadcrumb_segments = match breadcrumb_rect {
+                    Some(rect) => {
+                        let path = buffer_metadata
+                            .get(&buffer_id)
+                            .map_or("", |m| m.display_name.as_str());
+                        let symbols: Vec<(String, usize)> = state
+                            .scope_outline
+                            .enclosing(cursor)
+                            .into_iter()
+                            .map(|s| (s.name.clone(), s.position))
+                            .collect();
+                        render_breadcrumbs(buf, rect, path, &symbols, theme)
+                    }
+                    None => Vec::new(),
+                };
+                scope_header_areas.push(ScopeHeaderArea {
+                    split_id,
+                    buffer_id,
+                    sticky_rect,
+                    sticky_targets: lines.iter().map(|l| l.target).collect(),
+                    breadcrumb_rect,
+                    breadcrumb_segments,
+                });
+            }
+
             // Store view line mappings for mouse click handling
             view_line_mappings.insert(split_id, split_view_mappings);
 
@@ -583,6 +707,26 @@ pub(crate) fn render_content(
                 (0, 0)
             };
 
+            if let Some(minimap_rect) = minimap_rect {
+                let _span = tracing::trace_span!("render_minimap").entered();
+                let row_bytes = render_minimap(
+                    buf,
+                    state,
+                    &viewport,
+                    minimap_rect,
+                    theme,
+                    large_file_threshold_bytes,
+                    estimated_line_length,
+                    highlight_context_bytes,
+                );
+                minimap_areas.push(MinimapArea {
+                    split_id,
+                    buffer_id,
+                    rect: minimap_rect,
+                    row_bytes,
+                });
+            }
+
             // Compute the actual max line length for horizontal scrollbar
             let max_content_width = if show_horizontal_scrollbar && !viewport.line_wrap_enabled {
                 let mcw = compute_max_line_length(state, &mut viewport);
@@ -681,6 +825,8 @@ pub(crate) fn render_content(
         view_line_mappings,
         horizontal_scrollbar_areas,
         grouped_separator_areas,
+        minimap_areas,
+        scope_header_areas,
     )
 }
 
@@ -1148,6 +1294,7 @@ pub(crate) fn compute_content_layout(
     area: Rect,
     split_manager: &SplitManager,
     buffers: &mut HashMap<BufferId, EditorState>,
+    buffer_metadata: &HashMap<BufferId, BufferMetadata>,
     split_view_states: &mut HashMap<LeafId, crate::view::split::SplitViewState>,
     theme: &crate::view::theme::Theme,
     lsp_waiting: bool,
@@ -1160,6 +1307,9 @@ pub(crate) fn compute_content_layout(
     tab_bar_visible: bool,
     show_vertical_scrollbar: bool,
     show_horizontal_scrollbar: bool,
+    show_minimap: bool,
+    minimap_width: u16,
+    show_breadcrumbs: bool,
     diagnostics_inline_text: bool,
     show_tilde: bool,
     bracket_highlight: BracketHighlightSettings,
@@ -1177,7 +1327,7 @@ pub(crate) fn compute_content_layout(
                 .get(&split_id)
                 .is_some_and(|vs| vs.suppress_chrome);
 
-        let layout = split_layout(
+        let mut layout = split_layout(
             split_area,
             split_tab_bar_visible,
             show_vertical_scrollbar,
@@ -1189,6 +1339,20 @@ pub(crate) fn compute_content_layout(
             None => continue,
         };
 
+        // Same minimap carve as `render_content`, so the cached mappings use
+        // the rendered text width.
+        if minimap_enabled(
+            state,
+            split_view_states.get(&split_id).map(|vs| vs.active_state()),
+            buffer_metadata.get(&buffer_id),
+            show_minimap,
+        ) {
+            layout.content_rect = carve_minimap(layout.content_rect, minimap_width).0;
+        }
+        if show_breadcrumbs && scope_headers_eligible(state, buffer_metadata.get(&buffer_id)) {
+            layout.content_rect = carve_breadcrumbs(layout.content_rect).0;
+        }
+
         // Skip composite buffers — they don't produce view_line_mappings
         if state.is_composite_buffer {
             view_line_mappings.insert(split_id, Vec::new());
@@ -247,6 +247,20 @@ pub(crate) fn decoration_context(
         &bracket_skip_ranges,
     );
 
+    // Underline misspellings in the lines of the viewport not checked yet.
+    // A no-op unless the editor has handed this buffer a spell checker.
+    state.spell_check_overlay.update(
+        &state.buffer,
+        &mut state.overlays,
+        &mut state.marker_list,
+        &mut state.highlighter,
+        &highlight_spans,
+        &state.language,
+        viewport_start,
+        viewport_end,
+        theme.diagnostic_info_fg,
+    );
+
     // Semantic tokens are stored as overlays so their ranges track edits.
     // Convert them into highlight spans for the render pipeline.
     let is_compose = matches!(view_mode, ViewMode::PageView);
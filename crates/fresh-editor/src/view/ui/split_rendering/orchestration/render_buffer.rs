@@ -156,8 +156,12 @@ pub(crate) fn compute_buffer_layout(
     // compose mode, where the render below reclaims its width from the desk
     // margin (issue #2146). In normal editor mode, line-numbers-off means no
     // gutter at all — otherwise the 1-col indicator slot would eat into the
-    // text width and shift content right.
-    if !show_line_numbers && !matches!(view_mode, ViewMode::PageView) {
+    // text width and shift content right — unless the buffer asks to keep the
+    // slot (terminal scroll-back exit-status marks).
+    if !show_line_numbers
+        && !matches!(view_mode, ViewMode::PageView)
+        && !state.margins.keep_indicator_slot
+    {
         state.margins.left_config.enabled = false;
         state.margins.left_config.width = 0;
     }
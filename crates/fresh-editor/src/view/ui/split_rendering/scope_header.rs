@@ -0,0 +1,548 @@
+//! Sticky scroll and the breadcrumb bar: where in the code a split is.
+//!
+//! Sticky scroll paints the header lines of the scopes enclosing the top of
+//! the viewport over the first text rows, outermost first, once their real
+//! header has scrolled out of view. It runs after the buffer is drawn and
+//! reads the rows from the view line mappings, so folded regions, wrapped
+//! lines and view transforms are already accounted for: the enclosing scopes
+//! are those of the first source byte on the row below the pinned lines. A
+//! pinned line never covers the row holding the primary cursor.
+//!
+//! The breadcrumb bar takes the top row of the text area and shows the
+//! file's path followed by the symbols enclosing the primary cursor.
+//!
+//! Both read the buffer's [`ScopeOutline`](crate::view::scope_outline::ScopeOutline).
+
+use crate::app::types::ViewLineMapping;
+use crate::model::buffer::Buffer;
+use crate::model::event::{BufferId, LeafId};
+use crate::state::EditorState;
+use crate::view::scope_outline::ScopeOutline;
+use crate::view::theme::Theme;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use std::ops::Range;
+use unicode_width::UnicodeWidthChar;
+
+/// A split shorter than this many text rows keeps all of them: no
+/// breadcrumb row.
+const MIN_TEXT_ROWS: u16 = 4;
+
+/// Between breadcrumb segments.
+const SEPARATOR: &str = " › ";
+
+/// Stands in for the segments dropped from the front of a breadcrumb that
+/// does not fit.
+const ELLIPSIS: &str = "…";
+
+/// What a breadcrumb segment offers when clicked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreadcrumbTarget {
+    /// The file path: the other files in its directory.
+    File,
+    /// A symbol, by the byte offset of its name: its siblings.
+    Symbol(usize),
+}
+
+/// One clickable breadcrumb segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreadcrumbSegment {
+    /// Screen columns the segment covers.
+    pub columns: Range<u16>,
+    pub target: BreadcrumbTarget,
+}
+
+/// The sticky lines and breadcrumb row drawn for one split, kept for mouse
+/// hit testing.
+#[derive(Debug, Clone)]
+pub struct ScopeHeaderArea {
+    pub split_id: LeafId,
+    pub buffer_id: BufferId,
+    /// The pinned lines, one row each from the top of the text area.
+    pub sticky_rect: Option<Rect>,
+    /// Byte offset each pinned line jumps to when clicked.
+    pub sticky_targets: Vec<usize>,
+    pub breadcrumb_rect: Option<Rect>,
+    pub breadcrumb_segments: Vec<BreadcrumbSegment>,
+}
+
+impl ScopeHeaderArea {
+    /// The jump target of the pinned line at a screen cell.
+    pub fn sticky_target_at(&self, col: u16, row: u16) -> Option<usize> {
+        let rect = self.sticky_rect?;
+        if !contains(rect, col, row) {
+            return None;
+        }
+        self.sticky_targets.get((row - rect.y) as usize).copied()
+    }
+
+    /// The breadcrumb segment at a screen cell.
+    pub fn breadcrumb_at(&self, col: u16, row: u16) -> Option<&BreadcrumbSegment> {
+        let rect = self.breadcrumb_rect?;
+        if !contains(rect, col, row) {
+            return None;
+        }
+        self.breadcrumb_segments
+            .iter()
+            .find(|segment| segment.columns.contains(&col))
+    }
+}
+
+fn contains(rect: Rect, col: u16, row: u16) -> bool {
+    col >= rect.x && col < rect.x + rect.width && row >= rect.y && row < rect.y + rect.height
+}
+
+/// Whether a buffer gets sticky scroll and breadcrumbs at all. Both are a
+/// source-code aid: terminals, composite and virtual buffers, fixed panels
+/// and the empty-workspace placeholder never do.
+pub(crate) fn scope_headers_eligible(
+    state: &EditorState,
+    metadata: Option<&crate::app::BufferMetadata>,
+) -> bool {
+    let not_code = metadata.is_some_and(|m| m.is_virtual() || m.synthetic_placeholder);
+    !state.is_composite_buffer && state.scrollable && !not_code
+}
+
+/// Split the breadcrumb row off the top of `content_rect`. Returns the
+/// narrowed content rect and the breadcrumb rect, or the content rect
+/// unchanged when the split is too short to spare the row.
+pub(crate) fn carve_breadcrumbs(content_rect: Rect) -> (Rect, Option<Rect>) {
+    if content_rect.height < MIN_TEXT_ROWS + 1 || content_rect.width == 0 {
+        return (content_rect, None);
+    }
+    let breadcrumbs = Rect::new(content_rect.x, content_rect.y, content_rect.width, 1);
+    let text = Rect::new(
+        content_rect.x,
+        content_rect.y + 1,
+        content_rect.width,
+        content_rect.height - 1,
+    );
+    (text, Some(breadcrumbs))
+}
+
+/// A scope header pinned by sticky scroll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct StickyLine {
+    /// Line number of the header.
+    pub line: usize,
+    /// Byte offset of the symbol's name, where a click jumps.
+    pub target: usize,
+}
+
+/// First source byte shown at or below visual row `row`, skipping rows
+/// injected by plugins.
+fn source_byte_from_row(mappings: &[ViewLineMapping], row: usize) -> Option<usize> {
+    mappings.get(row..)?.iter().find_map(|mapping| {
+        if mapping.is_plugin_virtual {
+            return None;
+        }
+        mapping
+            .char_source_bytes
+            .iter()
+            .flatten()
+            .next()
+            .copied()
+            .or(Some(mapping.line_end_byte))
+    })
+}
+
+/// Header lines of the scopes enclosing `byte` whose header is above its
+/// line, outermost first, one per line and at most `max_lines` of them.
+fn enclosing_headers(
+    outline: &ScopeOutline,
+    buffer: &Buffer,
+    byte: usize,
+    max_lines: usize,
+) -> Vec<StickyLine> {
+    let anchor_line = buffer.get_line_number(byte);
+    let mut lines: Vec<StickyLine> = Vec::new();
+    for symbol in outline.enclosing(byte) {
+        let line = buffer.get_line_number(symbol.range.start);
+        if line >= anchor_line || lines.last().is_some_and(|l| l.line >= line) {
+            continue;
+        }
+        lines.push(StickyLine {
+            line,
+            target: symbol.position,
+        });
+        if lines.len() == max_lines {
+            break;
+        }
+    }
+    lines
+}
+
+/// The header lines sticky scroll pins for a split, given the rows the
+/// buffer render produced. The scopes are taken at the first row the pinned
+/// lines leave uncovered, which moves down as lines are added, so this
+/// settles on a count in at most `max_lines` rounds.
+pub(super) fn sticky_lines(
+    outline: &ScopeOutline,
+    buffer: &Buffer,
+    mappings: &[ViewLineMapping],
+    cursor: usize,
+    max_lines: usize,
+) -> Vec<StickyLine> {
+    let mut lines: Vec<StickyLine> = Vec::new();
+    while lines.len() < max_lines {
+        let Some(byte) = source_byte_from_row(mappings, lines.len()) else {
+            break;
+        };
+        let next = enclosing_headers(outline, buffer, byte, max_lines);
+        let grew = next.len() > lines.len();
+        lines = next;
+        if !grew {
+            break;
+        }
+    }
+
+    let cursor_row = mappings.iter().take(lines.len()).position(|mapping| {
+        !mapping.is_plugin_virtual
+            && (mapping.line_end_byte == cursor
+                || mapping.char_source_bytes.contains(&Some(cursor)))
+    });
+    if let Some(row) = cursor_row {
+        lines.truncate(row);
+    }
+    lines
+}
+
+/// Paint the pinned lines over the top rows of `rect` (the split's text
+/// area, gutter included), each with its line number and syntax colours.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_sticky_lines(
+    buf: &mut ratatui::buffer::Buffer,
+    state: &mut EditorState,
+    lines: &[StickyLine],
+    rect: Rect,
+    left_column: usize,
+    theme: &Theme,
+    highlight_context_bytes: usize,
+) {
+    let tab_size = state.buffer_settings.tab_size.max(1);
+    let margin = &state.margins.left_config;
+    let (number_width, separator) = if margin.enabled {
+        let separator = if margin.show_separator {
+            margin.separator.clone()
+        } else {
+            String::new()
+        };
+        (margin.width, separator)
+    } else {
+        (0, String::new())
+    };
+    let gutter_width = if margin.enabled {
+        1 + number_width + separator.chars().count()
+    } else {
+        0
+    };
+
+    for (row, sticky) in lines.iter().enumerate() {
+        let y = rect.y + row as u16;
+        if y >= rect.y + rect.height {
+            break;
+        }
+        let mut base = Style::default().fg(theme.editor_fg).bg(theme.editor_bg);
+        if row + 1 == lines.len() {
+            base = base.add_modifier(Modifier::UNDERLINED);
+        }
+        for x in rect.x..rect.x + rect.width {
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.set_symbol(" ").set_style(base);
+            }
+        }
+
+        if gutter_width > 0 {
+            let gutter = format!(
+                " {:>width$}{}",
+                sticky.line + 1,
+                separator,
+                width = number_width
+            );
+            let style = base.fg(theme.line_number_fg);
+            for (i, ch) in gutter.chars().take(rect.width as usize).enumerate() {
+                if let Some(cell) = buf.cell_mut((rect.x + i as u16, y)) {
+                    cell.set_char(ch).set_style(style);
+                }
+            }
+        }
+
+        let Some(line_start) = state.buffer.line_start_offset(sticky.line) else {
+            continue;
+        };
+        let Some(text) = state.buffer.get_line(sticky.line) else {
+            continue;
+        };
+        let mut spans = state.highlighter.highlight_viewport(
+            &state.buffer,
+            line_start,
+            line_start + text.len(),
+            theme,
+            highlight_context_bytes,
+        );
+        spans.sort_by_key(|s| s.range.start);
+
+        let text = String::from_utf8_lossy(&text);
+        let text_x = rect.x + gutter_width as u16;
+        let text_width = (rect.width as usize).saturating_sub(gutter_width);
+        let mut col = 0usize;
+        for (offset, ch) in text.char_indices() {
+            if ch == '\n' || ch == '\r' {
+                break;
+            }
+            let (glyph, width) = if ch == '\t' {
+                (' ', tab_size - col % tab_size)
+            } else {
+                (ch, ch.width().unwrap_or(0))
+            };
+            if width == 0 {
+                continue;
+            }
+            let fg = spans
+                .iter()
+                .rev()
+                .find(|s| s.range.contains(&(line_start + offset)))
+                .map_or(theme.editor_fg, |s| s.color);
+            for i in 0..width {
+                let visual = col + i;
+                if visual < left_column || visual - left_column >= text_width {
+                    continue;
+                }
+                let Some(cell) = buf.cell_mut((text_x + (visual - left_column) as u16, y)) else {
+                    continue;
+                };
+                // A wide glyph that lost its left half to the horizontal
+                // scroll shows as blank.
+                if ch == '\t' || i > 0 || visual + width - i > left_column + text_width {
+                    if i == 0 || visual == left_column {
+                        cell.set_char(' ').set_style(base.fg(fg));
+                    }
+                } else {
+                    cell.set_char(glyph).set_style(base.fg(fg));
+                }
+            }
+            col += width;
+            if col >= left_column + text_width {
+                break;
+            }
+        }
+    }
+}
+
+/// Lay out the breadcrumb text for `width` columns: the path segment and
+/// one segment per symbol name. Segments are dropped from the front, behind
+/// an ellipsis, until the rest fits; the innermost symbol is always kept.
+/// Returns each segment's text with its column range relative to the start.
+fn layout_breadcrumbs(
+    path: &str,
+    symbols: &[(String, usize)],
+    width: usize,
+) -> Vec<(String, Range<usize>, Option<BreadcrumbTarget>)> {
+    let text_width = |s: &str| s.chars().map(|c| c.width().unwrap_or(0)).sum::<usize>();
+    let mut segments: Vec<(String, BreadcrumbTarget)> = Vec::with_capacity(symbols.len() + 1);
+    segments.push((
+        path.split(['/', '\\'])
+            .filter(|c| !c.is_empty())
+            .collect::<Vec<_>>()
+            .join(SEPARATOR),
+        BreadcrumbTarget::File,
+    ));
+    segments.extend(
+        symbols
+            .iter()
+            .map(|(name, position)| (name.clone(), BreadcrumbTarget::Symbol(*position))),
+    );
+
+    let total = |segments: &[(String, BreadcrumbTarget)], elided: bool| {
+        let separators = segments.len().saturating_sub(1) + usize::from(elided);
+        segments.iter().map(|(s, _)| text_width(s)).sum::<usize>()
+            + separators * text_width(SEPARATOR)
+            + if elided { text_width(ELLIPSIS) } else { 0 }
+    };
+    let mut first = 0;
+    while first + 1 < segments.len() && total(&segments[first..], first > 0) > width {
+        first += 1;
+    }
+
+    let mut laid_out = Vec::new();
+    let mut col = 0;
+    let mut push = |text: String, target: Option<BreadcrumbTarget>| {
+        let w = text_width(&text);
+        laid_out.push((text, col..col + w, target));
+        col += w;
+    };
+    if first > 0 {
+        push(ELLIPSIS.to_string(), None);
+        push(SEPARATOR.to_string(), None);
+    }
+    let shown = segments.len() - first;
+    for (i, (text, target)) in segments.into_iter().skip(first).enumerate() {
+        push(text, Some(target));
+        if i + 1 < shown {
+            push(SEPARATOR.to_string(), None);
+        }
+    }
+    laid_out
+}
+
+/// Draw the breadcrumb row: `path`, then the `(name, position)` of each
+/// symbol enclosing the cursor, outermost first. Returns the clickable
+/// segments.
+pub(super) fn render_breadcrumbs(
+    buf: &mut ratatui::buffer::Buffer,
+    rect: Rect,
+    path: &str,
+    symbols: &[(String, usize)],
+    theme: &Theme,
+) -> Vec<BreadcrumbSegment> {
+    let base = Style::default()
+        .fg(theme.tab_inactive_fg)
+        .bg(theme.editor_bg);
+    for x in rect.x..rect.x + rect.width {
+        if let Some(cell) = buf.cell_mut((x, rect.y)) {
+            cell.set_symbol(" ").set_style(base);
+        }
+    }
+
+    // One column of padding on each side.
+    let inner = (rect.width as usize).saturating_sub(2);
+    let laid_out = layout_breadcrumbs(path, symbols, inner);
+    let innermost = laid_out.iter().rposition(|(_, _, target)| target.is_some());
+    let mut segments = Vec::new();
+    for (i, (text, columns, target)) in laid_out.into_iter().enumerate() {
+        let style = match target {
+            None => base.fg(theme.line_number_fg),
+            Some(_) if Some(i) == innermost => base.fg(theme.editor_fg),
+            Some(_) => base,
+        };
+        let start = rect.x + 1 + columns.start as u16;
+        let mut x = start;
+        for ch in text.chars() {
+            let w = ch.width().unwrap_or(0) as u16;
+            if x + w > rect.x + 1 + inner as u16 {
+                break;
+            }
+            if let Some(cell) = buf.cell_mut((x, rect.y)) {
+                cell.set_char(ch).set_style(style);
+            }
+            x += w;
+        }
+        if let Some(target) = target {
+            segments.push(BreadcrumbSegment {
+                columns: start..x,
+                target,
+            });
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::outline::OutlineSymbol;
+    use lsp_types::SymbolKind;
+
+    fn row(bytes: Range<usize>) -> ViewLineMapping {
+        ViewLineMapping {
+            char_source_bytes: bytes.clone().map(Some).collect(),
+            visual_to_char: (0..bytes.len()).collect(),
+            line_end_byte: bytes.end,
+            is_plugin_virtual: false,
+        }
+    }
+
+    /// Rows for `text` starting at line `first`, one per line.
+    fn rows(text: &str, first: usize) -> Vec<ViewLineMapping> {
+        let mut start = 0;
+        let mut rows = Vec::new();
+        for line in text.split_inclusive('\n') {
+            rows.push(row(start..start + line.len() - 1));
+            start += line.len();
+        }
+        rows.split_off(first)
+    }
+
+    fn outline(text: &str) -> ScopeOutline {
+        let symbol = |name: &str, header: &str, end: &str| {
+            let start = text.find(header).unwrap();
+            OutlineSymbol {
+                name: name.to_string(),
+                kind: SymbolKind::FUNCTION,
+                position: start,
+                depth: 0,
+                container: None,
+                range: start..text.find(end).unwrap() + end.len(),
+            }
+        };
+        let mut outline = ScopeOutline::new();
+        outline.set_from_lsp(
+            0,
+            vec![
+                symbol("Point", "impl Point", "\n}"),
+                symbol("new", "    fn new", "\n    }"),
+            ],
+        );
+        outline
+    }
+
+    const TEXT: &str = "impl Point {\n    fn new() {\n        a();\n        b();\n        c();\n        d();\n    }\n}\n";
+
+    #[test]
+    fn sticky_lines_pin_the_scopes_above_the_viewport() {
+        let buffer = Buffer::from_str_test(TEXT);
+        let outline = outline(TEXT);
+        let lines = |first: usize| -> Vec<usize> {
+            sticky_lines(&outline, &buffer, &rows(TEXT, first), usize::MAX, 5)
+                .into_iter()
+                .map(|l| l.line)
+                .collect()
+        };
+        // Both headers visible: nothing to pin.
+        assert_eq!(lines(0), Vec::<usize>::new());
+        // `impl` scrolled off: pinning it covers the `fn new` header row, so
+        // `fn new` is pinned below it.
+        assert_eq!(lines(1), vec![0, 1]);
+        assert_eq!(lines(2), vec![0, 1]);
+        // Past the end of `fn new`, only `impl` is left.
+        assert_eq!(lines(7), vec![0]);
+    }
+
+    #[test]
+    fn sticky_lines_leave_the_cursor_row_uncovered() {
+        let buffer = Buffer::from_str_test(TEXT);
+        let outline = outline(TEXT);
+        let mappings = rows(TEXT, 3);
+        let cursor = TEXT.find("c();").unwrap();
+        let lines = sticky_lines(&outline, &buffer, &mappings, cursor, 5);
+        assert_eq!(lines.len(), 1);
+    }
+
+    #[test]
+    fn breadcrumbs_drop_outer_segments_to_fit() {
+        let symbols = vec![("Point".to_string(), 10), ("new".to_string(), 30)];
+        let texts = |width: usize| -> Vec<String> {
+            layout_breadcrumbs("src/point.rs", &symbols, width)
+                .into_iter()
+                .map(|(text, _, _)| text)
+                .collect()
+        };
+        assert_eq!(
+            texts(80).concat(),
+            "src › point.rs › Point › new".to_string()
+        );
+        assert_eq!(texts(15).concat(), "… › Point › new".to_string());
+        assert_eq!(texts(3).concat(), "… › new".to_string());
+    }
+
+    #[test]
+    fn carve_leaves_short_splits_alone() {
+        let short = Rect::new(0, 0, 40, 4);
+        assert_eq!(carve_breadcrumbs(short), (short, None));
+        let tall = Rect::new(2, 1, 40, 10);
+        assert_eq!(
+            carve_breadcrumbs(tall),
+            (Rect::new(2, 2, 40, 9), Some(Rect::new(2, 1, 40, 1)))
+        );
+    }
+}
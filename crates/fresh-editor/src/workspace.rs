@@ -30,6 +30,8 @@ use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::input::input_history::get_data_dir;
+use crate::model::event::LogEntry;
+use crate::services::recovery::path_hash;
 
 /// Current workspace file format version
 pub const WORKSPACE_VERSION: u32 = 1;
@@ -301,6 +303,12 @@ pub struct SerializedFileState {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fold_indicators: Option<bool>,
 
+    /// Explicit per-buffer minimap override (`None` = follow
+    /// `editor.show_minimap`). Persists the "Toggle Minimap (Current Buffer)"
+    /// choice across restarts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimap: Option<bool>,
+
     /// Explicit per-buffer indentation-style override (`None` = follow the
     /// language default). Persists the "Toggle Indentation: Spaces ↔ Tabs
     /// (Current Buffer)" choice across restarts.
@@ -756,6 +764,233 @@ impl PersistedFileWorkspace {
     }
 }
 
+// ============================================================================
+// Persistent undo history (per-file, opt-in)
+// ============================================================================
+
+/// Current undo history file format version
+pub const UNDO_HISTORY_VERSION: u32 = 1;
+
+/// Undo histories not rewritten for this long are deleted by
+/// [`PersistedUndoStore::prune`].
+const UNDO_HISTORY_MAX_AGE_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// A file's undo history as stored on disk
+///
+/// The entries are the event log up to the point where the buffer matched
+/// the file on disk, so undoing them from the freshly loaded content walks
+/// back through the previous session's edits. `content_hash` records that
+/// content: if the file has changed since, the history no longer applies
+/// and is discarded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedUndoHistory {
+    /// Schema version for future migrations
+    pub version: u32,
+
+    /// The file this history belongs to (canonical path)
+    pub path: PathBuf,
+
+    /// SHA-256 of the buffer content the last entry leaves behind
+    pub content_hash: String,
+
+    /// Timestamp when last saved (Unix epoch seconds)
+    pub saved_at: u64,
+
+    /// Event log entries, oldest first
+    pub entries: Vec<LogEntry>,
+}
+
+/// Per-file storage for undo history (`editor.persistent_undo`)
+///
+/// Each file's history is stored at
+/// `$XDG_DATA_HOME/fresh/undo_history/{path_hash}.json`, keyed by the same
+/// path hash as recovery files. Histories are written when a file is saved,
+/// closed, or the editor quits, and read back when the file is opened.
+pub struct PersistedUndoStore;
+
+impl PersistedUndoStore {
+    /// Get the directory for undo history files
+    fn history_dir() -> io::Result<PathBuf> {
+        Ok(get_data_dir()?.join("undo_history"))
+    }
+
+    /// Get the history file path for a source file within `dir`
+    fn history_file_path(dir: &Path, source_path: &Path) -> PathBuf {
+        let canonical = source_path
+            .canonicalize()
+            .unwrap_or_else(|_| source_path.to_path_buf());
+        dir.join(format!("{}.json", path_hash(&canonical)))
+    }
+
+    /// Best-effort delete of a history file; already gone is fine.
+    fn remove_history_file(history_path: &Path) {
+        if let Err(e) = std::fs::remove_file(history_path) {
+            if e.kind() != io::ErrorKind::NotFound {
+                tracing::warn!("Failed to delete undo history {:?}: {}", history_path, e);
+            }
+        }
+    }
+
+    /// Hash of buffer content, as stored in [`PersistedUndoHistory::content_hash`]
+    pub fn content_hash(content: &[u8]) -> String {
+        use sha2::{Digest, Sha256};
+        format!("{:x}", Sha256::digest(content))
+    }
+
+    /// Load the undo history for `path`, provided it was saved for exactly
+    /// `content`. A history that no longer matches the file is deleted.
+    pub fn load(
+        path: &Path,
+        content: &[u8],
+        ephemeral_patterns: &[String],
+    ) -> Option<Vec<LogEntry>> {
+        if is_ephemeral_file(path, ephemeral_patterns) {
+            return None;
+        }
+        Self::load_from(&Self::history_dir().ok()?, path, content)
+    }
+
+    fn load_from(dir: &Path, path: &Path, content: &[u8]) -> Option<Vec<LogEntry>> {
+        let history_path = Self::history_file_path(dir, path);
+        let data = std::fs::read_to_string(&history_path).ok()?;
+        let history: PersistedUndoHistory = match serde_json::from_str(&data) {
+            Ok(h) => h,
+            Err(e) => {
+                tracing::debug!("Discarding unreadable undo history for {:?}: {}", path, e);
+                Self::remove_history_file(&history_path);
+                return None;
+            }
+        };
+
+        if history.version > UNDO_HISTORY_VERSION {
+            return None;
+        }
+
+        if history.content_hash != Self::content_hash(content) {
+            // Modified outside the editor (or by an editor without this
+            // history): the recorded edits no longer line up with the text.
+            tracing::debug!("Discarding stale undo history for {:?}", path);
+            Self::remove_history_file(&history_path);
+            return None;
+        }
+
+        Some(history.entries)
+    }
+
+    /// Save the undo history for `path` (atomic write). `content` is the
+    /// buffer content after the last entry, i.e. what is on disk. The
+    /// oldest entries are dropped to keep the file under `max_bytes`.
+    pub fn save(
+        path: &Path,
+        content: &[u8],
+        entries: Vec<LogEntry>,
+        max_bytes: u64,
+        ephemeral_patterns: &[String],
+    ) {
+        if is_ephemeral_file(path, ephemeral_patterns) {
+            return;
+        }
+        match Self::history_dir() {
+            Ok(dir) => Self::save_to(&dir, path, content, entries, max_bytes),
+            Err(e) => tracing::warn!("Failed to get undo history dir: {}", e),
+        }
+    }
+
+    fn save_to(dir: &Path, path: &Path, content: &[u8], entries: Vec<LogEntry>, max_bytes: u64) {
+        let history_path = Self::history_file_path(dir, path);
+        let entries = Self::cap_entries(entries, max_bytes);
+        if entries.is_empty() {
+            Self::remove_history_file(&history_path);
+            return;
+        }
+
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            tracing::warn!("Failed to create undo history dir: {}", e);
+            return;
+        }
+
+        let history = PersistedUndoHistory {
+            version: UNDO_HISTORY_VERSION,
+            path: path.canonicalize().unwrap_or_else(|_| path.to_path_buf()),
+            content_hash: Self::content_hash(content),
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            entries,
+        };
+        let data = match serde_json::to_string(&history) {
+            Ok(d) => d,
+            Err(e) => {
+                tracing::warn!("Failed to serialize undo history: {}", e);
+                return;
+            }
+        };
+
+        // Write atomically: temp file + rename
+        let temp_path = history_path.with_extension("json.tmp");
+        let write_result = (|| -> io::Result<()> {
+            let mut file = std::fs::File::create(&temp_path)?;
+            file.write_all(data.as_bytes())?;
+            file.sync_all()?;
+            std::fs::rename(&temp_path, &history_path)?;
+            Ok(())
+        })();
+
+        if let Err(e) = write_result {
+            tracing::warn!("Failed to save undo history for {:?}: {}", path, e);
+        } else {
+            tracing::trace!("Undo history saved for {:?}", path);
+        }
+    }
+
+    /// Drop the oldest entries until the serialized entries fit in
+    /// `max_bytes`.
+    fn cap_entries(mut entries: Vec<LogEntry>, max_bytes: u64) -> Vec<LogEntry> {
+        let sizes: Vec<u64> = entries
+            .iter()
+            .map(|e| serde_json::to_string(e).map_or(u64::MAX, |s| s.len() as u64 + 1))
+            .collect();
+        let mut total: u64 = sizes.iter().fold(0, |acc, &s| acc.saturating_add(s));
+        let mut drop = 0;
+        while total > max_bytes && drop < sizes.len() {
+            total = total.saturating_sub(sizes[drop]);
+            drop += 1;
+        }
+        entries.drain(..drop);
+        entries
+    }
+
+    /// Delete histories that have not been written for a month, and those
+    /// whose file no longer exists.
+    pub fn prune() {
+        if let Ok(dir) = Self::history_dir() {
+            Self::prune_in(&dir, UNDO_HISTORY_MAX_AGE_SECS);
+        }
+    }
+
+    fn prune_in(dir: &Path, max_age_secs: u64) {
+        let Ok(read_dir) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        for entry in read_dir.flatten() {
+            let file_path = entry.path();
+            let keep = std::fs::read_to_string(&file_path)
+                .ok()
+                .and_then(|data| serde_json::from_str::<PersistedUndoHistory>(&data).ok())
+                .is_some_and(|h| now.saturating_sub(h.saved_at) <= max_age_secs && h.path.exists());
+            if !keep {
+                tracing::debug!("Pruning undo history {:?}", file_path);
+                Self::remove_history_file(&file_path);
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Workspace file management
 // ============================================================================
@@ -1526,6 +1761,7 @@ mod tests {
             virtual_space: None,
             indentation_guide: None,
             fold_indicators: None,
+            minimap: None,
             use_tabs: None,
             whitespace_indicators: None,
             tab_indicators: None,
@@ -1890,6 +2126,7 @@ mod tests {
             virtual_space: None,
             indentation_guide: None,
             fold_indicators: None,
+            minimap: None,
             use_tabs: None,
             whitespace_indicators: None,
             tab_indicators: None,
@@ -1916,4 +2153,69 @@ mod tests {
             "a configured pattern must exclude a non-git file from the store"
         );
     }
+
+    fn insert_entry(position: usize, text: &str) -> LogEntry {
+        LogEntry::new(crate::model::event::Event::Insert {
+            position,
+            text: text.to_string(),
+            cursor_id: crate::model::event::CursorId(0),
+        })
+    }
+
+    #[test]
+    fn test_undo_history_round_trip_and_stale_content() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let store = temp.path().join("undo_history");
+        let file = temp.path().join("notes.txt");
+        std::fs::write(&file, "ab").unwrap();
+
+        let entries = vec![insert_entry(0, "a"), insert_entry(1, "b")];
+        PersistedUndoStore::save_to(&store, &file, b"ab", entries, u64::MAX);
+
+        let loaded = PersistedUndoStore::load_from(&store, &file, b"ab").unwrap();
+        assert_eq!(loaded.len(), 2);
+
+        // Changed outside the editor: the history is dropped for good.
+        assert!(PersistedUndoStore::load_from(&store, &file, b"abc").is_none());
+        assert!(PersistedUndoStore::load_from(&store, &file, b"ab").is_none());
+    }
+
+    #[test]
+    fn test_undo_history_drops_oldest_entries_over_cap() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let store = temp.path().join("undo_history");
+        let file = temp.path().join("notes.txt");
+        std::fs::write(&file, "").unwrap();
+
+        let entries: Vec<LogEntry> = (0..10).map(|i| insert_entry(i, "x")).collect();
+        let one = serde_json::to_string(&entries[0]).unwrap().len() as u64 + 1;
+        PersistedUndoStore::save_to(&store, &file, b"", entries, one * 3);
+
+        let loaded = PersistedUndoStore::load_from(&store, &file, b"").unwrap();
+        let positions: Vec<usize> = loaded
+            .iter()
+            .map(|e| match e.event {
+                crate::model::event::Event::Insert { position, .. } => position,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(positions, vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn test_undo_history_prune_removes_orphans() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let store = temp.path().join("undo_history");
+        let kept = temp.path().join("kept.txt");
+        let deleted = temp.path().join("deleted.txt");
+        std::fs::write(&kept, "k").unwrap();
+        std::fs::write(&deleted, "d").unwrap();
+        PersistedUndoStore::save_to(&store, &kept, b"k", vec![insert_entry(0, "k")], u64::MAX);
+        PersistedUndoStore::save_to(&store, &deleted, b"d", vec![insert_entry(0, "d")], u64::MAX);
+        std::fs::remove_file(&deleted).unwrap();
+
+        PersistedUndoStore::prune_in(&store, UNDO_HISTORY_MAX_AGE_SECS);
+        assert!(PersistedUndoStore::load_from(&store, &kept, b"k").is_some());
+        assert_eq!(std::fs::read_dir(&store).unwrap().count(), 1);
+    }
 }
@@ -1,5 +1,6 @@
 // Test file fixtures
 
+use fresh::config::Config;
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
@@ -101,6 +102,17 @@ pub fn test_temp_dir(test_name: &str) -> anyhow::Result<PathBuf> {
     Ok(path)
 }
 
+/// Default config keeping only the built-in `language`, for settings tests
+/// that open it from the General page: its entry is then the page's first
+/// `[Enter to edit]` map row. Debug Adapters sorts above Languages there, so
+/// its built-in entries are dropped too.
+pub fn single_language_config(language: &str) -> Config {
+    let mut config = Config::default();
+    config.languages.retain(|name, _| name == language);
+    config.debug_adapters.clear();
+    config
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
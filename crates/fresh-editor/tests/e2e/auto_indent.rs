@@ -2220,3 +2220,37 @@ fn test_config_indent_next_line_pattern_applies() {
         content
     );
 }
+
+/// Test that Enter inside code embedded in another language indents by the
+/// embedded language: JavaScript in a Templ `<script>`
+#[test]
+fn test_auto_indent_in_injected_language() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("page.templ");
+    let source =
+        "package main\n\ntempl page() {\n\t<script>\n\t\tif (ready) {\n\t\t}\n\t</script>\n}\n";
+    std::fs::write(&file_path, source).unwrap();
+
+    let mut harness = harness_with_auto_indent();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    let line_end = source.find("if (ready) {").unwrap() + "if (ready) {".len();
+    harness.editor_mut().goto_byte_offset(line_end);
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    // One level deeper than the `if` line, which sits two tabs in.
+    let content = harness.get_buffer_content().unwrap();
+    let new_line = content[line_end + 1..].lines().next().unwrap();
+    let width: usize = new_line
+        .chars()
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum();
+    assert_eq!(
+        width, 12,
+        "Expected one more indent level, got: {content:?}"
+    );
+}
@@ -362,6 +362,87 @@ fn test_quick_open_goto_line_live_preview_cancel_restores() {
         .expect("Esc should restore cursor to pre-preview line 1");
 }
 
+fn write_markdown_sections(path: &std::path::Path) {
+    let mut s = String::from("# Intro\n");
+    for (heading, filler) in [("## Install", 30), ("## Usage", 40)] {
+        for i in 0..filler {
+            s.push_str(&format!("text {i}\n"));
+        }
+        s.push_str(heading);
+        s.push('\n');
+    }
+    fs::write(path, s).unwrap();
+}
+
+/// `@` lists the current file's symbols (here Markdown headings from the
+/// built-in outline, as no language server runs), previews the selected one
+/// and jumps there on Enter.
+#[test]
+fn test_quick_open_document_symbols_jump() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut harness =
+        EditorTestHarness::with_temp_project_and_config(100, 24, Default::default()).unwrap();
+    let path = harness.project_dir().unwrap().join("notes.md");
+    write_markdown_sections(&path);
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("@").unwrap();
+    harness.wait_for_screen_contains("Install").unwrap();
+    harness.assert_screen_contains("Usage");
+
+    harness.type_text("usage").unwrap();
+    harness
+        .wait_until(|h| !h.screen_to_string().contains("text 0"))
+        .expect("Selecting ## Usage should preview it");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("Ln 73,").unwrap();
+}
+
+/// Esc after moving through `@` symbols puts the cursor back.
+#[test]
+fn test_quick_open_document_symbols_cancel_restores() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut harness =
+        EditorTestHarness::with_temp_project_and_config(100, 24, Default::default()).unwrap();
+    let path = harness.project_dir().unwrap().join("notes.md");
+    write_markdown_sections(&path);
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(
+            KeyCode::Char('o'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    harness.wait_for_screen_contains("Install").unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .wait_until(|h| !h.screen_to_string().contains("text 0"))
+        .expect("Moving to ## Usage should preview it");
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("Ln 1,") && screen.contains("# Intro")
+        })
+        .expect("Esc should restore the cursor to line 1");
+}
+
 /// Clicking in the editor while a goto-line preview is active should commit
 /// the click as the new cursor position — Esc afterwards must NOT restore
 /// the pre-preview snapshot over the user's click.
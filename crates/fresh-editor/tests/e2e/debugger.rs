@@ -0,0 +1,235 @@
+//! Debug Adapter Protocol client, driven end-to-end against a fake adapter.
+//!
+//! The adapter is a bash script speaking DAP over stdio. It stops at the
+//! first breakpoint the editor sends in `setBreakpoints`, steps one line on
+//! `next`, and prints output + terminates on `continue` — enough to walk the
+//! whole session lifecycle through the editor's key bindings.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn create_fake_adapter_script(dir: &std::path::Path) -> std::path::PathBuf {
+    let script = r##"#!/bin/bash
+LOG_FILE="$1"
+SOURCE="$2"
+> "$LOG_FILE"
+SEQ=0
+STOP_LINE=1
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}
+
+respond() {
+    SEQ=$((SEQ + 1))
+    send_message '{"seq":'"$SEQ"',"type":"response","request_seq":'"$1"',"success":true,"command":"'"$2"'","body":'"$3"'}'
+}
+
+event() {
+    SEQ=$((SEQ + 1))
+    send_message '{"seq":'"$SEQ"',"type":"event","event":"'"$1"'","body":'"$2"'}'
+}
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+    command=$(echo "$msg" | grep -o '"command":"[^"]*"' | head -1 | cut -d'"' -f4)
+    seq=$(echo "$msg" | grep -o '"seq":[0-9]*' | head -1 | cut -d':' -f2)
+    echo "RECV: $command" >> "$LOG_FILE"
+    case "$command" in
+        "initialize")
+            respond "$seq" initialize '{"supportsConfigurationDoneRequest":true}'
+            event initialized '{}'
+            ;;
+        "setBreakpoints")
+            line=$(echo "$msg" | grep -o '"line":[0-9]*' | head -1 | cut -d':' -f2)
+            echo "BREAKPOINT: $line" >> "$LOG_FILE"
+            if [ -n "$line" ]; then STOP_LINE=$line; fi
+            respond "$seq" setBreakpoints '{"breakpoints":[{"verified":true}]}'
+            ;;
+        "configurationDone")
+            respond "$seq" configurationDone '{}'
+            event stopped '{"reason":"breakpoint","threadId":1}'
+            ;;
+        "stackTrace")
+            respond "$seq" stackTrace '{"stackFrames":[{"id":1,"name":"main","line":'"$STOP_LINE"',"column":1,"source":{"path":"'"$SOURCE"'"}}]}'
+            ;;
+        "next")
+            STOP_LINE=$((STOP_LINE + 1))
+            respond "$seq" next '{}'
+            event stopped '{"reason":"step","threadId":1}'
+            ;;
+        "continue")
+            respond "$seq" continue '{"allThreadsContinued":true}'
+            event continued '{"threadId":1}'
+            event output '{"category":"stdout","output":"hello\n"}'
+            event terminated '{}'
+            ;;
+        "disconnect")
+            respond "$seq" disconnect '{}'
+            break
+            ;;
+        *)
+            if [ -n "$seq" ]; then
+                respond "$seq" "$command" '{}'
+            fi
+            ;;
+    esac
+done
+echo "ADAPTER: exiting" >> "$LOG_FILE"
+"##;
+
+    let script_path = dir.join("fake_debug_adapter.sh");
+    std::fs::write(&script_path, script).expect("failed to write fake adapter");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path).unwrap().permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms).unwrap();
+    }
+
+    script_path
+}
+
+fn harness_with_fake_adapter(
+    temp_dir: &std::path::Path,
+    source: &std::path::Path,
+    log_file: &std::path::Path,
+) -> anyhow::Result<EditorTestHarness> {
+    let script_path = create_fake_adapter_script(temp_dir);
+    let mut config = fresh::config::Config::default();
+    config.debug_adapters.insert(
+        "python".to_string(),
+        fresh::types::DebugAdapterConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: vec![
+                log_file.to_string_lossy().to_string(),
+                source.to_string_lossy().to_string(),
+            ],
+            request: fresh::types::DebugRequestKind::Launch,
+            configuration: serde_json::json!({ "program": "${file}" }),
+            env: Default::default(),
+        },
+    );
+    EditorTestHarness::with_config_and_working_dir(100, 24, config, temp_dir.to_path_buf())
+}
+
+fn status_contains(harness: &EditorTestHarness, needle: &str) -> bool {
+    harness
+        .editor()
+        .get_status_message()
+        .is_some_and(|msg| msg.contains(needle))
+}
+
+fn log_contains(log_file: &std::path::Path, needle: &str) -> bool {
+    std::fs::read_to_string(log_file)
+        .map(|log| log.contains(needle))
+        .unwrap_or(false)
+}
+
+/// Breakpoint in the gutter → adapter receives it → the editor stops there,
+/// steps, continues, and the session ends when the adapter terminates.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_debug_session_stops_at_breakpoint_steps_and_ends() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("main.py");
+    std::fs::write(&source, "a = 1\nb = 2\nc = 3\nprint(a + b + c)\n")?;
+    let log_file = temp_dir.path().join("adapter.log");
+
+    let mut harness = harness_with_fake_adapter(temp_dir.path(), &source, &log_file)?;
+    harness.open_file(&source)?;
+    harness.render()?;
+
+    // Breakpoint on line 2.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::F(9), KeyModifiers::NONE)?;
+    harness.render()?;
+    assert!(
+        harness.screen_to_string().contains('●'),
+        "breakpoint indicator should be drawn in the gutter:\n{}",
+        harness.screen_to_string()
+    );
+
+    // Move away so the stop has to bring the cursor back.
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::F(5), KeyModifiers::CONTROL)?;
+    harness.wait_until(|h| status_contains(h, "Stopped: breakpoint"))?;
+    harness.wait_until(|h| h.screen_to_string().contains('▶'))?;
+    assert!(log_contains(&log_file, "BREAKPOINT: 2"));
+    assert!(log_contains(&log_file, "RECV: configurationDone"));
+    assert_eq!(
+        harness.editor().active_cursors().primary().position,
+        6,
+        "cursor should be at the start of the stopped line"
+    );
+
+    // Step over: the adapter reports the next line.
+    harness.send_key(KeyCode::F(10), KeyModifiers::CONTROL)?;
+    harness.wait_until(|h| status_contains(h, "Stopped: step"))?;
+    harness.wait_until(|h| h.editor().active_cursors().primary().position == 12)?;
+
+    // Continue: the adapter terminates and the session is torn down.
+    harness.send_key(KeyCode::F(5), KeyModifiers::CONTROL)?;
+    harness.wait_until(|h| status_contains(h, "Debug session ended"))?;
+    harness.render()?;
+    let screen = harness.screen_to_string();
+    assert!(
+        !screen.contains('▶'),
+        "stopped-line marker must be cleared:\n{}",
+        screen
+    );
+    assert!(
+        screen.contains('●'),
+        "breakpoints outlive the session:\n{}",
+        screen
+    );
+    harness.wait_until(|_| log_contains(&log_file, "RECV: disconnect"))?;
+
+    Ok(())
+}
+
+/// A missing adapter is reported instead of silently doing nothing.
+#[test]
+fn test_debug_start_without_adapter_reports_it() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("notes.txt");
+    std::fs::write(&source, "plain text\n")?;
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        fresh::config::Config::default(),
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&source)?;
+    harness.send_key(KeyCode::F(5), KeyModifiers::CONTROL)?;
+    harness.render()?;
+    assert!(
+        status_contains(&harness, "No debug adapter configured for text"),
+        "status: {:?}",
+        harness.editor().get_status_message()
+    );
+    Ok(())
+}
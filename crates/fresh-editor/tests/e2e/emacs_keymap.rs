@@ -239,3 +239,66 @@ fn default_keymap_minibuffer_ctrl_a_still_selects_all() {
     harness.assert_screen_not_contains("123");
     harness.assert_screen_contains("X");
 }
+
+fn emacs_harness_with_private_clipboard() -> EditorTestHarness {
+    let mut harness = emacs_harness();
+    // Keep parallel tests off the shared system clipboard.
+    harness.editor_mut().set_clipboard_for_test(String::new());
+    harness
+}
+
+/// Consecutive `C-k` kills build one kill ring entry, so a single `C-y`
+/// yanks back everything they removed.
+#[test]
+fn consecutive_kills_yank_back_as_one_block() {
+    let mut harness = emacs_harness_with_private_clipboard();
+    harness.type_text("first\nsecond\nkeep").unwrap();
+    alt(&mut harness, '<');
+    ctrl(&mut harness, 'k');
+    ctrl(&mut harness, 'k');
+    ctrl(&mut harness, 'k');
+    ctrl(&mut harness, 'k');
+    assert_eq!(harness.get_buffer_content().unwrap(), "keep");
+
+    ctrl(&mut harness, 'y');
+    assert_eq!(harness.get_buffer_content().unwrap(), "first\nsecond\nkeep");
+}
+
+/// `M-y` right after `C-y` swaps the yanked text for the previous kill.
+#[test]
+fn meta_y_cycles_to_the_previous_kill() {
+    let mut harness = emacs_harness_with_private_clipboard();
+    harness.type_text("one two").unwrap();
+    alt(&mut harness, '<');
+    alt(&mut harness, 'd');
+    // Break the kill chain so the second kill is its own entry.
+    ctrl(&mut harness, 'f');
+    ctrl(&mut harness, 'k');
+    assert_eq!(harness.get_buffer_content().unwrap(), "t");
+
+    ctrl(&mut harness, 'y');
+    assert_eq!(harness.get_buffer_content().unwrap(), "two");
+    alt(&mut harness, 'y');
+    assert_eq!(harness.get_buffer_content().unwrap(), "tone ");
+    alt(&mut harness, 'y');
+    assert_eq!(harness.get_buffer_content().unwrap(), "two");
+}
+
+/// A click between two kills ends the chain like a motion key does, so the
+/// second kill is an entry of its own.
+#[test]
+fn click_between_kills_starts_a_new_entry() {
+    let mut harness = emacs_harness_with_private_clipboard();
+    harness.type_text("one two\nthree").unwrap();
+    alt(&mut harness, '<');
+    alt(&mut harness, 'd');
+    assert_eq!(harness.get_buffer_content().unwrap(), "two\nthree");
+
+    let (col, row) = harness.find_text_on_screen("three").unwrap();
+    harness.mouse_click(col, row).unwrap();
+    ctrl(&mut harness, 'k');
+    assert_eq!(harness.get_buffer_content().unwrap(), "two\n");
+
+    ctrl(&mut harness, 'y');
+    assert_eq!(harness.get_buffer_content().unwrap(), "two\nthree");
+}
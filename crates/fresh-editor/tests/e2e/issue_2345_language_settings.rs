@@ -19,6 +19,7 @@
 //! Both tests drive only keyboard events and assert on rendered output, per
 //! CONTRIBUTING.md ("E2E Tests Observe, Not Inspect").
 
+use crate::common::fixtures::single_language_config;
 use crate::common::harness::EditorTestHarness;
 use crossterm::event::{KeyCode, KeyModifiers};
 use fresh::config::Config;
@@ -34,8 +35,7 @@ const LONG_HTML: &str = "<!DOCTYPE html>\n<p>This is a deliberately very long li
 /// "Languages" map is deterministic to navigate. Global `line_wrap` and
 /// `auto_surround` keep their defaults (both `true`).
 fn html_only_config() -> Config {
-    let mut config = Config::default();
-    config.languages.retain(|name, _| name == "html");
+    let config = single_language_config("html");
     assert!(
         config.editor.line_wrap,
         "precondition: global line_wrap defaults to true"
@@ -186,9 +186,10 @@ fn issue_2345_inherited_auto_surround_shows_neutral_chip() {
 /// that one field to inherited, without touching its siblings.
 #[test]
 fn issue_2345_per_field_inherit_button_reverts_single_field() {
-    // 32 rows: the dialog lists fields alphabetically and the assertion below
-    // needs the "Line Wrap" row on screen ("Indentation Guide" sits above it).
-    let mut harness = EditorTestHarness::with_config(120, 32, html_only_config()).unwrap();
+    // 33 rows: the dialog lists fields alphabetically and the assertion below
+    // needs the "Line Wrap" row on screen ("Format On Type" and "Indentation
+    // Guide" sit above it).
+    let mut harness = EditorTestHarness::with_config(120, 33, html_only_config()).unwrap();
     harness.render().unwrap();
 
     open_html_language_dialog(&mut harness);
@@ -427,8 +428,7 @@ fn issue_2345_reset_button_reachable_by_back_tab() {
 fn issue_2345_reset_restores_builtin_formatter() {
     // A language that ships a formatter (c -> clang-format), overridden here to
     // a custom command so it differs from the built-in default.
-    let mut config = Config::default();
-    config.languages.retain(|name, _| name == "c");
+    let mut config = single_language_config("c");
     let mut fmt = config
         .languages
         .get("c")
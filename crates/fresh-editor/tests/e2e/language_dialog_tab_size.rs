@@ -10,6 +10,7 @@
 //! asserts on rendered output, per CONTRIBUTING.md ("E2E Tests Observe, Not
 //! Inspect").
 
+use crate::common::fixtures::single_language_config;
 use crate::common::harness::EditorTestHarness;
 use crossterm::event::{KeyCode, KeyModifiers};
 use fresh::config::Config;
@@ -17,8 +18,7 @@ use fresh::config::Config;
 /// A config with a single `typescript` language entry so the Settings
 /// "Languages" map is deterministic to navigate.
 fn typescript_only_config() -> Config {
-    let mut config = Config::default();
-    config.languages.retain(|name, _| name == "typescript");
+    let config = single_language_config("typescript");
     assert!(
         config.languages.contains_key("typescript"),
         "precondition: typescript ships as a built-in language"
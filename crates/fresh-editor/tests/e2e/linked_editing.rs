@@ -0,0 +1,260 @@
+//! E2E tests for linked editing and on-type formatting.
+//!
+//! A fake server links the opening and closing tag names of a one-line
+//! `<name>hi</name>` document, and answers `;` with an edit that appends a
+//! comment to the line — enough to see both features go through the
+//! language server and back into the buffer.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn fake_server_script(log_path: &str) -> String {
+    format!(
+        r##"#!/bin/bash
+
+LOG_FILE="{log_path}"
+
+> "$LOG_FILE"
+
+read_message() {{
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}}
+
+send_message() {{
+    local message="$1"
+    local length=${{#message}}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}}
+
+range() {{
+    echo '{{"start":{{"line":0,"character":'$1'}},"end":{{"line":0,"character":'$2'}}}}'
+}}
+
+# The one-line document, kept in sync from didOpen / didChange.
+TEXT=""
+TAG_RE='^<([a-z]+)>hi</([a-z]+)>$'
+
+while true; do
+    msg=$(read_message)
+
+    if [ -z "$msg" ]; then
+        break
+    fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    echo "RECV: method=$method id=$msg_id" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"capabilities":{{"linkedEditingRangeProvider":true,"documentOnTypeFormattingProvider":{{"firstTriggerCharacter":";"}},"textDocumentSync":2}}}}}}'
+            ;;
+        "textDocument/didOpen")
+            TEXT=$(echo "$msg" | grep -o '"text":"[^"]*"' | cut -d'"' -f4)
+            ;;
+        "textDocument/didChange")
+            changes=$(echo "$msg" | grep -o '"range":{{"start":{{"line":0,"character":[0-9]*}},"end":{{"line":0,"character":[0-9]*}}}}[^}}]*"text":"[^"]*"')
+            while read -r change; do
+                [ -z "$change" ] && continue
+                S=$(echo "$change" | grep -o '"character":[0-9]*' | sed -n 1p | cut -d: -f2)
+                E=$(echo "$change" | grep -o '"character":[0-9]*' | sed -n 2p | cut -d: -f2)
+                T=$(echo "$change" | grep -o '"text":"[^"]*"' | cut -d'"' -f4)
+                TEXT="${{TEXT:0:S}}${{T}}${{TEXT:E}}"
+            done <<< "$changes"
+            ;;
+        "textDocument/linkedEditingRange")
+            POS=$(echo "$msg" | grep -o '"character":[0-9]*' | cut -d: -f2)
+            RESULT=null
+            if [[ $TEXT =~ $TAG_RE ]] && [ "${{BASH_REMATCH[1]}}" = "${{BASH_REMATCH[2]}}" ]; then
+                LEN=${{#BASH_REMATCH[1]}}
+                CLOSE=$((LEN + 6))
+                if [ $POS -ge 1 ] && [ $POS -le $((LEN + 1)) ] || [ $POS -ge $CLOSE ] && [ $POS -le $((CLOSE + LEN)) ]; then
+                    RESULT='{{"ranges":['"$(range 1 $((LEN + 1)))"','"$(range $CLOSE $((CLOSE + LEN)))"']}}'
+                fi
+            fi
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":'"$RESULT"'}}'
+            ;;
+        "textDocument/onTypeFormatting")
+            END=${{#TEXT}}
+            EDIT='{{"range":'"$(range $END $END)"',"newText":" <!-- formatted -->"}}'
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":['"$EDIT"']}}'
+            ;;
+        "textDocument/diagnostic")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"kind":"full","items":[]}}}}'
+            ;;
+        "shutdown")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":null}}'
+            break
+            ;;
+    esac
+done
+"##
+    )
+}
+
+/// Open `text` as an HTML file served by the fake server, with `configure`
+/// applied to the `html` language settings.
+fn open_html(
+    text: &str,
+    configure: impl FnOnce(&mut fresh::config::LanguageConfig),
+) -> anyhow::Result<(EditorTestHarness, tempfile::TempDir, std::path::PathBuf)> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().canonicalize()?;
+
+    let main_file = project_root.join("index.html");
+    std::fs::write(&main_file, text)?;
+
+    let log_file = project_root.join("lsp_log.txt");
+    let script_path = project_root.join("fake_html_lsp.sh");
+    std::fs::write(&script_path, fake_server_script(log_file.to_str().unwrap()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let mut config = fresh::config::Config::default();
+    configure(config.languages.get_mut("html").unwrap());
+    config.lsp.insert(
+        "html".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, project_root)?;
+    harness.open_file(&main_file)?;
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(&log_file).unwrap_or_default();
+        log.contains("method=initialized")
+    })?;
+    Ok((harness, temp_dir, log_file))
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_linked_editing_renames_the_closing_tag() -> anyhow::Result<()> {
+    let (mut harness, _dir, log_file) = open_html("<div>hi</div>", |_| {})?;
+
+    // Cursor at the end of the opening tag name: a cursor joins it at the
+    // end of the closing one.
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE)?;
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+    harness.wait_until(|h| h.cursor_count() == 2)?;
+    assert_eq!(harness.cursor_position(), 4);
+
+    harness.type_text("x")?;
+    harness.wait_until(|h| h.get_buffer_content().unwrap() == "<divx>hi</divx>")?;
+    harness.type_text("y")?;
+    harness.wait_until(|h| h.get_buffer_content().unwrap() == "<divxy>hi</divxy>")?;
+
+    // One undo takes the keystroke back from both tags.
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)?;
+    assert_eq!(harness.get_buffer_content().unwrap(), "<divx>hi</divx>");
+
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(log.contains("method=textDocument/linkedEditingRange"));
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_linked_editing_stops_at_a_non_name_character() -> anyhow::Result<()> {
+    let (mut harness, _dir, _log) = open_html("<div>hi</div>", |_| {})?;
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE)?;
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+    harness.wait_until(|h| h.cursor_count() == 2)?;
+
+    // A space starts the attributes: only the opening tag gets it.
+    harness.type_text(" ")?;
+    assert_eq!(harness.get_buffer_content().unwrap(), "<div >hi</div>");
+    assert_eq!(harness.cursor_count(), 1);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_linked_editing_off_for_the_language() -> anyhow::Result<()> {
+    let (mut harness, _dir, log_file) =
+        open_html("<div>hi</div>", |lang| lang.linked_editing = false)?;
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE)?;
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+    harness.type_text("x")?;
+    harness.render()?;
+    assert_eq!(harness.get_buffer_content().unwrap(), "<divx>hi</div>");
+    assert_eq!(harness.cursor_count(), 1);
+
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(!log.contains("method=textDocument/linkedEditingRange"));
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_format_on_type_applies_the_server_edits() -> anyhow::Result<()> {
+    let (mut harness, _dir, log_file) = open_html("<p>hi</p>", |lang| {
+        lang.linked_editing = false;
+        lang.format_on_type = true;
+    })?;
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE)?;
+    harness.type_text(";")?;
+    harness.wait_until(|h| h.get_buffer_content().unwrap() == "<p>hi</p>; <!-- formatted -->")?;
+
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(log.contains("method=textDocument/onTypeFormatting"));
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_format_on_type_is_off_by_default() -> anyhow::Result<()> {
+    let (mut harness, _dir, log_file) = open_html("<p>hi</p>", |lang| lang.linked_editing = false)?;
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE)?;
+    harness.type_text(";")?;
+    harness.render()?;
+    assert_eq!(harness.get_buffer_content().unwrap(), "<p>hi</p>;");
+
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(!log.contains("method=textDocument/onTypeFormatting"));
+    Ok(())
+}
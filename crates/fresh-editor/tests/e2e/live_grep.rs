@@ -1354,3 +1354,112 @@ fn test_live_grep_overlay_is_mouse_modal() {
         "overlay should still be open after the clicks"
     );
 }
+
+/// Editable results: the last search opens as a plain buffer of
+/// `file:line: content` rows, and Ctrl+S writes changed rows back — on
+/// disk for files that aren't open, into the buffer (unsaved) for files
+/// that are — while a line that changed since the search is left alone
+/// and reported.
+#[test]
+fn test_live_grep_edit_results_writes_back_and_reports_conflicts() {
+    let git_check = std::process::Command::new("git").arg("--version").output();
+    if git_check.is_err() || !git_check.as_ref().unwrap().status.success() {
+        eprintln!("Skipping test: `git` is not installed or not in PATH");
+        return;
+    }
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().canonicalize().unwrap().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let run_git = |args: &[&str]| {
+        let out = git_command(&project_root).args(args).output().unwrap();
+        assert!(out.status.success(), "git {:?} failed", args);
+    };
+    run_git(&["init", "--quiet", "-b", "main"]);
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+    copy_plugin(&plugins_dir, "live_grep");
+
+    let token = "WGREP_TOKEN_3f9b";
+    let closed = project_root.join("a.rs");
+    let open = project_root.join("b.rs");
+    fs::write(&closed, format!("let {token} = 1;\n{token} += 2;\n")).unwrap();
+    fs::write(&open, format!("fn f({token}: u8) {{}}\n")).unwrap();
+    run_git(&["add", "a.rs", "b.rs"]);
+    run_git(&["commit", "--quiet", "-m", "seed"]);
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        140,
+        30,
+        Default::default(),
+        project_root.clone(),
+    )
+    .unwrap();
+    harness.open_file(&open).unwrap();
+    harness.render().unwrap();
+
+    harness.run_palette_command("Live Grep (Find").unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Search in:"))
+        .unwrap();
+    harness.type_text(token).unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("b.rs:1"))
+        .unwrap();
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+
+    // Someone else touches a matched line after the search.
+    fs::write(&closed, format!("let {token} = 1;\n{token} += 3;\n")).unwrap();
+
+    harness
+        .run_palette_command("Live Grep: Edit Results")
+        .unwrap();
+    harness
+        .wait_for_screen_contains("Edit the lines below")
+        .unwrap();
+    harness.wait_for_screen_contains("a.rs:2:").unwrap();
+
+    // Row order follows the results: hint, a.rs:1, a.rs:2, b.rs:1.
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("9;").unwrap();
+    for suffix in [" // stale", " // ok"] {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+        harness.type_text(suffix).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    harness
+        .wait_until(|_| {
+            fs::read_to_string(&closed).unwrap() == format!("let {token} = 9;\n{token} += 3;\n")
+        })
+        .unwrap();
+    harness
+        .wait_for_screen_contains("2 applied, 1 changed since search (a.rs:2)")
+        .unwrap();
+
+    // The open file was edited in its buffer and left for the user to save.
+    assert_eq!(
+        fs::read_to_string(&open).unwrap(),
+        format!("fn f({token}: u8) {{}}\n")
+    );
+    harness.open_file(&open).unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        format!("fn f({token}: u8) {{}} // ok\n")
+    );
+}
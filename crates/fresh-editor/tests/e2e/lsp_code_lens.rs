@@ -0,0 +1,184 @@
+//! E2E test for LSP code lens.
+//!
+//! A fake LSP server (bash script) advertises `codeLensProvider` with
+//! `resolveProvider`. `main` gets one ready lens ("Run") and one that only
+//! carries `data` and must be resolved ("N references"); `helper` gets a
+//! "Debug" lens. Executing "Debug" makes the server ask for a
+//! `workspace/codeLens/refresh`, after which the reference count changes.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Screen position of the first occurrence of `needle`.
+fn find_on_screen(harness: &EditorTestHarness, needle: &str) -> Option<(u16, u16)> {
+    harness
+        .screen_to_string()
+        .lines()
+        .enumerate()
+        .find_map(|(row, line)| {
+            let byte = line.find(needle)?;
+            Some((line[..byte].chars().count() as u16, row as u16))
+        })
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_code_lens_render_resolve_execute_and_refresh() -> anyhow::Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().canonicalize()?;
+
+    let main_file = project_root.join("main.py");
+    std::fs::write(
+        &main_file,
+        "def main():\n    helper()\n\ndef helper():\n    pass\n",
+    )?;
+
+    let log_file = project_root.join("lsp_log.txt");
+    let log_path = log_file.to_str().unwrap();
+
+    let script = format!(
+        r##"#!/bin/bash
+
+LOG_FILE="{log_path}"
+REFS=2
+
+> "$LOG_FILE"
+
+read_message() {{
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}}
+
+send_message() {{
+    local message="$1"
+    local length=${{#message}}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}}
+
+range() {{
+    echo '{{"start":{{"line":'$1',"character":4}},"end":{{"line":'$1',"character":8}}}}'
+}}
+
+while true; do
+    msg=$(read_message)
+
+    if [ -z "$msg" ]; then
+        break
+    fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    echo "RECV: method=$method id=$msg_id" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"capabilities":{{"codeLensProvider":{{"resolveProvider":true}},"executeCommandProvider":{{"commands":["test.run","test.debug"]}},"textDocumentSync":1}}}}}}'
+            ;;
+        "initialized")
+            echo "ACTION: initialized" >> "$LOG_FILE"
+            ;;
+        "textDocument/codeLens")
+            RUN='{{"range":'"$(range 0)"',"command":{{"title":"Run","command":"test.run","arguments":["main"]}}}}'
+            REFS_LENS='{{"range":'"$(range 0)"',"data":{{"refs":'$REFS'}}}}'
+            DEBUG='{{"range":'"$(range 3)"',"command":{{"title":"Debug","command":"test.debug"}}}}'
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":['"$RUN"','"$REFS_LENS"','"$DEBUG"']}}'
+            ;;
+        "codeLens/resolve")
+            n=$(echo "$msg" | grep -o '"refs":[0-9]*' | cut -d':' -f2)
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"range":'"$(range 0)"',"command":{{"title":"'$n' references","command":"test.refs"}}}}}}'
+            ;;
+        "workspace/executeCommand")
+            cmd=$(echo "$msg" | grep -o '"command":"[^"]*"' | cut -d'"' -f4)
+            echo "ACTION: execute $cmd" >> "$LOG_FILE"
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":null}}'
+            if [ "$cmd" = "test.debug" ]; then
+                REFS=3
+                send_message '{{"jsonrpc":"2.0","id":"refresh-1","method":"workspace/codeLens/refresh"}}'
+            fi
+            ;;
+        "textDocument/diagnostic")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"kind":"full","items":[]}}}}'
+            ;;
+        "shutdown")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":null}}'
+            break
+            ;;
+    esac
+done
+"##
+    );
+
+    let script_path = project_root.join("fake_code_lens_lsp.sh");
+    std::fs::write(&script_path, &script)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "python".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, project_root)?;
+    harness.open_file(&main_file)?;
+
+    // The "Run" lens is ready at once; "2 references" only appears after
+    // the visible lens was resolved.
+    harness.wait_until(|h| h.screen_to_string().contains("Run | 2 references"))?;
+    harness.wait_until(|h| h.screen_to_string().contains("Debug"))?;
+
+    // Two lenses above the cursor line: the action offers a chooser, and
+    // Enter runs the first one.
+    harness.send_key(KeyCode::Char('l'), KeyModifiers::SHIFT | KeyModifiers::ALT)?;
+    harness.wait_until(|h| h.screen_to_string().contains("1. Run"))?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(&log_file).unwrap_or_default();
+        log.contains("ACTION: execute test.run")
+    })?;
+
+    // Clicking a lens title runs it; this one also triggers a refresh.
+    let (col, row) = find_on_screen(&harness, "Debug").expect("Debug lens on screen");
+    harness.mouse_click(col + 1, row)?;
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(&log_file).unwrap_or_default();
+        log.contains("ACTION: execute test.debug")
+    })?;
+    harness.wait_until(|h| h.screen_to_string().contains("Run | 3 references"))?;
+
+    Ok(())
+}
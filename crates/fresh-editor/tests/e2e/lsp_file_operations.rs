@@ -0,0 +1,276 @@
+//! E2E tests for language-server file operations from the file explorer.
+//!
+//! A fake server registers for `willRenameFiles` / `didRenameFiles` on
+//! `**/*.ts` and answers any rename with an edit that points the import in
+//! `main.ts` at `./b` — the shape of a real import-path update.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn fake_server_script(log_path: &str, main_uri: &str) -> String {
+    format!(
+        r##"#!/bin/bash
+
+LOG_FILE="{log_path}"
+
+> "$LOG_FILE"
+
+read_message() {{
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}}
+
+send_message() {{
+    local message="$1"
+    local length=${{#message}}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}}
+
+FILTERS='{{"filters":[{{"scheme":"file","pattern":{{"glob":"**/*.ts"}}}}]}}'
+
+while true; do
+    msg=$(read_message)
+
+    if [ -z "$msg" ]; then
+        break
+    fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    echo "RECV: method=$method id=$msg_id" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"capabilities":{{"textDocumentSync":1,"workspace":{{"fileOperations":{{"willRename":'"$FILTERS"',"didRename":'"$FILTERS"'}}}}}}}}}}'
+            ;;
+        "workspace/willRenameFiles")
+            EDIT='{{"range":{{"start":{{"line":0,"character":19}},"end":{{"line":0,"character":22}}}},"newText":"./b"}}'
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"changes":{{"{main_uri}":['"$EDIT"']}}}}}}'
+            ;;
+        "textDocument/diagnostic")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"kind":"full","items":[]}}}}'
+            ;;
+        "shutdown")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":null}}'
+            break
+            ;;
+    esac
+done
+"##
+    )
+}
+
+/// A project with `a.ts` and a `main.ts` importing it, `main.ts` open and
+/// the fake server up. The server's files live outside the project so the
+/// explorer lists just the two sources.
+fn open_project() -> anyhow::Result<(
+    EditorTestHarness,
+    tempfile::TempDir,
+    std::path::PathBuf,
+    std::path::PathBuf,
+)> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let root = temp_dir.path().canonicalize()?;
+    let project_root = root.join("project");
+    std::fs::create_dir(&project_root)?;
+
+    std::fs::write(project_root.join("a.ts"), "export const a = 1;\n")?;
+    let main_file = project_root.join("main.ts");
+    std::fs::write(&main_file, "import { a } from './a';\n")?;
+
+    let log_file = root.join("lsp_log.txt");
+    let script_path = root.join("fake_ts_lsp.sh");
+    let main_uri = format!("file://{}", main_file.display());
+    std::fs::write(
+        &script_path,
+        fake_server_script(log_file.to_str().unwrap(), &main_uri),
+    )?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "typescript".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, project_root.clone())?;
+    harness.open_file(&main_file)?;
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(&log_file).unwrap_or_default();
+        log.contains("method=initialized")
+    })?;
+    Ok((harness, temp_dir, project_root, log_file))
+}
+
+/// The text of `main.ts`, switching to its tab (selecting `a.ts` in the
+/// explorer opens a preview tab in front of it).
+fn main_ts_content(harness: &mut EditorTestHarness, project_root: &std::path::Path) -> String {
+    harness
+        .editor_mut()
+        .open_file(&project_root.join("main.ts"))
+        .unwrap();
+    harness.get_buffer_content().unwrap()
+}
+
+/// Rename `a.ts` to `b.ts` through the explorer's rename prompt, and wait
+/// for the review popup.
+fn rename_a_to_b(harness: &mut EditorTestHarness) -> anyhow::Result<()> {
+    harness.editor_mut().focus_file_explorer();
+    harness.wait_for_file_explorer()?;
+    harness.wait_for_file_explorer_item("a.ts")?;
+    // The explorer opens on the active `main.ts`; `a.ts` sits just above.
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE)?;
+
+    harness.editor_mut().file_explorer_rename();
+    harness.send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)?;
+    harness.type_text("b.ts")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.screen_to_string().contains("Apply changes and continue"))?;
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_explorer_rename_applies_server_edits_then_renames() -> anyhow::Result<()> {
+    let (mut harness, _dir, project_root, log_file) = open_project()?;
+    rename_a_to_b(&mut harness)?;
+
+    // Nothing moves until the edits have been reviewed.
+    assert!(project_root.join("a.ts").exists());
+    let screen = harness.screen_to_string();
+    assert!(screen.contains("main.ts"), "screen:\n{screen}");
+    assert!(
+        screen.contains("import { a } from './b';"),
+        "screen:\n{screen}"
+    );
+
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|_| project_root.join("b.ts").exists())?;
+    assert!(!project_root.join("a.ts").exists());
+    assert_eq!(
+        main_ts_content(&mut harness, &project_root),
+        "import { a } from './b';\n"
+    );
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(&log_file).unwrap_or_default();
+        log.contains("method=workspace/didRenameFiles")
+    })?;
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(log.contains("method=workspace/willRenameFiles"));
+
+    // The import update is one undo step in `main.ts`.
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE)?;
+    assert!(matches!(
+        harness.editor().get_key_context(),
+        fresh::input::keybindings::KeyContext::Normal
+    ));
+    main_ts_content(&mut harness, &project_root);
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)?;
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "import { a } from './a';\n"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_explorer_rename_cancelled_from_review_touches_nothing() -> anyhow::Result<()> {
+    let (mut harness, _dir, project_root, log_file) = open_project()?;
+    rename_a_to_b(&mut harness)?;
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE)?;
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("File operation cancelled")
+    );
+    assert!(project_root.join("a.ts").exists());
+    assert!(!project_root.join("b.ts").exists());
+    assert_eq!(
+        main_ts_content(&mut harness, &project_root),
+        "import { a } from './a';\n"
+    );
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(!log.contains("method=workspace/didRenameFiles"));
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_explorer_rename_can_skip_server_edits() -> anyhow::Result<()> {
+    let (mut harness, _dir, project_root, log_file) = open_project()?;
+    rename_a_to_b(&mut harness)?;
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|_| project_root.join("b.ts").exists())?;
+    assert_eq!(
+        main_ts_content(&mut harness, &project_root),
+        "import { a } from './a';\n"
+    );
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(&log_file).unwrap_or_default();
+        log.contains("method=workspace/didRenameFiles")
+    })?;
+    Ok(())
+}
+
+/// A review closed without an answer (dismissed along with other menus)
+/// cancels the operation, so the explorer isn't left busy.
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_explorer_rename_review_closed_another_way_is_cancelled() -> anyhow::Result<()> {
+    let (mut harness, _dir, project_root, _log_file) = open_project()?;
+    rename_a_to_b(&mut harness)?;
+
+    harness.editor_mut().dismiss_menu_popups_for_prompt();
+    harness.wait_until(|h| {
+        h.editor().get_status_message().map(String::as_str) == Some("File operation cancelled")
+    })?;
+    assert!(project_root.join("a.ts").exists());
+
+    // `a.ts` is still selected; the same rename goes through this time.
+    harness.editor_mut().file_explorer_rename();
+    harness.send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)?;
+    harness.type_text("b.ts")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.screen_to_string().contains("Apply changes and continue"))?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|_| project_root.join("b.ts").exists())?;
+    Ok(())
+}
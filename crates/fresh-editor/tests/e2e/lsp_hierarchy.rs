@@ -0,0 +1,186 @@
+//! E2E test for LSP call hierarchies ("Show Call Hierarchy").
+//!
+//! A fake LSP server (bash script) advertises `callHierarchyProvider`,
+//! resolves `textDocument/prepareCallHierarchy` to `helper` and answers
+//! `callHierarchy/incomingCalls` with its single caller, `main`. The test
+//! drives `Shift+Alt+H` with the real `lsp_hierarchy` plugin loaded and
+//! checks the whole loop: prepare → hook → panel → lazy expansion → jump.
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn status_contains(harness: &EditorTestHarness, needle: &str) -> bool {
+    harness
+        .editor()
+        .get_status_message()
+        .is_some_and(|msg| msg.contains(needle))
+}
+
+/// `Shift+Alt+H` on a function opens the hierarchy panel with its callers
+/// already expanded, and Enter on a caller jumps to it.
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_call_hierarchy_panel_expands_callers_and_jumps() -> anyhow::Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().canonicalize()?;
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir)?;
+    copy_plugin(&plugins_dir, "lsp_hierarchy");
+    copy_plugin_lib(&plugins_dir);
+
+    let main_file = project_root.join("main.py");
+    std::fs::write(
+        &main_file,
+        "def helper():\n    pass\n\ndef main():\n    helper()\n",
+    )?;
+    let main_uri = format!("file://{}", main_file.to_str().unwrap());
+
+    let log_file = project_root.join("lsp_log.txt");
+    let log_path = log_file.to_str().unwrap();
+
+    let script = format!(
+        r##"#!/bin/bash
+
+MAIN_URI="{main_uri}"
+LOG_FILE="{log_path}"
+
+> "$LOG_FILE"
+
+read_message() {{
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}}
+
+send_message() {{
+    local message="$1"
+    local length=${{#message}}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}}
+
+item() {{
+    # name, line, start character, end character
+    echo '{{"name":"'$1'","kind":12,"uri":"'"$MAIN_URI"'","range":{{"start":{{"line":'$2',"character":0}},"end":{{"line":'$2',"character":'$4'}}}},"selectionRange":{{"start":{{"line":'$2',"character":'$3'}},"end":{{"line":'$2',"character":'$4'}}}},"data":{{"id":"'$1'"}}}}'
+}}
+
+while true; do
+    msg=$(read_message)
+
+    if [ -z "$msg" ]; then
+        break
+    fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    echo "RECV: method=$method id=$msg_id" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"capabilities":{{"callHierarchyProvider":true,"textDocumentSync":1}}}}}}'
+            ;;
+        "initialized")
+            echo "ACTION: initialized" >> "$LOG_FILE"
+            ;;
+        "textDocument/prepareCallHierarchy")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":['"$(item helper 0 4 10)"']}}'
+            ;;
+        "callHierarchy/incomingCalls")
+            # The opaque `data` must come back untouched.
+            echo "$msg" | grep -q '"data":{{"id":"helper"}}' && echo "ACTION: item round-tripped" >> "$LOG_FILE"
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":[{{"from":'"$(item main 3 4 8)"',"fromRanges":[]}}]}}'
+            ;;
+        "textDocument/diagnostic")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"kind":"full","items":[]}}}}'
+            ;;
+        "textDocument/inlayHint")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":[]}}'
+            ;;
+        "textDocument/semanticTokens/full"|"textDocument/semanticTokens/full/delta"|"textDocument/semanticTokens/range")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"data":[]}}}}'
+            ;;
+        "shutdown")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":null}}'
+            break
+            ;;
+    esac
+done
+"##
+    );
+
+    let script_path = project_root.join("fake_hierarchy_lsp.sh");
+    std::fs::write(&script_path, &script)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "python".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, project_root)?;
+    harness.open_file(&main_file)?;
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(&log_file).unwrap_or_default();
+        log.contains("ACTION: initialized")
+    })?;
+
+    // Cursor onto "helper" in "def helper():".
+    for _ in 0..5 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+    harness.send_key(KeyCode::Char('h'), KeyModifiers::SHIFT | KeyModifiers::ALT)?;
+
+    harness.wait_until(|h| status_contains(h, "Call hierarchy for 'helper'"))?;
+    // The plugin expands the root on open, so the caller shows up without
+    // any further input — proof the item went back to the server.
+    harness.wait_until(|h| {
+        let screen = h.screen_to_string();
+        screen.contains("Callers of 'helper'") && screen.contains("fn main  main.py:4")
+    })?;
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(log.contains("ACTION: item round-tripped"), "log:\n{}", log);
+
+    // Down to the caller row, Enter jumps to `main` in the source split.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    // "def helper():\n    pass\n\n" is 24 bytes; "def " puts `main` at 28.
+    harness.wait_until(|h| h.editor().active_cursors().primary().position == 28)?;
+
+    Ok(())
+}
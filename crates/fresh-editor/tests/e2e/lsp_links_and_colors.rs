@@ -0,0 +1,320 @@
+//! E2E tests for LSP document links and document colours.
+//!
+//! A fake server underlines the `other.css` of an `@import` as a link that
+//! only gets its target (`other.css#L2`) from `documentLink/resolve`, and
+//! reports the `#ff0000` on the next line as a colour. Asked how to write
+//! red it offers `rgb(255, 0, 0)` and `#ff0000`; asked about green it
+//! offers `#00ff00`.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+const MAIN_CSS: &str = "@import \"other.css\";\na { color: #ff0000; }\n";
+
+fn fake_server_script(log_path: &str, link_target: &str) -> String {
+    format!(
+        r##"#!/bin/bash
+
+LOG_FILE="{log_path}"
+
+> "$LOG_FILE"
+
+read_message() {{
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}}
+
+send_message() {{
+    local message="$1"
+    local length=${{#message}}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}}
+
+range() {{
+    echo '{{"start":{{"line":'$1',"character":'$2'}},"end":{{"line":'$1',"character":'$3'}}}}'
+}}
+
+while true; do
+    msg=$(read_message)
+
+    if [ -z "$msg" ]; then
+        break
+    fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    echo "RECV: method=$method id=$msg_id" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"capabilities":{{"documentLinkProvider":{{"resolveProvider":true}},"colorProvider":true,"textDocumentSync":1}}}}}}'
+            ;;
+        "textDocument/documentLink")
+            LINK='{{"range":'"$(range 0 9 18)"',"tooltip":"Open other.css"}}'
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":['"$LINK"']}}'
+            ;;
+        "documentLink/resolve")
+            LINK='{{"range":'"$(range 0 9 18)"',"target":"{link_target}"}}'
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":'"$LINK"'}}'
+            ;;
+        "textDocument/documentColor")
+            COLOR='{{"range":'"$(range 1 11 18)"',"color":{{"red":1,"green":0,"blue":0,"alpha":1}}}}'
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":['"$COLOR"']}}'
+            ;;
+        "textDocument/colorPresentation")
+            if echo "$msg" | grep -q '"green":1'; then
+                RESULT='[{{"label":"#00ff00"}}]'
+            else
+                RESULT='[{{"label":"rgb(255, 0, 0)"}},{{"label":"#ff0000"}}]'
+            fi
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":'"$RESULT"'}}'
+            ;;
+        "textDocument/diagnostic")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"kind":"full","items":[]}}}}'
+            ;;
+        "shutdown")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":null}}'
+            break
+            ;;
+    esac
+done
+"##
+    )
+}
+
+struct Setup {
+    harness: EditorTestHarness,
+    _dir: tempfile::TempDir,
+    log_file: std::path::PathBuf,
+    main_file: std::path::PathBuf,
+    other_file: std::path::PathBuf,
+}
+
+/// Open `main.css` (served by the fake server) next to `other.css`, and
+/// wait for its links and colours to arrive.
+fn open_css() -> anyhow::Result<Setup> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().canonicalize()?;
+
+    let main_file = project_root.join("main.css");
+    std::fs::write(&main_file, MAIN_CSS)?;
+    let other_file = project_root.join("other.css");
+    std::fs::write(&other_file, "b {}\ni {}\n")?;
+
+    let log_file = project_root.join("lsp_log.txt");
+    let script_path = project_root.join("fake_css_lsp.sh");
+    let link_target = format!("file://{}#L2", other_file.display());
+    std::fs::write(
+        &script_path,
+        fake_server_script(log_file.to_str().unwrap(), &link_target),
+    )?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "css".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, project_root)?;
+    harness.open_file(&main_file)?;
+    // The swatch shows once the colours are in; the links were asked for
+    // in the same frame.
+    harness.wait_for_screen_contains("■ #ff0000")?;
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(&log_file).unwrap_or_default();
+        log.contains("method=textDocument/documentLink")
+    })?;
+    Ok(Setup {
+        harness,
+        _dir: temp_dir,
+        log_file,
+        main_file,
+        other_file,
+    })
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) -> anyhow::Result<()> {
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.type_text(name)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.render()?;
+    Ok(())
+}
+
+/// Screen position of the first occurrence of `needle`.
+fn find_on_screen(harness: &EditorTestHarness, needle: &str) -> Option<(u16, u16)> {
+    harness
+        .screen_to_string()
+        .lines()
+        .enumerate()
+        .find_map(|(row, line)| {
+            let byte = line.find(needle)?;
+            Some((line[..byte].chars().count() as u16, row as u16))
+        })
+}
+
+fn is_showing(harness: &EditorTestHarness, path: &std::path::Path) -> bool {
+    harness
+        .editor()
+        .active_state()
+        .buffer
+        .file_path()
+        .is_some_and(|p| p == path)
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_follow_link_resolves_and_opens_the_target_line() -> anyhow::Result<()> {
+    let Setup {
+        mut harness,
+        _dir,
+        log_file,
+        other_file,
+        ..
+    } = open_css()?;
+
+    // Inside `other.css` on the first line.
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE)?;
+    for _ in 0..10 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+    run_command(&mut harness, "Follow Link")?;
+
+    harness.wait_until(|h| is_showing(h, &other_file))?;
+    // `#L2` puts the cursor at the start of the second line.
+    assert_eq!(harness.cursor_position(), 5);
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(log.contains("method=documentLink/resolve"));
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_ctrl_click_follows_a_link_and_plain_click_does_not() -> anyhow::Result<()> {
+    let Setup {
+        mut harness,
+        _dir,
+        main_file,
+        other_file,
+        ..
+    } = open_css()?;
+
+    let (col, row) = find_on_screen(&harness, "other.css").expect("link on screen");
+
+    // A plain click only moves the cursor. It lands on another cell than
+    // the Ctrl+click below, which would otherwise count as a double click.
+    harness.mouse_click(col + 5, row)?;
+    assert!(is_showing(&harness, &main_file));
+
+    for kind in [
+        MouseEventKind::Down(MouseButton::Left),
+        MouseEventKind::Up(MouseButton::Left),
+    ] {
+        harness.send_mouse(MouseEvent {
+            kind,
+            column: col + 2,
+            row,
+            modifiers: KeyModifiers::CONTROL,
+        })?;
+    }
+    harness.wait_until(|h| is_showing(h, &other_file))?;
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_pick_color_rewrites_the_literal() -> anyhow::Result<()> {
+    let Setup {
+        mut harness,
+        _dir,
+        log_file,
+        ..
+    } = open_css()?;
+
+    // Inside `#ff0000` on the second line.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE)?;
+    for _ in 0..13 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+
+    run_command(&mut harness, "Pick Color")?;
+    harness.wait_for_screen_contains("rgb(255, 0, 0)")?;
+    harness.assert_screen_contains("Other color…");
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "@import \"other.css\";\na { color: rgb(255, 0, 0); }\n"
+    );
+
+    // One undo restores the literal; wait for the colours to be re-pulled
+    // for the restored text before picking again.
+    let pulls = |log_file: &std::path::Path| {
+        std::fs::read_to_string(log_file)
+            .unwrap_or_default()
+            .matches("method=textDocument/documentColor")
+            .count()
+    };
+    let before = pulls(&log_file);
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)?;
+    assert_eq!(harness.get_buffer_content().unwrap(), MAIN_CSS);
+    harness.wait_until(|_| pulls(&log_file) > before)?;
+    harness.wait_for_screen_contains("■ #ff0000")?;
+
+    // "Other color…" prompts for a hex value, pre-filled with the current
+    // colour, and offers that colour's presentations instead.
+    run_command(&mut harness, "Pick Color")?;
+    harness.wait_for_screen_contains("Other color…")?;
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("#ff0000")?;
+    for _ in 0..6 {
+        harness.send_key(KeyCode::Backspace, KeyModifiers::NONE)?;
+    }
+    harness.type_text("00ff00")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("Color #00ff00")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "@import \"other.css\";\na { color: #00ff00; }\n"
+    );
+    Ok(())
+}
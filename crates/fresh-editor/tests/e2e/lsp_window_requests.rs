@@ -0,0 +1,202 @@
+//! E2E tests for the server-to-client `window/showMessageRequest` and
+//! `window/showDocument` requests.
+//!
+//! The fake server chains three requests once the document is open, each
+//! sent when the answer to the previous one comes back:
+//! 1. showMessageRequest with two actions → the user's pick is the response
+//! 2. showDocument for another file with a selection → `success: true`
+//! 3. showDocument for an external URL → declined by the user, `success: false`
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn create_window_requests_lsp_script(dir: &std::path::Path) -> std::path::PathBuf {
+    let script = r##"#!/bin/bash
+
+LOG_FILE="$1"
+OTHER_URI="$2"
+> "$LOG_FILE"
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | head -1 | cut -d':' -f2)
+
+    if [ -z "$method" ]; then
+        # A response to one of our requests: log it, then send the next one.
+        echo "RESPONSE:$msg" >> "$LOG_FILE"
+        case "$msg_id" in
+            9001)
+                send_message '{"jsonrpc":"2.0","id":9002,"method":"window/showDocument","params":{"uri":"'"$OTHER_URI"'","selection":{"start":{"line":1,"character":4},"end":{"line":1,"character":9}}}}'
+                ;;
+            9002)
+                send_message '{"jsonrpc":"2.0","id":9003,"method":"window/showDocument","params":{"uri":"https://example.com/docs","external":true}}'
+                ;;
+        esac
+        continue
+    fi
+
+    echo "METHOD:$method" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"textDocumentSync":1}}}'
+            ;;
+        "textDocument/didOpen")
+            if [ -z "$ASKED" ]; then
+                ASKED=1
+                send_message '{"jsonrpc":"2.0","id":9001,"method":"window/showMessageRequest","params":{"type":3,"message":"Project is not indexed yet","actions":[{"title":"Retry"},{"title":"Index now","kind":"full"}]}}'
+            fi
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+        *)
+            if [ -n "$msg_id" ]; then
+                send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            fi
+            ;;
+    esac
+done
+"##;
+
+    let script_path = dir.join("fake_lsp_window_requests.sh");
+    std::fs::write(&script_path, script).expect("Failed to write fake LSP script");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path).unwrap().permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms).unwrap();
+    }
+
+    script_path
+}
+
+fn log_contains(log_file: &std::path::Path, needle: &str) -> bool {
+    std::fs::read_to_string(log_file)
+        .unwrap_or_default()
+        .contains(needle)
+}
+
+/// A harness whose `test.rs` is open under the fake server, which asks its
+/// first question right away. Returns the server's log file too.
+fn open_with_window_requests_server(
+    temp_dir: &std::path::Path,
+) -> anyhow::Result<(EditorTestHarness, std::path::PathBuf)> {
+    let log_file = temp_dir.join("lsp_window_requests_log.txt");
+    let test_file = temp_dir.join("test.rs");
+    std::fs::write(&test_file, "fn main() {}\n")?;
+    let other_file = temp_dir.join("other.rs");
+    std::fs::write(&other_file, "fn other() {\n    value + 1\n}\n")?;
+    let other_uri = format!("file://{}", other_file.canonicalize()?.display());
+    let script_path = create_window_requests_lsp_script(temp_dir);
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![log_file.to_string_lossy().to_string(), other_uri]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: Some("fake-ls".to_string()),
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        crate::common::harness::HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp_dir.to_path_buf()),
+    )?;
+    harness.open_file(&test_file)?;
+    Ok((harness, log_file))
+}
+
+/// The picked action goes back verbatim, the file is opened with its range
+/// selected, and a declined external open answers `success: false`.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_show_message_request_and_show_document() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, log_file) = open_with_window_requests_server(temp_dir.path())?;
+
+    // 1. The message and its actions are shown; pick the second one.
+    harness.wait_for_screen_contains("Project is not indexed yet")?;
+    harness.assert_screen_contains("fake-ls asks");
+    harness.assert_screen_contains("Retry");
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|_| log_contains(&log_file, r#""id":9001,"result":{"#))?;
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(
+        log.contains(r#""title":"Index now""#) && log.contains(r#""kind":"full""#),
+        "the chosen action should come back with its extra properties:\n{}",
+        log
+    );
+
+    // 2. The other file is opened with "value" selected.
+    harness.wait_until(|_| log_contains(&log_file, r#""id":9002,"result":{"success":true}"#))?;
+    assert_eq!(
+        harness.get_buffer_content().as_deref(),
+        Some("fn other() {\n    value + 1\n}\n")
+    );
+    assert_eq!(harness.get_selection_range(), Some(17..22));
+
+    // 3. Opening an external program asks first; Escape declines.
+    harness.wait_for_screen_contains("https://example.com/docs")?;
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE)?;
+    harness.wait_until(|_| log_contains(&log_file, r#""id":9003,"result":{"success":false}"#))?;
+    Ok(())
+}
+
+/// A question whose popup is closed without confirm or cancel — here along
+/// with the other menus when a prompt opens — is still answered, with null.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_show_message_request_closed_another_way_answers_null() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, log_file) = open_with_window_requests_server(temp_dir.path())?;
+
+    harness.wait_for_screen_contains("Project is not indexed yet")?;
+    harness.editor_mut().dismiss_menu_popups_for_prompt();
+    harness.wait_until(|_| log_contains(&log_file, r#""id":9001,"result":null"#))?;
+    harness.render()?;
+    harness.assert_screen_not_contains("Project is not indexed yet");
+    Ok(())
+}
@@ -1234,3 +1234,177 @@ fn test_merge_mouse_click_on_buttons() {
         .unwrap();
     harness.render().unwrap();
 }
+
+/// Commit `base`, change it to `ours` on the default branch and to `theirs`
+/// on a `feature` branch, then merge `feature` so git leaves `file_name`
+/// unmerged. Assumes git is already initialized in project_root.
+fn setup_git_merge(
+    project_root: &Path,
+    file_name: &str,
+    base: &str,
+    ours: &str,
+    theirs: &str,
+) -> std::path::PathBuf {
+    use std::process::Command;
+
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(project_root)
+            .output()
+            .unwrap()
+    };
+    git(&["config", "commit.gpgsign", "false"]);
+
+    let file = project_root.join(file_name);
+    fs::write(&file, base).unwrap();
+    git(&["add", file_name]);
+    git(&["commit", "-m", "base"]);
+    let default_branch = String::from_utf8(git(&["branch", "--show-current"]).stdout).unwrap();
+
+    git(&["checkout", "-b", "feature"]);
+    fs::write(&file, theirs).unwrap();
+    git(&["commit", "-am", "theirs"]);
+
+    git(&["checkout", default_branch.trim()]);
+    fs::write(&file, ours).unwrap();
+    git(&["commit", "-am", "ours"]);
+
+    assert!(
+        !git(&["merge", "feature"]).status.success(),
+        "git merge should have produced conflicts"
+    );
+    file
+}
+
+fn start_merge_resolution(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Merge: Start Resolution").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+}
+
+/// Changes to different lines merge on their own; only the overlapping
+/// change is left to pick, and picks can be undone and redone.
+#[test]
+fn test_three_way_merge_auto_resolves_and_undoes_picks() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    setup_merge_conflict_test(&project_root);
+
+    // Default (2-way) markers carry no base, so the plugin merges git's
+    // index stages itself: the alpha and omega edits resolve natively.
+    let file_path = setup_git_merge(
+        &project_root,
+        "merge.txt",
+        "alpha\none\nmiddle\ntwo\nomega\n",
+        "alpha ours\none\nmiddle ours\ntwo\nomega\n",
+        "alpha\none\nmiddle theirs\ntwo\nomega theirs\n",
+    );
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 40, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    start_merge_resolution(&mut harness);
+    harness
+        .wait_for_screen_contains("1 conflicts to resolve (2 auto")
+        .unwrap();
+    let screen = harness.screen_to_string();
+    for label in ["OURS", "RESULT", "THEIRS"] {
+        assert!(screen.contains(label), "missing {label} pane:\n{screen}");
+    }
+
+    harness
+        .send_key(KeyCode::Char('t'), KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("All 1 conflicts resolved")
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("Undo: conflict 1")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("Cannot save: 1 unresolved")
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('Z'), KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("Redo: conflict 1")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("Merge complete").unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "alpha ours\none\nmiddle theirs\ntwo\nomega theirs\n"
+    );
+}
+
+/// A conflict whose markers were damaged is re-derived from git's base,
+/// ours and theirs stages instead of being rejected.
+#[test]
+fn test_three_way_merge_rederives_mangled_markers() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    setup_merge_conflict_test(&project_root);
+
+    let file_path = setup_git_merge(
+        &project_root,
+        "mangled.txt",
+        "head\nvalue = 1\n",
+        "head\nvalue = 2\n",
+        "head\nvalue = 3\n",
+    );
+    // Lose the separator, as a careless hand edit would.
+    let content = fs::read_to_string(&file_path).unwrap();
+    let mangled: String = content
+        .lines()
+        .filter(|line| *line != "=======")
+        .map(|line| format!("{line}\n"))
+        .collect();
+    fs::write(&file_path, mangled).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 40, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    start_merge_resolution(&mut harness);
+    harness.wait_for_screen_contains("re-derived").unwrap();
+
+    harness
+        .send_key(KeyCode::Char('b'), KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("All 1 conflicts resolved")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("Merge complete").unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "head\nvalue = 2\nvalue = 3\n"
+    );
+}
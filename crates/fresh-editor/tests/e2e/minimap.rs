@@ -0,0 +1,236 @@
+//! E2E tests for the per-split minimap column.
+//!
+//! Everything here asserts on rendered cells: the braille overview between the
+//! text and the scrollbar, its marker lane, and the viewport the editor shows
+//! after the user clicks or drags in it.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use tempfile::TempDir;
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 24;
+
+/// Marker-lane glyph painted for diagnostics, git-gutter marks and search hits.
+const LANE_GLYPH: &str = "▌";
+
+fn minimap_config() -> Config {
+    let mut config = Config::default();
+    config.editor.show_minimap = true;
+    config
+}
+
+/// Screen column of the minimap's marker lane: the minimap sits immediately
+/// left of the one-column vertical scrollbar.
+fn lane_col(config: &Config) -> u16 {
+    WIDTH - 1 - config.editor.minimap_width
+}
+
+fn numbered_lines(count: usize) -> String {
+    (0..count)
+        .map(|i| format!("line {i:04}: lorem ipsum dolor sit amet"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_braille(cell: &str) -> bool {
+    cell.chars()
+        .next()
+        .is_some_and(|c| ('\u{2801}'..='\u{28FF}').contains(&c))
+}
+
+/// Content rows whose minimap cells hold at least one lit braille glyph.
+fn minimap_rows(harness: &EditorTestHarness, config: &Config) -> Vec<u16> {
+    let lane = lane_col(config);
+    let (first, last) = harness.content_area_rows();
+    (first..=last)
+        .map(|row| row as u16)
+        .filter(|row| {
+            (lane + 1..lane + config.editor.minimap_width)
+                .any(|col| harness.get_cell(col, *row).is_some_and(|c| is_braille(&c)))
+        })
+        .collect()
+}
+
+fn open_with(config: Config, name: &str, content: &str) -> (EditorTestHarness, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join(name);
+    std::fs::write(&file, content).unwrap();
+    let mut harness = EditorTestHarness::with_config(WIDTH, HEIGHT, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+    (harness, temp_dir)
+}
+
+/// With `editor.show_minimap` on, the column renders one braille row per four
+/// source lines.
+#[test]
+fn test_minimap_renders_overview_beside_scrollbar() {
+    let config = minimap_config();
+    let (harness, _dir) = open_with(config.clone(), "a.txt", &numbered_lines(20));
+
+    let rows = minimap_rows(&harness, &config);
+    assert_eq!(
+        rows.len(),
+        5,
+        "20 lines should fill five braille rows\n{}",
+        harness.screen_to_string()
+    );
+}
+
+/// Search matches show up in the marker lane on the row covering their line.
+#[test]
+fn test_minimap_marks_search_matches() {
+    let config = minimap_config();
+    let mut content = numbered_lines(60);
+    content = content.replace("line 0041: lorem", "line 0041: needle");
+    let (mut harness, _dir) = open_with(config.clone(), "a.txt", &content);
+
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("needle").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    // The whole file fits in the minimap, so line 41 sits on row 41 / 4.
+    let (first, _) = harness.content_area_rows();
+    let lane = lane_col(&config);
+    let marked: Vec<u16> = (first as u16..first as u16 + 15)
+        .filter(|row| harness.get_cell(lane, *row).as_deref() == Some(LANE_GLYPH))
+        .collect();
+    assert_eq!(
+        marked,
+        vec![first as u16 + 10],
+        "only the row holding the match should be marked\n{}",
+        harness.screen_to_string()
+    );
+}
+
+/// Clicking a minimap row centres the split on the lines it shows, and
+/// dragging maps the pointer across the whole file like a scrollbar.
+#[test]
+fn test_minimap_click_and_drag_scroll_the_split() {
+    let config = minimap_config();
+    let (mut harness, _dir) = open_with(config.clone(), "a.txt", &numbered_lines(400));
+    let (first, last) = harness.content_area_rows();
+    let (first, last) = (first as u16, last as u16);
+    let col = lane_col(&config) + 3;
+    assert_eq!(harness.top_byte(), 0);
+
+    // The minimap starts at line 0, so its row 10 covers lines 40..44.
+    harness.mouse_click(col, first + 10).unwrap();
+    assert!(harness.top_byte() > 0, "the click should scroll the split");
+    harness.assert_screen_contains("line 0040");
+    harness.assert_screen_not_contains("line 0000");
+
+    harness.mouse_drag(col, first, col, last).unwrap();
+    harness.assert_screen_contains("line 0399");
+
+    harness.mouse_drag(col, last, col, first).unwrap();
+    assert_eq!(
+        harness.top_byte(),
+        0,
+        "dragging back to the top should return to the start"
+    );
+}
+
+/// Large-file mode samples the file instead of walking every line, and still
+/// draws the overview with the viewport on the current-line background.
+#[test]
+fn test_minimap_samples_large_files() {
+    let mut config = minimap_config();
+    config.editor.large_file_threshold_bytes = 1024;
+    let (mut harness, _dir) = open_with(config.clone(), "big.txt", &numbered_lines(2000));
+    assert!(harness.editor().active_state().buffer.is_large_file());
+    harness.render().unwrap();
+
+    let rows = minimap_rows(&harness, &config);
+    assert!(
+        !rows.is_empty(),
+        "the sampled minimap should draw loaded regions\n{}",
+        harness.screen_to_string()
+    );
+
+    let (first, last) = harness.content_area_rows();
+    let lane = lane_col(&config);
+    let bg_at = |row: usize| {
+        harness
+            .get_cell_style(lane + 1, row as u16)
+            .and_then(|s| s.bg)
+    };
+    assert_ne!(
+        bg_at(first),
+        bg_at(last),
+        "the row covering the viewport should be highlighted"
+    );
+}
+
+/// "Toggle Minimap (Current Buffer)" pins one buffer without touching the
+/// others, and the pin survives a session restart.
+#[test]
+fn test_minimap_current_buffer_toggle_scopes_and_persists() {
+    let config = Config::default();
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let a = project_dir.join("a.txt");
+    let b = project_dir.join("b.txt");
+    std::fs::write(&a, numbered_lines(20)).unwrap();
+    std::fs::write(&b, numbered_lines(20)).unwrap();
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            WIDTH,
+            HEIGHT,
+            config.clone(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        harness.open_file(&a).unwrap();
+        harness.open_file(&b).unwrap();
+        harness.render().unwrap();
+        assert!(minimap_rows(&harness, &config).is_empty(), "off by default");
+
+        harness.editor_mut().toggle_minimap_current_buffer();
+        harness.render().unwrap();
+        assert!(
+            !minimap_rows(&harness, &config).is_empty(),
+            "the toggle should show the minimap in b.txt\n{}",
+            harness.screen_to_string()
+        );
+
+        harness.open_file(&a).unwrap();
+        harness.render().unwrap();
+        assert!(
+            minimap_rows(&harness, &config).is_empty(),
+            "the toggle must not leak into other buffers\n{}",
+            harness.screen_to_string()
+        );
+
+        harness.open_file(&b).unwrap();
+        harness.render().unwrap();
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            WIDTH,
+            HEIGHT,
+            config.clone(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        let restored = harness.editor_mut().try_restore_workspace().unwrap();
+        assert!(restored, "workspace should have been restored");
+        harness.render().unwrap();
+        assert!(
+            !minimap_rows(&harness, &config).is_empty(),
+            "the per-buffer minimap choice should survive a restart\n{}",
+            harness.screen_to_string()
+        );
+    }
+}
@@ -27,6 +27,7 @@ pub mod csi_u_session_input;
 pub mod ctrl_slash_legacy_terminal;
 pub mod cursor_style_rendering;
 pub mod dabbrev_completion;
+pub mod debugger;
 pub mod dispatch_precedence;
 pub mod dock_create_terminal_resize;
 #[cfg(feature = "plugins")]
@@ -137,6 +138,7 @@ pub mod line_number_bugs;
 #[cfg(feature = "plugins")]
 pub mod line_numbers_override;
 pub mod line_wrapping;
+pub mod linked_editing;
 pub mod list_clamp_no_spurious_select;
 pub mod live_grep;
 pub mod locale;
@@ -146,6 +148,7 @@ pub mod lsp_bulk_edit_undo_desync;
 pub mod lsp_code_action_diagnostic_context;
 pub mod lsp_code_action_modal;
 pub mod lsp_code_action_resolve_and_commands;
+pub mod lsp_code_lens;
 pub mod lsp_completion_duplicate_entries_1514;
 pub mod lsp_completion_duplicate_label_import_2952;
 pub mod lsp_completion_dynamic_registration;
@@ -157,15 +160,18 @@ pub mod lsp_crash_loop;
 pub mod lsp_cross_language_diagnostic_pull;
 pub mod lsp_diagnostic_flow;
 pub mod lsp_env;
+pub mod lsp_file_operations;
 pub mod lsp_global_disable;
 pub mod lsp_goto_definition_readonly;
 pub mod lsp_goto_implementation;
+pub mod lsp_hierarchy;
 #[cfg(feature = "plugins")]
 pub mod lsp_indicator_click_bugs;
 pub mod lsp_indicator_click_to_open;
 pub mod lsp_inlay_hints_capability;
 pub mod lsp_inlay_hints_refresh_on_edit;
 pub mod lsp_lifecycle_visibility;
+pub mod lsp_links_and_colors;
 pub mod lsp_missing_binary_and_dismiss;
 pub mod lsp_multi_semantic_tokens;
 pub mod lsp_multi_server_diagnostic_pull;
@@ -179,6 +185,7 @@ pub mod lsp_toggle_desync;
 pub mod lsp_unified_code_actions;
 pub mod lsp_unified_hover;
 pub mod lsp_unresponsive_capability_does_not_block;
+pub mod lsp_window_requests;
 pub mod macros;
 pub mod mark_mode_actions;
 pub mod markdown_compose;
@@ -205,6 +212,7 @@ pub mod menu_cursor_bleed;
 pub mod menu_render_golden;
 pub mod menu_tab_color_bleed;
 pub mod merge_conflict;
+pub mod minimap;
 pub mod mouse;
 pub mod movement;
 pub mod multi_file_opening;
@@ -290,6 +298,8 @@ pub mod shift_backspace;
 pub mod slow_filesystem;
 pub mod smart_editing;
 pub mod smart_home;
+pub mod smart_selection;
+pub mod spell_check;
 pub mod split_close_confirm;
 pub mod split_focus_tab_click;
 pub mod split_tabs;
@@ -301,6 +311,7 @@ pub mod status_bar_config;
 pub mod status_bar_message_click;
 pub mod stdin_input;
 pub mod sticky_column_units;
+pub mod sticky_scroll;
 pub mod sudo_save_prompt;
 #[cfg(unix)]
 pub mod symlinks;
@@ -312,6 +323,7 @@ pub mod tab_config;
 pub mod tab_drag;
 pub mod tab_new_button;
 pub mod tab_path_disambiguation;
+pub mod tasks;
 pub mod terminal;
 pub mod terminal_close;
 pub mod terminal_link;
@@ -320,6 +332,7 @@ pub mod terminal_resize;
 pub mod terminal_resume_mode_after_close;
 pub mod terminal_split_focus_live;
 pub mod test_scrollbar_keybinds_cursor;
+pub mod text_objects;
 pub mod theme;
 pub mod theme_screenshots;
 pub mod toggle_bars;
@@ -327,6 +340,7 @@ pub mod toggle_comment;
 pub mod trust_activation_cancellable;
 pub mod undo_bulk_edit_after_save;
 pub mod undo_redo_marker_roundtrip;
+pub mod undo_tree;
 pub mod unicode_cursor;
 pub mod unicode_prompt_bugs;
 pub mod universal_lsp;
@@ -58,6 +58,8 @@ fn test_format_on_save() {
             word_characters: None,
             indentation_guide: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
         },
     );
 
@@ -129,6 +131,8 @@ fn test_on_save_linter_style() {
             word_characters: None,
             indentation_guide: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
         },
     );
 
@@ -200,6 +204,8 @@ fn test_on_save_action_failure() {
             word_characters: None,
             indentation_guide: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
         },
     );
 
@@ -280,6 +286,8 @@ fn test_on_save_file_placeholder() {
             word_characters: None,
             indentation_guide: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
         },
     );
 
@@ -354,6 +362,8 @@ fn test_formatter_stdin_mode() {
             word_characters: None,
             indentation_guide: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
         },
     );
 
@@ -435,6 +445,8 @@ fn test_on_save_stops_on_failure() {
             word_characters: None,
             indentation_guide: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
         },
     );
 
@@ -537,6 +549,8 @@ fn test_formatter_not_found_shows_message() {
             word_characters: None,
             indentation_guide: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
         },
     );
 
@@ -852,6 +866,8 @@ fn test_format_on_save_keeps_cursor_anchored_to_its_text() {
             word_characters: None,
             indentation_guide: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
         },
     );
 
@@ -925,6 +941,8 @@ fn test_format_on_save_cursor_before_edit_stays_put() {
             word_characters: None,
             indentation_guide: None,
             indent: None,
+            linked_editing: true,
+            format_on_type: false,
         },
     );
 
@@ -0,0 +1,105 @@
+//! E2E tests for the Compare Folders plugin: the tree lists what differs
+//! between two directories (minus ignored files), and the copy keys move a
+//! hunk or a whole file across.
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::path::Path;
+
+fn write(path: &Path, text: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, text).unwrap();
+}
+
+fn answer_prompt(harness: &mut EditorTestHarness, label: &str, text: &str) {
+    harness.wait_for_screen_contains(label).unwrap();
+    harness.type_text(text).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+}
+
+#[test]
+fn test_compare_folders_lists_changes_and_copies_across() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().canonicalize().unwrap();
+    let (left, right) = (project_root.join("left"), project_root.join("right"));
+    for root in [&left, &right] {
+        write(&root.join(".gitignore"), "*.log\n");
+        write(&root.join("same.txt"), "same\n");
+    }
+    write(&left.join("debug.log"), "left log\n");
+    write(&right.join("debug.log"), "right log\n");
+    write(&left.join("changed.txt"), "one\ntwo\n");
+    write(&right.join("changed.txt"), "one\n2\n");
+    write(&left.join("hunks.txt"), "a\nb\nc\nd\n");
+    write(&right.join("hunks.txt"), "a\nB\nc\nD\n");
+    write(&left.join("only_left.txt"), "gone\n");
+    write(&right.join("src/new.rs"), "fn main() {}\n");
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "dir_diff");
+    copy_plugin_lib(&plugins_dir);
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 30, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    // Both prompts are prefilled with a folder, so a bare name completes them.
+    harness.run_palette_command("Compare Folders").unwrap();
+    answer_prompt(&mut harness, "Left folder:", "left");
+    answer_prompt(&mut harness, "Right folder:", "right");
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("4 differ, 2 identical")
+                && screen.contains("~ changed.txt  +1 -1")
+                && screen.contains("~ hunks.txt  +2 -2")
+                && screen.contains("- only_left.txt  +0 -1")
+                && screen.contains("▾ src/")
+                && screen.contains("+ new.rs  +1 -0")
+        })
+        .unwrap();
+    assert!(!harness.screen_to_string().contains("debug.log"));
+
+    // Into hunks.txt's diff, past the first hunk's header row onto "b";
+    // `<` copies the right side of just that hunk to the left file.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Compare: hunks.txt"))
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char('<'), KeyModifiers::NONE)
+        .unwrap();
+    let hunks = left.join("hunks.txt");
+    harness
+        .wait_until(|_| std::fs::read_to_string(&hunks).unwrap() == "a\nB\nc\nd\n")
+        .unwrap();
+    harness
+        .wait_for_screen_contains("~ hunks.txt  +1 -1")
+        .unwrap();
+
+    // Back in the tree, `>` copies the whole of changed.txt to the right.
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Compare: changed.txt"))
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('>'), KeyModifiers::NONE)
+        .unwrap();
+    let changed = right.join("changed.txt");
+    harness
+        .wait_until(|_| std::fs::read_to_string(&changed).unwrap() == "one\ntwo\n")
+        .unwrap();
+    harness
+        .wait_for_screen_contains("3 differ, 3 identical")
+        .unwrap();
+}
@@ -34,6 +34,7 @@ pub mod devcontainer_usability_repros;
 pub mod diagnostics_panel_bugs;
 pub mod diagnostics_panel_jump;
 pub mod diff_cursor;
+pub mod dir_diff;
 pub mod env_manager;
 pub mod file_explorer_slots;
 pub mod find_file;
@@ -75,6 +76,7 @@ pub mod review_diff_ux_bugs;
 pub mod set_split_ratio_leaf;
 pub mod tab_actions;
 pub mod terminal_hooks;
+pub mod test_runner;
 pub mod theme_editor;
 pub mod trust_lockdown;
 pub mod unified_keybindings;
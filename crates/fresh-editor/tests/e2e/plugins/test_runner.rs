@@ -0,0 +1,95 @@
+//! E2E tests for the test runner plugin: running a file's tests shows
+//! pass/fail markers in the gutter and the failure message inline.
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+const LIB_RS: &str = "mod other;
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn adds() {
+        assert_eq!(1 + 1, 2);
+    }
+
+    #[test]
+    fn subtracts() {
+        assert_eq!(2 - 1, 0, \"subtraction is broken\");
+    }
+}
+";
+
+/// A failing test whose name is also in `LIB_RS`, in another module.
+const OTHER_RS: &str = "#[cfg(test)]
+mod tests {
+    #[test]
+    fn adds() {
+        panic!(\"the wrong adds ran\");
+    }
+}
+";
+
+fn run_palette_command(harness: &mut EditorTestHarness, command: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text(command).unwrap();
+    harness.wait_for_screen_contains(command).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+}
+
+/// A crate with one passing and one failing test, found by pattern (no
+/// language server) and run with `cargo test`. Only the file's own tests run,
+/// not same-named ones in other modules.
+#[test]
+fn test_run_file_tests_marks_results() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir_all(project_root.join("src")).unwrap();
+    std::fs::write(
+        project_root.join("Cargo.toml"),
+        "[package]\nname = \"sample\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+    )
+    .unwrap();
+    let lib = project_root.join("src/lib.rs");
+    std::fs::write(&lib, LIB_RS).unwrap();
+    std::fs::write(project_root.join("src/other.rs"), OTHER_RS).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir_all(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "test_runner");
+    copy_plugin_lib(&plugins_dir);
+
+    // Wide enough for the inline message to fit on the assertion's row.
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(160, 30, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&lib).unwrap();
+    harness.render().unwrap();
+
+    run_palette_command(&mut harness, "Test: Run File Tests");
+    // The panic message, shown inline; the custom part alone is also in
+    // the source.
+    harness.wait_for_screen_contains("left == right").unwrap();
+
+    let screen = harness.screen_to_string();
+    let row_of = |needle: &str| {
+        screen
+            .lines()
+            .find(|line| line.contains(needle))
+            .unwrap_or_else(|| panic!("{needle:?} not on screen:\n{screen}"))
+            .to_string()
+    };
+    assert!(row_of("fn adds").contains('✓'), "{screen}");
+    assert!(row_of("fn subtracts").contains('✗'), "{screen}");
+    // The message sits on the failing assertion, not the test's first line.
+    assert!(
+        row_of("assert_eq!(2 - 1").contains("left == right"),
+        "{screen}"
+    );
+}
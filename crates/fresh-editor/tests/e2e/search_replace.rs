@@ -2880,3 +2880,66 @@ fn test_search_replace_positions_track_bulk_edits() {
         .wait_until(|h| cursor_sits_on(h, "ZZNEEDLE two"))
         .unwrap();
 }
+
+/// Structural mode matches a code pattern against each file's syntax tree:
+/// `foo($A, $B)` → `bar($B, $A)` rewrites calls regardless of layout and
+/// comments, skips look-alikes (`food(...)`), previews each rewrite in the
+/// results, and leaves files without a grammar alone.
+#[test]
+fn test_search_replace_structural_rewrite() {
+    init_tracing_from_env();
+    let (_temp_dir, project_root) = setup_search_replace_project();
+    fs::write(
+        project_root.join("calls.js"),
+        "foo(a, b);\nfoo(\n  x, // first\n  y\n);\nfood(a, b);\n",
+    )
+    .unwrap();
+    fs::write(project_root.join("notes.txt"), "foo(a, b)\n").unwrap();
+    let start_file = project_root.join("start.txt");
+    fs::write(&start_file, "nothing here\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        Default::default(),
+        project_root.clone(),
+    )
+    .unwrap();
+    harness.open_file(&start_file).unwrap();
+    harness.render().unwrap();
+
+    open_search_replace_via_palette(&mut harness);
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::ALT)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("[v] Structural"))
+        .unwrap();
+    enter_search_and_replace(&mut harness, "foo($A, $B)", "bar($B, $A)");
+
+    // Every keystroke in the replace field re-runs the search to refresh
+    // the previews; wait for the last run to settle before Alt+Enter.
+    harness
+        .wait_until_stable(|h| {
+            let s = h.screen_to_string();
+            s.contains("→ bar(b, a)") && s.contains("→ bar(y, x)")
+        })
+        .unwrap();
+    let screen = harness.screen_to_string();
+    assert!(
+        !screen.contains("notes.txt") && !screen.contains("food"),
+        "only the two JavaScript calls to `foo` should match. Screen:\n{}",
+        screen
+    );
+
+    confirm_replace_all(&mut harness);
+
+    assert_eq!(
+        fs::read_to_string(project_root.join("calls.js")).unwrap(),
+        "bar(b, a);\nbar(y, x);\nfood(a, b);\n"
+    );
+    assert_eq!(
+        fs::read_to_string(project_root.join("notes.txt")).unwrap(),
+        "foo(a, b)\n"
+    );
+}
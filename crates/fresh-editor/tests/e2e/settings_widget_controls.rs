@@ -10,6 +10,7 @@
 //! - An entry dialog's text field (a language's Grammar) aligns its
 //!   value cell with the sibling toggles' chips.
 
+use crate::common::fixtures::single_language_config;
 use crate::common::harness::EditorTestHarness;
 use crossterm::event::{KeyCode, KeyModifiers};
 use fresh::config::Config;
@@ -211,12 +212,12 @@ fn narrow_terminal_keeps_toggle_chip_visible() {
 fn language_entry_text_field_aligns_with_toggles() {
     // A config with a single language keeps the map to one row so the
     // dialog is reached deterministically.
-    let mut config = Config::default();
-    let keep = if config.languages.contains_key("html") {
+    let defaults = Config::default();
+    let keep = if defaults.languages.contains_key("html") {
         "html"
     } else {
         // Fall back to whatever the first language is.
-        config
+        defaults
             .languages
             .keys()
             .next()
@@ -224,7 +225,7 @@ fn language_entry_text_field_aligns_with_toggles() {
             .expect("at least one language")
             .leak()
     };
-    config.languages.retain(|name, _| name == keep);
+    let config = single_language_config(keep);
 
     let mut harness = EditorTestHarness::with_config(120, 40, config).unwrap();
     harness.render().unwrap();
@@ -0,0 +1,233 @@
+//! E2E tests for Smart Expand / Shrink Selection.
+//!
+//! Without a language server the ranges come from the bracket/quote
+//! heuristic (plain text) — each cursor expands independently. With a
+//! server advertising `selectionRangeProvider`, its ranges win.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// The text selected by each cursor, in buffer order.
+fn selections(harness: &EditorTestHarness) -> Vec<String> {
+    let content = harness.get_buffer_content().unwrap();
+    let mut ranges: Vec<_> = harness
+        .editor()
+        .active_cursors()
+        .iter()
+        .map(|(_, c)| {
+            let anchor = c.anchor.unwrap_or(c.position);
+            c.position.min(anchor)..c.position.max(anchor)
+        })
+        .collect();
+    ranges.sort_by_key(|r| r.start);
+    ranges.into_iter().map(|r| content[r].to_string()).collect()
+}
+
+fn expand(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('='), KeyModifiers::ALT)
+        .unwrap();
+}
+
+fn shrink(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::ALT)
+        .unwrap();
+}
+
+#[test]
+fn test_smart_selection_expands_each_cursor_and_shrinks_back() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("f(a1, \"x y\")\ng(c2, \"z w\")").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness.editor_mut().add_cursor_below();
+    assert_eq!(harness.cursor_count(), 2);
+
+    expand(&mut harness);
+    assert_eq!(selections(&harness), vec!["a1", "c2"]);
+    expand(&mut harness);
+    assert_eq!(selections(&harness), vec!["a1, \"x y\"", "c2, \"z w\""]);
+    expand(&mut harness);
+    assert_eq!(selections(&harness), vec!["(a1, \"x y\")", "(c2, \"z w\")"]);
+
+    // Shrink retraces the expand steps.
+    shrink(&mut harness);
+    assert_eq!(selections(&harness), vec!["a1, \"x y\"", "c2, \"z w\""]);
+    shrink(&mut harness);
+    assert_eq!(selections(&harness), vec!["a1", "c2"]);
+    shrink(&mut harness);
+    assert_eq!(selections(&harness), vec!["", ""]);
+}
+
+#[test]
+fn test_smart_shrink_without_history_uses_inner_range() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("call(inner(x))").unwrap();
+    // Select backwards from just after `x`, leaving the cursor at 0.
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    for _ in 0..2 {
+        harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(selections(&harness), vec!["call(inner(x"]);
+
+    // Nothing was expanded, so shrink picks the largest range around the
+    // cursor that fits inside the selection.
+    shrink(&mut harness);
+    assert_eq!(selections(&harness), vec!["call"]);
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_smart_selection_uses_lsp_selection_ranges() -> anyhow::Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().canonicalize()?;
+
+    let main_file = project_root.join("main.py");
+    std::fs::write(&main_file, "x = compute(alpha, beta)\n")?;
+
+    let log_file = project_root.join("lsp_log.txt");
+    let log_path = log_file.to_str().unwrap();
+
+    // Whatever the position, answer with alpha -> (alpha, beta) ->
+    // compute(...) -> the whole line.
+    let script = format!(
+        r##"#!/bin/bash
+
+LOG_FILE="{log_path}"
+
+> "$LOG_FILE"
+
+read_message() {{
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}}
+
+send_message() {{
+    local message="$1"
+    local length=${{#message}}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}}
+
+range() {{
+    echo '{{"start":{{"line":0,"character":'$1'}},"end":{{"line":0,"character":'$2'}}}}'
+}}
+
+while true; do
+    msg=$(read_message)
+
+    if [ -z "$msg" ]; then
+        break
+    fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    echo "RECV: method=$method id=$msg_id" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"capabilities":{{"selectionRangeProvider":true,"textDocumentSync":1}}}}}}'
+            ;;
+        "textDocument/selectionRange")
+            LINE='{{"range":'"$(range 0 24)"'}}'
+            CALL='{{"range":'"$(range 4 24)"',"parent":'"$LINE"'}}'
+            ARGS='{{"range":'"$(range 11 24)"',"parent":'"$CALL"'}}'
+            ALPHA='{{"range":'"$(range 12 17)"',"parent":'"$ARGS"'}}'
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":['"$ALPHA"']}}'
+            ;;
+        "textDocument/diagnostic")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"kind":"full","items":[]}}}}'
+            ;;
+        "shutdown")
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":null}}'
+            break
+            ;;
+    esac
+done
+"##
+    );
+
+    let script_path = project_root.join("fake_selection_range_lsp.sh");
+    std::fs::write(&script_path, &script)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "python".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, project_root)?;
+    harness.open_file(&main_file)?;
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(&log_file).unwrap_or_default();
+        log.contains("method=initialized")
+    })?;
+
+    // Cursor inside `alpha`.
+    for _ in 0..14 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+
+    expand(&mut harness);
+    harness.wait_until(|h| selections(h) == vec!["alpha"])?;
+    // The heuristic would pick `alpha, beta` next; the server says the
+    // parentheses belong to the argument list.
+    expand(&mut harness);
+    harness.wait_until(|h| selections(h) == vec!["(alpha, beta)"])?;
+    expand(&mut harness);
+    harness.wait_until(|h| selections(h) == vec!["compute(alpha, beta)"])?;
+
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(log.contains("method=textDocument/selectionRange"));
+
+    shrink(&mut harness);
+    assert_eq!(selections(&harness), vec!["(alpha, beta)"]);
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! E2E tests for the built-in spell checker: misspellings are underlined
+//! only where prose lives, and the code-action popup fixes them or adds them
+//! to the workspace dictionary.
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use ratatui::style::Modifier;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const AFF: &str = "SET UTF-8\nTRY esianrtolcdugmphbyfvkwz\n";
+const DIC: &str = "6\nthe\nquick\nbrown\nfox\njumps\nmain\n";
+
+/// A harness with spell checking on, a tiny `en_US` dictionary in its config
+/// directory, and `name` containing `text` opened from the project.
+fn spell_checked_file(
+    name: &str,
+    text: &str,
+    full_grammar: bool,
+) -> (EditorTestHarness, TempDir, PathBuf) {
+    let temp = TempDir::new().unwrap();
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    let dir_context = DirectoryContext::for_testing(temp.path());
+    let dictionaries = dir_context.dictionaries_dir();
+    std::fs::create_dir_all(&dictionaries).unwrap();
+    std::fs::write(dictionaries.join("en_US.aff"), AFF).unwrap();
+    std::fs::write(dictionaries.join("en_US.dic"), DIC).unwrap();
+
+    let mut config = Config::default();
+    config.editor.spell_check = true;
+    let mut options = HarnessOptions::new()
+        .with_config(config)
+        .with_working_dir(project.clone())
+        .with_shared_dir_context(dir_context)
+        .without_empty_plugins_dir();
+    if full_grammar {
+        options = options.with_full_grammar_registry();
+    }
+    let mut harness = EditorTestHarness::create(80, 24, options).unwrap();
+
+    let file = project.join(name);
+    std::fs::write(&file, text).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+    (harness, temp, project)
+}
+
+fn is_underlined(harness: &EditorTestHarness, word: &str) -> bool {
+    let (x, y) = harness
+        .find_text_on_screen(word)
+        .unwrap_or_else(|| panic!("{word:?} should be on screen"));
+    harness
+        .get_cell_style(x, y)
+        .is_some_and(|style| style.add_modifier.contains(Modifier::UNDERLINED))
+}
+
+#[test]
+fn only_comments_are_checked_in_code() {
+    let (harness, _temp, _) =
+        spell_checked_file("main.rs", "fn mian() {}\n// the quikc brown fox\n", true);
+    assert!(is_underlined(&harness, "quikc"));
+    assert!(!is_underlined(&harness, "brown"));
+    assert!(!is_underlined(&harness, "mian"));
+}
+
+fn open_spelling_actions(harness: &mut EditorTestHarness) {
+    // Cursor onto "quikc" in "the quikc fox".
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 5)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('.'), KeyModifiers::ALT)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Change to 'quick'"))
+        .unwrap();
+}
+
+#[test]
+fn code_action_replaces_misspelled_word() {
+    let (mut harness, _temp, _) = spell_checked_file("notes.txt", "the quikc fox\n", false);
+    assert!(is_underlined(&harness, "quikc"));
+
+    open_spelling_actions(&mut harness);
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("the quick fox\n");
+    harness.render().unwrap();
+    assert!(!is_underlined(&harness, "quick"));
+}
+
+#[test]
+fn code_action_adds_word_to_workspace_dictionary() {
+    let (mut harness, _temp, project) = spell_checked_file("notes.txt", "the quikc fox\n", false);
+
+    open_spelling_actions(&mut harness);
+    let screen = harness.screen_to_string();
+    assert!(
+        screen.contains("Add 'quikc' to workspace dictionary"),
+        "{screen}"
+    );
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("the quikc fox\n");
+    assert!(!is_underlined(&harness, "quikc"));
+    let words = std::fs::read_to_string(workspace_dictionary(&project)).unwrap();
+    assert_eq!(words, "quikc\n");
+}
+
+fn workspace_dictionary(project: &Path) -> PathBuf {
+    project.join(".fresh").join("dictionary.txt")
+}
@@ -0,0 +1,133 @@
+//! E2E tests for sticky scroll headers and the breadcrumb bar.
+//!
+//! The outline comes from tree-sitter here (no language server runs), so
+//! these cover the local fallback end to end: the lines pinned at the top
+//! of the split, the breadcrumb row above it, and what clicking either does.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use tempfile::TempDir;
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 24;
+
+/// An `impl` holding a function far longer than the viewport.
+fn long_impl() -> String {
+    let mut text = String::from("struct Point;\n\nimpl Point {\n    fn long_function() {\n");
+    for i in 0..60 {
+        text.push_str(&format!("        let value_{i:02} = {i};\n"));
+    }
+    text.push_str("    }\n\n    fn after() {}\n}\n");
+    text
+}
+
+fn open_with(config: Config) -> (EditorTestHarness, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("point.rs");
+    std::fs::write(&file, long_impl()).unwrap();
+    let mut harness = EditorTestHarness::with_config(WIDTH, HEIGHT, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+    (harness, temp_dir)
+}
+
+/// Move the cursor down `lines` lines, scrolling the split with it.
+fn move_down(harness: &mut EditorTestHarness, lines: usize) {
+    for _ in 0..lines {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_sticky_scroll_pins_enclosing_scopes() {
+    let mut config = Config::default();
+    config.editor.show_sticky_scroll = true;
+    let (mut harness, _dir) = open_with(config);
+    let (first, _) = harness.content_area_rows();
+
+    // At the top of the file every header is on screen: nothing is pinned.
+    assert!(harness.get_screen_row(first).contains("struct Point;"));
+
+    move_down(&mut harness, 45);
+    let top = harness.get_screen_row(first);
+    let second = harness.get_screen_row(first + 1);
+    assert!(
+        top.contains("impl Point {") && top.contains('3'),
+        "the impl header should be pinned with its line number\n{}",
+        harness.screen_to_string()
+    );
+    assert!(
+        second.contains("fn long_function() {"),
+        "the fn header should be pinned below it\n{}",
+        harness.screen_to_string()
+    );
+    assert!(!harness
+        .get_screen_row(first + 2)
+        .contains("fn long_function"));
+}
+
+#[test]
+fn test_sticky_scroll_click_jumps_to_the_header() {
+    let mut config = Config::default();
+    config.editor.show_sticky_scroll = true;
+    let (mut harness, _dir) = open_with(config);
+    let (first, _) = harness.content_area_rows();
+
+    move_down(&mut harness, 45);
+    harness.mouse_click(20, first as u16 + 1).unwrap();
+    harness.render().unwrap();
+
+    let header = long_impl().find("long_function").unwrap();
+    assert_eq!(harness.cursor_position(), header);
+    harness.assert_screen_contains("let value_00 = 0;");
+}
+
+#[test]
+fn test_breadcrumbs_follow_the_cursor_and_pick_siblings() {
+    let mut config = Config::default();
+    config.editor.show_breadcrumbs = true;
+    let (mut harness, _dir) = open_with(config);
+    let (first, _) = harness.content_area_rows();
+
+    move_down(&mut harness, 10);
+    let crumbs = harness.get_screen_row(first);
+    assert!(
+        crumbs.contains("point.rs › Point › long_function"),
+        "breadcrumbs should show the path and enclosing symbols\n{}",
+        harness.screen_to_string()
+    );
+
+    // Clicking the function segment lists the functions of the impl.
+    let (col, row) = harness.find_text_on_screen("long_function").unwrap();
+    harness.mouse_click(col, row).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("after");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    let after = long_impl().find("after").unwrap();
+    assert_eq!(harness.cursor_position(), after);
+    assert!(harness
+        .get_screen_row(first)
+        .contains("point.rs › Point › after"));
+}
+
+#[test]
+fn test_breadcrumbs_take_a_row_from_the_text_area() {
+    let mut plain = Config::default();
+    plain.editor.show_breadcrumbs = false;
+    let (harness, _dir) = open_with(plain);
+    let (first, _) = harness.content_area_rows();
+    assert!(harness.get_screen_row(first).contains("struct Point;"));
+
+    let mut config = Config::default();
+    config.editor.show_breadcrumbs = true;
+    let (harness, _dir) = open_with(config);
+    assert!(!harness.get_screen_row(first).contains("struct Point;"));
+    assert!(harness.get_screen_row(first + 1).contains("struct Point;"));
+}
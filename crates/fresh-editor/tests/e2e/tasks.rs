@@ -0,0 +1,185 @@
+//! Task runner, driven end-to-end with `printf` standing in for a compiler.
+//!
+//! Tasks run through the shell, so the "compiler output" is whatever the
+//! command prints; the problem matchers turn it into diagnostics on the
+//! files it names.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn status_contains(harness: &EditorTestHarness, needle: &str) -> bool {
+    harness
+        .editor()
+        .get_status_message()
+        .is_some_and(|msg| msg.contains(needle))
+}
+
+/// Sources of the stored diagnostics for the file named `name`.
+fn diagnostic_sources(harness: &EditorTestHarness, name: &str) -> Vec<String> {
+    harness
+        .editor()
+        .get_stored_diagnostics()
+        .iter()
+        .filter(|(uri, _)| uri.ends_with(name))
+        .flat_map(|(_, diagnostics)| diagnostics.iter())
+        .filter_map(|d| d.source.clone())
+        .collect()
+}
+
+fn run_task(harness: &mut EditorTestHarness, name: &str) -> anyhow::Result<()> {
+    harness.send_key(
+        KeyCode::Char('B'),
+        KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+    )?;
+    harness.type_text(name)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    Ok(())
+}
+
+/// A gcc-style error and warning become diagnostics on the file, shown
+/// inline, and Alt+F8 / Shift+Alt+F8 step through them.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_task_problems_become_diagnostics_and_can_be_stepped_through() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("main.c");
+    std::fs::write(&source, "int main() {\n  return 0\n}\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.editor.diagnostics_inline_text = true;
+    config.tasks.insert(
+        "build".to_string(),
+        fresh::types::TaskConfig {
+            command: r"printf '%s\n' 'main.c:2:11: error: expected semicolon' 'main.c:3:1: warning: trailing brace'"
+                .to_string(),
+            problem_matchers: vec![fresh::types::ProblemMatcherConfig::Builtin(
+                "gcc".to_string(),
+            )],
+            ..Default::default()
+        },
+    );
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&source)?;
+
+    run_task(&mut harness, "build")?;
+    harness.wait_until(|h| status_contains(h, "Task build finished (2 problems)"))?;
+    assert_eq!(
+        diagnostic_sources(&harness, "main.c"),
+        vec!["task: build".to_string(), "task: build".to_string()]
+    );
+    harness.render()?;
+    let screen = harness.screen_to_string();
+    assert!(
+        screen.contains("expected semicolon"),
+        "problem should be shown inline:\n{}",
+        screen
+    );
+    assert!(
+        screen.contains("*Task: build*"),
+        "output should be in the dock:\n{}",
+        screen
+    );
+
+    harness.send_key(KeyCode::F(8), KeyModifiers::ALT)?;
+    assert!(status_contains(&harness, "Problem 1/2: expected semicolon"));
+    // Line 2, column 11: after "int main() {\n" (13 bytes) and 10 more.
+    assert_eq!(harness.editor().active_cursors().primary().position, 23);
+
+    harness.send_key(KeyCode::F(8), KeyModifiers::ALT)?;
+    assert!(status_contains(&harness, "Problem 2/2: trailing brace"));
+    harness.send_key(KeyCode::F(8), KeyModifiers::SHIFT | KeyModifiers::ALT)?;
+    assert!(status_contains(&harness, "Problem 1/2"));
+    Ok(())
+}
+
+/// A background task from `.fresh/tasks.json` publishes each time its
+/// custom matcher sees a cycle end, while the process keeps running.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_background_task_from_workspace_file_publishes_per_cycle() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("app.txt");
+    std::fs::write(&source, "one\ntwo\nthree\n")?;
+    std::fs::create_dir(temp_dir.path().join(".fresh"))?;
+    std::fs::write(
+        temp_dir.path().join(".fresh/tasks.json"),
+        r#"{
+            // Two watch cycles: the first finds a problem, the second is clean.
+            "watch": {
+                "command": "printf '%s\\n' 'start' 'app.txt line 2: bad word' 'done'; sleep 1; printf '%s\\n' 'start' 'done'; sleep 5",
+                "background": true,
+                "problem_matchers": [{
+                    "pattern": ["^(?P<file>\\S+) line (?P<line>\\d+): (?P<message>.*)$"],
+                    "begins": "^start$",
+                    "ends": "^done$"
+                }]
+            }
+        }"#,
+    )?;
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        fresh::config::Config::default(),
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&source)?;
+
+    run_task(&mut harness, "watch")?;
+    harness.wait_until(|h| diagnostic_sources(h, "app.txt") == vec!["task: watch".to_string()])?;
+    // The second cycle is clean, so its end clears the file again.
+    harness.wait_until(|h| diagnostic_sources(h, "app.txt").is_empty())?;
+    assert!(
+        !status_contains(&harness, "finished"),
+        "the watcher is still running"
+    );
+    Ok(())
+}
+
+/// A background task run in a terminal is matched as its output arrives,
+/// not only when it exits, so each watch cycle still publishes.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_background_task_in_terminal_publishes_per_cycle() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("app.txt");
+    std::fs::write(&source, "one\ntwo\nthree\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.tasks.insert(
+        "watch".to_string(),
+        fresh::types::TaskConfig {
+            command: "printf '%s\\n' 'start' 'app.txt line 2: bad word' 'done'; sleep 1; printf '%s\\n' 'start' 'done'; sleep 5"
+                .to_string(),
+            background: true,
+            presentation: fresh::types::TaskPresentation::Terminal,
+            problem_matchers: vec![serde_json::from_value(serde_json::json!({
+                "pattern": ["^(?P<file>\\S+) line (?P<line>\\d+): (?P<message>.*)$"],
+                "begins": "^start$",
+                "ends": "^done$"
+            }))?],
+            ..Default::default()
+        },
+    );
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&source)?;
+
+    run_task(&mut harness, "watch")?;
+    harness.wait_until(|h| diagnostic_sources(h, "app.txt") == vec!["task: watch".to_string()])?;
+    harness.wait_until(|h| diagnostic_sources(h, "app.txt").is_empty())?;
+    assert!(
+        !status_contains(&harness, "finished"),
+        "the watcher is still running"
+    );
+    Ok(())
+}
@@ -0,0 +1,115 @@
+//! E2E tests for tree-sitter text objects: selecting a function or
+//! parameter, jumping between functions, and swapping parameters.
+
+use crate::common::fixtures::TestFixture;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::input::keybindings::Action;
+
+const SOURCE: &str =
+    "function add(a, b) {\n  return a + b;\n}\n\nfunction twice(x) {\n  return add(x, x);\n}\n";
+
+fn open_source() -> anyhow::Result<(EditorTestHarness, TestFixture)> {
+    let fixture = TestFixture::new("objects.js", SOURCE)?;
+    let mut harness = EditorTestHarness::new(80, 24)?;
+    harness.open_file(&fixture.path)?;
+    harness.render()?;
+    Ok((harness, fixture))
+}
+
+fn selected(harness: &EditorTestHarness) -> String {
+    let content = harness.get_buffer_content().unwrap();
+    harness
+        .get_selection_range()
+        .map(|r| content[r].to_string())
+        .unwrap_or_default()
+}
+
+fn function_motion(harness: &mut EditorTestHarness, key: KeyCode) {
+    harness
+        .send_key(
+            key,
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_select_function_body_then_whole_function() -> anyhow::Result<()> {
+    let (mut harness, _fixture) = open_source()?;
+    let cursor = SOURCE.find("return a").unwrap();
+    harness.editor_mut().goto_byte_offset(cursor);
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::SelectTextObject("function.inner".to_string()));
+    assert_eq!(selected(&harness), "return a + b;");
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::SelectTextObject("function.outer".to_string()));
+    assert_eq!(
+        selected(&harness),
+        "function add(a, b) {\n  return a + b;\n}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_function_motions_move_between_definitions() -> anyhow::Result<()> {
+    let (mut harness, _fixture) = open_source()?;
+    let second = SOURCE.find("function twice").unwrap();
+
+    function_motion(&mut harness, KeyCode::Down);
+    assert_eq!(harness.cursor_position(), second);
+    function_motion(&mut harness, KeyCode::Up);
+    assert_eq!(harness.cursor_position(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_swap_parameter_is_one_undo_step() -> anyhow::Result<()> {
+    let (mut harness, _fixture) = open_source()?;
+    let a = SOURCE.find("a, b").unwrap();
+    harness.editor_mut().goto_byte_offset(a);
+
+    // Ctrl+Alt+Shift+Right: swap `a` with `b`; the cursor follows `a`.
+    harness.send_key(
+        KeyCode::Right,
+        KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+    )?;
+    let swapped = SOURCE.replacen("add(a, b)", "add(b, a)", 1);
+    assert_eq!(
+        harness.get_buffer_content().as_deref(),
+        Some(swapped.as_str())
+    );
+    assert_eq!(harness.cursor_position(), a + 3);
+
+    // `a` is now last: there is nothing after it to swap with.
+    harness.send_key(
+        KeyCode::Right,
+        KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+    )?;
+    assert_eq!(
+        harness.get_buffer_content().as_deref(),
+        Some(swapped.as_str())
+    );
+
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)?;
+    assert_eq!(harness.get_buffer_content().as_deref(), Some(SOURCE));
+    Ok(())
+}
+
+#[test]
+fn test_text_objects_unavailable_without_grammar() -> anyhow::Result<()> {
+    // Wide enough for the status message to fit next to the status bar items.
+    let mut harness = EditorTestHarness::new(200, 24)?;
+    harness.type_text("plain text")?;
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::SelectTextObject("function".to_string()));
+    harness.render()?;
+    harness.assert_screen_contains("Text objects are not available");
+    assert_eq!(selected(&harness), "");
+    Ok(())
+}
@@ -100,6 +100,44 @@ fn test_toggle_comment_shell_prefix() {
     );
 }
 
+/// Test that Toggle Comment inside an injected language uses that language's
+/// prefix, and the host's outside it
+#[test]
+fn test_toggle_comment_uses_injected_language() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("query.js");
+    let source = "const q = sql`\nSELECT 1\n`;\n";
+    std::fs::write(&file_path, source).unwrap();
+
+    let config = Config::default();
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().with_config(config)).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Inside the tagged template: SQL's `--`.
+    harness
+        .editor_mut()
+        .goto_byte_offset(source.find("SELECT").unwrap());
+    run_command(&mut harness, "Toggle Comment");
+    let content = harness.get_buffer_content().unwrap();
+    assert!(
+        content.contains("\n-- SELECT 1\n"),
+        "Embedded SQL should use -- for comments. Got: {:?}",
+        content
+    );
+
+    // On the JavaScript line: `//`.
+    harness.editor_mut().goto_byte_offset(0);
+    run_command(&mut harness, "Toggle Comment");
+    let content = harness.get_buffer_content().unwrap();
+    assert!(
+        content.starts_with("// const q"),
+        "JavaScript should use // for comments. Got: {:?}",
+        content
+    );
+}
+
 /// Test that selection is preserved after commenting multiple lines
 #[test]
 fn test_toggle_comment_preserves_selection() {
@@ -0,0 +1,69 @@
+//! E2E coverage for the undo tree: "Undo in Time" / "Redo in Time" reach
+//! edits that linear undo abandoned, and the `undo_history` plugin lists
+//! the branches, previews a state and jumps to it.
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Type "a", undo, type "b": "a" is on an abandoned branch that plain
+/// undo/redo can no longer reach.
+fn make_branch(harness: &mut EditorTestHarness) -> anyhow::Result<()> {
+    harness.type_text("a")?;
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)?;
+    harness.assert_buffer_content("");
+    harness.type_text("b")?;
+    harness.assert_buffer_content("b");
+    Ok(())
+}
+
+#[test]
+fn test_undo_in_time_reaches_abandoned_branch() -> anyhow::Result<()> {
+    let mut harness = EditorTestHarness::with_temp_project_no_plugins(80, 24)?;
+    make_branch(&mut harness)?;
+
+    harness.run_palette_command("Undo in Time")?;
+    harness.assert_buffer_content("a");
+
+    harness.run_palette_command("Redo in Time")?;
+    harness.assert_buffer_content("b");
+    Ok(())
+}
+
+/// The history panel shows both branches; moving onto the older one
+/// previews it as a diff, Enter switches the buffer to it.
+#[test]
+fn test_undo_history_panel_previews_and_jumps() -> anyhow::Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().canonicalize()?;
+    let plugins_dir = project_root.join("plugins");
+    std::fs::create_dir(&plugins_dir)?;
+    copy_plugin(&plugins_dir, "undo_history");
+    copy_plugin_lib(&plugins_dir);
+
+    let file = project_root.join("notes.txt");
+    std::fs::write(&file, "")?;
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        30,
+        fresh::config::Config::default(),
+        project_root,
+    )?;
+    harness.open_file(&file)?;
+    make_branch(&mut harness)?;
+
+    harness.run_palette_command("Undo History")?;
+    harness.wait_until(|h| {
+        let screen = h.screen_to_string();
+        screen.contains("Undo history: notes.txt")
+            && screen.contains("Insert \"a\"")
+            && screen.contains("Insert \"b\"")
+    })?;
+
+    // The list opens on the current state ("b"); Up selects "a".
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.screen_to_string().contains("State 1"))?;
+
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.get_buffer_content().as_deref() == Some("a"))?;
+    Ok(())
+}
@@ -2094,6 +2094,51 @@ fn test_vi_change_inner_quotes() {
         .unwrap();
 }
 
+/// Test 'daf' deletes the function around the cursor and 'cia' changes
+/// an argument, using the language's tree-sitter text objects
+#[test]
+fn test_vi_syntax_text_objects() {
+    let (mut harness, _temp_dir) = vi_mode_harness(80, 24);
+
+    let fixture = TestFixture::new(
+        "test.js",
+        "function f() {\n  return 1;\n}\ncall(one, two);\n",
+    )
+    .unwrap();
+    harness.open_file(&fixture.path).unwrap();
+    harness.render().unwrap();
+
+    enable_vi_mode(&mut harness);
+
+    // Move into the function body.
+    send_vi_key(&mut harness, 'j');
+    harness.wait_until(|h| h.cursor_position() > 0).unwrap();
+
+    send_vi_key(&mut harness, 'd');
+    send_vi_key(&mut harness, 'a');
+    harness
+        .wait_until(|h| h.editor().editor_mode() == Some("vi-text-object".to_string()))
+        .unwrap();
+    send_vi_key(&mut harness, 'f');
+    harness
+        .wait_for_buffer_content("\ncall(one, two);\n")
+        .unwrap();
+
+    // From the start of the line, 'cia' targets the next argument.
+    send_vi_key(&mut harness, 'j');
+    harness.wait_until(|h| h.cursor_position() == 1).unwrap();
+    send_vi_key(&mut harness, 'c');
+    send_vi_key(&mut harness, 'i');
+    send_vi_key(&mut harness, 'a');
+    harness
+        .wait_until(|h| h.editor().editor_mode() == Some("vi-insert".to_string()))
+        .unwrap();
+    harness.type_text("uno").unwrap();
+    harness
+        .wait_for_buffer_content("\ncall(uno, two);\n")
+        .unwrap();
+}
+
 // =============================================================================
 // Colon Command Tests
 // =============================================================================
@@ -0,0 +1,16 @@
+; Vendored from https://github.com/vrischmann/tree-sitter-templ
+; (queries/templ/injections.scm @ v2.2.0). MIT license.
+;
+; Go ships no injections query, so the upstream "; inherits: go" has nothing
+; to pull in here.
+((element_comment) @injection.content
+  (#set! injection.language "comment"))
+
+((script_block_text) @injection.content
+  (#set! injection.language "javascript"))
+
+((script_element_text) @injection.content
+  (#set! injection.language "javascript"))
+
+((style_element_text) @injection.content
+  (#set! injection.language "css"))
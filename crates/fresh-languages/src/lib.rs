@@ -1,5 +1,7 @@
 use std::path::Path;
 
+pub mod runtime;
+
 // Re-export tree-sitter crates for use by fresh-editor
 pub use tree_sitter;
 pub use tree_sitter_highlight;
@@ -200,8 +202,29 @@ impl Language {
             .copied()
     }
 
-    /// Get tree-sitter highlight configuration for this language
+    /// Get tree-sitter highlight configuration for this language.
+    ///
+    /// Uses the bundled grammar when there is one, else the grammar
+    /// registered at runtime (see [`runtime`]).
     pub fn highlight_config(&self) -> Result<HighlightConfiguration, String> {
+        self.bundled_highlight_config().or_else(|bundled_err| {
+            let Some(grammar) = runtime::grammar(*self) else {
+                return Err(bundled_err);
+            };
+            let mut config = HighlightConfiguration::new(
+                grammar.language.clone(),
+                self.id(),
+                &grammar.highlights,
+                grammar.injections.as_deref().unwrap_or(""),
+                grammar.locals.as_deref().unwrap_or(""),
+            )
+            .map_err(|e| format!("Failed to create {} highlight config: {e}", self.id()))?;
+            config.configure(DEFAULT_HIGHLIGHT_CAPTURES);
+            Ok(config)
+        })
+    }
+
+    fn bundled_highlight_config(&self) -> Result<HighlightConfiguration, String> {
         match self {
             Self::JavaScript => {
                 #[cfg(feature = "tree-sitter-javascript")]
@@ -210,7 +233,7 @@ impl Language {
                         tree_sitter_javascript::LANGUAGE.into(),
                         "javascript",
                         tree_sitter_javascript::HIGHLIGHT_QUERY,
-                        "",
+                        tree_sitter_javascript::INJECTIONS_QUERY,
                         "",
                     )
                     .map_err(|e| format!("Failed to create JavaScript highlight config: {e}"))?;
@@ -232,7 +255,7 @@ impl Language {
                         tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
                         "typescript",
                         &combined_highlights,
-                        "",
+                        tree_sitter_javascript::INJECTIONS_QUERY,
                         tree_sitter_typescript::LOCALS_QUERY,
                     )
                     .map_err(|e| format!("Failed to create TypeScript highlight config: {e}"))?;
@@ -316,7 +339,7 @@ impl Language {
                         tree_sitter_templ::LANGUAGE.into(),
                         "templ",
                         &combined_highlights,
-                        "",
+                        TEMPL_INJECTIONS_QUERY,
                         "",
                     )
                     .map_err(|e| format!("Failed to create Templ highlight config: {e}"))?;
@@ -332,10 +355,56 @@ impl Language {
         }
     }
 
+    /// The `injections.scm` query for this language: which nodes hold code in
+    /// another language, and which one. `None` when it has no grammar or the
+    /// grammar embeds nothing.
+    ///
+    /// TypeScript reuses JavaScript's query — the tagged-template and regex
+    /// nodes it matches are shared by both grammars.
+    pub fn injections_query(&self) -> Option<std::borrow::Cow<'static, str>> {
+        let bundled: Option<&'static str> = match self {
+            #[cfg(feature = "tree-sitter-javascript")]
+            Self::JavaScript => Some(tree_sitter_javascript::INJECTIONS_QUERY),
+            #[cfg(all(feature = "tree-sitter-typescript", feature = "tree-sitter-javascript"))]
+            Self::TypeScript => Some(tree_sitter_javascript::INJECTIONS_QUERY),
+            #[cfg(feature = "tree-sitter-templ")]
+            Self::Templ => Some(TEMPL_INJECTIONS_QUERY),
+            _ => None,
+        };
+        if let Some(query) = bundled {
+            return Some(query.into());
+        }
+        if self.bundled_ts_language().is_some() {
+            return None;
+        }
+        runtime::grammar(*self)
+            .and_then(|grammar| grammar.injections.clone())
+            .map(Into::into)
+    }
+
+    /// The capture names this language's highlight configuration is
+    /// configured with; `highlight_category` maps indices into this list.
+    ///
+    /// An injected language's configuration must be configured with the
+    /// *host's* list, since tree-sitter-highlight reports every highlight as
+    /// an index into a single list.
+    pub fn highlight_capture_names(&self) -> &'static [&'static str] {
+        match self {
+            Self::TypeScript if self.bundled_ts_language().is_some() => {
+                TYPESCRIPT_HIGHLIGHT_CAPTURES
+            }
+            _ => DEFAULT_HIGHLIGHT_CAPTURES,
+        }
+    }
+
     /// Map tree-sitter highlight index to a highlight category
     pub fn highlight_category(&self, index: usize) -> Option<HighlightCategory> {
         match self {
-            Self::TypeScript => HighlightCategory::from_typescript_index(index),
+            // A runtime TypeScript grammar is configured with the default
+            // captures, like every other runtime grammar.
+            Self::TypeScript if self.bundled_ts_language().is_some() => {
+                HighlightCategory::from_typescript_index(index)
+            }
             _ => HighlightCategory::from_default_index(index),
         }
     }
@@ -350,8 +419,14 @@ impl Language {
     /// syntect ships no grammar for them (JavaScript, TypeScript, JSON-with-
     /// comments, Templ — plus Go, which Templ extends) are bundled by default;
     /// every other arm returns `None` unless the opt-in `all-languages` feature
-    /// re-enables its grammar.
+    /// re-enables its grammar. A grammar registered at runtime (see
+    /// [`runtime`]) fills in for any language without a bundled one.
     pub fn ts_language(&self) -> Option<tree_sitter::Language> {
+        self.bundled_ts_language()
+            .or_else(|| runtime::grammar(*self).map(|g| g.language.clone()))
+    }
+
+    fn bundled_ts_language(&self) -> Option<tree_sitter::Language> {
         match self {
             Self::JavaScript => {
                 #[cfg(feature = "tree-sitter-javascript")]
@@ -618,10 +693,7 @@ impl std::fmt::Display for Language {
     }
 }
 
-// Used by every per-language `highlight_config` arm; each arm is gated by its
-// own grammar feature, so with zero grammars enabled this is (legitimately)
-// unused.
-#[allow(dead_code)]
+// Used by every per-language `highlight_config` arm and by runtime grammars.
 const DEFAULT_HIGHLIGHT_CAPTURES: &[&str] = &[
     "attribute",
     "comment",
@@ -652,8 +724,11 @@ const DEFAULT_HIGHLIGHT_CAPTURES: &[&str] = &[
 #[cfg(feature = "tree-sitter-templ")]
 const TEMPL_HIGHLIGHTS_QUERY: &str = include_str!("../queries/templ/highlights.scm");
 
-// Only referenced by the TypeScript arm; unused when that grammar is disabled.
-#[allow(dead_code)]
+/// Templ's injections (`<script>` as JavaScript, `<style>` as CSS), vendored
+/// from the same upstream crate for the same reason.
+#[cfg(feature = "tree-sitter-templ")]
+const TEMPL_INJECTIONS_QUERY: &str = include_str!("../queries/templ/injections.scm");
+
 const TYPESCRIPT_HIGHLIGHT_CAPTURES: &[&str] = &[
     "attribute",
     "comment",
@@ -755,6 +830,56 @@ mod tests {
             .expect("Templ highlight config should build");
     }
 
+    #[test]
+    #[cfg(feature = "tree-sitter-json")]
+    fn test_runtime_grammar_fills_in_for_unbundled_language() {
+        // Lua has no bundled grammar; stand the JSON parser in for a grammar
+        // loaded from disk. No other test registers Lua.
+        assert!(Language::Lua.ts_language().is_none());
+        runtime::register(
+            Language::Lua,
+            runtime::RuntimeGrammar {
+                language: tree_sitter_json::LANGUAGE.into(),
+                highlights: tree_sitter_json::HIGHLIGHTS_QUERY.to_string(),
+                indents: None,
+                locals: None,
+                injections: None,
+            },
+        )
+        .unwrap();
+
+        assert!(Language::Lua.ts_language().is_some());
+        assert!(Language::Lua.highlight_config().is_ok());
+        assert!(runtime::registered().contains(&Language::Lua));
+        // Bundled grammars are unaffected.
+        assert!(runtime::grammar(Language::Json).is_none());
+    }
+
+    #[test]
+    fn test_runtime_grammar_symbol_names() {
+        assert_eq!(
+            runtime::symbol_names(Language::Rust),
+            vec!["tree_sitter_rust"]
+        );
+        assert_eq!(
+            runtime::symbol_names(Language::CSharp),
+            vec!["tree_sitter_csharp", "tree_sitter_c_sharp"]
+        );
+        assert_eq!(
+            runtime::symbol_names(Language::Cpp),
+            vec!["tree_sitter_cpp"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "tree-sitter-templ")]
+    fn test_templ_injections_query_parses() {
+        let query = Language::Templ.injections_query().unwrap();
+        tree_sitter::Query::new(&Language::Templ.ts_language().unwrap(), &query)
+            .expect("Templ injections query should parse");
+        assert!(Language::Go.injections_query().is_none());
+    }
+
     /// Guard: `from_path` and `extensions()` must stay in sync — they used to
     /// be two hand-maintained tables with a "keep in sync" comment, which
     /// silently drifted when either was edited in isolation.
@@ -0,0 +1,115 @@
+//! Tree-sitter grammars registered at runtime.
+//!
+//! Only a handful of grammars are compiled into the binary (see Cargo.toml).
+//! The editor can load more from disk — a compiled parser plus its queries —
+//! and register them here; `Language::ts_language` and
+//! `Language::highlight_config` then fall back to this table for any language
+//! without a bundled grammar, so indentation, reference highlighting and the
+//! other tree-sitter features pick it up without a rebuild.
+//!
+//! Loading the parser itself (a shared library) is the caller's job; this
+//! module only keeps what was loaded. Registration is process-wide and
+//! last-writer-wins.
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::Language;
+
+/// A grammar loaded at runtime, with the queries shipped alongside it.
+#[derive(Clone)]
+pub struct RuntimeGrammar {
+    /// The parser.
+    pub language: tree_sitter::Language,
+    /// `highlights.scm`; empty when the grammar ships none.
+    pub highlights: String,
+    /// `indents.scm`, overriding the indent query bundled for the language.
+    pub indents: Option<String>,
+    /// `locals.scm`, overriding the locals query bundled for the language.
+    pub locals: Option<String>,
+    /// `injections.scm`: where other languages are embedded in this one.
+    pub injections: Option<String>,
+}
+
+impl std::fmt::Debug for RuntimeGrammar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuntimeGrammar")
+            .field("abi_version", &self.language.abi_version())
+            .field("highlights", &self.highlights.len())
+            .field("indents", &self.indents.is_some())
+            .field("locals", &self.locals.is_some())
+            .field("injections", &self.injections.is_some())
+            .finish()
+    }
+}
+
+fn registry() -> &'static RwLock<HashMap<Language, Arc<RuntimeGrammar>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<Language, Arc<RuntimeGrammar>>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Register (or replace) the runtime grammar for `language`.
+///
+/// Fails when the parser was generated for a tree-sitter ABI this build
+/// cannot read; the previous registration, if any, is kept.
+pub fn register(language: Language, grammar: RuntimeGrammar) -> Result<(), String> {
+    let abi = grammar.language.abi_version();
+    if !(tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION..=tree_sitter::LANGUAGE_VERSION)
+        .contains(&abi)
+    {
+        return Err(format!(
+            "{language} grammar uses tree-sitter ABI {abi}, expected {}..={}",
+            tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION,
+            tree_sitter::LANGUAGE_VERSION
+        ));
+    }
+    registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(language, Arc::new(grammar));
+    Ok(())
+}
+
+/// The runtime grammar registered for `language`, if any.
+pub fn grammar(language: Language) -> Option<Arc<RuntimeGrammar>> {
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&language)
+        .cloned()
+}
+
+/// Languages that currently have a runtime grammar.
+pub fn registered() -> Vec<Language> {
+    let mut languages: Vec<Language> = registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys()
+        .copied()
+        .collect();
+    languages.sort_by_key(|l| l.id());
+    languages
+}
+
+/// The exported symbols a parser library for `language` may use for its
+/// language function, most likely first (`tree_sitter_rust`,
+/// `tree_sitter_c_sharp`, ...).
+pub fn symbol_names(language: Language) -> Vec<String> {
+    let mut names = vec![format!("tree_sitter_{}", language.id())];
+    let alternative = match language {
+        Language::CSharp => "c_sharp".to_string(),
+        Language::Cpp => "cpp".to_string(),
+        Language::Jsonc => "json".to_string(),
+        _ => language
+            .display_name()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect(),
+    };
+    let name = format!("tree_sitter_{alternative}");
+    if !names.contains(&name) {
+        names.push(name);
+    }
+    names
+}
@@ -468,6 +468,7 @@ fn rust_to_typescript(ty: &Type, attrs: &[Attribute]) -> String {
 
                 // Types renamed by ts-rs — map Rust name to TypeScript name
                 "CompositeHunk" => "TsCompositeHunk".to_string(),
+                "CompositeSection" => "TsCompositeSection".to_string(),
                 "CreateCompositeBufferOptions" => "TsCreateCompositeBufferOptions".to_string(),
                 "Suggestion" => "PromptSuggestion".to_string(),
                 "LspMenuItem" => "TsLspMenuItem".to_string(),
@@ -1501,6 +1502,7 @@ mod tests {
         // Renamed types
         let renamed = vec![
             ("CompositeHunk", "TsCompositeHunk"),
+            ("CompositeSection", "TsCompositeSection"),
             (
                 "CreateCompositeBufferOptions",
                 "TsCreateCompositeBufferOptions",
@@ -88,10 +88,10 @@
 
 use anyhow::{anyhow, Result};
 use fresh_core::api::{
-    ActionSpec, BufferInfo, CompositeHunk, CreateCompositeBufferOptions, EditorStateSnapshot,
-    GrammarInfoSnapshot, JsCallbackId, LanguagePackConfig, LspServerPackConfig, OverlayOptions,
-    PluginCommand, PluginMarker, PluginResponse, ScrollbarMarker, SearchHandleRegistry,
-    SearchHandleState, SearchTakeResult, SplitWindowOptions,
+    ActionSpec, BufferInfo, CompositeHunk, CompositeSection, CreateCompositeBufferOptions,
+    EditorStateSnapshot, GrammarInfoSnapshot, JsCallbackId, LanguagePackConfig,
+    LspServerPackConfig, OverlayOptions, PluginCommand, PluginMarker, PluginResponse,
+    ScrollbarMarker, SearchHandleRegistry, SearchHandleState, SearchTakeResult, SplitWindowOptions,
 };
 use fresh_core::command::Command;
 use fresh_core::overlay::OverlayNamespace;
@@ -2481,6 +2481,27 @@ impl JsEditorApi {
             .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))
     }
 
+    /// Three-way line merge (native diff3; see `fresh_core::merge`):
+    /// applies the changes `ours` and `theirs` each made to `base`.
+    /// Changes to different lines, and identical changes on both sides,
+    /// merge cleanly; the rest become conflict regions, written into
+    /// `merged` with `<<<<<<<`/`|||||||`/`=======`/`>>>>>>>` markers.
+    /// `regions` tiles all four texts in order, so a caller can map any
+    /// line of the merge back to base, ours and theirs. Line numbering
+    /// follows `computeLineDiff`.
+    #[plugin_api(ts_return = "MergeResult")]
+    pub fn merge_three_way<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        base: String,
+        ours: String,
+        theirs: String,
+    ) -> rquickjs::Result<Value<'js>> {
+        let result = fresh_core::merge::merge_three_way(&base, &ours, &theirs);
+        rquickjs_serde::to_value(ctx, &result)
+            .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))
+    }
+
     // === File System ===
 
     /// Check if a file exists on the path's filesystem (a window's authority,
@@ -2529,6 +2550,30 @@ impl JsEditorApi {
             .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))
     }
 
+    /// Compare two directory trees file by file. Each side is walked on its
+    /// own path's filesystem, honouring `.gitignore`/`.ignore` files and
+    /// skipping `.git`. Returns the differing files sorted by path, with
+    /// per-file line stats, plus a count of identical files. Synchronous:
+    /// every file on both sides is read, so keep the roots reasonably small.
+    #[plugin_api(ts_return = "DirDiff")]
+    pub fn compare_directories<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        #[plugin_api(ts_type = "string | LocalPath | WindowPath | AuthorityPath")]
+        left: fresh_core::api::PluginPath,
+        #[plugin_api(ts_type = "string | LocalPath | WindowPath | AuthorityPath")]
+        right: fresh_core::api::PluginPath,
+    ) -> rquickjs::Result<Value<'js>> {
+        let diff = crate::dir_diff::compare_directories(
+            self.fs_for(&left).as_ref(),
+            Path::new(left.as_str()),
+            self.fs_for(&right).as_ref(),
+            Path::new(right.as_str()),
+        );
+        rquickjs_serde::to_value(ctx, &diff)
+            .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))
+    }
+
     /// Create a directory (and all parent directories) recursively on the
     /// path's filesystem. Returns true if the directory was created or already
     /// exists.
@@ -3171,7 +3216,8 @@ impl JsEditorApi {
         });
     }
 
-    /// Register a TextMate grammar file for a language
+    /// Register a TextMate grammar file for a language, or a compiled
+    /// tree-sitter parser (.so, .dylib, .dll) for one of Fresh's built-in languages
     /// The grammar will be pending until reload_grammars() is called
     pub fn register_grammar<'js>(
         &self,
@@ -3771,6 +3817,7 @@ impl JsEditorApi {
                 layout: opts.layout,
                 sources: opts.sources,
                 hunks: opts.hunks,
+                sections: opts.sections,
                 initial_focus_hunk: opts.initial_focus_hunk,
                 request_id: Some(id),
             });
@@ -3790,6 +3837,21 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Replace a composite buffer's alignment with aligned sections, for
+    /// views with more than two panes (see `TsCompositeSection`)
+    pub fn update_composite_sections(
+        &self,
+        buffer_id: u32,
+        sections: Vec<CompositeSection>,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::UpdateCompositeSections {
+                buffer_id: BufferId(buffer_id as usize),
+                sections,
+            })
+            .is_ok()
+    }
+
     /// Close a composite buffer
     pub fn close_composite_buffer(&self, buffer_id: u32) -> bool {
         self.command_sender
@@ -7587,7 +7649,7 @@ impl JsEditorApi {
     /// `handle.cancel()` to abort.
     #[plugin_api(
         js_name = "beginSearch",
-        ts_raw = "beginSearch(pattern: string, opts?: { fixedString?: boolean; caseSensitive?: boolean; maxResults?: number; wholeWords?: boolean; sourceBufferId?: number; fileGlob?: string }): SearchHandle"
+        ts_raw = "beginSearch(pattern: string, opts?: { fixedString?: boolean; caseSensitive?: boolean; maxResults?: number; wholeWords?: boolean; sourceBufferId?: number; fileGlob?: string; structural?: boolean; rewrite?: string }): SearchHandle"
     )]
     // The argument list is the JS-facing binding (see the `ts_raw`
     // signature above): rquickjs maps each parameter positionally to the
@@ -7605,6 +7667,8 @@ impl JsEditorApi {
         whole_words: bool,
         source_buffer_id: u32,
         file_glob: String,
+        structural: bool,
+        rewrite: String,
     ) -> u64 {
         let id = self.alloc_request_id();
         // Register the shared state before sending the command so the
@@ -7621,6 +7685,8 @@ impl JsEditorApi {
             whole_words,
             file_glob,
             source_buffer_id: source_buffer_id as usize,
+            structural,
+            rewrite,
             handle_id: id,
         });
         id
@@ -7693,19 +7759,21 @@ impl JsEditorApi {
 
     /// Replace matches in a file's buffer (async)
     /// Opens the file if not already in a buffer, applies edits via the buffer model,
-    /// and saves. All edits are grouped as a single undo action.
+    /// and saves. All edits are grouped as a single undo action. `replacement`
+    /// is either one text for every match or an array with one per match; an
+    /// array of any other length is rejected and the file is left unchanged.
     #[plugin_api(
         async_promise,
         js_name = "replaceInFile",
-        ts_raw = "replaceInFile(filePath: string, matches: number[][], replacement: string, bufferId?: number): Promise<ReplaceResult>"
+        ts_raw = "replaceInFile(filePath: string, matches: number[][], replacement: string | string[], bufferId?: number): Promise<ReplaceResult>"
     )]
     #[qjs(rename = "_replaceInFileStart")]
-    pub fn replace_in_file_start(
+    pub fn replace_in_file_start<'js>(
         &self,
-        _ctx: rquickjs::Ctx<'_>,
+        _ctx: rquickjs::Ctx<'js>,
         file_path: String,
         matches: Vec<Vec<u32>>,
-        replacement: String,
+        replacement: Value<'js>,
         buffer_id: rquickjs::function::Opt<u32>,
     ) -> u64 {
         let id = self.alloc_request_id();
@@ -7714,16 +7782,58 @@ impl JsEditorApi {
             .iter()
             .map(|m| (m[0] as usize, m[1] as usize))
             .collect();
+        // Either one text for every match or an array with one per match.
+        let (replacement, replacements) = match replacement.as_array() {
+            Some(texts) => (
+                String::new(),
+                texts
+                    .iter::<String>()
+                    .collect::<rquickjs::Result<Vec<_>>>()
+                    .unwrap_or_default(),
+            ),
+            None => (
+                replacement
+                    .as_string()
+                    .and_then(|text| text.to_string().ok())
+                    .unwrap_or_default(),
+                Vec::new(),
+            ),
+        };
         let _ = self.command_sender.send(PluginCommand::ReplaceInBuffer {
             file_path: PathBuf::from(file_path),
             buffer_id: buffer_id.0.unwrap_or(0) as usize,
             matches: match_pairs,
             replacement,
+            replacements,
             callback_id: JsCallbackId::new(id),
         });
         id
     }
 
+    /// Rewrite whole lines across files (async). Each edit names a 1-based
+    /// line and what it said when the edit was made (`expected`); a line
+    /// that says something else now is skipped and reported as a conflict.
+    /// Open buffers are edited in memory, one undo step per buffer, and
+    /// left unsaved; other files are patched on disk in place.
+    #[plugin_api(
+        async_promise,
+        js_name = "applyLineEdits",
+        ts_raw = "applyLineEdits(edits: LineEdit[]): Promise<LineEditResult>"
+    )]
+    #[qjs(rename = "_applyLineEditsStart")]
+    pub fn apply_line_edits_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        edits: Vec<fresh_core::api::LineEdit>,
+    ) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self.command_sender.send(PluginCommand::ApplyLineEdits {
+            edits,
+            request_id: id,
+        });
+        id
+    }
+
     /// Send LSP request (async, returns request_id)
     #[plugin_api(async_promise, js_name = "sendLspRequest", ts_return = "unknown")]
     #[qjs(rename = "_sendLspRequestStart")]
@@ -7749,6 +7859,152 @@ impl JsEditorApi {
         Ok(id)
     }
 
+    /// Get a buffer's undo tree (async). Resolves with the state the
+    /// buffer is in, the saved state and every state across all branches.
+    #[plugin_api(async_promise, js_name = "getUndoHistory", ts_return = "UndoHistory")]
+    #[qjs(rename = "_getUndoHistoryStart")]
+    pub fn get_undo_history_start(&self, _ctx: rquickjs::Ctx<'_>, buffer_id: u32) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self.command_sender.send(PluginCommand::GetUndoHistory {
+            buffer_id: BufferId(buffer_id as usize),
+            request_id: id,
+        });
+        id
+    }
+
+    /// Get a buffer's text as of an undo state (async), without moving
+    /// the buffer there. Rejects for an unknown state or one past an
+    /// edit that cannot be replayed.
+    #[plugin_api(async_promise, js_name = "getUndoStateText", ts_return = "string")]
+    #[qjs(rename = "_getUndoStateTextStart")]
+    pub fn get_undo_state_text_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        state: u32,
+    ) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self.command_sender.send(PluginCommand::GetUndoStateText {
+            buffer_id: BufferId(buffer_id as usize),
+            state: state as u64,
+            request_id: id,
+        });
+        id
+    }
+
+    /// Move a buffer to an undo state from `getUndoHistory`, switching
+    /// undo branches as needed. The buffer must be the active one.
+    pub fn undo_to_state(&self, buffer_id: u32, state: u32) -> bool {
+        self.command_sender
+            .send(PluginCommand::UndoToState {
+                buffer_id: BufferId(buffer_id as usize),
+                state: state as u64,
+            })
+            .is_ok()
+    }
+
+    /// Find a tree-sitter text object around a byte offset (async).
+    /// `object` is a kind with an optional part, e.g. `"function.inner"` or
+    /// `"parameter"`; resolves with the innermost enclosing occurrence, or
+    /// the next one after the offset, or `null` when there is none or the
+    /// buffer's language has no text objects.
+    #[plugin_api(
+        async_promise,
+        js_name = "getTextObjectRange",
+        ts_return = "TextObjectSpan | null"
+    )]
+    #[qjs(rename = "_getTextObjectRangeStart")]
+    pub fn get_text_object_range_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        position: u32,
+        object: String,
+    ) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self.command_sender.send(PluginCommand::GetTextObjectRange {
+            buffer_id: BufferId(buffer_id as usize),
+            position: position as usize,
+            object,
+            request_id: id,
+        });
+        id
+    }
+
+    /// Prepare an LSP call or type hierarchy at a byte offset (async).
+    /// `kind` is `"call"` or `"type"`; resolves with the hierarchy roots,
+    /// rejects when no server for the buffer supports it.
+    #[plugin_api(
+        async_promise,
+        js_name = "prepareHierarchy",
+        ts_return = "HierarchyItem[]"
+    )]
+    #[qjs(rename = "_prepareHierarchyStart")]
+    pub fn prepare_hierarchy_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        kind: String,
+        buffer_id: u32,
+        position: u32,
+    ) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self.command_sender.send(PluginCommand::PrepareHierarchy {
+            kind,
+            buffer_id: BufferId(buffer_id as usize),
+            position: position as usize,
+            request_id: id,
+        });
+        id
+    }
+
+    /// Expand an LSP hierarchy item one level (async). `direction` is
+    /// `"incoming"` / `"outgoing"` for call hierarchies and `"supertypes"` /
+    /// `"subtypes"` for type hierarchies.
+    #[plugin_api(
+        async_promise,
+        js_name = "expandHierarchy",
+        ts_return = "HierarchyItem[]"
+    )]
+    #[qjs(rename = "_expandHierarchyStart")]
+    pub fn expand_hierarchy_start<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        direction: String,
+        #[plugin_api(ts_type = "HierarchyItem")] item: rquickjs::Object<'js>,
+    ) -> rquickjs::Result<u64> {
+        let id = self.alloc_request_id();
+        let item_json = js_to_json(&ctx, item.into_value());
+        let _ = self.command_sender.send(PluginCommand::ExpandHierarchy {
+            direction,
+            item: item_json,
+            request_id: id,
+        });
+        Ok(id)
+    }
+
+    /// Send DAP request to the active debug session (async, returns request_id).
+    /// Resolves with the response body; rejects when no session is running.
+    #[plugin_api(async_promise, js_name = "debugRequest", ts_return = "unknown")]
+    #[qjs(rename = "_debugRequestStart")]
+    pub fn debug_request_start<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        command: String,
+        args: Option<rquickjs::Object<'js>>,
+    ) -> rquickjs::Result<u64> {
+        let id = self.alloc_request_id();
+        let arguments_json: Option<serde_json::Value> = args.map(|obj| {
+            let val = obj.into_value();
+            js_to_json(&ctx, val)
+        });
+        let _ = self.command_sender.send(PluginCommand::DebugRequest {
+            request_id: id,
+            command,
+            arguments: arguments_json,
+        });
+        Ok(id)
+    }
+
     /// Spawn a background process (async, returns request_id which is also process_id)
     #[plugin_api(
         async_thenable,
@@ -8445,6 +8701,12 @@ const EDITOR_PROMISE_BOOTSTRAP: &str = r#"
                 editor.createVirtualBufferInExistingSplit = _wrapAsync("_createVirtualBufferInExistingSplitStart", "createVirtualBufferInExistingSplit");
                 editor.createBufferGroup = _wrapAsync("_createBufferGroupStart", "createBufferGroup");
                 editor.sendLspRequest = _wrapAsync("_sendLspRequestStart", "sendLspRequest");
+                editor.debugRequest = _wrapAsync("_debugRequestStart", "debugRequest");
+                editor.getUndoHistory = _wrapAsync("_getUndoHistoryStart", "getUndoHistory");
+                editor.getUndoStateText = _wrapAsync("_getUndoStateTextStart", "getUndoStateText");
+                editor.getTextObjectRange = _wrapAsync("_getTextObjectRangeStart", "getTextObjectRange");
+                editor.prepareHierarchy = _wrapAsync("_prepareHierarchyStart", "prepareHierarchy");
+                editor.expandHierarchy = _wrapAsync("_expandHierarchyStart", "expandHierarchy");
                 editor.spawnBackgroundProcess = _wrapAsyncThenable("_spawnBackgroundProcessStart", "spawnBackgroundProcess");
                 editor.httpFetch = _wrapAsyncThenable("_httpFetchStart", "httpFetch");
                 editor.spawnProcessWait = _wrapAsync("_spawnProcessWaitStart", "spawnProcessWait");
@@ -8516,8 +8778,11 @@ const EDITOR_PROMISE_BOOTSTRAP: &str = r#"
                     const wholeWords = opts.wholeWords || false;
                     const sourceBufferId = opts.sourceBufferId || 0;
                     const fileGlob = opts.fileGlob || "";
+                    const structural = opts.structural || false;
+                    const rewrite = opts.rewrite || "";
                     const handleId = editor._beginSearch(
-                        pattern, fixedString, caseSensitive, maxResults, wholeWords, sourceBufferId, fileGlob
+                        pattern, fixedString, caseSensitive, maxResults, wholeWords, sourceBufferId, fileGlob,
+                        structural, rewrite
                     );
                     return {
                         searchId: handleId,
@@ -12530,6 +12795,52 @@ mod tests {
             });
     }
 
+    #[test]
+    fn test_api_compare_directories() {
+        let (mut backend, _rx) = create_test_backend();
+
+        let root = std::env::temp_dir().join(format!("fresh_dir_diff_{}", std::process::id()));
+        let (left, right) = (root.join("left"), root.join("right"));
+        std::fs::create_dir_all(&left).unwrap();
+        std::fs::create_dir_all(&right).unwrap();
+        std::fs::write(left.join("same.txt"), "same\n").unwrap();
+        std::fs::write(right.join("same.txt"), "same\n").unwrap();
+        std::fs::write(left.join("main.rs"), "a\nb\n").unwrap();
+        std::fs::write(right.join("main.rs"), "a\nc\nd\n").unwrap();
+        std::fs::write(right.join("new.rs"), "x\n").unwrap();
+
+        let js = format!(
+            r#"
+            const editor = getEditor();
+            const diff = editor.compareDirectories("{left}", "{right}");
+            globalThis._identical = diff.identical;
+            globalThis._entries = diff.entries
+                .map(e => `${{e.path}}:${{e.status}}:+${{e.added}}-${{e.removed}}`)
+                .join(",");
+        "#,
+            left = left.to_string_lossy().replace('\\', "\\\\"),
+            right = right.to_string_lossy().replace('\\', "\\\\"),
+        );
+        backend.execute_js(&js, "test.js").unwrap();
+
+        backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let global = ctx.globals();
+                assert_eq!(global.get::<_, u32>("_identical").unwrap(), 1);
+                assert_eq!(
+                    global.get::<_, String>("_entries").unwrap(),
+                    "main.rs:modified:+2-1,new.rs:added:+1-0"
+                );
+            });
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
     // ==================== Execute Action Test ====================
 
     #[test]
@@ -0,0 +1,276 @@
+//! Directory tree comparison behind the `compareDirectories` plugin API.
+//!
+//! Both trees are walked through a [`PluginFilesystem`], so either side can
+//! live on a remote authority. `.gitignore` and `.ignore` files are honoured
+//! per tree, with the usual precedence (a deeper file overrides its
+//! ancestors, and a `!pattern` re-includes); `.git` itself is always skipped.
+
+use fresh_core::api::{DirDiff, DirDiffEntry, DirDiffStatus};
+use fresh_core::services::PluginFilesystem;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Ignore files read in every directory, lowest precedence first.
+const IGNORE_FILES: [&str; 2] = [".gitignore", ".ignore"];
+
+/// Compare the files under `left` with those under `right`.
+///
+/// Reads every file on both sides, so cost is linear in the size of the
+/// trees. Files whose bytes match count towards `identical`; the rest are
+/// reported with line stats from the native line diff.
+pub fn compare_directories(
+    left_fs: &dyn PluginFilesystem,
+    left: &Path,
+    right_fs: &dyn PluginFilesystem,
+    right: &Path,
+) -> DirDiff {
+    let left_files = walk(left_fs, left);
+    let right_files = walk(right_fs, right);
+    let paths: BTreeSet<&String> = left_files.keys().chain(right_files.keys()).collect();
+
+    let mut diff = DirDiff::default();
+    for path in paths {
+        let read = |fs: &dyn PluginFilesystem, file: Option<&PathBuf>| {
+            file.map(|file| fs.read_file(file).unwrap_or_default())
+        };
+        let entry = match (
+            read(left_fs, left_files.get(path)),
+            read(right_fs, right_files.get(path)),
+        ) {
+            (Some(old), Some(new)) if old == new => {
+                diff.identical += 1;
+                continue;
+            }
+            (Some(old), Some(new)) => diff_entry(path, DirDiffStatus::Modified, &old, &new),
+            (Some(old), None) => diff_entry(path, DirDiffStatus::Removed, &old, &[]),
+            (None, Some(new)) => diff_entry(path, DirDiffStatus::Added, &[], &new),
+            (None, None) => continue,
+        };
+        diff.entries.push(entry);
+    }
+    diff
+}
+
+fn diff_entry(path: &str, status: DirDiffStatus, old: &[u8], new: &[u8]) -> DirDiffEntry {
+    let mut entry = DirDiffEntry {
+        path: path.to_string(),
+        status,
+        added: 0,
+        removed: 0,
+        binary: true,
+    };
+    if let (Some(old), Some(new)) = (as_text(old), as_text(new)) {
+        for hunk in fresh_core::diff::compute_line_diff(old, new) {
+            entry.added += hunk.new_count;
+            entry.removed += hunk.old_count;
+        }
+        entry.binary = false;
+    }
+    entry
+}
+
+/// `bytes` as text, or `None` if they look binary (not UTF-8, or a NUL).
+fn as_text(bytes: &[u8]) -> Option<&str> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    std::str::from_utf8(bytes).ok()
+}
+
+/// Every non-ignored file under `root`, keyed by `/`-separated relative path.
+fn walk(fs: &dyn PluginFilesystem, root: &Path) -> BTreeMap<String, PathBuf> {
+    let mut files = BTreeMap::new();
+    // Symlinked directories can form cycles; visit each real directory once.
+    let mut visited = HashSet::new();
+    // Each directory carries the ignore rules of its ancestors, innermost last.
+    let mut stack: Vec<(PathBuf, Vec<Rc<Gitignore>>)> = vec![(root.to_path_buf(), Vec::new())];
+
+    while let Some((dir, mut rules)) = stack.pop() {
+        if !visited.insert(fs.canonicalize(&dir).unwrap_or_else(|| dir.clone())) {
+            continue;
+        }
+        for name in IGNORE_FILES {
+            if let Some(rule) = load_ignore_file(fs, &dir, name) {
+                rules.push(Rc::new(rule));
+            }
+        }
+        for entry in fs.read_dir(&dir) {
+            if entry.name == ".git" {
+                continue;
+            }
+            let path = dir.join(&entry.name);
+            if is_ignored(&rules, &path, entry.is_dir) {
+                continue;
+            }
+            if entry.is_dir {
+                stack.push((path, rules.clone()));
+            } else if entry.is_file {
+                if let Ok(relative) = path.strip_prefix(root) {
+                    let key = relative.to_string_lossy().replace('\\', "/");
+                    files.insert(key, path);
+                }
+            }
+        }
+    }
+    files
+}
+
+fn load_ignore_file(fs: &dyn PluginFilesystem, dir: &Path, name: &str) -> Option<Gitignore> {
+    let contents = fs.read_file(&dir.join(name))?;
+    let mut builder = GitignoreBuilder::new(dir);
+    for line in String::from_utf8_lossy(&contents).lines() {
+        // A malformed glob only loses that one rule, as in git.
+        let _ = builder.add_line(None, line);
+    }
+    builder.build().ok()
+}
+
+/// Whether the innermost rule that has an opinion on `path` ignores it.
+fn is_ignored(rules: &[Rc<Gitignore>], path: &Path, is_dir: bool) -> bool {
+    for rule in rules.iter().rev() {
+        match rule.matched(path, is_dir) {
+            Match::Ignore(_) => return true,
+            Match::Whitelist(_) => return false,
+            Match::None => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fresh_core::api::DirEntry;
+    use fresh_core::services::PluginFileStat;
+
+    /// Files by absolute path; directories are implied by the file paths.
+    struct MemFs(BTreeMap<PathBuf, Vec<u8>>);
+
+    impl MemFs {
+        fn new(files: &[(&str, &str)]) -> Self {
+            Self(
+                files
+                    .iter()
+                    .map(|(path, text)| (PathBuf::from(path), text.as_bytes().to_vec()))
+                    .collect(),
+            )
+        }
+    }
+
+    impl PluginFilesystem for MemFs {
+        fn read_file(&self, path: &Path) -> Option<Vec<u8>> {
+            self.0.get(path).cloned()
+        }
+        fn write_file(&self, _path: &Path, _contents: &[u8]) -> bool {
+            false
+        }
+        fn exists(&self, path: &Path) -> bool {
+            self.0.keys().any(|file| file.starts_with(path))
+        }
+        fn read_dir(&self, path: &Path) -> Vec<DirEntry> {
+            let mut entries: BTreeMap<String, bool> = BTreeMap::new();
+            for file in self.0.keys() {
+                if let Ok(rest) = file.strip_prefix(path) {
+                    let mut parts = rest.components();
+                    if let Some(first) = parts.next() {
+                        let name = first.as_os_str().to_string_lossy().to_string();
+                        entries.insert(name, parts.next().is_some());
+                    }
+                }
+            }
+            entries
+                .into_iter()
+                .map(|(name, is_dir)| DirEntry {
+                    name,
+                    is_file: !is_dir,
+                    is_dir,
+                })
+                .collect()
+        }
+        fn create_dir_all(&self, _path: &Path) -> bool {
+            false
+        }
+        fn remove_path(&self, _path: &Path) -> bool {
+            false
+        }
+        fn rename(&self, _from: &Path, _to: &Path) -> bool {
+            false
+        }
+        fn copy(&self, _from: &Path, _to: &Path) -> bool {
+            false
+        }
+        fn stat(&self, _path: &Path) -> Option<PluginFileStat> {
+            None
+        }
+        fn canonicalize(&self, _path: &Path) -> Option<PathBuf> {
+            None
+        }
+    }
+
+    fn compare(fs: &MemFs) -> DirDiff {
+        compare_directories(fs, Path::new("/l"), fs, Path::new("/r"))
+    }
+
+    fn entry(path: &str, status: DirDiffStatus, added: u32, removed: u32) -> DirDiffEntry {
+        DirDiffEntry {
+            path: path.to_string(),
+            status,
+            added,
+            removed,
+            binary: false,
+        }
+    }
+
+    #[test]
+    fn reports_added_removed_and_modified_files_with_line_stats() {
+        let fs = MemFs::new(&[
+            ("/l/same.txt", "a\nb\n"),
+            ("/r/same.txt", "a\nb\n"),
+            ("/l/src/lib.rs", "one\ntwo\nthree\n"),
+            ("/r/src/lib.rs", "one\n2\nthree\nfour\n"),
+            ("/l/old.txt", "x\ny\n"),
+            ("/r/src/new.rs", "fn main() {}\n"),
+        ]);
+        let diff = compare(&fs);
+        assert_eq!(diff.identical, 1);
+        assert_eq!(
+            diff.entries,
+            vec![
+                entry("old.txt", DirDiffStatus::Removed, 0, 2),
+                entry("src/lib.rs", DirDiffStatus::Modified, 2, 1),
+                entry("src/new.rs", DirDiffStatus::Added, 1, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn honours_ignore_files_per_tree_and_skips_git() {
+        let fs = MemFs::new(&[
+            ("/l/.gitignore", "target/\n*.log\n!keep.log\n"),
+            ("/r/.gitignore", "target/\n*.log\n!keep.log\n"),
+            ("/l/target/out.bin", "left build"),
+            ("/r/target/out.bin", "right build"),
+            ("/l/debug.log", "left"),
+            ("/r/keep.log", "kept"),
+            ("/l/.git/HEAD", "ref: refs/heads/main\n"),
+            // A nested ignore file only applies below its directory.
+            ("/r/docs/.ignore", "draft.md\n"),
+            ("/r/docs/draft.md", "wip\n"),
+            ("/r/draft.md", "not ignored here\n"),
+        ]);
+        let paths: Vec<String> = compare(&fs).entries.into_iter().map(|e| e.path).collect();
+        assert_eq!(paths, vec!["docs/.ignore", "draft.md", "keep.log"]);
+    }
+
+    #[test]
+    fn binary_files_have_no_line_stats() {
+        let fs = MemFs::new(&[("/l/blob", "a\0b"), ("/r/blob", "a\0c")]);
+        let diff = compare(&fs);
+        assert_eq!(diff.entries.len(), 1);
+        assert!(diff.entries[0].binary);
+        assert_eq!((diff.entries[0].added, diff.entries[0].removed), (0, 0));
+    }
+}
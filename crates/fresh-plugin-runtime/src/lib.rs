@@ -1,4 +1,5 @@
 pub mod backend;
+pub mod dir_diff;
 pub mod process;
 pub mod thread;
 pub mod ts_export;
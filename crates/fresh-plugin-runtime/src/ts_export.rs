@@ -17,16 +17,19 @@ use ts_rs::{Config as TsConfig, TS};
 use fresh_core::api::{
     ActionPopupAction, ActionPopupOptions, ActionSpec, AnimationRect, BackgroundProcessResult,
     BufferGroupResult, BufferInfo, BufferSavedDiff, CompositeHunk, CompositeLayoutConfig,
-    CompositePaneStyle, CompositeSourceConfig, CreateCompositeBufferOptions, CreateTerminalOptions,
-    CreateVirtualBufferInExistingSplitOptions, CreateVirtualBufferInSplitOptions,
-    CreateVirtualBufferOptions, CursorInfo, DiffBaselineResult, DirEntry, FormatterPackConfig,
-    GrammarInfoSnapshot, GrepMatch, JsDiagnostic, JsPosition, JsRange, JsTextPropertyEntry,
-    KeyEventPayload, LanguagePackConfig, LayoutHints, LineDiffHunk, LspServerPackConfig,
+    CompositePaneStyle, CompositeSection, CompositeSourceConfig, CreateCompositeBufferOptions,
+    CreateTerminalOptions, CreateVirtualBufferInExistingSplitOptions,
+    CreateVirtualBufferInSplitOptions, CreateVirtualBufferOptions, CursorInfo, DiffBaselineResult,
+    DirDiff, DirDiffEntry, DirDiffStatus, DirEntry, FormatterPackConfig, GrammarInfoSnapshot,
+    GrepMatch, HierarchyItem, JsDiagnostic, JsPosition, JsRange, JsTextPropertyEntry,
+    KeyEventPayload, LanguagePackConfig, LayoutHints, LineDiffHunk, LineEdit, LineEditConflict,
+    LineEditResult, LspServerPackConfig, MergeRegion, MergeRegionKind, MergeResult,
     OverlayColorSpec, OverlayOptions, PluginAnimationEdge, PluginAnimationKind,
     ProcessLimitsPackConfig, RemoteBackendInfo, ReplaceResult, ScreenSize, ScrollbarMarker,
-    SearchTakeResult, SpawnResult, SplitSnapshot, TerminalResult, TextPropertiesAtCursor,
-    TokenColor, TsHighlightSpan, ViewTokenStyle, ViewTokenWire, ViewTokenWireKind, ViewportInfo,
-    VirtualBufferResult, WindowInfo,
+    SearchTakeResult, SpawnResult, SplitSnapshot, TerminalResult, TextObjectSpan,
+    TextPropertiesAtCursor, TokenColor, TsHighlightSpan, UndoHistory, UndoHistoryState,
+    ViewTokenStyle, ViewTokenWire, ViewTokenWireKind, ViewportInfo, VirtualBufferResult,
+    WindowInfo,
 };
 use fresh_core::command::Suggestion;
 use fresh_core::file_explorer::{
@@ -68,6 +71,12 @@ fn get_type_decl(type_name: &str) -> Option<String> {
         "ActionSpec" => Some(ActionSpec::decl(&cfg)),
         "BufferSavedDiff" => Some(BufferSavedDiff::decl(&cfg)),
         "LineDiffHunk" => Some(LineDiffHunk::decl(&cfg)),
+        "MergeRegionKind" => Some(MergeRegionKind::decl(&cfg)),
+        "MergeRegion" => Some(MergeRegion::decl(&cfg)),
+        "MergeResult" => Some(MergeResult::decl(&cfg)),
+        "DirDiffStatus" => Some(DirDiffStatus::decl(&cfg)),
+        "DirDiffEntry" => Some(DirDiffEntry::decl(&cfg)),
+        "DirDiff" => Some(DirDiff::decl(&cfg)),
         "DiffBaselineResult" => Some(DiffBaselineResult::decl(&cfg)),
         "LayoutHints" => Some(LayoutHints::decl(&cfg)),
 
@@ -77,7 +86,14 @@ fn get_type_decl(type_name: &str) -> Option<String> {
 
         // Grep/Replace types
         "GrepMatch" => Some(GrepMatch::decl(&cfg)),
+        "HierarchyItem" => Some(HierarchyItem::decl(&cfg)),
+        "TextObjectSpan" => Some(TextObjectSpan::decl(&cfg)),
+        "UndoHistory" => Some(UndoHistory::decl(&cfg)),
+        "UndoHistoryState" => Some(UndoHistoryState::decl(&cfg)),
         "ReplaceResult" => Some(ReplaceResult::decl(&cfg)),
+        "LineEdit" => Some(LineEdit::decl(&cfg)),
+        "LineEditConflict" => Some(LineEditConflict::decl(&cfg)),
+        "LineEditResult" => Some(LineEditResult::decl(&cfg)),
         "SearchTakeResult" => Some(SearchTakeResult::decl(&cfg)),
         // SearchHandle is the JS-side wrapper over a numeric handle id.
         // The Rust type can't be exported (non-serializable runtime state).
@@ -108,6 +124,7 @@ fn get_type_decl(type_name: &str) -> Option<String> {
         }
         "TsCompositePaneStyle" | "CompositePaneStyle" => Some(CompositePaneStyle::decl(&cfg)),
         "TsCompositeHunk" | "CompositeHunk" => Some(CompositeHunk::decl(&cfg)),
+        "TsCompositeSection" | "CompositeSection" => Some(CompositeSection::decl(&cfg)),
         "TsCreateCompositeBufferOptions" | "CreateCompositeBufferOptions" => {
             Some(CreateCompositeBufferOptions::decl(&cfg))
         }
@@ -343,6 +360,7 @@ const DEPENDENCY_TYPES: &[&str] = &[
     "TsCompositeSourceConfig",         // Used in createCompositeBuffer opts.sources
     "TsCompositePaneStyle",            // Used in TsCompositeSourceConfig.style
     "TsCompositeHunk",                 // Used in createCompositeBuffer opts.hunks
+    "TsCompositeSection",              // Used in createCompositeBuffer opts.sections
     "TsCreateCompositeBufferOptions",  // Options for createCompositeBuffer
     "ViewportInfo",                    // Used by plugins for viewport queries
     "ScreenSize",                      // Used by editor.getScreenSize()
@@ -393,6 +411,12 @@ const DEPENDENCY_TYPES: &[&str] = &[
     "OffsetUnit",                      // Used by InlineOverlay.unit
     "StyledSegment",                   // Used by TextPropertyEntry.segments
     "GrammarInfoSnapshot",             // Used by listGrammars
+    "HierarchyItem",                   // Used by the lsp_hierarchy hook
+    "UndoHistoryState",                // Used by UndoHistory.states
+    "MergeRegionKind",                 // Used by MergeRegion.kind
+    "MergeRegion",                     // Used by MergeResult.regions
+    "DirDiffStatus",                   // Used by DirDiffEntry.status
+    "DirDiffEntry",                    // Used by DirDiff.entries
     "AnimationRect",                   // Used by animateArea
     "PluginAnimationEdge",             // Used by PluginAnimationKind
     "PluginAnimationKind",             // Used by animateArea/animateVirtualBuffer
@@ -408,6 +432,10 @@ const DEPENDENCY_TYPES: &[&str] = &[
     "SearchHandle",
     // Replace result (referenced via ts_raw on replaceInFile)
     "ReplaceResult",
+    // Line edits and their result (referenced via ts_raw on applyLineEdits)
+    "LineEdit",
+    "LineEditConflict",
+    "LineEditResult",
 ];
 
 /// Collect TypeScript type declarations based on referenced types from proc macro
@@ -751,6 +779,7 @@ interface HookEventMap {
     symbol: string;
     locations: { file: string; line: number; column: number }[];
   };
+  lsp_hierarchy: { kind: "call" | "type"; symbol: string; items: HierarchyItem[] };
   lsp_server_request: {
     language: string;
     method: string;
@@ -770,6 +799,18 @@ interface HookEventMap {
     user_dismissed: boolean;
   };
 
+  // ── Debugger (DAP) ───────────────────────────────────────────────────────
+  debug_session_changed: {
+    state: "starting" | "running" | "stopped" | "ended";
+    language: string;
+  };
+  debug_stopped: {
+    thread_id: number | null;
+    reason: string;
+    frames: { id: number; name: string; file: string | null; line: number; column: number }[];
+  };
+  debug_output: { category: string; output: string };
+
   // ── UI events ────────────────────────────────────────────────────────────
   action_popup_result: { popup_id: string; action_id: string };
   /**
@@ -992,6 +1033,15 @@ mod tests {
             "ActionSpec",
             "BufferSavedDiff",
             "LineDiffHunk",
+            "MergeRegionKind",
+            "MergeRegion",
+            "MergeResult",
+            "DirDiffStatus",
+            "DirDiffEntry",
+            "DirDiff",
+            "LineEdit",
+            "LineEditConflict",
+            "LineEditResult",
             "DiffBaselineResult",
             "LayoutHints",
             "SpawnResult",
@@ -1006,6 +1056,7 @@ mod tests {
             "TsCompositeSourceConfig",
             "TsCompositePaneStyle",
             "TsCompositeHunk",
+            "TsCompositeSection",
             "TsCreateCompositeBufferOptions",
             "ViewTokenWireKind",
             "TokenColor",
@@ -1576,6 +1627,12 @@ mod tests {
             "getBufferText",
             "delay",
             "sendLspRequest",
+            "debugRequest",
+            "getUndoHistory",
+            "getUndoStateText",
+            "getTextObjectRange",
+            "prepareHierarchy",
+            "expandHierarchy",
             "spawnBackgroundProcess",
             "killBackgroundProcess",
             "createTerminal",
@@ -71,7 +71,11 @@ export default defineConfig({
           { text: "Guided Code Tours", link: "/features/code-tours" },
           { text: "Language Support", link: "/features/languages" },
           { text: "LSP Integration", link: "/features/lsp" },
+          { text: "Debugging", link: "/features/debugging" },
+          { text: "Tasks", link: "/features/tasks" },
+          { text: "Testing", link: "/features/testing" },
           { text: "Git", link: "/features/git" },
+          { text: "Compare Folders", link: "/features/compare-folders" },
           { text: "Themes", link: "/features/themes" },
           { text: "Encoding", link: "/features/encoding" },
           { text: "Workspace Trust", link: "/features/workspace-trust" },